@@ -480,6 +480,19 @@ impl AnthropicProvider {
                     Content::Image { data, mime_type } => {
                         format_image_block(data, mime_type)
                     }
+                    Content::Document {
+                        data,
+                        mime_type,
+                        name,
+                    } => Some(json!({
+                        "type": "document",
+                        "source": {
+                            "type": "base64",
+                            "media_type": mime_type,
+                            "data": data,
+                        },
+                        "title": name
+                    })),
                     Content::Thinking { .. } => None,
                 })
                 .collect();
@@ -572,6 +585,7 @@ impl AnthropicProvider {
             cache_read_tokens: response.usage.cache_read_input_tokens,
             cache_write_tokens: response.usage.cache_creation_input_tokens,
             stop_reason: response.stop_reason.clone(),
+            key_index: None,
         }
     }
 }
@@ -675,7 +689,7 @@ impl Provider for AnthropicProvider {
             let (_, is_oauth) = self.keys.get(best);
             let body = self.build_body(system, messages, tools, false, is_oauth);
 
-            let (response, _key_index) = self.send_with_retry(&body, has_tools).await?;
+            let (response, key_index) = self.send_with_retry(&body, has_tools).await?;
 
             let api_response: AnthropicResponse = response
                 .json()
@@ -683,7 +697,7 @@ impl Provider for AnthropicProvider {
                 .context("failed to parse Anthropic response")?;
 
             let message = Self::parse_response(&api_response, is_oauth);
-            let usage = Self::parse_usage(&api_response);
+            let usage = Self::parse_usage(&api_response).with_key_index(Some(key_index));
 
             debug!(
                 input_tokens = usage.input_tokens,
@@ -725,13 +739,13 @@ impl Provider for AnthropicProvider {
         let (_, is_oauth) = self.keys.get(best);
         let body = self.build_body(system, messages, tools, true, is_oauth);
 
-        let (response, _key_index) = self.send_with_retry(&body, has_tools).await?;
+        let (response, key_index) = self.send_with_retry(&body, has_tools).await?;
 
         let byte_stream = response.bytes_stream();
 
         let stream = futures::stream::unfold(
             SseState::new(byte_stream, is_oauth, tools),
-            |mut state| async move {
+            move |mut state| async move {
                 loop {
                     let line = match state.next_line().await {
                         Ok(Some(line)) => line,
@@ -762,6 +776,7 @@ impl Provider for AnthropicProvider {
                             return Some((Ok((Some(msg), None)), state));
                         }
                         SseAction::YieldFinal(msg, usage) => {
+                            let usage = usage.with_key_index(Some(key_index));
                             return Some((Ok((Some(msg), Some(usage))), state));
                         }
                         SseAction::Continue => {}
@@ -2147,6 +2162,23 @@ mod tests {
         assert_eq!(result["source"]["data"].as_str().unwrap(), b64);
     }
 
+    #[test]
+    fn format_messages_sends_documents_as_document_blocks() {
+        let messages = vec![Message::user().with_text("What is due?").with_document(
+            "JVBERi0=",
+            "application/pdf",
+            "bill.pdf",
+        )];
+
+        let formatted = AnthropicProvider::format_messages(&messages, false, None);
+
+        let block = &formatted[0]["content"][1];
+        assert_eq!(block["type"], "document");
+        assert_eq!(block["source"]["media_type"], "application/pdf");
+        assert_eq!(block["source"]["data"], "JVBERi0=");
+        assert_eq!(block["title"], "bill.pdf");
+    }
+
     #[test]
     fn format_messages_downscales_every_oversized_image_in_many_image_request() {
         let wide_jpeg = make_jpeg(2_200, 400);
@@ -182,6 +182,7 @@ mod tests {
             extra_headers: std::collections::BTreeMap::default(),
             refresh_token: None,
             reasoning: None,
+            keep_alive: None,
         }
     }
 
@@ -170,7 +170,7 @@ impl GenAiProvider {
                         &response.content,
                         response.reasoning_content.as_deref(),
                     );
-                    let usage = usage_from_response(&response);
+                    let usage = usage_from_response(&response).with_key_index(key_index);
                     debug!(
                         provider = self.name(),
                         model = %model_info.name,
@@ -230,7 +230,7 @@ impl GenAiProvider {
                 .await
             {
                 Ok(response) => {
-                    return Ok(into_provider_stream(response));
+                    return Ok(into_provider_stream(response, key_index));
                 }
                 Err(error) => {
                     self.log_request_failure("stream", &model_info.name, attempt, &error)
@@ -666,6 +666,7 @@ mod tests {
             extra_headers: BTreeMap::new(),
             refresh_token: None,
             reasoning: None,
+            keep_alive: None,
         })
         .unwrap();
 
@@ -687,6 +688,7 @@ mod tests {
             extra_headers: BTreeMap::new(),
             refresh_token: None,
             reasoning: None,
+            keep_alive: None,
         })
         .unwrap();
 
@@ -7,7 +7,10 @@ mod key_pool;
 mod message_mapping;
 mod model_context;
 mod model_mapping;
+mod model_pricing;
 mod models_dev;
+mod ollama_models;
+mod ollama_provider;
 mod openai_codex;
 mod openai_codex_parser;
 mod openai_compatible_images;
@@ -29,6 +32,11 @@ pub use gemini_images::{
     GeminiImageGenerationResult, GeneratedImage, InputImage, generate_gemini_image,
 };
 pub use key_pool::{KeyPool, resolve_key_refs};
+pub use model_pricing::{ModelPricing, lookup_model_pricing};
+pub use ollama_models::{
+    OLLAMA_DEFAULT_BASE_URL, OllamaModel, OllamaPullProgress, list_ollama_models,
+    ollama_server_root, pull_ollama_model,
+};
 pub use openai_compatible_images::generate_openai_compatible_image;
 pub use provider_spec::{
     OpenAiReasoningConfig, OpenAiReasoningEffort, OpenAiReasoningSummary, ProviderKind,
@@ -37,14 +45,13 @@ pub use provider_spec::{
 
 use codex_provider::CodexProvider;
 use genai_provider::GenAiProvider;
+use ollama_provider::OllamaProvider;
 
 pub fn create_provider(spec: ProviderSpec) -> Result<Arc<dyn Provider>> {
     match spec.kind {
         ProviderKind::Anthropic => Ok(Arc::new(AnthropicProvider::from_spec(&spec)?)),
-        ProviderKind::Gemini
-        | ProviderKind::OpenAi
-        | ProviderKind::OpenAiCompatible
-        | ProviderKind::Ollama => {
+        ProviderKind::Ollama => Ok(Arc::new(OllamaProvider::new(spec)?)),
+        ProviderKind::Gemini | ProviderKind::OpenAi | ProviderKind::OpenAiCompatible => {
             // If the OpenAI key is a Codex OAuth JWT, use the Codex provider.
             if spec.kind == ProviderKind::OpenAi && is_codex_oauth_token(&spec) {
                 return Ok(Arc::new(CodexProvider::new(&spec)?));
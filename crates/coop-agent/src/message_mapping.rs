@@ -55,6 +55,15 @@ pub(crate) fn map_message(provider: ProviderKind, message: &Message) -> Vec<Chat
                     )));
                 }
             }
+            Content::Document {
+                data,
+                mime_type,
+                name,
+            } => regular_parts.push(ContentPart::Binary(Binary::from_base64(
+                mime_type.clone(),
+                data.clone(),
+                Some(name.clone()),
+            ))),
             Content::ToolRequest {
                 id,
                 name,
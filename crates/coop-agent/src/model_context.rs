@@ -10,6 +10,7 @@ use serde_json::{Value, json};
 use tracing::{debug, info};
 
 use crate::models_dev;
+use crate::ollama_models;
 use crate::openai_codex::extract_account_id;
 use crate::provider_spec::ProviderKind;
 use crate::sync_http;
@@ -183,7 +184,7 @@ fn resolution_base_url(
         })
 }
 
-fn effective_provider(
+pub(crate) fn effective_provider(
     kind: ProviderKind,
     base_url: Option<&str>,
     api_key: Option<&str>,
@@ -430,24 +431,7 @@ async fn query_ollama_context_limit(
         .await
         .ok()?;
 
-    let model_info = payload.get("model_info")?.as_object()?;
-    for (key, value) in model_info {
-        if key.contains("context_length")
-            && let Some(limit) = value.as_u64().and_then(|limit| usize::try_from(limit).ok())
-        {
-            return Some(limit);
-        }
-    }
-
-    let parameters = payload.get("parameters")?.as_str()?;
-    for line in parameters.lines() {
-        if line.contains("num_ctx") {
-            let limit = line.split_whitespace().last()?.parse::<usize>().ok()?;
-            return Some(limit);
-        }
-    }
-
-    None
+    ollama_models::context_length_from_show(&payload)
 }
 
 async fn query_lm_studio_context_limit(
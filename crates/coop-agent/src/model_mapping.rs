@@ -136,7 +136,7 @@ fn infer_openai_adapter(model: &str) -> (AdapterKind, String) {
     (adapter_kind, model.to_owned())
 }
 
-fn strip_ollama_prefix(model: &str) -> String {
+pub(crate) fn strip_ollama_prefix(model: &str) -> String {
     let stripped = strip_prefix(model, "ollama/");
     stripped
         .strip_prefix("ollama::")
@@ -0,0 +1,58 @@
+use serde::{Deserialize, Serialize};
+
+use crate::model_context::effective_provider;
+use crate::models_dev;
+use crate::provider_spec::ProviderKind;
+
+/// Token prices for a model in USD per million tokens.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ModelPricing {
+    pub input: f64,
+    pub output: f64,
+    /// Cache reads are billed at the input price when this is unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache_read: Option<f64>,
+    /// Cache writes are billed at the input price when this is unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache_write: Option<f64>,
+}
+
+impl ModelPricing {
+    /// Cost in USD of the given token counts.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn cost(&self, input: u64, output: u64, cache_read: u64, cache_write: u64) -> f64 {
+        let per_token = |price: f64, tokens: u64| price * tokens as f64 / 1_000_000.0;
+        per_token(self.input, input)
+            + per_token(self.output, output)
+            + per_token(self.cache_read.unwrap_or(self.input), cache_read)
+            + per_token(self.cache_write.unwrap_or(self.input), cache_write)
+    }
+}
+
+/// Look up a model's prices on models.dev. Returns `None` for local and
+/// unknown endpoints.
+pub fn lookup_model_pricing(
+    kind: ProviderKind,
+    base_url: Option<&str>,
+    model: &str,
+) -> Option<ModelPricing> {
+    let provider = effective_provider(kind, base_url, None)?;
+    models_dev::lookup_pricing(provider, model)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cache_tokens_fall_back_to_input_price() {
+        let pricing = ModelPricing {
+            input: 3.0,
+            output: 15.0,
+            cache_read: Some(0.3),
+            cache_write: None,
+        };
+        let cost = pricing.cost(1_000_000, 100_000, 2_000_000, 1_000_000);
+        assert!((cost - (3.0 + 1.5 + 0.6 + 3.0)).abs() < 1e-9);
+    }
+}
@@ -7,6 +7,7 @@ use reqwest::Url;
 use serde_json::Value;
 use tracing::debug;
 
+use crate::model_pricing::ModelPricing;
 use crate::sync_http;
 
 const MODELS_DEV_URL: &str = "https://models.dev/api.json";
@@ -46,6 +47,10 @@ const PROVIDER_TO_MODELS_DEV: &[(&str, &str)] = &[
     ("openai", "openai"),
 ];
 
+/// Providers whose models.dev prices are used but whose context limits come
+/// from the built-in defaults.
+const PRICING_ONLY_PROVIDERS: &[(&str, &str)] = &[("gemini", "google")];
+
 #[derive(Default)]
 struct ModelsDevCache {
     data: Option<Arc<Value>>,
@@ -74,6 +79,21 @@ pub(crate) fn lookup_context_limit(provider: &str, model: &str) -> Option<usize>
     lookup_in_registry(registry.as_ref(), provider_id, model)
 }
 
+/// Per-million-token prices for a model, as listed on models.dev.
+pub(crate) fn lookup_pricing(provider: &str, model: &str) -> Option<ModelPricing> {
+    let provider_id = PROVIDER_TO_MODELS_DEV
+        .iter()
+        .chain(PRICING_ONLY_PROVIDERS)
+        .find_map(|(name, mapped)| (*name == provider).then_some(*mapped))?;
+    let registry = load_registry()?;
+    find_in_registry(
+        registry.as_ref(),
+        provider_id,
+        model,
+        extract_pricing_from_entry,
+    )
+}
+
 fn load_registry() -> Option<Arc<Value>> {
     {
         let state = cache().lock().expect("models.dev cache mutex poisoned");
@@ -120,15 +140,22 @@ fn load_registry() -> Option<Arc<Value>> {
 }
 
 fn lookup_in_registry(registry: &Value, provider: &str, model: &str) -> Option<usize> {
+    find_in_registry(registry, provider, model, |entry| {
+        extract_context_limit_from_entry(entry).filter(|limit| *limit > 0)
+    })
+}
+
+fn find_in_registry<T>(
+    registry: &Value,
+    provider: &str,
+    model: &str,
+    extract: impl Fn(&Value) -> Option<T>,
+) -> Option<T> {
     let models = registry.get(provider)?.get("models")?.as_object()?;
 
     for candidate in model_candidates(model) {
-        if let Some(limit) = models
-            .get(candidate)
-            .and_then(extract_context_limit_from_entry)
-            .filter(|limit| *limit > 0)
-        {
-            return Some(limit);
+        if let Some(found) = models.get(candidate).and_then(&extract) {
+            return Some(found);
         }
     }
 
@@ -138,9 +165,9 @@ fn lookup_in_registry(registry: &Value, provider: &str, model: &str) -> Option<u
             .rsplit_once('/')
             .is_some_and(|(_, bare)| bare.eq_ignore_ascii_case(model.trim()));
         if (candidate.to_ascii_lowercase() == requested || bare_match)
-            && let Some(limit) = extract_context_limit_from_entry(entry).filter(|limit| *limit > 0)
+            && let Some(found) = extract(entry)
         {
-            return Some(limit);
+            return Some(found);
         }
     }
 
@@ -155,6 +182,17 @@ fn extract_context_limit_from_entry(entry: &Value) -> Option<usize> {
         .and_then(|limit| usize::try_from(limit).ok())
 }
 
+fn extract_pricing_from_entry(entry: &Value) -> Option<ModelPricing> {
+    let cost = entry.get("cost")?;
+    let price = |field: &str| cost.get(field).and_then(Value::as_f64);
+    Some(ModelPricing {
+        input: price("input")?,
+        output: price("output")?,
+        cache_read: price("cache_read"),
+        cache_write: price("cache_write"),
+    })
+}
+
 fn model_candidates(model: &str) -> Vec<&str> {
     let trimmed = model.trim();
     let mut candidates = vec![trimmed];
@@ -290,4 +328,38 @@ mod tests {
             Some(1_000_000)
         );
     }
+
+    #[test]
+    fn pricing_lookup_reads_cost_fields() {
+        let registry = json!({
+            "anthropic": {
+                "models": {
+                    "claude-sonnet-4-6": {
+                        "limit": { "context": 1_000_000 },
+                        "cost": { "input": 3, "output": 15, "cache_read": 0.3, "cache_write": 3.75 }
+                    },
+                    "claude-free": { "limit": { "context": 1000 } }
+                }
+            }
+        });
+
+        let pricing = find_in_registry(
+            &registry,
+            "anthropic",
+            "anthropic/claude-sonnet-4-6",
+            extract_pricing_from_entry,
+        )
+        .expect("pricing");
+        assert!((pricing.output - 15.0).abs() < f64::EPSILON);
+        assert_eq!(pricing.cache_read, Some(0.3));
+        assert!(
+            find_in_registry(
+                &registry,
+                "anthropic",
+                "claude-free",
+                extract_pricing_from_entry
+            )
+            .is_none()
+        );
+    }
 }
@@ -0,0 +1,368 @@
+//! Model discovery and pulls against an Ollama server.
+
+use anyhow::{Context, Result};
+use futures::StreamExt;
+use serde::Deserialize;
+use serde_json::{Value, json};
+use std::time::Duration;
+use tracing::debug;
+
+pub const OLLAMA_DEFAULT_BASE_URL: &str = "http://localhost:11434";
+
+/// A model installed on an Ollama server.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OllamaModel {
+    pub name: String,
+    /// Size on disk in bytes.
+    pub size: u64,
+    /// Longest context the model supports, from `/api/show`.
+    pub context_length: Option<usize>,
+    /// Capabilities reported by `/api/show`, e.g. `completion`, `tools`,
+    /// `vision`, `thinking`.
+    pub capabilities: Vec<String>,
+}
+
+impl OllamaModel {
+    pub fn supports(&self, capability: &str) -> bool {
+        self.capabilities.iter().any(|c| c == capability)
+    }
+}
+
+/// One progress line from `/api/pull`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+pub struct OllamaPullProgress {
+    #[serde(default)]
+    pub status: String,
+    #[serde(default)]
+    pub digest: Option<String>,
+    #[serde(default)]
+    pub total: Option<u64>,
+    #[serde(default)]
+    pub completed: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TagsResponse {
+    #[serde(default)]
+    models: Vec<TagEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TagEntry {
+    name: String,
+    #[serde(default)]
+    size: u64,
+}
+
+/// Server root for a configured `base_url`, without a trailing `/v1`.
+pub fn ollama_server_root(base_url: Option<&str>) -> String {
+    base_url
+        .map(str::trim)
+        .filter(|url| !url.is_empty())
+        .unwrap_or(OLLAMA_DEFAULT_BASE_URL)
+        .trim_end_matches('/')
+        .trim_end_matches("/v1")
+        .to_owned()
+}
+
+/// List installed models with their context length and capabilities.
+pub async fn list_ollama_models(base_url: Option<&str>) -> Result<Vec<OllamaModel>> {
+    let root = ollama_server_root(base_url);
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(5))
+        .build()?;
+
+    let tags = client
+        .get(format!("{root}/api/tags"))
+        .send()
+        .await
+        .with_context(|| format!("failed to reach Ollama at {root}"))?
+        .error_for_status()?
+        .json::<TagsResponse>()
+        .await
+        .context("unexpected /api/tags response")?;
+
+    let mut models = Vec::with_capacity(tags.models.len());
+    for tag in tags.models {
+        let (context_length, capabilities) = match show_model(&client, &root, &tag.name).await {
+            Ok(payload) => (
+                context_length_from_show(&payload),
+                payload
+                    .get("capabilities")
+                    .and_then(Value::as_array)
+                    .into_iter()
+                    .flatten()
+                    .filter_map(Value::as_str)
+                    .map(str::to_owned)
+                    .collect(),
+            ),
+            Err(error) => {
+                debug!(model = %tag.name, error = %error, "ollama /api/show failed");
+                (None, Vec::new())
+            }
+        };
+        models.push(OllamaModel {
+            name: tag.name,
+            size: tag.size,
+            context_length,
+            capabilities,
+        });
+    }
+    Ok(models)
+}
+
+async fn show_model(client: &reqwest::Client, root: &str, model: &str) -> Result<Value> {
+    Ok(client
+        .post(format!("{root}/api/show"))
+        .json(&json!({ "model": model }))
+        .send()
+        .await?
+        .error_for_status()?
+        .json::<Value>()
+        .await?)
+}
+
+/// Context length from an `/api/show` payload: the model's trained context,
+/// else a `num_ctx` parameter from its Modelfile.
+pub(crate) fn context_length_from_show(payload: &Value) -> Option<usize> {
+    let trained = payload
+        .get("model_info")
+        .and_then(Value::as_object)
+        .into_iter()
+        .flatten()
+        .find_map(|(key, value)| {
+            key.ends_with("context_length")
+                .then(|| value.as_u64().and_then(|limit| usize::try_from(limit).ok()))
+                .flatten()
+        });
+    trained.or_else(|| {
+        payload
+            .get("parameters")?
+            .as_str()?
+            .lines()
+            .find(|line| line.split_whitespace().next() == Some("num_ctx"))?
+            .split_whitespace()
+            .last()?
+            .parse()
+            .ok()
+    })
+}
+
+/// Download `model` onto the server, reporting each progress line.
+pub async fn pull_ollama_model(
+    base_url: Option<&str>,
+    model: &str,
+    mut on_progress: impl FnMut(&OllamaPullProgress),
+) -> Result<()> {
+    let root = ollama_server_root(base_url);
+    let client = reqwest::Client::builder()
+        .connect_timeout(Duration::from_secs(5))
+        .build()?;
+    let response = client
+        .post(format!("{root}/api/pull"))
+        .json(&json!({ "model": model, "stream": true }))
+        .send()
+        .await
+        .with_context(|| format!("failed to reach Ollama at {root}"))?
+        .error_for_status()?;
+
+    let mut lines = NdjsonLines::new(response.bytes_stream());
+    let mut succeeded = false;
+    while let Some(line) = lines.next_line().await? {
+        let value: Value = serde_json::from_str(&line)
+            .with_context(|| format!("unexpected /api/pull line: {line}"))?;
+        if let Some(error) = value.get("error").and_then(Value::as_str) {
+            anyhow::bail!("ollama pull failed: {error}");
+        }
+        let progress: OllamaPullProgress = serde_json::from_value(value)?;
+        succeeded |= progress.status == "success";
+        on_progress(&progress);
+    }
+    anyhow::ensure!(succeeded, "ollama pull ended before reporting success");
+    Ok(())
+}
+
+/// Splits a streamed response body into newline-delimited JSON lines.
+pub(crate) struct NdjsonLines<S> {
+    bytes: S,
+    buf: Vec<u8>,
+    done: bool,
+}
+
+impl<S> NdjsonLines<S>
+where
+    S: futures::Stream<Item = Result<bytes::Bytes, reqwest::Error>> + Unpin,
+{
+    pub(crate) fn new(bytes: S) -> Self {
+        Self {
+            bytes,
+            buf: Vec::new(),
+            done: false,
+        }
+    }
+
+    pub(crate) async fn next_line(&mut self) -> Result<Option<String>> {
+        loop {
+            if let Some(pos) = self.buf.iter().position(|&byte| byte == b'\n') {
+                let line: Vec<u8> = self.buf.drain(..=pos).collect();
+                let line = String::from_utf8_lossy(&line).trim().to_owned();
+                if line.is_empty() {
+                    continue;
+                }
+                return Ok(Some(line));
+            }
+            if self.done {
+                let rest = String::from_utf8_lossy(&std::mem::take(&mut self.buf))
+                    .trim()
+                    .to_owned();
+                return Ok((!rest.is_empty()).then_some(rest));
+            }
+            match self.bytes.next().await {
+                Some(Ok(chunk)) => self.buf.extend_from_slice(&chunk),
+                Some(Err(error)) => anyhow::bail!("ollama stream error: {error}"),
+                None => self.done = true,
+            }
+        }
+    }
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    /// Serve canned responses keyed by request path, one connection each,
+    /// recording every request.
+    pub(crate) async fn stub_server(
+        routes: Vec<(&'static str, String)>,
+    ) -> (String, tokio::sync::mpsc::UnboundedReceiver<String>) {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
+        tokio::spawn(async move {
+            loop {
+                let Ok((mut socket, _)) = listener.accept().await else {
+                    return;
+                };
+                let request = read_request(&mut socket).await;
+                let path = request.split_whitespace().nth(1).unwrap_or("").to_owned();
+                let _ = tx.send(request);
+                let (status, body) = routes
+                    .iter()
+                    .find(|(route, _)| *route == path)
+                    .map_or(("404 Not Found", String::new()), |(_, body)| {
+                        ("200 OK", body.clone())
+                    });
+                let response = format!(
+                    "HTTP/1.1 {status}\r\ncontent-type: application/x-ndjson\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+                    body.len()
+                );
+                let _ = socket.write_all(response.as_bytes()).await;
+            }
+        });
+        (format!("http://{addr}"), rx)
+    }
+
+    async fn read_request(socket: &mut tokio::net::TcpStream) -> String {
+        let mut buffer = Vec::new();
+        let mut chunk = [0_u8; 4096];
+        loop {
+            let read = socket.read(&mut chunk).await.unwrap_or(0);
+            if read == 0 {
+                break;
+            }
+            buffer.extend_from_slice(&chunk[..read]);
+            let Some(end) = buffer.windows(4).position(|w| w == b"\r\n\r\n") else {
+                continue;
+            };
+            let headers = String::from_utf8_lossy(&buffer[..end]).to_ascii_lowercase();
+            let length = headers
+                .lines()
+                .find_map(|line| line.strip_prefix("content-length:"))
+                .and_then(|value| value.trim().parse::<usize>().ok())
+                .unwrap_or(0);
+            if buffer.len() >= end + 4 + length {
+                break;
+            }
+        }
+        String::from_utf8_lossy(&buffer).into_owned()
+    }
+
+    #[test]
+    fn server_root_strips_v1() {
+        assert_eq!(ollama_server_root(None), "http://localhost:11434");
+        assert_eq!(
+            ollama_server_root(Some("http://box:11434/v1/")),
+            "http://box:11434"
+        );
+    }
+
+    #[tokio::test]
+    async fn lists_models_with_context_and_capabilities() {
+        let tags = json!({"models": [{"name": "qwen3:8b", "size": 5_200_000_000_u64}]});
+        let show = json!({
+            "model_info": {"qwen3.context_length": 40960, "general.architecture": "qwen3"},
+            "capabilities": ["completion", "tools", "thinking"]
+        });
+        let (base_url, _) = stub_server(vec![
+            ("/api/tags", tags.to_string()),
+            ("/api/show", show.to_string()),
+        ])
+        .await;
+
+        let models = list_ollama_models(Some(&format!("{base_url}/v1")))
+            .await
+            .unwrap();
+        assert_eq!(models.len(), 1);
+        assert_eq!(models[0].name, "qwen3:8b");
+        assert_eq!(models[0].context_length, Some(40960));
+        assert!(models[0].supports("tools"));
+        assert!(!models[0].supports("vision"));
+    }
+
+    #[test]
+    fn context_length_falls_back_to_num_ctx() {
+        let payload = json!({"parameters": "stop \"<|im_end|>\"\nnum_ctx 8192"});
+        assert_eq!(context_length_from_show(&payload), Some(8192));
+    }
+
+    #[tokio::test]
+    async fn pull_reports_progress() {
+        let body = [
+            json!({"status": "pulling manifest"}),
+            json!({"status": "pulling abc", "digest": "sha256:abc", "total": 100, "completed": 40}),
+            json!({"status": "success"}),
+        ]
+        .iter()
+        .map(|line| line.to_string() + "\n")
+        .collect::<String>();
+        let (base_url, mut requests) = stub_server(vec![("/api/pull", body)]).await;
+
+        let mut seen = Vec::new();
+        pull_ollama_model(Some(&base_url), "llama3.2", |progress| {
+            seen.push((progress.status.clone(), progress.completed));
+        })
+        .await
+        .unwrap();
+        assert_eq!(seen[1], ("pulling abc".to_owned(), Some(40)));
+        assert_eq!(seen.len(), 3);
+        assert!(
+            requests
+                .recv()
+                .await
+                .unwrap()
+                .contains("\"model\":\"llama3.2\"")
+        );
+
+        let (base_url, _) = stub_server(vec![(
+            "/api/pull",
+            "{\"error\":\"file does not exist\"}\n".into(),
+        )])
+        .await;
+        let error = pull_ollama_model(Some(&base_url), "nope", |_| {})
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("file does not exist"));
+    }
+}
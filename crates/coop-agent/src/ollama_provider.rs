@@ -0,0 +1,591 @@
+//! Native Ollama provider speaking `/api/chat`.
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::{Map, Value, json};
+use std::collections::HashMap;
+use std::sync::RwLock;
+use std::time::Duration;
+use tracing::{Instrument, debug, info_span, warn};
+
+use coop_core::traits::{Provider, ProviderStream};
+use coop_core::types::{Content, Message, ModelInfo, Role, ToolDef, Usage};
+
+use crate::image_prep::prepare_image_for_provider;
+use crate::model_context::{ContextLimitInput, resolve_context_limit};
+use crate::model_mapping::strip_ollama_prefix;
+use crate::ollama_models::{NdjsonLines, ollama_server_root};
+use crate::provider_spec::{ProviderKind, ProviderSpec};
+
+const MAX_RETRIES: u32 = 3;
+
+pub(crate) struct OllamaProvider {
+    client: reqwest::Client,
+    root: String,
+    spec: RwLock<ProviderSpec>,
+    model: RwLock<ModelInfo>,
+}
+
+impl OllamaProvider {
+    pub(crate) fn new(spec: ProviderSpec) -> Result<Self> {
+        let root = ollama_server_root(spec.base_url.as_deref());
+        let model = model_info(&spec);
+        let client = reqwest::Client::builder()
+            .connect_timeout(Duration::from_secs(10))
+            .timeout(Duration::from_mins(10))
+            .build()?;
+        Ok(Self {
+            client,
+            root,
+            spec: RwLock::new(spec),
+            model: RwLock::new(model),
+        })
+    }
+
+    fn model_snapshot(&self) -> ModelInfo {
+        self.model.read().expect("model lock poisoned").clone()
+    }
+
+    fn build_body(
+        &self,
+        system: &[String],
+        messages: &[Message],
+        tools: &[ToolDef],
+        stream: bool,
+    ) -> Value {
+        let (model, keep_alive, num_ctx) = {
+            let spec = self.spec.read().expect("spec lock poisoned");
+            (
+                strip_ollama_prefix(&spec.model),
+                spec.keep_alive.clone(),
+                spec.configured_context_limit(&spec.model),
+            )
+        };
+        let mut body = json!({
+            "model": model,
+            "messages": map_messages(system, messages),
+            "stream": stream,
+        });
+        if !tools.is_empty() {
+            body["tools"] = tools.iter().map(map_tool).collect();
+        }
+        if let Some(keep_alive) = keep_alive.as_deref() {
+            // Ollama takes seconds as a number and durations as strings.
+            body["keep_alive"] = keep_alive
+                .parse::<i64>()
+                .map_or_else(|_| json!(keep_alive), |seconds| json!(seconds));
+        }
+        // Without num_ctx Ollama runs a small default window and silently
+        // drops the start of longer prompts.
+        if let Some(num_ctx) = num_ctx {
+            body["options"] = json!({ "num_ctx": num_ctx });
+        }
+        body
+    }
+
+    async fn send(&self, body: &Value) -> Result<reqwest::Response> {
+        let url = format!("{}/api/chat", self.root);
+        let mut attempt = 0;
+        loop {
+            let response = self
+                .client
+                .post(&url)
+                .json(body)
+                .send()
+                .await
+                .with_context(|| format!("failed to reach Ollama at {}", self.root))?;
+            let status = response.status();
+            if status.is_success() {
+                return Ok(response);
+            }
+            let text = response.text().await.unwrap_or_default();
+            if status.is_server_error() && attempt < MAX_RETRIES {
+                let backoff_ms = 1000u64 * 2u64.pow(attempt);
+                warn!(
+                    provider = "ollama",
+                    attempt = attempt + 1,
+                    status = status.as_u16(),
+                    backoff_ms,
+                    body = %text,
+                    "retryable provider error, backing off"
+                );
+                tokio::time::sleep(Duration::from_millis(backoff_ms)).await;
+                attempt += 1;
+                continue;
+            }
+            let error = serde_json::from_str::<Value>(&text)
+                .ok()
+                .and_then(|value| value.get("error")?.as_str().map(str::to_owned))
+                .unwrap_or(text);
+            if attempt > 0 {
+                anyhow::bail!("Ollama API error after {attempt} retries ({status}): {error}");
+            }
+            anyhow::bail!("Ollama API error ({status}): {error}");
+        }
+    }
+}
+
+fn model_info(spec: &ProviderSpec) -> ModelInfo {
+    ModelInfo {
+        name: strip_ollama_prefix(&spec.model),
+        context_limit: resolve_context_limit(ContextLimitInput {
+            kind: ProviderKind::Ollama,
+            model: &spec.model,
+            base_url: spec.base_url.as_deref(),
+            api_key: None,
+            configured_limit: spec.configured_context_limit(&spec.model),
+        }),
+    }
+}
+
+impl std::fmt::Debug for OllamaProvider {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("OllamaProvider")
+            .field("root", &self.root)
+            .field("model", &self.model_snapshot().name)
+            .finish_non_exhaustive()
+    }
+}
+
+#[async_trait]
+impl Provider for OllamaProvider {
+    fn name(&self) -> &str {
+        ProviderKind::Ollama.as_str()
+    }
+
+    fn model_info(&self) -> ModelInfo {
+        self.model_snapshot()
+    }
+
+    fn set_model(&self, model: &str) {
+        let mut spec = self.spec.write().expect("spec lock poisoned");
+        if spec.model == model {
+            return;
+        }
+        model.clone_into(&mut spec.model);
+        let info = model_info(&spec);
+        drop(spec);
+        debug!(provider = "ollama", new = %info.name, "provider model updated");
+        *self.model.write().expect("model lock poisoned") = info;
+    }
+
+    async fn complete(
+        &self,
+        system: &[String],
+        messages: &[Message],
+        tools: &[ToolDef],
+    ) -> Result<(Message, Usage)> {
+        let span = info_span!(
+            "provider_request",
+            provider = "ollama",
+            model = %self.model_snapshot().name,
+            method = "complete",
+            message_count = messages.len(),
+            tool_count = tools.len(),
+        );
+        async {
+            let body = self.build_body(system, messages, tools, false);
+            let chunk: ChatChunk = self
+                .send(&body)
+                .await?
+                .json()
+                .await
+                .context("unexpected /api/chat response")?;
+            let mut response = ChatResponse::default();
+            response.push(chunk)?;
+            Ok(response.finish())
+        }
+        .instrument(span)
+        .await
+    }
+
+    async fn stream(
+        &self,
+        system: &[String],
+        messages: &[Message],
+        tools: &[ToolDef],
+    ) -> Result<ProviderStream> {
+        let span = info_span!(
+            "provider_request",
+            provider = "ollama",
+            model = %self.model_snapshot().name,
+            method = "stream",
+            message_count = messages.len(),
+            tool_count = tools.len(),
+        );
+        let body = self.build_body(system, messages, tools, true);
+        let response = self.send(&body).instrument(span).await?;
+
+        let stream = futures::stream::unfold(
+            Some((
+                NdjsonLines::new(response.bytes_stream()),
+                ChatResponse::default(),
+            )),
+            |state| async move {
+                let (mut lines, mut response) = state?;
+                loop {
+                    let line = match lines.next_line().await {
+                        Ok(Some(line)) => line,
+                        Ok(None) => {
+                            let error = anyhow::anyhow!("Ollama stream ended before done");
+                            return Some((Err(error), None));
+                        }
+                        Err(error) => return Some((Err(error), None)),
+                    };
+                    let chunk = match serde_json::from_str::<ChatChunk>(&line) {
+                        Ok(chunk) => chunk,
+                        Err(error) => {
+                            debug!(error = %error, line, "skipping unparseable Ollama chunk");
+                            continue;
+                        }
+                    };
+                    let done = chunk.done;
+                    let delta = match response.push(chunk) {
+                        Ok(delta) => delta,
+                        Err(error) => return Some((Err(error), None)),
+                    };
+                    if done {
+                        let (message, usage) = response.finish();
+                        return Some((Ok((Some(message), Some(usage))), None));
+                    }
+                    if let Some(text) = delta {
+                        let message = Message::assistant().with_text(text);
+                        return Some((Ok((Some(message), None)), Some((lines, response))));
+                    }
+                }
+            },
+        );
+        Ok(Box::pin(stream))
+    }
+
+    fn supports_streaming(&self) -> bool {
+        true
+    }
+}
+
+fn map_tool(tool: &ToolDef) -> Value {
+    json!({
+        "type": "function",
+        "function": {
+            "name": tool.name,
+            "description": tool.description,
+            "parameters": tool.parameters,
+        }
+    })
+}
+
+fn map_messages(system: &[String], messages: &[Message]) -> Vec<Value> {
+    let mut mapped: Vec<Value> = system
+        .iter()
+        .map(|block| json!({ "role": "system", "content": block }))
+        .collect();
+    // Ollama matches tool results to calls by tool name, not id.
+    let mut tool_names = HashMap::new();
+
+    for message in messages {
+        let mut text = Vec::new();
+        let mut thinking = Vec::new();
+        let mut images = Vec::new();
+        let mut tool_calls = Vec::new();
+
+        for content in &message.content {
+            match content {
+                Content::Text { text: part } => text.push(part.as_str()),
+                Content::Image { data, mime_type } => {
+                    if let Some(prepared) =
+                        prepare_image_for_provider(ProviderKind::Ollama, data, mime_type)
+                    {
+                        images.push(prepared.data);
+                    }
+                }
+                Content::ToolRequest {
+                    id,
+                    name,
+                    arguments,
+                } => {
+                    tool_names.insert(id.as_str(), name.as_str());
+                    tool_calls.push(json!({
+                        "function": { "name": name, "arguments": arguments }
+                    }));
+                }
+                Content::ToolResult {
+                    id,
+                    output,
+                    is_error,
+                } => {
+                    let content = if *is_error {
+                        format!("ERROR: {output}")
+                    } else {
+                        output.clone()
+                    };
+                    mapped.push(json!({
+                        "role": "tool",
+                        "content": content,
+                        "tool_name": tool_names.get(id.as_str()).copied().unwrap_or_default(),
+                    }));
+                }
+                Content::Thinking { thinking: part, .. } => thinking.push(part.as_str()),
+                // Ollama has no document input; the gateway sends extracted text instead.
+                Content::Document { .. } => {}
+            }
+        }
+
+        if text.is_empty() && images.is_empty() && tool_calls.is_empty() {
+            continue;
+        }
+        let role = match message.role {
+            Role::User => "user",
+            Role::Assistant => "assistant",
+        };
+        let mut entry = Map::new();
+        entry.insert("role".into(), json!(role));
+        entry.insert("content".into(), json!(text.join("\n\n")));
+        if !images.is_empty() {
+            entry.insert("images".into(), json!(images));
+        }
+        if !tool_calls.is_empty() {
+            entry.insert("tool_calls".into(), json!(tool_calls));
+        }
+        if message.role == Role::Assistant && !thinking.is_empty() {
+            entry.insert("thinking".into(), json!(thinking.join("\n\n")));
+        }
+        mapped.push(Value::Object(entry));
+    }
+    mapped
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatChunk {
+    #[serde(default)]
+    message: Option<ChunkMessage>,
+    #[serde(default)]
+    done: bool,
+    #[serde(default)]
+    done_reason: Option<String>,
+    #[serde(default)]
+    prompt_eval_count: Option<u32>,
+    #[serde(default)]
+    eval_count: Option<u32>,
+    #[serde(default)]
+    error: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct ChunkMessage {
+    #[serde(default)]
+    content: String,
+    #[serde(default)]
+    thinking: Option<String>,
+    #[serde(default)]
+    tool_calls: Vec<WireToolCall>,
+}
+
+#[derive(Debug, Deserialize)]
+struct WireToolCall {
+    #[serde(default)]
+    id: Option<String>,
+    function: WireFunction,
+}
+
+#[derive(Debug, Deserialize)]
+struct WireFunction {
+    name: String,
+    #[serde(default)]
+    arguments: Value,
+}
+
+/// Accumulates `/api/chat` chunks into one assistant message. Ollama sends
+/// each tool call whole, in whichever chunk the model finished it.
+#[derive(Debug, Default)]
+struct ChatResponse {
+    text: String,
+    thinking: String,
+    tool_calls: Vec<(String, String, Value)>,
+    usage: Usage,
+}
+
+impl ChatResponse {
+    /// Add a chunk, returning its new text.
+    fn push(&mut self, chunk: ChatChunk) -> Result<Option<String>> {
+        if let Some(error) = chunk.error {
+            anyhow::bail!("Ollama API error: {error}");
+        }
+        let mut delta = None;
+        if let Some(message) = chunk.message {
+            if let Some(thinking) = message.thinking {
+                self.thinking.push_str(&thinking);
+            }
+            for call in message.tool_calls {
+                let id = call.id.unwrap_or_else(|| {
+                    format!("ollama_call_{}_{}", call_id_seed(), self.tool_calls.len())
+                });
+                self.tool_calls
+                    .push((id, call.function.name, call.function.arguments));
+            }
+            if !message.content.is_empty() {
+                self.text.push_str(&message.content);
+                delta = Some(message.content);
+            }
+        }
+        if chunk.done {
+            self.usage.input_tokens = chunk.prompt_eval_count;
+            self.usage.output_tokens = chunk.eval_count;
+            self.usage.stop_reason = if self.tool_calls.is_empty() {
+                chunk.done_reason
+            } else {
+                Some("tool_use".to_owned())
+            };
+        }
+        Ok(delta)
+    }
+
+    fn finish(self) -> (Message, Usage) {
+        let mut message = Message::assistant();
+        if !self.thinking.trim().is_empty() {
+            message.content.push(Content::Thinking {
+                thinking: self.thinking,
+                signature: None,
+            });
+        }
+        if !self.text.is_empty() {
+            message = message.with_text(self.text);
+        }
+        for (id, name, arguments) in self.tool_calls {
+            message = message.with_tool_request(id, name, arguments);
+        }
+        (message, self.usage)
+    }
+}
+
+/// Ollama doesn't always id its tool calls, so make ones that stay unique
+/// across a session.
+fn call_id_seed() -> u128 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos()
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ollama_models::tests::stub_server;
+    use futures::StreamExt;
+
+    fn provider(base_url: &str) -> OllamaProvider {
+        let mut spec = ProviderSpec::new(ProviderKind::Ollama, "ollama/qwen3:8b");
+        spec.base_url = Some(base_url.to_owned());
+        spec.keep_alive = Some("30m".to_owned());
+        spec.model_context_limits
+            .insert("qwen3:8b".to_owned(), 16_384);
+        OllamaProvider::new(spec).unwrap()
+    }
+
+    #[test]
+    fn maps_tool_round_trip_and_images() {
+        let messages = [
+            Message::user()
+                .with_text("what is in this picture?")
+                .with_image("aGk=", "image/png"),
+            Message::assistant().with_tool_request("t1", "read_file", json!({"path": "a"})),
+            Message::user().with_tool_result("t1", "contents", false),
+        ];
+        let mapped = map_messages(&["be brief".to_owned()], &messages);
+        assert_eq!(mapped[0], json!({"role": "system", "content": "be brief"}));
+        assert_eq!(mapped[1]["images"], json!(["aGk="]));
+        assert_eq!(
+            mapped[2]["tool_calls"][0]["function"]["name"],
+            json!("read_file")
+        );
+        assert_eq!(
+            mapped[3],
+            json!({"role": "tool", "content": "contents", "tool_name": "read_file"})
+        );
+        assert_eq!(mapped.len(), 4);
+    }
+
+    #[tokio::test]
+    async fn complete_sends_options_and_parses_tool_calls() {
+        let reply = json!({
+            "message": {
+                "role": "assistant",
+                "content": "",
+                "tool_calls": [{"function": {"name": "memory_search", "arguments": {"query": "wifi"}}}]
+            },
+            "done": true,
+            "done_reason": "stop",
+            "prompt_eval_count": 120,
+            "eval_count": 9
+        });
+        let (base_url, mut requests) = stub_server(vec![("/api/chat", reply.to_string())]).await;
+        let provider = provider(&format!("{base_url}/v1"));
+
+        let (message, usage) = provider
+            .complete(&[], &[Message::user().with_text("wifi?")], &[])
+            .await
+            .unwrap();
+        let calls = message.tool_requests();
+        assert_eq!(calls[0].name, "memory_search");
+        assert_eq!(calls[0].arguments, json!({"query": "wifi"}));
+        assert_eq!(usage.input_tokens, Some(120));
+        assert_eq!(usage.stop_reason.as_deref(), Some("tool_use"));
+
+        let request = requests.recv().await.unwrap();
+        assert!(request.starts_with("POST /api/chat "));
+        assert!(request.contains("\"model\":\"qwen3:8b\""));
+        assert!(request.contains("\"keep_alive\":\"30m\""));
+        assert!(request.contains("\"num_ctx\":16384"));
+        assert_eq!(provider.model_info().context_limit, 16_384);
+    }
+
+    #[tokio::test]
+    async fn stream_yields_deltas_then_final_message() {
+        let body = [
+            json!({"message": {"role": "assistant", "content": "", "thinking": "hmm"}, "done": false}),
+            json!({"message": {"role": "assistant", "content": "Hel"}, "done": false}),
+            json!({"message": {"role": "assistant", "content": "lo", "tool_calls": [
+                {"function": {"name": "remind", "arguments": {"at": "5pm"}}}
+            ]}, "done": false}),
+            json!({"message": {"role": "assistant", "content": ""}, "done": true, "done_reason": "stop", "prompt_eval_count": 10, "eval_count": 3}),
+        ]
+        .iter()
+        .map(|line| line.to_string() + "\n")
+        .collect::<String>();
+        let (base_url, _) = stub_server(vec![("/api/chat", body)]).await;
+        let provider = provider(&base_url);
+
+        let mut stream = provider
+            .stream(&[], &[Message::user().with_text("hi")], &[])
+            .await
+            .unwrap();
+        let mut deltas = Vec::new();
+        let mut last = None;
+        while let Some(item) = stream.next().await {
+            let (message, usage) = item.unwrap();
+            match usage {
+                Some(usage) => last = Some((message.unwrap(), usage)),
+                None => deltas.push(message.unwrap().text()),
+            }
+        }
+        assert_eq!(deltas, ["Hel", "lo"]);
+        let (message, usage) = last.unwrap();
+        assert_eq!(message.text(), "Hello");
+        assert_eq!(message.tool_requests()[0].name, "remind");
+        assert!(
+            matches!(&message.content[0], Content::Thinking { thinking, .. } if thinking == "hmm")
+        );
+        assert_eq!(usage.output_tokens, Some(3));
+    }
+
+    #[tokio::test]
+    async fn errors_carry_the_status() {
+        let (base_url, _) = stub_server(Vec::new()).await;
+        let error = provider(&base_url)
+            .complete(&[], &[Message::user().with_text("hi")], &[])
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("(404 Not Found)"), "{error}");
+    }
+}
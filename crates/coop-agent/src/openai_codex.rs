@@ -193,6 +193,15 @@ fn build_codex_input(messages: &[Message]) -> Vec<Value> {
                             "detail": "auto",
                             "image_url": format!("data:{mime_type};base64,{data}"),
                         })),
+                        Content::Document {
+                            data,
+                            mime_type,
+                            name,
+                        } => user_content.push(json!({
+                            "type": "input_file",
+                            "filename": name,
+                            "file_data": format!("data:{mime_type};base64,{data}"),
+                        })),
                         Content::ToolResult { id, output, .. } => {
                             if !user_content.is_empty() {
                                 items.push(json!({
@@ -258,6 +267,7 @@ fn build_codex_input(messages: &[Message]) -> Vec<Value> {
                             }
                         }
                         Content::Image { .. }
+                        | Content::Document { .. }
                         | Content::ToolResult { .. }
                         | Content::Thinking { .. } => {}
                     }
@@ -249,6 +249,7 @@ mod tests {
             extra_headers: std::collections::BTreeMap::default(),
             refresh_token: None,
             reasoning: None,
+            keep_alive: None,
         }
     }
 
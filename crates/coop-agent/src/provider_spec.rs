@@ -113,6 +113,9 @@ pub struct ProviderSpec {
     pub extra_headers: BTreeMap<String, String>,
     pub refresh_token: Option<String>,
     pub reasoning: Option<OpenAiReasoningConfig>,
+    /// How long Ollama keeps the model loaded after a request, e.g. `"30m"`,
+    /// `"-1"` (forever) or `"0"` (unload at once).
+    pub keep_alive: Option<String>,
 }
 
 impl ProviderSpec {
@@ -129,6 +132,7 @@ impl ProviderSpec {
             extra_headers: BTreeMap::new(),
             refresh_token: None,
             reasoning: None,
+            keep_alive: None,
         }
     }
 
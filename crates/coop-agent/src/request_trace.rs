@@ -434,6 +434,7 @@ mod tests {
             extra_headers: BTreeMap::from([("x-test".into(), "1".into())]),
             refresh_token: None,
             reasoning: None,
+            keep_alive: None,
         };
 
         let trace = summarize_provider_trace(ProviderKind::OpenAiCompatible, &spec, false);
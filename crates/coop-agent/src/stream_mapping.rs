@@ -9,14 +9,23 @@ use crate::message_mapping::map_response_message;
 use crate::request_trace::summarize_transport_error;
 use crate::usage_mapping::usage_from_stream_end;
 
-pub(crate) fn into_provider_stream(chat_stream: genai::chat::ChatStreamResponse) -> ProviderStream {
-    let stream = chat_stream.stream.filter_map(|item| async move {
+pub(crate) fn into_provider_stream(
+    chat_stream: genai::chat::ChatStreamResponse,
+    key_index: Option<usize>,
+) -> ProviderStream {
+    let stream = chat_stream.stream.filter_map(move |item| async move {
         match item {
             Ok(ChatStreamEvent::Chunk(chunk)) => {
                 let message = Message::assistant().with_text(chunk.content);
                 Some(Ok((Some(message), None)))
             }
-            Ok(ChatStreamEvent::End(end)) => Some(Ok(final_stream_item(&end))),
+            Ok(ChatStreamEvent::End(end)) => {
+                let (message, usage) = final_stream_item(&end);
+                Some(Ok((
+                    message,
+                    usage.map(|usage| usage.with_key_index(key_index)),
+                )))
+            }
             Ok(
                 ChatStreamEvent::Start
                 | ChatStreamEvent::ToolCallChunk(_)
@@ -654,6 +654,7 @@ mod tests {
             extra_headers: BTreeMap::new(),
             refresh_token: None,
             reasoning: None,
+            keep_alive: None,
         };
         let transport = TransportErrorTrace {
             variant: "web_model_call",
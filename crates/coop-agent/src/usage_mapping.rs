@@ -69,6 +69,7 @@ fn usage_from_genai(usage: &genai::chat::Usage, stop_reason: Option<&str>) -> Us
         cache_read_tokens,
         cache_write_tokens,
         stop_reason: stop_reason.map(str::to_owned),
+        key_index: None,
     }
 }
 
@@ -33,6 +33,7 @@ async fn live_openai_auth_roundtrip() {
         extra_headers: Default::default(),
         refresh_token: None,
         reasoning: None,
+        keep_alive: None,
     };
 
     // Ensure env var is set for resolve_key_refs
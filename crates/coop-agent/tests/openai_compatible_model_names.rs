@@ -20,6 +20,7 @@ async fn preserves_exact_model_name_for_openai_compatible_requests() {
         extra_headers: BTreeMap::new(),
         refresh_token: None,
         reasoning: None,
+        keep_alive: None,
     })
     .expect("provider creates");
 
@@ -102,6 +102,7 @@ async fn provider_trace_logs_request_shape_and_transport_details() {
         extra_headers: BTreeMap::new(),
         refresh_token: None,
         reasoning: None,
+        keep_alive: None,
     })
     .expect("provider creates");
 
@@ -189,6 +190,7 @@ async fn provider_failure_probe_can_show_models_endpoint_is_up() {
         extra_headers: BTreeMap::new(),
         refresh_token: None,
         reasoning: None,
+        keep_alive: None,
     })
     .expect("provider creates");
 
@@ -1,7 +1,13 @@
+#[cfg(any(feature = "signal", feature = "telegram"))]
+mod attachments;
 #[cfg(feature = "signal")]
 pub mod signal;
 #[cfg(feature = "signal")]
 pub mod signal_tools;
+#[cfg(feature = "telegram")]
+pub mod telegram;
+#[cfg(feature = "telegram")]
+pub mod telegram_tools;
 
 #[cfg(feature = "signal")]
 pub use signal::{
@@ -9,3 +15,10 @@ pub use signal::{
 };
 #[cfg(feature = "signal")]
 pub use signal_tools::SignalToolExecutor;
+#[cfg(feature = "telegram")]
+pub use telegram::{
+    MockBotApi, TelegramAction, TelegramChannel, TelegramSettings, TelegramTarget,
+    TelegramTypingNotifier,
+};
+#[cfg(feature = "telegram")]
+pub use telegram_tools::TelegramToolExecutor;
@@ -15,6 +15,7 @@ pub mod testkit;
 pub use query::SignalQuery;
 pub use testkit::MockSignalChannel;
 
+use crate::attachments::{MAX_ATTACHMENT_BYTES, attachment_save_name};
 use anyhow::{Context, Result};
 use async_trait::async_trait;
 use coop_core::{
@@ -941,11 +942,6 @@ fn extract_attachment_pointers(
     }
 }
 
-/// Download each attachment, save to disk, and rewrite the inbound message
-/// content to include scope-relative file paths so the agent can access them.
-/// Maximum attachment size we'll save to disk (100 MB).
-const MAX_ATTACHMENT_BYTES: usize = 100 * 1024 * 1024;
-
 fn attachment_scope_for_inbound(
     workspace_root: &Path,
     sender_aci: &str,
@@ -980,6 +976,8 @@ fn attachment_scope_for_inbound(
     ))
 }
 
+/// Download each attachment, save to disk, and rewrite the inbound message
+/// content to include scope-relative file paths so the agent can access them.
 #[allow(clippy::too_many_arguments, clippy::too_many_lines)]
 async fn download_and_rewrite_attachments(
     manager: &SignalManager,
@@ -1108,17 +1106,6 @@ async fn download_and_rewrite_attachments(
     inbound.content = rewrite_attachment_lines(&inbound.content, &original_metas, &replacements);
 }
 
-fn attachment_save_name(
-    timestamp: u64,
-    attachment_index: usize,
-    file_name: &str,
-    content_type: Option<&str>,
-) -> String {
-    let sanitized = sanitize_filename(file_name);
-    let base_name = format!("{timestamp}_{attachment_index:03}_{sanitized}");
-    ensure_media_extension(&base_name, content_type)
-}
-
 fn rewrite_attachment_lines(
     content: &str,
     originals: &[String],
@@ -1155,71 +1142,6 @@ fn rewrite_attachment_lines(
     rewritten.join("\n")
 }
 
-/// Append a file extension when the filename lacks one and the content-type
-/// is a recognized MIME type. This ensures downloaded Signal attachments are
-/// discoverable by the media-injection pipeline in `coop_core::images`,
-/// which requires a recognized extension.
-fn ensure_media_extension(name: &str, content_type: Option<&str>) -> String {
-    let lower = name.to_lowercase();
-    let known_extensions = [
-        "jpg", "jpeg", "png", "gif", "webp", "heic", "heif", "mp4", "mov", "avi", "mkv", "mp3",
-        "m4a", "ogg", "wav", "aac", "flac", "opus", "pdf",
-    ];
-    if known_extensions
-        .iter()
-        .any(|ext| lower.ends_with(&format!(".{ext}")))
-    {
-        return name.to_owned();
-    }
-
-    let ext = match content_type {
-        // Images
-        Some("image/jpeg") => ".jpg",
-        Some("image/png") => ".png",
-        Some("image/gif") => ".gif",
-        Some("image/webp") => ".webp",
-        Some("image/heic") => ".heic",
-        Some("image/heif") => ".heif",
-        // Audio
-        Some("audio/aac") => ".aac",
-        Some("audio/mp4" | "audio/x-m4a") => ".m4a",
-        Some("audio/mpeg") => ".mp3",
-        Some("audio/ogg" | "audio/ogg; codecs=opus") => ".ogg",
-        Some("audio/wav" | "audio/x-wav") => ".wav",
-        Some("audio/flac") => ".flac",
-        Some("audio/opus") => ".opus",
-        // Video
-        Some("video/mp4") => ".mp4",
-        Some("video/quicktime") => ".mov",
-        Some("video/x-msvideo") => ".avi",
-        Some("video/x-matroska") => ".mkv",
-        // Documents
-        Some("application/pdf") => ".pdf",
-        _ => return name.to_owned(),
-    };
-
-    format!("{name}{ext}")
-}
-
-/// Sanitize a filename for safe filesystem storage.
-fn sanitize_filename(name: &str) -> String {
-    let sanitized: String = name
-        .chars()
-        .map(|c| {
-            if c.is_alphanumeric() || c == '.' || c == '-' || c == '_' {
-                c
-            } else {
-                '_'
-            }
-        })
-        .collect();
-    if sanitized.is_empty() {
-        "unnamed".to_owned()
-    } else {
-        sanitized
-    }
-}
-
 fn now_epoch_millis() -> u64 {
     std::time::SystemTime::now()
         .duration_since(UNIX_EPOCH)
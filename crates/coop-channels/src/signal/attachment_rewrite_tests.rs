@@ -145,6 +145,7 @@ fn inbound(chat_id: Option<&str>, is_group: bool) -> InboundMessage {
         reply_to: None,
         kind: InboundKind::Attachment,
         message_timestamp: None,
+        message_id: None,
         group_revision: None,
     }
 }
@@ -1,4 +1,4 @@
-use super::ensure_media_extension;
+use crate::attachments::ensure_media_extension;
 
 #[test]
 fn adds_jpg_for_jpeg_mime() {
@@ -39,6 +39,7 @@ pub(crate) fn parse_content(
                 reply_to,
                 kind: InboundKind::Typing,
                 message_timestamp: Some(timestamp),
+                message_id: None,
                 group_revision: None,
             })
         }
@@ -52,6 +53,7 @@ pub(crate) fn parse_content(
             reply_to: Some(sender),
             kind: InboundKind::Receipt,
             message_timestamp: Some(timestamp),
+            message_id: None,
             group_revision: None,
         }),
         ContentBody::SynchronizeMessage(sync_message) => {
@@ -186,6 +188,7 @@ fn inbound_from_data_message(
             reply_to,
             kind: InboundKind::Command,
             message_timestamp: Some(timestamp),
+            message_id: None,
             group_revision: None,
         });
     }
@@ -206,6 +209,7 @@ fn inbound_from_data_message(
         reply_to,
         kind,
         message_timestamp: Some(timestamp),
+        message_id: None,
         group_revision: None,
     })
 }
@@ -247,6 +251,7 @@ fn inbound_from_edit_message(
         reply_to,
         kind: InboundKind::Edit,
         message_timestamp: Some(timestamp),
+        message_id: None,
         group_revision: None,
     })
 }
@@ -0,0 +1,701 @@
+mod api;
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod channel_tests;
+mod inbound;
+pub mod testkit;
+
+pub use testkit::{MockBotApi, MockBotCall};
+
+use crate::attachments::{MAX_ATTACHMENT_BYTES, attachment_save_name};
+use anyhow::{Context, Result};
+use api::{BotApi, Update};
+use async_trait::async_trait;
+use coop_core::{
+    Channel, ChannelHealth, InboundMessage, OutboundMessage, SessionKey, SessionKind, TrustLevel,
+    TypingNotifier, WorkspaceScope,
+};
+use inbound::{InboundContext, ParsedUpdate, inbound_from_update};
+use serde_json::{Value, json};
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tracing::{Instrument, debug, info, info_span, warn};
+
+pub const DEFAULT_API_BASE_URL: &str = "https://api.telegram.org";
+
+/// Telegram rejects messages longer than this many characters.
+const MAX_MESSAGE_CHARS: usize = 4096;
+
+/// Chat actions expire after ~5 seconds, so typing is re-sent while a turn
+/// is running.
+const TYPING_REFRESH_INTERVAL: Duration = Duration::from_secs(4);
+
+/// Upper bound on how long a typing indicator is kept alive if the stop
+/// signal never arrives.
+const TYPING_MAX_DURATION: Duration = Duration::from_mins(5);
+
+type HealthState = Arc<Mutex<ChannelHealth>>;
+
+/// Most recent inbound message id per chat. Reactions and replies without an
+/// explicit message id target this message.
+type LastMessageIds = Arc<Mutex<HashMap<i64, i64>>>;
+
+#[derive(Debug, Clone)]
+pub enum TelegramAction {
+    SendText(OutboundMessage),
+    SendPhoto {
+        target: TelegramTarget,
+        path: PathBuf,
+        caption: Option<String>,
+    },
+    React {
+        target: TelegramTarget,
+        message_id: Option<i64>,
+        emoji: String,
+        remove: bool,
+    },
+    Reply {
+        target: TelegramTarget,
+        text: String,
+        reply_to_message_id: Option<i64>,
+    },
+    Typing {
+        target: TelegramTarget,
+        started: bool,
+    },
+    Shutdown,
+}
+
+/// A Telegram chat: a private chat with a user, or a group/supergroup.
+///
+/// Targets are written as the numeric chat id for DMs and `group:<chat id>`
+/// for groups, optionally prefixed with `telegram:`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TelegramTarget {
+    Direct(i64),
+    Group(i64),
+}
+
+impl TelegramTarget {
+    pub fn parse(value: &str) -> Result<Self> {
+        let value = value.trim().trim_start_matches("telegram:");
+
+        if let Some(group_id) = value.strip_prefix("group:") {
+            let chat_id = group_id
+                .parse()
+                .with_context(|| format!("invalid telegram group target: {group_id}"))?;
+            return Ok(Self::Group(chat_id));
+        }
+
+        anyhow::ensure!(!value.is_empty(), "direct target cannot be empty");
+        let chat_id = value
+            .parse()
+            .with_context(|| format!("invalid telegram chat target: {value}"))?;
+        Ok(Self::Direct(chat_id))
+    }
+
+    pub fn chat_id(self) -> i64 {
+        match self {
+            Self::Direct(chat_id) | Self::Group(chat_id) => chat_id,
+        }
+    }
+}
+
+impl std::fmt::Display for TelegramTarget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Direct(chat_id) => write!(f, "{chat_id}"),
+            Self::Group(chat_id) => write!(f, "group:{chat_id}"),
+        }
+    }
+}
+
+/// Connection settings for [`TelegramChannel::connect`].
+#[derive(Clone)]
+pub struct TelegramSettings {
+    pub token: String,
+    pub api_base_url: String,
+    /// Long-poll timeout passed to `getUpdates`.
+    pub poll_timeout: Duration,
+    pub workspace_root: PathBuf,
+    /// Telegram user ids with at least inner trust. Photos are only
+    /// downloaded and saved for these senders.
+    pub trusted_senders: HashSet<String>,
+    pub user_trusts: HashMap<String, TrustLevel>,
+    /// Telegram user id → coop user name from `[[users]]` config.
+    pub user_names: HashMap<String, String>,
+}
+
+impl TelegramSettings {
+    pub fn new(token: impl Into<String>, workspace_root: impl Into<PathBuf>) -> Self {
+        Self {
+            token: token.into(),
+            api_base_url: DEFAULT_API_BASE_URL.to_owned(),
+            poll_timeout: Duration::from_secs(30),
+            workspace_root: workspace_root.into(),
+            trusted_senders: HashSet::new(),
+            user_trusts: HashMap::new(),
+            user_names: HashMap::new(),
+        }
+    }
+}
+
+impl std::fmt::Debug for TelegramSettings {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TelegramSettings")
+            .field("api_base_url", &self.api_base_url)
+            .field("poll_timeout", &self.poll_timeout)
+            .field("workspace_root", &self.workspace_root)
+            .field("trusted_senders", &self.trusted_senders)
+            .finish_non_exhaustive()
+    }
+}
+
+#[allow(missing_debug_implementations)]
+pub struct TelegramChannel {
+    id: String,
+    inbound_rx: mpsc::Receiver<InboundMessage>,
+    action_tx: mpsc::Sender<TelegramAction>,
+    health: HealthState,
+    bot_username: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct TelegramTypingNotifier {
+    action_tx: mpsc::Sender<TelegramAction>,
+}
+
+impl TelegramTypingNotifier {
+    pub fn new(action_tx: mpsc::Sender<TelegramAction>) -> Self {
+        Self { action_tx }
+    }
+}
+
+#[async_trait]
+impl TypingNotifier for TelegramTypingNotifier {
+    async fn set_typing(&self, session_key: &SessionKey, started: bool) {
+        let chat = match &session_key.kind {
+            SessionKind::Dm(identity) => identity.strip_prefix("telegram:"),
+            SessionKind::Group(group_id) => group_id.strip_prefix("telegram:"),
+            SessionKind::Main
+            | SessionKind::Isolated(_)
+            | SessionKind::Cron(_)
+            | SessionKind::Subagent(_) => None,
+        };
+        let Some(Ok(target)) = chat.map(TelegramTarget::parse) else {
+            return;
+        };
+
+        let _ = self
+            .action_tx
+            .send(TelegramAction::Typing { target, started })
+            .await;
+    }
+}
+
+impl TelegramChannel {
+    /// Connect to the Bot API and start long-polling for updates.
+    ///
+    /// Fails if the token is rejected by `getMe`; transient polling errors
+    /// after startup are retried with backoff and reported via `probe`.
+    pub async fn connect(settings: TelegramSettings) -> Result<Self> {
+        let api = BotApi::new(&settings.api_base_url, &settings.token)?;
+        let me = api
+            .call("getMe", &json!({}))
+            .await
+            .context("failed to validate telegram bot token")?;
+        let bot_id = me.get("id").and_then(Value::as_i64).unwrap_or_default();
+        let bot_username = me
+            .get("username")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_owned();
+        info!(bot_username = %bot_username, "telegram bot authenticated");
+
+        let (inbound_tx, inbound_rx) = mpsc::channel(64);
+        let (action_tx, action_rx) = mpsc::channel(64);
+        let health = Arc::new(Mutex::new(ChannelHealth::Healthy));
+        let last_message_ids: LastMessageIds = Arc::default();
+
+        let ctx = InboundContext {
+            bot_id,
+            bot_username: bot_username.clone(),
+            user_names: settings.user_names.clone(),
+        };
+
+        tokio::spawn(poll_task(
+            api.clone(),
+            settings,
+            ctx,
+            inbound_tx,
+            Arc::clone(&health),
+            Arc::clone(&last_message_ids),
+        ));
+        tokio::spawn(send_task(
+            api,
+            action_rx,
+            Arc::clone(&health),
+            last_message_ids,
+        ));
+
+        Ok(Self {
+            id: "telegram".to_owned(),
+            inbound_rx,
+            action_tx,
+            health,
+            bot_username,
+        })
+    }
+
+    pub fn action_sender(&self) -> mpsc::Sender<TelegramAction> {
+        self.action_tx.clone()
+    }
+
+    /// The bot's `@username` (without the `@`), as reported by `getMe`.
+    pub fn bot_username(&self) -> &str {
+        &self.bot_username
+    }
+}
+
+#[async_trait]
+impl Channel for TelegramChannel {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    async fn recv(&mut self) -> Result<InboundMessage> {
+        self.inbound_rx
+            .recv()
+            .await
+            .ok_or_else(|| anyhow::anyhow!("telegram channel closed"))
+    }
+
+    async fn send(&self, msg: OutboundMessage) -> Result<()> {
+        self.action_tx
+            .send(TelegramAction::SendText(msg))
+            .await
+            .map_err(|_send_err| anyhow::anyhow!("telegram action channel closed"))
+    }
+
+    async fn probe(&self) -> ChannelHealth {
+        self.health.lock().expect("health mutex poisoned").clone()
+    }
+}
+
+async fn poll_task(
+    api: BotApi,
+    settings: TelegramSettings,
+    ctx: InboundContext,
+    inbound_tx: mpsc::Sender<InboundMessage>,
+    health: HealthState,
+    last_message_ids: LastMessageIds,
+) {
+    let mut offset: i64 = 0;
+    let mut backoff = Duration::from_secs(1);
+    let params_timeout = settings.poll_timeout.as_secs();
+    let request_timeout = settings.poll_timeout + Duration::from_secs(10);
+
+    info!("telegram long-poll started");
+
+    loop {
+        let params = json!({
+            "offset": offset,
+            "timeout": params_timeout,
+            "allowed_updates": ["message", "edited_message", "message_reaction"],
+        });
+
+        let updates = match api
+            .call_with_timeout("getUpdates", &params, request_timeout)
+            .await
+        {
+            Ok(Value::Array(updates)) => updates,
+            Ok(other) => {
+                warn!(result = %other, "telegram getUpdates returned a non-array result");
+                Vec::new()
+            }
+            Err(error) => {
+                warn!(error = %format!("{error:#}"), "telegram getUpdates failed");
+                set_health(
+                    &health,
+                    ChannelHealth::Degraded(format!("telegram polling failed: {error:#}")),
+                );
+                tokio::time::sleep(backoff).await;
+                let next_secs = backoff.as_secs().saturating_mul(2).min(30);
+                backoff = Duration::from_secs(next_secs.max(1));
+                continue;
+            }
+        };
+
+        set_health(&health, ChannelHealth::Healthy);
+        backoff = Duration::from_secs(1);
+
+        for raw in updates {
+            // Advance past every update, even ones we can't parse, so a
+            // malformed update is not redelivered forever.
+            if let Some(update_id) = raw.get("update_id").and_then(Value::as_i64) {
+                offset = offset.max(update_id + 1);
+            }
+
+            let update: Update = match serde_json::from_value(raw) {
+                Ok(update) => update,
+                Err(error) => {
+                    warn!(error = %error, "skipping malformed telegram update");
+                    continue;
+                }
+            };
+
+            let span = info_span!("telegram_receive_event", telegram.update_id = update.id);
+            let Some(mut parsed) = span.in_scope(|| inbound_from_update(&update, &ctx)) else {
+                continue;
+            };
+
+            if parsed.inbound.kind != coop_core::InboundKind::Reaction
+                && let Some(message_id) = parsed.inbound.message_id
+            {
+                last_message_ids
+                    .lock()
+                    .expect("last message mutex poisoned")
+                    .insert(parsed.chat_id, message_id);
+            }
+
+            if parsed.photo.is_some() && settings.trusted_senders.contains(&parsed.inbound.sender) {
+                download_and_rewrite_photo(&api, &settings, &mut parsed)
+                    .instrument(span.clone())
+                    .await;
+            }
+
+            debug!(
+                telegram.inbound_kind = ?parsed.inbound.kind,
+                telegram.sender = %parsed.inbound.sender,
+                telegram.chat_id = ?parsed.inbound.chat_id,
+                telegram.message_id = ?parsed.inbound.message_id,
+                telegram.raw_content = %parsed.inbound.content,
+                "received telegram inbound"
+            );
+
+            if inbound_tx.send(parsed.inbound).await.is_err() {
+                return;
+            }
+        }
+    }
+}
+
+fn attachment_scope_for_inbound(
+    settings: &TelegramSettings,
+    inbound: &InboundMessage,
+) -> Option<WorkspaceScope> {
+    if inbound.is_group {
+        let chat_id = inbound.chat_id.as_deref()?;
+        return Some(WorkspaceScope::for_group_principal(
+            &settings.workspace_root,
+            &format!("telegram:{chat_id}"),
+        ));
+    }
+
+    let coop_name = settings.user_names.get(&inbound.sender)?;
+    let trust = settings
+        .user_trusts
+        .get(&inbound.sender)
+        .copied()
+        .unwrap_or(TrustLevel::Inner);
+    let session_kind = SessionKind::Dm(format!("telegram:{}", inbound.sender));
+    Some(WorkspaceScope::for_turn(
+        &settings.workspace_root,
+        &session_kind,
+        trust,
+        Some(coop_name),
+    ))
+}
+
+/// Download the largest variant of an inbound photo into the sender's scoped
+/// `attachments/` directory and replace its metadata line with the saved path.
+async fn download_and_rewrite_photo(
+    api: &BotApi,
+    settings: &TelegramSettings,
+    parsed: &mut ParsedUpdate,
+) {
+    let Some(photo) = parsed.photo.take() else {
+        return;
+    };
+    let inbound = &mut parsed.inbound;
+
+    let Some(scope) = attachment_scope_for_inbound(settings, inbound) else {
+        warn!(
+            sender = %inbound.sender,
+            chat_id = ?inbound.chat_id,
+            is_group = inbound.is_group,
+            "skipping attachment save: no scoped workspace available"
+        );
+        return;
+    };
+
+    let replacement = match save_photo(api, &scope, &photo.file_id, inbound).await {
+        Ok(relative_ref) => format!("{}\n[file saved: {relative_ref}]", photo.meta_line),
+        Err(error) => {
+            warn!(error = %format!("{error:#}"), "failed to save telegram photo");
+            format!("{}\n[download failed: {error}]", photo.meta_line)
+        }
+    };
+    inbound.content = inbound.content.replacen(&photo.meta_line, &replacement, 1);
+}
+
+async fn save_photo(
+    api: &BotApi,
+    scope: &WorkspaceScope,
+    file_id: &str,
+    inbound: &InboundMessage,
+) -> Result<String> {
+    let attachments_dir = scope.attachments_dir()?;
+    std::fs::create_dir_all(&attachments_dir)
+        .with_context(|| format!("failed to create {}", attachments_dir.display()))?;
+
+    let data = api.download_file(file_id, MAX_ATTACHMENT_BYTES).await?;
+    let save_name = attachment_save_name(
+        inbound
+            .message_id
+            .and_then(|id| u64::try_from(id).ok())
+            .unwrap_or_default(),
+        1,
+        "photo",
+        Some("image/jpeg"),
+    );
+    let save_path = attachments_dir.join(&save_name);
+    std::fs::write(&save_path, &data)
+        .with_context(|| format!("failed to write {}", save_path.display()))?;
+
+    let relative_ref = scope
+        .scope_relative_path(&save_path)
+        .unwrap_or_else(|_| format!("./attachments/{save_name}"));
+    info!(
+        path = %save_path.display(),
+        relative_path = %relative_ref,
+        size = data.len(),
+        scoped_root = %scope.scope_display(),
+        "saved telegram photo"
+    );
+    Ok(relative_ref)
+}
+
+async fn send_task(
+    api: BotApi,
+    mut action_rx: mpsc::Receiver<TelegramAction>,
+    health: HealthState,
+    last_message_ids: LastMessageIds,
+) {
+    let mut typing: HashMap<i64, JoinHandle<()>> = HashMap::new();
+
+    while let Some(action) = action_rx.recv().await {
+        if matches!(action, TelegramAction::Shutdown) {
+            info!("telegram send task shutting down gracefully");
+            break;
+        }
+
+        debug!(action = ?action, "sending telegram action");
+
+        if let TelegramAction::Typing { target, started } = action {
+            let chat_id = target.chat_id();
+            if let Some(task) = typing.remove(&chat_id) {
+                task.abort();
+            }
+            if started {
+                typing.insert(chat_id, spawn_typing_refresher(api.clone(), chat_id));
+            }
+            continue;
+        }
+
+        match send_telegram_action(&api, action, &last_message_ids).await {
+            Ok(()) => set_health(&health, ChannelHealth::Healthy),
+            Err(error) => {
+                warn!(error = %format!("{error:#}"), "failed to send telegram action");
+                set_health(
+                    &health,
+                    ChannelHealth::Degraded(format!("telegram send failed: {error:#}")),
+                );
+            }
+        }
+    }
+
+    for task in typing.into_values() {
+        task.abort();
+    }
+    set_health(
+        &health,
+        ChannelHealth::Unhealthy("telegram sender task stopped".to_owned()),
+    );
+}
+
+fn spawn_typing_refresher(api: BotApi, chat_id: i64) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let refresh = async {
+            loop {
+                if let Err(error) = api
+                    .call(
+                        "sendChatAction",
+                        &json!({ "chat_id": chat_id, "action": "typing" }),
+                    )
+                    .await
+                {
+                    debug!(error = %format!("{error:#}"), "telegram typing indicator failed");
+                }
+                tokio::time::sleep(TYPING_REFRESH_INTERVAL).await;
+            }
+        };
+        let _ = tokio::time::timeout(TYPING_MAX_DURATION, refresh).await;
+    })
+}
+
+async fn send_telegram_action(
+    api: &BotApi,
+    action: TelegramAction,
+    last_message_ids: &LastMessageIds,
+) -> Result<()> {
+    let last_message_id = |target: TelegramTarget| {
+        last_message_ids
+            .lock()
+            .expect("last message mutex poisoned")
+            .get(&target.chat_id())
+            .copied()
+    };
+
+    match action {
+        TelegramAction::SendText(outbound) => {
+            let target = TelegramTarget::parse(&outbound.target)?;
+            let span = info_span!(
+                "telegram_action_send",
+                telegram.action = "send_text",
+                telegram.target = %target,
+                telegram.raw_content = %outbound.content,
+            );
+            send_text(api, target, &outbound.content, None)
+                .instrument(span)
+                .await
+        }
+        TelegramAction::Reply {
+            target,
+            text,
+            reply_to_message_id,
+        } => {
+            let reply_to = reply_to_message_id.or_else(|| last_message_id(target));
+            let span = info_span!(
+                "telegram_action_send",
+                telegram.action = "reply",
+                telegram.target = %target,
+                telegram.raw_content = %text,
+                telegram.reply_to_message_id = ?reply_to,
+            );
+            send_text(api, target, &text, reply_to)
+                .instrument(span)
+                .await
+        }
+        TelegramAction::SendPhoto {
+            target,
+            path,
+            caption,
+        } => {
+            let span = info_span!(
+                "telegram_action_send",
+                telegram.action = "send_photo",
+                telegram.target = %target,
+                telegram.attachment_path = %path.display(),
+            );
+            api.send_photo(target.chat_id(), &path, caption.as_deref())
+                .instrument(span)
+                .await
+                .map(|_| ())
+        }
+        TelegramAction::React {
+            target,
+            message_id,
+            emoji,
+            remove,
+        } => {
+            let message_id = message_id
+                .or_else(|| last_message_id(target))
+                .context("no telegram message to react to")?;
+            let span = info_span!(
+                "telegram_action_send",
+                telegram.action = "react",
+                telegram.target = %target,
+                telegram.message_id = message_id,
+                telegram.emoji = %emoji,
+                telegram.remove = remove,
+            );
+            let reaction = if remove {
+                json!([])
+            } else {
+                json!([{ "type": "emoji", "emoji": emoji }])
+            };
+            api.call(
+                "setMessageReaction",
+                &json!({
+                    "chat_id": target.chat_id(),
+                    "message_id": message_id,
+                    "reaction": reaction,
+                }),
+            )
+            .instrument(span)
+            .await
+            .map(|_| ())
+        }
+        TelegramAction::Typing { .. } | TelegramAction::Shutdown => Ok(()),
+    }
+}
+
+/// Send text, splitting it into multiple messages when it exceeds Telegram's
+/// length limit. Only the first chunk carries the reply reference.
+async fn send_text(
+    api: &BotApi,
+    target: TelegramTarget,
+    text: &str,
+    reply_to_message_id: Option<i64>,
+) -> Result<()> {
+    for (index, chunk) in split_message(text, MAX_MESSAGE_CHARS)
+        .into_iter()
+        .enumerate()
+    {
+        let mut params = json!({ "chat_id": target.chat_id(), "text": chunk });
+        if index == 0
+            && let Some(message_id) = reply_to_message_id
+        {
+            params["reply_parameters"] = json!({
+                "message_id": message_id,
+                "allow_sending_without_reply": true,
+            });
+        }
+        api.call("sendMessage", &params).await?;
+    }
+    Ok(())
+}
+
+/// Split `text` into chunks of at most `max_chars` characters, preferring to
+/// break on newlines.
+fn split_message(text: &str, max_chars: usize) -> Vec<String> {
+    let mut chunks = Vec::new();
+    let mut rest = text;
+
+    while rest.chars().count() > max_chars {
+        let limit = rest
+            .char_indices()
+            .nth(max_chars)
+            .map_or(rest.len(), |(index, _)| index);
+        let split_at = rest[..limit]
+            .rfind('\n')
+            .filter(|&index| index > 0)
+            .map_or(limit, |index| index + 1);
+        chunks.push(rest[..split_at].trim_end_matches('\n').to_owned());
+        rest = &rest[split_at..];
+    }
+
+    if !rest.is_empty() || chunks.is_empty() {
+        chunks.push(rest.to_owned());
+    }
+    chunks
+}
+
+fn set_health(health: &HealthState, state: ChannelHealth) {
+    *health.lock().expect("health mutex poisoned") = state;
+}
@@ -0,0 +1,372 @@
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use serde_json::{Value, json};
+use std::path::Path;
+use std::time::Duration;
+use tracing::warn;
+
+/// How many times a request is retried after a `429 Too Many Requests`.
+const MAX_RATE_LIMIT_RETRIES: usize = 3;
+
+/// Minimal Telegram Bot API client.
+///
+/// Request URLs embed the bot token, so errors are stripped of their URL
+/// before they are surfaced.
+#[derive(Clone)]
+pub(crate) struct BotApi {
+    client: reqwest::Client,
+    base_url: String,
+    token: String,
+}
+
+impl std::fmt::Debug for BotApi {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BotApi")
+            .field("base_url", &self.base_url)
+            .finish_non_exhaustive()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiResponse {
+    ok: bool,
+    #[serde(default)]
+    result: Option<Value>,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    error_code: Option<i64>,
+    #[serde(default)]
+    parameters: Option<ResponseParameters>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ResponseParameters {
+    #[serde(default)]
+    retry_after: Option<u64>,
+}
+
+impl BotApi {
+    pub(crate) fn new(base_url: &str, token: &str) -> Result<Self> {
+        let client = reqwest::Client::builder()
+            .connect_timeout(Duration::from_secs(10))
+            .build()
+            .context("failed to build telegram http client")?;
+        Ok(Self {
+            client,
+            base_url: base_url.trim_end_matches('/').to_owned(),
+            token: token.to_owned(),
+        })
+    }
+
+    fn method_url(&self, method: &str) -> String {
+        format!("{}/bot{}/{method}", self.base_url, self.token)
+    }
+
+    /// Call a Bot API method with a JSON body and return its `result`.
+    pub(crate) async fn call(&self, method: &str, params: &Value) -> Result<Value> {
+        self.call_with_timeout(method, params, Duration::from_secs(30))
+            .await
+    }
+
+    pub(crate) async fn call_with_timeout(
+        &self,
+        method: &str,
+        params: &Value,
+        timeout: Duration,
+    ) -> Result<Value> {
+        let mut attempt = 0;
+        loop {
+            let response = self
+                .client
+                .post(self.method_url(method))
+                .timeout(timeout)
+                .json(params)
+                .send()
+                .await
+                .map_err(reqwest::Error::without_url)
+                .with_context(|| format!("telegram {method} request failed"))?;
+
+            match parse_response(method, response).await {
+                Err(ApiError::RateLimited(retry_after)) if attempt < MAX_RATE_LIMIT_RETRIES => {
+                    attempt += 1;
+                    warn!(
+                        method,
+                        retry_after, attempt, "telegram rate limited, retrying"
+                    );
+                    tokio::time::sleep(Duration::from_secs(retry_after)).await;
+                }
+                Err(error) => return Err(error.into()),
+                Ok(result) => return Ok(result),
+            }
+        }
+    }
+
+    /// Upload a local photo with `sendPhoto`.
+    pub(crate) async fn send_photo(
+        &self,
+        chat_id: i64,
+        path: &Path,
+        caption: Option<&str>,
+    ) -> Result<Value> {
+        let bytes = tokio::fs::read(path)
+            .await
+            .with_context(|| format!("failed to read photo: {}", path.display()))?;
+        let file_name = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or("photo")
+            .to_owned();
+
+        let mut form = reqwest::multipart::Form::new()
+            .text("chat_id", chat_id.to_string())
+            .part(
+                "photo",
+                reqwest::multipart::Part::bytes(bytes).file_name(file_name),
+            );
+        if let Some(caption) = caption {
+            form = form.text("caption", caption.to_owned());
+        }
+
+        let response = self
+            .client
+            .post(self.method_url("sendPhoto"))
+            .timeout(Duration::from_mins(2))
+            .multipart(form)
+            .send()
+            .await
+            .map_err(reqwest::Error::without_url)
+            .context("telegram sendPhoto request failed")?;
+        Ok(parse_response("sendPhoto", response).await?)
+    }
+
+    /// Resolve a `file_id` with `getFile` and download its contents.
+    ///
+    /// Downloads larger than `max_bytes` are rejected before the body is read
+    /// when the size is known up front.
+    pub(crate) async fn download_file(&self, file_id: &str, max_bytes: usize) -> Result<Vec<u8>> {
+        let file = self.call("getFile", &json!({ "file_id": file_id })).await?;
+        let file_path = file
+            .get("file_path")
+            .and_then(Value::as_str)
+            .context("telegram getFile returned no file_path")?;
+
+        let url = format!("{}/file/bot{}/{file_path}", self.base_url, self.token);
+        let response = self
+            .client
+            .get(url)
+            .timeout(Duration::from_mins(2))
+            .send()
+            .await
+            .map_err(reqwest::Error::without_url)
+            .context("telegram file download failed")?;
+
+        let status = response.status();
+        anyhow::ensure!(
+            status.is_success(),
+            "telegram file download failed with status {status}"
+        );
+        if let Some(length) = response.content_length()
+            && usize::try_from(length).unwrap_or(usize::MAX) > max_bytes
+        {
+            anyhow::bail!("file too large ({length} bytes, max {max_bytes} bytes)");
+        }
+
+        let bytes = response
+            .bytes()
+            .await
+            .map_err(reqwest::Error::without_url)
+            .context("telegram file download failed")?;
+        anyhow::ensure!(
+            bytes.len() <= max_bytes,
+            "file too large ({} bytes, max {max_bytes} bytes)",
+            bytes.len()
+        );
+        Ok(bytes.to_vec())
+    }
+}
+
+#[derive(Debug)]
+enum ApiError {
+    RateLimited(u64),
+    Failed(anyhow::Error),
+}
+
+impl From<ApiError> for anyhow::Error {
+    fn from(error: ApiError) -> Self {
+        match error {
+            ApiError::RateLimited(retry_after) => {
+                anyhow::anyhow!("telegram rate limit exceeded (retry after {retry_after}s)")
+            }
+            ApiError::Failed(error) => error,
+        }
+    }
+}
+
+async fn parse_response(method: &str, response: reqwest::Response) -> Result<Value, ApiError> {
+    let status = response.status();
+    let body: ApiResponse = response.json().await.map_err(|error| {
+        ApiError::Failed(anyhow::anyhow!(
+            "telegram {method} returned an invalid response ({status}): {}",
+            error.without_url()
+        ))
+    })?;
+
+    if body.ok {
+        return Ok(body.result.unwrap_or(Value::Null));
+    }
+
+    if body.error_code == Some(429)
+        && let Some(retry_after) = body.parameters.and_then(|p| p.retry_after)
+    {
+        return Err(ApiError::RateLimited(retry_after));
+    }
+
+    Err(ApiError::Failed(anyhow::anyhow!(
+        "telegram {method} failed ({}): {}",
+        body.error_code
+            .unwrap_or_else(|| i64::from(status.as_u16())),
+        body.description.as_deref().unwrap_or("no description")
+    )))
+}
+
+// ---------------------------------------------------------------------------
+// Update types (only the fields coop uses)
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct Update {
+    #[serde(rename = "update_id")]
+    pub id: i64,
+    #[serde(default)]
+    pub message: Option<Message>,
+    #[serde(default)]
+    pub edited_message: Option<Message>,
+    #[serde(default)]
+    pub message_reaction: Option<MessageReactionUpdated>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct Message {
+    #[serde(rename = "message_id")]
+    pub id: i64,
+    #[serde(default)]
+    pub from: Option<User>,
+    pub chat: Chat,
+    pub date: i64,
+    #[serde(default)]
+    pub text: Option<String>,
+    #[serde(default)]
+    pub caption: Option<String>,
+    #[serde(default)]
+    pub photo: Vec<PhotoSize>,
+    #[serde(default)]
+    pub document: Option<Document>,
+    #[serde(default, rename = "reply_to_message")]
+    pub reply_to: Option<Box<Message>>,
+}
+
+impl Message {
+    /// Message text, or the media caption when there is no text.
+    pub(crate) fn body(&self) -> Option<&str> {
+        self.text
+            .as_deref()
+            .or(self.caption.as_deref())
+            .map(str::trim)
+            .filter(|body| !body.is_empty())
+    }
+
+    /// The highest-resolution variant of an attached photo.
+    pub(crate) fn largest_photo(&self) -> Option<&PhotoSize> {
+        self.photo
+            .iter()
+            .max_by_key(|photo| (photo.width * photo.height, photo.file_size.unwrap_or(0)))
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct User {
+    pub id: i64,
+    #[serde(default)]
+    pub is_bot: bool,
+    #[serde(default)]
+    pub first_name: String,
+    #[serde(default)]
+    pub last_name: Option<String>,
+    #[serde(default)]
+    pub username: Option<String>,
+}
+
+impl User {
+    pub(crate) fn full_name(&self) -> String {
+        let name = match &self.last_name {
+            Some(last) => format!("{} {last}", self.first_name),
+            None => self.first_name.clone(),
+        };
+        let name = name.trim();
+        if name.is_empty() {
+            self.username.clone().unwrap_or_else(|| self.id.to_string())
+        } else {
+            name.to_owned()
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct Chat {
+    pub id: i64,
+    #[serde(rename = "type")]
+    pub kind: String,
+}
+
+impl Chat {
+    pub(crate) fn is_private(&self) -> bool {
+        self.kind == "private"
+    }
+
+    pub(crate) fn is_group(&self) -> bool {
+        matches!(self.kind.as_str(), "group" | "supergroup")
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct PhotoSize {
+    pub file_id: String,
+    #[serde(default)]
+    pub width: u64,
+    #[serde(default)]
+    pub height: u64,
+    #[serde(default)]
+    pub file_size: Option<u64>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct Document {
+    #[serde(default)]
+    pub file_name: Option<String>,
+    #[serde(default)]
+    pub mime_type: Option<String>,
+    #[serde(default)]
+    pub file_size: Option<u64>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct MessageReactionUpdated {
+    pub chat: Chat,
+    pub message_id: i64,
+    #[serde(default)]
+    pub user: Option<User>,
+    pub date: i64,
+    #[serde(default)]
+    pub old_reaction: Vec<ReactionType>,
+    #[serde(default)]
+    pub new_reaction: Vec<ReactionType>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub(crate) struct ReactionType {
+    #[serde(rename = "type")]
+    pub kind: String,
+    #[serde(default)]
+    pub emoji: Option<String>,
+}
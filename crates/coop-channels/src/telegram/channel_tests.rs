@@ -0,0 +1,316 @@
+use super::{
+    MockBotApi, TelegramAction, TelegramChannel, TelegramSettings, TelegramTarget,
+    TelegramTypingNotifier, split_message,
+};
+use coop_core::{
+    Channel, ChannelHealth, InboundKind, OutboundMessage, SessionKey, SessionKind, TrustLevel,
+    TypingNotifier,
+};
+use serde_json::json;
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+
+const WAIT: Duration = Duration::from_secs(5);
+
+async fn connect(mock: &MockBotApi, workspace: &std::path::Path) -> TelegramChannel {
+    let mut settings = TelegramSettings::new("123:test-token", workspace);
+    settings.api_base_url = mock.api_base_url().to_owned();
+    settings.poll_timeout = Duration::from_secs(1);
+    settings.trusted_senders = HashSet::from(["42".to_owned()]);
+    settings.user_trusts = HashMap::from([("42".to_owned(), TrustLevel::Full)]);
+    settings.user_names = HashMap::from([("42".to_owned(), "alice".to_owned())]);
+    TelegramChannel::connect(settings).await.unwrap()
+}
+
+fn private_message(message_id: i64, text: &str) -> serde_json::Value {
+    json!({
+        "message": {
+            "message_id": message_id,
+            "from": {"id": 42, "is_bot": false, "first_name": "Alice"},
+            "chat": {"id": 42, "type": "private"},
+            "date": 1_700_000_000,
+            "text": text
+        }
+    })
+}
+
+async fn recv(channel: &mut TelegramChannel) -> coop_core::InboundMessage {
+    tokio::time::timeout(WAIT, channel.recv())
+        .await
+        .unwrap()
+        .unwrap()
+}
+
+#[test]
+fn parse_targets() {
+    assert_eq!(
+        TelegramTarget::parse("42").unwrap(),
+        TelegramTarget::Direct(42)
+    );
+    assert_eq!(
+        TelegramTarget::parse("telegram:42").unwrap(),
+        TelegramTarget::Direct(42)
+    );
+    assert_eq!(
+        TelegramTarget::parse("group:-100123").unwrap(),
+        TelegramTarget::Group(-100_123)
+    );
+    assert_eq!(
+        TelegramTarget::parse("telegram:group:-100123").unwrap(),
+        TelegramTarget::Group(-100_123)
+    );
+    assert_eq!(TelegramTarget::Group(-5).to_string(), "group:-5");
+    assert!(TelegramTarget::parse("").is_err());
+    assert!(TelegramTarget::parse("alice").is_err());
+    assert!(TelegramTarget::parse("group:abc").is_err());
+}
+
+#[test]
+fn split_message_prefers_newlines() {
+    assert_eq!(split_message("short", 10), vec!["short"]);
+    assert_eq!(
+        split_message("line one\nline two", 12),
+        vec!["line one", "line two"]
+    );
+    assert_eq!(split_message("abcdefghij", 4), vec!["abcd", "efgh", "ij"]);
+    assert_eq!(split_message("ééééé", 2), vec!["éé", "éé", "é"]);
+}
+
+#[tokio::test]
+async fn connect_rejects_invalid_token() {
+    let mock = MockBotApi::start().await.unwrap();
+    mock.reject_next("getMe");
+
+    let mut settings = TelegramSettings::new("bad", "/tmp");
+    settings.api_base_url = mock.api_base_url().to_owned();
+    let Err(error) = TelegramChannel::connect(settings).await else {
+        panic!("expected connect to fail");
+    };
+    let message = format!("{error:#}");
+    assert!(message.contains("failed to validate telegram bot token"));
+    assert!(message.contains("Unauthorized"));
+    assert!(
+        !message.contains("bad"),
+        "token leaked into error: {message}"
+    );
+}
+
+#[tokio::test]
+async fn polls_updates_into_inbound_messages() {
+    let workspace = tempfile::tempdir().unwrap();
+    let mock = MockBotApi::start().await.unwrap();
+    let mut channel = connect(&mock, workspace.path()).await;
+    assert_eq!(channel.bot_username(), "coop_bot");
+
+    mock.push_update(private_message(7, "hello"));
+    mock.push_update(json!({
+        "message": {
+            "message_id": 8,
+            "from": {"id": 43, "is_bot": false, "first_name": "Bob"},
+            "chat": {"id": -100_123, "type": "supergroup"},
+            "date": 1_700_000_001,
+            "text": "/status@coop_bot"
+        }
+    }));
+
+    let dm = recv(&mut channel).await;
+    assert_eq!(dm.channel, "telegram");
+    assert_eq!(dm.sender, "42");
+    assert_eq!(dm.content, "hello");
+    assert!(!dm.is_group);
+    assert_eq!(dm.reply_to.as_deref(), Some("42"));
+
+    let command = recv(&mut channel).await;
+    assert_eq!(command.kind, InboundKind::Command);
+    assert_eq!(command.content, "/status");
+    assert!(command.is_group);
+    assert_eq!(command.chat_id.as_deref(), Some("group:-100123"));
+    assert_eq!(command.reply_to.as_deref(), Some("group:-100123"));
+
+    assert!(matches!(channel.probe().await, ChannelHealth::Healthy));
+}
+
+#[tokio::test]
+async fn sends_text_replies_and_reactions() {
+    let workspace = tempfile::tempdir().unwrap();
+    let mock = MockBotApi::start().await.unwrap();
+    let mut channel = connect(&mock, workspace.path()).await;
+
+    mock.push_update(private_message(7, "hello"));
+    recv(&mut channel).await;
+
+    channel
+        .send(OutboundMessage {
+            channel: "telegram".to_owned(),
+            target: "42".to_owned(),
+            content: "hi there".to_owned(),
+        })
+        .await
+        .unwrap();
+    let action_tx = channel.action_sender();
+    action_tx
+        .send(TelegramAction::Reply {
+            target: TelegramTarget::Direct(42),
+            text: "quoted".to_owned(),
+            reply_to_message_id: None,
+        })
+        .await
+        .unwrap();
+    action_tx
+        .send(TelegramAction::React {
+            target: TelegramTarget::Direct(42),
+            message_id: None,
+            emoji: "👍".to_owned(),
+            remove: false,
+        })
+        .await
+        .unwrap();
+
+    let messages = mock.wait_for_calls("sendMessage", 2, WAIT).await;
+    assert_eq!(messages.len(), 2);
+    assert_eq!(
+        messages[0].params,
+        json!({"chat_id": 42, "text": "hi there"})
+    );
+    assert_eq!(messages[1].params["text"], "quoted");
+    assert_eq!(messages[1].params["reply_parameters"]["message_id"], 7);
+
+    let reactions = mock.wait_for_calls("setMessageReaction", 1, WAIT).await;
+    assert_eq!(
+        reactions[0].params,
+        json!({
+            "chat_id": 42,
+            "message_id": 7,
+            "reaction": [{"type": "emoji", "emoji": "👍"}]
+        })
+    );
+}
+
+#[tokio::test]
+async fn retries_rate_limited_sends() {
+    let workspace = tempfile::tempdir().unwrap();
+    let mock = MockBotApi::start().await.unwrap();
+    let channel = connect(&mock, workspace.path()).await;
+
+    mock.rate_limit_next("sendMessage");
+    channel
+        .send(OutboundMessage {
+            channel: "telegram".to_owned(),
+            target: "group:-100123".to_owned(),
+            content: "eventually".to_owned(),
+        })
+        .await
+        .unwrap();
+
+    let messages = mock.wait_for_calls("sendMessage", 1, WAIT).await;
+    assert_eq!(messages.len(), 1);
+    assert_eq!(messages[0].params["chat_id"], -100_123);
+}
+
+#[tokio::test]
+async fn typing_notifier_maps_sessions_to_chat_actions() {
+    let workspace = tempfile::tempdir().unwrap();
+    let mock = MockBotApi::start().await.unwrap();
+    let channel = connect(&mock, workspace.path()).await;
+    let notifier = TelegramTypingNotifier::new(channel.action_sender());
+
+    let signal_session = SessionKey {
+        agent_id: "coop".to_owned(),
+        kind: SessionKind::Dm("signal:alice-uuid".to_owned()),
+    };
+    notifier.set_typing(&signal_session, true).await;
+
+    let session = SessionKey {
+        agent_id: "coop".to_owned(),
+        kind: SessionKind::Group("telegram:group:-100123".to_owned()),
+    };
+    notifier.set_typing(&session, true).await;
+
+    let actions = mock.wait_for_calls("sendChatAction", 1, WAIT).await;
+    assert_eq!(
+        actions[0].params,
+        json!({"chat_id": -100_123, "action": "typing"})
+    );
+    notifier.set_typing(&session, false).await;
+}
+
+#[tokio::test]
+async fn trusted_photos_are_saved_to_scoped_attachments() {
+    let workspace = tempfile::tempdir().unwrap();
+    let mock = MockBotApi::start().await.unwrap();
+    let mut channel = connect(&mock, workspace.path()).await;
+    mock.add_file("large", b"jpeg-bytes".to_vec());
+
+    mock.push_update(json!({
+        "message": {
+            "message_id": 11,
+            "from": {"id": 42, "is_bot": false, "first_name": "Alice"},
+            "chat": {"id": -100_123, "type": "supergroup"},
+            "date": 0,
+            "caption": "look",
+            "photo": [
+                {"file_id": "small", "width": 90, "height": 60},
+                {"file_id": "large", "width": 1280, "height": 960, "file_size": 10}
+            ]
+        }
+    }));
+
+    let inbound = recv(&mut channel).await;
+    assert_eq!(
+        inbound.content,
+        "[from Alice (user:alice) in group:-100123 msg 11]\nlook\n[attachment: photo.jpg (image/jpeg, 10 bytes)]\n[file saved: ./attachments/11_001_photo.jpg]"
+    );
+
+    let scope =
+        coop_core::WorkspaceScope::for_group_principal(workspace.path(), "telegram:group:-100123");
+    let saved = scope.attachments_dir().unwrap().join("11_001_photo.jpg");
+    assert_eq!(std::fs::read(saved).unwrap(), b"jpeg-bytes");
+}
+
+#[tokio::test]
+async fn untrusted_photos_are_not_downloaded() {
+    let workspace = tempfile::tempdir().unwrap();
+    let mock = MockBotApi::start().await.unwrap();
+    let mut channel = connect(&mock, workspace.path()).await;
+    mock.add_file("p", b"jpeg-bytes".to_vec());
+
+    mock.push_update(json!({
+        "message": {
+            "message_id": 3,
+            "from": {"id": 77, "is_bot": false, "first_name": "Mallory"},
+            "chat": {"id": 77, "type": "private"},
+            "date": 0,
+            "photo": [{"file_id": "p", "width": 10, "height": 10}]
+        }
+    }));
+
+    let inbound = recv(&mut channel).await;
+    assert_eq!(inbound.kind, InboundKind::Attachment);
+    assert_eq!(inbound.content, "[attachment: photo.jpg (image/jpeg)]");
+    assert!(mock.calls().iter().all(|call| call.method != "getFile"));
+}
+
+#[tokio::test]
+async fn sends_photos_as_multipart_uploads() {
+    let workspace = tempfile::tempdir().unwrap();
+    let mock = MockBotApi::start().await.unwrap();
+    let channel = connect(&mock, workspace.path()).await;
+    let path = workspace.path().join("chart.png");
+    std::fs::write(&path, b"png-bytes").unwrap();
+
+    channel
+        .action_sender()
+        .send(TelegramAction::SendPhoto {
+            target: TelegramTarget::Direct(42),
+            path,
+            caption: Some("chart".to_owned()),
+        })
+        .await
+        .unwrap();
+
+    let photos = mock.wait_for_calls("sendPhoto", 1, WAIT).await;
+    assert_eq!(
+        photos[0].params,
+        json!({"chat_id": "42", "photo": 9, "caption": "chart"})
+    );
+}
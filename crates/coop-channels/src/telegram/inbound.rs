@@ -0,0 +1,573 @@
+use chrono::{DateTime, Utc};
+use coop_core::{InboundKind, InboundMessage};
+use std::collections::HashMap;
+
+use super::api::{Chat, Message, MessageReactionUpdated, ReactionType, Update, User};
+
+/// Identity of the bot and the coop users it knows about, used when
+/// formatting inbound envelopes.
+#[derive(Debug, Clone)]
+pub(crate) struct InboundContext {
+    pub bot_id: i64,
+    pub bot_username: String,
+    /// Telegram user id → coop user name from `[[users]]` config.
+    pub user_names: HashMap<String, String>,
+}
+
+/// A photo that should be downloaded and saved before the inbound message is
+/// handed to the gateway.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct PendingPhoto {
+    pub file_id: String,
+    pub file_size: Option<u64>,
+    /// The metadata line in `inbound.content` that describes this photo.
+    pub meta_line: String,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct ParsedUpdate {
+    pub inbound: InboundMessage,
+    pub chat_id: i64,
+    pub photo: Option<PendingPhoto>,
+}
+
+pub(crate) fn inbound_from_update(update: &Update, ctx: &InboundContext) -> Option<ParsedUpdate> {
+    if let Some(message) = update.message.as_ref() {
+        return inbound_from_message(message, false, ctx);
+    }
+    if let Some(message) = update.edited_message.as_ref() {
+        return inbound_from_message(message, true, ctx);
+    }
+    if let Some(reaction) = update.message_reaction.as_ref() {
+        return inbound_from_reaction(reaction, ctx);
+    }
+    None
+}
+
+fn inbound_from_message(
+    message: &Message,
+    edited: bool,
+    ctx: &InboundContext,
+) -> Option<ParsedUpdate> {
+    let from = message.from.as_ref().filter(|from| !from.is_bot)?;
+    let (chat_id, is_group, reply_to) = chat_context(&message.chat, from)?;
+    let sender = from.id.to_string();
+    let message_id = Some(message.id);
+
+    // Slash commands are passed through without an envelope so the router
+    // can match them directly. `/cmd@otherbot` is meant for a different bot
+    // in the same group and is ignored.
+    if !edited
+        && let Some(text) = message.text.as_deref()
+        && text.trim_start().starts_with('/')
+    {
+        let command = normalize_command(text.trim(), &ctx.bot_username)?;
+        return Some(ParsedUpdate {
+            inbound: InboundMessage {
+                channel: "telegram".to_owned(),
+                sender,
+                content: command,
+                chat_id,
+                is_group,
+                timestamp: from_unix_seconds(message.date),
+                reply_to,
+                kind: InboundKind::Command,
+                message_timestamp: None,
+                message_id,
+                group_revision: None,
+            },
+            chat_id: message.chat.id,
+            photo: None,
+        });
+    }
+
+    let body = message.body();
+    let mut lines = Vec::new();
+
+    if let Some(quoted) = message.reply_to.as_deref() {
+        lines.push(format_reply_context(quoted, ctx));
+    }
+    if let Some(body) = body {
+        lines.push(body.to_owned());
+    }
+
+    let photo = message.largest_photo().map(|photo| {
+        let meta_line = format_attachment_metadata("photo.jpg", "image/jpeg", photo.file_size);
+        lines.push(meta_line.clone());
+        PendingPhoto {
+            file_id: photo.file_id.clone(),
+            file_size: photo.file_size,
+            meta_line,
+        }
+    });
+
+    if let Some(document) = message.document.as_ref() {
+        lines.push(format_attachment_metadata(
+            document.file_name.as_deref().unwrap_or("unnamed"),
+            document
+                .mime_type
+                .as_deref()
+                .unwrap_or("application/octet-stream"),
+            document.file_size,
+        ));
+    }
+
+    if lines.is_empty() {
+        return None;
+    }
+
+    let kind = if edited {
+        lines.insert(0, format!("[edited message {}]", message.id));
+        InboundKind::Edit
+    } else if body.is_none() && (photo.is_some() || message.document.is_some()) {
+        InboundKind::Attachment
+    } else {
+        InboundKind::Text
+    };
+
+    let body = lines.join("\n");
+    let content = if is_group {
+        prepend_sender_context(&body, from, chat_id.as_deref(), message.id, ctx)
+    } else {
+        body
+    };
+
+    Some(ParsedUpdate {
+        inbound: InboundMessage {
+            channel: "telegram".to_owned(),
+            sender,
+            content,
+            chat_id,
+            is_group,
+            timestamp: from_unix_seconds(message.date),
+            reply_to,
+            kind,
+            message_timestamp: None,
+            message_id,
+            group_revision: None,
+        },
+        chat_id: message.chat.id,
+        photo,
+    })
+}
+
+fn inbound_from_reaction(
+    reaction: &MessageReactionUpdated,
+    ctx: &InboundContext,
+) -> Option<ParsedUpdate> {
+    let user = reaction.user.as_ref().filter(|user| !user.is_bot)?;
+    let (chat_id, is_group, reply_to) = chat_context(&reaction.chat, user)?;
+
+    let added: Vec<&str> = reaction
+        .new_reaction
+        .iter()
+        .filter(|r| !reaction.old_reaction.contains(r))
+        .filter_map(reaction_emoji)
+        .collect();
+    let removed: Vec<&str> = reaction
+        .old_reaction
+        .iter()
+        .filter(|r| !reaction.new_reaction.contains(r))
+        .filter_map(reaction_emoji)
+        .collect();
+
+    let body = if let Some(emoji) = added.first() {
+        format!("[reacted {emoji} to message {}]", reaction.message_id)
+    } else if let Some(emoji) = removed.first() {
+        format!(
+            "[removed reaction {emoji} from message {}]",
+            reaction.message_id
+        )
+    } else {
+        return None;
+    };
+
+    let content = if is_group {
+        prepend_sender_context(&body, user, chat_id.as_deref(), reaction.message_id, ctx)
+    } else {
+        body
+    };
+
+    Some(ParsedUpdate {
+        inbound: InboundMessage {
+            channel: "telegram".to_owned(),
+            sender: user.id.to_string(),
+            content,
+            chat_id,
+            is_group,
+            timestamp: from_unix_seconds(reaction.date),
+            reply_to,
+            kind: InboundKind::Reaction,
+            message_timestamp: None,
+            message_id: Some(reaction.message_id),
+            group_revision: None,
+        },
+        chat_id: reaction.chat.id,
+        photo: None,
+    })
+}
+
+/// Returns `(chat_id, is_group, reply_to)`, or `None` for chat types coop
+/// does not handle (channels).
+fn chat_context(chat: &Chat, from: &User) -> Option<(Option<String>, bool, Option<String>)> {
+    if chat.is_group() {
+        let chat_id = format!("group:{}", chat.id);
+        Some((Some(chat_id.clone()), true, Some(chat_id)))
+    } else if chat.is_private() {
+        Some((None, false, Some(from.id.to_string())))
+    } else {
+        None
+    }
+}
+
+fn reaction_emoji(reaction: &ReactionType) -> Option<&str> {
+    (reaction.kind == "emoji")
+        .then_some(reaction.emoji.as_deref())
+        .flatten()
+}
+
+/// Strip a `@botname` suffix from the command word. Returns `None` when the
+/// command is addressed to a different bot.
+fn normalize_command(text: &str, bot_username: &str) -> Option<String> {
+    let (word, rest) = match text.split_once(char::is_whitespace) {
+        Some((word, rest)) => (word, Some(rest)),
+        None => (text, None),
+    };
+
+    let word = match word.split_once('@') {
+        Some((command, target)) if target.eq_ignore_ascii_case(bot_username) => command,
+        Some(_) => return None,
+        None => word,
+    };
+
+    Some(match rest {
+        Some(rest) => format!("{word} {}", rest.trim_start()),
+        None => word.to_owned(),
+    })
+}
+
+fn format_reply_context(quoted: &Message, ctx: &InboundContext) -> String {
+    let quoted_text = quoted.body().map_or_else(
+        || "<quoted message>".to_owned(),
+        |text| text.replace('"', "\\\""),
+    );
+    let author = quoted.from.as_ref().map(|from| {
+        if from.id == ctx.bot_id {
+            format!("{} (self)", from.full_name())
+        } else {
+            from.full_name()
+        }
+    });
+
+    match author {
+        Some(name) => format!(
+            "[reply to {name}: \"{quoted_text}\" (message {})]",
+            quoted.id
+        ),
+        None => format!("[reply to \"{quoted_text}\" (message {})]", quoted.id),
+    }
+}
+
+pub(crate) fn format_attachment_metadata(
+    file_name: &str,
+    content_type: &str,
+    size: Option<u64>,
+) -> String {
+    match size {
+        Some(size) => format!("[attachment: {file_name} ({content_type}, {size} bytes)]"),
+        None => format!("[attachment: {file_name} ({content_type})]"),
+    }
+}
+
+fn sender_header(from: &User, ctx: &InboundContext) -> String {
+    let name = from.full_name();
+    match ctx.user_names.get(&from.id.to_string()) {
+        Some(coop_name) => format!("{name} (user:{coop_name})"),
+        None => match from.username.as_deref() {
+            Some(username) => format!("{name} (@{username})"),
+            None => name,
+        },
+    }
+}
+
+fn prepend_sender_context(
+    body: &str,
+    from: &User,
+    chat_id: Option<&str>,
+    message_id: i64,
+    ctx: &InboundContext,
+) -> String {
+    let sender_display = sender_header(from, ctx);
+    let header = match chat_id {
+        Some(chat_id) => format!("[from {sender_display} in {chat_id} msg {message_id}]"),
+        None => format!("[from {sender_display} msg {message_id}]"),
+    };
+
+    if body.is_empty() {
+        header
+    } else {
+        format!("{header}\n{body}")
+    }
+}
+
+fn from_unix_seconds(date: i64) -> DateTime<Utc> {
+    DateTime::from_timestamp(date, 0).unwrap_or_else(Utc::now)
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn context() -> InboundContext {
+        InboundContext {
+            bot_id: 999,
+            bot_username: "coop_bot".to_owned(),
+            user_names: HashMap::from([("42".to_owned(), "alice".to_owned())]),
+        }
+    }
+
+    fn update(value: serde_json::Value) -> Update {
+        serde_json::from_value(value).unwrap()
+    }
+
+    fn alice() -> serde_json::Value {
+        json!({"id": 42, "is_bot": false, "first_name": "Alice", "last_name": "Walker", "username": "alicew"})
+    }
+
+    #[test]
+    fn private_text_maps_to_dm() {
+        let parsed = inbound_from_update(
+            &update(json!({
+                "update_id": 1,
+                "message": {
+                    "message_id": 7,
+                    "from": alice(),
+                    "chat": {"id": 42, "type": "private"},
+                    "date": 1_700_000_000,
+                    "text": "hello"
+                }
+            })),
+            &context(),
+        )
+        .unwrap();
+
+        let inbound = parsed.inbound;
+        assert_eq!(inbound.channel, "telegram");
+        assert_eq!(inbound.sender, "42");
+        assert_eq!(inbound.content, "hello");
+        assert_eq!(inbound.chat_id, None);
+        assert!(!inbound.is_group);
+        assert_eq!(inbound.reply_to.as_deref(), Some("42"));
+        assert_eq!(inbound.kind, InboundKind::Text);
+        assert_eq!(inbound.message_id, Some(7));
+        assert_eq!(inbound.message_timestamp, None);
+        assert_eq!(inbound.timestamp.timestamp(), 1_700_000_000);
+    }
+
+    #[test]
+    fn group_text_gets_sender_envelope() {
+        let parsed = inbound_from_update(
+            &update(json!({
+                "update_id": 2,
+                "message": {
+                    "message_id": 8,
+                    "from": alice(),
+                    "chat": {"id": -100_123, "type": "supergroup", "title": "Friends"},
+                    "date": 1_700_000_000,
+                    "text": "hi all"
+                }
+            })),
+            &context(),
+        )
+        .unwrap();
+
+        let inbound = parsed.inbound;
+        assert!(inbound.is_group);
+        assert_eq!(inbound.chat_id.as_deref(), Some("group:-100123"));
+        assert_eq!(inbound.reply_to.as_deref(), Some("group:-100123"));
+        assert_eq!(
+            inbound.content,
+            "[from Alice Walker (user:alice) in group:-100123 msg 8]\nhi all"
+        );
+        assert_eq!(parsed.chat_id, -100_123);
+    }
+
+    #[test]
+    fn channel_posts_and_bot_messages_are_ignored() {
+        let ctx = context();
+        assert!(
+            inbound_from_update(
+                &update(json!({
+                    "update_id": 3,
+                    "message": {
+                        "message_id": 1,
+                        "from": alice(),
+                        "chat": {"id": -5, "type": "channel"},
+                        "date": 0,
+                        "text": "news"
+                    }
+                })),
+                &ctx,
+            )
+            .is_none()
+        );
+        assert!(
+            inbound_from_update(
+                &update(json!({
+                    "update_id": 4,
+                    "message": {
+                        "message_id": 1,
+                        "from": {"id": 5, "is_bot": true, "first_name": "Other"},
+                        "chat": {"id": 5, "type": "private"},
+                        "date": 0,
+                        "text": "beep"
+                    }
+                })),
+                &ctx,
+            )
+            .is_none()
+        );
+    }
+
+    #[test]
+    fn commands_strip_bot_mention_and_skip_other_bots() {
+        let command = |text: &str| {
+            inbound_from_update(
+                &update(json!({
+                    "update_id": 5,
+                    "message": {
+                        "message_id": 9,
+                        "from": alice(),
+                        "chat": {"id": -7, "type": "group"},
+                        "date": 0,
+                        "text": text
+                    }
+                })),
+                &context(),
+            )
+            .map(|parsed| parsed.inbound)
+        };
+
+        let inbound = command("/status@Coop_Bot verbose").unwrap();
+        assert_eq!(inbound.kind, InboundKind::Command);
+        assert_eq!(inbound.content, "/status verbose");
+        assert_eq!(command("/new").unwrap().content, "/new");
+        assert!(command("/status@other_bot").is_none());
+    }
+
+    #[test]
+    fn photo_with_caption_and_reply_context() {
+        let parsed = inbound_from_update(
+            &update(json!({
+                "update_id": 6,
+                "message": {
+                    "message_id": 11,
+                    "from": alice(),
+                    "chat": {"id": 42, "type": "private"},
+                    "date": 0,
+                    "caption": "look",
+                    "photo": [
+                        {"file_id": "small", "width": 90, "height": 60, "file_size": 100},
+                        {"file_id": "large", "width": 1280, "height": 960, "file_size": 9000}
+                    ],
+                    "reply_to_message": {
+                        "message_id": 10,
+                        "from": {"id": 999, "is_bot": true, "first_name": "Coop"},
+                        "chat": {"id": 42, "type": "private"},
+                        "date": 0,
+                        "text": "send me a \"pic\""
+                    }
+                }
+            })),
+            &context(),
+        )
+        .unwrap();
+
+        assert_eq!(parsed.inbound.kind, InboundKind::Text);
+        assert_eq!(
+            parsed.inbound.content,
+            "[reply to Coop (self): \"send me a \\\"pic\\\"\" (message 10)]\nlook\n[attachment: photo.jpg (image/jpeg, 9000 bytes)]"
+        );
+        let photo = parsed.photo.unwrap();
+        assert_eq!(photo.file_id, "large");
+        assert_eq!(
+            photo.meta_line,
+            "[attachment: photo.jpg (image/jpeg, 9000 bytes)]"
+        );
+    }
+
+    #[test]
+    fn photo_without_caption_is_attachment_kind() {
+        let parsed = inbound_from_update(
+            &update(json!({
+                "update_id": 7,
+                "message": {
+                    "message_id": 12,
+                    "from": alice(),
+                    "chat": {"id": 42, "type": "private"},
+                    "date": 0,
+                    "photo": [{"file_id": "only", "width": 10, "height": 10}]
+                }
+            })),
+            &context(),
+        )
+        .unwrap();
+        assert_eq!(parsed.inbound.kind, InboundKind::Attachment);
+        assert_eq!(
+            parsed.inbound.content,
+            "[attachment: photo.jpg (image/jpeg)]"
+        );
+    }
+
+    #[test]
+    fn edited_messages_are_marked() {
+        let parsed = inbound_from_update(
+            &update(json!({
+                "update_id": 8,
+                "edited_message": {
+                    "message_id": 13,
+                    "from": alice(),
+                    "chat": {"id": 42, "type": "private"},
+                    "date": 0,
+                    "text": "/fixed typo"
+                }
+            })),
+            &context(),
+        )
+        .unwrap();
+        assert_eq!(parsed.inbound.kind, InboundKind::Edit);
+        assert_eq!(parsed.inbound.content, "[edited message 13]\n/fixed typo");
+    }
+
+    #[test]
+    fn reactions_report_added_and_removed_emoji() {
+        let reaction = |old: serde_json::Value, new: serde_json::Value| {
+            inbound_from_update(
+                &update(json!({
+                    "update_id": 9,
+                    "message_reaction": {
+                        "chat": {"id": 42, "type": "private"},
+                        "message_id": 20,
+                        "user": alice(),
+                        "date": 0,
+                        "old_reaction": old,
+                        "new_reaction": new
+                    }
+                })),
+                &context(),
+            )
+            .map(|parsed| parsed.inbound)
+        };
+
+        let added = reaction(json!([]), json!([{"type": "emoji", "emoji": "👍"}])).unwrap();
+        assert_eq!(added.kind, InboundKind::Reaction);
+        assert_eq!(added.content, "[reacted 👍 to message 20]");
+
+        let removed = reaction(json!([{"type": "emoji", "emoji": "👍"}]), json!([])).unwrap();
+        assert_eq!(removed.content, "[removed reaction 👍 from message 20]");
+
+        assert!(reaction(json!([]), json!([{"type": "custom_emoji"}])).is_none());
+    }
+}
@@ -0,0 +1,372 @@
+#![allow(clippy::unwrap_used)]
+
+//! A local stand-in for the Telegram Bot API, for exercising
+//! [`TelegramChannel`](super::TelegramChannel) end to end without network
+//! access.
+
+use anyhow::{Context, Result};
+use serde_json::{Map, Value, json};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::Notify;
+
+/// The identity returned by the mock's `getMe`.
+pub const MOCK_BOT_ID: i64 = 999;
+pub const MOCK_BOT_USERNAME: &str = "coop_bot";
+
+/// A Bot API method call recorded by [`MockBotApi`]. Multipart uploads are
+/// flattened into `params`; file parts are recorded as their size in bytes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MockBotCall {
+    pub method: String,
+    pub params: Value,
+}
+
+#[derive(Debug, Default)]
+struct MockState {
+    updates: Mutex<Vec<Value>>,
+    next_update_id: Mutex<i64>,
+    next_message_id: Mutex<i64>,
+    calls: Mutex<Vec<MockBotCall>>,
+    files: Mutex<HashMap<String, Vec<u8>>>,
+    failures: Mutex<Vec<(String, &'static str, Value)>>,
+    updates_changed: Notify,
+    calls_changed: Notify,
+}
+
+/// Mock Telegram Bot API server bound to `127.0.0.1`.
+///
+/// Queue updates with [`push_update`](Self::push_update) and inspect the
+/// bot's outbound calls with [`calls`](Self::calls). `getUpdates` calls are
+/// served but not recorded.
+#[derive(Debug, Clone)]
+pub struct MockBotApi {
+    base_url: String,
+    state: Arc<MockState>,
+}
+
+impl MockBotApi {
+    pub async fn start() -> Result<Self> {
+        let listener = TcpListener::bind("127.0.0.1:0")
+            .await
+            .context("failed to bind mock bot api")?;
+        let base_url = format!("http://{}", listener.local_addr()?);
+        let state = Arc::new(MockState::default());
+
+        let server_state = Arc::clone(&state);
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                let state = Arc::clone(&server_state);
+                tokio::spawn(async move {
+                    let _ = serve_connection(stream, &state).await;
+                });
+            }
+        });
+
+        Ok(Self { base_url, state })
+    }
+
+    /// Base URL to use as `TelegramSettings::api_base_url`.
+    pub fn api_base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// Queue an update for the next `getUpdates`. An `update_id` is assigned
+    /// when the update does not carry one.
+    pub fn push_update(&self, mut update: Value) {
+        {
+            let mut next_id = self.state.next_update_id.lock().unwrap();
+            if let Some(id) = update.get("update_id").and_then(Value::as_i64) {
+                *next_id = (*next_id).max(id + 1);
+            } else {
+                *next_id += 1;
+                update["update_id"] = json!(*next_id);
+            }
+        }
+        self.state.updates.lock().unwrap().push(update);
+        self.state.updates_changed.notify_waiters();
+    }
+
+    /// Make a file downloadable via `getFile` under `file_id`.
+    pub fn add_file(&self, file_id: &str, bytes: Vec<u8>) {
+        self.state
+            .files
+            .lock()
+            .unwrap()
+            .insert(file_id.to_owned(), bytes);
+    }
+
+    /// Answer the next call to `method` with `429 Too Many Requests` and a
+    /// zero-second `retry_after`.
+    pub fn rate_limit_next(&self, method: &str) {
+        self.push_failure(
+            method,
+            "429 Too Many Requests",
+            json!({
+                "ok": false,
+                "error_code": 429,
+                "description": "Too Many Requests: retry after 0",
+                "parameters": {"retry_after": 0},
+            }),
+        );
+    }
+
+    /// Answer the next call to `method` with `401 Unauthorized`, as the Bot
+    /// API does for a revoked or mistyped token.
+    pub fn reject_next(&self, method: &str) {
+        self.push_failure(
+            method,
+            "401 Unauthorized",
+            json!({"ok": false, "error_code": 401, "description": "Unauthorized"}),
+        );
+    }
+
+    fn push_failure(&self, method: &str, status: &'static str, body: Value) {
+        self.state
+            .failures
+            .lock()
+            .unwrap()
+            .push((method.to_owned(), status, body));
+    }
+
+    pub fn calls(&self) -> Vec<MockBotCall> {
+        self.state.calls.lock().unwrap().clone()
+    }
+
+    /// Wait until `count` calls to `method` have been recorded and return
+    /// them, or whatever has been recorded when `timeout` elapses.
+    pub async fn wait_for_calls(
+        &self,
+        method: &str,
+        count: usize,
+        timeout: Duration,
+    ) -> Vec<MockBotCall> {
+        let matching = || -> Vec<MockBotCall> {
+            self.calls()
+                .into_iter()
+                .filter(|call| call.method == method)
+                .collect()
+        };
+
+        let wait = async {
+            loop {
+                let notified = self.state.calls_changed.notified();
+                let calls = matching();
+                if calls.len() >= count {
+                    return calls;
+                }
+                notified.await;
+            }
+        };
+        tokio::time::timeout(timeout, wait)
+            .await
+            .unwrap_or_else(|_| matching())
+    }
+}
+
+async fn serve_connection(stream: TcpStream, state: &MockState) -> Result<()> {
+    let mut reader = BufReader::new(stream);
+
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line).await?;
+    let mut parts = request_line.split_whitespace();
+    let http_method = parts.next().unwrap_or_default().to_owned();
+    let path = parts.next().unwrap_or_default().to_owned();
+
+    let mut content_length = 0usize;
+    let mut content_type = String::new();
+    loop {
+        let mut line = String::new();
+        reader.read_line(&mut line).await?;
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            let value = value.trim();
+            if name.eq_ignore_ascii_case("content-length") {
+                content_length = value.parse().unwrap_or(0);
+            } else if name.eq_ignore_ascii_case("content-type") {
+                value.clone_into(&mut content_type);
+            }
+        }
+    }
+
+    let mut body = vec![0u8; content_length];
+    reader.read_exact(&mut body).await?;
+
+    let (status, response_body, response_type) = if http_method == "GET" {
+        serve_file(&path, state)
+    } else {
+        let method = path.rsplit('/').next().unwrap_or_default().to_owned();
+        let params = parse_params(&content_type, &body);
+        let (status, value) = handle_method(&method, params, state).await;
+        (status, value.to_string().into_bytes(), "application/json")
+    };
+
+    let mut stream = reader.into_inner();
+    let head = format!(
+        "HTTP/1.1 {status}\r\ncontent-type: {response_type}\r\ncontent-length: {}\r\nconnection: close\r\n\r\n",
+        response_body.len()
+    );
+    stream.write_all(head.as_bytes()).await?;
+    stream.write_all(&response_body).await?;
+    stream.shutdown().await?;
+    Ok(())
+}
+
+fn serve_file(path: &str, state: &MockState) -> (&'static str, Vec<u8>, &'static str) {
+    let file_id = path
+        .rsplit('/')
+        .next()
+        .and_then(|name| name.strip_suffix(".jpg"))
+        .unwrap_or_default();
+    match state.files.lock().unwrap().get(file_id) {
+        Some(bytes) => ("200 OK", bytes.clone(), "application/octet-stream"),
+        None => ("404 Not Found", Vec::new(), "text/plain"),
+    }
+}
+
+async fn handle_method(method: &str, params: Value, state: &MockState) -> (&'static str, Value) {
+    {
+        let mut failures = state.failures.lock().unwrap();
+        if let Some(index) = failures.iter().position(|(m, _, _)| m == method) {
+            let (_, status, body) = failures.remove(index);
+            drop(failures);
+            return (status, body);
+        }
+    }
+
+    if method == "getUpdates" {
+        return ("200 OK", ok(&get_updates(&params, state).await));
+    }
+
+    state.calls.lock().unwrap().push(MockBotCall {
+        method: method.to_owned(),
+        params: params.clone(),
+    });
+    state.calls_changed.notify_waiters();
+
+    match method {
+        "getMe" => (
+            "200 OK",
+            ok(&json!({
+                "id": MOCK_BOT_ID,
+                "is_bot": true,
+                "first_name": "Coop",
+                "username": MOCK_BOT_USERNAME,
+            })),
+        ),
+        "getFile" => {
+            let file_id = params
+                .get("file_id")
+                .and_then(Value::as_str)
+                .unwrap_or_default();
+            if state.files.lock().unwrap().contains_key(file_id) {
+                (
+                    "200 OK",
+                    ok(&json!({"file_id": file_id, "file_path": format!("photos/{file_id}.jpg")})),
+                )
+            } else {
+                (
+                    "400 Bad Request",
+                    json!({"ok": false, "error_code": 400, "description": "Bad Request: invalid file_id"}),
+                )
+            }
+        }
+        "sendMessage" | "sendPhoto" => {
+            let message_id = {
+                let mut next = state.next_message_id.lock().unwrap();
+                *next += 1;
+                *next
+            };
+            let chat_id = params
+                .get("chat_id")
+                .and_then(|id| id.as_i64().or_else(|| id.as_str()?.parse().ok()))
+                .unwrap_or_default();
+            (
+                "200 OK",
+                ok(&json!({
+                    "message_id": message_id,
+                    "chat": {"id": chat_id, "type": if chat_id < 0 { "supergroup" } else { "private" }},
+                    "date": 0,
+                })),
+            )
+        }
+        _ => ("200 OK", ok(&json!(true))),
+    }
+}
+
+async fn get_updates(params: &Value, state: &MockState) -> Value {
+    let offset = params.get("offset").and_then(Value::as_i64).unwrap_or(0);
+    let timeout = params.get("timeout").and_then(Value::as_u64).unwrap_or(0);
+
+    let pending = || -> Vec<Value> {
+        let mut updates = state.updates.lock().unwrap();
+        updates.retain(|u| u.get("update_id").and_then(Value::as_i64).unwrap_or(0) >= offset);
+        updates.clone()
+    };
+
+    let wait = async {
+        loop {
+            let notified = state.updates_changed.notified();
+            let updates = pending();
+            if !updates.is_empty() {
+                return updates;
+            }
+            notified.await;
+        }
+    };
+    let updates = tokio::time::timeout(Duration::from_secs(timeout), wait)
+        .await
+        .unwrap_or_default();
+    Value::Array(updates)
+}
+
+fn ok(result: &Value) -> Value {
+    json!({"ok": true, "result": result})
+}
+
+fn parse_params(content_type: &str, body: &[u8]) -> Value {
+    if let Some(boundary) = content_type
+        .split(';')
+        .find_map(|part| part.trim().strip_prefix("boundary="))
+    {
+        return parse_multipart(body, boundary.trim_matches('"'));
+    }
+    serde_json::from_slice(body).unwrap_or(Value::Null)
+}
+
+/// Flatten a `multipart/form-data` body: text fields become strings, file
+/// fields become their size in bytes.
+fn parse_multipart(body: &[u8], boundary: &str) -> Value {
+    let delimiter = format!("--{boundary}");
+    let body = String::from_utf8_lossy(body);
+    let mut fields = Map::new();
+
+    for part in body.split(delimiter.as_str()) {
+        let Some((headers, content)) = part.split_once("\r\n\r\n") else {
+            continue;
+        };
+        let Some(name) = headers
+            .split(';')
+            .find_map(|h| h.trim().strip_prefix("name="))
+            .map(|name| name.trim_matches('"').to_owned())
+        else {
+            continue;
+        };
+        let content = content.strip_suffix("\r\n").unwrap_or(content);
+        let value = if headers.contains("filename=") {
+            json!(content.len())
+        } else {
+            json!(content)
+        };
+        fields.insert(name, value);
+    }
+
+    Value::Object(fields)
+}
@@ -0,0 +1,555 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use coop_core::{Tool, ToolContext, ToolDef, ToolExecutor, ToolOutput};
+use serde::Deserialize;
+use tokio::sync::mpsc;
+use tracing::{Instrument, debug, info_span};
+
+use crate::telegram::{TelegramAction, TelegramTarget};
+
+/// Extract a `TelegramTarget` from a session ID like `agent:dm:telegram:42`
+/// or `agent:group:telegram:group:-100123`.
+fn extract_telegram_target_from_session(session_id: &str) -> Option<TelegramTarget> {
+    if let Some((_, rest)) = session_id.split_once(":dm:telegram:") {
+        return TelegramTarget::parse(rest).ok();
+    }
+    if let Some((_, rest)) = session_id.split_once(":group:telegram:") {
+        return TelegramTarget::parse(rest).ok();
+    }
+    None
+}
+
+fn session_target(ctx: &ToolContext, tool_name: &str) -> Result<TelegramTarget> {
+    extract_telegram_target_from_session(&ctx.session_id)
+        .ok_or_else(|| anyhow::anyhow!("{tool_name} is only available in Telegram chat sessions"))
+}
+
+async fn dispatch(action_tx: &mpsc::Sender<TelegramAction>, action: TelegramAction) -> Result<()> {
+    action_tx
+        .send(action)
+        .await
+        .map_err(|_send_err| anyhow::anyhow!("telegram action channel closed"))
+}
+
+#[derive(Debug)]
+pub struct TelegramReactTool {
+    action_tx: mpsc::Sender<TelegramAction>,
+}
+
+impl TelegramReactTool {
+    pub fn new(action_tx: mpsc::Sender<TelegramAction>) -> Self {
+        Self { action_tx }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ReactArgs {
+    emoji: String,
+    #[serde(default)]
+    message_id: Option<i64>,
+    #[serde(default)]
+    remove: bool,
+}
+
+#[async_trait]
+impl Tool for TelegramReactTool {
+    fn definition(&self) -> ToolDef {
+        ToolDef::new(
+            "telegram_react",
+            "React to a message in the current Telegram conversation with an emoji. Defaults to the most recent incoming message.",
+            serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "emoji": {
+                        "type": "string",
+                        "description": "Emoji to react with (must be one Telegram allows as a reaction)"
+                    },
+                    "message_id": {
+                        "type": "integer",
+                        "description": "Message to react to, e.g. the `msg` number from a group envelope"
+                    },
+                    "remove": {
+                        "type": "boolean",
+                        "description": "Remove the reaction instead of adding"
+                    }
+                },
+                "required": ["emoji"]
+            }),
+        )
+    }
+
+    async fn execute(&self, arguments: serde_json::Value, ctx: &ToolContext) -> Result<ToolOutput> {
+        let span = info_span!("telegram_tool_react");
+        async {
+            let args: ReactArgs = serde_json::from_value(arguments)?;
+            let target = session_target(ctx, "telegram_react")?;
+
+            debug!(
+                tool.name = "telegram_react",
+                telegram.target = %target,
+                telegram.emoji = %args.emoji.as_str(),
+                telegram.message_id = ?args.message_id,
+                telegram.remove = args.remove,
+                "telegram tool action queued"
+            );
+
+            dispatch(
+                &self.action_tx,
+                TelegramAction::React {
+                    target,
+                    message_id: args.message_id,
+                    emoji: args.emoji,
+                    remove: args.remove,
+                },
+            )
+            .await?;
+
+            Ok(ToolOutput::success("reaction sent"))
+        }
+        .instrument(span)
+        .await
+    }
+}
+
+#[derive(Debug)]
+pub struct TelegramReplyTool {
+    action_tx: mpsc::Sender<TelegramAction>,
+}
+
+impl TelegramReplyTool {
+    pub fn new(action_tx: mpsc::Sender<TelegramAction>) -> Self {
+        Self { action_tx }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ReplyArgs {
+    text: String,
+    #[serde(default)]
+    message_id: Option<i64>,
+}
+
+#[async_trait]
+impl Tool for TelegramReplyTool {
+    fn definition(&self) -> ToolDef {
+        ToolDef::new(
+            "telegram_reply",
+            "Reply to a specific message in the current Telegram conversation (shows as a quote). Defaults to the most recent incoming message.",
+            serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "text": {
+                        "type": "string",
+                        "description": "Reply text"
+                    },
+                    "message_id": {
+                        "type": "integer",
+                        "description": "Message to reply to, e.g. the `msg` number from a group envelope"
+                    }
+                },
+                "required": ["text"]
+            }),
+        )
+    }
+
+    async fn execute(&self, arguments: serde_json::Value, ctx: &ToolContext) -> Result<ToolOutput> {
+        let span = info_span!("telegram_tool_reply");
+        async {
+            let args: ReplyArgs = serde_json::from_value(arguments)?;
+            let target = session_target(ctx, "telegram_reply")?;
+
+            debug!(
+                tool.name = "telegram_reply",
+                telegram.target = %target,
+                telegram.raw_content = %args.text.as_str(),
+                telegram.reply_to_message_id = ?args.message_id,
+                "telegram tool action queued"
+            );
+
+            dispatch(
+                &self.action_tx,
+                TelegramAction::Reply {
+                    target,
+                    text: args.text,
+                    reply_to_message_id: args.message_id,
+                },
+            )
+            .await?;
+
+            Ok(ToolOutput::success("reply sent"))
+        }
+        .instrument(span)
+        .await
+    }
+}
+
+#[derive(Debug)]
+pub struct TelegramSendTool {
+    action_tx: mpsc::Sender<TelegramAction>,
+}
+
+impl TelegramSendTool {
+    pub fn new(action_tx: mpsc::Sender<TelegramAction>) -> Self {
+        Self { action_tx }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct SendArgs {
+    text: String,
+}
+
+#[async_trait]
+impl Tool for TelegramSendTool {
+    fn definition(&self) -> ToolDef {
+        ToolDef::new(
+            "telegram_send",
+            "Send a message to the current Telegram conversation immediately, mid-turn. Use this to notify the user before a long-running task. Your final turn reply is still delivered separately.",
+            serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "text": {
+                        "type": "string",
+                        "description": "Message text to send"
+                    }
+                },
+                "required": ["text"]
+            }),
+        )
+    }
+
+    async fn execute(&self, arguments: serde_json::Value, ctx: &ToolContext) -> Result<ToolOutput> {
+        let span = info_span!("telegram_tool_send");
+        async {
+            let args: SendArgs = serde_json::from_value(arguments)?;
+            let target = session_target(ctx, "telegram_send")?;
+
+            debug!(
+                tool.name = "telegram_send",
+                telegram.target = %target,
+                telegram.raw_content = %args.text.as_str(),
+                "telegram tool send queued"
+            );
+
+            dispatch(
+                &self.action_tx,
+                TelegramAction::SendText(coop_core::OutboundMessage {
+                    channel: "telegram".to_owned(),
+                    target: target.to_string(),
+                    content: args.text,
+                }),
+            )
+            .await?;
+
+            Ok(ToolOutput::success("message sent"))
+        }
+        .instrument(span)
+        .await
+    }
+}
+
+#[derive(Debug)]
+pub struct TelegramSendImageTool {
+    action_tx: mpsc::Sender<TelegramAction>,
+}
+
+impl TelegramSendImageTool {
+    pub fn new(action_tx: mpsc::Sender<TelegramAction>) -> Self {
+        Self { action_tx }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct SendImageArgs {
+    path: String,
+    #[serde(default)]
+    caption: Option<String>,
+}
+
+#[async_trait]
+impl Tool for TelegramSendImageTool {
+    fn definition(&self) -> ToolDef {
+        ToolDef::new(
+            "telegram_send_image",
+            "Send an image file as a Telegram photo to the current conversation. Supports JPEG, PNG, GIF, and WEBP. The file must exist on disk.",
+            serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "path": {
+                        "type": "string",
+                        "description": "Path to the image file relative to the current workspace scope"
+                    },
+                    "caption": {
+                        "type": "string",
+                        "description": "Optional text caption to include with the image"
+                    }
+                },
+                "required": ["path"]
+            }),
+        )
+    }
+
+    async fn execute(&self, arguments: serde_json::Value, ctx: &ToolContext) -> Result<ToolOutput> {
+        let span = info_span!("telegram_tool_send_image");
+        async {
+            let args: SendImageArgs = serde_json::from_value(arguments)?;
+            let target = session_target(ctx, "telegram_send_image")?;
+
+            let file_path = match ctx.workspace_scope.resolve_user_path_for_write(&args.path) {
+                Ok(path) => path,
+                Err(error) => return Ok(ToolOutput::error(error.to_string())),
+            };
+
+            let file_bytes = match std::fs::read(&file_path) {
+                Ok(bytes) => bytes,
+                Err(error) if error.kind() == std::io::ErrorKind::NotFound => {
+                    return Ok(ToolOutput::error(format!("file not found: {}", args.path)));
+                }
+                Err(error) => return Err(error.into()),
+            };
+            let Some(mime_type) = coop_core::validate_image_magic(&file_bytes) else {
+                return Ok(ToolOutput::error(format!(
+                    "file content is not a recognized image format: {}",
+                    file_path.display()
+                )));
+            };
+
+            debug!(
+                tool.name = "telegram_send_image",
+                telegram.target = %target,
+                telegram.attachment_path = %args.path,
+                telegram.attachment_mime = %mime_type,
+                telegram.caption = ?args.caption,
+                "telegram tool send_image queued"
+            );
+
+            dispatch(
+                &self.action_tx,
+                TelegramAction::SendPhoto {
+                    target,
+                    path: file_path,
+                    caption: args.caption,
+                },
+            )
+            .await?;
+
+            Ok(ToolOutput::success("image sent"))
+        }
+        .instrument(span)
+        .await
+    }
+}
+
+#[allow(missing_debug_implementations)]
+pub struct TelegramToolExecutor {
+    tools: Vec<Box<dyn Tool>>,
+}
+
+impl TelegramToolExecutor {
+    pub fn new(action_tx: mpsc::Sender<TelegramAction>) -> Self {
+        Self {
+            tools: vec![
+                Box::new(TelegramReactTool::new(action_tx.clone())),
+                Box::new(TelegramReplyTool::new(action_tx.clone())),
+                Box::new(TelegramSendTool::new(action_tx.clone())),
+                Box::new(TelegramSendImageTool::new(action_tx)),
+            ],
+        }
+    }
+}
+
+#[async_trait]
+impl ToolExecutor for TelegramToolExecutor {
+    async fn execute(
+        &self,
+        name: &str,
+        arguments: serde_json::Value,
+        ctx: &ToolContext,
+    ) -> Result<ToolOutput> {
+        for tool in &self.tools {
+            if tool.definition().name == name {
+                return tool.execute(arguments, ctx).await;
+            }
+        }
+        Ok(ToolOutput::error(format!("unknown tool: {name}")))
+    }
+
+    fn tools(&self) -> Vec<ToolDef> {
+        self.tools.iter().map(|tool| tool.definition()).collect()
+    }
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use coop_core::{SessionKind, TrustLevel};
+    use std::path::PathBuf;
+
+    fn context(session_id: &str, workspace: PathBuf) -> ToolContext {
+        ToolContext::new(
+            session_id,
+            SessionKind::Main,
+            TrustLevel::Full,
+            workspace,
+            None,
+        )
+    }
+
+    #[test]
+    fn extracts_targets_from_session_ids() {
+        assert_eq!(
+            extract_telegram_target_from_session("coop:dm:telegram:42"),
+            Some(TelegramTarget::Direct(42))
+        );
+        assert_eq!(
+            extract_telegram_target_from_session("coop:group:telegram:group:-100123"),
+            Some(TelegramTarget::Group(-100_123))
+        );
+        assert_eq!(
+            extract_telegram_target_from_session("coop:dm:signal:alice-uuid"),
+            None
+        );
+        assert_eq!(extract_telegram_target_from_session("coop:main"), None);
+    }
+
+    #[tokio::test]
+    async fn react_tool_targets_current_chat() {
+        let (tx, mut rx) = mpsc::channel(1);
+        let tool = TelegramReactTool::new(tx);
+
+        let result = tool
+            .execute(
+                serde_json::json!({"emoji": "🔥", "message_id": 9}),
+                &context("coop:group:telegram:group:-5", PathBuf::from(".")),
+            )
+            .await
+            .unwrap();
+        assert_eq!(result.content, "reaction sent");
+
+        match rx.recv().await.unwrap() {
+            TelegramAction::React {
+                target,
+                message_id,
+                emoji,
+                remove,
+            } => {
+                assert_eq!(target, TelegramTarget::Group(-5));
+                assert_eq!(message_id, Some(9));
+                assert_eq!(emoji, "🔥");
+                assert!(!remove);
+            }
+            other => panic!("expected react action, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn reply_tool_defaults_to_latest_message() {
+        let (tx, mut rx) = mpsc::channel(1);
+        let tool = TelegramReplyTool::new(tx);
+
+        tool.execute(
+            serde_json::json!({"text": "on it"}),
+            &context("coop:dm:telegram:42", PathBuf::from(".")),
+        )
+        .await
+        .unwrap();
+
+        match rx.recv().await.unwrap() {
+            TelegramAction::Reply {
+                target,
+                text,
+                reply_to_message_id,
+            } => {
+                assert_eq!(target, TelegramTarget::Direct(42));
+                assert_eq!(text, "on it");
+                assert_eq!(reply_to_message_id, None);
+            }
+            other => panic!("expected reply action, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn send_tool_rejects_non_telegram_sessions() {
+        let (tx, _rx) = mpsc::channel(1);
+        let tool = TelegramSendTool::new(tx);
+
+        let error = tool
+            .execute(
+                serde_json::json!({"text": "hi"}),
+                &context("coop:dm:signal:alice-uuid", PathBuf::from(".")),
+            )
+            .await
+            .unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains("only available in Telegram chat sessions")
+        );
+    }
+
+    #[tokio::test]
+    async fn send_tool_formats_group_target() {
+        let (tx, mut rx) = mpsc::channel(1);
+        let tool = TelegramSendTool::new(tx);
+
+        tool.execute(
+            serde_json::json!({"text": "heads up"}),
+            &context("coop:group:telegram:group:-100123", PathBuf::from(".")),
+        )
+        .await
+        .unwrap();
+
+        match rx.recv().await.unwrap() {
+            TelegramAction::SendText(outbound) => {
+                assert_eq!(outbound.channel, "telegram");
+                assert_eq!(outbound.target, "group:-100123");
+                assert_eq!(outbound.content, "heads up");
+            }
+            other => panic!("expected send action, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn send_image_tool_validates_image_content() {
+        let workspace = tempfile::tempdir().unwrap();
+        std::fs::write(workspace.path().join("notes.txt"), b"not an image").unwrap();
+        std::fs::write(
+            workspace.path().join("pixel.png"),
+            [0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A, 0, 0, 0, 0],
+        )
+        .unwrap();
+
+        let (tx, mut rx) = mpsc::channel(1);
+        let tool = TelegramSendImageTool::new(tx);
+        let ctx = context("coop:dm:telegram:42", workspace.path().to_path_buf());
+
+        let rejected = tool
+            .execute(serde_json::json!({"path": "notes.txt"}), &ctx)
+            .await
+            .unwrap();
+        assert!(rejected.is_error);
+
+        let sent = tool
+            .execute(
+                serde_json::json!({"path": "pixel.png", "caption": "pixel"}),
+                &ctx,
+            )
+            .await
+            .unwrap();
+        assert_eq!(sent.content, "image sent");
+
+        match rx.recv().await.unwrap() {
+            TelegramAction::SendPhoto {
+                target,
+                path,
+                caption,
+            } => {
+                assert_eq!(target, TelegramTarget::Direct(42));
+                assert!(path.ends_with("pixel.png"));
+                assert_eq!(caption.as_deref(), Some("pixel"));
+            }
+            other => panic!("expected photo action, got {other:?}"),
+        }
+    }
+}
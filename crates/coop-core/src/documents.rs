@@ -0,0 +1,369 @@
+//! Auto-detect PDF paths in message text and attach them for the model,
+//! either as `Content::Document` blocks for providers that read PDFs
+//! natively or as extracted text for everyone else.
+
+use anyhow::{Context, Result, bail};
+use base64::Engine as _;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+use tokio::process::Command;
+
+use crate::images::{extract_candidate_paths, recent_user_messages_start};
+use crate::{Content, Message, Role, WorkspaceScope};
+
+/// Document extensions we recognize (lowercase, without dot).
+const DOCUMENT_EXTENSIONS: &[&str] = &["pdf"];
+
+/// Largest PDF we attach natively (Anthropic's request limit).
+const MAX_DOCUMENT_FILE_SIZE: u64 = 32 * 1024 * 1024;
+
+/// Extracted text beyond this many characters is truncated.
+const MAX_EXTRACTED_CHARS: usize = 100_000;
+
+/// Extracted text is cached next to the PDF as `<file>.txt`.
+const EXTRACTED_TEXT_SUFFIX: &str = ".txt";
+
+/// `pdftotext` runs longer than this are killed.
+const EXTRACTION_TIMEOUT: Duration = Duration::from_mins(1);
+
+/// How documents reach the model.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DocumentInput {
+    /// Attach the PDF itself as a `Content::Document` block.
+    Native,
+    /// Attach the text extracted with `pdftotext`.
+    Text,
+}
+
+/// Scan text for local file paths ending in a recognized document extension.
+///
+/// Uses the same rules as [`crate::images::detect_image_paths`].
+pub fn detect_document_paths(text: &str) -> Vec<String> {
+    let mut seen = HashSet::new();
+    extract_candidate_paths(text, DOCUMENT_EXTENSIONS)
+        .into_iter()
+        .filter(|path| seen.insert(path.clone()))
+        .collect()
+}
+
+/// Check file header bytes for the PDF signature.
+pub fn is_pdf(bytes: &[u8]) -> bool {
+    bytes.starts_with(b"%PDF-")
+}
+
+/// Document blocks loaded during one turn. The provider context is rebuilt
+/// on every iteration of the tool loop; this keeps each PDF from being read,
+/// encoded or extracted again each time.
+#[derive(Debug, Default)]
+pub struct DocumentCache {
+    /// `None` records a path that failed to load.
+    blocks: Mutex<HashMap<(String, DocumentInput), Option<Content>>>,
+}
+
+impl DocumentCache {
+    async fn block(
+        &self,
+        path: &str,
+        scope: &WorkspaceScope,
+        input: DocumentInput,
+    ) -> Option<Content> {
+        let key = (path.to_owned(), input);
+        if let Some(block) = self
+            .blocks
+            .lock()
+            .expect("document cache mutex poisoned")
+            .get(&key)
+        {
+            return block.clone();
+        }
+
+        let block = match input {
+            DocumentInput::Native => load_document(path, scope).await,
+            DocumentInput::Text => extracted_text_block(path, scope).await,
+        };
+        let block = block
+            .inspect_err(|error| {
+                tracing::trace!(path = %path, error = %error, "skipping document injection");
+            })
+            .ok();
+        self.blocks
+            .lock()
+            .expect("document cache mutex poisoned")
+            .insert(key, block.clone());
+        block
+    }
+}
+
+/// Read a PDF from the current workspace scope and return it as a
+/// `Content::Document` block named after the file.
+pub async fn load_document(path: &str, scope: &WorkspaceScope) -> Result<Content> {
+    let resolved = scope.resolve_host_path_for_read(path)?;
+
+    let metadata = tokio::fs::metadata(&resolved).await?;
+    if metadata.len() > MAX_DOCUMENT_FILE_SIZE {
+        bail!(
+            "document too large ({} bytes, max {MAX_DOCUMENT_FILE_SIZE}): {path}",
+            metadata.len()
+        );
+    }
+
+    let bytes = tokio::fs::read(&resolved).await?;
+    if !is_pdf(&bytes) {
+        bail!("file content is not a PDF: {path}");
+    }
+    let data = base64::engine::general_purpose::STANDARD.encode(&bytes);
+
+    Ok(Content::document(
+        data,
+        "application/pdf",
+        file_name(&resolved),
+    ))
+}
+
+/// Extract the text of a PDF with `pdftotext`, caching the result next to
+/// the file so later turns don't run it again.
+pub async fn extract_document_text(path: &str, scope: &WorkspaceScope) -> Result<String> {
+    let resolved = scope.resolve_host_path_for_read(path)?;
+    let cache = cache_path(&resolved);
+    if let Ok(cached) = std::fs::read_to_string(&cache) {
+        return Ok(cached);
+    }
+
+    let mut header = [0_u8; 5];
+    std::io::Read::read_exact(&mut std::fs::File::open(&resolved)?, &mut header)
+        .with_context(|| format!("failed to read {path}"))?;
+    if !is_pdf(&header) {
+        bail!("file content is not a PDF: {path}");
+    }
+
+    let output = tokio::time::timeout(
+        EXTRACTION_TIMEOUT,
+        Command::new("pdftotext")
+            .args(["-layout", "-enc", "UTF-8"])
+            .arg(&resolved)
+            .arg("-")
+            .kill_on_drop(true)
+            .output(),
+    )
+    .await
+    .map_err(|_elapsed| anyhow::anyhow!("pdftotext timed out"))?
+    .context("failed to run pdftotext (install poppler-utils)")?;
+    if !output.status.success() {
+        bail!(
+            "pdftotext exited with {}: {}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    let text = truncate_chars(String::from_utf8_lossy(&output.stdout).trim());
+    if let Err(error) = std::fs::write(&cache, &text) {
+        tracing::warn!(path = %cache.display(), error = %error, "failed to cache document text");
+    }
+    Ok(text)
+}
+
+/// Attach documents referenced in the last 4 user messages of a cloned
+/// message list, natively or as extracted text depending on `input`.
+///
+/// Only `Content::Text` blocks are scanned so tool output listing PDFs does
+/// not pull whole files into the context. The original session messages are
+/// not mutated. Loaded blocks are kept in `cache` for the rest of the turn.
+pub async fn inject_documents_for_provider(
+    messages: &[Message],
+    scope: &WorkspaceScope,
+    input: DocumentInput,
+    cache: &DocumentCache,
+) -> Vec<Message> {
+    let mut cloned: Vec<Message> = messages.to_vec();
+    let start_from = recent_user_messages_start(&cloned);
+
+    for msg in cloned.iter_mut().skip(start_from) {
+        if msg.role == Role::User {
+            inject_documents_into_message(msg, scope, input, cache).await;
+        }
+    }
+    cloned
+}
+
+async fn inject_documents_into_message(
+    message: &mut Message,
+    scope: &WorkspaceScope,
+    input: DocumentInput,
+    cache: &DocumentCache,
+) {
+    let mut seen_paths: HashSet<String> = HashSet::new();
+    let mut candidate_paths = Vec::new();
+    for block in &message.content {
+        let Content::Text { text } = block else {
+            continue;
+        };
+        for path in detect_document_paths(text) {
+            if seen_paths.insert(path.clone()) {
+                candidate_paths.push(path);
+            }
+        }
+    }
+
+    for path in candidate_paths {
+        let Some(block) = cache.block(&path, scope, input).await else {
+            continue;
+        };
+        if message.content.contains(&block) {
+            continue;
+        }
+        tracing::debug!(path = %path, ?input, "injecting document into message");
+        message.content.push(block);
+    }
+}
+
+async fn extracted_text_block(path: &str, scope: &WorkspaceScope) -> Result<Content> {
+    // Missing files are stale references; say nothing about them.
+    if !scope.resolve_host_path_for_read(path)?.is_file() {
+        bail!("document not found: {path}");
+    }
+    let text = match extract_document_text(path, scope).await {
+        Ok(text) if text.is_empty() => "(no text layer; the document may be a scan)".to_owned(),
+        Ok(text) => text,
+        Err(error) => {
+            tracing::warn!(path = %path, error = %format!("{error:#}"), "document text extraction failed");
+            format!("(text extraction failed: {error})")
+        }
+    };
+    Ok(Content::text(format!("[document text: {path}]\n{text}")))
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name().map_or_else(
+        || "document.pdf".to_owned(),
+        |name| name.to_string_lossy().into_owned(),
+    )
+}
+
+fn cache_path(document: &Path) -> PathBuf {
+    let mut name = document.file_name().unwrap_or_default().to_os_string();
+    name.push(EXTRACTED_TEXT_SUFFIX);
+    document.with_file_name(name)
+}
+
+fn truncate_chars(text: &str) -> String {
+    match text.char_indices().nth(MAX_EXTRACTED_CHARS) {
+        Some((end, _)) => format!("{}\n[... truncated]", &text[..end]),
+        None => text.to_owned(),
+    }
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{SessionKind, TrustLevel};
+
+    const TINY_PDF: &[u8] = b"%PDF-1.4\n%%EOF\n";
+
+    fn scope(root: &Path) -> WorkspaceScope {
+        WorkspaceScope::for_turn(root, &SessionKind::Main, TrustLevel::Full, Some("alice"))
+    }
+
+    #[test]
+    fn detects_saved_pdf_paths() {
+        let text = "[file saved: ./attachments/1_form.PDF]\nsee /tmp/bill.pdf and ./photo.jpg";
+        assert_eq!(
+            detect_document_paths(text),
+            vec!["./attachments/1_form.PDF", "/tmp/bill.pdf"]
+        );
+        assert!(detect_document_paths("https://example.com/a.pdf").is_empty());
+    }
+
+    #[tokio::test]
+    async fn native_injection_attaches_pdf_once() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("form.pdf"), TINY_PDF).unwrap();
+        std::fs::write(dir.path().join("fake.pdf"), b"not a pdf at all").unwrap();
+        let scope = scope(dir.path());
+
+        let messages =
+            vec![Message::user().with_text("[file saved: ./form.pdf]\n./fake.pdf ./form.pdf")];
+        let cache = DocumentCache::default();
+        let injected =
+            inject_documents_for_provider(&messages, &scope, DocumentInput::Native, &cache).await;
+        let documents: Vec<_> = injected[0]
+            .content
+            .iter()
+            .filter(|content| matches!(content, Content::Document { .. }))
+            .collect();
+        assert_eq!(documents.len(), 1);
+        let Content::Document {
+            mime_type, name, ..
+        } = documents[0]
+        else {
+            unreachable!();
+        };
+        assert_eq!(mime_type, "application/pdf");
+        assert_eq!(name, "form.pdf");
+
+        let again =
+            inject_documents_for_provider(&injected, &scope, DocumentInput::Native, &cache).await;
+        assert_eq!(again[0].content.len(), injected[0].content.len());
+        assert_eq!(messages[0].content.len(), 1);
+
+        // Later iterations of the turn reuse the loaded block.
+        std::fs::remove_file(dir.path().join("form.pdf")).unwrap();
+        let cached =
+            inject_documents_for_provider(&messages, &scope, DocumentInput::Native, &cache).await;
+        assert_eq!(cached[0].content, injected[0].content);
+    }
+
+    #[tokio::test]
+    async fn text_injection_uses_cached_extraction() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("bill.pdf"), TINY_PDF).unwrap();
+        std::fs::write(dir.path().join("bill.pdf.txt"), "Amount due: $42").unwrap();
+        let scope = scope(dir.path());
+
+        let messages = vec![Message::user().with_text("[file saved: ./bill.pdf]")];
+        let injected = inject_documents_for_provider(
+            &messages,
+            &scope,
+            DocumentInput::Text,
+            &DocumentCache::default(),
+        )
+        .await;
+        assert_eq!(
+            injected[0].content[1].as_text(),
+            Some("[document text: ./bill.pdf]\nAmount due: $42")
+        );
+        assert!(
+            !injected[0]
+                .content
+                .iter()
+                .any(|content| matches!(content, Content::Document { .. }))
+        );
+    }
+
+    #[tokio::test]
+    async fn missing_documents_are_skipped() {
+        let dir = tempfile::tempdir().unwrap();
+        let scope = scope(dir.path());
+        let messages = vec![Message::user().with_text("[file saved: ./gone.pdf]")];
+        for input in [DocumentInput::Native, DocumentInput::Text] {
+            let injected =
+                inject_documents_for_provider(&messages, &scope, input, &DocumentCache::default())
+                    .await;
+            assert_eq!(injected[0].content.len(), 1);
+        }
+    }
+
+    #[test]
+    fn long_text_is_truncated() {
+        let text = "é".repeat(MAX_EXTRACTED_CHARS + 5);
+        let truncated = truncate_chars(&text);
+        assert!(truncated.ends_with("[... truncated]"));
+        assert_eq!(
+            truncated.chars().filter(|c| *c == 'é').count(),
+            MAX_EXTRACTED_CHARS
+        );
+    }
+}
@@ -20,7 +20,7 @@ pub fn detect_image_paths(text: &str) -> Vec<String> {
     let mut seen = HashSet::new();
     let mut result = Vec::new();
 
-    for path in extract_candidate_paths(text) {
+    for path in extract_candidate_paths(text, IMAGE_EXTENSIONS) {
         if seen.insert(path.clone()) {
             result.push(path);
         }
@@ -152,28 +152,33 @@ pub fn inject_images_into_message(message: &mut Message, scope: &WorkspaceScope)
 /// mutated.
 pub fn inject_images_for_provider(messages: &[Message], scope: &WorkspaceScope) -> Vec<Message> {
     let mut cloned: Vec<Message> = messages.to_vec();
+    let start_from = recent_user_messages_start(&cloned);
 
-    let user_indices: Vec<usize> = cloned
+    let mut failed_paths: HashSet<String> = HashSet::new();
+
+    for msg in cloned.iter_mut().skip(start_from) {
+        if matches!(msg.role, crate::Role::User) {
+            inject_images_into_message_dedup(msg, scope, &mut failed_paths);
+        }
+    }
+    cloned
+}
+
+/// Index of the fourth-to-last user message, or 0 when there are fewer.
+/// Attachments referenced before that are not re-read from disk.
+pub(crate) fn recent_user_messages_start(messages: &[Message]) -> usize {
+    let user_indices: Vec<usize> = messages
         .iter()
         .enumerate()
         .filter(|(_, m)| matches!(m.role, crate::Role::User))
         .map(|(i, _)| i)
         .collect();
 
-    let start_from = if user_indices.len() > 4 {
+    if user_indices.len() > 4 {
         user_indices[user_indices.len() - 4]
     } else {
         0
-    };
-
-    let mut failed_paths: HashSet<String> = HashSet::new();
-
-    for msg in cloned.iter_mut().skip(start_from) {
-        if matches!(msg.role, crate::Role::User) {
-            inject_images_into_message_dedup(msg, scope, &mut failed_paths);
-        }
     }
-    cloned
 }
 
 /// Like `inject_images_into_message` but skips paths already known to have
@@ -224,7 +229,7 @@ fn inject_images_into_message_dedup(
 
 // ---- internal helpers -----------------------------------------------------
 
-fn extract_candidate_paths(text: &str) -> Vec<String> {
+pub(crate) fn extract_candidate_paths(text: &str, extensions: &[&str]) -> Vec<String> {
     let mut paths = Vec::new();
 
     for line in text.lines() {
@@ -235,7 +240,7 @@ fn extract_candidate_paths(text: &str) -> Vec<String> {
             if let Some(end) = rest[start..].find(']') {
                 let inside = &rest[start + 1..start + end];
                 for word in inside.split_whitespace() {
-                    if let Some(p) = try_image_path(word) {
+                    if let Some(p) = try_local_path(word, extensions) {
                         paths.push(p);
                     }
                 }
@@ -249,7 +254,7 @@ fn extract_candidate_paths(text: &str) -> Vec<String> {
         for word in line.split_whitespace() {
             // Strip surrounding brackets/parens that might remain
             let word = word.trim_matches(&['[', ']', '(', ')', '<', '>'] as &[char]);
-            if let Some(p) = try_image_path(word) {
+            if let Some(p) = try_local_path(word, extensions) {
                 paths.push(p);
             }
         }
@@ -258,8 +263,9 @@ fn extract_candidate_paths(text: &str) -> Vec<String> {
     paths
 }
 
-/// Returns `Some(path)` if `word` looks like a local image file path.
-fn try_image_path(word: &str) -> Option<String> {
+/// Returns `Some(path)` if `word` looks like a local file path ending in one
+/// of `extensions`.
+fn try_local_path(word: &str, extensions: &[&str]) -> Option<String> {
     // Reject URLs
     if word.starts_with("http://") || word.starts_with("https://") {
         return None;
@@ -274,9 +280,9 @@ fn try_image_path(word: &str) -> Option<String> {
         return None;
     }
 
-    // Must end with a recognized image extension
+    // Must end with a recognized extension
     let lower = word.to_lowercase();
-    let has_ext = IMAGE_EXTENSIONS
+    let has_ext = extensions
         .iter()
         .any(|ext| lower.ends_with(&format!(".{ext}")));
     if !has_ext {
@@ -1,7 +1,9 @@
+pub mod documents;
 pub mod fakes;
 pub mod image_artifacts;
 pub mod images;
 pub mod prompt;
+pub mod redaction;
 pub mod tool_args;
 pub mod tools;
 pub mod traits;
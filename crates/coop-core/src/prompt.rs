@@ -436,6 +436,28 @@ pub fn default_channel_prompt(channel: &str) -> Option<&'static str> {
             "Only include raw output (logs, errors, code) when the user needs ",
             "to see it, and keep it brief.",
         )),
+        "telegram" => Some(concat!(
+            "You are responding via Telegram.\n",
+            "\n",
+            "Formatting: Replies are sent as plain text. Do not use markdown ",
+            "syntax: no asterisks, backticks, code fences, headers, or bullet ",
+            "markers. Write in plain text with short paragraphs.\n",
+            "\n",
+            "Tone: This is a chat conversation. Be concise, warm, and natural, ",
+            "and match the user's energy and length.\n",
+            "\n",
+            "Reply behavior: The user receives ONE message from you at the end of ",
+            "your turn — all your tool calls run silently and only the final text ",
+            "reply is delivered. Unless higher-priority runtime instructions ",
+            "explicitly tell you to use a suppression token such as NO_REPLY, ",
+            "end every Telegram turn with exactly one non-empty final reply.\n",
+            "\n",
+            "Proactive updates (telegram_send): For long-running tasks, use ",
+            "telegram_send to give the user a brief heads-up before starting. ",
+            "Use telegram_react to acknowledge a message with an emoji and ",
+            "telegram_reply to answer a specific earlier message. Group messages ",
+            "carry a `msg <id>` header you can pass as message_id.",
+        )),
         _ => None,
     }
 }
@@ -2257,6 +2279,14 @@ mod tests {
         );
     }
 
+    #[test]
+    fn default_channel_prompt_telegram_returns_content() {
+        let text = default_channel_prompt("telegram").unwrap();
+        assert!(text.contains("Telegram"));
+        assert!(text.contains("telegram_send"));
+        assert!(text.contains("non-empty final reply"));
+    }
+
     #[test]
     fn default_channel_prompt_terminal_returns_none() {
         assert!(default_channel_prompt("terminal:default").is_none());
@@ -71,6 +71,13 @@ pub enum Content {
     /// Base64-encoded image.
     Image { data: String, mime_type: String },
 
+    /// Base64-encoded document (currently PDF) with its file name.
+    Document {
+        data: String,
+        mime_type: String,
+        name: String,
+    },
+
     /// A request from the assistant to call a tool.
     ToolRequest {
         id: String,
@@ -104,6 +111,18 @@ impl Content {
         }
     }
 
+    pub fn document(
+        data: impl Into<String>,
+        mime_type: impl Into<String>,
+        name: impl Into<String>,
+    ) -> Self {
+        Self::Document {
+            data: data.into(),
+            mime_type: mime_type.into(),
+            name: name.into(),
+        }
+    }
+
     pub fn tool_request(
         id: impl Into<String>,
         name: impl Into<String>,
@@ -168,6 +187,7 @@ impl fmt::Display for Content {
         match self {
             Self::Text { text } => write!(f, "{text}"),
             Self::Image { mime_type, .. } => write!(f, "[image: {mime_type}]"),
+            Self::Document { name, .. } => write!(f, "[document: {name}]"),
             Self::ToolRequest { name, .. } => write!(f, "[tool_request: {name}]"),
             Self::ToolResult { id, is_error, .. } => {
                 write!(f, "[tool_result: {id}, error={is_error}]")
@@ -240,6 +260,17 @@ impl Message {
         self.with_content(Content::image(data, mime_type))
     }
 
+    /// Add a document.
+    #[must_use]
+    pub fn with_document(
+        self,
+        data: impl Into<String>,
+        mime_type: impl Into<String>,
+        name: impl Into<String>,
+    ) -> Self {
+        self.with_content(Content::document(data, mime_type, name))
+    }
+
     /// Add a tool request.
     #[must_use]
     pub fn with_tool_request(
@@ -389,9 +420,19 @@ pub struct Usage {
     pub cache_write_tokens: Option<u32>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub stop_reason: Option<String>,
+    /// Which of the provider's API keys served the request, when it
+    /// rotates between several.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key_index: Option<usize>,
 }
 
 impl Usage {
+    #[must_use]
+    pub fn with_key_index(mut self, key_index: Option<usize>) -> Self {
+        self.key_index = key_index;
+        self
+    }
+
     pub fn total_tokens(&self) -> u32 {
         self.input_tokens.unwrap_or(0) + self.output_tokens.unwrap_or(0)
     }
@@ -417,6 +458,7 @@ impl std::ops::Add for Usage {
             cache_read_tokens: sum_opt(self.cache_read_tokens, rhs.cache_read_tokens),
             cache_write_tokens: sum_opt(self.cache_write_tokens, rhs.cache_write_tokens),
             stop_reason: rhs.stop_reason.or(self.stop_reason),
+            key_index: rhs.key_index.or(self.key_index),
         }
     }
 }
@@ -430,6 +472,9 @@ impl std::ops::AddAssign for Usage {
         if rhs.stop_reason.is_some() {
             self.stop_reason = rhs.stop_reason;
         }
+        if rhs.key_index.is_some() {
+            self.key_index = rhs.key_index;
+        }
     }
 }
 
@@ -517,8 +562,14 @@ pub struct InboundMessage {
     pub reply_to: Option<String>,
     #[serde(default)]
     pub kind: InboundKind,
+    /// Sender-assigned send time in epoch millis. Signal identifies
+    /// messages (for replies, reactions and receipts) by this value.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub message_timestamp: Option<u64>,
+    /// Channel-assigned message id, for channels whose ids are not
+    /// timestamps (Telegram's per-chat `message_id`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message_id: Option<i64>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub group_revision: Option<u32>,
 }
@@ -705,6 +756,7 @@ mod tests {
             reply_to: Some("group:deadbeef".to_owned()),
             kind: InboundKind::Reaction,
             message_timestamp: Some(1234),
+            message_id: None,
             group_revision: Some(3),
         };
 
@@ -0,0 +1,286 @@
+//! Spend budgets for agents, users and cron jobs.
+//!
+//! Spend is read back from the usage ledger, so a budget covers every
+//! provider call made in its scope: turns, compaction and cron review alike.
+//! User and cron budgets sum the ledgers of every hosted agent.
+//! Budgets are checked before each turn; a turn that is already running
+//! finishes even if it crosses a limit.
+
+use chrono::{DateTime, Datelike, NaiveTime, Utc};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Mutex;
+
+use crate::config::BudgetConfig;
+use crate::usage_ledger::{UsageFilter, UsageLine};
+
+/// What a budget covers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum BudgetScope<'a> {
+    /// Every session of the agent.
+    Agent,
+    User(&'a str),
+    Cron(&'a str),
+}
+
+impl<'a> BudgetScope<'a> {
+    /// Ledger filter for this scope's calls since `since`.
+    pub(crate) fn filter(self, since: DateTime<Utc>) -> UsageFilter<'a> {
+        let filter = UsageFilter {
+            since: Some(since),
+            ..UsageFilter::default()
+        };
+        match self {
+            Self::Agent => filter,
+            Self::User(name) => UsageFilter {
+                user: Some(name),
+                ..filter
+            },
+            Self::Cron(name) => UsageFilter {
+                cron: Some(name),
+                ..filter
+            },
+        }
+    }
+}
+
+impl fmt::Display for BudgetScope<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Agent => write!(f, "agent"),
+            Self::User(name) => write!(f, "user '{name}'"),
+            Self::Cron(name) => write!(f, "cron job '{name}'"),
+        }
+    }
+}
+
+/// The period a limit applies to, in UTC.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum BudgetWindow {
+    Day,
+    Month,
+}
+
+impl BudgetWindow {
+    pub(crate) const ALL: [Self; 2] = [Self::Day, Self::Month];
+
+    /// Start of the window containing `now`.
+    pub(crate) fn start(self, now: DateTime<Utc>) -> DateTime<Utc> {
+        let date = now.date_naive();
+        let date = match self {
+            Self::Day => date,
+            Self::Month => date.with_day(1).unwrap_or(date),
+        };
+        date.and_time(NaiveTime::MIN).and_utc()
+    }
+
+    /// Label of the window containing `now`, e.g. `2026-03-14` or `2026-03`.
+    pub(crate) fn period(self, now: DateTime<Utc>) -> String {
+        match self {
+            Self::Day => now.format("%Y-%m-%d").to_string(),
+            Self::Month => now.format("%Y-%m").to_string(),
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Day => "daily",
+            Self::Month => "monthly",
+        }
+    }
+
+    fn limits(self, budget: &BudgetConfig) -> (Option<f64>, Option<u64>) {
+        match self {
+            Self::Day => (budget.daily_usd, budget.daily_tokens),
+            Self::Month => (budget.monthly_usd, budget.monthly_tokens),
+        }
+    }
+}
+
+/// How much of one window's limits has been spent.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct BudgetUse {
+    pub window: BudgetWindow,
+    /// Fraction spent of whichever limit is closest to running out.
+    pub fraction: f64,
+    /// The spend against that limit, e.g. `$4.10 of $5.00`.
+    pub detail: String,
+}
+
+impl BudgetUse {
+    pub(crate) fn exhausted(&self) -> bool {
+        self.fraction >= 1.0
+    }
+}
+
+/// Spend against `budget` in `window`, or `None` when the window has no
+/// limits.
+#[allow(clippy::cast_precision_loss)] // token counts far below 2^52
+pub(crate) fn budget_use(
+    budget: &BudgetConfig,
+    window: BudgetWindow,
+    spent: &UsageLine,
+) -> Option<BudgetUse> {
+    let (usd, tokens) = window.limits(budget);
+    let usd = usd.map(|limit| {
+        (
+            fraction(spent.cost, limit),
+            format!("${:.2} of ${limit:.2}", spent.cost),
+        )
+    });
+    let tokens = tokens.map(|limit| {
+        let total = spent.input_tokens
+            + spent.output_tokens
+            + spent.cache_read_tokens
+            + spent.cache_write_tokens;
+        (
+            fraction(total as f64, limit as f64),
+            format!("{total} of {limit} tokens"),
+        )
+    });
+
+    usd.into_iter()
+        .chain(tokens)
+        .max_by(|a, b| a.0.total_cmp(&b.0))
+        .map(|(fraction, detail)| BudgetUse {
+            window,
+            fraction,
+            detail,
+        })
+}
+
+fn fraction(used: f64, limit: f64) -> f64 {
+    if limit > 0.0 {
+        used / limit
+    } else {
+        f64::INFINITY
+    }
+}
+
+/// What to do with a turn after checking its budgets.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum BudgetDecision {
+    Allow,
+    /// Run the turn on this model instead of the selected one.
+    Downgrade(String),
+    /// Skip the turn and reply with this notice.
+    Refuse(String),
+}
+
+/// Budget alerts already sent. Shared by every hosted agent, so a user or
+/// cron budget that spans agents alerts once. Each alert remembers only the
+/// period it was last sent in; a new period replaces it.
+#[derive(Debug, Default)]
+pub(crate) struct AlertLog {
+    sent: Mutex<HashMap<String, String>>,
+}
+
+impl AlertLog {
+    /// Record an alert for `key` in `period`. `false` when it was already
+    /// sent in that period.
+    pub(crate) fn record(&self, key: String, period: String) -> bool {
+        let mut sent = self.sent.lock().expect("alert log mutex poisoned");
+        if sent.get(&key) == Some(&period) {
+            return false;
+        }
+        sent.insert(key, period);
+        true
+    }
+}
+
+/// Reply sent in place of a refused turn.
+pub(crate) fn refusal_notice(scope: BudgetScope<'_>, used: &BudgetUse) -> String {
+    format!(
+        "The {} budget for {scope} is used up ({}). Try again once it resets.",
+        used.window.name(),
+        used.detail,
+    )
+}
+
+/// Message sent to the owner when a budget passes its warning threshold or
+/// runs out.
+pub(crate) fn alert_message(agent_id: &str, scope: BudgetScope<'_>, used: &BudgetUse) -> String {
+    if used.exhausted() {
+        format!(
+            "Budget alert ({agent_id}): {scope} has used up its {} budget ({}).",
+            used.window.name(),
+            used.detail,
+        )
+    } else {
+        format!(
+            "Budget warning ({agent_id}): {scope} has used {:.0}% of its {} budget ({}).",
+            used.fraction * 100.0,
+            used.window.name(),
+            used.detail,
+        )
+    }
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::BudgetAction;
+
+    fn budget() -> BudgetConfig {
+        BudgetConfig {
+            daily_usd: Some(2.0),
+            monthly_usd: None,
+            daily_tokens: Some(10_000),
+            monthly_tokens: None,
+            warn_at: 0.8,
+            on_exhausted: BudgetAction::Refuse,
+            downgrade_model: None,
+        }
+    }
+
+    #[test]
+    fn use_reports_the_closest_limit() {
+        let spent = UsageLine {
+            input_tokens: 6_000,
+            output_tokens: 3_000,
+            cost: 0.5,
+            ..UsageLine::default()
+        };
+        let used = budget_use(&budget(), BudgetWindow::Day, &spent).unwrap();
+        assert!((used.fraction - 0.9).abs() < 1e-9);
+        assert_eq!(used.detail, "9000 of 10000 tokens");
+        assert!(!used.exhausted());
+        assert!(budget_use(&budget(), BudgetWindow::Month, &spent).is_none());
+
+        let spent = UsageLine { cost: 2.5, ..spent };
+        let used = budget_use(&budget(), BudgetWindow::Day, &spent).unwrap();
+        assert!(used.exhausted());
+        assert_eq!(
+            refusal_notice(BudgetScope::User("alice"), &used),
+            "The daily budget for user 'alice' is used up ($2.50 of $2.00). Try again once it resets."
+        );
+    }
+
+    #[test]
+    fn windows_start_at_utc_day_and_month() {
+        let now = DateTime::parse_from_rfc3339("2026-03-14T15:09:26Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        assert_eq!(
+            BudgetWindow::Day.start(now).to_rfc3339(),
+            "2026-03-14T00:00:00+00:00"
+        );
+        assert_eq!(
+            BudgetWindow::Month.start(now).to_rfc3339(),
+            "2026-03-01T00:00:00+00:00"
+        );
+        assert_eq!(BudgetWindow::Month.period(now), "2026-03");
+    }
+
+    #[test]
+    fn alert_log_sends_once_per_period() {
+        let log = AlertLog::default();
+        assert!(log.record("user 'alice':Day:false".into(), "2026-03-01".into()));
+        assert!(!log.record("user 'alice':Day:false".into(), "2026-03-01".into()));
+        assert!(log.record("user 'alice':Day:true".into(), "2026-03-01".into()));
+        assert!(log.record("user 'alice':Day:false".into(), "2026-03-02".into()));
+        assert!(!log.record("user 'alice':Day:false".into(), "2026-03-02".into()));
+        assert_eq!(log.sent.lock().unwrap().len(), 2);
+    }
+}
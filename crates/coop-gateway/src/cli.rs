@@ -31,10 +31,20 @@ pub(crate) enum Commands {
         /// User to load as (defaults to first user in config).
         #[arg(short, long)]
         user: Option<String>,
+        /// Agent to chat with (defaults to `[agent]`).
+        #[arg(short, long)]
+        agent: Option<String>,
     },
     Attach {
         #[arg(short, long, default_value = "main")]
         session: String,
+        /// Agent whose session to attach to (defaults to `[agent]`).
+        #[arg(short, long)]
+        agent: Option<String>,
+        /// Attach to a gateway's `[ipc.remote]` listener at host:port,
+        /// authenticating with the token in `COOP_IPC_TOKEN`.
+        #[arg(long)]
+        remote: Option<String>,
     },
     Signal {
         #[command(subcommand)]
@@ -43,6 +53,36 @@ pub(crate) enum Commands {
     Memory {
         #[command(subcommand)]
         command: MemoryCommands,
+        /// Print JSON instead of human-readable output.
+        #[arg(long, global = true)]
+        json: bool,
+    },
+    Sessions {
+        #[command(subcommand)]
+        command: SessionsCommands,
+    },
+    /// Report provider spend from the usage ledger.
+    Usage {
+        /// Agent whose ledger to read (defaults to every agent).
+        #[arg(short, long)]
+        agent: Option<String>,
+        /// Group by user, model, cron or day; repeatable (defaults to all four).
+        #[arg(long)]
+        by: Vec<String>,
+        /// Only calls from the last N days.
+        #[arg(long, default_value_t = 30)]
+        days: u32,
+        /// Only calls made for this `[[users]]` name.
+        #[arg(long)]
+        user: Option<String>,
+    },
+    Models {
+        #[command(subcommand)]
+        command: ModelsCommands,
+    },
+    Cron {
+        #[command(subcommand)]
+        command: CronCommands,
     },
     Sandbox {
         #[command(subcommand)]
@@ -121,6 +161,132 @@ pub(crate) enum GatewayCommands {
 pub(crate) enum MemoryCommands {
     /// Rebuild the vector search index from stored embeddings.
     RebuildIndex,
+    /// List observations, most recent first.
+    List {
+        /// Only observations in this store (private, shared or social); repeatable.
+        #[arg(long)]
+        store: Vec<String>,
+        /// Only observations of this type; repeatable.
+        #[arg(long = "type")]
+        obs_type: Vec<String>,
+        #[arg(short, long, default_value_t = 20)]
+        limit: usize,
+        /// List archived observations instead.
+        #[arg(long)]
+        archived: bool,
+    },
+    /// Search observations by text.
+    Search {
+        query: String,
+        /// Only observations in this store; repeatable.
+        #[arg(long)]
+        store: Vec<String>,
+        /// Only observations of this type; repeatable.
+        #[arg(long = "type")]
+        obs_type: Vec<String>,
+        #[arg(short, long, default_value_t = 20)]
+        limit: usize,
+    },
+    /// Show an observation in full.
+    Show { id: i64 },
+    /// Show how an observation changed over time.
+    History { id: i64 },
+    /// Correct an observation; only the given fields change.
+    Edit {
+        id: i64,
+        #[arg(long)]
+        title: Option<String>,
+        #[arg(long)]
+        narrative: Option<String>,
+        /// Replace the facts; repeat for several.
+        #[arg(long = "fact")]
+        facts: Vec<String>,
+        /// Replace the tags; repeat for several.
+        #[arg(long = "tag")]
+        tags: Vec<String>,
+        #[arg(long = "type")]
+        obs_type: Option<String>,
+        /// Move to another store (private, shared or social).
+        #[arg(long)]
+        store: Option<String>,
+    },
+    /// Permanently delete an observation and its history.
+    Delete { id: i64 },
+    /// List known people.
+    People {
+        /// Only people whose name or alias contains this.
+        query: Option<String>,
+    },
+    /// Fold one person into another; the old name becomes an alias.
+    MergePeople { from: String, into: String },
+    /// Erase a person everywhere: rows only about them are deleted, other
+    /// mentions redacted. Prints an erasure report.
+    ForgetPerson { name: String },
+    /// Move an observation to the archive.
+    Archive { id: i64 },
+    /// Move an archived observation back into memory.
+    Restore { id: i64 },
+    /// Export observations, people, history and session summaries.
+    Export {
+        /// jsonl or markdown.
+        #[arg(long, default_value = "jsonl")]
+        format: String,
+        /// File to write for jsonl (defaults to stdout); a new directory for markdown.
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+    /// Import a JSONL export, a Markdown vault, or a workspace's MEMORY.md and
+    /// memory/*.md daily notes. Duplicates merge into existing observations.
+    Import { path: String },
+}
+
+#[derive(Subcommand)]
+pub(crate) enum SessionsCommands {
+    /// List sessions in the SQLite session store.
+    List {
+        /// Agent whose sessions to list (defaults to `[agent]`).
+        #[arg(short, long)]
+        agent: Option<String>,
+        /// Only sessions of this kind: main, dm, group, isolated, subagent or cron.
+        #[arg(long)]
+        kind: Option<String>,
+        /// Only sessions belonging to this `[[users]]` name.
+        #[arg(long)]
+        user: Option<String>,
+    },
+    /// Import JSONL session files into the SQLite session store.
+    Migrate {
+        /// Agent whose sessions to migrate (defaults to every agent).
+        #[arg(short, long)]
+        agent: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+pub(crate) enum ModelsCommands {
+    /// List the models `/model` can switch to, including those installed on
+    /// local Ollama servers.
+    List,
+    /// Download a model onto the configured Ollama server.
+    Pull {
+        /// Model name as Ollama knows it, e.g. `qwen3:8b`.
+        model: String,
+    },
+}
+
+#[derive(Subcommand)]
+pub(crate) enum CronCommands {
+    /// Show recent cron runs, newest first.
+    History {
+        /// Only runs of this cron.
+        name: Option<String>,
+        /// Agent whose history to read (defaults to every agent).
+        #[arg(short, long)]
+        agent: Option<String>,
+        /// How many runs to show per agent.
+        #[arg(short, long, default_value_t = 20)]
+        limit: usize,
+    },
 }
 
 #[derive(Subcommand)]
@@ -2,6 +2,7 @@ use coop_core::SessionKey;
 use coop_core::TrustLevel;
 
 use crate::gateway::Gateway;
+use crate::usage_ledger::{UsageFilter, UsageGrouping, format_report};
 
 fn format_number(value: u64) -> String {
     let digits = value.to_string();
@@ -33,6 +34,11 @@ pub(crate) async fn handle_slash_command(
             gateway.clear_session(session_key);
             Some("New session ✅".to_owned())
         }
+        "/undo" => Some(match gateway.undo_last_turn(session_key) {
+            Ok(turn) => format!("Undid last turn: “{}” ✅", preview(&turn.input)),
+            Err(error) => format!("Could not undo: {error}"),
+        }),
+        "/fork" => Some(handle_fork_command(gateway, trimmed, session_key)),
         "/stop" => {
             if gateway.cancel_active_turn(session_key) {
                 Some("Stopping agent…".to_owned())
@@ -46,11 +52,50 @@ pub(crate) async fn handle_slash_command(
             handle_model_command(gateway, trimmed, session_key, trust, channel, user_name).await,
         ),
         "/subagents" => Some(handle_subagents_command(gateway, trimmed)),
+        "/usage" => Some(handle_usage_command(gateway, trimmed, trust, user_name)),
         "/help" | "/?" => Some(help_text().to_owned()),
         _ => None,
     }
 }
 
+/// A parsed `/retry [model]`. Retrying runs a turn, so callers handle it
+/// instead of [`handle_slash_command`].
+pub(crate) struct RetryRequest<'a> {
+    pub model: Option<&'a str>,
+}
+
+pub(crate) fn parse_retry(input: &str) -> Option<RetryRequest<'_>> {
+    let mut parts = input.split_whitespace();
+    if parts.next()? != "/retry" {
+        return None;
+    }
+    Some(RetryRequest {
+        model: parts.next(),
+    })
+}
+
+fn handle_fork_command(gateway: &Gateway, input: &str, session_key: &SessionKey) -> String {
+    let turns = match input.split_whitespace().nth(1).map(str::parse::<usize>) {
+        None => None,
+        Some(Ok(turns)) => Some(turns),
+        Some(Err(_)) => return "Usage: /fork [turns]".to_owned(),
+    };
+    match gateway.fork_session(session_key, turns) {
+        Ok(fork) => format!("Forked into {fork} ✅\nAttach with: coop attach -s {fork}"),
+        Err(error) => format!("Could not fork: {error}"),
+    }
+}
+
+fn preview(input: &str) -> String {
+    const MAX_CHARS: usize = 60;
+    let line = input.lines().next().unwrap_or_default();
+    if line.chars().count() > MAX_CHARS {
+        format!("{}…", line.chars().take(MAX_CHARS).collect::<String>())
+    } else {
+        line.to_owned()
+    }
+}
+
 fn format_status(gateway: &Gateway, session_key: &SessionKey, user_name: Option<&str>) -> String {
     let count = gateway.session_message_count(session_key);
     let usage = gateway.session_usage(session_key);
@@ -76,8 +121,20 @@ fn format_status(gateway: &Gateway, session_key: &SessionKey, user_name: Option<
         .into_iter()
         .filter(|run| run.parent_session_key == *session_key && run.status.is_active())
         .count();
+    let failover = gateway
+        .last_failover(session_key)
+        .map(|event| {
+            format!(
+                "\nFailover: {} -> {} at {} ({})",
+                event.from,
+                event.to,
+                event.at.format("%Y-%m-%d %H:%M UTC"),
+                event.error
+            )
+        })
+        .unwrap_or_default();
     format!(
-        "Session: {}{active}\nAgent: {}\nModel: {}\nMessages: {}\nContext: {} / {} tokens ({:.1}%)\nTotal tokens used: {} in / {} out\nActive subagents: {}",
+        "Session: {}{active}\nAgent: {}\nModel: {}\nMessages: {}\nContext: {} / {} tokens ({:.1}%)\nTotal tokens used: {} in / {} out\nActive subagents: {}{failover}",
         session_key,
         gateway.agent_id(),
         model,
@@ -272,9 +329,62 @@ fn handle_subagents_command(gateway: &Gateway, input: &str) -> String {
     }
 }
 
+fn handle_usage_command(
+    gateway: &Gateway,
+    input: &str,
+    trust: TrustLevel,
+    user_name: Option<&str>,
+) -> String {
+    const DEFAULT_DAYS: u32 = 30;
+
+    let mut groupings = Vec::new();
+    let mut days = DEFAULT_DAYS;
+    for arg in input.split_whitespace().skip(1) {
+        if let Some(by) = UsageGrouping::parse(arg) {
+            groupings.push(by);
+        } else if let Ok(value) = arg.parse() {
+            days = value;
+        } else {
+            return "Usage: /usage [user|model|cron|day] [days]".to_owned();
+        }
+    }
+    if groupings.is_empty() {
+        groupings = UsageGrouping::ALL.to_vec();
+    }
+
+    // Below full trust, people only see what they spent themselves.
+    let user = if trust <= TrustLevel::Full {
+        None
+    } else if let Some(user_name) = user_name {
+        Some(user_name)
+    } else {
+        return "Usage is only available to configured users.".to_owned();
+    };
+    let filter = UsageFilter {
+        since: Some(chrono::Utc::now() - chrono::Duration::days(i64::from(days))),
+        user,
+        cron: None,
+    };
+
+    let mut sections = Vec::with_capacity(groupings.len());
+    for by in groupings {
+        match gateway.usage_report(by, filter) {
+            Ok(lines) => sections.push((by, lines)),
+            Err(error) => return format!("Could not read usage: {error}"),
+        }
+    }
+    format!(
+        "Usage for the last {days} days:\n\n{}",
+        format_report(&sections).trim_end()
+    )
+}
+
 fn help_text() -> &'static str {
     "Available commands:\n\
          /new, /clear        — Start a new session (clears history)\n\
+         /undo               — Remove your last message and the reply to it\n\
+         /retry [model]      — Run your last message again, optionally on another model\n\
+         /fork [turns]       — Copy this session (or its first N turns) into a new one\n\
          /stop               — Stop the current agent turn and any active child runs\n\
          /status             — Show session info\n\
          /models             — List available primary models\n\
@@ -282,5 +392,6 @@ fn help_text() -> &'static str {
          /subagents          — List active and recent subagent runs\n\
          /subagents inspect <run_id> — Show subagent run details\n\
          /subagents kill <run_id>    — Stop an active subagent run\n\
+         /usage [by] [days]  — Show spend by user, model, cron job or day\n\
          /help, /?           — Show this help"
 }
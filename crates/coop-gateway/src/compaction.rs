@@ -8,7 +8,7 @@
 
 use anyhow::Result;
 use coop_core::traits::Provider;
-use coop_core::types::{Content, Message, Role};
+use coop_core::types::{Content, Message, Role, Usage};
 use tracing::{Instrument, debug, info_span};
 
 /// Tokens to reserve for the model's response, matching pi's default.
@@ -148,6 +148,7 @@ pub(crate) fn estimate_message_chars(msg: &Message) -> usize {
             }
             Content::ToolResult { output, .. } => output.len().min(2000), // cap tool output
             Content::Image { .. } => 1000, // rough estimate for images
+            Content::Document { .. } => 4000, // rough estimate for documents
             Content::Thinking { thinking, .. } => thinking.len(),
         })
         .sum()
@@ -375,13 +376,14 @@ fn prepare_compaction_messages(
 /// When `previous_state` is provided, performs iterative compaction:
 /// only messages after the previous cut point are sent for summarization,
 /// and the previous summary is included for the model to update.
+/// Returns the new state with the usage of the summarization call.
 pub(crate) async fn compact(
     messages: &[Message],
     provider: &dyn Provider,
     system_prompt: &[String],
     previous_state: Option<&CompactionState>,
     recent_context_target: u32,
-) -> Result<CompactionState> {
+) -> Result<(CompactionState, Usage)> {
     let span = info_span!("compaction", message_count = messages.len());
 
     async {
@@ -414,14 +416,15 @@ pub(crate) async fn compact(
 
         // If there's nothing to summarize, return previous state or empty
         if let (true, Some(prev)) = (msgs_to_summarize.is_empty(), previous_state) {
-            return Ok(CompactionState {
+            let state = CompactionState {
                 summary: prev.summary.clone(),
                 files_touched: prev.files_touched.clone(),
                 compaction_count: prev.compaction_count,
                 tokens_at_compaction: prev.tokens_at_compaction,
                 created_at: chrono::Utc::now(),
                 messages_at_compaction: Some(cut_point),
-            });
+            };
+            return Ok((state, Usage::default()));
         }
 
         let new_files = extract_files_touched(msgs_to_summarize);
@@ -465,14 +468,15 @@ pub(crate) async fn compact(
             "compaction complete"
         );
 
-        Ok(CompactionState {
+        let state = CompactionState {
             summary,
             files_touched: all_files,
             compaction_count,
             tokens_at_compaction: total_tokens,
             created_at: chrono::Utc::now(),
             messages_at_compaction: Some(cut_point),
-        })
+        };
+        Ok((state, usage))
     }
     .instrument(span)
     .await
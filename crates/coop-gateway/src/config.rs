@@ -1,8 +1,9 @@
 use anyhow::{Context, Result};
 use arc_swap::ArcSwap;
-use coop_agent::OpenAiReasoningConfig;
+use coop_agent::{ModelPricing, OpenAiReasoningConfig};
 use coop_core::TrustLevel;
 use coop_core::prompt::{CacheHint, PromptFileConfig};
+use coop_core::redaction::RedactionMode;
 use coop_memory::MemoryMaintenanceConfig;
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
@@ -36,7 +37,12 @@ pub(crate) fn find_group_config_by_session<'a>(
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub(crate) struct Config {
+    /// The default agent. Handles every message not bound to one of `agents`.
     pub agent: AgentConfig,
+    /// Additional agents hosted by the same gateway, selected through the
+    /// `agent` binding on `[[users]]` and `[[groups]]`.
+    #[serde(default)]
+    pub agents: Vec<AgentConfig>,
     #[serde(default)]
     pub users: Vec<UserConfig>,
     #[serde(default)]
@@ -56,9 +62,27 @@ pub(crate) struct Config {
     #[serde(default)]
     pub tools: ToolsConfig,
     #[serde(default)]
+    pub mcp: Vec<McpServerConfig>,
+    #[serde(default)]
     pub cron: Vec<CronConfig>,
     #[serde(default)]
+    pub trigger: Vec<TriggerConfig>,
+    #[serde(default)]
+    pub webhooks: Option<WebhooksConfig>,
+    #[serde(default)]
     pub sandbox: SandboxConfig,
+    #[serde(default)]
+    pub ipc: IpcConfig,
+    #[serde(default)]
+    pub sessions: SessionsConfig,
+    #[serde(default)]
+    pub usage: UsageConfig,
+    #[serde(default)]
+    pub routing: RoutingConfig,
+    #[serde(default)]
+    pub transcription: Option<TranscriptionConfig>,
+    #[serde(default)]
+    pub redaction: RedactionConfig,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
@@ -78,6 +102,20 @@ pub(crate) struct AgentConfig {
     pub workspace: String,
     #[serde(default)]
     pub subagents: SubagentsConfig,
+    /// Prompt files for this agent, replacing the top-level `[prompt]`.
+    #[serde(default)]
+    pub prompt: Option<PromptConfig>,
+    /// Memory database for this agent. Defaults to `memory.db_path` for the
+    /// default agent and `memory-<id>.db` next to it for additional agents.
+    #[serde(default)]
+    pub memory_db: Option<String>,
+    /// Spend limits across every session of this agent.
+    #[serde(default)]
+    pub budget: Option<BudgetConfig>,
+    /// Models to try, in order, when the selected model's provider stays
+    /// unavailable after retries.
+    #[serde(default)]
+    pub fallback: Vec<String>,
 }
 
 fn default_workspace() -> String {
@@ -189,6 +227,27 @@ pub(crate) struct UserConfig {
     pub timezone: Option<String>,
     #[serde(default)]
     pub sandbox: Option<SandboxOverrides>,
+    /// Agent that handles this user's DMs and cron jobs.
+    #[serde(default)]
+    pub agent: Option<String>,
+    /// Environment variable holding this user's token for the remote IPC
+    /// listener. Remote clients presenting it act as this user.
+    #[serde(default)]
+    pub ipc_token_env: Option<String>,
+    /// Spend limits for turns this user starts, including their cron jobs.
+    #[serde(default)]
+    pub budget: Option<BudgetConfig>,
+    /// Failover chain for this user's turns, replacing the agent's.
+    #[serde(default)]
+    pub fallback: Vec<String>,
+    /// Whether `[routing]` may send this user's simple turns to the cheap
+    /// model. Unset follows `[routing]`.
+    #[serde(default)]
+    pub routing: Option<bool>,
+    /// Whether this user's voice notes are transcribed. Unset follows
+    /// `[transcription] default_enabled`.
+    #[serde(default)]
+    pub transcribe: Option<bool>,
 }
 
 // ---------------------------------------------------------------------------
@@ -216,6 +275,13 @@ pub(crate) struct GroupConfig {
     pub trust_ceiling: TrustCeiling,
     #[serde(default = "default_group_history_limit")]
     pub history_limit: usize,
+    /// Agent that handles this group.
+    #[serde(default)]
+    pub agent: Option<String>,
+    /// Whether `[routing]` applies to this group, ahead of each member's
+    /// `routing` setting. Unset follows `[routing]`.
+    #[serde(default)]
+    pub routing: Option<bool>,
 }
 
 impl GroupConfig {
@@ -271,6 +337,8 @@ const fn default_group_history_limit() -> usize {
 pub(crate) struct ChannelsConfig {
     #[serde(default)]
     pub signal: Option<SignalChannelConfig>,
+    #[serde(default)]
+    pub telegram: Option<TelegramChannelConfig>,
 }
 
 /// How to handle messages that arrive while a turn is already active
@@ -300,6 +368,37 @@ pub(crate) struct SignalChannelConfig {
     pub mid_turn_messages: MidTurnMessages,
 }
 
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct TelegramChannelConfig {
+    /// Environment variable holding the bot token from @BotFather.
+    #[serde(default = "default_telegram_token_env")]
+    pub token_env: String,
+    /// Bot API base URL. Override for a self-hosted Bot API server.
+    #[serde(default = "default_telegram_api_base_url")]
+    pub api_base_url: String,
+    /// Long-poll timeout for `getUpdates`, in seconds.
+    #[serde(default = "default_telegram_poll_timeout_secs")]
+    pub poll_timeout_secs: u64,
+    /// Same as `channels.signal.verbose`.
+    #[serde(default)]
+    pub verbose: bool,
+    /// Same as `channels.signal.mid_turn_messages`.
+    #[serde(default)]
+    pub mid_turn_messages: MidTurnMessages,
+}
+
+fn default_telegram_token_env() -> String {
+    "TELEGRAM_BOT_TOKEN".to_owned()
+}
+
+fn default_telegram_api_base_url() -> String {
+    "https://api.telegram.org".to_owned()
+}
+
+const fn default_telegram_poll_timeout_secs() -> u64 {
+    30
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
 pub(crate) struct ProviderConfig {
     #[serde(default = "default_provider")]
@@ -333,6 +432,10 @@ pub(crate) struct ProviderConfig {
     /// OAuth backend and maps to the Responses API `reasoning` field.
     #[serde(default)]
     pub reasoning: Option<OpenAiReasoningConfig>,
+    /// Ollama only: how long the server keeps a model loaded after each
+    /// request (`"30m"`, `"-1"` for forever, `"0"` to unload at once).
+    #[serde(default)]
+    pub keep_alive: Option<String>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
@@ -340,6 +443,7 @@ pub(crate) struct ProviderConfig {
 pub(crate) enum ModelModality {
     Text,
     Image,
+    Document,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
@@ -717,6 +821,29 @@ impl fmt::Display for CronDeliveryMode {
     }
 }
 
+/// Which runs to replay at startup when the gateway was down at fire time.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum CronCatchUp {
+    /// Skip missed runs.
+    #[default]
+    None,
+    /// Run once for the most recent missed fire time.
+    Last,
+    /// Run once for every missed fire time, oldest first.
+    All,
+}
+
+impl fmt::Display for CronCatchUp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::None => write!(f, "none"),
+            Self::Last => write!(f, "last"),
+            Self::All => write!(f, "all"),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub(crate) struct CronConfig {
     pub name: String,
@@ -734,6 +861,14 @@ pub(crate) struct CronConfig {
     pub review_prompt: Option<String>,
     #[serde(default)]
     pub sandbox: Option<SandboxOverrides>,
+    #[serde(default)]
+    pub budget: Option<BudgetConfig>,
+    /// Failover chain for this job, replacing the user's and agent's.
+    #[serde(default)]
+    pub fallback: Vec<String>,
+    /// Missed-run replay policy, applied at startup.
+    #[serde(default)]
+    pub catch_up: CronCatchUp,
 }
 
 impl CronConfig {
@@ -752,6 +887,70 @@ impl CronConfig {
     }
 }
 
+// ---------------------------------------------------------------------------
+// Event triggers
+// ---------------------------------------------------------------------------
+
+/// A job started by an event instead of a schedule: a file changing under
+/// the agent's workspace or a POST to the local webhook listener. The event
+/// payload becomes the turn message, delivered like a `[[cron]]` reply.
+/// Budgets and failover come from the job's user.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct TriggerConfig {
+    pub name: String,
+    /// Workspace-relative glob (e.g. `inbox/*.md`) whose changes fire the job.
+    #[serde(default)]
+    pub watch: Option<String>,
+    /// Fire on `POST /hooks/<name>` to `[webhooks] listen`.
+    #[serde(default)]
+    pub webhook: bool,
+    /// Instruction placed before the event payload.
+    #[serde(default)]
+    pub message: Option<String>,
+    #[serde(default)]
+    pub user: Option<String>,
+    #[serde(default)]
+    pub delivery: Option<CronDeliveryMode>,
+    #[serde(default)]
+    pub deliver: Option<CronDelivery>,
+    #[serde(default)]
+    pub review_prompt: Option<String>,
+}
+
+impl TriggerConfig {
+    /// The cron job one event runs as, with `payload` as its message.
+    pub(crate) fn to_cron(&self, payload: &str) -> CronConfig {
+        let message = match self.message.as_deref().map(str::trim) {
+            Some(message) if !message.is_empty() => format!("{message}\n\n{payload}"),
+            _ => payload.to_owned(),
+        };
+        CronConfig {
+            name: self.name.clone(),
+            cron: String::new(),
+            timezone: None,
+            message,
+            user: self.user.clone(),
+            // The payload may mention HEARTBEAT.md; never infer delivery from it.
+            delivery: Some(self.delivery.unwrap_or(CronDeliveryMode::Always)),
+            deliver: self.deliver.clone(),
+            review_prompt: self.review_prompt.clone(),
+            sandbox: None,
+            budget: None,
+            fallback: Vec::new(),
+            catch_up: CronCatchUp::None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct WebhooksConfig {
+    /// Address to listen on, e.g. `127.0.0.1:7448`. Keep it on loopback.
+    pub listen: String,
+    /// Environment variable holding the shared secret callers send as
+    /// `Authorization: Bearer <secret>`.
+    pub secret_env: String,
+}
+
 // ---------------------------------------------------------------------------
 // Tools config
 // ---------------------------------------------------------------------------
@@ -760,6 +959,83 @@ impl CronConfig {
 pub(crate) struct ToolsConfig {
     #[serde(default)]
     pub web: WebToolConfig,
+    #[serde(default)]
+    pub policy: ToolPolicyConfig,
+}
+
+/// Declarative tool access. Each trust level starts from the built-in
+/// default (see `tool_policy::default_rule`); a configured rule narrows it
+/// with `allow`/`deny` and widens it only through `grant`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub(crate) struct ToolPolicyConfig {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub owner: Option<ToolAccessRule>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub full: Option<ToolAccessRule>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub inner: Option<ToolAccessRule>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub familiar: Option<ToolAccessRule>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub public: Option<ToolAccessRule>,
+    /// Extra rules for group sessions, matched like `[[groups]]`.
+    #[serde(default)]
+    pub groups: Vec<GroupToolPolicy>,
+}
+
+impl ToolPolicyConfig {
+    /// The rule configured for `trust`, if any.
+    pub(crate) fn configured_for_trust(&self, trust: TrustLevel) -> Option<&ToolAccessRule> {
+        match trust {
+            TrustLevel::Owner => self.owner.as_ref(),
+            TrustLevel::Full => self.full.as_ref(),
+            TrustLevel::Inner => self.inner.as_ref(),
+            TrustLevel::Familiar => self.familiar.as_ref(),
+            TrustLevel::Public => self.public.as_ref(),
+        }
+    }
+
+    /// Every configured rule with a label for diagnostics, e.g.
+    /// `tools.policy.inner`.
+    pub(crate) fn labeled_rules(&self) -> Vec<(String, &ToolAccessRule)> {
+        let mut rules: Vec<(String, &ToolAccessRule)> = [
+            ("owner", TrustLevel::Owner),
+            ("full", TrustLevel::Full),
+            ("inner", TrustLevel::Inner),
+            ("familiar", TrustLevel::Familiar),
+            ("public", TrustLevel::Public),
+        ]
+        .into_iter()
+        .filter_map(|(name, trust)| {
+            self.configured_for_trust(trust)
+                .map(|rule| (format!("tools.policy.{name}"), rule))
+        })
+        .collect();
+        for (i, group) in self.groups.iter().enumerate() {
+            rules.push((format!("tools.policy.groups[{i}]"), &group.rule));
+        }
+        rules
+    }
+}
+
+/// Tool name patterns (`*` matches any run of characters). When `allow` is
+/// set only matching tools are offered; `deny` always wins. `grant`
+/// re-enables tools the trust level's default denies; group rules ignore it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub(crate) struct ToolAccessRule {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allow: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub deny: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub grant: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct GroupToolPolicy {
+    pub r#match: Vec<String>,
+    #[serde(flatten)]
+    pub rule: ToolAccessRule,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
@@ -828,6 +1104,247 @@ pub(crate) struct WebFetchConfig {
     pub user_agent: Option<String>,
 }
 
+// ---------------------------------------------------------------------------
+// MCP config
+// ---------------------------------------------------------------------------
+
+/// An external MCP server whose tools are exposed to the agent.
+///
+/// Exactly one of `command` (stdio transport) or `url` (streamable HTTP) must be set.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct McpServerConfig {
+    pub name: String,
+    #[serde(default)]
+    pub command: Option<String>,
+    #[serde(default)]
+    pub args: Vec<String>,
+    /// Extra environment for the spawned server. Values support `env:VAR`.
+    #[serde(default)]
+    pub env: BTreeMap<String, String>,
+    #[serde(default)]
+    pub cwd: Option<String>,
+    #[serde(default)]
+    pub url: Option<String>,
+    /// Extra HTTP headers. Values support `env:VAR`.
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+    /// Minimum trust level required to call this server's tools.
+    #[serde(default = "default_mcp_trust")]
+    pub trust: TrustLevel,
+    /// Optional allowlist of server tool names (unprefixed).
+    #[serde(default)]
+    pub tools: Option<Vec<String>>,
+    #[serde(default = "default_mcp_timeout_seconds")]
+    pub timeout_seconds: u64,
+    #[serde(default = "default_mcp_enabled")]
+    pub enabled: bool,
+}
+
+fn default_mcp_trust() -> TrustLevel {
+    TrustLevel::Full
+}
+
+const fn default_mcp_timeout_seconds() -> u64 {
+    30
+}
+
+const fn default_mcp_enabled() -> bool {
+    true
+}
+
+// ---------------------------------------------------------------------------
+// IPC config
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub(crate) struct IpcConfig {
+    /// TCP listener for `coop attach --remote`, next to the local socket.
+    #[serde(default)]
+    pub remote: Option<RemoteIpcConfig>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct RemoteIpcConfig {
+    /// Address to listen on, e.g. `100.64.0.2:7447`. Traffic is not
+    /// encrypted, so bind to a VPN or LAN interface.
+    pub listen: String,
+}
+
+// ---------------------------------------------------------------------------
+// Sessions config
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct SessionsConfig {
+    /// Where conversation history is persisted in each agent's workspace.
+    #[serde(default)]
+    pub store: SessionStoreKind,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum SessionStoreKind {
+    /// One `sessions/{slug}.jsonl` file per session.
+    #[default]
+    Jsonl,
+    /// A single `sessions/sessions.db`, written transactionally. Import
+    /// existing JSONL history with `coop sessions migrate`.
+    Sqlite,
+}
+
+// ---------------------------------------------------------------------------
+// Usage config
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub(crate) struct UsageConfig {
+    /// Per-model prices in USD per million tokens, keyed by model id.
+    /// Models without an entry use models.dev prices.
+    #[serde(default)]
+    pub pricing: BTreeMap<String, ModelPricing>,
+}
+
+/// Spend limits for an agent, a user or a cron job. Each limit covers the
+/// current UTC day or calendar month; unset limits are not enforced.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct BudgetConfig {
+    #[serde(default)]
+    pub daily_usd: Option<f64>,
+    #[serde(default)]
+    pub monthly_usd: Option<f64>,
+    /// Input plus output tokens, cached tokens included.
+    #[serde(default)]
+    pub daily_tokens: Option<u64>,
+    #[serde(default)]
+    pub monthly_tokens: Option<u64>,
+    /// Fraction of a limit at which the owner is warned.
+    #[serde(default = "default_budget_warn_at")]
+    pub warn_at: f64,
+    #[serde(default)]
+    pub on_exhausted: BudgetAction,
+    /// Model to switch to when `on_exhausted = "downgrade"`.
+    #[serde(default)]
+    pub downgrade_model: Option<String>,
+}
+
+const fn default_budget_warn_at() -> f64 {
+    0.8
+}
+
+/// What happens to a turn once a budget is used up.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum BudgetAction {
+    /// Reply with a notice instead of calling the provider.
+    #[default]
+    Refuse,
+    /// Run the turn on `downgrade_model`.
+    Downgrade,
+}
+
+// ---------------------------------------------------------------------------
+// Routing config
+// ---------------------------------------------------------------------------
+
+/// Sends turns that look simple to a cheaper model than the user's own.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct RoutingConfig {
+    /// Model for simple turns. Routing is off while this is unset.
+    #[serde(default)]
+    pub cheap_model: Option<String>,
+    /// Longest message, in characters, that can count as simple.
+    #[serde(default = "default_routing_max_simple_chars")]
+    pub max_simple_chars: usize,
+    /// Ask the cheap model to classify messages the heuristics can't place.
+    #[serde(default)]
+    pub classify_with_model: bool,
+}
+
+impl Default for RoutingConfig {
+    fn default() -> Self {
+        Self {
+            cheap_model: None,
+            max_simple_chars: default_routing_max_simple_chars(),
+            classify_with_model: false,
+        }
+    }
+}
+
+const fn default_routing_max_simple_chars() -> usize {
+    280
+}
+
+// ---------------------------------------------------------------------------
+// Transcription config
+// ---------------------------------------------------------------------------
+
+/// Speech-to-text for inbound audio attachments such as voice notes.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct TranscriptionConfig {
+    pub backend: TranscriptionBackend,
+    /// whisper.cpp binary. Non-WAV audio is converted with `ffmpeg` first.
+    #[serde(default = "default_whisper_command")]
+    pub command: String,
+    /// Path to the ggml model for `whisper_cpp`, or the API model name for
+    /// `openai` (default `whisper-1`).
+    #[serde(default)]
+    pub model: Option<String>,
+    /// API base URL for `openai`; `/audio/transcriptions` is appended.
+    #[serde(default)]
+    pub base_url: Option<String>,
+    #[serde(default)]
+    pub api_key_env: Option<String>,
+    /// Spoken language hint, e.g. `en`. Unset lets the model detect it.
+    #[serde(default)]
+    pub language: Option<String>,
+    /// Whether users without a `transcribe` setting get transcripts.
+    #[serde(default = "default_transcription_enabled")]
+    pub default_enabled: bool,
+}
+
+impl TranscriptionConfig {
+    pub(crate) fn enabled_for(&self, user: Option<&UserConfig>) -> bool {
+        user.and_then(|user| user.transcribe)
+            .unwrap_or(self.default_enabled)
+    }
+
+    pub(crate) fn effective_api_key_env(&self) -> &str {
+        self.api_key_env.as_deref().unwrap_or("OPENAI_API_KEY")
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum TranscriptionBackend {
+    /// A local whisper.cpp binary.
+    WhisperCpp,
+    /// An OpenAI-compatible `/audio/transcriptions` endpoint.
+    Openai,
+}
+
+fn default_whisper_command() -> String {
+    "whisper-cli".to_owned()
+}
+
+const fn default_transcription_enabled() -> bool {
+    true
+}
+
+// ---------------------------------------------------------------------------
+// Redaction config
+// ---------------------------------------------------------------------------
+
+/// Secret scanning for memory writes, the session search index, session
+/// persistence and trace output.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct RedactionConfig {
+    /// `block` refuses memory writes that contain a secret, `mask` replaces
+    /// it with `[redacted:<kind>]`, `warn` only logs it. Transcripts and the
+    /// search index can't refuse a write, so `block` masks them.
+    #[serde(default)]
+    pub mode: RedactionMode,
+}
+
 // ---------------------------------------------------------------------------
 // Sandbox config
 // ---------------------------------------------------------------------------
@@ -967,6 +1484,60 @@ impl Config {
         }
     }
 
+    /// Every agent the gateway hosts, default agent first.
+    pub(crate) fn all_agents(&self) -> impl Iterator<Item = &AgentConfig> {
+        std::iter::once(&self.agent).chain(&self.agents)
+    }
+
+    pub(crate) fn find_agent(&self, id: &str) -> Option<&AgentConfig> {
+        self.all_agents().find(|agent| agent.id == id)
+    }
+
+    /// Resolve an `agent` binding, falling back to the default agent when the
+    /// binding is absent or names an agent that does not exist.
+    pub(crate) fn bound_agent_id(&self, binding: Option<&str>) -> &str {
+        binding
+            .and_then(|id| self.find_agent(id))
+            .map_or(&self.agent.id, |agent| &agent.id)
+    }
+
+    /// Agent bound to `user_name` (see [`Config::bound_agent_id`]).
+    pub(crate) fn agent_id_for_user(&self, user_name: Option<&str>) -> &str {
+        self.bound_agent_id(
+            user_name
+                .and_then(|name| self.users.iter().find(|user| user.name == name))
+                .and_then(|user| user.agent.as_deref()),
+        )
+    }
+
+    /// Memory database path for an agent (see [`AgentConfig::memory_db`]).
+    pub(crate) fn agent_memory_db_path(&self, agent: &AgentConfig) -> String {
+        if let Some(path) = &agent.memory_db {
+            return path.clone();
+        }
+        if agent.id == self.agent.id {
+            return self.memory.db_path.clone();
+        }
+        Path::new(&self.memory.db_path)
+            .with_file_name(format!("memory-{}.db", agent.id))
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    /// The config as seen by one agent's gateway: `agent` is that agent, and
+    /// its prompt files and memory database replace the top-level ones.
+    /// Everything else (users, groups, providers, tools, channels) is shared.
+    pub(crate) fn for_agent(&self, id: &str) -> Option<Self> {
+        let agent = self.find_agent(id)?.clone();
+        let mut config = self.clone();
+        config.memory.db_path = self.agent_memory_db_path(&agent);
+        if let Some(prompt) = &agent.prompt {
+            config.prompt = prompt.clone();
+        }
+        config.agent = agent;
+        Some(config)
+    }
+
     /// Load config from a TOML file.
     pub(crate) fn load(path: &Path) -> Result<Self> {
         let content = std::fs::read_to_string(path)
@@ -1185,6 +1756,27 @@ name = "anthropic"
         assert_eq!(config.memory.retention.archive_after_days, 30);
     }
 
+    #[test]
+    fn parse_telegram_channel_config() {
+        let toml_str = r#"
+[agent]
+id = "coop"
+model = "test"
+
+[channels.telegram]
+verbose = true
+mid_turn_messages = "queue"
+"#;
+        let config: Config = toml::from_str(toml_str).unwrap();
+        let telegram = config.channels.telegram.unwrap();
+        assert_eq!(telegram.token_env, "TELEGRAM_BOT_TOKEN");
+        assert_eq!(telegram.api_base_url, "https://api.telegram.org");
+        assert_eq!(telegram.poll_timeout_secs, 30);
+        assert!(telegram.verbose);
+        assert_eq!(telegram.mid_turn_messages, MidTurnMessages::Queue);
+        assert!(config.channels.signal.is_none());
+    }
+
     #[test]
     fn parse_config_with_cron() {
         let toml_str = r#"
@@ -1311,6 +1903,95 @@ message = "check HEARTBEAT.md"
         let config: Config = toml::from_str(toml_str).unwrap();
         assert_eq!(config.cron.len(), 1);
         assert_eq!(config.cron[0].delivery, Some(CronDeliveryMode::AsNeeded));
+        assert_eq!(config.cron[0].catch_up, CronCatchUp::None);
+    }
+
+    #[test]
+    fn parse_config_with_cron_catch_up() {
+        let toml_str = r#"
+[agent]
+id = "coop"
+model = "test"
+
+[[cron]]
+name = "briefing"
+cron = "0 8 * * *"
+catch_up = "last"
+message = "Morning briefing"
+"#;
+        let config: Config = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.cron[0].catch_up, CronCatchUp::Last);
+        assert!(toml::from_str::<Config>(&toml_str.replace("\"last\"", "\"every\"")).is_err());
+    }
+
+    #[test]
+    fn parse_config_with_transcription() {
+        let toml_str = r#"
+[agent]
+id = "coop"
+model = "test"
+
+[transcription]
+backend = "openai"
+base_url = "http://127.0.0.1:8080/v1"
+default_enabled = false
+
+[[users]]
+name = "alice"
+trust = "full"
+transcribe = true
+
+[[users]]
+name = "bob"
+trust = "inner"
+"#;
+        let config: Config = toml::from_str(toml_str).unwrap();
+        let transcription = config.transcription.as_ref().unwrap();
+        assert_eq!(transcription.backend, TranscriptionBackend::Openai);
+        assert_eq!(transcription.command, "whisper-cli");
+        assert_eq!(transcription.effective_api_key_env(), "OPENAI_API_KEY");
+        assert!(transcription.enabled_for(Some(&config.users[0])));
+        assert!(!transcription.enabled_for(Some(&config.users[1])));
+        assert!(!transcription.enabled_for(None));
+    }
+
+    #[test]
+    fn parse_config_with_triggers() {
+        let toml_str = r#"
+[agent]
+id = "coop"
+model = "test"
+
+[webhooks]
+listen = "127.0.0.1:7448"
+secret_env = "COOP_WEBHOOK_SECRET"
+
+[[trigger]]
+name = "inbox"
+watch = "inbox/*.md"
+message = "Triage this note."
+user = "alice"
+
+[[trigger]]
+name = "doorbell"
+webhook = true
+delivery = "as_needed"
+"#;
+        let config: Config = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.trigger.len(), 2);
+        assert_eq!(config.trigger[0].watch.as_deref(), Some("inbox/*.md"));
+        assert!(config.trigger[1].webhook);
+        assert_eq!(
+            config.webhooks.as_ref().unwrap().secret_env,
+            "COOP_WEBHOOK_SECRET"
+        );
+
+        let inbox = config.trigger[0].to_cron("changed: inbox/a.md");
+        assert_eq!(inbox.message, "Triage this note.\n\nchanged: inbox/a.md");
+        assert_eq!(inbox.delivery, Some(CronDeliveryMode::Always));
+        let doorbell = config.trigger[1].to_cron("{\"who\":\"courier\"}");
+        assert_eq!(doorbell.message, "{\"who\":\"courier\"}");
+        assert_eq!(doorbell.delivery, Some(CronDeliveryMode::AsNeeded));
     }
 
     #[test]
@@ -1863,6 +2544,7 @@ stream_policy = "require"
             stream_policy: StreamPolicy::Prefer,
             refresh_token: None,
             reasoning: None,
+            keep_alive: None,
         };
         assert_eq!(
             provider.effective_api_key_env().as_deref(),
@@ -1884,6 +2566,7 @@ stream_policy = "require"
             stream_policy: StreamPolicy::Prefer,
             refresh_token: None,
             reasoning: None,
+            keep_alive: None,
         };
         assert_eq!(
             provider.effective_api_key_env().as_deref(),
@@ -1905,6 +2588,7 @@ stream_policy = "require"
             stream_policy: StreamPolicy::Prefer,
             refresh_token: None,
             reasoning: None,
+            keep_alive: None,
         };
         assert_eq!(
             provider.effective_api_key_env().as_deref(),
@@ -2264,4 +2948,173 @@ history_limit = 50
         let config2: Config = toml::from_str(&serialized).unwrap();
         assert_eq!(config.groups, config2.groups);
     }
+
+    #[test]
+    fn parse_multiple_agents() {
+        let toml_str = r#"
+[agent]
+id = "coop"
+model = "test"
+
+[memory]
+db_path = "./db/memory.db"
+
+[[agents]]
+id = "work"
+model = "work-model"
+workspace = "./workspaces/work"
+prompt = { shared_files = [], user_files = [] }
+
+[agents.subagents]
+max_spawn_depth = 1
+
+[[agents]]
+id = "home"
+model = "home-model"
+memory_db = "./db/home.db"
+
+[[users]]
+name = "alice"
+trust = "full"
+match = ["signal:alice-uuid"]
+agent = "work"
+
+[[groups]]
+match = ["signal:group:aabb"]
+agent = "home"
+"#;
+        let config: Config = toml::from_str(toml_str).unwrap();
+        let ids: Vec<&str> = config.all_agents().map(|a| a.id.as_str()).collect();
+        assert_eq!(ids, vec!["coop", "work", "home"]);
+        assert_eq!(config.users[0].agent.as_deref(), Some("work"));
+        assert_eq!(config.groups[0].agent.as_deref(), Some("home"));
+        assert_eq!(config.agent_id_for_user(Some("alice")), "work");
+        assert_eq!(config.agent_id_for_user(Some("bob")), "coop");
+        assert_eq!(config.agent_id_for_user(None), "coop");
+
+        let work = config.for_agent("work").unwrap();
+        assert_eq!(work.agent.model, "work-model");
+        assert_eq!(work.agent.subagents.max_spawn_depth, 1);
+        assert!(work.prompt.shared_files.is_empty());
+        assert_eq!(
+            Path::new(&work.memory.db_path),
+            Path::new("./db/memory-work.db")
+        );
+        assert_eq!(work.users, config.users);
+
+        let home = config.for_agent("home").unwrap();
+        assert_eq!(home.memory.db_path, "./db/home.db");
+        assert_eq!(home.prompt, config.prompt);
+
+        let default = config.for_agent("coop").unwrap();
+        assert_eq!(default.memory.db_path, "./db/memory.db");
+        assert!(config.for_agent("missing").is_none());
+    }
+
+    #[test]
+    fn parse_usage_pricing_overrides() {
+        let toml_str = r#"
+[agent]
+id = "coop"
+model = "test"
+
+[usage.pricing."ollama/qwen3"]
+input = 0.0
+output = 0.0
+
+[usage.pricing."anthropic/claude-sonnet-4-20250514"]
+input = 3.0
+output = 15.0
+cache_read = 0.3
+"#;
+        let config: Config = toml::from_str(toml_str).unwrap();
+        let sonnet = config.usage.pricing["anthropic/claude-sonnet-4-20250514"];
+        assert_eq!(sonnet.cache_read, Some(0.3));
+        assert_eq!(sonnet.cache_write, None);
+        assert_eq!(config.usage.pricing.len(), 2);
+    }
+
+    #[test]
+    fn parse_budgets() {
+        let toml_str = r#"
+[agent]
+id = "coop"
+model = "test"
+budget = { monthly_usd = 50.0 }
+
+[[users]]
+name = "bob"
+trust = "inner"
+budget = { daily_tokens = 200000, on_exhausted = "downgrade", downgrade_model = "gpt-4o-mini", warn_at = 0.5 }
+
+[[cron]]
+name = "digest"
+cron = "0 8 * * *"
+message = "digest"
+budget = { daily_usd = 0.25 }
+"#;
+        let config: Config = toml::from_str(toml_str).unwrap();
+        let agent = config.agent.budget.unwrap();
+        assert_eq!(agent.monthly_usd, Some(50.0));
+        assert_eq!(agent.on_exhausted, BudgetAction::Refuse);
+        assert!((agent.warn_at - 0.8).abs() < f64::EPSILON);
+
+        let bob = config.users[0].budget.as_ref().unwrap();
+        assert_eq!(bob.daily_tokens, Some(200_000));
+        assert_eq!(bob.on_exhausted, BudgetAction::Downgrade);
+        assert_eq!(bob.downgrade_model.as_deref(), Some("gpt-4o-mini"));
+
+        assert_eq!(
+            config.cron[0].budget.as_ref().unwrap().daily_usd,
+            Some(0.25)
+        );
+    }
+
+    #[test]
+    fn parse_routing() {
+        let toml_str = r#"
+[agent]
+id = "coop"
+model = "test"
+
+[routing]
+cheap_model = "gpt-4o-mini"
+
+[[users]]
+name = "bob"
+trust = "inner"
+routing = false
+
+[[groups]]
+match = ["signal:group:family"]
+routing = true
+"#;
+        let config: Config = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.routing.cheap_model.as_deref(), Some("gpt-4o-mini"));
+        assert_eq!(config.routing.max_simple_chars, 280);
+        assert!(!config.routing.classify_with_model);
+        assert_eq!(config.users[0].routing, Some(false));
+        assert_eq!(config.groups[0].routing, Some(true));
+
+        let config: Config = toml::from_str("[agent]\nid = \"coop\"\nmodel = \"test\"\n").unwrap();
+        assert_eq!(config.routing, RoutingConfig::default());
+    }
+
+    #[test]
+    fn parse_redaction_mode() {
+        let config: Config = toml::from_str("[agent]\nid = \"coop\"\nmodel = \"test\"\n").unwrap();
+        assert_eq!(config.redaction.mode, RedactionMode::Mask);
+
+        let config: Config = toml::from_str(
+            "[agent]\nid = \"coop\"\nmodel = \"test\"\n\n[redaction]\nmode = \"block\"\n",
+        )
+        .unwrap();
+        assert_eq!(config.redaction.mode, RedactionMode::Block);
+        assert!(
+            toml::from_str::<Config>(
+                "[agent]\nid = \"coop\"\nmodel = \"test\"\n\n[redaction]\nmode = \"drop\"\n"
+            )
+            .is_err()
+        );
+    }
 }
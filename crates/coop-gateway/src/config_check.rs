@@ -4,12 +4,13 @@ use std::path::Path;
 use coop_core::TrustLevel;
 use coop_core::prompt::{PromptBuilder, WorkspaceIndex};
 
-use crate::config::{Config, ProviderConfig};
+use crate::config::{BudgetAction, BudgetConfig, Config, ProviderConfig, TranscriptionBackend};
 use crate::model_capabilities::{model_capabilities, provider_model_capabilities};
 use crate::model_catalog::{
     normalize_model_key, provider_model_candidates, resolve_available_model,
     resolve_configured_model, resolve_default_main_model, resolve_model_reference,
 };
+use crate::tool_policy;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub(crate) enum Severity {
@@ -249,6 +250,9 @@ pub(crate) fn validate_config(config_path: &Path, config_dir: &Path) -> CheckRep
     // 10. users
     check_users(&mut report, &config);
 
+    // 10b. additional agents and agent bindings
+    check_agents(&mut report, &config, config_dir);
+
     // 10. signal_channel
     if let Some(ref signal) = config.channels.signal {
         let db_path = crate::tui_helpers::resolve_config_path(config_dir, &signal.db_path);
@@ -265,16 +269,58 @@ pub(crate) fn validate_config(config_path: &Path, config_dir: &Path) -> CheckRep
         });
     }
 
+    // 10. telegram_channel
+    if let Some(ref telegram) = config.channels.telegram {
+        let token_set = std::env::var(&telegram.token_env).is_ok_and(|v| !v.trim().is_empty());
+        report.push(CheckResult {
+            name: "telegram_channel",
+            severity: Severity::Warning,
+            passed: token_set,
+            message: if token_set {
+                format!("telegram bot token: {} is set", telegram.token_env)
+            } else {
+                format!("telegram bot token: {} is not set", telegram.token_env)
+            },
+        });
+    }
+
+    // 10c. remote ipc listener
+    check_remote_ipc(&mut report, &config);
+
+    // 10d. spend budgets
+    check_budgets(&mut report, &config);
+
+    // 10e. failover chains
+    check_fallbacks(&mut report, &config);
+
+    // 10f. model routing
+    check_routing(&mut report, &config);
+
     // 11. groups
     check_groups(&mut report, &config);
 
     // 12-14. cron checks
     check_cron(&mut report, &config);
 
+    // 14b. event triggers and webhooks
+    check_triggers(&mut report, &config);
+
+    // 14c. voice note transcription
+    check_transcription(&mut report, &config);
+
+    // 14d. secret redaction
+    check_redaction(&mut report, &config);
+
     // 15. web tools config
     check_web_tools(&mut report, &config);
 
-    // 15. binary_exists
+    // 16. tool policy
+    check_tool_policy(&mut report, &config);
+
+    // 17. mcp servers
+    check_mcp(&mut report, &config);
+
+    // 18. binary_exists
     check_binary_exists(&mut report);
 
     report
@@ -1354,6 +1400,161 @@ fn check_sandbox(report: &mut CheckReport, config: &Config) {
     }
 }
 
+fn check_remote_ipc(report: &mut CheckReport, config: &Config) {
+    let Some(ref remote) = config.ipc.remote else {
+        return;
+    };
+
+    if remote.listen.parse::<std::net::SocketAddr>().is_err() {
+        report.push(CheckResult {
+            name: "ipc_remote",
+            severity: Severity::Error,
+            passed: false,
+            message: format!(
+                "ipc.remote.listen '{}' is not an address:port (e.g. 100.64.0.2:7447)",
+                remote.listen
+            ),
+        });
+    }
+
+    let token_users: Vec<_> = config
+        .users
+        .iter()
+        .filter_map(|user| Some((&user.name, user.ipc_token_env.as_ref()?)))
+        .collect();
+    if token_users.is_empty() {
+        report.push(CheckResult {
+            name: "ipc_remote",
+            severity: Severity::Warning,
+            passed: false,
+            message: "ipc.remote is set but no user has ipc_token_env, so nobody can attach"
+                .to_owned(),
+        });
+    }
+    for (name, env) in token_users {
+        let token_set = std::env::var(env).is_ok_and(|v| !v.trim().is_empty());
+        if !token_set {
+            report.push(CheckResult {
+                name: "ipc_remote",
+                severity: Severity::Warning,
+                passed: false,
+                message: format!("user '{name}' ipc token: {env} is not set"),
+            });
+        }
+    }
+}
+
+fn check_budgets(report: &mut CheckReport, config: &Config) {
+    let agents = config
+        .all_agents()
+        .filter_map(|agent| Some((format!("agent '{}'", agent.id), agent.budget.as_ref()?)));
+    let users = config
+        .users
+        .iter()
+        .filter_map(|user| Some((format!("user '{}'", user.name), user.budget.as_ref()?)));
+    let cron = config
+        .cron
+        .iter()
+        .filter_map(|job| Some((format!("cron '{}'", job.name), job.budget.as_ref()?)));
+
+    for (owner, budget) in agents.chain(users).chain(cron) {
+        for (severity, message) in budget_problems(config, budget) {
+            report.push(CheckResult {
+                name: "budgets",
+                severity,
+                passed: false,
+                message: format!("{owner} budget: {message}"),
+            });
+        }
+    }
+}
+
+fn check_fallbacks(report: &mut CheckReport, config: &Config) {
+    let agents = config
+        .all_agents()
+        .map(|agent| (format!("agent '{}'", agent.id), &agent.fallback));
+    let users = config
+        .users
+        .iter()
+        .map(|user| (format!("user '{}'", user.name), &user.fallback));
+    let cron = config
+        .cron
+        .iter()
+        .map(|job| (format!("cron '{}'", job.name), &job.fallback));
+
+    for (owner, chain) in agents.chain(users).chain(cron) {
+        for model in chain {
+            if resolve_available_model(config, model).is_none() {
+                report.push(CheckResult {
+                    name: "fallbacks",
+                    severity: Severity::Error,
+                    passed: false,
+                    message: format!("{owner} fallback '{model}' is not an available model"),
+                });
+            }
+        }
+    }
+}
+
+fn check_routing(report: &mut CheckReport, config: &Config) {
+    let Some(model) = config.routing.cheap_model.as_deref() else {
+        return;
+    };
+    let available = resolve_available_model(config, model).is_some();
+    report.push(CheckResult {
+        name: "routing",
+        severity: Severity::Error,
+        passed: available,
+        message: if available {
+            format!("simple turns route to '{model}'")
+        } else {
+            format!("routing cheap_model '{model}' is not an available model")
+        },
+    });
+}
+
+fn budget_problems(config: &Config, budget: &BudgetConfig) -> Vec<(Severity, String)> {
+    let mut problems = Vec::new();
+    let usd = [budget.daily_usd, budget.monthly_usd];
+    let has_limit = usd.iter().any(Option::is_some)
+        || budget.daily_tokens.is_some()
+        || budget.monthly_tokens.is_some();
+    if !has_limit {
+        problems.push((
+            Severity::Warning,
+            "no daily or monthly limit is set".to_owned(),
+        ));
+    }
+    if usd.iter().flatten().any(|limit| *limit < 0.0) {
+        problems.push((
+            Severity::Error,
+            "USD limits must not be negative".to_owned(),
+        ));
+    }
+    if !(budget.warn_at > 0.0 && budget.warn_at <= 1.0) {
+        problems.push((
+            Severity::Error,
+            format!("warn_at {} must be in (0, 1]", budget.warn_at),
+        ));
+    }
+    if budget.on_exhausted == BudgetAction::Downgrade {
+        match &budget.downgrade_model {
+            None => problems.push((
+                Severity::Error,
+                "on_exhausted = \"downgrade\" needs downgrade_model".to_owned(),
+            )),
+            Some(model) if resolve_available_model(config, model).is_none() => {
+                problems.push((
+                    Severity::Error,
+                    format!("downgrade_model '{model}' is not an available model"),
+                ));
+            }
+            Some(_) => {}
+        }
+    }
+    problems
+}
+
 fn check_users(report: &mut CheckReport, config: &Config) {
     if config.users.is_empty() {
         report.push(CheckResult {
@@ -1437,6 +1638,116 @@ fn check_users(report: &mut CheckReport, config: &Config) {
     }
 }
 
+#[allow(clippy::too_many_lines)]
+fn check_agents(report: &mut CheckReport, config: &Config, config_dir: &Path) {
+    let bindings: Vec<(String, &str)> = config
+        .users
+        .iter()
+        .filter_map(|user| {
+            user.agent
+                .as_deref()
+                .map(|agent| (format!("user '{}'", user.name), agent))
+        })
+        .chain(config.groups.iter().filter_map(|group| {
+            group
+                .agent
+                .as_deref()
+                .map(|agent| (format!("group {:?}", group.r#match), agent))
+        }))
+        .collect();
+    for (owner, agent) in &bindings {
+        let known = config.find_agent(agent).is_some();
+        report.push(CheckResult {
+            name: "agent_bindings",
+            severity: Severity::Error,
+            passed: known,
+            message: if known {
+                format!("{owner} -> agent '{agent}'")
+            } else {
+                format!("{owner} is bound to unknown agent '{agent}'")
+            },
+        });
+    }
+
+    if config.agents.is_empty() {
+        return;
+    }
+
+    let mut seen_ids = HashSet::new();
+    let duplicate_ids: Vec<&str> = config
+        .all_agents()
+        .map(|agent| agent.id.as_str())
+        .filter(|id| !seen_ids.insert(*id))
+        .collect();
+    let mut seen_dbs = HashSet::new();
+    let shared_dbs: Vec<String> = config
+        .all_agents()
+        .map(|agent| {
+            crate::tui_helpers::resolve_config_path(config_dir, &config.agent_memory_db_path(agent))
+        })
+        .filter(|path| !seen_dbs.insert(path.clone()))
+        .map(|path| path.display().to_string())
+        .collect();
+    let ids_ok = duplicate_ids.is_empty() && shared_dbs.is_empty();
+    report.push(CheckResult {
+        name: "agents",
+        severity: Severity::Error,
+        passed: ids_ok,
+        message: if !duplicate_ids.is_empty() {
+            format!("duplicate agent ids: {}", duplicate_ids.join(", "))
+        } else if !shared_dbs.is_empty() {
+            format!(
+                "agents must not share a memory db: {}",
+                shared_dbs.join(", ")
+            )
+        } else {
+            format!(
+                "{} agent(s): {}",
+                config.agents.len() + 1,
+                config
+                    .all_agents()
+                    .map(|agent| agent.id.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )
+        },
+    });
+
+    for agent in &config.agents {
+        let Some(view) = config.for_agent(&agent.id) else {
+            continue;
+        };
+        let fields_ok = !agent.id.is_empty() && !agent.model.is_empty();
+        let model_ok = fields_ok && resolve_default_main_model(&view).is_some();
+        report.push(CheckResult {
+            name: "agents_models",
+            severity: Severity::Error,
+            passed: model_ok,
+            message: if model_ok {
+                format!("agent '{}' model '{}'", agent.id, agent.model)
+            } else if fields_ok {
+                format!(
+                    "agent '{}' model '{}' must appear in one configured provider's model list or built-in catalog",
+                    agent.id, agent.model
+                )
+            } else {
+                "agents[].id and agents[].model must be non-empty".to_owned()
+            },
+        });
+
+        let workspace = view.resolve_workspace(config_dir);
+        report.push(CheckResult {
+            name: "agents_workspace",
+            severity: Severity::Error,
+            passed: workspace.is_ok(),
+            message: match workspace {
+                Ok(ws) => format!("agent '{}' workspace: {}", agent.id, ws.display()),
+                Err(e) => format!("agent '{}': {e}", agent.id),
+            },
+        });
+    }
+}
+
 #[allow(clippy::too_many_lines)]
 fn check_groups(report: &mut CheckReport, config: &Config) {
     use crate::config::{GroupTrigger, TrustCeiling};
@@ -1459,14 +1770,17 @@ fn check_groups(report: &mut CheckReport, config: &Config) {
         }
 
         for pattern in &group.r#match {
-            if pattern != "*" && !pattern.starts_with("signal:group:") {
+            if pattern != "*"
+                && !pattern.starts_with("signal:group:")
+                && !pattern.starts_with("telegram:group:")
+            {
                 report.push(CheckResult {
                     name: "groups",
                     severity: Severity::Warning,
                     passed: false,
                     message: format!(
                         "groups[{i}]: match pattern '{pattern}' does not look like a group id \
-                         (expected 'signal:group:<hex>' or '*')"
+                         (expected 'signal:group:<hex>', 'telegram:group:<id>' or '*')"
                     ),
                 });
             }
@@ -1566,12 +1880,12 @@ fn check_groups(report: &mut CheckReport, config: &Config) {
         }
     }
 
-    if config.channels.signal.is_none() {
+    if config.channels.signal.is_none() && config.channels.telegram.is_none() {
         report.push(CheckResult {
             name: "groups",
             severity: Severity::Warning,
             passed: false,
-            message: "groups configured but no signal channel is set up".to_owned(),
+            message: "groups configured but no chat channel is set up".to_owned(),
         });
     }
 
@@ -1590,6 +1904,183 @@ fn check_groups(report: &mut CheckReport, config: &Config) {
     }
 }
 
+fn check_triggers(report: &mut CheckReport, config: &Config) {
+    let mut seen: HashSet<&str> = config.cron.iter().map(|job| job.name.as_str()).collect();
+    let dupes: Vec<&str> = config
+        .trigger
+        .iter()
+        .map(|trigger| trigger.name.as_str())
+        .filter(|name| !seen.insert(*name))
+        .collect();
+    if !dupes.is_empty() {
+        report.push(CheckResult {
+            name: "trigger_names",
+            severity: Severity::Error,
+            passed: false,
+            message: format!(
+                "trigger names must be unique and differ from cron names: {}",
+                dupes.join(", ")
+            ),
+        });
+    }
+
+    for trigger in &config.trigger {
+        let problem = match (&trigger.watch, trigger.webhook) {
+            (Some(_), true) | (None, false) => {
+                Some("set exactly one of watch or webhook = true".to_owned())
+            }
+            (Some(pattern), false) => crate::triggers::watch_regex(pattern)
+                .err()
+                .map(|error| error.to_string()),
+            (None, true) if config.webhooks.is_none() => {
+                Some("webhook = true needs a [webhooks] listener".to_owned())
+            }
+            (None, true) => None,
+        };
+        if let Some(problem) = problem {
+            report.push(CheckResult {
+                name: "trigger_source",
+                severity: Severity::Error,
+                passed: false,
+                message: format!("trigger '{}': {problem}", trigger.name),
+            });
+        }
+        if let Some(ref user) = trigger.user
+            && !config.users.iter().any(|u| u.name == *user)
+        {
+            report.push(CheckResult {
+                name: "trigger_users",
+                severity: Severity::Warning,
+                passed: false,
+                message: format!(
+                    "trigger '{}' references unknown user '{user}'",
+                    trigger.name
+                ),
+            });
+        }
+    }
+
+    let Some(ref webhooks) = config.webhooks else {
+        return;
+    };
+    match webhooks.listen.parse::<std::net::SocketAddr>() {
+        Ok(addr) if !addr.ip().is_loopback() => report.push(CheckResult {
+            name: "webhooks",
+            severity: Severity::Warning,
+            passed: false,
+            message: format!(
+                "webhooks.listen '{}' is not a loopback address; webhook traffic is unencrypted",
+                webhooks.listen
+            ),
+        }),
+        Ok(_) => {}
+        Err(_) => report.push(CheckResult {
+            name: "webhooks",
+            severity: Severity::Error,
+            passed: false,
+            message: format!(
+                "webhooks.listen '{}' is not an address:port (e.g. 127.0.0.1:7448)",
+                webhooks.listen
+            ),
+        }),
+    }
+    let secret_set = std::env::var(&webhooks.secret_env).is_ok_and(|v| !v.trim().is_empty());
+    report.push(CheckResult {
+        name: "webhooks",
+        severity: Severity::Warning,
+        passed: secret_set,
+        message: if secret_set {
+            format!("webhook secret: {} is set", webhooks.secret_env)
+        } else {
+            format!(
+                "webhook secret: {} is not set, so the listener won't start",
+                webhooks.secret_env
+            )
+        },
+    });
+}
+
+/// Report where `redaction.mode` applies.
+fn check_redaction(report: &mut CheckReport, config: &Config) {
+    let mode = config.redaction.mode;
+    report.push(CheckResult {
+        name: "redaction",
+        severity: Severity::Info,
+        passed: true,
+        message: format!(
+            "redaction: memory writes {}, session index and transcripts {}, traces {}",
+            mode.as_str(),
+            mode.non_blocking().as_str(),
+            mode.as_str()
+        ),
+    });
+}
+
+fn check_transcription(report: &mut CheckReport, config: &Config) {
+    let Some(ref transcription) = config.transcription else {
+        return;
+    };
+
+    let mut problems = Vec::new();
+    match transcription.backend {
+        TranscriptionBackend::WhisperCpp => {
+            if !command_exists(&transcription.command) {
+                problems.push(format!(
+                    "whisper.cpp command '{}' not found",
+                    transcription.command
+                ));
+            }
+            match transcription.model.as_deref() {
+                None => problems
+                    .push("transcription.model must point to a whisper.cpp model file".to_owned()),
+                Some(model) if !Path::new(model).is_file() => {
+                    problems.push(format!("whisper.cpp model not found: {model}"));
+                }
+                Some(_) => {}
+            }
+            if !command_exists("ffmpeg") {
+                problems.push("ffmpeg not found; only WAV audio can be transcribed".to_owned());
+            }
+        }
+        TranscriptionBackend::Openai => {
+            let env = transcription.effective_api_key_env();
+            if std::env::var(env).is_err() {
+                problems.push(format!("transcription api key: {env} is not set"));
+            }
+            if let Some(ref base_url) = transcription.base_url
+                && !base_url.starts_with("http://")
+                && !base_url.starts_with("https://")
+            {
+                problems
+                    .push("transcription.base_url must start with http:// or https://".to_owned());
+            }
+        }
+    }
+
+    if problems.is_empty() {
+        report.push(CheckResult {
+            name: "transcription",
+            severity: Severity::Info,
+            passed: true,
+            message: format!(
+                "transcription: {} backend",
+                match transcription.backend {
+                    TranscriptionBackend::WhisperCpp => "whisper.cpp",
+                    TranscriptionBackend::Openai => "openai",
+                }
+            ),
+        });
+    }
+    for message in problems {
+        report.push(CheckResult {
+            name: "transcription",
+            severity: Severity::Warning,
+            passed: false,
+            message,
+        });
+    }
+}
+
 #[allow(clippy::too_many_lines)]
 fn check_cron(report: &mut CheckReport, config: &Config) {
     let mut seen = HashSet::new();
@@ -1662,14 +2153,14 @@ fn check_cron(report: &mut CheckReport, config: &Config) {
     // 13. cron_delivery
     for entry in &config.cron {
         if let Some(ref deliver) = entry.deliver
-            && deliver.channel != "signal"
+            && !matches!(deliver.channel.as_str(), "signal" | "telegram")
         {
             report.push(CheckResult {
                 name: "cron_delivery",
                 severity: Severity::Warning,
                 passed: false,
                 message: format!(
-                    "cron '{}' delivery channel '{}' not supported (only 'signal')",
+                    "cron '{}' delivery channel '{}' not supported (only 'signal' or 'telegram')",
                     entry.name, deliver.channel
                 ),
             });
@@ -1817,6 +2308,194 @@ fn check_web_tools(report: &mut CheckReport, config: &Config) {
     }
 }
 
+fn check_tool_policy(report: &mut CheckReport, config: &Config) {
+    let policy = &config.tools.policy;
+    if *policy == crate::config::ToolPolicyConfig::default() {
+        return;
+    }
+
+    let mut errors = Vec::new();
+    let mut warnings = Vec::new();
+
+    for (label, rule) in policy.labeled_rules() {
+        let allow = rule.allow.as_deref().unwrap_or(&[]);
+        for pattern in allow.iter().chain(&rule.deny).chain(&rule.grant) {
+            if !tool_policy::is_valid_pattern(pattern) {
+                errors.push(format!(
+                    "{label}: '{pattern}' is not a valid tool name pattern \
+                     (letters, digits, '_', '-', '.' and '*' only)"
+                ));
+            }
+        }
+        if rule.allow.as_ref().is_some_and(Vec::is_empty) {
+            warnings.push(format!("{label}: empty allow list disables every tool"));
+        }
+        for pattern in allow.iter().filter(|pattern| rule.deny.contains(pattern)) {
+            warnings.push(format!("{label}: '{pattern}' is both allowed and denied"));
+        }
+    }
+
+    for (name, trust) in [
+        ("inner", TrustLevel::Inner),
+        ("familiar", TrustLevel::Familiar),
+        ("public", TrustLevel::Public),
+    ] {
+        let Some(rule) = policy.configured_for_trust(trust) else {
+            continue;
+        };
+        for pattern in &rule.grant {
+            if tool_policy::is_admin_tool(pattern) {
+                warnings.push(format!(
+                    "tools.policy.{name}: granting '{pattern}' has no effect, \
+                     config and cron tools require full trust"
+                ));
+            }
+        }
+    }
+
+    for (i, group) in policy.groups.iter().enumerate() {
+        if !group.rule.grant.is_empty() {
+            warnings.push(format!(
+                "tools.policy.groups[{i}]: grant has no effect, group rules only narrow access"
+            ));
+        }
+        if group.r#match.is_empty() {
+            errors.push(format!("tools.policy.groups[{i}]: match list is empty"));
+        }
+        for pattern in &group.r#match {
+            if pattern != "*"
+                && !pattern.starts_with("signal:group:")
+                && !pattern.starts_with("telegram:group:")
+            {
+                warnings.push(format!(
+                    "tools.policy.groups[{i}]: match pattern '{pattern}' does not look like a group id \
+                     (expected 'signal:group:<hex>', 'telegram:group:<id>' or '*')"
+                ));
+            }
+        }
+    }
+
+    for message in &errors {
+        report.push(CheckResult {
+            name: "tool_policy",
+            severity: Severity::Error,
+            passed: false,
+            message: message.clone(),
+        });
+    }
+    for message in warnings {
+        report.push(CheckResult {
+            name: "tool_policy",
+            severity: Severity::Warning,
+            passed: false,
+            message,
+        });
+    }
+    if errors.is_empty() {
+        report.push(CheckResult {
+            name: "tool_policy",
+            severity: Severity::Info,
+            passed: true,
+            message: format!(
+                "tools.policy: {} group rule(s) configured",
+                policy.groups.len()
+            ),
+        });
+    }
+}
+
+fn check_mcp(report: &mut CheckReport, config: &Config) {
+    if config.mcp.is_empty() {
+        return;
+    }
+
+    let mut errors = Vec::new();
+    let mut warnings = Vec::new();
+    let mut seen = HashSet::new();
+
+    for server in &config.mcp {
+        let name = server.name.trim();
+        if name.is_empty() {
+            errors.push("mcp server name must not be empty".to_owned());
+            continue;
+        }
+        if !seen.insert(name) {
+            errors.push(format!("duplicate mcp server name '{name}'"));
+        }
+
+        match (&server.command, &server.url) {
+            (Some(_), Some(_)) | (None, None) => {
+                errors.push(format!(
+                    "mcp server '{name}' must set exactly one of 'command' or 'url'"
+                ));
+            }
+            (None, Some(url)) => {
+                if !(url.starts_with("http://") || url.starts_with("https://")) {
+                    errors.push(format!(
+                        "mcp server '{name}' url must start with http:// or https://"
+                    ));
+                }
+            }
+            (Some(command), None) => {
+                if server.enabled && !command_exists(command) {
+                    warnings.push(format!("mcp server '{name}' command not found: {command}"));
+                }
+            }
+        }
+
+        if server.timeout_seconds == 0 {
+            errors.push(format!(
+                "mcp server '{name}' timeout_seconds must be positive"
+            ));
+        }
+
+        for value in server.env.values().chain(server.headers.values()) {
+            if let Some(var) = value.strip_prefix("env:")
+                && std::env::var(var).is_err()
+            {
+                warnings.push(format!(
+                    "mcp server '{name}' references unset env var {var}"
+                ));
+            }
+        }
+    }
+
+    for message in &errors {
+        report.push(CheckResult {
+            name: "mcp",
+            severity: Severity::Error,
+            passed: false,
+            message: message.clone(),
+        });
+    }
+    for message in warnings {
+        report.push(CheckResult {
+            name: "mcp",
+            severity: Severity::Warning,
+            passed: false,
+            message,
+        });
+    }
+    if errors.is_empty() {
+        report.push(CheckResult {
+            name: "mcp",
+            severity: Severity::Info,
+            passed: true,
+            message: format!("{} mcp server(s) configured", config.mcp.len()),
+        });
+    }
+}
+
+/// Whether `command` is a path to an existing file or resolves on `PATH`.
+fn command_exists(command: &str) -> bool {
+    let path = Path::new(command);
+    if path.components().count() > 1 {
+        return path.is_file();
+    }
+    std::env::var_os("PATH")
+        .is_some_and(|paths| std::env::split_paths(&paths).any(|dir| dir.join(command).is_file()))
+}
+
 fn check_binary_exists(report: &mut CheckReport) {
     match std::env::current_exe() {
         Ok(path) => {
@@ -2535,6 +3214,102 @@ mod tests {
         assert!(report.has_errors());
     }
 
+    #[test]
+    fn test_trigger_checks() {
+        let dir = tempfile::tempdir().unwrap();
+        let workspace = dir.path().join("workspace");
+        std::fs::create_dir_all(&workspace).unwrap();
+        std::fs::write(workspace.join("SOUL.md"), "test soul").unwrap();
+
+        let config_path = dir.path().join("coop.toml");
+        std::fs::write(
+            &config_path,
+            format!(
+                "[agent]\nid = \"test\"\nmodel = \"test-model\"\nworkspace = \"{}\"\n\n[[cron]]\nname = \"heartbeat\"\ncron = \"*/30 * * * *\"\nmessage = \"check\"\n\n[[trigger]]\nname = \"heartbeat\"\nwatch = \"inbox/*.md\"\n\n[[trigger]]\nname = \"escape\"\nwatch = \"../*.md\"\n\n[[trigger]]\nname = \"doorbell\"\nwebhook = true\n",
+                workspace.display()
+            ),
+        )
+        .unwrap();
+
+        let report = validate_config(&config_path, dir.path());
+        let failed = |name: &str| {
+            report
+                .results
+                .iter()
+                .filter(|r| r.name == name && !r.passed)
+                .map(|r| r.message.clone())
+                .collect::<Vec<_>>()
+        };
+        assert!(failed("trigger_names")[0].contains("heartbeat"));
+        let sources = failed("trigger_source");
+        assert_eq!(sources.len(), 2);
+        assert!(sources[0].contains("escape"));
+        assert!(sources[1].contains("[webhooks]"));
+        assert!(report.has_errors());
+    }
+
+    #[test]
+    fn test_transcription_checks() {
+        let dir = tempfile::tempdir().unwrap();
+        let workspace = dir.path().join("workspace");
+        std::fs::create_dir_all(&workspace).unwrap();
+        std::fs::write(workspace.join("SOUL.md"), "test soul").unwrap();
+
+        let config_path = dir.path().join("coop.toml");
+        std::fs::write(
+            &config_path,
+            format!(
+                "[agent]\nid = \"test\"\nmodel = \"test-model\"\nworkspace = \"{}\"\n\n[transcription]\nbackend = \"whisper_cpp\"\ncommand = \"/nonexistent/whisper-cli\"\nmodel = \"/nonexistent/ggml-base.bin\"\n",
+                workspace.display()
+            ),
+        )
+        .unwrap();
+
+        let report = validate_config(&config_path, dir.path());
+        let failed: Vec<_> = report
+            .results
+            .iter()
+            .filter(|r| r.name == "transcription" && !r.passed)
+            .map(|r| r.message.as_str())
+            .collect();
+        assert!(
+            failed
+                .iter()
+                .any(|m| m.contains("/nonexistent/whisper-cli"))
+        );
+        assert!(failed.iter().any(|m| m.contains("model not found")));
+    }
+
+    #[test]
+    fn test_redaction_reports_trace_masking() {
+        let dir = tempfile::tempdir().unwrap();
+        let workspace = dir.path().join("workspace");
+        std::fs::create_dir_all(&workspace).unwrap();
+        std::fs::write(workspace.join("SOUL.md"), "test soul").unwrap();
+
+        let config_path = dir.path().join("coop.toml");
+        std::fs::write(
+            &config_path,
+            format!(
+                "[agent]\nid = \"test\"\nmodel = \"test-model\"\nworkspace = \"{}\"\n\n[redaction]\nmode = \"block\"\n",
+                workspace.display()
+            ),
+        )
+        .unwrap();
+
+        let report = validate_config(&config_path, dir.path());
+        let check = report
+            .results
+            .iter()
+            .find(|r| r.name == "redaction")
+            .unwrap();
+        assert!(check.passed);
+        assert_eq!(
+            check.message,
+            "redaction: memory writes block, session index and transcripts mask, traces block"
+        );
+    }
+
     #[test]
     fn test_invalid_user_timezone() {
         let dir = tempfile::tempdir().unwrap();
@@ -3038,7 +3813,7 @@ mod tests {
             .iter()
             .filter(|r| r.name == "groups")
             .collect();
-        // Should have the "groups configured but no signal channel" warning
+        // Should have the "groups configured but no chat channel" warning
         // and the summary, but no errors
         let errors: Vec<_> = checks
             .iter()
@@ -3050,6 +3825,61 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_agents_with_bindings_pass() {
+        let dir = tempfile::tempdir().unwrap();
+        let work = dir.path().join("work");
+        std::fs::create_dir_all(&work).unwrap();
+        let config_path = write_config_with_groups(
+            dir.path(),
+            &format!(
+                "[[agents]]\nid = \"work\"\nmodel = \"test-model\"\nworkspace = \"{}\"\n\n[[users]]\nname = \"alice\"\ntrust = \"full\"\nmatch = []\nagent = \"work\"\n\n[[groups]]\nmatch = [\"signal:group:aabb\"]\ntrigger = \"always\"\nagent = \"test\"\n",
+                work.display()
+            ),
+        );
+        let report = validate_config(&config_path, dir.path());
+        let failures: Vec<_> = report
+            .results
+            .iter()
+            .filter(|r| r.name.starts_with("agent") && !r.passed)
+            .collect();
+        assert!(failures.is_empty(), "unexpected failures: {failures:?}");
+        assert!(
+            report
+                .results
+                .iter()
+                .any(|r| r.name == "agents" && r.message == "2 agent(s): test, work")
+        );
+    }
+
+    #[test]
+    fn test_agents_reject_duplicates_and_unknown_bindings() {
+        let dir = tempfile::tempdir().unwrap();
+        let config_path = write_config_with_groups(
+            dir.path(),
+            "[[agents]]\nid = \"test\"\nmodel = \"test-model\"\n\n[[agents]]\nid = \"work\"\nmodel = \"test-model\"\nworkspace = \"./missing\"\n\n[[users]]\nname = \"alice\"\ntrust = \"full\"\nmatch = []\nagent = \"nobody\"\n",
+        );
+        let report = validate_config(&config_path, dir.path());
+        let failed = |name: &str| {
+            report
+                .results
+                .iter()
+                .find(|r| r.name == name && !r.passed)
+                .map(|r| r.message.clone())
+        };
+        assert!(
+            failed("agents")
+                .unwrap()
+                .contains("duplicate agent ids: test")
+        );
+        assert!(failed("agents_workspace").is_some());
+        assert!(
+            failed("agent_bindings")
+                .unwrap()
+                .contains("unknown agent 'nobody'")
+        );
+    }
+
     #[test]
     fn test_group_empty_match_fails() {
         let dir = tempfile::tempdir().unwrap();
@@ -3149,10 +3979,40 @@ mod tests {
         let check = report
             .results
             .iter()
-            .find(|r| r.name == "groups" && r.message.contains("no signal channel"));
+            .find(|r| r.name == "groups" && r.message.contains("no chat channel"));
         assert!(check.is_some(), "groups without signal should warn");
     }
 
+    #[test]
+    fn test_telegram_group_pattern_accepted() {
+        let dir = tempfile::tempdir().unwrap();
+        let config_path = write_config_with_groups(
+            dir.path(),
+            "[channels.telegram]\ntoken_env = \"COOP_TEST_UNSET_TELEGRAM_TOKEN\"\n\n\
+             [[groups]]\nmatch = [\"telegram:group:-100123\"]\ntrigger = \"always\"\n",
+        );
+        let report = validate_config(&config_path, dir.path());
+        assert!(
+            !report
+                .results
+                .iter()
+                .any(|r| r.name == "groups" && !r.passed),
+            "telegram group pattern should be accepted: {:?}",
+            report.results
+        );
+        let token = report
+            .results
+            .iter()
+            .find(|r| r.name == "telegram_channel")
+            .unwrap();
+        assert!(!token.passed);
+        assert!(
+            token
+                .message
+                .contains("COOP_TEST_UNSET_TELEGRAM_TOKEN is not set")
+        );
+    }
+
     #[test]
     fn test_group_duplicate_match_warns() {
         let dir = tempfile::tempdir().unwrap();
@@ -3220,4 +4080,184 @@ mod tests {
         assert!(!check.passed);
         assert!(check.message.contains("missing-model"));
     }
+
+    fn write_config_with_mcp(dir: &Path, mcp_toml: &str) -> std::path::PathBuf {
+        let workspace = dir.join("workspace");
+        std::fs::create_dir_all(&workspace).unwrap();
+        std::fs::write(workspace.join("SOUL.md"), "test soul").unwrap();
+
+        let config_path = dir.join("coop.toml");
+        std::fs::write(
+            &config_path,
+            format!(
+                "[agent]\nid = \"test\"\nmodel = \"test-model\"\nworkspace = \"{}\"\n\n[provider]\nname = \"anthropic\"\n\n{mcp_toml}\n",
+                workspace.display()
+            ),
+        )
+        .unwrap();
+        config_path
+    }
+
+    #[test]
+    fn test_valid_mcp_config_passes() {
+        let dir = tempfile::tempdir().unwrap();
+        let config_path = write_config_with_mcp(
+            dir.path(),
+            "[[mcp]]\nname = \"fs\"\ncommand = \"sh\"\nargs = [\"-c\", \"true\"]\n\n[[mcp]]\nname = \"remote\"\nurl = \"https://example.com/mcp\"\n",
+        );
+        let report = validate_config(&config_path, dir.path());
+        let errors = non_env_errors(&report);
+        assert!(errors.is_empty(), "expected no config errors: {errors:?}");
+        assert!(
+            report
+                .results
+                .iter()
+                .any(|r| r.name == "mcp" && r.passed && r.message.contains("2 mcp server"))
+        );
+    }
+
+    #[test]
+    fn test_invalid_mcp_config_fails() {
+        let dir = tempfile::tempdir().unwrap();
+        let config_path = write_config_with_mcp(
+            dir.path(),
+            "[[mcp]]\nname = \"a\"\ncommand = \"sh\"\nurl = \"https://example.com\"\n\n[[mcp]]\nname = \"b\"\nurl = \"ftp://example.com\"\ntimeout_seconds = 0\n\n[[mcp]]\nname = \"b\"\ncommand = \"definitely-not-a-real-mcp-binary\"\n",
+        );
+        let report = validate_config(&config_path, dir.path());
+        let failures: Vec<_> = report
+            .results
+            .iter()
+            .filter(|r| r.name == "mcp" && !r.passed)
+            .map(|r| (r.severity, r.message.as_str()))
+            .collect();
+        assert!(failures.contains(&(
+            Severity::Error,
+            "mcp server 'a' must set exactly one of 'command' or 'url'"
+        )));
+        assert!(failures.contains(&(
+            Severity::Error,
+            "mcp server 'b' url must start with http:// or https://"
+        )));
+        assert!(failures.contains(&(
+            Severity::Error,
+            "mcp server 'b' timeout_seconds must be positive"
+        )));
+        assert!(failures.contains(&(Severity::Error, "duplicate mcp server name 'b'")));
+        assert!(failures.contains(&(
+            Severity::Warning,
+            "mcp server 'b' command not found: definitely-not-a-real-mcp-binary"
+        )));
+        assert!(report.has_errors());
+    }
+
+    #[test]
+    fn test_tool_policy_validated() {
+        let dir = tempfile::tempdir().unwrap();
+        let config_path = write_config_with_mcp(
+            dir.path(),
+            "[tools.policy.public]\nallow = [\"read_file\", \"web search\"]\ndeny = [\"read_file\"]\n\n[tools.policy.familiar]\nallow = []\ngrant = [\"cron_*\"]\n\n[[tools.policy.groups]]\nmatch = [\"family\"]\ndeny = [\"bash\"]\ngrant = [\"bash\"]\n",
+        );
+        let report = validate_config(&config_path, dir.path());
+        let failures: Vec<_> = report
+            .results
+            .iter()
+            .filter(|r| r.name == "tool_policy" && !r.passed)
+            .map(|r| (r.severity, r.message.as_str()))
+            .collect();
+        assert!(failures.contains(&(
+            Severity::Error,
+            "tools.policy.public: 'web search' is not a valid tool name pattern \
+             (letters, digits, '_', '-', '.' and '*' only)"
+        )));
+        assert!(failures.contains(&(
+            Severity::Warning,
+            "tools.policy.public: 'read_file' is both allowed and denied"
+        )));
+        assert!(failures.contains(&(
+            Severity::Warning,
+            "tools.policy.familiar: empty allow list disables every tool"
+        )));
+        assert!(failures.contains(&(
+            Severity::Warning,
+            "tools.policy.familiar: granting 'cron_*' has no effect, \
+             config and cron tools require full trust"
+        )));
+        assert!(failures.contains(&(
+            Severity::Warning,
+            "tools.policy.groups[0]: grant has no effect, group rules only narrow access"
+        )));
+        assert!(failures.iter().any(|(severity, message)| {
+            *severity == Severity::Warning
+                && message.starts_with("tools.policy.groups[0]: match pattern 'family'")
+        }));
+        assert!(report.has_errors());
+    }
+
+    #[test]
+    fn test_remote_ipc_validated() {
+        let dir = tempfile::tempdir().unwrap();
+        let config_path = write_config_with_mcp(
+            dir.path(),
+            "[ipc.remote]\nlisten = \"tailnet:7447\"\n\n[[users]]\nname = \"bob\"\ntrust = \"inner\"\nmatch = []\nipc_token_env = \"COOP_TEST_UNSET_IPC_TOKEN\"\n",
+        );
+        let report = validate_config(&config_path, dir.path());
+        let failures: Vec<_> = report
+            .results
+            .iter()
+            .filter(|r| r.name == "ipc_remote" && !r.passed)
+            .map(|r| (r.severity, r.message.as_str()))
+            .collect();
+        assert!(failures.contains(&(
+            Severity::Error,
+            "ipc.remote.listen 'tailnet:7447' is not an address:port (e.g. 100.64.0.2:7447)"
+        )));
+        assert!(failures.contains(&(
+            Severity::Warning,
+            "user 'bob' ipc token: COOP_TEST_UNSET_IPC_TOKEN is not set"
+        )));
+    }
+
+    #[test]
+    fn test_budgets_validated() {
+        let dir = tempfile::tempdir().unwrap();
+        let config_path = write_config_with_mcp(
+            dir.path(),
+            "[[users]]\nname = \"bob\"\ntrust = \"inner\"\nmatch = []\nbudget = { daily_usd = 1.0, warn_at = 1.5, on_exhausted = \"downgrade\" }\n\n[[cron]]\nname = \"digest\"\ncron = \"0 8 * * *\"\nmessage = \"digest\"\nbudget = {}\n",
+        );
+        let report = validate_config(&config_path, dir.path());
+        let failures: Vec<_> = report
+            .results
+            .iter()
+            .filter(|r| r.name == "budgets" && !r.passed)
+            .map(|r| r.message.as_str())
+            .collect();
+        assert_eq!(
+            failures,
+            vec![
+                "user 'bob' budget: warn_at 1.5 must be in (0, 1]",
+                "user 'bob' budget: on_exhausted = \"downgrade\" needs downgrade_model",
+                "cron 'digest' budget: no daily or monthly limit is set",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unknown_fallback_model_is_an_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let config_path = write_config_with_mcp(
+            dir.path(),
+            "[[users]]\nname = \"bob\"\ntrust = \"inner\"\nmatch = []\nfallback = [\"no-such-model\"]\n",
+        );
+        let report = validate_config(&config_path, dir.path());
+        let failures: Vec<_> = report
+            .results
+            .iter()
+            .filter(|r| r.name == "fallbacks" && !r.passed)
+            .map(|r| r.message.as_str())
+            .collect();
+        assert_eq!(
+            failures,
+            vec!["user 'bob' fallback 'no-such-model' is not an available model"]
+        );
+    }
 }
@@ -350,7 +350,7 @@ impl ToolExecutor for ConfigToolExecutor {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::config::{CronConfig, SandboxConfig};
+    use crate::config::{CronCatchUp, CronConfig, SandboxConfig};
     use coop_core::SessionKind;
     use std::path::Path;
 
@@ -385,6 +385,12 @@ mod tests {
             r#match: vec!["signal:alice-uuid".to_owned()],
             timezone: None,
             sandbox: None,
+            agent: None,
+            ipc_token_env: None,
+            budget: None,
+            fallback: Vec::new(),
+            routing: None,
+            transcribe: None,
         }
     }
 
@@ -396,6 +402,12 @@ mod tests {
             r#match: vec!["signal:bob-uuid".to_owned()],
             timezone: None,
             sandbox: None,
+            agent: None,
+            ipc_token_env: None,
+            budget: None,
+            fallback: Vec::new(),
+            routing: None,
+            transcribe: None,
         }
     }
 
@@ -511,6 +523,9 @@ mod tests {
             deliver: None,
             review_prompt: None,
             sandbox: None,
+            budget: None,
+            fallback: Vec::new(),
+            catch_up: CronCatchUp::None,
         }];
         let mut proposed = current.clone();
         proposed.cron[0].sandbox = Some(SandboxOverrides {
@@ -577,6 +592,9 @@ mod tests {
             deliver: None,
             review_prompt: None,
             sandbox: None,
+            budget: None,
+            fallback: Vec::new(),
+            catch_up: CronCatchUp::None,
         }];
         let mut proposed = current.clone();
         proposed.cron[0].message = "updated check".to_owned();
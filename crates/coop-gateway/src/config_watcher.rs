@@ -11,21 +11,32 @@ use crate::config_check;
 /// hot-swaps the `SharedConfig` when the file is modified.
 ///
 /// Fields that require a process restart (`agent.id`, `agent.workspace`,
-/// `provider.name`, `channels`, `memory.db_path`, `memory.embedding`) are
-/// guarded — the reload is rejected if any of those change.
+/// `provider.name`, `channels`, `ipc`, `webhooks`, `memory.db_path`,
+/// `memory.embedding`) are guarded — the reload is rejected if any of those change.
+///
+/// Each `(agent id, view)` in `agent_views` is re-projected from the new
+/// config with [`Config::for_agent`] after a successful reload.
 ///
 /// If `cron_notify` is provided, it is notified whenever cron entries change
 /// so the scheduler can wake from its sleep and re-evaluate.
 pub(crate) fn spawn_config_watcher(
     config_path: PathBuf,
     config: SharedConfig,
+    agent_views: Vec<(String, SharedConfig)>,
     shutdown: CancellationToken,
     cron_notify: Option<Arc<tokio::sync::Notify>>,
 ) -> tokio::task::JoinHandle<()> {
     let span = info_span!("config_watcher", path = %config_path.display());
     tokio::spawn(
         async move {
-            config_poll_loop(&config_path, &config, shutdown, cron_notify.as_deref()).await;
+            config_poll_loop(
+                &config_path,
+                &config,
+                &agent_views,
+                shutdown,
+                cron_notify.as_deref(),
+            )
+            .await;
         }
         .instrument(span),
     )
@@ -37,6 +48,7 @@ const DEBOUNCE: Duration = Duration::from_millis(200);
 async fn config_poll_loop(
     config_path: &Path,
     config: &SharedConfig,
+    agent_views: &[(String, SharedConfig)],
     shutdown: CancellationToken,
     cron_notify: Option<&tokio::sync::Notify>,
 ) {
@@ -62,7 +74,7 @@ async fn config_poll_loop(
         last_hash = file_content_hash(config_path);
 
         let old_cron = config.load().cron.clone();
-        try_reload(config_path, config);
+        try_reload(config_path, config, agent_views);
 
         if let Some(notify) = cron_notify
             && config.load().cron != old_cron
@@ -83,7 +95,7 @@ fn file_content_hash(path: &Path) -> u64 {
     hasher.finish()
 }
 
-fn try_reload(config_path: &Path, config: &SharedConfig) {
+fn try_reload(config_path: &Path, config: &SharedConfig, agent_views: &[(String, SharedConfig)]) {
     let new_config = match Config::load(config_path) {
         Ok(c) => c,
         Err(e) => {
@@ -132,6 +144,11 @@ fn try_reload(config_path: &Path, config: &SharedConfig) {
     }
 
     let changed = diff_sections(&current, &new_config);
+    for (agent_id, view) in agent_views {
+        if let Some(projected) = new_config.for_agent(agent_id) {
+            view.store(Arc::new(projected));
+        }
+    }
     config.store(Arc::new(new_config));
     info!(changed = ?changed, "config reloaded");
 }
@@ -149,6 +166,12 @@ fn check_restart_only_fields(current: &Config, new: &Config) -> Option<Vec<&'sta
     if new.agent.workspace != current.agent.workspace {
         reasons.push("agent.workspace");
     }
+    if new.agent.memory_db != current.agent.memory_db {
+        reasons.push("agent.memory_db");
+    }
+    if agents_restart_fields(&new.agents) != agents_restart_fields(&current.agents) {
+        reasons.push("agents");
+    }
     if new.providers != current.providers {
         reasons.push("providers");
     }
@@ -167,6 +190,18 @@ fn check_restart_only_fields(current: &Config, new: &Config) -> Option<Vec<&'sta
     if new.sandbox.enabled != current.sandbox.enabled {
         reasons.push("sandbox.enabled");
     }
+    if new.mcp != current.mcp {
+        reasons.push("mcp");
+    }
+    if new.ipc != current.ipc {
+        reasons.push("ipc");
+    }
+    if new.webhooks != current.webhooks {
+        reasons.push("webhooks");
+    }
+    if new.sessions != current.sessions {
+        reasons.push("sessions");
+    }
 
     if reasons.is_empty() {
         None
@@ -175,6 +210,24 @@ fn check_restart_only_fields(current: &Config, new: &Config) -> Option<Vec<&'sta
     }
 }
 
+/// The parts of `[[agents]]` that are fixed at startup: which agents exist,
+/// and each one's context limit, workspace, and memory database.
+fn agents_restart_fields(
+    agents: &[crate::config::AgentConfig],
+) -> Vec<(&str, Option<usize>, &str, Option<&str>)> {
+    agents
+        .iter()
+        .map(|agent| {
+            (
+                agent.id.as_str(),
+                agent.context_limit,
+                agent.workspace.as_str(),
+                agent.memory_db.as_deref(),
+            )
+        })
+        .collect()
+}
+
 /// Summarize which top-level sections changed.
 fn diff_sections(current: &Config, new: &Config) -> Vec<&'static str> {
     let mut changed = Vec::new();
@@ -205,9 +258,15 @@ fn diff_sections(current: &Config, new: &Config) -> Vec<&'static str> {
     if new.agent.subagents != current.agent.subagents {
         changed.push("agent.subagents");
     }
+    if new.agents != current.agents {
+        changed.push("agents");
+    }
     if new.cron != current.cron {
         changed.push("cron");
     }
+    if new.tools.policy != current.tools.policy {
+        changed.push("tools.policy");
+    }
     changed
 }
 
@@ -270,6 +329,49 @@ mod tests {
         assert!(reasons.contains(&"sandbox.enabled"));
     }
 
+    #[test]
+    fn check_restart_only_rejects_remote_ipc_change() {
+        let ws = "/tmp/ws";
+        let a: Config = toml::from_str(&minimal_toml("a", "m", ws)).unwrap();
+        let mut b: Config = toml::from_str(&minimal_toml("a", "m", ws)).unwrap();
+        b.ipc.remote = Some(crate::config::RemoteIpcConfig {
+            listen: "127.0.0.1:7447".to_owned(),
+        });
+        let reasons = check_restart_only_fields(&a, &b).unwrap();
+        assert!(reasons.contains(&"ipc"));
+
+        let mut c: Config = toml::from_str(&minimal_toml("a", "m", ws)).unwrap();
+        c.webhooks = Some(crate::config::WebhooksConfig {
+            listen: "127.0.0.1:7448".to_owned(),
+            secret_env: "COOP_WEBHOOK_SECRET".to_owned(),
+        });
+        let reasons = check_restart_only_fields(&a, &c).unwrap();
+        assert!(reasons.contains(&"webhooks"));
+    }
+
+    #[test]
+    fn check_restart_only_rejects_session_store_change() {
+        let ws = "/tmp/ws";
+        let a: Config = toml::from_str(&minimal_toml("a", "m", ws)).unwrap();
+        let mut b: Config = toml::from_str(&minimal_toml("a", "m", ws)).unwrap();
+        b.sessions.store = crate::config::SessionStoreKind::Sqlite;
+        let reasons = check_restart_only_fields(&a, &b).unwrap();
+        assert!(reasons.contains(&"sessions"));
+    }
+
+    #[test]
+    fn check_restart_only_rejects_mcp_change() {
+        let ws = "/tmp/ws";
+        let a: Config = toml::from_str(&minimal_toml("a", "m", ws)).unwrap();
+        let b: Config = toml::from_str(&format!(
+            "{}\n[[mcp]]\nname = \"fs\"\ncommand = \"mcp-fs\"\n",
+            minimal_toml("a", "m", ws)
+        ))
+        .unwrap();
+        let reasons = check_restart_only_fields(&a, &b).unwrap();
+        assert!(reasons.contains(&"mcp"));
+    }
+
     #[test]
     fn check_restart_only_rejects_workspace_change() {
         let a: Config = toml::from_str(&minimal_toml("a", "m", "/ws1")).unwrap();
@@ -368,6 +470,18 @@ mod tests {
         assert!(changed.is_empty());
     }
 
+    #[test]
+    fn diff_sections_detects_tool_policy_change() {
+        let a: Config = toml::from_str("[agent]\nid = \"a\"\nmodel = \"m\"\n").unwrap();
+        let b: Config = toml::from_str(
+            "[agent]\nid = \"a\"\nmodel = \"m\"\n\n[tools.policy.familiar]\ndeny = [\"bash\"]\n",
+        )
+        .unwrap();
+        assert!(check_restart_only_fields(&a, &b).is_none());
+        let changed = diff_sections(&a, &b);
+        assert!(changed.contains(&"tools.policy"));
+    }
+
     #[test]
     fn diff_sections_detects_prompt_change() {
         let a: Config = toml::from_str("[agent]\nid = \"a\"\nmodel = \"m\"\n").unwrap();
@@ -389,7 +503,7 @@ mod tests {
 
         // Overwrite with garbage
         fs::write(&path, "{{not toml").unwrap();
-        try_reload(&path, &config);
+        try_reload(&path, &config, &[]);
 
         // Config should be unchanged
         assert_eq!(config.load().agent.id, "test");
@@ -406,7 +520,7 @@ mod tests {
         // Change agent.id (restart-only)
         let new_toml = minimal_toml("changed", "test-model", &ws.display().to_string());
         fs::write(&path, &new_toml).unwrap();
-        try_reload(&path, &config);
+        try_reload(&path, &config, &[]);
 
         // Config should be unchanged
         assert_eq!(config.load().agent.id, "test");
@@ -431,7 +545,7 @@ mod tests {
             ws.display()
         );
         fs::write(&path, &new_toml).unwrap();
-        try_reload(&path, &config);
+        try_reload(&path, &config, &[]);
 
         // Config should be updated
         assert_eq!(config.load().users.len(), 2);
@@ -450,11 +564,66 @@ mod tests {
         fs::write(&path, &toml_str).unwrap();
         // This should not log "config reloaded" (no way to assert that here,
         // but it exercises the identical-content code path)
-        try_reload(&path, &config);
+        try_reload(&path, &config, &[]);
 
         assert_eq!(config.load().agent.id, "test");
     }
 
+    #[test]
+    fn check_restart_only_rejects_agents_workspace_change() {
+        let ws = "/tmp/ws";
+        let base = minimal_toml("a", "m", ws);
+        let a: Config = toml::from_str(&format!(
+            "{base}\n[[agents]]\nid = \"work\"\nmodel = \"m\"\n"
+        ))
+        .unwrap();
+        let b: Config = toml::from_str(&format!(
+            "{base}\n[[agents]]\nid = \"work\"\nmodel = \"m\"\nworkspace = \"/tmp/work\"\n"
+        ))
+        .unwrap();
+        let reasons = check_restart_only_fields(&a, &b).unwrap();
+        assert!(reasons.contains(&"agents"));
+
+        let c: Config = toml::from_str(&format!(
+            "{base}\n[[agents]]\nid = \"work\"\nmodel = \"m2\"\n"
+        ))
+        .unwrap();
+        assert!(check_restart_only_fields(&a, &c).is_none());
+        assert!(diff_sections(&a, &c).contains(&"agents"));
+    }
+
+    #[test]
+    fn try_reload_reprojects_agent_views() {
+        let dir = tempfile::tempdir().unwrap();
+        let ws = setup_workspace(dir.path());
+        let base = minimal_toml("test", "test-model", &ws.display().to_string());
+        let work_agent = format!(
+            "[[agents]]\nid = \"work\"\nworkspace = \"{}\"\n",
+            ws.display()
+        );
+        let path = write_config(
+            dir.path(),
+            &format!("{base}\n{work_agent}model = \"work-model\"\n"),
+        );
+        let loaded = Config::load(&path).unwrap();
+        let work = shared_config(loaded.for_agent("work").unwrap());
+        let config = shared_config(loaded);
+
+        fs::write(
+            &path,
+            format!(
+                "{base}\n{work_agent}model = \"work-model-2\"\n\n[[users]]\nname = \"alice\"\ntrust = \"full\"\nmatch = []\nagent = \"work\"\n"
+            ),
+        )
+        .unwrap();
+        try_reload(&path, &config, &[("work".to_owned(), Arc::clone(&work))]);
+
+        assert_eq!(work.load().agent.id, "work");
+        assert_eq!(work.load().agent.model, "work-model-2");
+        assert_eq!(work.load().users.len(), 1);
+        assert_eq!(config.load().agent.model, "test-model");
+    }
+
     #[tokio::test]
     async fn poll_loop_detects_change() {
         let dir = tempfile::tempdir().unwrap();
@@ -467,8 +636,13 @@ mod tests {
         let config = shared_config(Config::load(&path).unwrap());
         let shutdown = CancellationToken::new();
 
-        let handle =
-            spawn_config_watcher(path.clone(), Arc::clone(&config), shutdown.clone(), None);
+        let handle = spawn_config_watcher(
+            path.clone(),
+            Arc::clone(&config),
+            Vec::new(),
+            shutdown.clone(),
+            None,
+        );
 
         // Wait for the watcher to start
         tokio::time::sleep(Duration::from_millis(100)).await;
@@ -505,6 +679,7 @@ mod tests {
         let handle = spawn_config_watcher(
             path.clone(),
             Arc::clone(&config),
+            Vec::new(),
             shutdown.clone(),
             Some(Arc::clone(&notify)),
         );
@@ -545,6 +720,7 @@ mod tests {
         let handle = spawn_config_watcher(
             path.clone(),
             Arc::clone(&config),
+            Vec::new(),
             shutdown.clone(),
             Some(Arc::clone(&notify)),
         );
@@ -584,7 +760,13 @@ mod tests {
         let config = shared_config(Config::load(&path).unwrap());
         let shutdown = CancellationToken::new();
 
-        let handle = spawn_config_watcher(path, Arc::clone(&config), shutdown.clone(), None);
+        let handle = spawn_config_watcher(
+            path,
+            Arc::clone(&config),
+            Vec::new(),
+            shutdown.clone(),
+            None,
+        );
 
         tokio::time::sleep(Duration::from_millis(100)).await;
         shutdown.cancel();
@@ -0,0 +1,258 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
+use rusqlite::{Connection, OptionalExtension, params};
+use std::fmt::Write as _;
+use std::path::Path;
+use std::sync::Mutex;
+
+/// History file in each agent's workspace.
+pub(crate) const CRON_HISTORY_DB_FILE: &str = "cron_history.db";
+
+/// One finished cron execution.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct CronRun {
+    pub cron_name: String,
+    /// `scheduled`, `catch_up`, `manual`, `file_watch` or `webhook`.
+    pub trigger: String,
+    /// Fire time the run was for; `None` for manual runs.
+    pub scheduled_for: Option<DateTime<Utc>>,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: DateTime<Utc>,
+    /// A `CronTriggerStatus` name, or `failed`.
+    pub status: String,
+    pub error: Option<String>,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub delivered_to: usize,
+    pub attempted_to: usize,
+}
+
+/// Append-only record of every cron run an agent makes.
+pub(crate) struct CronHistory {
+    conn: Mutex<Connection>,
+}
+
+// Each method holds the connection for exactly one statement.
+#[allow(clippy::significant_drop_tightening)]
+impl CronHistory {
+    pub(crate) fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).with_context(|| {
+                format!("failed to create cron history dir: {}", parent.display())
+            })?;
+        }
+        let conn = Connection::open(path)
+            .with_context(|| format!("failed to open cron history db: {}", path.display()))?;
+        conn.execute_batch(
+            "
+            PRAGMA journal_mode = WAL;
+            PRAGMA synchronous = NORMAL;
+            PRAGMA busy_timeout = 5000;
+
+            CREATE TABLE IF NOT EXISTS runs (
+                id             INTEGER PRIMARY KEY,
+                cron_name      TEXT NOT NULL,
+                trigger        TEXT NOT NULL,
+                scheduled_for  INTEGER,
+                started_at     INTEGER NOT NULL,
+                finished_at    INTEGER NOT NULL,
+                status         TEXT NOT NULL,
+                error          TEXT,
+                input_tokens   INTEGER NOT NULL,
+                output_tokens  INTEGER NOT NULL,
+                delivered_to   INTEGER NOT NULL,
+                attempted_to   INTEGER NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS idx_runs_name ON runs(cron_name, started_at);
+            ",
+        )
+        .with_context(|| format!("failed to initialize cron history db: {}", path.display()))?;
+        Ok(Self {
+            conn: Mutex::new(conn),
+        })
+    }
+
+    pub(crate) fn record(&self, run: &CronRun) -> Result<()> {
+        let conn = self.conn.lock().expect("cron history mutex poisoned");
+        conn.prepare_cached(
+            "INSERT INTO runs (
+                cron_name, trigger, scheduled_for, started_at, finished_at, status, error,
+                input_tokens, output_tokens, delivered_to, attempted_to
+             ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+        )?
+        .execute(params![
+            run.cron_name,
+            run.trigger,
+            run.scheduled_for.map(|at| at.timestamp_millis()),
+            run.started_at.timestamp_millis(),
+            run.finished_at.timestamp_millis(),
+            run.status,
+            run.error,
+            i64::try_from(run.input_tokens).unwrap_or(i64::MAX),
+            i64::try_from(run.output_tokens).unwrap_or(i64::MAX),
+            i64::try_from(run.delivered_to).unwrap_or(i64::MAX),
+            i64::try_from(run.attempted_to).unwrap_or(i64::MAX),
+        ])?;
+        Ok(())
+    }
+
+    /// Most recent runs first, optionally for one job.
+    pub(crate) fn recent(&self, cron_name: Option<&str>, limit: usize) -> Result<Vec<CronRun>> {
+        let conn = self.conn.lock().expect("cron history mutex poisoned");
+        let mut stmt = conn.prepare_cached(
+            "SELECT cron_name, trigger, scheduled_for, started_at, finished_at, status, error,
+                    input_tokens, output_tokens, delivered_to, attempted_to
+             FROM runs
+             WHERE ?1 IS NULL OR cron_name = ?1
+             ORDER BY started_at DESC, id DESC
+             LIMIT ?2",
+        )?;
+        let runs = stmt
+            .query_map(
+                params![cron_name, i64::try_from(limit).unwrap_or(i64::MAX)],
+                |row| {
+                    let count = |index| {
+                        row.get::<_, i64>(index)
+                            .map(|count| u64::try_from(count).unwrap_or(0))
+                    };
+                    Ok(CronRun {
+                        cron_name: row.get(0)?,
+                        trigger: row.get(1)?,
+                        scheduled_for: row
+                            .get::<_, Option<i64>>(2)?
+                            .and_then(DateTime::from_timestamp_millis),
+                        started_at: timestamp(row.get(3)?),
+                        finished_at: timestamp(row.get(4)?),
+                        status: row.get(5)?,
+                        error: row.get(6)?,
+                        input_tokens: count(7)?,
+                        output_tokens: count(8)?,
+                        delivered_to: usize::try_from(count(9)?).unwrap_or(0),
+                        attempted_to: usize::try_from(count(10)?).unwrap_or(0),
+                    })
+                },
+            )?
+            .collect::<Result<_, _>>()?;
+        Ok(runs)
+    }
+
+    /// Latest fire time `cron_name` ran for, scheduled or caught up.
+    pub(crate) fn last_scheduled(&self, cron_name: &str) -> Result<Option<DateTime<Utc>>> {
+        let conn = self.conn.lock().expect("cron history mutex poisoned");
+        let last: Option<i64> = conn
+            .prepare_cached("SELECT MAX(scheduled_for) FROM runs WHERE cron_name = ?1")?
+            .query_row(params![cron_name], |row| row.get(0))
+            .optional()?
+            .flatten();
+        Ok(last.and_then(DateTime::from_timestamp_millis))
+    }
+}
+
+fn timestamp(millis: i64) -> DateTime<Utc> {
+    DateTime::from_timestamp_millis(millis).unwrap_or_default()
+}
+
+/// Render runs as plain text for the CLI and the `cron_history` tool.
+pub(crate) fn format_history(runs: &[CronRun], timezone: Tz) -> String {
+    if runs.is_empty() {
+        return "No cron runs recorded.\n".to_owned();
+    }
+    let mut out = String::new();
+    for run in runs {
+        let duration = (run.finished_at - run.started_at).num_seconds();
+        let _ = write!(
+            out,
+            "{}  {:<20} {:<10} {:<17} {:>4}s {:>8} in {:>7} out",
+            run.started_at
+                .with_timezone(&timezone)
+                .format("%Y-%m-%d %H:%M %Z"),
+            run.cron_name,
+            run.trigger,
+            run.status,
+            duration,
+            run.input_tokens,
+            run.output_tokens,
+        );
+        if run.attempted_to > 0 {
+            let _ = write!(out, "  delivered {}/{}", run.delivered_to, run.attempted_to);
+        }
+        if let Some(ref scheduled_for) = run.scheduled_for
+            && run.trigger == "catch_up"
+        {
+            let _ = write!(
+                out,
+                "  (missed {})",
+                scheduled_for
+                    .with_timezone(&timezone)
+                    .format("%Y-%m-%d %H:%M")
+            );
+        }
+        if let Some(ref error) = run.error {
+            let _ = write!(out, "  error: {error}");
+        }
+        out.push('\n');
+    }
+    out
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn run(name: &str, trigger: &str, at: DateTime<Utc>, status: &str) -> CronRun {
+        CronRun {
+            cron_name: name.to_owned(),
+            trigger: trigger.to_owned(),
+            scheduled_for: (trigger != "manual").then_some(at),
+            started_at: at,
+            finished_at: at + chrono::Duration::seconds(12),
+            status: status.to_owned(),
+            error: None,
+            input_tokens: 1_200,
+            output_tokens: 80,
+            delivered_to: 1,
+            attempted_to: 1,
+        }
+    }
+
+    #[test]
+    fn records_and_lists_runs() {
+        let dir = tempfile::tempdir().unwrap();
+        let history = CronHistory::open(dir.path().join(CRON_HISTORY_DB_FILE)).unwrap();
+        let morning = Utc.with_ymd_and_hms(2026, 3, 2, 8, 0, 0).unwrap();
+        let evening = Utc.with_ymd_and_hms(2026, 3, 2, 20, 0, 0).unwrap();
+
+        history
+            .record(&run("briefing", "scheduled", morning, "completed"))
+            .unwrap();
+        history
+            .record(&run("heartbeat", "scheduled", morning, "suppressed"))
+            .unwrap();
+        history
+            .record(&run("briefing", "manual", evening, "completed"))
+            .unwrap();
+
+        let all = history.recent(None, 10).unwrap();
+        assert_eq!(all.len(), 3);
+        assert_eq!(all[0].trigger, "manual");
+        assert_eq!(all[0].scheduled_for, None);
+
+        let briefing = history.recent(Some("briefing"), 1).unwrap();
+        assert_eq!(
+            briefing,
+            vec![run("briefing", "manual", evening, "completed")]
+        );
+
+        assert_eq!(history.last_scheduled("briefing").unwrap(), Some(morning));
+        assert_eq!(history.last_scheduled("unknown").unwrap(), None);
+
+        let text = format_history(&all[1..2], chrono_tz::UTC);
+        assert!(text.starts_with("2026-03-02 08:00 UTC  heartbeat"));
+        assert!(text.contains("suppressed"));
+        assert!(text.contains("delivered 1/1"));
+    }
+}
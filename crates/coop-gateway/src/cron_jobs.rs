@@ -0,0 +1,759 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use coop_core::SessionKind;
+use coop_core::tool_args::reject_unknown_fields;
+use coop_core::traits::{Tool, ToolContext, ToolExecutor};
+use coop_core::types::{ToolDef, ToolOutput, TrustLevel};
+use serde::{Deserialize, Serialize};
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use tracing::{info, warn};
+
+use crate::config::{Config, CronCatchUp, CronConfig, CronDeliveryMode, SharedConfig};
+use crate::cron_timezone::resolve_cron_timezone;
+use crate::scheduler::parse_cron;
+
+// ---------------------------------------------------------------------------
+// Managed cron data model
+// ---------------------------------------------------------------------------
+
+/// A cron job created through `cron_manage` rather than coop.toml.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct ManagedCron {
+    pub name: String,
+    pub cron: String,
+    #[serde(default)]
+    pub timezone: Option<String>,
+    pub message: String,
+    /// The user who created the job; it runs and delivers as them.
+    #[serde(default)]
+    pub user: Option<String>,
+    #[serde(default)]
+    pub delivery: Option<CronDeliveryMode>,
+    #[serde(default)]
+    pub paused: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+impl ManagedCron {
+    pub(crate) fn to_config(&self) -> CronConfig {
+        CronConfig {
+            name: self.name.clone(),
+            cron: self.cron.clone(),
+            timezone: self.timezone.clone(),
+            message: self.message.clone(),
+            user: self.user.clone(),
+            delivery: self.delivery,
+            deliver: None,
+            review_prompt: None,
+            sandbox: None,
+            budget: None,
+            fallback: Vec::new(),
+            catch_up: CronCatchUp::None,
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// CronJobStore — in-memory + JSON file persistence
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone)]
+pub(crate) struct CronJobStore {
+    inner: Arc<Mutex<Vec<ManagedCron>>>,
+    path: PathBuf,
+}
+
+impl CronJobStore {
+    pub(crate) fn new(dir: impl AsRef<Path>) -> Result<Self> {
+        let path = dir.as_ref().join("cron_jobs.json");
+        let jobs = Self::load_from_disk(&path)?;
+        if !jobs.is_empty() {
+            info!(count = jobs.len(), path = %path.display(), "loaded managed cron jobs from disk");
+        }
+        Ok(Self {
+            inner: Arc::new(Mutex::new(jobs)),
+            path,
+        })
+    }
+
+    fn load_from_disk(path: &Path) -> Result<Vec<ManagedCron>> {
+        match std::fs::read_to_string(path) {
+            Ok(content) => serde_json::from_str(&content)
+                .with_context(|| format!("failed to parse cron jobs file: {}", path.display())),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+            Err(e) => Err(e).with_context(|| format!("failed to read {}", path.display())),
+        }
+    }
+
+    fn flush(&self, jobs: &[ManagedCron]) {
+        if let Err(e) = Self::write_to_disk(&self.path, jobs) {
+            tracing::error!(error = %e, path = %self.path.display(), "failed to persist cron jobs");
+        }
+    }
+
+    fn write_to_disk(path: &Path, jobs: &[ManagedCron]) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("failed to create {}", parent.display()))?;
+        }
+        let content = serde_json::to_string_pretty(jobs)?;
+        std::fs::write(path, content)
+            .with_context(|| format!("failed to write {}", path.display()))?;
+        Ok(())
+    }
+
+    pub(crate) fn list(&self) -> Vec<ManagedCron> {
+        self.inner
+            .lock()
+            .expect("cron job store mutex poisoned")
+            .clone()
+    }
+
+    pub(crate) fn get(&self, name: &str) -> Option<ManagedCron> {
+        self.inner
+            .lock()
+            .expect("cron job store mutex poisoned")
+            .iter()
+            .find(|job| job.name == name)
+            .cloned()
+    }
+
+    /// Jobs the scheduler should run: everything not paused.
+    pub(crate) fn active_configs(&self) -> Vec<CronConfig> {
+        self.inner
+            .lock()
+            .expect("cron job store mutex poisoned")
+            .iter()
+            .filter(|job| !job.paused)
+            .map(ManagedCron::to_config)
+            .collect()
+    }
+
+    /// Add `job`, or return false when a job with its name exists.
+    pub(crate) fn insert(&self, job: ManagedCron) -> bool {
+        let mut jobs = self.inner.lock().expect("cron job store mutex poisoned");
+        if jobs.iter().any(|existing| existing.name == job.name) {
+            return false;
+        }
+        jobs.push(job);
+        self.flush(&jobs);
+        drop(jobs);
+        true
+    }
+
+    /// Replace the job with `job`'s name, returning false when there is none.
+    pub(crate) fn replace(&self, job: ManagedCron) -> bool {
+        let mut jobs = self.inner.lock().expect("cron job store mutex poisoned");
+        let Some(existing) = jobs.iter_mut().find(|existing| existing.name == job.name) else {
+            return false;
+        };
+        *existing = job;
+        self.flush(&jobs);
+        drop(jobs);
+        true
+    }
+
+    pub(crate) fn remove(&self, name: &str) -> bool {
+        let mut jobs = self.inner.lock().expect("cron job store mutex poisoned");
+        let len_before = jobs.len();
+        jobs.retain(|job| job.name != name);
+        let removed = jobs.len() < len_before;
+        if removed {
+            self.flush(&jobs);
+        }
+        drop(jobs);
+        removed
+    }
+}
+
+/// Config-defined jobs followed by active managed ones. A managed job whose
+/// name is taken by a config job is left out.
+pub(crate) fn all_cron_jobs(config: &Config, store: Option<&CronJobStore>) -> Vec<CronConfig> {
+    let mut jobs = config.cron.clone();
+    for job in store.map(CronJobStore::active_configs).unwrap_or_default() {
+        if jobs.iter().any(|existing| existing.name == job.name) {
+            warn!(cron.name = %job.name, "managed cron job shadowed by a coop.toml job");
+            continue;
+        }
+        jobs.push(job);
+    }
+    jobs
+}
+
+// ---------------------------------------------------------------------------
+// CronManageTool
+// ---------------------------------------------------------------------------
+
+#[derive(Debug)]
+pub(crate) struct CronManageTool {
+    store: CronJobStore,
+    config: SharedConfig,
+    scheduler_notify: Arc<tokio::sync::Notify>,
+}
+
+impl CronManageTool {
+    fn new(
+        store: CronJobStore,
+        config: SharedConfig,
+        scheduler_notify: Arc<tokio::sync::Notify>,
+    ) -> Self {
+        Self {
+            store,
+            config,
+            scheduler_notify,
+        }
+    }
+
+    fn handle_create(
+        &self,
+        arguments: &serde_json::Value,
+        ctx: &ToolContext,
+    ) -> Result<ToolOutput> {
+        if let Some(output) = reject_unknown_fields(
+            "cron_manage",
+            arguments,
+            &["action", "name", "cron", "message", "timezone", "delivery"],
+        ) {
+            return Ok(output);
+        }
+
+        let name = required_str(arguments, "name")?;
+        if name.is_empty()
+            || !name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        {
+            return Ok(ToolOutput::error(
+                "name must be non-empty and use only letters, digits, '-' and '_'",
+            ));
+        }
+        if self.config.load().cron.iter().any(|job| job.name == name)
+            || self.store.get(name).is_some()
+        {
+            return Ok(ToolOutput::error(format!(
+                "a cron named '{name}' already exists"
+            )));
+        }
+
+        let job = ManagedCron {
+            name: name.to_owned(),
+            cron: required_str(arguments, "cron")?.to_owned(),
+            timezone: optional_str(arguments, "timezone").map(str::to_owned),
+            message: required_str(arguments, "message")?.to_owned(),
+            user: ctx.user_name.clone(),
+            delivery: parse_delivery(arguments)?,
+            paused: false,
+            created_at: Utc::now(),
+        };
+        if let Some(output) = self.validate(&job) {
+            return Ok(output);
+        }
+
+        info!(
+            cron.name = %job.name,
+            cron = %job.cron,
+            user = ?job.user,
+            "managed cron created"
+        );
+        if !self.store.insert(job) {
+            return Ok(ToolOutput::error(format!(
+                "a cron named '{name}' already exists"
+            )));
+        }
+        self.scheduler_notify.notify_one();
+        Ok(ToolOutput::success(format!("Cron '{name}' created.")))
+    }
+
+    fn handle_update(
+        &self,
+        arguments: &serde_json::Value,
+        ctx: &ToolContext,
+    ) -> Result<ToolOutput> {
+        if let Some(output) = reject_unknown_fields(
+            "cron_manage",
+            arguments,
+            &["action", "name", "cron", "message", "timezone", "delivery"],
+        ) {
+            return Ok(output);
+        }
+
+        let mut job = match self.owned_job(arguments, ctx)? {
+            Ok(job) => job,
+            Err(output) => return Ok(output),
+        };
+        if let Some(cron) = optional_str(arguments, "cron") {
+            cron.clone_into(&mut job.cron);
+        }
+        if let Some(message) = optional_str(arguments, "message") {
+            message.clone_into(&mut job.message);
+        }
+        if let Some(timezone) = optional_str(arguments, "timezone") {
+            job.timezone = (!timezone.is_empty()).then(|| timezone.to_owned());
+        }
+        if let Some(delivery) = parse_delivery(arguments)? {
+            job.delivery = Some(delivery);
+        }
+        if let Some(output) = self.validate(&job) {
+            return Ok(output);
+        }
+
+        info!(cron.name = %job.name, cron = %job.cron, "managed cron updated");
+        let name = job.name.clone();
+        self.store.replace(job);
+        self.scheduler_notify.notify_one();
+        Ok(ToolOutput::success(format!("Cron '{name}' updated.")))
+    }
+
+    fn handle_set_paused(
+        &self,
+        arguments: &serde_json::Value,
+        ctx: &ToolContext,
+        paused: bool,
+    ) -> Result<ToolOutput> {
+        if let Some(output) = reject_unknown_fields("cron_manage", arguments, &["action", "name"]) {
+            return Ok(output);
+        }
+
+        let mut job = match self.owned_job(arguments, ctx)? {
+            Ok(job) => job,
+            Err(output) => return Ok(output),
+        };
+        job.paused = paused;
+        info!(cron.name = %job.name, paused, "managed cron paused state changed");
+        let name = job.name.clone();
+        self.store.replace(job);
+        self.scheduler_notify.notify_one();
+        Ok(ToolOutput::success(if paused {
+            format!("Cron '{name}' paused.")
+        } else {
+            format!("Cron '{name}' resumed.")
+        }))
+    }
+
+    fn handle_delete(
+        &self,
+        arguments: &serde_json::Value,
+        ctx: &ToolContext,
+    ) -> Result<ToolOutput> {
+        if let Some(output) = reject_unknown_fields("cron_manage", arguments, &["action", "name"]) {
+            return Ok(output);
+        }
+
+        let job = match self.owned_job(arguments, ctx)? {
+            Ok(job) => job,
+            Err(output) => return Ok(output),
+        };
+        self.store.remove(&job.name);
+        info!(cron.name = %job.name, "managed cron deleted");
+        self.scheduler_notify.notify_one();
+        Ok(ToolOutput::success(format!("Cron '{}' deleted.", job.name)))
+    }
+
+    fn handle_list(&self, ctx: &ToolContext) -> ToolOutput {
+        let mut lines = Vec::new();
+        for job in self.store.list() {
+            if !can_manage(&job, ctx) {
+                continue;
+            }
+            lines.push(format!(
+                "- {} [{}{}]{} → \"{}\"",
+                job.name,
+                job.cron,
+                job.timezone
+                    .as_deref()
+                    .map(|tz| format!(" {tz}"))
+                    .unwrap_or_default(),
+                if job.paused { " (paused)" } else { "" },
+                job.message,
+            ));
+        }
+        let configured: Vec<String> = self
+            .config
+            .load()
+            .cron
+            .iter()
+            .map(|job| job.name.clone())
+            .collect();
+
+        let mut out = if lines.is_empty() {
+            "No managed cron jobs.".to_owned()
+        } else {
+            format!("Managed cron jobs:\n{}", lines.join("\n"))
+        };
+        if !configured.is_empty() {
+            let _ = write!(
+                out,
+                "\n\nDefined in coop.toml (not editable here): {}",
+                configured.join(", ")
+            );
+        }
+        ToolOutput::success(out)
+    }
+
+    /// The managed job named in `arguments`, if the caller may change it.
+    fn owned_job(
+        &self,
+        arguments: &serde_json::Value,
+        ctx: &ToolContext,
+    ) -> Result<std::result::Result<ManagedCron, ToolOutput>> {
+        let name = required_str(arguments, "name")?;
+        let Some(job) = self.store.get(name) else {
+            if self.config.load().cron.iter().any(|job| job.name == name) {
+                return Ok(Err(ToolOutput::error(format!(
+                    "cron '{name}' is defined in coop.toml and can only be changed there"
+                ))));
+            }
+            return Ok(Err(ToolOutput::error(format!("unknown cron: {name}"))));
+        };
+        if !can_manage(&job, ctx) {
+            return Ok(Err(ToolOutput::error(format!(
+                "cron '{name}' belongs to another user"
+            ))));
+        }
+        Ok(Ok(job))
+    }
+
+    /// Check the schedule and timezone, returning an error output if either
+    /// is invalid.
+    fn validate(&self, job: &ManagedCron) -> Option<ToolOutput> {
+        if let Err(error) = parse_cron(&job.cron) {
+            return Some(ToolOutput::error(error.to_string()));
+        }
+        if let Err(error) = resolve_cron_timezone(&job.to_config(), &self.config.load().users) {
+            return Some(ToolOutput::error(error.to_string()));
+        }
+        None
+    }
+}
+
+/// Owners manage every job; everyone else only the jobs they created.
+fn can_manage(job: &ManagedCron, ctx: &ToolContext) -> bool {
+    ctx.trust == TrustLevel::Owner || (job.user.is_some() && job.user == ctx.user_name)
+}
+
+fn required_str<'a>(arguments: &'a serde_json::Value, key: &str) -> Result<&'a str> {
+    optional_str(arguments, key).ok_or_else(|| anyhow::anyhow!("missing required parameter: {key}"))
+}
+
+fn optional_str<'a>(arguments: &'a serde_json::Value, key: &str) -> Option<&'a str> {
+    arguments.get(key).and_then(|v| v.as_str())
+}
+
+fn parse_delivery(arguments: &serde_json::Value) -> Result<Option<CronDeliveryMode>> {
+    arguments
+        .get("delivery")
+        .map(|value| {
+            serde_json::from_value(value.clone()).map_err(|_parse_err| {
+                anyhow::anyhow!("delivery must be \"always\" or \"as_needed\"")
+            })
+        })
+        .transpose()
+}
+
+#[async_trait]
+impl Tool for CronManageTool {
+    fn definition(&self) -> ToolDef {
+        ToolDef::new(
+            "cron_manage",
+            "Create, update, pause, resume, delete or list recurring scheduled jobs. \
+             A job sends its message to you on a cron schedule, as the user who \
+             created it, and delivers your reply to that user's channels. Use this \
+             for requests like 'check my inbox every weekday at 9'. Jobs defined \
+             in coop.toml are listed but can't be changed here.\n\
+             \n\
+             Write the message as a self-contained instruction: the job runs in a \
+             fresh session without this conversation.",
+            serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "action": {
+                        "type": "string",
+                        "enum": ["create", "update", "pause", "resume", "delete", "list"],
+                        "description": "Action to perform"
+                    },
+                    "name": {
+                        "type": "string",
+                        "description": "Job name: letters, digits, '-' and '_'. Required for every action but 'list'."
+                    },
+                    "cron": {
+                        "type": "string",
+                        "description": "Cron expression, e.g. '0 9 * * 1-5' for weekdays at 9:00. Required for 'create'."
+                    },
+                    "message": {
+                        "type": "string",
+                        "description": "Instruction sent to you each time the job fires. Required for 'create'."
+                    },
+                    "timezone": {
+                        "type": "string",
+                        "description": "IANA timezone, e.g. 'Europe/Berlin'. Defaults to the user's timezone."
+                    },
+                    "delivery": {
+                        "type": "string",
+                        "enum": ["always", "as_needed"],
+                        "description": "'always' delivers every reply; 'as_needed' lets you reply NO_ACTION_NEEDED to stay quiet. Default: always."
+                    }
+                },
+                "required": ["action"]
+            }),
+        )
+    }
+
+    async fn execute(&self, arguments: serde_json::Value, ctx: &ToolContext) -> Result<ToolOutput> {
+        if ctx.trust > TrustLevel::Full {
+            return Ok(ToolOutput::error("cron_manage requires Full trust level"));
+        }
+        if matches!(ctx.session_kind, SessionKind::Cron(_)) {
+            return Ok(ToolOutput::error(
+                "cron_manage cannot be used from a cron session",
+            ));
+        }
+
+        let action = required_str(&arguments, "action")?;
+        match action {
+            "create" => self.handle_create(&arguments, ctx),
+            "update" => self.handle_update(&arguments, ctx),
+            "pause" => self.handle_set_paused(&arguments, ctx, true),
+            "resume" => self.handle_set_paused(&arguments, ctx, false),
+            "delete" => self.handle_delete(&arguments, ctx),
+            "list" => {
+                if let Some(output) = reject_unknown_fields("cron_manage", &arguments, &["action"])
+                {
+                    return Ok(output);
+                }
+                Ok(self.handle_list(ctx))
+            }
+            other => Ok(ToolOutput::error(format!("unknown action: {other}"))),
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// CronJobToolExecutor
+// ---------------------------------------------------------------------------
+
+#[allow(missing_debug_implementations)]
+pub(crate) struct CronJobToolExecutor {
+    tool: CronManageTool,
+}
+
+impl CronJobToolExecutor {
+    pub(crate) fn new(
+        store: CronJobStore,
+        config: SharedConfig,
+        scheduler_notify: Arc<tokio::sync::Notify>,
+    ) -> Self {
+        Self {
+            tool: CronManageTool::new(store, config, scheduler_notify),
+        }
+    }
+}
+
+#[async_trait]
+impl ToolExecutor for CronJobToolExecutor {
+    async fn execute(
+        &self,
+        name: &str,
+        arguments: serde_json::Value,
+        ctx: &ToolContext,
+    ) -> Result<ToolOutput> {
+        if name == "cron_manage" {
+            self.tool.execute(arguments, ctx).await
+        } else {
+            Ok(ToolOutput::error(format!("unknown tool: {name}")))
+        }
+    }
+
+    fn tools(&self) -> Vec<ToolDef> {
+        vec![self.tool.definition()]
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::shared_config;
+    use serde_json::json;
+
+    fn make_tool(config_toml: &str) -> (CronJobToolExecutor, CronJobStore, tempfile::TempDir) {
+        let dir = tempfile::tempdir().unwrap();
+        let store = CronJobStore::new(dir.path()).unwrap();
+        let config: Config = toml::from_str(config_toml).unwrap();
+        let executor = CronJobToolExecutor::new(
+            store.clone(),
+            shared_config(config),
+            Arc::new(tokio::sync::Notify::new()),
+        );
+        (executor, store, dir)
+    }
+
+    const CONFIG: &str = "[agent]\nid = \"test\"\nmodel = \"test\"\n\n\
+        [[users]]\nname = \"alice\"\ntrust = \"full\"\nmatch = [\"signal:alice-uuid\"]\n\n\
+        [[cron]]\nname = \"heartbeat\"\ncron = \"*/30 * * * *\"\nmessage = \"check HEARTBEAT.md\"\n";
+
+    fn ctx(user: &str, trust: TrustLevel) -> ToolContext {
+        ToolContext::new(
+            format!("test:dm:signal:{user}-uuid"),
+            SessionKind::Dm(format!("signal:{user}-uuid")),
+            trust,
+            PathBuf::from("."),
+            Some(user),
+        )
+    }
+
+    async fn run(
+        executor: &CronJobToolExecutor,
+        args: serde_json::Value,
+        ctx: &ToolContext,
+    ) -> ToolOutput {
+        executor.execute("cron_manage", args, ctx).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn create_pause_resume_and_delete() {
+        let (executor, store, dir) = make_tool(CONFIG);
+        let alice = ctx("alice", TrustLevel::Full);
+
+        let output = run(
+            &executor,
+            json!({"action": "create", "name": "inbox", "cron": "0 9 * * 1-5", "message": "check my inbox", "timezone": "Europe/Berlin"}),
+            &alice,
+        )
+        .await;
+        assert!(!output.is_error, "{}", output.content);
+        let job = store.get("inbox").unwrap();
+        assert_eq!(job.user.as_deref(), Some("alice"));
+        assert_eq!(store.active_configs().len(), 1);
+
+        let output = run(
+            &executor,
+            json!({"action": "pause", "name": "inbox"}),
+            &alice,
+        )
+        .await;
+        assert!(!output.is_error);
+        assert!(store.active_configs().is_empty());
+
+        run(
+            &executor,
+            json!({"action": "resume", "name": "inbox"}),
+            &alice,
+        )
+        .await;
+        run(
+            &executor,
+            json!({"action": "update", "name": "inbox", "cron": "30 8 * * 1-5"}),
+            &alice,
+        )
+        .await;
+        let reloaded = CronJobStore::new(dir.path()).unwrap();
+        let job = reloaded.get("inbox").unwrap();
+        assert_eq!(job.cron, "30 8 * * 1-5");
+        assert!(!job.paused);
+
+        let listed = run(&executor, json!({"action": "list"}), &alice).await;
+        assert!(
+            listed
+                .content
+                .contains("inbox [30 8 * * 1-5 Europe/Berlin]")
+        );
+        assert!(listed.content.contains("heartbeat"));
+
+        run(
+            &executor,
+            json!({"action": "delete", "name": "inbox"}),
+            &alice,
+        )
+        .await;
+        assert!(store.list().is_empty());
+    }
+
+    #[tokio::test]
+    async fn rejects_bad_input_and_other_users_jobs() {
+        let (executor, store, _dir) = make_tool(CONFIG);
+        let alice = ctx("alice", TrustLevel::Full);
+
+        let invalid = run(
+            &executor,
+            json!({"action": "create", "name": "bad", "cron": "every day", "message": "x"}),
+            &alice,
+        )
+        .await;
+        assert!(invalid.is_error);
+        let taken = run(
+            &executor,
+            json!({"action": "create", "name": "heartbeat", "cron": "0 9 * * *", "message": "x"}),
+            &alice,
+        )
+        .await;
+        assert!(taken.content.contains("already exists"));
+        let config_job = run(
+            &executor,
+            json!({"action": "pause", "name": "heartbeat"}),
+            &alice,
+        )
+        .await;
+        assert!(config_job.content.contains("coop.toml"));
+
+        let inner = run(
+            &executor,
+            json!({"action": "create", "name": "x", "cron": "0 9 * * *", "message": "x"}),
+            &ctx("bob", TrustLevel::Inner),
+        )
+        .await;
+        assert!(inner.is_error);
+
+        run(
+            &executor,
+            json!({"action": "create", "name": "inbox", "cron": "0 9 * * *", "message": "x"}),
+            &alice,
+        )
+        .await;
+        let other = run(
+            &executor,
+            json!({"action": "delete", "name": "inbox"}),
+            &ctx("carol", TrustLevel::Full),
+        )
+        .await;
+        assert!(other.content.contains("another user"));
+        let owner = run(
+            &executor,
+            json!({"action": "delete", "name": "inbox"}),
+            &ctx("owner", TrustLevel::Owner),
+        )
+        .await;
+        assert!(!owner.is_error);
+        assert!(store.list().is_empty());
+    }
+
+    #[test]
+    fn config_jobs_shadow_managed_jobs() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = CronJobStore::new(dir.path()).unwrap();
+        let config: Config = toml::from_str(CONFIG).unwrap();
+        for name in ["heartbeat", "inbox"] {
+            store.insert(ManagedCron {
+                name: name.to_owned(),
+                cron: "0 9 * * *".to_owned(),
+                timezone: None,
+                message: "managed".to_owned(),
+                user: None,
+                delivery: None,
+                paused: false,
+                created_at: Utc::now(),
+            });
+        }
+
+        let jobs = all_cron_jobs(&config, Some(&store));
+        assert_eq!(jobs.len(), 2);
+        assert_eq!(jobs[0].message, "check HEARTBEAT.md");
+        assert_eq!(jobs[1].name, "inbox");
+    }
+}
@@ -1,12 +1,17 @@
 use anyhow::Result;
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use chrono_tz::Tz;
-use coop_core::{InboundKind, InboundMessage, Message, OutboundMessage, SessionKey, SessionKind};
+use coop_core::{
+    InboundKind, InboundMessage, Message, OutboundMessage, SessionKey, SessionKind, TrustLevel,
+    Usage,
+};
 use std::path::Path;
 use tokio::sync::{mpsc, oneshot};
 use tracing::{Instrument, debug, error, info, info_span, warn};
 
-use crate::config::{Config, CronConfig, CronDeliveryMode, SharedConfig};
+use crate::config::{Config, CronConfig, CronDeliveryMode, SharedConfig, UserConfig};
+use crate::cron_history::CronRun;
+use crate::cron_jobs::{CronJobStore, all_cron_jobs};
 use crate::cron_timezone::resolve_cron_timezone;
 use crate::heartbeat::{
     NO_ACTION_NEEDED_TOKEN, SuppressionTokenResult, contains_legacy_heartbeat_token,
@@ -24,7 +29,7 @@ pub(crate) struct DeliverySender {
 }
 
 impl DeliverySender {
-    #[cfg(any(feature = "signal", test))]
+    #[cfg(any(feature = "signal", feature = "telegram", test))]
     pub(crate) fn new(tx: mpsc::Sender<OutboundMessage>) -> Self {
         Self { tx }
     }
@@ -66,6 +71,53 @@ pub(crate) fn spawn_signal_delivery_bridge(
     DeliverySender::new(outbound_tx)
 }
 
+/// Spawn a bridge task that forwards `OutboundMessage`s to a Telegram action sender.
+#[cfg(feature = "telegram")]
+pub(crate) fn spawn_telegram_delivery_bridge(
+    telegram_tx: mpsc::Sender<coop_channels::TelegramAction>,
+) -> DeliverySender {
+    let (outbound_tx, mut outbound_rx) = mpsc::channel::<OutboundMessage>(64);
+    tokio::spawn(async move {
+        while let Some(msg) = outbound_rx.recv().await {
+            if telegram_tx
+                .send(coop_channels::TelegramAction::SendText(msg))
+                .await
+                .is_err()
+            {
+                break;
+            }
+        }
+    });
+    DeliverySender::new(outbound_tx)
+}
+
+/// Combine per-channel delivery senders into one that routes each message by
+/// its `channel`. A single sender is returned as-is; messages for channels
+/// without a sender are dropped with a warning.
+#[cfg(any(feature = "signal", feature = "telegram", test))]
+pub(crate) fn spawn_delivery_router(
+    mut routes: Vec<(&'static str, DeliverySender)>,
+) -> Option<DeliverySender> {
+    if routes.len() <= 1 {
+        return routes.pop().map(|(_, sender)| sender);
+    }
+
+    let (outbound_tx, mut outbound_rx) = mpsc::channel::<OutboundMessage>(64);
+    tokio::spawn(async move {
+        while let Some(msg) = outbound_rx.recv().await {
+            let Some((_, sender)) = routes.iter().find(|(channel, _)| *channel == msg.channel)
+            else {
+                warn!(channel = %msg.channel, "no delivery sender for channel, dropping message");
+                continue;
+            };
+            if sender.tx.send(msg).await.is_err() {
+                break;
+            }
+        }
+    });
+    Some(DeliverySender::new(outbound_tx))
+}
+
 pub(crate) enum CronCommand {
     RunNow {
         name: String,
@@ -86,6 +138,18 @@ pub(crate) enum CronTriggerStatus {
     Busy,
 }
 
+impl CronTriggerStatus {
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            Self::Completed => "completed",
+            Self::CompletedEmpty => "completed_empty",
+            Self::Suppressed => "suppressed",
+            Self::SkippedHeartbeat => "skipped_heartbeat",
+            Self::Busy => "busy",
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub(crate) struct CronTriggerResult {
     pub cron_name: String,
@@ -93,6 +157,9 @@ pub(crate) struct CronTriggerResult {
     pub response: Option<String>,
     pub delivered_to: usize,
     pub attempted_to: usize,
+    /// Tokens the cron turn used.
+    pub input_tokens: u64,
+    pub output_tokens: u64,
 }
 
 impl CronTriggerResult {
@@ -108,6 +175,8 @@ impl CronTriggerResult {
             response: Some(response),
             delivered_to,
             attempted_to,
+            input_tokens: 0,
+            output_tokens: 0,
         }
     }
 
@@ -118,6 +187,8 @@ impl CronTriggerResult {
             response: None,
             delivered_to: 0,
             attempted_to: 0,
+            input_tokens: 0,
+            output_tokens: 0,
         }
     }
 
@@ -128,6 +199,8 @@ impl CronTriggerResult {
             response: None,
             delivered_to: 0,
             attempted_to: 0,
+            input_tokens: 0,
+            output_tokens: 0,
         }
     }
 
@@ -138,6 +211,8 @@ impl CronTriggerResult {
             response: None,
             delivered_to: 0,
             attempted_to: 0,
+            input_tokens: 0,
+            output_tokens: 0,
         }
     }
 
@@ -148,36 +223,79 @@ impl CronTriggerResult {
             response: None,
             delivered_to: 0,
             attempted_to: 0,
+            input_tokens: 0,
+            output_tokens: 0,
         }
     }
+
+    fn with_usage(mut self, usage: &Usage) -> Self {
+        self.input_tokens = u64::from(usage.input_tokens.unwrap_or(0));
+        self.output_tokens = u64::from(usage.output_tokens.unwrap_or(0));
+        self
+    }
 }
 
 enum CronRunTrigger {
-    Scheduled,
+    Scheduled {
+        fire_time: DateTime<Utc>,
+    },
+    /// Replay of a fire time missed while the gateway was down.
+    CatchUp {
+        fire_time: DateTime<Utc>,
+    },
     Manual {
         deliver: bool,
         origin_session_id: String,
     },
+    /// A `[[trigger]]` fired by a file change or webhook.
+    Event {
+        source: TriggerSource,
+    },
+}
+
+/// What started a `[[trigger]]` job.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum TriggerSource {
+    FileWatch,
+    Webhook,
+}
+
+impl TriggerSource {
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            Self::FileWatch => "file_watch",
+            Self::Webhook => "webhook",
+        }
+    }
 }
 
 impl CronRunTrigger {
     fn kind(&self) -> &'static str {
         match self {
-            Self::Scheduled => "scheduled",
+            Self::Scheduled { .. } => "scheduled",
+            Self::CatchUp { .. } => "catch_up",
             Self::Manual { .. } => "manual",
+            Self::Event { source } => source.as_str(),
         }
     }
 
     fn delivery_enabled(&self) -> bool {
         match self {
-            Self::Scheduled => true,
+            Self::Scheduled { .. } | Self::CatchUp { .. } | Self::Event { .. } => true,
             Self::Manual { deliver, .. } => *deliver,
         }
     }
 
+    fn scheduled_for(&self) -> Option<DateTime<Utc>> {
+        match self {
+            Self::Scheduled { fire_time } | Self::CatchUp { fire_time } => Some(*fire_time),
+            Self::Manual { .. } | Self::Event { .. } => None,
+        }
+    }
+
     fn origin_session_id(&self) -> Option<&str> {
         match self {
-            Self::Scheduled => None,
+            Self::Scheduled { .. } | Self::CatchUp { .. } | Self::Event { .. } => None,
             Self::Manual {
                 origin_session_id, ..
             } => Some(origin_session_id.as_str()),
@@ -191,18 +309,61 @@ pub(crate) async fn run_scheduled_cron(
     router: &MessageRouter,
     deliver_tx: Option<&DeliverySender>,
     shared_config: &SharedConfig,
+    fire_time: DateTime<Utc>,
+) -> Result<CronTriggerResult> {
+    run_cron_once(
+        cfg,
+        timezone,
+        router,
+        deliver_tx,
+        shared_config,
+        CronRunTrigger::Scheduled { fire_time },
+    )
+    .await
+}
+
+/// Run `cfg` for a fire time that passed while the gateway was down.
+pub(crate) async fn run_catch_up_cron(
+    cfg: &CronConfig,
+    timezone: Tz,
+    router: &MessageRouter,
+    deliver_tx: Option<&DeliverySender>,
+    shared_config: &SharedConfig,
+    fire_time: DateTime<Utc>,
+) -> Result<CronTriggerResult> {
+    run_cron_once(
+        cfg,
+        timezone,
+        router,
+        deliver_tx,
+        shared_config,
+        CronRunTrigger::CatchUp { fire_time },
+    )
+    .await
+}
+
+/// Run a `[[trigger]]` job for one event; `cfg` already carries the payload
+/// as its message.
+pub(crate) async fn run_triggered_job(
+    cfg: &CronConfig,
+    router: &MessageRouter,
+    deliver_tx: Option<&DeliverySender>,
+    shared_config: &SharedConfig,
+    source: TriggerSource,
 ) -> Result<CronTriggerResult> {
+    let timezone = resolve_cron_timezone(cfg, &shared_config.load().users)?;
     run_cron_once(
         cfg,
         timezone,
         router,
         deliver_tx,
         shared_config,
-        CronRunTrigger::Scheduled,
+        CronRunTrigger::Event { source },
     )
     .await
 }
 
+#[allow(clippy::too_many_arguments)]
 pub(crate) async fn run_manual_cron_by_name(
     name: &str,
     deliver: bool,
@@ -210,13 +371,12 @@ pub(crate) async fn run_manual_cron_by_name(
     router: &MessageRouter,
     deliver_tx: Option<&DeliverySender>,
     shared_config: &SharedConfig,
+    cron_jobs: Option<&CronJobStore>,
 ) -> Result<CronTriggerResult> {
     let config_snapshot = shared_config.load();
-    let matching: Vec<CronConfig> = config_snapshot
-        .cron
-        .iter()
+    let matching: Vec<CronConfig> = all_cron_jobs(&config_snapshot, cron_jobs)
+        .into_iter()
         .filter(|cron| cron.name.as_str() == name)
-        .cloned()
         .collect();
 
     let cfg = match matching.as_slice() {
@@ -242,7 +402,7 @@ pub(crate) async fn run_manual_cron_by_name(
     .await
 }
 
-fn cron_session_key(agent_id: &str, cron_name: &str) -> SessionKey {
+pub(crate) fn cron_session_key(agent_id: &str, cron_name: &str) -> SessionKey {
     SessionKey {
         agent_id: agent_id.to_owned(),
         kind: SessionKind::Cron(cron_name.to_owned()),
@@ -271,6 +431,20 @@ pub(crate) fn resolve_cron_delivery_targets(
         return Vec::new();
     };
 
+    user_delivery_targets(user)
+}
+
+/// Channel targets reaching the owner, for budget alerts.
+fn owner_delivery_targets(config: &Config) -> Vec<(String, String)> {
+    config
+        .users
+        .iter()
+        .filter(|user| user.trust == TrustLevel::Owner)
+        .flat_map(user_delivery_targets)
+        .collect()
+}
+
+fn user_delivery_targets(user: &UserConfig) -> Vec<(String, String)> {
     user.r#match
         .iter()
         .filter_map(|pattern| {
@@ -343,9 +517,9 @@ async fn run_cron_once(
     let delivery_enabled = trigger.delivery_enabled();
     let origin_session_id = trigger.origin_session_id().map(str::to_owned);
 
-    let span = if trigger_kind == "scheduled" {
+    let span = if trigger_kind == "manual" {
         info_span!(
-            "cron_fired",
+            "cron_triggered",
             cron.name = %cfg.name,
             cron.timezone = %timezone,
             cron.trigger = %trigger_kind,
@@ -353,10 +527,11 @@ async fn run_cron_once(
             cron.deliver = delivery_enabled,
             cron.legacy_delivery_mode = cfg.uses_legacy_delivery_mode(),
             user = ?cfg.user,
+            origin.session = ?origin_session_id,
         )
     } else {
         info_span!(
-            "cron_triggered",
+            "cron_fired",
             cron.name = %cfg.name,
             cron.timezone = %timezone,
             cron.trigger = %trigger_kind,
@@ -364,14 +539,18 @@ async fn run_cron_once(
             cron.deliver = delivery_enabled,
             cron.legacy_delivery_mode = cfg.uses_legacy_delivery_mode(),
             user = ?cfg.user,
-            origin.session = ?origin_session_id,
         )
     };
 
-    async {
+    let agent_id = shared_config
+        .load()
+        .agent_id_for_user(cfg.user.as_deref())
+        .to_owned();
+    let session_key = cron_session_key(&agent_id, &cfg.name);
+    let started_at = Utc::now();
+
+    let result = async {
         let config_snapshot = shared_config.load();
-        let agent_id = config_snapshot.agent.id.clone();
-        let session_key = cron_session_key(&agent_id, &cfg.name);
 
         info!(
             cron = %cfg.cron,
@@ -395,7 +574,7 @@ async fn run_cron_once(
             Vec::new()
         };
 
-        if should_skip_heartbeat(&config_snapshot, &cfg.message) {
+        if should_skip_heartbeat(&config_snapshot, &agent_id, &cfg.message) {
             debug!(cron.name = %cfg.name, "heartbeat skipped: empty heartbeat file");
             return Ok(CronTriggerResult::skipped_heartbeat(&cfg.name));
         }
@@ -437,13 +616,14 @@ async fn run_cron_once(
             reply_to: None,
             kind: InboundKind::Text,
             message_timestamp: None,
+            message_id: None,
             group_revision: None,
         };
 
         let prompt_delivery_mode = (!delivery_targets.is_empty()).then_some(delivery_mode);
         let delivery_prompt_channel = prompt_channel.clone();
 
-        let (decision, response) = router
+        let (decision, response, usage) = router
             .dispatch_collect_text_with_channel_and_cron_delivery(
                 &inbound,
                 prompt_channel,
@@ -466,7 +646,7 @@ async fn run_cron_once(
                 debug!(cron.name = %cfg.name, "cron produced empty response");
                 CronTriggerResult::completed_empty(&cfg.name)
             };
-            return Ok(status);
+            return Ok(status.with_usage(&usage));
         }
 
         deliver_cron_response(
@@ -481,9 +661,28 @@ async fn run_cron_once(
             origin_session_id.as_deref(),
         )
         .await
+        .map(|result| result.with_usage(&usage))
     }
     .instrument(span)
-    .await
+    .await;
+
+    router.gateway_for(&session_key).record_cron_run(&CronRun {
+        cron_name: cfg.name.clone(),
+        trigger: trigger_kind.to_owned(),
+        scheduled_for: trigger.scheduled_for(),
+        started_at,
+        finished_at: Utc::now(),
+        status: result
+            .as_ref()
+            .map_or("failed", |result| result.status.as_str())
+            .to_owned(),
+        error: result.as_ref().err().map(|error| format!("{error:#}")),
+        input_tokens: result.as_ref().map_or(0, |result| result.input_tokens),
+        output_tokens: result.as_ref().map_or(0, |result| result.output_tokens),
+        delivered_to: result.as_ref().map_or(0, |result| result.delivered_to),
+        attempted_to: result.as_ref().map_or(0, |result| result.attempted_to),
+    });
+    result
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -659,12 +858,14 @@ pub(crate) async fn announce_to_session(
 }
 
 /// Check if the cron message references HEARTBEAT.md and if that file is empty.
-fn should_skip_heartbeat(config: &Config, message: &str) -> bool {
+fn should_skip_heartbeat(config: &Config, agent_id: &str, message: &str) -> bool {
     if !message.contains("HEARTBEAT.md") {
         return false;
     }
 
-    let workspace_str = &config.agent.workspace;
+    let workspace_str = config
+        .find_agent(agent_id)
+        .map_or(&config.agent.workspace, |agent| &agent.workspace);
     let workspace = std::path::PathBuf::from(workspace_str);
     if !workspace.is_absolute() {
         let cwd_relative = std::env::current_dir().ok().map(|cwd| cwd.join(&workspace));
@@ -685,6 +886,26 @@ fn check_heartbeat_file(workspace: &Path) -> bool {
     }
 }
 
+/// Forward budget alerts from the gateways to the owner's channels.
+pub(crate) fn spawn_budget_alert_delivery(
+    config: SharedConfig,
+    deliver_tx: DeliverySender,
+) -> mpsc::Sender<String> {
+    let (alert_tx, mut alert_rx) = mpsc::channel::<String>(16);
+    tokio::spawn(async move {
+        while let Some(message) = alert_rx.recv().await {
+            let targets = owner_delivery_targets(&config.load());
+            if targets.is_empty() {
+                warn!("budget alert not delivered: no owner with a channel match");
+            }
+            for (channel, target) in &targets {
+                deliver_to_target(channel, target, &message, Some(&deliver_tx)).await;
+            }
+        }
+    });
+    alert_tx
+}
+
 pub(crate) async fn deliver_to_target(
     channel: &str,
     target: &str,
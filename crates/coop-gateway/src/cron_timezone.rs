@@ -84,6 +84,7 @@ pub(crate) fn next_cron_fire_after(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::CronCatchUp;
 
     use crate::scheduler::parse_cron;
     use chrono::TimeZone;
@@ -114,6 +115,9 @@ mod tests {
             deliver: None,
             review_prompt: None,
             sandbox: None,
+            budget: None,
+            fallback: Vec::new(),
+            catch_up: CronCatchUp::None,
         };
         let users = vec![UserConfig {
             name: "alice".to_owned(),
@@ -122,6 +126,12 @@ mod tests {
             r#match: vec![],
             timezone: Some("America/Chicago".to_owned()),
             sandbox: None,
+            agent: None,
+            ipc_token_env: None,
+            budget: None,
+            fallback: Vec::new(),
+            routing: None,
+            transcribe: None,
         }];
 
         let timezone = resolve_cron_timezone(&cron, &users).expect("should parse timezone");
@@ -140,6 +150,9 @@ mod tests {
             deliver: None,
             review_prompt: None,
             sandbox: None,
+            budget: None,
+            fallback: Vec::new(),
+            catch_up: CronCatchUp::None,
         };
         let users = vec![UserConfig {
             name: "alice".to_owned(),
@@ -148,6 +161,12 @@ mod tests {
             r#match: vec![],
             timezone: Some("America/Chicago".to_owned()),
             sandbox: None,
+            agent: None,
+            ipc_token_env: None,
+            budget: None,
+            fallback: Vec::new(),
+            routing: None,
+            transcribe: None,
         }];
 
         let timezone = resolve_cron_timezone(&cron, &users).expect("should parse timezone");
@@ -163,6 +182,12 @@ mod tests {
             r#match: vec![],
             timezone: None,
             sandbox: None,
+            agent: None,
+            ipc_token_env: None,
+            budget: None,
+            fallback: Vec::new(),
+            routing: None,
+            transcribe: None,
         };
 
         let timezone =
@@ -183,6 +208,9 @@ mod tests {
             deliver: None,
             review_prompt: None,
             sandbox: None,
+            budget: None,
+            fallback: Vec::new(),
+            catch_up: CronCatchUp::None,
         };
         let users = vec![UserConfig {
             name: "alice".to_owned(),
@@ -191,6 +219,12 @@ mod tests {
             r#match: vec![],
             timezone: None,
             sandbox: None,
+            agent: None,
+            ipc_token_env: None,
+            budget: None,
+            fallback: Vec::new(),
+            routing: None,
+            transcribe: None,
         }];
 
         let timezone =
@@ -211,6 +245,9 @@ mod tests {
             deliver: None,
             review_prompt: None,
             sandbox: None,
+            budget: None,
+            fallback: Vec::new(),
+            catch_up: CronCatchUp::None,
         };
 
         let timezone =
@@ -231,6 +268,9 @@ mod tests {
             deliver: None,
             review_prompt: None,
             sandbox: None,
+            budget: None,
+            fallback: Vec::new(),
+            catch_up: CronCatchUp::None,
         };
 
         let timezone = resolve_cron_timezone_with_default(&cron, &[], || {
@@ -4,11 +4,15 @@ use coop_core::SessionKind;
 use coop_core::traits::{Tool, ToolContext, ToolExecutor};
 use coop_core::types::{ToolDef, ToolOutput, TrustLevel};
 use serde::Deserialize;
+use std::sync::Arc;
 use tokio::sync::oneshot;
 use tracing::{Instrument, info, info_span};
 
 use crate::config::{Config, SharedConfig};
+use crate::cron_history::{CronHistory, format_history};
+use crate::cron_jobs::{CronJobStore, all_cron_jobs};
 use crate::cron_runner::{CronCommand, CronCommandSender, CronTriggerResult, CronTriggerStatus};
+use crate::cron_timezone::resolve_cron_timezone;
 use crate::trust::resolve_trust;
 
 #[derive(Debug, Deserialize)]
@@ -23,11 +27,16 @@ struct CronTriggerArgs {
 struct CronTriggerTool {
     config: SharedConfig,
     command_tx: CronCommandSender,
+    cron_jobs: Option<CronJobStore>,
 }
 
 impl CronTriggerTool {
     fn new(config: SharedConfig, command_tx: CronCommandSender) -> Self {
-        Self { config, command_tx }
+        Self {
+            config,
+            command_tx,
+            cron_jobs: None,
+        }
     }
 }
 
@@ -78,9 +87,8 @@ impl Tool for CronTriggerTool {
             }
 
             let config = self.config.load();
-            let matching: Vec<_> = config
-                .cron
-                .iter()
+            let matching: Vec<_> = all_cron_jobs(&config, self.cron_jobs.as_ref())
+                .into_iter()
                 .filter(|cron| cron.name.as_str() == args.name.as_str())
                 .collect();
             let cron = match matching.as_slice() {
@@ -90,7 +98,7 @@ impl Tool for CronTriggerTool {
                         args.name
                     )));
                 }
-                [cron] => *cron,
+                [cron] => cron,
                 _ => {
                     return Ok(ToolOutput::error(format!(
                         "cron name '{}' is not unique",
@@ -208,17 +216,99 @@ fn format_result(result: CronTriggerResult, deliver_requested: bool) -> ToolOutp
     }
 }
 
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CronHistoryArgs {
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    limit: Option<usize>,
+}
+
+const DEFAULT_HISTORY_LIMIT: usize = 10;
+const MAX_HISTORY_LIMIT: usize = 50;
+
+struct CronHistoryTool {
+    config: SharedConfig,
+    history: Arc<CronHistory>,
+}
+
+#[async_trait]
+impl Tool for CronHistoryTool {
+    fn definition(&self) -> ToolDef {
+        ToolDef::new(
+            "cron_history",
+            "List recent cron runs, newest first: when each ran, whether it was scheduled, a catch-up of a missed run, or manual, its outcome (completed, completed_empty, suppressed, skipped_heartbeat, busy, failed), tokens used and how many delivery targets it reached. Use this to answer questions like whether the morning briefing ran.",
+            serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "name": {
+                        "type": "string",
+                        "description": "Only runs of this cron (as named in coop.toml). Omit for all crons."
+                    },
+                    "limit": {
+                        "type": "integer",
+                        "description": "How many runs to return. Default: 10, max: 50."
+                    }
+                }
+            }),
+        )
+    }
+
+    async fn execute(&self, arguments: serde_json::Value, ctx: &ToolContext) -> Result<ToolOutput> {
+        let args: CronHistoryArgs = serde_json::from_value(arguments)?;
+        if ctx.trust > TrustLevel::Inner {
+            return Ok(ToolOutput::error(
+                "cron_history requires Full or Inner trust level",
+            ));
+        }
+
+        let timezone = history_timezone(&self.config.load(), args.name.as_deref());
+        let limit = args
+            .limit
+            .unwrap_or(DEFAULT_HISTORY_LIMIT)
+            .clamp(1, MAX_HISTORY_LIMIT);
+        let runs = self.history.recent(args.name.as_deref(), limit)?;
+        Ok(ToolOutput::success(format_history(&runs, timezone)))
+    }
+}
+
+/// Timezone to show run times in: the named cron's, else UTC.
+pub(crate) fn history_timezone(config: &Config, cron_name: Option<&str>) -> chrono_tz::Tz {
+    cron_name
+        .and_then(|name| config.cron.iter().find(|cron| cron.name == name))
+        .and_then(|cron| resolve_cron_timezone(cron, &config.users).ok())
+        .unwrap_or(chrono_tz::UTC)
+}
+
 #[allow(missing_debug_implementations)]
 pub(crate) struct CronToolExecutor {
     tool: CronTriggerTool,
+    history: Option<CronHistoryTool>,
 }
 
 impl CronToolExecutor {
     pub(crate) fn new(config: SharedConfig, command_tx: CronCommandSender) -> Self {
         Self {
             tool: CronTriggerTool::new(config, command_tx),
+            history: None,
         }
     }
+
+    /// Let `cron_trigger` run jobs created through `cron_manage` too.
+    pub(crate) fn with_jobs(mut self, cron_jobs: CronJobStore) -> Self {
+        self.tool.cron_jobs = Some(cron_jobs);
+        self
+    }
+
+    /// Also offer `cron_history`, answering from `history`.
+    pub(crate) fn with_history(mut self, history: Arc<CronHistory>) -> Self {
+        self.history = Some(CronHistoryTool {
+            config: Arc::clone(&self.tool.config),
+            history,
+        });
+        self
+    }
 }
 
 #[async_trait]
@@ -229,15 +319,19 @@ impl ToolExecutor for CronToolExecutor {
         arguments: serde_json::Value,
         ctx: &ToolContext,
     ) -> Result<ToolOutput> {
-        if name == "cron_trigger" {
-            self.tool.execute(arguments, ctx).await
-        } else {
-            Ok(ToolOutput::error(format!("unknown tool: {name}")))
+        match (name, &self.history) {
+            ("cron_trigger", _) => self.tool.execute(arguments, ctx).await,
+            ("cron_history", Some(history)) => history.execute(arguments, ctx).await,
+            _ => Ok(ToolOutput::error(format!("unknown tool: {name}"))),
         }
     }
 
     fn tools(&self) -> Vec<ToolDef> {
-        vec![self.tool.definition()]
+        let mut tools = vec![self.tool.definition()];
+        if let Some(ref history) = self.history {
+            tools.push(history.definition());
+        }
+        tools
     }
 }
 
@@ -245,7 +339,7 @@ impl ToolExecutor for CronToolExecutor {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::config::{CronConfig, UserConfig, shared_config};
+    use crate::config::{CronCatchUp, CronConfig, UserConfig, shared_config};
     use coop_core::ToolContext;
     use std::path::PathBuf;
 
@@ -277,6 +371,12 @@ mod tests {
             r#match: vec![format!("signal:{name}-uuid")],
             timezone: None,
             sandbox: None,
+            agent: None,
+            ipc_token_env: None,
+            budget: None,
+            fallback: Vec::new(),
+            routing: None,
+            transcribe: None,
         }
     }
 
@@ -288,6 +388,12 @@ mod tests {
             r#match: vec![format!("signal:{name}-uuid")],
             timezone: None,
             sandbox: None,
+            agent: None,
+            ipc_token_env: None,
+            budget: None,
+            fallback: Vec::new(),
+            routing: None,
+            transcribe: None,
         }
     }
 
@@ -306,6 +412,9 @@ mod tests {
                     deliver: None,
                     review_prompt: None,
                     sandbox: None,
+                    budget: None,
+                    fallback: Vec::new(),
+                    catch_up: CronCatchUp::None,
                 },
                 Vec::new(),
             ),
@@ -344,6 +453,9 @@ mod tests {
                     deliver: None,
                     review_prompt: None,
                     sandbox: None,
+                    budget: None,
+                    fallback: Vec::new(),
+                    catch_up: CronCatchUp::None,
                 },
                 vec![owner_user("alice")],
             ),
@@ -378,6 +490,9 @@ mod tests {
                     deliver: None,
                     review_prompt: None,
                     sandbox: None,
+                    budget: None,
+                    fallback: Vec::new(),
+                    catch_up: CronCatchUp::None,
                 },
                 vec![inner_user("bob")],
             ),
@@ -404,6 +519,8 @@ mod tests {
                     response: Some("cron response ok".to_owned()),
                     delivered_to: 0,
                     attempted_to: 0,
+                    input_tokens: 0,
+                    output_tokens: 0,
                 }))
                 .unwrap();
         });
@@ -422,4 +539,74 @@ mod tests {
         assert!(output.content.contains("Cron 'inner-task' completed."));
         assert!(output.content.contains("cron response ok"));
     }
+
+    #[tokio::test]
+    async fn cron_history_lists_recorded_runs() {
+        let dir = tempfile::tempdir().unwrap();
+        let history = Arc::new(
+            CronHistory::open(dir.path().join(crate::cron_history::CRON_HISTORY_DB_FILE)).unwrap(),
+        );
+        let started_at = chrono::Utc::now();
+        history
+            .record(&crate::cron_history::CronRun {
+                cron_name: "briefing".to_owned(),
+                trigger: "catch_up".to_owned(),
+                scheduled_for: Some(started_at),
+                started_at,
+                finished_at: started_at,
+                status: "completed".to_owned(),
+                error: None,
+                input_tokens: 900,
+                output_tokens: 40,
+                delivered_to: 1,
+                attempted_to: 1,
+            })
+            .unwrap();
+        let (command_tx, _command_rx) = tokio::sync::mpsc::channel(1);
+        let executor = CronToolExecutor::new(
+            make_config(
+                CronConfig {
+                    name: "briefing".to_owned(),
+                    cron: "0 8 * * *".to_owned(),
+                    timezone: None,
+                    message: "morning briefing".to_owned(),
+                    user: None,
+                    delivery: None,
+                    deliver: None,
+                    review_prompt: None,
+                    sandbox: None,
+                    budget: None,
+                    fallback: Vec::new(),
+                    catch_up: CronCatchUp::Last,
+                },
+                Vec::new(),
+            ),
+            command_tx,
+        )
+        .with_history(history);
+        assert_eq!(executor.tools().len(), 2);
+
+        let output = executor
+            .execute(
+                "cron_history",
+                serde_json::json!({"name": "briefing"}),
+                &tool_context(SessionKind::Main, TrustLevel::Inner),
+            )
+            .await
+            .unwrap();
+        assert!(!output.is_error);
+        assert!(output.content.contains("briefing"));
+        assert!(output.content.contains("catch_up"));
+        assert!(output.content.contains("delivered 1/1"));
+
+        let denied = executor
+            .execute(
+                "cron_history",
+                serde_json::json!({}),
+                &tool_context(SessionKind::Main, TrustLevel::Familiar),
+            )
+            .await
+            .unwrap();
+        assert!(denied.is_error);
+    }
 }
@@ -0,0 +1,197 @@
+//! Cross-provider failover for main turns.
+//!
+//! Providers retry transient failures themselves (and rotate keys within a
+//! pool), so an error reaching the turn loop means the provider is still
+//! unavailable. The turn then moves to the next model in the `fallback`
+//! chain that can serve it.
+
+use chrono::{DateTime, Utc};
+use coop_core::{Content, Message, SessionKey, SessionKind};
+
+use crate::config::{Config, ModelModality};
+use crate::model_capabilities::EffectiveModelCapabilities;
+
+/// A turn that moved from one model to another.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct FailoverEvent {
+    pub from: String,
+    pub to: String,
+    pub error: String,
+    pub at: DateTime<Utc>,
+}
+
+/// The fallback chain for a turn: the cron job's, else the user's, else
+/// the agent's.
+pub(crate) fn fallback_chain<'a>(
+    config: &'a Config,
+    session_key: &SessionKey,
+    user_name: Option<&str>,
+) -> &'a [String] {
+    let cron = match &session_key.kind {
+        SessionKind::Cron(name) => config.cron.iter().find(|job| job.name == *name),
+        _ => None,
+    };
+    let user = user_name.and_then(|name| config.users.iter().find(|user| user.name == name));
+    [
+        cron.map(|job| job.fallback.as_slice()),
+        user.map(|user| user.fallback.as_slice()),
+    ]
+    .into_iter()
+    .flatten()
+    .find(|chain| !chain.is_empty())
+    .unwrap_or(&config.agent.fallback)
+}
+
+/// Whether `error` says the provider is overloaded, failing or rate limited
+/// (as opposed to rejecting the request itself).
+pub(crate) fn is_provider_unavailable(error: &anyhow::Error) -> bool {
+    let text = format!("{error:#}").to_ascii_lowercase();
+    [
+        "overloaded",
+        "rate limited",
+        "(429",
+        "(500",
+        "(502",
+        "(503",
+        "(504",
+        "(529",
+        "server error",
+        "service unavailable",
+        "bad gateway",
+        "retries",
+    ]
+    .into_iter()
+    .any(|needle| text.contains(needle))
+}
+
+/// What a fallback model must support to take over a turn.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct TurnRequirements {
+    pub tools: bool,
+    pub images: bool,
+}
+
+impl TurnRequirements {
+    pub(crate) fn for_request(messages: &[Message], has_tools: bool) -> Self {
+        Self {
+            tools: has_tools,
+            images: messages.iter().any(|message| {
+                message
+                    .content
+                    .iter()
+                    .any(|content| matches!(content, Content::Image { .. }))
+            }),
+        }
+    }
+
+    pub(crate) fn admits(self, capabilities: &EffectiveModelCapabilities) -> bool {
+        !capabilities.subagent_only
+            && (!self.tools || capabilities.supports_tools)
+            && (!self.images || capabilities.supports_input(ModelModality::Image))
+    }
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> Config {
+        toml::from_str(
+            r#"
+[agent]
+id = "coop"
+model = "anthropic/claude-sonnet-4-20250514"
+fallback = ["gpt-5", "ollama/qwen3"]
+
+[[users]]
+name = "alice"
+trust = "full"
+fallback = ["gpt-5-mini"]
+
+[[users]]
+name = "bob"
+trust = "inner"
+
+[[cron]]
+name = "digest"
+cron = "0 8 * * *"
+message = "digest"
+user = "alice"
+fallback = ["ollama/qwen3"]
+"#,
+        )
+        .unwrap()
+    }
+
+    fn key(kind: SessionKind) -> SessionKey {
+        SessionKey {
+            agent_id: "coop".to_owned(),
+            kind,
+        }
+    }
+
+    #[test]
+    fn chain_prefers_cron_then_user_then_agent() {
+        let config = config();
+        let main = key(SessionKind::Main);
+        let digest = key(SessionKind::Cron("digest".to_owned()));
+        assert_eq!(
+            fallback_chain(&config, &digest, Some("alice")),
+            ["ollama/qwen3"]
+        );
+        assert_eq!(
+            fallback_chain(&config, &main, Some("alice")),
+            ["gpt-5-mini"]
+        );
+        assert_eq!(
+            fallback_chain(&config, &main, Some("bob")),
+            ["gpt-5", "ollama/qwen3"]
+        );
+        assert_eq!(fallback_chain(&config, &main, None).len(), 2);
+    }
+
+    #[test]
+    fn classifies_unavailable_providers() {
+        for message in [
+            "API overloaded (503 Service Unavailable): Overloaded",
+            "Anthropic API error after 3 retries: Rate limited (429 Too Many Requests): slow down",
+            "provider request failed after 3 retries",
+            "Anthropic API error (502 Bad Gateway): upstream",
+        ] {
+            assert!(
+                is_provider_unavailable(&anyhow::anyhow!(message)),
+                "{message}"
+            );
+        }
+        for message in [
+            "Invalid request (400 Bad Request): prompt is too long",
+            "Authentication failed (401 Unauthorized): invalid x-api-key",
+        ] {
+            assert!(
+                !is_provider_unavailable(&anyhow::anyhow!(message)),
+                "{message}"
+            );
+        }
+    }
+
+    #[test]
+    fn vision_turns_need_image_input() {
+        let image = Message::user().with_image("aGk=", "image/png");
+        let needs = TurnRequirements::for_request(&[image], false);
+        assert!(needs.images);
+
+        let text_only = EffectiveModelCapabilities {
+            input_modalities: [ModelModality::Text].into(),
+            ..EffectiveModelCapabilities::default()
+        };
+        assert!(!needs.admits(&text_only));
+        assert!(needs.admits(&EffectiveModelCapabilities::default()));
+
+        let no_tools = EffectiveModelCapabilities {
+            supports_tools: false,
+            ..EffectiveModelCapabilities::default()
+        };
+        assert!(!TurnRequirements::for_request(&[], true).admits(&no_tools));
+    }
+}
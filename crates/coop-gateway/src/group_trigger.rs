@@ -78,7 +78,7 @@ pub(crate) fn evaluate_trigger(
 }
 
 /// Check if assistant output is the silent reply token.
-#[cfg(any(test, feature = "signal"))]
+#[cfg(any(test, feature = "signal", feature = "telegram"))]
 pub(crate) fn is_silent_reply(text: &str) -> bool {
     let trimmed = text.trim();
     trimmed == SILENT_REPLY_TOKEN
@@ -118,6 +118,7 @@ mod tests {
             reply_to: None,
             kind: InboundKind::Text,
             message_timestamp: None,
+            message_id: None,
             group_revision: None,
         }
     }
@@ -133,6 +134,8 @@ mod tests {
             default_trust: TrustLevel::Familiar,
             trust_ceiling: TrustCeiling::None,
             history_limit: 50,
+            agent: None,
+            routing: None,
         }
     }
 
@@ -147,6 +150,8 @@ mod tests {
             default_trust: TrustLevel::Familiar,
             trust_ceiling: TrustCeiling::None,
             history_limit: 50,
+            agent: None,
+            routing: None,
         }
     }
 
@@ -161,6 +166,8 @@ mod tests {
             default_trust: TrustLevel::Familiar,
             trust_ceiling: TrustCeiling::None,
             history_limit: 50,
+            agent: None,
+            routing: None,
         }
     }
 
@@ -0,0 +1,874 @@
+use anyhow::Result;
+use chrono::Utc;
+use coop_core::{InboundKind, InboundMessage, SessionKey, SessionKind, TrustLevel, TurnEvent};
+use coop_ipc::{
+    ClientMessage, IpcConnection, PROTOCOL_VERSION, ServerMessage, TurnEventEncoder,
+    negotiate_version,
+};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{OwnedSemaphorePermit, broadcast, mpsc};
+use tokio::task::JoinHandle;
+use tracing::info;
+
+use crate::config::{Config, SharedConfig};
+use crate::gateway::Gateway;
+use crate::router::{MessageRouter, REMOTE_IPC_CHANNEL};
+use crate::subagents::registry::SubagentRunRecord;
+
+const TERMINAL_CHANNEL: &str = "terminal:default";
+
+/// Slows down token guessing on the remote listener.
+const AUTH_FAILURE_DELAY: Duration = Duration::from_secs(1);
+
+/// How long a remote client has to answer the challenge.
+const HELLO_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Remote connections allowed to be mid-handshake at once. Each holds its
+/// slot through [`AUTH_FAILURE_DELAY`], which also caps the guess rate.
+pub(crate) const MAX_PENDING_REMOTE_HANDSHAKES: usize = 16;
+
+/// Who is on the other end of an IPC connection.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Peer {
+    /// The local Unix socket, i.e. the machine's owner.
+    Local,
+    /// A remote client authenticated as this `[[users]]` entry.
+    Remote(String),
+}
+
+/// Serve one local-socket client until it disconnects.
+pub(crate) async fn handle_client(
+    connection: IpcConnection,
+    router: Arc<MessageRouter>,
+) -> Result<()> {
+    serve(connection, router, Peer::Local, None).await
+}
+
+/// Serve one remote client. The server opens with a nonce and the
+/// client's `Hello` must prove it holds some user's `ipc_token_env` token
+/// without sending it; the connection then acts as that user.
+///
+/// `handshake` is the listener's slot for an unauthenticated connection,
+/// released once the client authenticates or is turned away.
+pub(crate) async fn handle_remote_client(
+    mut connection: IpcConnection,
+    router: Arc<MessageRouter>,
+    config: SharedConfig,
+    handshake: OwnedSemaphorePermit,
+) -> Result<()> {
+    let nonce = coop_ipc::auth::new_challenge();
+    let hello = tokio::time::timeout(HELLO_TIMEOUT, async {
+        connection
+            .send(ServerMessage::Challenge {
+                nonce: nonce.clone(),
+            })
+            .await?;
+        connection.recv().await
+    })
+    .await;
+    let Ok(hello) = hello else {
+        tracing::warn!("remote ipc client did not answer the challenge in time");
+        return Ok(());
+    };
+    let (version, proof) = match hello? {
+        ClientMessage::Hello { version, proof } => (version, proof),
+        _ => (0, None),
+    };
+    let user = proof.and_then(|proof| {
+        authenticate(&config.load(), &nonce, &proof, |name| {
+            std::env::var(name).ok()
+        })
+    });
+    let Some(user) = user else {
+        tracing::warn!("remote ipc authentication failed");
+        tokio::time::sleep(AUTH_FAILURE_DELAY).await;
+        connection
+            .send(ServerMessage::Error {
+                session: String::new(),
+                message: "authentication failed".to_owned(),
+            })
+            .await?;
+        return Ok(());
+    };
+    drop(handshake);
+
+    info!(user = %user, "remote ipc client authenticated");
+    serve(connection, router, Peer::Remote(user), Some(version)).await
+}
+
+/// Name of the user whose `ipc_token_env` token produced `proof` for
+/// `nonce`.
+fn authenticate<F>(config: &Config, nonce: &str, proof: &str, lookup: F) -> Option<String>
+where
+    F: Fn(&str) -> Option<String>,
+{
+    config
+        .users
+        .iter()
+        .find(|user| {
+            user.ipc_token_env
+                .as_deref()
+                .and_then(&lookup)
+                .filter(|token| !token.is_empty())
+                .is_some_and(|token| coop_ipc::auth::verify_proof(&token, nonce, proof))
+        })
+        .map(|user| user.name.clone())
+}
+
+async fn serve(
+    connection: IpcConnection,
+    router: Arc<MessageRouter>,
+    peer: Peer,
+    hello_version: Option<u32>,
+) -> Result<()> {
+    let (progress_tx, progress_rx) = mpsc::channel(64);
+    let forwarders: Vec<JoinHandle<()>> = router
+        .gateways()
+        .map(|gateway| {
+            forward_subagent_runs(gateway.subagents().subscribe_runs(), progress_tx.clone())
+        })
+        .collect();
+    drop(progress_tx);
+
+    let mut client = ClientSession {
+        connection,
+        router,
+        peer,
+        // Until the client says hello, assume the oldest protocol.
+        version: 1,
+        watched: HashMap::new(),
+        progress_rx,
+    };
+    // The remote listener has already read `Hello` to authenticate.
+    let result = match hello_version {
+        Some(version) => match client
+            .handle(ClientMessage::Hello {
+                version,
+                proof: None,
+            })
+            .await
+        {
+            Ok(()) => client.run().await,
+            Err(error) => Err(error),
+        },
+        None => client.run().await,
+    };
+
+    for forwarder in forwarders {
+        forwarder.abort();
+    }
+    result
+}
+
+fn forward_subagent_runs(
+    mut runs: broadcast::Receiver<SubagentRunRecord>,
+    progress_tx: mpsc::Sender<SubagentRunRecord>,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        loop {
+            match runs.recv().await {
+                Ok(record) => {
+                    if progress_tx.send(record).await.is_err() {
+                        break;
+                    }
+                }
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    tracing::debug!(skipped, "ipc subagent progress lagged");
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+            }
+        }
+    })
+}
+
+struct ClientSession {
+    connection: IpcConnection,
+    router: Arc<MessageRouter>,
+    peer: Peer,
+    /// Protocol version negotiated in `Hello`.
+    version: u32,
+    /// Sessions the client has subscribed or sent to, with the name the
+    /// client used for them. Subagent progress is forwarded for these.
+    watched: HashMap<SessionKey, String>,
+    progress_rx: mpsc::Receiver<SubagentRunRecord>,
+}
+
+impl ClientSession {
+    async fn run(&mut self) -> Result<()> {
+        loop {
+            tokio::select! {
+                message = self.connection.recv() => {
+                    let Ok(message) = message else {
+                        return Ok(());
+                    };
+                    self.handle(message).await?;
+                }
+                Some(record) = self.progress_rx.recv() => {
+                    self.send_subagent_progress(record).await?;
+                }
+            }
+        }
+    }
+
+    /// Send `message` downgraded to the negotiated version.
+    async fn send(&mut self, message: ServerMessage) -> Result<()> {
+        match message.for_version(self.version) {
+            Some(message) => self.connection.send(message).await,
+            None => Ok(()),
+        }
+    }
+
+    async fn send_error(&mut self, session: String, message: impl Into<String>) -> Result<()> {
+        self.send(ServerMessage::Error {
+            session,
+            message: message.into(),
+        })
+        .await
+    }
+
+    async fn send_subagent_progress(&mut self, record: SubagentRunRecord) -> Result<()> {
+        let Some(session) = self.watched.get(&record.parent_session_key).cloned() else {
+            return Ok(());
+        };
+        let status = serde_json::to_value(record.status)?
+            .as_str()
+            .unwrap_or_default()
+            .to_owned();
+        self.send(ServerMessage::SubagentProgress {
+            session,
+            run_id: record.run_id.to_string(),
+            status,
+            task: record.task,
+            model: record.model,
+            summary: record.summary,
+            error: record.error,
+        })
+        .await
+    }
+
+    /// Resolve a client session name, reporting unknown ones to the client.
+    async fn resolve(&mut self, session: &str) -> Result<Option<(Arc<Gateway>, SessionKey)>> {
+        if let Some((gateway, key)) = self.router.resolve_session(session) {
+            return Ok(Some((Arc::clone(gateway), key)));
+        }
+        self.send_error(session.to_owned(), "unknown session")
+            .await?;
+        Ok(None)
+    }
+
+    fn channel(&self) -> &str {
+        match &self.peer {
+            Peer::Local => TERMINAL_CHANNEL,
+            Peer::Remote(_) => REMOTE_IPC_CHANNEL,
+        }
+    }
+
+    /// The inbound message a request on `session` is routed as.
+    fn inbound(&self, session: &str, content: String, kind: InboundKind) -> InboundMessage {
+        let sender = match &self.peer {
+            Peer::Local => "tui",
+            Peer::Remote(user) => user.as_str(),
+        };
+        InboundMessage {
+            channel: self.channel().to_owned(),
+            sender: sender.to_owned(),
+            content,
+            chat_id: None,
+            is_group: false,
+            timestamp: Utc::now(),
+            reply_to: Some(session.to_owned()),
+            kind,
+            message_timestamp: None,
+            message_id: None,
+            group_revision: None,
+        }
+    }
+
+    /// Remote users below `full` trust may only use their own DM session,
+    /// so they cannot read or steer anyone else's conversation.
+    fn may_access(&self, session_key: &SessionKey, trust: TrustLevel) -> bool {
+        match &self.peer {
+            Peer::Local => true,
+            Peer::Remote(user) => {
+                trust <= TrustLevel::Full
+                    || session_key.kind == SessionKind::Dm(format!("{REMOTE_IPC_CHANNEL}:{user}"))
+            }
+        }
+    }
+
+    #[allow(clippy::too_many_lines)]
+    async fn handle(&mut self, message: ClientMessage) -> Result<()> {
+        if message.min_version() > self.version {
+            let session = message.session().unwrap_or_default().to_owned();
+            return self
+                .send_error(
+                    session,
+                    format!(
+                        "request needs protocol version {} (negotiated {})",
+                        message.min_version(),
+                        self.version
+                    ),
+                )
+                .await;
+        }
+
+        if let Some(session) = message.session() {
+            let decision =
+                self.router
+                    .route(&self.inbound(session, String::new(), InboundKind::Text));
+            if !self.may_access(&decision.session_key, decision.trust) {
+                let session = session.to_owned();
+                return self.send_error(session, "session not permitted").await;
+            }
+        }
+
+        match message {
+            ClientMessage::Hello { version, .. } => {
+                self.version = negotiate_version(version);
+                if version != PROTOCOL_VERSION {
+                    tracing::info!(
+                        client_version = version,
+                        server_version = PROTOCOL_VERSION,
+                        negotiated = self.version,
+                        "ipc client speaks a different protocol version"
+                    );
+                }
+                let agents: Vec<String> = self
+                    .router
+                    .gateways()
+                    .map(|gateway| gateway.agent_id())
+                    .collect();
+                self.send(ServerMessage::Hello {
+                    version: self.version,
+                    agent_id: agents[0].clone(),
+                    agents,
+                })
+                .await?;
+            }
+            ClientMessage::Subscribe { session } => {
+                if let Some((_, key)) = self.resolve(&session).await? {
+                    self.watched.insert(key, session);
+                }
+            }
+            ClientMessage::ListSessions { agent } => {
+                let trust = self
+                    .router
+                    .route(&self.inbound("", String::new(), InboundKind::Text))
+                    .trust;
+                let keys = self
+                    .router
+                    .gateways()
+                    .filter(|gateway| {
+                        agent
+                            .as_deref()
+                            .is_none_or(|agent| gateway.agent_id() == agent)
+                    })
+                    .flat_map(|gateway| gateway.list_sessions())
+                    .filter(|key| self.may_access(key, trust))
+                    .map(|key| key.to_string())
+                    .collect();
+                self.send(ServerMessage::Sessions { keys }).await?;
+            }
+            ClientMessage::Clear { session } => {
+                if let Some((gateway, key)) = self.resolve(&session).await? {
+                    gateway.clear_session(&key);
+                }
+            }
+            ClientMessage::Stop { session } => {
+                if let Some((gateway, key)) = self.resolve(&session).await? {
+                    gateway.cancel_active_turn(&key);
+                }
+            }
+            ClientMessage::Send { session, content } => {
+                if let Some((_, key)) = self.router.resolve_session(&session) {
+                    self.watched.insert(key, session.clone());
+                }
+                self.handle_send(session, content).await?;
+            }
+            ClientMessage::GetHistory {
+                session,
+                before,
+                limit,
+            } => {
+                if let Some((gateway, key)) = self.resolve(&session).await? {
+                    let messages = gateway.messages(&key);
+                    self.send(ServerMessage::history(session, &messages, before, limit))
+                        .await?;
+                }
+            }
+            ClientMessage::RunCommand { session, command } => {
+                self.handle_command(session, command).await?;
+            }
+            ClientMessage::SetModel { session, model } => {
+                let Some((gateway, key)) = self.resolve(&session).await? else {
+                    return Ok(());
+                };
+                let decision =
+                    self.router
+                        .route(&self.inbound(&session, String::new(), InboundKind::Text));
+                match gateway
+                    .set_user_model_for_session(
+                        &key,
+                        decision.trust,
+                        decision.user_name.as_deref(),
+                        Some(self.channel()),
+                        &model,
+                    )
+                    .await
+                {
+                    Ok(outcome) => {
+                        self.send(ServerMessage::ModelChanged {
+                            session,
+                            model: outcome.selection.model,
+                            context_limit: outcome.selection.context_limit,
+                            compacted: outcome.compacted_for_handoff,
+                        })
+                        .await?;
+                    }
+                    Err(error) => {
+                        self.send_error(session, format!("could not change model: {error}"))
+                            .await?;
+                    }
+                }
+            }
+            ClientMessage::GetUsage { session } => {
+                let Some((gateway, key)) = self.resolve(&session).await? else {
+                    return Ok(());
+                };
+                let decision =
+                    self.router
+                        .route(&self.inbound(&session, String::new(), InboundKind::Text));
+                let (model, context_limit) =
+                    match gateway.resolve_main_model(decision.user_name.as_deref()) {
+                        Ok(selection) => (selection.model, selection.context_limit),
+                        Err(error) => {
+                            tracing::debug!(error = %error, "main model unavailable for usage");
+                            (
+                                gateway.model_name_for_user(decision.user_name.as_deref()),
+                                0,
+                            )
+                        }
+                    };
+                let usage = gateway.session_usage(&key);
+                self.send(ServerMessage::Usage {
+                    session,
+                    model,
+                    context_limit,
+                    context_tokens: usage.last_input_tokens,
+                    input_tokens: usage.cumulative.input_tokens.unwrap_or(0),
+                    output_tokens: usage.cumulative.output_tokens.unwrap_or(0),
+                    cache_read_tokens: usage.cumulative.cache_read_tokens.unwrap_or(0),
+                    cache_write_tokens: usage.cumulative.cache_write_tokens.unwrap_or(0),
+                    messages: gateway.session_message_count(&key),
+                })
+                .await?;
+            }
+        }
+        Ok(())
+    }
+
+    async fn handle_send(&mut self, session: String, content: String) -> Result<()> {
+        let kind = if content.trim_start().starts_with('/') {
+            InboundKind::Command
+        } else {
+            InboundKind::Text
+        };
+        let inbound = self.inbound(&session, content, kind);
+
+        let (event_tx, mut event_rx) = mpsc::channel(64);
+        let router = Arc::clone(&self.router);
+        let router_task = tokio::spawn(async move { router.dispatch(&inbound, event_tx).await });
+
+        let mut encoder = TurnEventEncoder::new(session.clone());
+        loop {
+            tokio::select! {
+                event = event_rx.recv() => {
+                    let Some(event) = event else {
+                        break;
+                    };
+                    if let Some(message) = encoder.encode(event) {
+                        self.send(message).await?;
+                    }
+                }
+                Some(record) = self.progress_rx.recv() => {
+                    self.send_subagent_progress(record).await?;
+                }
+            }
+        }
+
+        match router_task.await {
+            Ok(Ok(_)) => {}
+            Ok(Err(error)) => self.send_error(session, error.to_string()).await?,
+            Err(error) => {
+                self.send_error(session, format!("internal server error: {error}"))
+                    .await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Run a slash command through the router, which applies the same
+    /// trust checks as a typed command, and return its reply in one piece.
+    async fn handle_command(&mut self, session: String, command: String) -> Result<()> {
+        if !command.trim_start().starts_with('/') {
+            return self.send_error(session, "commands start with '/'").await;
+        }
+        if self.resolve(&session).await?.is_none() {
+            return Ok(());
+        }
+
+        let inbound = self.inbound(&session, command.clone(), InboundKind::Command);
+        let (event_tx, mut event_rx) = mpsc::channel(64);
+        let router = Arc::clone(&self.router);
+        let router_task = tokio::spawn(async move { router.dispatch(&inbound, event_tx).await });
+
+        let mut output = String::new();
+        while let Some(event) = event_rx.recv().await {
+            if let TurnEvent::TextDelta(delta) = event {
+                output.push_str(&delta);
+            }
+        }
+
+        match router_task.await {
+            Ok(Ok(_)) => {
+                self.send(ServerMessage::CommandOutput {
+                    session,
+                    command,
+                    output,
+                })
+                .await
+            }
+            Ok(Err(error)) => self.send_error(session, error.to_string()).await,
+            Err(error) => {
+                self.send_error(session, format!("internal server error: {error}"))
+                    .await
+            }
+        }
+    }
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use coop_core::fakes::FakeProvider;
+    use coop_core::tools::DefaultExecutor;
+    use coop_ipc::{IpcClient, IpcServer, RemoteIpcServer};
+
+    use crate::config::shared_config;
+    use crate::provider_registry::ProviderRegistry;
+
+    struct Harness {
+        _workspace: tempfile::TempDir,
+        client: IpcClient,
+    }
+
+    fn test_config() -> Config {
+        toml::from_str(
+            r#"
+[agent]
+id = "coop"
+model = "test-model"
+
+[[users]]
+name = "alice"
+trust = "full"
+match = ["terminal:default"]
+
+[[users]]
+name = "bob"
+trust = "inner"
+match = ["signal:bob-uuid"]
+ipc_token_env = "BOB_IPC_TOKEN"
+"#,
+        )
+        .unwrap()
+    }
+
+    fn test_router(workspace: &tempfile::TempDir) -> Arc<MessageRouter> {
+        std::fs::write(workspace.path().join("SOUL.md"), "You are a test agent.").unwrap();
+        let shared = shared_config(test_config());
+        let gateway = Arc::new(
+            Gateway::new(
+                Arc::clone(&shared),
+                workspace.path().to_path_buf(),
+                ProviderRegistry::new(Arc::new(FakeProvider::new("hi from coop"))),
+                Arc::new(DefaultExecutor::new()),
+                None,
+                None,
+            )
+            .unwrap(),
+        );
+        Arc::new(MessageRouter::new(shared, gateway))
+    }
+
+    async fn connect(version: u32) -> Harness {
+        let workspace = tempfile::tempdir().unwrap();
+        let router = test_router(&workspace);
+
+        let server = IpcServer::bind(workspace.path().join("coop.sock")).unwrap();
+        let mut client = IpcClient::connect(server.socket_path()).await.unwrap();
+        tokio::spawn(async move {
+            let connection = server.accept().await.unwrap();
+            handle_client(connection, router).await
+        });
+
+        client
+            .send(ClientMessage::Hello {
+                version,
+                proof: None,
+            })
+            .await
+            .unwrap();
+        Harness {
+            _workspace: workspace,
+            client,
+        }
+    }
+
+    async fn send_and_wait_for_done(client: &mut IpcClient, content: &str) -> Vec<ServerMessage> {
+        client
+            .send(ClientMessage::Send {
+                session: "main".into(),
+                content: content.into(),
+            })
+            .await
+            .unwrap();
+        let mut received = Vec::new();
+        loop {
+            let message = client.recv().await.unwrap();
+            let done = matches!(message, ServerMessage::Done { .. });
+            received.push(message);
+            if done {
+                return received;
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn v1_clients_negotiate_down_and_cannot_use_v2_requests() {
+        let mut harness = connect(1).await;
+        let client = &mut harness.client;
+        assert!(matches!(
+            client.recv().await.unwrap(),
+            ServerMessage::Hello { version: 1, .. }
+        ));
+
+        client
+            .send(ClientMessage::GetUsage {
+                session: "main".into(),
+            })
+            .await
+            .unwrap();
+        let ServerMessage::Error { session, message } = client.recv().await.unwrap() else {
+            panic!("expected an error");
+        };
+        assert_eq!(session, "main");
+        assert!(message.contains("protocol version 2"), "{message}");
+
+        let received = send_and_wait_for_done(client, "hello").await;
+        assert!(
+            received.iter().all(|message| message.min_version() == 1),
+            "{received:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn v2_clients_page_history_and_query_the_session() {
+        let mut harness = connect(PROTOCOL_VERSION).await;
+        let client = &mut harness.client;
+        assert!(matches!(
+            client.recv().await.unwrap(),
+            ServerMessage::Hello {
+                version: PROTOCOL_VERSION,
+                ..
+            }
+        ));
+        send_and_wait_for_done(client, "hello").await;
+
+        client
+            .send(ClientMessage::GetHistory {
+                session: "main".into(),
+                before: None,
+                limit: 1,
+            })
+            .await
+            .unwrap();
+        let ServerMessage::History {
+            start,
+            total,
+            messages,
+            ..
+        } = client.recv().await.unwrap()
+        else {
+            panic!("expected history");
+        };
+        assert_eq!((start, total), (1, 2));
+        assert_eq!(messages[0].text, "hi from coop");
+
+        client
+            .send(ClientMessage::RunCommand {
+                session: "main".into(),
+                command: "/status".into(),
+            })
+            .await
+            .unwrap();
+        let ServerMessage::CommandOutput { output, .. } = client.recv().await.unwrap() else {
+            panic!("expected command output");
+        };
+        assert!(output.contains("Messages: 2"), "{output}");
+
+        client
+            .send(ClientMessage::GetUsage {
+                session: "main".into(),
+            })
+            .await
+            .unwrap();
+        let ServerMessage::Usage {
+            messages,
+            context_limit,
+            ..
+        } = client.recv().await.unwrap()
+        else {
+            panic!("expected usage");
+        };
+        assert_eq!(messages, 2);
+        assert!(context_limit > 0);
+    }
+
+    #[tokio::test]
+    async fn unknown_sessions_are_reported() {
+        let mut harness = connect(PROTOCOL_VERSION).await;
+        let client = &mut harness.client;
+        client.recv().await.unwrap();
+
+        client
+            .send(ClientMessage::GetHistory {
+                session: "nobody:main".into(),
+                before: None,
+                limit: 10,
+            })
+            .await
+            .unwrap();
+        assert_eq!(
+            client.recv().await.unwrap(),
+            ServerMessage::Error {
+                session: "nobody:main".into(),
+                message: "unknown session".into(),
+            }
+        );
+    }
+
+    #[test]
+    fn tokens_authenticate_as_the_user_that_owns_them() {
+        let config = test_config();
+        let lookup = |name: &str| (name == "BOB_IPC_TOKEN").then(|| "s3cret".to_owned());
+        let nonce = coop_ipc::auth::new_challenge();
+        let proof = |token: &str| coop_ipc::auth::challenge_proof(token, &nonce);
+
+        assert_eq!(
+            authenticate(&config, &nonce, &proof("s3cret"), lookup).as_deref(),
+            Some("bob")
+        );
+        assert_eq!(
+            authenticate(&config, &nonce, &proof("s3cret!"), lookup),
+            None
+        );
+        assert_eq!(
+            authenticate(&config, "other-nonce", &proof("s3cret"), lookup),
+            None
+        );
+        assert_eq!(
+            authenticate(&config, &nonce, &proof(""), |_| Some(String::new())),
+            None
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn silent_remote_clients_are_dropped_and_release_their_slot() {
+        let workspace = tempfile::tempdir().unwrap();
+        let router = test_router(&workspace);
+        let server = RemoteIpcServer::bind("127.0.0.1:0").await.unwrap();
+        let addr = server.local_addr().unwrap().to_string();
+        let handshakes = Arc::new(tokio::sync::Semaphore::new(1));
+        let permit = Arc::clone(&handshakes).try_acquire_owned().unwrap();
+        let server_task = tokio::spawn(async move {
+            let (connection, _) = server.accept().await.unwrap();
+            handle_remote_client(connection, router, shared_config(test_config()), permit).await
+        });
+
+        let mut client = IpcClient::connect_tcp(&addr).await.unwrap();
+        assert!(matches!(
+            client.recv().await.unwrap(),
+            ServerMessage::Challenge { .. }
+        ));
+        assert!(handshakes.try_acquire().is_err());
+
+        server_task.await.unwrap().unwrap();
+        assert!(handshakes.try_acquire().is_ok());
+        assert!(client.recv().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn remote_users_below_full_trust_only_reach_their_own_session() {
+        let workspace = tempfile::tempdir().unwrap();
+        let router = test_router(&workspace);
+        let server = RemoteIpcServer::bind("127.0.0.1:0").await.unwrap();
+        let addr = server.local_addr().unwrap().to_string();
+        tokio::spawn(async move {
+            let (connection, _) = server.accept().await.unwrap();
+            serve(
+                connection,
+                router,
+                Peer::Remote("bob".to_owned()),
+                Some(PROTOCOL_VERSION),
+            )
+            .await
+        });
+        let mut client = IpcClient::connect_tcp(&addr).await.unwrap();
+        assert!(matches!(
+            client.recv().await.unwrap(),
+            ServerMessage::Hello { .. }
+        ));
+
+        client
+            .send(ClientMessage::GetHistory {
+                session: "main".into(),
+                before: None,
+                limit: 10,
+            })
+            .await
+            .unwrap();
+        let ServerMessage::Error { message, .. } = client.recv().await.unwrap() else {
+            panic!("expected an error");
+        };
+        assert_eq!(message, "session not permitted");
+
+        client
+            .send(ClientMessage::Send {
+                session: "coop:dm:ipc:bob".into(),
+                content: "hello".into(),
+            })
+            .await
+            .unwrap();
+        loop {
+            match client.recv().await.unwrap() {
+                ServerMessage::Done { .. } => break,
+                ServerMessage::Error { message, .. } => panic!("{message}"),
+                _ => {}
+            }
+        }
+
+        client
+            .send(ClientMessage::ListSessions { agent: None })
+            .await
+            .unwrap();
+        let ServerMessage::Sessions { keys } = client.recv().await.unwrap() else {
+            panic!("expected sessions");
+        };
+        assert_eq!(keys, vec!["coop:dm:ipc:bob".to_owned()]);
+    }
+}
@@ -0,0 +1,115 @@
+//! Models discovered on local Ollama servers.
+//!
+//! Discovery runs once at startup. The results feed the model catalog (an
+//! `ollama` provider without a `models` list offers whatever is installed)
+//! and model capabilities (tool and vision support as Ollama reports them).
+
+use std::collections::HashMap;
+use std::sync::{LazyLock, RwLock};
+use tracing::{info, warn};
+
+use coop_agent::{OllamaModel, list_ollama_models, ollama_server_root};
+
+use crate::config::{Config, ProviderConfig};
+use crate::model_catalog::normalize_model_key;
+
+/// Installed models per Ollama server root.
+static DISCOVERED: LazyLock<RwLock<HashMap<String, Vec<OllamaModel>>>> =
+    LazyLock::new(Default::default);
+
+fn is_ollama(provider: &ProviderConfig) -> bool {
+    provider.normalized_name() == "ollama"
+}
+
+/// Ask every configured Ollama server which models it has installed.
+pub(crate) async fn discover(config: &Config) {
+    for provider in config.main_provider_configs() {
+        if !is_ollama(provider) {
+            continue;
+        }
+        let root = ollama_server_root(provider.base_url.as_deref());
+        match list_ollama_models(Some(&root)).await {
+            Ok(models) => {
+                info!(server = %root, count = models.len(), "discovered local ollama models");
+                record(&root, models);
+            }
+            Err(error) => {
+                warn!(server = %root, error = %format!("{error:#}"), "ollama model discovery failed");
+            }
+        }
+    }
+}
+
+pub(crate) fn record(server_root: &str, models: Vec<OllamaModel>) {
+    DISCOVERED
+        .write()
+        .expect("discovered models lock poisoned")
+        .insert(server_root.to_owned(), models);
+}
+
+/// Models discovered for `provider`, or `None` when it isn't an Ollama
+/// provider or its server wasn't reachable at startup.
+pub(crate) fn discovered_models(provider: &ProviderConfig) -> Option<Vec<OllamaModel>> {
+    if !is_ollama(provider) {
+        return None;
+    }
+    DISCOVERED
+        .read()
+        .expect("discovered models lock poisoned")
+        .get(&ollama_server_root(provider.base_url.as_deref()))
+        .cloned()
+}
+
+pub(crate) fn discovered_model(provider: &ProviderConfig, model: &str) -> Option<OllamaModel> {
+    let key = local_key(model);
+    discovered_models(provider)?
+        .into_iter()
+        .find(|candidate| local_key(&candidate.name) == key)
+}
+
+/// Ollama treats `llama3.2` and `llama3.2:latest` as the same model.
+fn local_key(model: &str) -> String {
+    let key = normalize_model_key(model);
+    key.strip_suffix(":latest")
+        .map_or_else(|| key.clone(), str::to_owned)
+}
+
+/// Catalog description for a discovered model, e.g. `local, 40k context,
+/// tools`.
+pub(crate) fn describe(model: &OllamaModel) -> String {
+    let mut parts = vec!["local".to_owned()];
+    if let Some(context) = model.context_length {
+        parts.push(format!("{}k context", context / 1024));
+    }
+    for capability in ["tools", "vision", "thinking"] {
+        if model.supports(capability) {
+            parts.push(capability.to_owned());
+        }
+    }
+    parts.join(", ")
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_latest_tag_and_prefix() {
+        let provider: ProviderConfig =
+            toml::from_str("name = \"ollama\"\nbase_url = \"http://discovery-test:11434/v1\"\n")
+                .unwrap();
+        record(
+            "http://discovery-test:11434",
+            vec![OllamaModel {
+                name: "llama3.2:latest".to_owned(),
+                size: 0,
+                context_length: Some(131_072),
+                capabilities: vec!["completion".to_owned(), "tools".to_owned()],
+            }],
+        );
+        let model = discovered_model(&provider, "ollama/llama3.2").unwrap();
+        assert_eq!(describe(&model), "local, 128k context, tools");
+        assert!(discovered_model(&provider, "qwen3").is_none());
+    }
+}
@@ -1,5 +1,6 @@
 #![allow(clippy::print_stdout, clippy::print_stderr)] // CLI binary — stdout/stderr is the UI
 
+mod budget;
 mod cli;
 mod commands;
 mod compaction;
@@ -10,9 +11,12 @@ mod config_tool;
 mod config_watcher;
 mod config_write;
 mod cron_delivery;
+mod cron_history;
+mod cron_jobs;
 mod cron_runner;
 mod cron_timezone;
 mod cron_tool;
+mod failover;
 mod final_reply;
 mod gateway;
 mod group_history;
@@ -23,6 +27,10 @@ mod init;
 mod init_templates;
 #[cfg(test)]
 mod injection;
+mod ipc_handler;
+mod local_models;
+mod mcp;
+mod memory_admin;
 mod memory_auto_capture;
 mod memory_embedding;
 mod memory_prompt_index;
@@ -30,6 +38,8 @@ mod memory_reconcile;
 mod memory_tools;
 mod model_capabilities;
 mod model_catalog;
+mod model_routing;
+mod office_documents;
 mod overflow_recovery;
 mod provider_factory;
 mod provider_registry;
@@ -38,14 +48,24 @@ mod router;
 mod sandbox_executor;
 mod scheduler;
 mod service;
+mod session_history;
 mod session_search;
 mod session_store;
 #[cfg(feature = "signal")]
 mod signal_loop;
+mod sqlite_session_store;
 mod subagents;
+#[cfg(feature = "telegram")]
+mod telegram_loop;
+mod tool_policy;
 mod tracing_setup;
+mod transcription;
+mod triggers;
 mod trust;
 mod tui_helpers;
+#[cfg(any(feature = "signal", feature = "telegram", test))]
+mod typing_router;
+mod usage_ledger;
 mod user_model_store;
 mod web_cache;
 mod web_fetch;
@@ -54,15 +74,14 @@ mod web_security;
 mod web_tools;
 
 use anyhow::{Context, Result};
-use chrono::Utc;
 use clap::Parser;
 use coop_core::tools::{CompositeExecutor, DefaultExecutor};
-use coop_core::{InboundKind, InboundMessage, Provider, TurnEvent};
+use coop_core::{Provider, Role, TurnEvent};
 use coop_ipc::{
-    ClientMessage, IpcClient, IpcConnection, IpcServer, PROTOCOL_VERSION, ServerMessage,
+    ClientMessage, IpcClient, IpcServer, PROTOCOL_VERSION, RemoteIpcServer, ServerMessage,
     socket_path,
 };
-use coop_memory::{Memory, SqliteMemory};
+use coop_memory::{Memory, SqliteMemory, group_principal, user_principal};
 use coop_tui::{
     App, Container, DisplayMessage, Editor, Footer, InputAction, StatusLine, Tui, handle_key_event,
     poll_event,
@@ -76,24 +95,38 @@ use tokio::sync::mpsc;
 use tokio_util::sync::CancellationToken;
 use tracing::{Instrument, debug, info, info_span, warn};
 
-use crate::cli::{Cli, Commands, GatewayCommands, MemoryCommands, SandboxCommands, SignalCommands};
-use crate::config::{Config, SharedConfig, shared_config};
+use crate::cli::{
+    Cli, Commands, CronCommands, GatewayCommands, MemoryCommands, ModelsCommands, SandboxCommands,
+    SessionsCommands, SignalCommands,
+};
+use crate::config::{Config, SessionStoreKind, SharedConfig, shared_config};
+use crate::cron_history::CronHistory;
+use crate::cron_jobs::{CronJobStore, CronJobToolExecutor};
 use crate::cron_tool::CronToolExecutor;
 use crate::gateway::Gateway;
+use crate::ipc_handler::{handle_client, handle_remote_client};
 use crate::memory_embedding::build_embedder;
 use crate::memory_reconcile::ProviderReconciler;
 use crate::memory_tools::MemoryToolExecutor;
 use crate::router::MessageRouter;
 #[cfg(feature = "signal")]
 use crate::signal_loop::run_signal_loop;
+use crate::sqlite_session_store::{SessionFilter, SqliteSessionStore};
 use crate::subagents::{SubagentManager, SubagentToolExecutor};
+#[cfg(feature = "telegram")]
+use crate::telegram_loop::run_telegram_loop;
 use crate::tui_helpers::{
     build_tui, extract_tool_result, format_tui_welcome, resolve_working_dir, sync_editor_from_app,
     update_chat_messages,
 };
+use crate::usage_ledger::{UsageFilter, UsageGrouping, UsageLedger};
 
 #[cfg(feature = "signal")]
 use coop_channels::{SignalChannel, SignalToolExecutor, SignalTypingNotifier};
+#[cfg(feature = "telegram")]
+use coop_channels::{
+    TelegramChannel, TelegramSettings, TelegramToolExecutor, TelegramTypingNotifier,
+};
 
 // Component indices — layout: header(0), chat(1), spacer(2), status(3), editor(4), footer(5)
 const CHAT_IDX: usize = 1;
@@ -112,6 +145,10 @@ async fn main() -> Result<()> {
             | Commands::Gateway { .. }
             | Commands::Signal { .. }
             | Commands::Memory { .. }
+            | Commands::Sessions { .. }
+            | Commands::Usage { .. }
+            | Commands::Models { .. }
+            | Commands::Cron { .. }
             | Commands::Sandbox { .. }
             | Commands::Version
             | Commands::Init { .. }
@@ -126,13 +163,44 @@ async fn main() -> Result<()> {
 
     match cli.command {
         Commands::Init { dir } => init::cmd_init(dir.as_deref()),
-        Commands::Check { format } => cmd_check(cli.config.as_deref(), &format),
+        Commands::Check { format } => cmd_check(cli.config.as_deref(), &format).await,
         Commands::Start => cmd_start(cli.config.as_deref()).await,
         Commands::Gateway { command } => cmd_gateway(cli.config.as_deref(), command).await,
-        Commands::Chat { user } => cmd_chat(cli.config.as_deref(), user.as_deref()).await,
-        Commands::Attach { session } => cmd_attach(cli.config.as_deref(), &session).await,
+        Commands::Chat { user, agent } => {
+            cmd_chat(cli.config.as_deref(), user.as_deref(), agent.as_deref()).await
+        }
+        Commands::Attach {
+            session,
+            agent,
+            remote,
+        } => {
+            cmd_attach(
+                cli.config.as_deref(),
+                &session,
+                agent.as_deref(),
+                remote.as_deref(),
+            )
+            .await
+        }
         Commands::Signal { command } => cmd_signal(cli.config.as_deref(), command).await,
-        Commands::Memory { command } => cmd_memory(cli.config.as_deref(), command).await,
+        Commands::Memory { command, json } => {
+            cmd_memory(cli.config.as_deref(), command, json).await
+        }
+        Commands::Sessions { command } => cmd_sessions(cli.config.as_deref(), command),
+        Commands::Usage {
+            agent,
+            by,
+            days,
+            user,
+        } => cmd_usage(
+            cli.config.as_deref(),
+            agent.as_deref(),
+            &by,
+            days,
+            user.as_deref(),
+        ),
+        Commands::Models { command } => cmd_models(cli.config.as_deref(), command).await,
+        Commands::Cron { command } => cmd_cron(cli.config.as_deref(), command),
         Commands::Sandbox { ref command } => cmd_sandbox(command),
         Commands::Version => {
             println!("🐔 coop {}", env!("CARGO_PKG_VERSION"));
@@ -210,17 +278,54 @@ fn signal_user_mappings(config: &Config) -> Vec<(String, String)> {
         .collect()
 }
 
+/// Build Telegram channel settings from `[channels.telegram]` and the
+/// `telegram:<user id>` match patterns in `[[users]]`.
+#[cfg(feature = "telegram")]
+fn telegram_settings(
+    config: &Config,
+    telegram: &config::TelegramChannelConfig,
+    workspace: &Path,
+) -> Result<TelegramSettings> {
+    let token = std::env::var(&telegram.token_env).with_context(|| {
+        format!(
+            "telegram bot token env var {} is not set",
+            telegram.token_env
+        )
+    })?;
+
+    let mut settings = TelegramSettings::new(token, workspace);
+    settings.api_base_url.clone_from(&telegram.api_base_url);
+    settings.poll_timeout = Duration::from_secs(telegram.poll_timeout_secs);
+    for user in &config.users {
+        for id in user
+            .r#match
+            .iter()
+            .filter_map(|pattern| pattern.strip_prefix("telegram:"))
+        {
+            if user.trust <= coop_core::TrustLevel::Inner {
+                settings.trusted_senders.insert(id.to_owned());
+            }
+            settings.user_trusts.insert(id.to_owned(), user.trust);
+            settings.user_names.insert(id.to_owned(), user.name.clone());
+        }
+    }
+    Ok(settings)
+}
+
 // ---------------------------------------------------------------------------
 // cmd_check — validate config without starting
 // ---------------------------------------------------------------------------
 
 #[allow(clippy::unnecessary_wraps)] // must return Result to match main's match arms
-fn cmd_check(config_path: Option<&str>, format: &str) -> Result<()> {
+async fn cmd_check(config_path: Option<&str>, format: &str) -> Result<()> {
     let config_file = Config::find_config_path(config_path);
     let config_dir = config_file
         .parent()
         .unwrap_or(&PathBuf::from("."))
         .to_path_buf();
+    if let Ok(config) = Config::load(&config_file) {
+        local_models::discover(&config).await;
+    }
 
     let report = config_check::validate_config(&config_file, &config_dir);
 
@@ -307,24 +412,273 @@ fn init_memory_store(
         .context("failed to initialize memory embedding provider")?;
     let reconciler: Arc<dyn coop_memory::Reconciler> = Arc::new(ProviderReconciler::new(provider));
 
-    let memory: Arc<dyn Memory> = Arc::new(
-        SqliteMemory::open_with_components(
-            &memory_db_path,
-            config.agent.id.clone(),
-            embedder,
-            Some(reconciler),
+    let sqlite = SqliteMemory::open_with_components(
+        &memory_db_path,
+        config.agent.id.clone(),
+        embedder,
+        Some(reconciler),
+    )
+    .with_context(|| {
+        format!(
+            "failed to initialize memory db at {}",
+            memory_db_path.display()
         )
-        .with_context(|| {
-            format!(
-                "failed to initialize memory db at {}",
-                memory_db_path.display()
-            )
-        })?,
-    );
+    })?;
+
+    match sqlite.assign_owners(|session_key| memory_owner_for_session(config, session_key)) {
+        Ok(0) => {}
+        Ok(assigned) => info!(assigned, "assigned owners to existing memory rows"),
+        Err(error) => warn!(error = %error, "failed to assign memory owners"),
+    }
 
+    let memory: Arc<dyn Memory> = Arc::new(sqlite);
     Ok(memory)
 }
 
+/// Owner principal for a memory row written before rows carried one: the
+/// group for group sessions, the matched user for DMs, and the job's user
+/// for cron sessions.
+fn memory_owner_for_session(config: &Config, session_key: &str) -> Option<String> {
+    let rest = session_key
+        .strip_prefix(config.agent.id.as_str())?
+        .strip_prefix(':')?;
+    if let Some(group_id) = rest.strip_prefix("group:") {
+        return Some(group_principal(group_id));
+    }
+
+    let user = if let Some(identity) = rest.strip_prefix("dm:") {
+        let user = config.users.iter().find(|user| {
+            user.name == identity || user.r#match.iter().any(|pattern| pattern == identity)
+        })?;
+        user.name.as_str()
+    } else if let Some(job) = rest.strip_prefix("cron:") {
+        config
+            .cron
+            .iter()
+            .find(|cron| cron.name == job)?
+            .user
+            .as_deref()?
+    } else {
+        return None;
+    };
+    Some(user_principal(user))
+}
+
+/// Process-wide services every hosted agent's gateway is wired into.
+struct AgentServices<'a> {
+    config: SharedConfig,
+    config_file: &'a Path,
+    config_dir: &'a Path,
+    mcp_executor: &'a mcp::McpToolExecutor,
+    deliver_tx: Option<&'a cron_runner::DeliverySender>,
+    budget_alerts: Option<mpsc::Sender<String>>,
+    reminder_store: &'a reminder::ReminderStore,
+    cron_jobs: &'a CronJobStore,
+    scheduler_notify: &'a Arc<tokio::sync::Notify>,
+    cron_command_tx: &'a cron_runner::CronCommandSender,
+    #[cfg(feature = "signal")]
+    signal: Option<(
+        mpsc::Sender<coop_channels::SignalAction>,
+        mpsc::Sender<coop_channels::SignalQuery>,
+    )>,
+    #[cfg(feature = "telegram")]
+    telegram_action_tx: Option<mpsc::Sender<coop_channels::TelegramAction>>,
+    typing_notifier: Option<Arc<dyn coop_core::TypingNotifier>>,
+}
+
+/// One agent's gateway along with its config view and memory store.
+struct AgentRuntime {
+    config: SharedConfig,
+    gateway: Arc<Gateway>,
+    memory: Arc<dyn Memory>,
+}
+
+/// Build the gateway for one configured agent: its own provider, workspace,
+/// memory DB and subagent manager, sharing channels, cron and MCP servers
+/// with the other agents.
+#[allow(clippy::too_many_lines)]
+fn build_agent_runtime(agent_id: &str, services: &AgentServices<'_>) -> Result<AgentRuntime> {
+    let config = services
+        .config
+        .load()
+        .for_agent(agent_id)
+        .with_context(|| format!("unknown agent '{agent_id}'"))?;
+    let workspace = config
+        .resolve_workspace(services.config_dir)
+        .with_context(|| format!("agent '{agent_id}'"))?;
+
+    let provider = provider_factory::create_primary_provider(&config)
+        .with_context(|| format!("failed to initialize provider for agent '{agent_id}'"))?;
+    let providers = provider_factory::build_provider_registry(Arc::clone(&provider), &config);
+    let memory = init_memory_store(&config, services.config_dir, Arc::clone(&provider))?;
+    let web_tool_config = config.tools.web.clone();
+
+    let shared = shared_config(config);
+    let subagents = Arc::new(SubagentManager::new(
+        Arc::clone(&shared),
+        workspace.clone(),
+    )?);
+    subagents.bind_delivery(
+        services
+            .deliver_tx
+            .map(cron_runner::DeliverySender::channel_sender),
+    );
+
+    let default_executor = DefaultExecutor::new();
+    let config_executor = config_tool::ConfigToolExecutor::new(services.config_file.to_path_buf());
+    let cron_history = CronHistory::open(workspace.join(cron_history::CRON_HISTORY_DB_FILE))?;
+    let cron_executor = CronToolExecutor::new(
+        Arc::clone(&services.config),
+        services.cron_command_tx.clone(),
+    )
+    .with_jobs(services.cron_jobs.clone())
+    .with_history(Arc::new(cron_history));
+    let cron_job_executor = CronJobToolExecutor::new(
+        services.cron_jobs.clone(),
+        Arc::clone(&services.config),
+        Arc::clone(services.scheduler_notify),
+    );
+    let memory_executor =
+        MemoryToolExecutor::new(Arc::clone(&memory)).with_config(Arc::clone(&services.config));
+    let reminder_executor = reminder::ReminderToolExecutor::new(
+        services.reminder_store.clone(),
+        Arc::clone(&services.config),
+        Arc::clone(services.scheduler_notify),
+    );
+    let image_executor = image_tools::ImageToolExecutor::new(Arc::clone(&shared));
+    let web_executor = web_tools::WebToolExecutor::new(&web_tool_config);
+    let session_search_executor =
+        session_search::SessionSearchExecutor::new(Arc::clone(&memory), Arc::clone(&provider));
+    let subagent_executor = SubagentToolExecutor::new(Arc::clone(&subagents));
+
+    let mut executors: Vec<Box<dyn coop_core::ToolExecutor>> = vec![
+        Box::new(default_executor),
+        Box::new(config_executor),
+        Box::new(cron_executor),
+        Box::new(cron_job_executor),
+        Box::new(memory_executor),
+        Box::new(reminder_executor),
+        Box::new(image_executor),
+        Box::new(web_executor),
+        Box::new(session_search_executor),
+        Box::new(subagent_executor),
+    ];
+
+    #[cfg(feature = "signal")]
+    if let Some((action_tx, query_tx)) = services.signal.clone() {
+        executors.push(Box::new(SignalToolExecutor::new(action_tx, query_tx)));
+    }
+
+    #[cfg(feature = "telegram")]
+    if let Some(action_tx) = services.telegram_action_tx.clone() {
+        executors.push(Box::new(TelegramToolExecutor::new(action_tx)));
+    }
+
+    // MCP goes last; its tools are renamed rather than shadow a built-in.
+    let builtin_names = executors
+        .iter()
+        .flat_map(|executor| executor.tools())
+        .map(|tool| tool.name)
+        .collect::<Vec<_>>();
+    executors.push(Box::new(
+        services
+            .mcp_executor
+            .clone()
+            .with_reserved_names(builtin_names),
+    ));
+
+    let executor: Arc<dyn coop_core::ToolExecutor> = Arc::new(CompositeExecutor::new(executors));
+
+    // Wrap executor with SandboxExecutor when sandbox is enabled
+    let executor: Arc<dyn coop_core::ToolExecutor> = if shared.load().sandbox.enabled {
+        let sandbox_info = coop_sandbox::probe().expect("sandbox already checked above");
+        info!(agent = %agent_id, sandbox = %sandbox_info.name, "sandbox enabled");
+        let base_policy = coop_sandbox::SandboxPolicy {
+            workspace: workspace.clone(),
+            network: coop_sandbox::NetworkMode::None,
+            memory_limit: coop_sandbox::parse_memory_size(&shared.load().sandbox.memory)
+                .unwrap_or(2 * 1024 * 1024 * 1024),
+            pids_limit: shared.load().sandbox.pids_limit,
+            long_lived: shared.load().sandbox.long_lived,
+        };
+        Arc::new(sandbox_executor::SandboxExecutor::new(
+            executor,
+            base_policy,
+            Arc::clone(&shared),
+        ))
+    } else {
+        executor
+    };
+
+    let gateway = Arc::new(Gateway::new_with_subagents(
+        Arc::clone(&shared),
+        workspace,
+        providers,
+        executor,
+        services.typing_notifier.clone(),
+        Some(Arc::clone(&memory)),
+        Arc::clone(&subagents),
+    )?);
+    subagents.bind_gateway(&gateway);
+    gateway.bind_budget_alerts(services.budget_alerts.clone());
+    info!(agent = %agent_id, "agent gateway ready");
+
+    Ok(AgentRuntime {
+        config: shared,
+        gateway,
+        memory,
+    })
+}
+
+fn spawn_remote_ipc_listener(
+    server: RemoteIpcServer,
+    router: Arc<MessageRouter>,
+    config: SharedConfig,
+    shutdown_token: CancellationToken,
+) -> tokio::task::JoinHandle<()> {
+    let handshakes = Arc::new(tokio::sync::Semaphore::new(
+        ipc_handler::MAX_PENDING_REMOTE_HANDSHAKES,
+    ));
+    tokio::spawn(async move {
+        loop {
+            tokio::select! {
+                () = shutdown_token.cancelled() => break,
+                accepted = server.accept() => match accepted {
+                    Ok((connection, peer)) => {
+                        let Ok(handshake) = Arc::clone(&handshakes).try_acquire_owned() else {
+                            tracing::warn!(peer = %peer, "too many pending remote ipc handshakes, dropping connection");
+                            continue;
+                        };
+                        let router = Arc::clone(&router);
+                        let config = Arc::clone(&config);
+                        tokio::spawn(async move {
+                            if let Err(error) =
+                                handle_remote_client(connection, router, config, handshake).await
+                            {
+                                tracing::debug!(peer = %peer, error = %error, "remote ipc client disconnected with error");
+                            }
+                        });
+                    }
+                    Err(error) => {
+                        tracing::error!(error = %error, "failed to accept remote IPC client");
+                    }
+                },
+            }
+        }
+    })
+}
+
+/// Point trace redaction at the live `[redaction] mode` and start reporting
+/// how many secrets it found.
+fn spawn_trace_redaction(
+    config: &SharedConfig,
+    shutdown_token: CancellationToken,
+) -> tokio::task::JoinHandle<()> {
+    let config = Arc::clone(config);
+    tracing_setup::bind_redaction_mode(move || config.load().redaction.mode);
+    tracing_setup::spawn_redaction_report(Duration::from_mins(1), shutdown_token)
+}
+
 fn spawn_memory_maintenance_loop(
     memory: Arc<dyn Memory>,
     config: SharedConfig,
@@ -440,6 +794,7 @@ async fn cmd_start(config_path: Option<&str>) -> Result<()> {
     let config_file = Config::find_config_path(config_path);
     let config = Config::load(&config_file)
         .with_context(|| format!("loading config from {}", config_file.display()))?;
+    local_models::discover(&config).await;
 
     let config_dir = config_file
         .parent()
@@ -447,10 +802,6 @@ async fn cmd_start(config_path: Option<&str>) -> Result<()> {
         .to_path_buf();
     let workspace = config.resolve_workspace(&config_dir)?;
 
-    let provider = provider_factory::create_primary_provider(&config)
-        .context("failed to initialize provider")?;
-    let providers = provider_factory::build_provider_registry(Arc::clone(&provider), &config);
-
     #[cfg(feature = "signal")]
     let mut signal_channel: Option<SignalChannel> = None;
     #[cfg(feature = "signal")]
@@ -502,75 +853,82 @@ async fn cmd_start(config_path: Option<&str>) -> Result<()> {
         }
     }
 
-    let memory = init_memory_store(&config, &config_dir, Arc::clone(&provider))?;
+    #[cfg(feature = "telegram")]
+    let mut telegram_channel: Option<TelegramChannel> = None;
+    #[cfg(feature = "telegram")]
+    let mut telegram_action_tx: Option<mpsc::Sender<coop_channels::TelegramAction>> = None;
+
+    if let Some(telegram) = &config.channels.telegram {
+        #[cfg(feature = "telegram")]
+        {
+            let connected = match telegram_settings(&config, telegram, &workspace) {
+                Ok(settings) => TelegramChannel::connect(settings).await,
+                Err(error) => Err(error),
+            };
+            match connected {
+                Ok(channel) => {
+                    info!(bot = channel.bot_username(), "telegram channel configured");
+                    telegram_action_tx = Some(channel.action_sender());
+                    telegram_channel = Some(channel);
+                }
+                Err(error) => {
+                    tracing::error!(
+                        error = format!("{error:#}"),
+                        "failed to initialize telegram channel",
+                    );
+                }
+            }
+        }
+
+        #[cfg(not(feature = "telegram"))]
+        {
+            let _ = telegram;
+            tracing::warn!(
+                "telegram is configured, but this binary was built without the 'telegram' feature"
+            );
+        }
+    }
 
     // Capture startup values before wrapping in SharedConfig.
     // SharedConfig is created early so it can be shared with tool executors.
     let agent_id = config.agent.id.clone();
     let agent_model = config.agent.model.clone();
-    let web_tool_config = config.tools.web.clone();
+    let agent_ids: Vec<String> = config.all_agents().map(|agent| agent.id.clone()).collect();
+    let mcp_executor = mcp::McpToolExecutor::connect(&config.mcp, &config_dir).await;
 
     let shared = shared_config(config);
-    let subagents = Arc::new(SubagentManager::new(
-        Arc::clone(&shared),
-        workspace.clone(),
-    )?);
 
     // Build the delivery sender early so both the send_message tool and the
     // scheduler can use it.
-    #[cfg(feature = "signal")]
-    let deliver_tx: Option<cron_runner::DeliverySender> = signal_action_tx
-        .as_ref()
-        .map(|tx| cron_runner::spawn_signal_delivery_bridge(tx.clone()));
+    #[cfg(any(feature = "signal", feature = "telegram"))]
+    let deliver_tx: Option<cron_runner::DeliverySender> = {
+        #[allow(unused_mut)]
+        let mut routes = Vec::new();
+        #[cfg(feature = "signal")]
+        if let Some(tx) = &signal_action_tx {
+            routes.push((
+                "signal",
+                cron_runner::spawn_signal_delivery_bridge(tx.clone()),
+            ));
+        }
+        #[cfg(feature = "telegram")]
+        if let Some(tx) = &telegram_action_tx {
+            routes.push((
+                "telegram",
+                cron_runner::spawn_telegram_delivery_bridge(tx.clone()),
+            ));
+        }
+        cron_runner::spawn_delivery_router(routes)
+    };
 
-    #[cfg(not(feature = "signal"))]
+    #[cfg(not(any(feature = "signal", feature = "telegram")))]
     let deliver_tx: Option<cron_runner::DeliverySender> = None;
 
-    subagents.bind_delivery(
-        deliver_tx
-            .as_ref()
-            .map(cron_runner::DeliverySender::channel_sender),
-    );
-
     let reminder_store = reminder::ReminderStore::new(workspace.join("sessions"))?;
+    let cron_jobs = CronJobStore::new(workspace.join("sessions"))?;
     let scheduler_notify = Arc::new(tokio::sync::Notify::new());
     let (cron_command_tx, cron_command_rx) = mpsc::channel(16);
 
-    let default_executor = DefaultExecutor::new();
-    let config_executor = config_tool::ConfigToolExecutor::new(config_file.clone());
-    let cron_executor = CronToolExecutor::new(Arc::clone(&shared), cron_command_tx);
-    let memory_executor = MemoryToolExecutor::new(Arc::clone(&memory));
-    let reminder_executor = reminder::ReminderToolExecutor::new(
-        reminder_store.clone(),
-        Arc::clone(&shared),
-        Arc::clone(&scheduler_notify),
-    );
-    let image_executor = image_tools::ImageToolExecutor::new(Arc::clone(&shared));
-    let web_executor = web_tools::WebToolExecutor::new(&web_tool_config);
-    let session_search_executor =
-        session_search::SessionSearchExecutor::new(Arc::clone(&memory), Arc::clone(&provider));
-    let subagent_executor = SubagentToolExecutor::new(Arc::clone(&subagents));
-
-    #[allow(unused_mut)]
-    let mut executors: Vec<Box<dyn coop_core::ToolExecutor>> = vec![
-        Box::new(default_executor),
-        Box::new(config_executor),
-        Box::new(cron_executor),
-        Box::new(memory_executor),
-        Box::new(reminder_executor),
-        Box::new(image_executor),
-        Box::new(web_executor),
-        Box::new(session_search_executor),
-        Box::new(subagent_executor),
-    ];
-
-    #[cfg(feature = "signal")]
-    if let (Some(action_tx), Some(query_tx)) = (signal_action_tx.clone(), signal_query_tx.clone()) {
-        executors.push(Box::new(SignalToolExecutor::new(action_tx, query_tx)));
-    }
-
-    let executor: Arc<dyn coop_core::ToolExecutor> = Arc::new(CompositeExecutor::new(executors));
-
     // Check sandbox availability early if enabled
     if shared.load().sandbox.enabled
         && let Err(e) = coop_sandbox::probe()
@@ -580,53 +938,67 @@ async fn cmd_start(config_path: Option<&str>) -> Result<()> {
         );
     }
 
-    // Wrap executor with SandboxExecutor when sandbox is enabled
-    let executor: Arc<dyn coop_core::ToolExecutor> = if shared.load().sandbox.enabled {
-        let sandbox_info = coop_sandbox::probe().expect("sandbox already checked above");
-        info!(sandbox = %sandbox_info.name, "sandbox enabled");
-        let base_policy = coop_sandbox::SandboxPolicy {
-            workspace: workspace.clone(),
-            network: coop_sandbox::NetworkMode::None,
-            memory_limit: coop_sandbox::parse_memory_size(&shared.load().sandbox.memory)
-                .unwrap_or(2 * 1024 * 1024 * 1024),
-            pids_limit: shared.load().sandbox.pids_limit,
-            long_lived: shared.load().sandbox.long_lived,
-        };
-        Arc::new(sandbox_executor::SandboxExecutor::new(
-            executor,
-            base_policy,
-            Arc::clone(&shared),
-        ))
-    } else {
-        executor
+    #[cfg(any(feature = "signal", feature = "telegram"))]
+    let typing_notifier: Option<Arc<dyn coop_core::TypingNotifier>> = {
+        #[allow(unused_mut)]
+        let mut notifiers: Vec<(&'static str, Arc<dyn coop_core::TypingNotifier>)> = Vec::new();
+        #[cfg(feature = "signal")]
+        if let Some(action_tx) = &signal_action_tx {
+            notifiers.push((
+                "signal",
+                Arc::new(SignalTypingNotifier::new(action_tx.clone())),
+            ));
+        }
+        #[cfg(feature = "telegram")]
+        if let Some(action_tx) = &telegram_action_tx {
+            notifiers.push((
+                "telegram",
+                Arc::new(TelegramTypingNotifier::new(action_tx.clone())),
+            ));
+        }
+        typing_router::ChannelTypingNotifier::new(notifiers).into_notifier()
     };
 
-    #[cfg(feature = "signal")]
-    let typing_notifier: Option<Arc<dyn coop_core::TypingNotifier>> =
-        signal_action_tx.as_ref().map(|action_tx| {
-            Arc::new(SignalTypingNotifier::new(action_tx.clone()))
-                as Arc<dyn coop_core::TypingNotifier>
-        });
-
-    #[cfg(not(feature = "signal"))]
+    #[cfg(not(any(feature = "signal", feature = "telegram")))]
     let typing_notifier: Option<Arc<dyn coop_core::TypingNotifier>> = None;
 
-    let maintenance_memory = Arc::clone(&memory);
-
-    let gateway = Arc::new(Gateway::new_with_subagents(
-        Arc::clone(&shared),
-        workspace,
-        providers,
-        executor,
+    let services = AgentServices {
+        config: Arc::clone(&shared),
+        config_file: &config_file,
+        config_dir: &config_dir,
+        mcp_executor: &mcp_executor,
+        deliver_tx: deliver_tx.as_ref(),
+        budget_alerts: deliver_tx.clone().map(|deliver_tx| {
+            cron_runner::spawn_budget_alert_delivery(Arc::clone(&shared), deliver_tx)
+        }),
+        reminder_store: &reminder_store,
+        cron_jobs: &cron_jobs,
+        scheduler_notify: &scheduler_notify,
+        cron_command_tx: &cron_command_tx,
+        #[cfg(feature = "signal")]
+        signal: signal_action_tx.clone().zip(signal_query_tx.clone()),
+        #[cfg(feature = "telegram")]
+        telegram_action_tx: telegram_action_tx.clone(),
         typing_notifier,
-        Some(memory),
-        Arc::clone(&subagents),
-    )?);
-    subagents.bind_gateway(&gateway);
-    let router = Arc::new(MessageRouter::new(
-        Arc::clone(&shared),
-        Arc::clone(&gateway),
-    ));
+    };
+    let runtimes = agent_ids
+        .iter()
+        .map(|id| build_agent_runtime(id, &services))
+        .collect::<Result<Vec<_>>>()?;
+    drop(services);
+    let gateways: Vec<_> = runtimes
+        .iter()
+        .map(|runtime| Arc::clone(&runtime.gateway))
+        .collect();
+    for gateway in &gateways {
+        gateway.bind_peers(&gateways);
+    }
+
+    let router = runtimes.iter().skip(1).fold(
+        MessageRouter::new(Arc::clone(&shared), Arc::clone(&runtimes[0].gateway)),
+        |router, runtime| router.with_agent(Arc::clone(&runtime.gateway)),
+    );
+    let router = Arc::new(router);
 
     let working_dir = resolve_working_dir();
 
@@ -651,19 +1023,52 @@ async fn cmd_start(config_path: Option<&str>) -> Result<()> {
             return Err(error);
         }
     };
+    let remote_listen = shared.load().ipc.remote.as_ref().map(|r| r.listen.clone());
+    let remote_server = match remote_listen {
+        Some(listen) => match RemoteIpcServer::bind(&listen).await {
+            Ok(server) => Some(server),
+            Err(error) => {
+                service::remove_pid_file(&agent_id);
+                return Err(error);
+            }
+        },
+        None => None,
+    };
     info!(path = %pid_file.display(), pid = std::process::id(), "pid file written");
 
     let shutdown_token = CancellationToken::new();
+    let _trace_redaction = spawn_trace_redaction(&shared, shutdown_token.clone());
+
+    if let Some(remote_server) = remote_server {
+        let addr = remote_server.local_addr()?;
+        info!(addr = %addr, "remote ipc listener started");
+        println!("remote ipc listening on {addr}");
+        spawn_remote_ipc_listener(
+            remote_server,
+            Arc::clone(&router),
+            Arc::clone(&shared),
+            shutdown_token.clone(),
+        );
+    }
 
-    let _maintenance_task = spawn_memory_maintenance_loop(
-        maintenance_memory,
-        Arc::clone(&shared),
-        shutdown_token.clone(),
-    );
+    let _maintenance_tasks: Vec<_> = runtimes
+        .iter()
+        .map(|runtime| {
+            spawn_memory_maintenance_loop(
+                Arc::clone(&runtime.memory),
+                Arc::clone(&runtime.config),
+                shutdown_token.clone(),
+            )
+        })
+        .collect();
 
     let _config_watcher = config_watcher::spawn_config_watcher(
         config_file,
         Arc::clone(&shared),
+        runtimes
+            .iter()
+            .map(|runtime| (runtime.gateway.agent_id(), Arc::clone(&runtime.config)))
+            .collect(),
         shutdown_token.clone(),
         Some(Arc::clone(&scheduler_notify)),
     );
@@ -684,6 +1089,22 @@ async fn cmd_start(config_path: Option<&str>) -> Result<()> {
         });
     }
 
+    #[cfg(feature = "telegram")]
+    let telegram_active = telegram_channel.is_some();
+    #[cfg(not(feature = "telegram"))]
+    let telegram_active = false;
+
+    #[cfg(feature = "telegram")]
+    if let Some(telegram_channel) = telegram_channel {
+        info!("telegram channel starting");
+        let router = Arc::clone(&router);
+        tokio::spawn(async move {
+            if let Err(error) = run_telegram_loop(telegram_channel, router).await {
+                tracing::error!(error = %error, "telegram loop stopped");
+            }
+        });
+    }
+
     if !signal_active {
         let has_signal_cron = shared
             .load()
@@ -698,6 +1119,29 @@ async fn cmd_start(config_path: Option<&str>) -> Result<()> {
         }
     }
 
+    if !telegram_active {
+        let has_telegram_cron = shared
+            .load()
+            .cron
+            .iter()
+            .any(|c| c.deliver.as_ref().is_some_and(|d| d.channel == "telegram"));
+        if has_telegram_cron {
+            warn!(
+                "cron jobs reference telegram delivery but telegram channel is not active \
+                 (add [channels.telegram] to config and build with --features telegram)"
+            );
+        }
+    }
+
+    triggers::spawn_triggers(
+        Arc::clone(&shared),
+        config_dir.clone(),
+        Arc::clone(&router),
+        deliver_tx.clone(),
+        shutdown_token.clone(),
+    )
+    .await?;
+
     {
         let sched_config = Arc::clone(&shared);
         let sched_router = Arc::clone(&router);
@@ -713,6 +1157,7 @@ async fn cmd_start(config_path: Option<&str>) -> Result<()> {
                 sched_token,
                 Some(sched_notify),
                 Some(sched_reminders),
+                Some(cron_jobs),
                 sched_commands,
             )
             .await;
@@ -734,10 +1179,8 @@ async fn cmd_start(config_path: Option<&str>) -> Result<()> {
                 match accepted {
                     Ok(connection) => {
                         let router = Arc::clone(&router);
-                        let gateway = Arc::clone(&gateway);
-                        let agent_id = agent_id.clone();
                         tokio::spawn(async move {
-                            if let Err(error) = handle_client(connection, router, gateway, agent_id).await {
+                            if let Err(error) = handle_client(connection, router).await {
                                 tracing::debug!(error = %error, "ipc client disconnected with error");
                             }
                         });
@@ -749,146 +1192,30 @@ async fn cmd_start(config_path: Option<&str>) -> Result<()> {
             }
             _ = tokio::signal::ctrl_c() => {
                 info!("shutting down");
-                break;
-            }
-        }
-    }
-
-    shutdown_token.cancel();
-
-    #[cfg(feature = "signal")]
-    if let Some(action_tx) = signal_action_tx {
-        let _ = action_tx.send(coop_channels::SignalAction::Shutdown).await;
-        // Brief grace period for the signal runtime to close the websocket cleanly.
-        tokio::time::sleep(Duration::from_millis(250)).await;
-    }
-
-    service::remove_pid_file(&agent_id);
-    info!("pid file removed");
-
-    Ok(())
-}
-
-async fn handle_client(
-    mut connection: IpcConnection,
-    router: Arc<MessageRouter>,
-    gateway: Arc<Gateway>,
-    agent_id: String,
-) -> Result<()> {
-    loop {
-        let Ok(message) = connection.recv().await else {
-            return Ok(());
-        };
-
-        match message {
-            ClientMessage::Hello { version } => {
-                if version != PROTOCOL_VERSION {
-                    tracing::warn!(
-                        client_version = version,
-                        server_version = PROTOCOL_VERSION,
-                        "ipc version mismatch"
-                    );
-                }
-                connection
-                    .send(ServerMessage::Hello {
-                        version: PROTOCOL_VERSION,
-                        agent_id: agent_id.clone(),
-                    })
-                    .await?;
-            }
-            ClientMessage::Subscribe { .. } => {}
-            ClientMessage::ListSessions => {
-                let keys = gateway
-                    .list_sessions()
-                    .into_iter()
-                    .map(|key| key.to_string())
-                    .collect();
-                connection.send(ServerMessage::Sessions { keys }).await?;
-            }
-            ClientMessage::Clear { session } => match gateway.resolve_session(&session) {
-                Some(key) => gateway.clear_session(&key),
-                None => {
-                    connection
-                        .send(ServerMessage::Error {
-                            session,
-                            message: "unknown session".to_owned(),
-                        })
-                        .await?;
-                }
-            },
-            ClientMessage::Stop { session } => match gateway.resolve_session(&session) {
-                Some(key) => {
-                    gateway.cancel_active_turn(&key);
-                }
-                None => {
-                    connection
-                        .send(ServerMessage::Error {
-                            session,
-                            message: "unknown session".to_owned(),
-                        })
-                        .await?;
-                }
-            },
-            ClientMessage::Send { session, content } => {
-                handle_send(&mut connection, Arc::clone(&router), session, content).await?;
+                break;
             }
         }
     }
-}
-
-async fn handle_send(
-    connection: &mut IpcConnection,
-    router: Arc<MessageRouter>,
-    session: String,
-    content: String,
-) -> Result<()> {
-    let kind = if content.trim_start().starts_with('/') {
-        InboundKind::Command
-    } else {
-        InboundKind::Text
-    };
-    let inbound = InboundMessage {
-        channel: "terminal:default".to_owned(),
-        sender: "tui".to_owned(),
-        content,
-        chat_id: None,
-        is_group: false,
-        timestamp: Utc::now(),
-        reply_to: Some(session.clone()),
-        kind,
-        message_timestamp: None,
-        group_revision: None,
-    };
 
-    let (event_tx, mut event_rx) = mpsc::channel(64);
-    let router_task = tokio::spawn(async move { router.dispatch(&inbound, event_tx).await });
+    shutdown_token.cancel();
 
-    while let Some(event) = event_rx.recv().await {
-        if let Some(message) = ServerMessage::from_turn_event(session.clone(), event) {
-            connection.send(message).await?;
-        }
+    #[cfg(feature = "signal")]
+    if let Some(action_tx) = signal_action_tx {
+        let _ = action_tx.send(coop_channels::SignalAction::Shutdown).await;
+        // Brief grace period for the signal runtime to close the websocket cleanly.
+        tokio::time::sleep(Duration::from_millis(250)).await;
     }
 
-    match router_task.await {
-        Ok(Ok(_)) => {}
-        Ok(Err(error)) => {
-            connection
-                .send(ServerMessage::Error {
-                    session,
-                    message: error.to_string(),
-                })
-                .await?;
-        }
-        Err(error) => {
-            connection
-                .send(ServerMessage::Error {
-                    session,
-                    message: format!("internal server error: {error}"),
-                })
-                .await?;
-        }
+    #[cfg(feature = "telegram")]
+    if let Some(action_tx) = telegram_action_tx {
+        let _ = action_tx
+            .send(coop_channels::TelegramAction::Shutdown)
+            .await;
     }
 
+    service::remove_pid_file(&agent_id);
+    info!("pid file removed");
+
     Ok(())
 }
 
@@ -897,10 +1224,19 @@ async fn handle_send(
 // ---------------------------------------------------------------------------
 
 #[allow(clippy::too_many_lines, clippy::items_after_statements)]
-async fn cmd_chat(config_path: Option<&str>, user_flag: Option<&str>) -> Result<()> {
+async fn cmd_chat(
+    config_path: Option<&str>,
+    user_flag: Option<&str>,
+    agent_flag: Option<&str>,
+) -> Result<()> {
     let config_file = Config::find_config_path(config_path);
-    let config = Config::load(&config_file)
+    let root_config = Config::load(&config_file)
         .with_context(|| format!("loading config from {}", config_file.display()))?;
+    local_models::discover(&root_config).await;
+    let agent_id = agent_flag.unwrap_or(&root_config.agent.id).to_owned();
+    let config = root_config
+        .for_agent(&agent_id)
+        .with_context(|| format!("unknown agent '{agent_id}'"))?;
 
     let tui_user = resolve_tui_user(&config, user_flag);
 
@@ -916,8 +1252,8 @@ async fn cmd_chat(config_path: Option<&str>, user_flag: Option<&str>) -> Result<
 
     let memory = init_memory_store(&config, &config_dir, Arc::clone(&provider))?;
 
-    let agent_id = config.agent.id.clone();
     let web_tool_config = config.tools.web.clone();
+    let mcp_executor = mcp::McpToolExecutor::connect(&config.mcp, &config_dir).await;
     let shared = shared_config(config);
     let subagents = Arc::new(SubagentManager::new(
         Arc::clone(&shared),
@@ -927,13 +1263,14 @@ async fn cmd_chat(config_path: Option<&str>, user_flag: Option<&str>) -> Result<
 
     let default_executor = DefaultExecutor::new();
     let config_executor = config_tool::ConfigToolExecutor::new(config_file.clone());
-    let memory_executor = MemoryToolExecutor::new(Arc::clone(&memory));
+    let memory_executor =
+        MemoryToolExecutor::new(Arc::clone(&memory)).with_config(Arc::clone(&shared));
     let image_executor = image_tools::ImageToolExecutor::new(Arc::clone(&shared));
     let web_executor = web_tools::WebToolExecutor::new(&web_tool_config);
     let session_search_executor =
         session_search::SessionSearchExecutor::new(Arc::clone(&memory), Arc::clone(&provider));
     let subagent_executor = SubagentToolExecutor::new(Arc::clone(&subagents));
-    let executor: Arc<dyn coop_core::ToolExecutor> = Arc::new(CompositeExecutor::new(vec![
+    let mut executors: Vec<Box<dyn coop_core::ToolExecutor>> = vec![
         Box::new(default_executor),
         Box::new(config_executor),
         Box::new(memory_executor),
@@ -941,7 +1278,14 @@ async fn cmd_chat(config_path: Option<&str>, user_flag: Option<&str>) -> Result<
         Box::new(web_executor),
         Box::new(session_search_executor),
         Box::new(subagent_executor),
-    ]));
+    ];
+    let builtin_names = executors
+        .iter()
+        .flat_map(|executor| executor.tools())
+        .map(|tool| tool.name)
+        .collect::<Vec<_>>();
+    executors.push(Box::new(mcp_executor.with_reserved_names(builtin_names)));
+    let executor: Arc<dyn coop_core::ToolExecutor> = Arc::new(CompositeExecutor::new(executors));
 
     let gateway = Arc::new(Gateway::new_with_subagents(
         Arc::clone(&shared),
@@ -955,9 +1299,11 @@ async fn cmd_chat(config_path: Option<&str>, user_flag: Option<&str>) -> Result<
     subagents.bind_gateway(&gateway);
 
     let shutdown_token = CancellationToken::new();
+    let _trace_redaction = spawn_trace_redaction(&shared, shutdown_token.clone());
     let _config_watcher = config_watcher::spawn_config_watcher(
         config_file,
-        Arc::clone(&shared),
+        shared_config(root_config),
+        vec![(agent_id.clone(), Arc::clone(&shared))],
         shutdown_token.clone(),
         None,
     );
@@ -1007,6 +1353,29 @@ async fn cmd_chat(config_path: Option<&str>, user_flag: Option<&str>) -> Result<
                             app.cursor_pos = app.input.len();
                             app.set_error("Cannot send while agent is responding");
                             set_status_error(&mut tui, app.error_message.clone());
+                        } else if let Some(retry) = commands::parse_retry(&input) {
+                            clear_editor(&mut tui);
+                            app.push_message(DisplayMessage::system("Retrying last turn…"));
+                            app.start_turn();
+                            set_status_loading(&mut tui, true);
+                            update_chat_messages(&mut tui, &app, CHAT_IDX);
+
+                            let gw = Arc::clone(&gateway);
+                            let sk = session_key.clone();
+                            let tx = event_tx.clone();
+                            let user = tui_user.clone();
+                            let model = retry.model.map(str::to_owned);
+                            turn_task = Some(tokio::spawn(async move {
+                                gw.retry_last_turn(
+                                    &sk,
+                                    coop_core::TrustLevel::Full,
+                                    Some(&user),
+                                    Some("terminal:default"),
+                                    model.as_deref(),
+                                    tx,
+                                )
+                                .await
+                            }));
                         } else if input.trim_start().starts_with('/') {
                             let response = commands::handle_slash_command(
                                 &gateway,
@@ -1107,29 +1476,70 @@ async fn cmd_chat(config_path: Option<&str>, user_flag: Option<&str>) -> Result<
 // cmd_attach — TUI client that connects to a running gateway via IPC
 // ---------------------------------------------------------------------------
 
-#[allow(clippy::too_many_lines)]
-async fn cmd_attach(config_path: Option<&str>, session: &str) -> Result<()> {
-    let config_file = Config::find_config_path(config_path);
-    let config = Config::load(&config_file)
-        .with_context(|| format!("loading config from {}", config_file.display()))?;
+/// Environment variable holding the token for `coop attach --remote`.
+const IPC_TOKEN_ENV: &str = "COOP_IPC_TOKEN";
 
-    let socket = socket_path(&config.agent.id);
-    let mut client = IpcClient::connect(&socket).await.with_context(|| {
-        format!(
-            "is the gateway running? (coop start)\nsocket: {}",
-            socket.display()
+#[allow(clippy::too_many_lines)]
+async fn cmd_attach(
+    config_path: Option<&str>,
+    session: &str,
+    agent: Option<&str>,
+    remote: Option<&str>,
+) -> Result<()> {
+    let session = qualify_attach_session(session, agent);
+    let session = session.as_str();
+
+    // A remote gateway's config is not on this machine; the model shown in
+    // the footer arrives with the first usage report instead.
+    let (mut client, proof, model, endpoint) = if let Some(addr) = remote {
+        let token = std::env::var(IPC_TOKEN_ENV)
+            .with_context(|| format!("set {IPC_TOKEN_ENV} to attach to a remote gateway"))?;
+        let mut client = IpcClient::connect_tcp(addr)
+            .await
+            .with_context(|| format!("connecting to remote gateway at {addr}"))?;
+        let nonce = match client.recv().await? {
+            ServerMessage::Challenge { nonce } => nonce,
+            other => anyhow::bail!("expected an auth challenge, got {other:?}"),
+        };
+        let proof = coop_ipc::auth::challenge_proof(&token, &nonce);
+        (client, Some(proof), String::new(), addr.to_owned())
+    } else {
+        let config_file = Config::find_config_path(config_path);
+        let config = Config::load(&config_file)
+            .with_context(|| format!("loading config from {}", config_file.display()))?;
+        let attached_agent = match agent {
+            Some(id) => config
+                .find_agent(id)
+                .with_context(|| format!("unknown agent '{id}'"))?,
+            None => &config.agent,
+        };
+        let socket = socket_path(&config.agent.id);
+        let client = IpcClient::connect(&socket).await.with_context(|| {
+            format!(
+                "is the gateway running? (coop start)\nsocket: {}",
+                socket.display()
+            )
+        })?;
+        (
+            client,
+            None,
+            attached_agent.model.clone(),
+            socket.display().to_string(),
         )
-    })?;
+    };
 
     client
         .send(ClientMessage::Hello {
             version: PROTOCOL_VERSION,
+            proof,
         })
         .await?;
     let hello = client.recv().await?;
-    let agent_id = match hello {
+    let (server_version, agent_id) = match hello {
         ServerMessage::Hello {
-            version, agent_id, ..
+            version,
+            agent_id,
+            agents,
         } => {
             if version != PROTOCOL_VERSION {
                 tracing::warn!(
@@ -1138,22 +1548,25 @@ async fn cmd_attach(config_path: Option<&str>, session: &str) -> Result<()> {
                     "protocol version mismatch"
                 );
             }
-            agent_id
+            let agent_id = match agent {
+                None => agent_id,
+                Some(id) if agent_id == id || agents.iter().any(|a| a == id) => id.to_owned(),
+                Some(id) => anyhow::bail!(
+                    "gateway does not host agent '{id}' (restart it after adding [[agents]])"
+                ),
+            };
+            (version, agent_id)
         }
+        ServerMessage::Error { message, .. } => anyhow::bail!("gateway refused: {message}"),
         other => anyhow::bail!("unexpected server response: {other:?}"),
     };
 
-    info!(agent = %agent_id, session = %session, socket = %socket.display(), "attached to gateway");
+    info!(agent = %agent_id, session = %session, endpoint = %endpoint, "attached to gateway");
 
     let working_dir = resolve_working_dir();
 
-    let (mut tui, mut app, mut tool_names) = build_tui(
-        &agent_id,
-        &config.agent.model,
-        session,
-        &working_dir,
-        200_000,
-    );
+    let (mut tui, mut app, mut tool_names) =
+        build_tui(&agent_id, &model, session, &working_dir, 200_000);
 
     tui.start()?;
     tui.request_render();
@@ -1173,6 +1586,25 @@ async fn cmd_attach(config_path: Option<&str>, session: &str) -> Result<()> {
 
     let session_name = session.to_owned();
 
+    // v2 gateways can replay the transcript and report the session's model.
+    if server_version >= 2 {
+        for request in [
+            ClientMessage::Subscribe {
+                session: session_name.clone(),
+            },
+            ClientMessage::GetHistory {
+                session: session_name.clone(),
+                before: None,
+                limit: coop_ipc::protocol::DEFAULT_HISTORY_LIMIT,
+            },
+            ClientMessage::GetUsage {
+                session: session_name.clone(),
+            },
+        ] {
+            writer.send(request).await?;
+        }
+    }
+
     loop {
         let mut needs_render = false;
 
@@ -1192,6 +1624,20 @@ async fn cmd_attach(config_path: Option<&str>, session: &str) -> Result<()> {
                             app.cursor_pos = app.input.len();
                             app.set_error("Cannot send while agent is responding");
                             set_status_error(&mut tui, app.error_message.clone());
+                        } else if server_version >= 2 && input.trim_start().starts_with('/') {
+                            clear_editor(&mut tui);
+                            app.push_message(DisplayMessage::user(&input));
+                            update_chat_messages(&mut tui, &app, CHAT_IDX);
+
+                            if let Err(error) = writer
+                                .send(attach_command_request(&session_name, &input))
+                                .await
+                            {
+                                app.push_message(DisplayMessage::system(format!(
+                                    "Error: {error:#}"
+                                )));
+                                update_chat_messages(&mut tui, &app, CHAT_IDX);
+                            }
                         } else {
                             clear_editor(&mut tui);
                             app.push_message(DisplayMessage::user(&input));
@@ -1285,11 +1731,40 @@ async fn cmd_attach(config_path: Option<&str>, session: &str) -> Result<()> {
     Ok(())
 }
 
+/// `/model <id>` switches models directly; other slash commands run as-is.
+fn attach_command_request(session: &str, input: &str) -> ClientMessage {
+    let command = input.trim();
+    match command
+        .strip_prefix("/model ")
+        .map(str::trim)
+        .filter(|model| !model.is_empty())
+    {
+        Some(model) => ClientMessage::SetModel {
+            session: session.to_owned(),
+            model: model.to_owned(),
+        },
+        None => ClientMessage::RunCommand {
+            session: session.to_owned(),
+            command: command.to_owned(),
+        },
+    }
+}
+
+/// Sessions of a non-default agent are addressed as `<agent>:<session>`.
+fn qualify_attach_session(session: &str, agent: Option<&str>) -> String {
+    match agent {
+        Some(agent) if !session.starts_with(&format!("{agent}:")) => {
+            format!("{agent}:{session}")
+        }
+        _ => session.to_owned(),
+    }
+}
+
 // ---------------------------------------------------------------------------
 // cmd_memory
 // ---------------------------------------------------------------------------
 
-async fn cmd_memory(config_path: Option<&str>, command: MemoryCommands) -> Result<()> {
+async fn cmd_memory(config_path: Option<&str>, command: MemoryCommands, json: bool) -> Result<()> {
     let config_file = Config::find_config_path(config_path);
     let config = Config::load(&config_file)
         .with_context(|| format!("loading config from {}", config_file.display()))?;
@@ -1304,13 +1779,236 @@ async fn cmd_memory(config_path: Option<&str>, command: MemoryCommands) -> Resul
 
     let memory = init_memory_store(&config, &config_dir, provider)?;
 
+    print!(
+        "{}",
+        memory_admin::run(memory.as_ref(), command, json).await?
+    );
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// cmd_sessions
+// ---------------------------------------------------------------------------
+
+fn cmd_sessions(config_path: Option<&str>, command: SessionsCommands) -> Result<()> {
+    let config_file = Config::find_config_path(config_path);
+    let config = Config::load(&config_file)
+        .with_context(|| format!("loading config from {}", config_file.display()))?;
+    let config_dir = config_file
+        .parent()
+        .unwrap_or(&PathBuf::from("."))
+        .to_path_buf();
+    let sessions_dir = |agent_id: &str| -> Result<PathBuf> {
+        let agent_config = config
+            .for_agent(agent_id)
+            .with_context(|| format!("unknown agent '{agent_id}'"))?;
+        Ok(agent_config
+            .resolve_workspace(&config_dir)?
+            .join("sessions"))
+    };
+
+    match command {
+        SessionsCommands::List { agent, kind, user } => {
+            let agent_id = agent.as_deref().unwrap_or(&config.agent.id);
+            let db_path = sessions_dir(agent_id)?.join(session_store::SESSION_DB_FILE);
+            if !db_path.exists() {
+                anyhow::bail!(
+                    "no session database at {} (set [sessions] store = \"sqlite\" or run `coop sessions migrate`)",
+                    db_path.display()
+                );
+            }
+            let store = SqliteSessionStore::open(&db_path)?;
+            let keys = store.list(SessionFilter {
+                agent_id: Some(agent_id),
+                kind: kind.as_deref(),
+                user: user.as_deref(),
+            })?;
+            for key in keys {
+                println!("{key}\t{}", store.message_count(&key)?);
+            }
+        }
+        SessionsCommands::Migrate { agent } => {
+            let agent_ids: Vec<String> = match agent {
+                Some(id) => vec![id],
+                None => config.all_agents().map(|agent| agent.id.clone()).collect(),
+            };
+            for agent_id in agent_ids {
+                let dir = sessions_dir(&agent_id)?;
+                let store = SqliteSessionStore::open(dir.join(session_store::SESSION_DB_FILE))?;
+                let known = session_store::known_session_keys(&config, &agent_id);
+                let summary =
+                    session_store::migrate_jsonl_sessions(&dir, &agent_id, &known, &store)?;
+                for (key, count) in &summary.imported {
+                    println!("imported {key} ({count} messages)");
+                }
+                for key in &summary.existing {
+                    println!("skipped {key} (already in database)");
+                }
+                for path in &summary.unrecognized {
+                    println!("⚠ could not determine the session for {}", path.display());
+                }
+                println!(
+                    "{agent_id}: {} imported, {} already present, {} unrecognized",
+                    summary.imported.len(),
+                    summary.existing.len(),
+                    summary.unrecognized.len()
+                );
+            }
+            if config.sessions.store != SessionStoreKind::Sqlite {
+                println!("set [sessions] store = \"sqlite\" and restart the gateway to use it");
+            }
+        }
+    }
+
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// cmd_usage
+// ---------------------------------------------------------------------------
+
+fn cmd_usage(
+    config_path: Option<&str>,
+    agent: Option<&str>,
+    by: &[String],
+    days: u32,
+    user: Option<&str>,
+) -> Result<()> {
+    let config_file = Config::find_config_path(config_path);
+    let config = Config::load(&config_file)
+        .with_context(|| format!("loading config from {}", config_file.display()))?;
+    let config_dir = config_file
+        .parent()
+        .unwrap_or(&PathBuf::from("."))
+        .to_path_buf();
+
+    let groupings = if by.is_empty() {
+        UsageGrouping::ALL.to_vec()
+    } else {
+        by.iter()
+            .map(|name| {
+                UsageGrouping::parse(name).with_context(|| {
+                    format!("unknown grouping '{name}' (expected user, model, cron or day)")
+                })
+            })
+            .collect::<Result<_>>()?
+    };
+    let agent_ids: Vec<String> = match agent {
+        Some(id) => vec![id.to_owned()],
+        None => config.all_agents().map(|agent| agent.id.clone()).collect(),
+    };
+    let filter = UsageFilter {
+        since: Some(chrono::Utc::now() - chrono::Duration::days(i64::from(days))),
+        user,
+        cron: None,
+    };
+
+    for agent_id in &agent_ids {
+        let agent_config = config
+            .for_agent(agent_id)
+            .with_context(|| format!("unknown agent '{agent_id}'"))?;
+        let db_path = agent_config
+            .resolve_workspace(&config_dir)?
+            .join(usage_ledger::USAGE_DB_FILE);
+        if !db_path.exists() {
+            println!("{agent_id}: no usage recorded yet");
+            continue;
+        }
+        let ledger = UsageLedger::open(&db_path)?;
+        let mut sections = Vec::with_capacity(groupings.len());
+        for by in &groupings {
+            let lines = ledger.report(*by, filter, &|model| {
+                usage_ledger::model_pricing(&agent_config, model)
+            })?;
+            sections.push((*by, lines));
+        }
+        println!("{agent_id} — last {days} days\n");
+        print!("{}", usage_ledger::format_report(&sections));
+        println!();
+    }
+
+    Ok(())
+}
+
+fn cmd_cron(config_path: Option<&str>, command: CronCommands) -> Result<()> {
+    let config_file = Config::find_config_path(config_path);
+    let config = Config::load(&config_file)
+        .with_context(|| format!("loading config from {}", config_file.display()))?;
+    let config_dir = config_file
+        .parent()
+        .unwrap_or(&PathBuf::from("."))
+        .to_path_buf();
+
     match command {
-        MemoryCommands::RebuildIndex => {
-            let count = memory.rebuild_index().await?;
-            println!("rebuilt vec index: {count} entries");
+        CronCommands::History { name, agent, limit } => {
+            let agent_ids: Vec<String> = match agent {
+                Some(id) => vec![id],
+                None => config.all_agents().map(|agent| agent.id.clone()).collect(),
+            };
+            let timezone = cron_tool::history_timezone(&config, name.as_deref());
+            for agent_id in &agent_ids {
+                let agent_config = config
+                    .for_agent(agent_id)
+                    .with_context(|| format!("unknown agent '{agent_id}'"))?;
+                let db_path = agent_config
+                    .resolve_workspace(&config_dir)?
+                    .join(cron_history::CRON_HISTORY_DB_FILE);
+                if !db_path.exists() {
+                    println!("{agent_id}: no cron runs recorded yet");
+                    continue;
+                }
+                let runs = CronHistory::open(&db_path)?.recent(name.as_deref(), limit)?;
+                println!("{agent_id}\n");
+                print!("{}", cron_history::format_history(&runs, timezone));
+                println!();
+            }
         }
     }
+    Ok(())
+}
+
+async fn cmd_models(config_path: Option<&str>, command: ModelsCommands) -> Result<()> {
+    let config_file = Config::find_config_path(config_path);
+    let config = Config::load(&config_file)
+        .with_context(|| format!("loading config from {}", config_file.display()))?;
 
+    match command {
+        ModelsCommands::List => {
+            local_models::discover(&config).await;
+            for model in model_catalog::available_main_models(&config) {
+                match model.description {
+                    Some(description) => println!("{}  ({description})", model.id),
+                    None => println!("{}", model.id),
+                }
+            }
+        }
+        ModelsCommands::Pull { model } => {
+            let provider = config
+                .main_provider_configs()
+                .into_iter()
+                .find(|provider| provider.normalized_name() == "ollama")
+                .context("no ollama provider is configured")?;
+            let mut stdout = std::io::stdout();
+            let mut status = String::new();
+            coop_agent::pull_ollama_model(provider.base_url.as_deref(), &model, |progress| {
+                if progress.status != status {
+                    if !status.is_empty() {
+                        println!();
+                    }
+                    status.clone_from(&progress.status);
+                    print!("{status}");
+                }
+                if let (Some(completed), Some(total)) = (progress.completed, progress.total)
+                    && total > 0
+                {
+                    print!("\r{status} {}%", completed * 100 / total);
+                }
+                let _ = std::io::Write::flush(&mut stdout);
+            })
+            .await?;
+            println!();
+        }
+    }
     Ok(())
 }
 
@@ -1430,6 +2128,7 @@ fn handle_turn_event(
     }
 }
 
+#[allow(clippy::too_many_lines)]
 fn handle_server_message(
     msg: ServerMessage,
     session_filter: &str,
@@ -1447,6 +2146,7 @@ fn handle_server_message(
             name,
             arguments,
             session,
+            ..
         } if session == session_filter => {
             tool_names.insert(id, name.clone());
             app.push_message(DisplayMessage::tool_call(&name, &arguments));
@@ -1456,12 +2156,18 @@ fn handle_server_message(
             id,
             output,
             is_error,
+            duration_ms,
             session,
         } if session == session_filter => {
             let name = tool_names
                 .get(&id)
                 .cloned()
                 .unwrap_or_else(|| "unknown".to_owned());
+            #[allow(clippy::cast_precision_loss)]
+            let name = match duration_ms {
+                Some(ms) => format!("{name} ({:.1}s)", ms as f64 / 1000.0),
+                None => name,
+            };
             app.push_message(DisplayMessage::tool_output(&name, output, is_error));
             update_chat_messages(tui, app, CHAT_IDX);
         }
@@ -1484,10 +2190,107 @@ fn handle_server_message(
             set_status_error(tui, Some(message));
             update_chat_messages(tui, app, CHAT_IDX);
         }
+        ServerMessage::History {
+            session,
+            start,
+            messages,
+            ..
+        } if session == session_filter => {
+            if start > 0 {
+                app.push_message(DisplayMessage::system(format!(
+                    "… {start} earlier messages"
+                )));
+            }
+            for message in messages {
+                push_history_message(app, tool_names, message);
+            }
+            update_chat_messages(tui, app, CHAT_IDX);
+        }
+        ServerMessage::CommandOutput {
+            session, output, ..
+        } if session == session_filter => {
+            app.push_message(DisplayMessage::system(output));
+            update_chat_messages(tui, app, CHAT_IDX);
+        }
+        ServerMessage::ModelChanged {
+            session,
+            model,
+            context_limit,
+            compacted,
+        } if session == session_filter => {
+            let mut notice = format!("Model set to {model}");
+            if compacted {
+                notice.push_str(" (session compacted before handoff)");
+            }
+            set_footer_model(tui, app, model, context_limit);
+            app.push_message(DisplayMessage::system(notice));
+            update_chat_messages(tui, app, CHAT_IDX);
+        }
+        ServerMessage::Usage {
+            session,
+            model,
+            context_limit,
+            ..
+        } if session == session_filter => {
+            set_footer_model(tui, app, model, context_limit);
+        }
+        ServerMessage::Compacting { session } if session == session_filter => {
+            app.push_message(DisplayMessage::system("Compacting conversation…"));
+            update_chat_messages(tui, app, CHAT_IDX);
+        }
+        ServerMessage::SubagentProgress {
+            session,
+            run_id,
+            status,
+            task,
+            summary,
+            error,
+            ..
+        } if session == session_filter => {
+            let short_id = run_id.get(..8).unwrap_or(&run_id);
+            let detail = error.or(summary).unwrap_or(task);
+            app.push_message(DisplayMessage::system(format!(
+                "Subagent {short_id} {status}: {detail}"
+            )));
+            update_chat_messages(tui, app, CHAT_IDX);
+        }
         _ => {}
     }
 }
 
+fn push_history_message(
+    app: &mut App,
+    tool_names: &mut HashMap<String, String>,
+    message: coop_ipc::HistoryMessage,
+) {
+    match message.role {
+        Role::User if !message.text.is_empty() => {
+            app.push_message(DisplayMessage::user(message.text));
+        }
+        Role::User => {}
+        Role::Assistant => {
+            if !message.text.is_empty() {
+                app.push_message(DisplayMessage::assistant(message.text));
+            }
+            for call in message.tool_calls {
+                app.push_message(DisplayMessage::tool_call(&call.name, &call.arguments));
+                tool_names.insert(call.id, call.name);
+            }
+        }
+    }
+    for result in message.tool_results {
+        let name = tool_names
+            .get(&result.id)
+            .cloned()
+            .unwrap_or_else(|| "unknown".to_owned());
+        app.push_message(DisplayMessage::tool_output(
+            &name,
+            result.output,
+            result.is_error,
+        ));
+    }
+}
+
 /// Returns true if a task error occurred and needs render.
 fn handle_turn_task_result(
     join_result: std::result::Result<Result<()>, tokio::task::JoinError>,
@@ -1568,17 +2371,20 @@ fn context_limit_u32(limit: usize) -> u32 {
 
 fn sync_model_display(tui: &mut Tui, app: &mut App, gateway: &Gateway, user_name: Option<&str>) {
     if let Ok(selection) = gateway.resolve_main_model(user_name) {
-        let model = selection.model;
-        let context_limit = context_limit_u32(selection.context_limit);
-        app.model_name.clone_from(&model);
-        app.context_limit = context_limit;
+        set_footer_model(tui, app, selection.model, selection.context_limit);
+    }
+}
 
-        let footer = tui.root_mut().children_mut()[FOOTER_IDX]
-            .as_any_mut()
-            .and_then(|a| a.downcast_mut::<Footer>());
-        if let Some(f) = footer {
-            f.set_model(model, context_limit);
-        }
+fn set_footer_model(tui: &mut Tui, app: &mut App, model: String, context_limit: usize) {
+    let context_limit = context_limit_u32(context_limit);
+    app.model_name.clone_from(&model);
+    app.context_limit = context_limit;
+
+    let footer = tui.root_mut().children_mut()[FOOTER_IDX]
+        .as_any_mut()
+        .and_then(|a| a.downcast_mut::<Footer>());
+    if let Some(f) = footer {
+        f.set_model(model, context_limit);
     }
 }
 
@@ -1606,6 +2412,7 @@ mod tests {
     use super::*;
     use crate::config::MemoryRetentionConfig;
     use async_trait::async_trait;
+    use chrono::Utc;
     use coop_core::SessionKey;
     use coop_memory::{
         MemoryMaintenanceConfig, MemoryMaintenanceReport, MemoryQuery, NewObservation, Observation,
@@ -1656,11 +2463,12 @@ match = ["signal:bob-uuid"]
             _anchor: i64,
             _before: usize,
             _after: usize,
+            _viewer: Option<&str>,
         ) -> Result<Vec<ObservationIndex>> {
             Ok(Vec::new())
         }
 
-        async fn get(&self, _ids: &[i64]) -> Result<Vec<Observation>> {
+        async fn get(&self, _ids: &[i64], _viewer: Option<&str>) -> Result<Vec<Observation>> {
             Ok(Vec::new())
         }
 
@@ -1668,7 +2476,7 @@ match = ["signal:bob-uuid"]
             Ok(WriteOutcome::Skipped)
         }
 
-        async fn people(&self, _query: &str) -> Result<Vec<Person>> {
+        async fn people(&self, _query: &str, _viewer: Option<&str>) -> Result<Vec<Person>> {
             Ok(Vec::new())
         }
 
@@ -1701,6 +2509,41 @@ match = ["signal:bob-uuid"]
         }
     }
 
+    #[test]
+    fn qualify_attach_session_prefixes_agent() {
+        assert_eq!(qualify_attach_session("main", None), "main");
+        assert_eq!(qualify_attach_session("main", Some("work")), "work:main");
+        assert_eq!(
+            qualify_attach_session("work:dm:signal:alice", Some("work")),
+            "work:dm:signal:alice"
+        );
+    }
+
+    #[test]
+    fn attach_slash_commands_map_to_v2_requests() {
+        assert_eq!(
+            attach_command_request("main", "/model  gpt-5 "),
+            ClientMessage::SetModel {
+                session: "main".into(),
+                model: "gpt-5".into(),
+            }
+        );
+        assert_eq!(
+            attach_command_request("main", "/model"),
+            ClientMessage::RunCommand {
+                session: "main".into(),
+                command: "/model".into(),
+            }
+        );
+        assert_eq!(
+            attach_command_request("main", " /status\n"),
+            ClientMessage::RunCommand {
+                session: "main".into(),
+                command: "/status".into(),
+            }
+        );
+    }
+
     #[test]
     fn resolve_tui_user_defaults_to_root() {
         let config = test_config();
@@ -1736,6 +2579,28 @@ model = "test-model"
         assert_eq!(user, "root");
     }
 
+    #[test]
+    fn memory_owner_for_session_maps_dm_group_and_unknown_keys() {
+        let config = test_config();
+        assert_eq!(
+            memory_owner_for_session(&config, "test:dm:signal:bob-uuid").as_deref(),
+            Some("user:bob")
+        );
+        assert_eq!(
+            memory_owner_for_session(&config, "test:group:signal:group:abc").as_deref(),
+            Some("group:signal:group:abc")
+        );
+        assert_eq!(memory_owner_for_session(&config, "test:main"), None);
+        assert_eq!(
+            memory_owner_for_session(&config, "test:dm:signal:stranger"),
+            None
+        );
+        assert_eq!(
+            memory_owner_for_session(&config, "other:dm:signal:bob-uuid"),
+            None
+        );
+    }
+
     #[tokio::test]
     async fn maintenance_loop_runs_startup_and_periodic_without_crashing() {
         let memory = Arc::new(CountingMemory::default());
@@ -0,0 +1,217 @@
+use std::fmt::Write as _;
+
+use anyhow::{Context, Result};
+use coop_core::types::ToolOutput;
+use serde::Deserialize;
+use serde_json::{Value, json};
+
+use super::transport::McpTransport;
+
+pub(crate) const PROTOCOL_VERSION: &str = "2025-06-18";
+
+/// A tool as advertised by an MCP server's `tools/list`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct McpTool {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default = "default_input_schema")]
+    pub input_schema: Value,
+}
+
+fn default_input_schema() -> Value {
+    json!({"type": "object", "properties": {}})
+}
+
+/// Protocol-level MCP client over any transport.
+pub(crate) struct McpClient {
+    transport: Box<dyn McpTransport>,
+    server_name: Option<String>,
+}
+
+impl std::fmt::Debug for McpClient {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("McpClient")
+            .field("server_name", &self.server_name)
+            .finish_non_exhaustive()
+    }
+}
+
+impl McpClient {
+    /// Run the `initialize` handshake and return a ready client.
+    pub(crate) async fn connect(transport: Box<dyn McpTransport>) -> Result<Self> {
+        let result = transport
+            .request(
+                "initialize",
+                json!({
+                    "protocolVersion": PROTOCOL_VERSION,
+                    "capabilities": {},
+                    "clientInfo": {"name": "coop", "version": env!("CARGO_PKG_VERSION")},
+                }),
+            )
+            .await
+            .context("MCP initialize failed")?;
+
+        let version = result
+            .get("protocolVersion")
+            .and_then(Value::as_str)
+            .unwrap_or(PROTOCOL_VERSION);
+        transport.set_protocol_version(version);
+        transport
+            .notify("notifications/initialized", json!({}))
+            .await
+            .context("MCP initialized notification failed")?;
+
+        let server_name = result
+            .pointer("/serverInfo/name")
+            .and_then(Value::as_str)
+            .map(str::to_owned);
+
+        Ok(Self {
+            transport,
+            server_name,
+        })
+    }
+
+    pub(crate) fn server_name(&self) -> Option<&str> {
+        self.server_name.as_deref()
+    }
+
+    /// Fetch every tool the server exposes, following pagination cursors.
+    pub(crate) async fn list_tools(&self) -> Result<Vec<McpTool>> {
+        let mut tools = Vec::new();
+        let mut cursor: Option<String> = None;
+        loop {
+            let params = cursor
+                .as_ref()
+                .map_or_else(|| json!({}), |cursor| json!({"cursor": cursor}));
+            let result = self.transport.request("tools/list", params).await?;
+            let page: Vec<McpTool> = serde_json::from_value(
+                result.get("tools").cloned().unwrap_or(Value::Array(vec![])),
+            )
+            .context("MCP server returned malformed tools/list")?;
+            tools.extend(page);
+
+            cursor = result
+                .get("nextCursor")
+                .and_then(Value::as_str)
+                .filter(|c| !c.is_empty())
+                .map(str::to_owned);
+            if cursor.is_none() {
+                return Ok(tools);
+            }
+        }
+    }
+
+    pub(crate) async fn call_tool(&self, name: &str, arguments: Value) -> Result<ToolOutput> {
+        let arguments = if arguments.is_null() {
+            json!({})
+        } else {
+            arguments
+        };
+        let result = self
+            .transport
+            .request("tools/call", json!({"name": name, "arguments": arguments}))
+            .await?;
+        Ok(tool_output_from_result(&result))
+    }
+}
+
+/// Flatten a `tools/call` result into text. Non-text content is summarized.
+pub(crate) fn tool_output_from_result(result: &Value) -> ToolOutput {
+    let mut text = String::new();
+    for item in result
+        .get("content")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+    {
+        if !text.is_empty() {
+            text.push('\n');
+        }
+        let field = |key: &str| item.get(key).and_then(Value::as_str).unwrap_or_default();
+        match field("type") {
+            "text" => text.push_str(field("text")),
+            "image" | "audio" => {
+                let size = field("data").len() * 3 / 4;
+                let _ = write!(
+                    text,
+                    "[{} content: {}, ~{size} bytes]",
+                    field("type"),
+                    field("mimeType")
+                );
+            }
+            "resource" => {
+                let resource = item.get("resource").cloned().unwrap_or(Value::Null);
+                let uri = resource.get("uri").and_then(Value::as_str).unwrap_or("");
+                match resource.get("text").and_then(Value::as_str) {
+                    Some(body) => {
+                        let _ = write!(text, "[resource {uri}]\n{body}");
+                    }
+                    None => {
+                        let _ = write!(text, "[resource {uri}]");
+                    }
+                }
+            }
+            "resource_link" => {
+                let _ = write!(text, "[resource link: {}]", field("uri"));
+            }
+            other => {
+                let _ = write!(text, "[unsupported content type: {other}]");
+            }
+        }
+    }
+
+    if text.is_empty()
+        && let Some(structured) = result.get("structuredContent")
+    {
+        text = structured.to_string();
+    }
+
+    if result.get("isError").and_then(Value::as_bool) == Some(true) {
+        ToolOutput::error(text)
+    } else {
+        ToolOutput::success(text)
+    }
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tool_output_joins_text_and_summarizes_media() {
+        let output = tool_output_from_result(&json!({
+            "content": [
+                {"type": "text", "text": "hello"},
+                {"type": "image", "data": "AAAA", "mimeType": "image/png"},
+                {"type": "resource", "resource": {"uri": "file:///a.txt", "text": "body"}},
+            ]
+        }));
+        assert!(!output.is_error);
+        assert_eq!(
+            output.content,
+            "hello\n[image content: image/png, ~3 bytes]\n[resource file:///a.txt]\nbody"
+        );
+    }
+
+    #[test]
+    fn tool_output_respects_is_error_and_structured_content() {
+        let output = tool_output_from_result(&json!({
+            "content": [],
+            "structuredContent": {"ok": false},
+            "isError": true,
+        }));
+        assert!(output.is_error);
+        assert_eq!(output.content, r#"{"ok":false}"#);
+    }
+
+    #[test]
+    fn tool_defaults_missing_schema() {
+        let tool: McpTool = serde_json::from_value(json!({"name": "t"})).unwrap();
+        assert_eq!(tool.input_schema["type"], "object");
+        assert!(tool.description.is_none());
+    }
+}
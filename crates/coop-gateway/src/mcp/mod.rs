@@ -0,0 +1,593 @@
+//! MCP (Model Context Protocol) client support.
+//!
+//! Each `[[mcp]]` entry is connected at startup over stdio or streamable HTTP.
+//! The server's tools are exposed to the agent as `mcp_<server>_<tool>` through
+//! [`McpToolExecutor`], which slots into the composite executor next to the
+//! built-in tool executors.
+
+mod client;
+mod transport;
+
+use std::collections::HashSet;
+use std::path::Path;
+use std::sync::{Arc, Mutex, RwLock, Weak};
+use std::time::Duration;
+
+use anyhow::{Context, Result, bail};
+use async_trait::async_trait;
+use coop_core::TrustLevel;
+use coop_core::traits::{ToolContext, ToolExecutor};
+use coop_core::types::{ToolDef, ToolOutput};
+use tokio::sync::Notify;
+use tracing::{info, warn};
+
+use crate::config::McpServerConfig;
+use client::{McpClient, McpTool};
+use transport::{HttpTransport, McpTransport, StdioTransport};
+
+/// Max tool name length accepted by the strictest providers.
+const MAX_TOOL_NAME_LEN: usize = 64;
+
+/// Length of the `_<hash>` suffix that disambiguates colliding names.
+const COLLISION_SUFFIX_LEN: usize = 9;
+
+struct McpServer {
+    name: String,
+    trust: TrustLevel,
+    allow: Option<Vec<String>>,
+    client: McpClient,
+    tools: RwLock<Vec<McpTool>>,
+}
+
+impl McpServer {
+    fn allows(&self, tool: &str) -> bool {
+        self.allow
+            .as_ref()
+            .is_none_or(|allow| allow.iter().any(|name| name == tool))
+    }
+
+    async fn refresh_tools(&self) -> Result<usize> {
+        let tools = self.client.list_tools().await?;
+        let count = tools.len();
+        *self.tools.write().expect("mcp tools lock poisoned") = tools;
+        Ok(count)
+    }
+}
+
+/// Tool executor backed by one or more connected MCP servers.
+#[allow(missing_debug_implementations)]
+#[derive(Clone)]
+pub(crate) struct McpToolExecutor {
+    servers: Vec<Arc<McpServer>>,
+    /// Names used by the other executors, which MCP tools must not shadow.
+    reserved: HashSet<String>,
+    /// Collisions already logged, so each is warned about once.
+    warned: Arc<Mutex<HashSet<String>>>,
+}
+
+/// An allowed MCP tool and the name the agent sees it under.
+struct ExposedTool {
+    name: String,
+    server: Arc<McpServer>,
+    tool: McpTool,
+}
+
+impl McpToolExecutor {
+    /// Connect every enabled server. Servers that fail to start are logged and skipped
+    /// so a broken MCP server never blocks the gateway.
+    pub(crate) async fn connect(configs: &[McpServerConfig], base_dir: &Path) -> Self {
+        let mut servers = Vec::new();
+        for config in configs.iter().filter(|config| config.enabled) {
+            match connect_server(config, base_dir).await {
+                Ok(server) => servers.push(server),
+                Err(error) => {
+                    warn!(mcp.server = %config.name, error = %format!("{error:#}"), "failed to connect MCP server");
+                }
+            }
+        }
+        Self::from_servers(servers)
+    }
+
+    fn from_servers(servers: Vec<Arc<McpServer>>) -> Self {
+        Self {
+            servers,
+            reserved: HashSet::new(),
+            warned: Arc::new(Mutex::new(HashSet::new())),
+        }
+    }
+
+    /// Keep MCP tools from taking `names`, the tools of the executors this
+    /// one is composed with.
+    pub(crate) fn with_reserved_names(mut self, names: impl IntoIterator<Item = String>) -> Self {
+        self.reserved = names.into_iter().collect();
+        self
+    }
+
+    /// Every allowed tool, in server order. A tool whose name is already
+    /// taken, by a built-in or by an earlier MCP tool that sanitizes to the
+    /// same string, gets a hash suffix instead of shadowing it.
+    fn exposed_tools(&self) -> Vec<ExposedTool> {
+        let mut taken = self.reserved.clone();
+        let mut exposed = Vec::new();
+        for server in &self.servers {
+            let tools = server.tools.read().expect("mcp tools lock poisoned");
+            for tool in tools.iter().filter(|tool| server.allows(&tool.name)) {
+                let mut name = exposed_tool_name(&server.name, &tool.name);
+                if taken.contains(&name) {
+                    let renamed = disambiguated_tool_name(&name, &server.name, &tool.name);
+                    self.warn_collision(&server.name, &tool.name, &name, &renamed);
+                    name = renamed;
+                }
+                taken.insert(name.clone());
+                exposed.push(ExposedTool {
+                    name,
+                    server: Arc::clone(server),
+                    tool: tool.clone(),
+                });
+            }
+        }
+        exposed
+    }
+
+    fn warn_collision(&self, server: &str, tool: &str, name: &str, renamed: &str) {
+        let mut warned = self.warned.lock().expect("mcp warned lock poisoned");
+        if warned.insert(renamed.to_owned()) {
+            warn!(
+                mcp.server = %server,
+                mcp.tool = %tool,
+                name,
+                renamed,
+                "MCP tool name already taken, exposing it under a suffixed name"
+            );
+        }
+    }
+
+    fn resolve(&self, tool_name: &str) -> Option<(Arc<McpServer>, String)> {
+        self.exposed_tools()
+            .into_iter()
+            .find(|exposed| exposed.name == tool_name)
+            .map(|exposed| (exposed.server, exposed.tool.name))
+    }
+}
+
+async fn connect_server(config: &McpServerConfig, base_dir: &Path) -> Result<Arc<McpServer>> {
+    let timeout = Duration::from_secs(config.timeout_seconds.max(1));
+    let tools_changed = Arc::new(Notify::new());
+
+    let transport: Box<dyn McpTransport> = match (&config.command, &config.url) {
+        (Some(command), None) => {
+            let env = config
+                .env
+                .iter()
+                .map(|(key, value)| Ok((key.clone(), resolve_value(value)?)))
+                .collect::<Result<_>>()?;
+            let cwd = config.cwd.as_ref().map(|cwd| base_dir.join(cwd));
+            Box::new(StdioTransport::spawn(
+                config,
+                command,
+                &env,
+                cwd.as_deref(),
+                timeout,
+                Arc::clone(&tools_changed),
+            )?)
+        }
+        (None, Some(url)) => {
+            let headers = config
+                .headers
+                .iter()
+                .map(|(key, value)| Ok((key.clone(), resolve_value(value)?)))
+                .collect::<Result<_>>()?;
+            Box::new(HttpTransport::new(url, headers, timeout))
+        }
+        _ => bail!("MCP server must set exactly one of 'command' or 'url'"),
+    };
+
+    let client = tokio::time::timeout(timeout, McpClient::connect(transport))
+        .await
+        .context("MCP initialize timed out")??;
+    let server = Arc::new(McpServer {
+        name: config.name.clone(),
+        trust: config.trust,
+        allow: config.tools.clone(),
+        client,
+        tools: RwLock::new(Vec::new()),
+    });
+    let count = server.refresh_tools().await?;
+    info!(
+        mcp.server = %server.name,
+        mcp.server_info = server.client.server_name().unwrap_or(""),
+        tools = count,
+        "MCP server connected"
+    );
+
+    spawn_refresh_on_change(Arc::downgrade(&server), tools_changed);
+    Ok(server)
+}
+
+/// Re-list tools whenever the server sends `notifications/tools/list_changed`.
+fn spawn_refresh_on_change(server: Weak<McpServer>, tools_changed: Arc<Notify>) {
+    tokio::spawn(async move {
+        loop {
+            tools_changed.notified().await;
+            let Some(server) = server.upgrade() else {
+                break;
+            };
+            match server.refresh_tools().await {
+                Ok(count) => info!(mcp.server = %server.name, tools = count, "MCP tools refreshed"),
+                Err(error) => {
+                    warn!(mcp.server = %server.name, error = %error, "MCP tools refresh failed");
+                }
+            }
+        }
+    });
+}
+
+fn resolve_value(value: &str) -> Result<String> {
+    match value.strip_prefix("env:") {
+        Some(var) => std::env::var(var).with_context(|| format!("env var {var} is not set")),
+        None => Ok(value.to_owned()),
+    }
+}
+
+/// Build the agent-facing tool name: `mcp_<server>_<tool>`, restricted to
+/// `[A-Za-z0-9_-]` and capped at 64 characters.
+pub(crate) fn exposed_tool_name(server: &str, tool: &str) -> String {
+    let mut name: String = format!("mcp_{server}_{tool}")
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '_' || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    name.truncate(MAX_TOOL_NAME_LEN);
+    name
+}
+
+/// `name` with an `_<hash>` suffix derived from the original server and tool
+/// names, so the result is stable across restarts.
+fn disambiguated_tool_name(name: &str, server: &str, tool: &str) -> String {
+    // FNV-1a: std's hasher is not guaranteed to be stable between releases.
+    let mut hash: u32 = 0x811c_9dc5;
+    for byte in server.bytes().chain([0]).chain(tool.bytes()) {
+        hash ^= u32::from(byte);
+        hash = hash.wrapping_mul(0x0100_0193);
+    }
+    let keep = name.len().min(MAX_TOOL_NAME_LEN - COLLISION_SUFFIX_LEN);
+    format!("{}_{hash:08x}", &name[..keep])
+}
+
+#[async_trait]
+impl ToolExecutor for McpToolExecutor {
+    async fn execute(
+        &self,
+        name: &str,
+        arguments: serde_json::Value,
+        ctx: &ToolContext,
+    ) -> Result<ToolOutput> {
+        let Some((server, tool)) = self.resolve(name) else {
+            return Ok(ToolOutput::error(format!("unknown tool: {name}")));
+        };
+
+        if ctx.trust > server.trust {
+            return Ok(ToolOutput::error(format!(
+                "{name} requires {:?} trust level",
+                server.trust
+            )));
+        }
+        if !ctx.visible_tools.is_empty() && !ctx.visible_tools.iter().any(|t| t == name) {
+            return Ok(ToolOutput::error(format!(
+                "tool not available in this session: {name}"
+            )));
+        }
+
+        match server.client.call_tool(&tool, arguments).await {
+            Ok(output) => Ok(output),
+            Err(error) => Ok(ToolOutput::error(format!(
+                "MCP server '{}' failed: {error:#}",
+                server.name
+            ))),
+        }
+    }
+
+    fn tools(&self) -> Vec<ToolDef> {
+        self.exposed_tools()
+            .into_iter()
+            .map(|ExposedTool { name, server, tool }| {
+                let description = match tool.description.as_deref() {
+                    Some(description) if !description.is_empty() => {
+                        format!("[MCP {}] {description}", server.name)
+                    }
+                    _ => format!("[MCP {}] {}", server.name, tool.name),
+                };
+                ToolDef::new(name, description, tool.input_schema)
+            })
+            .collect()
+    }
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use coop_core::SessionKind;
+    use serde_json::{Value, json};
+    use std::collections::BTreeMap;
+    use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+    use tokio::net::TcpListener;
+
+    fn server_config(name: &str) -> McpServerConfig {
+        McpServerConfig {
+            name: name.to_owned(),
+            command: None,
+            args: Vec::new(),
+            env: BTreeMap::new(),
+            cwd: None,
+            url: None,
+            headers: BTreeMap::new(),
+            trust: TrustLevel::Full,
+            tools: None,
+            timeout_seconds: 5,
+            enabled: true,
+        }
+    }
+
+    fn ctx(trust: TrustLevel) -> ToolContext {
+        ToolContext::new("s", SessionKind::Main, trust, ".", None)
+    }
+
+    fn fake_result(method: &str, params: &Value) -> Value {
+        match method {
+            "initialize" => json!({
+                "protocolVersion": client::PROTOCOL_VERSION,
+                "capabilities": {"tools": {}},
+                "serverInfo": {"name": "fake", "version": "1"},
+            }),
+            "tools/list" if params.get("cursor").is_none() => json!({
+                "tools": [{
+                    "name": "echo",
+                    "description": "Echo text",
+                    "inputSchema": {"type": "object", "properties": {"text": {"type": "string"}}},
+                }],
+                "nextCursor": "page2",
+            }),
+            "tools/list" => json!({"tools": [{"name": "secret.read"}]}),
+            "tools/call" => json!({
+                "content": [{"type": "text", "text": params["arguments"]["text"].clone()}],
+            }),
+            _ => json!({}),
+        }
+    }
+
+    /// Minimal stdio MCP server speaking newline-delimited JSON-RPC over a duplex pipe.
+    async fn stdio_server(name: &str, allow: Option<Vec<String>>) -> Arc<McpServer> {
+        let (client_io, server_io) = tokio::io::duplex(16 * 1024);
+        let (client_read, client_write) = tokio::io::split(client_io);
+        tokio::spawn(async move {
+            let (read, mut write) = tokio::io::split(server_io);
+            let mut lines = BufReader::new(read).lines();
+            while let Ok(Some(line)) = lines.next_line().await {
+                let request: Value = serde_json::from_str(&line).unwrap();
+                let Some(id) = request.get("id").cloned() else {
+                    continue;
+                };
+                let method = request["method"].as_str().unwrap_or_default();
+                let result = fake_result(method, &request["params"]);
+                let reply = json!({"jsonrpc": "2.0", "id": id, "result": result});
+                let mut bytes = serde_json::to_vec(&reply).unwrap();
+                bytes.push(b'\n');
+                write.write_all(&bytes).await.unwrap();
+            }
+        });
+
+        let transport = StdioTransport::from_io(
+            name,
+            client_read,
+            client_write,
+            Duration::from_secs(5),
+            Arc::new(Notify::new()),
+        );
+        let client = McpClient::connect(Box::new(transport)).await.unwrap();
+        let server = Arc::new(McpServer {
+            name: name.to_owned(),
+            trust: TrustLevel::Full,
+            allow,
+            client,
+            tools: RwLock::new(Vec::new()),
+        });
+        server.refresh_tools().await.unwrap();
+        server
+    }
+
+    #[test]
+    fn exposed_tool_name_is_sanitized_and_capped() {
+        assert_eq!(
+            exposed_tool_name("git hub", "list.repos"),
+            "mcp_git_hub_list_repos"
+        );
+        assert_eq!(exposed_tool_name("s", &"x".repeat(100)).len(), 64);
+    }
+
+    #[tokio::test]
+    async fn stdio_server_tools_are_listed_across_pages_and_callable() {
+        let executor = McpToolExecutor::from_servers(vec![stdio_server("fs", None).await]);
+
+        let names: Vec<String> = executor.tools().into_iter().map(|t| t.name).collect();
+        assert_eq!(names, vec!["mcp_fs_echo", "mcp_fs_secret_read"]);
+        assert_eq!(executor.tools()[0].description, "[MCP fs] Echo text");
+
+        let output = executor
+            .execute("mcp_fs_echo", json!({"text": "hi"}), &ctx(TrustLevel::Full))
+            .await
+            .unwrap();
+        assert!(!output.is_error);
+        assert_eq!(output.content, "hi");
+    }
+
+    #[tokio::test]
+    async fn colliding_names_get_a_suffix_instead_of_shadowing() {
+        // "f s" and "f.s" both sanitize to "mcp_f_s_echo"; "mcp_fs_echo" is
+        // taken by a built-in.
+        let executor = McpToolExecutor::from_servers(vec![
+            stdio_server("f s", None).await,
+            stdio_server("f.s", None).await,
+            stdio_server("fs", None).await,
+        ])
+        .with_reserved_names(["mcp_fs_echo".to_owned()]);
+
+        let names: Vec<String> = executor.tools().into_iter().map(|t| t.name).collect();
+        let unique: HashSet<&String> = names.iter().collect();
+        assert_eq!(unique.len(), names.len(), "{names:?}");
+        assert_eq!(names[0], "mcp_f_s_echo");
+        assert!(names[2].starts_with("mcp_f_s_echo_"), "{names:?}");
+        assert!(names[4].starts_with("mcp_fs_echo_"), "{names:?}");
+        assert!(names.iter().all(|name| name.len() <= MAX_TOOL_NAME_LEN));
+        assert_eq!(executor.tools()[2].name, names[2], "names are stable");
+
+        // Each suffixed name reaches its own server.
+        let output = executor
+            .execute(&names[2], json!({"text": "second"}), &ctx(TrustLevel::Full))
+            .await
+            .unwrap();
+        assert_eq!(output.content, "second");
+        let (server, tool) = executor.resolve(&names[2]).unwrap();
+        assert_eq!((server.name.as_str(), tool.as_str()), ("f.s", "echo"));
+        assert!(executor.resolve("mcp_fs_echo").is_none());
+
+        let long = disambiguated_tool_name(&"x".repeat(64), "s", "t");
+        assert_eq!(long.len(), MAX_TOOL_NAME_LEN);
+    }
+
+    #[tokio::test]
+    async fn allowlist_hides_tools() {
+        let executor = McpToolExecutor::from_servers(vec![
+            stdio_server("fs", Some(vec!["echo".to_owned()])).await,
+        ]);
+        let names: Vec<String> = executor.tools().into_iter().map(|t| t.name).collect();
+        assert_eq!(names, vec!["mcp_fs_echo"]);
+
+        let output = executor
+            .execute("mcp_fs_secret_read", json!({}), &ctx(TrustLevel::Full))
+            .await
+            .unwrap();
+        assert!(output.is_error);
+    }
+
+    #[tokio::test]
+    async fn execute_enforces_trust_and_visible_tools() {
+        let executor = McpToolExecutor::from_servers(vec![stdio_server("fs", None).await]);
+
+        let output = executor
+            .execute(
+                "mcp_fs_echo",
+                json!({"text": "hi"}),
+                &ctx(TrustLevel::Inner),
+            )
+            .await
+            .unwrap();
+        assert!(output.is_error);
+        assert!(output.content.contains("requires Full trust"));
+
+        let restricted = ctx(TrustLevel::Full).with_visible_tools(["bash"]);
+        let output = executor
+            .execute("mcp_fs_echo", json!({"text": "hi"}), &restricted)
+            .await
+            .unwrap();
+        assert!(output.is_error);
+        assert!(output.content.contains("not available"));
+    }
+
+    async fn read_http_request(stream: &mut tokio::net::TcpStream) -> (String, Value) {
+        use tokio::io::AsyncReadExt;
+        let mut buf = Vec::new();
+        let mut chunk = [0_u8; 4096];
+        loop {
+            let n = stream.read(&mut chunk).await.unwrap();
+            buf.extend_from_slice(&chunk[..n]);
+            let text = String::from_utf8_lossy(&buf).to_string();
+            if let Some(idx) = text.find("\r\n\r\n") {
+                let head = text[..idx].to_ascii_lowercase();
+                let len = head
+                    .lines()
+                    .find_map(|l| l.strip_prefix("content-length:"))
+                    .map_or(0, |v| v.trim().parse::<usize>().unwrap());
+                if buf.len() >= idx + 4 + len {
+                    let body = serde_json::from_slice(&buf[idx + 4..idx + 4 + len]).unwrap();
+                    return (head, body);
+                }
+            }
+            assert!(n != 0, "connection closed before full request");
+        }
+    }
+
+    #[tokio::test]
+    async fn http_server_uses_session_header_and_sse_responses() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let heads = Arc::new(Mutex::new(Vec::new()));
+        let seen = Arc::clone(&heads);
+        tokio::spawn(async move {
+            loop {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let (head, body) = read_http_request(&mut stream).await;
+                seen.lock().unwrap().push(head);
+                let response = match body.get("id") {
+                    None => "HTTP/1.1 202 Accepted\r\ncontent-length: 0\r\n\r\n".to_owned(),
+                    Some(id) => {
+                        let method = body["method"].as_str().unwrap_or_default();
+                        let payload = json!({
+                            "jsonrpc": "2.0",
+                            "id": id,
+                            "result": fake_result(method, &body["params"]),
+                        });
+                        let sse = format!("event: message\ndata: {payload}\n\n");
+                        format!(
+                            "HTTP/1.1 200 OK\r\ncontent-type: text/event-stream\r\n\
+                             mcp-session-id: sess-1\r\ncontent-length: {}\r\n\r\n{sse}",
+                            sse.len()
+                        )
+                    }
+                };
+                stream.write_all(response.as_bytes()).await.unwrap();
+            }
+        });
+
+        let mut config = server_config("remote");
+        config.url = Some(format!("http://{addr}/mcp"));
+        config
+            .headers
+            .insert("Authorization".to_owned(), "Bearer t".to_owned());
+        let executor = McpToolExecutor::connect(&[config], Path::new(".")).await;
+
+        let output = executor
+            .execute(
+                "mcp_remote_echo",
+                json!({"text": "over http"}),
+                &ctx(TrustLevel::Owner),
+            )
+            .await
+            .unwrap();
+        assert_eq!(output.content, "over http");
+
+        let heads = heads.lock().unwrap().clone();
+        assert!(!heads[0].contains("mcp-session-id"));
+        assert!(heads[0].contains("authorization: bearer t"));
+        assert!(heads.last().unwrap().contains("mcp-session-id: sess-1"));
+        assert!(
+            heads
+                .last()
+                .unwrap()
+                .contains("mcp-protocol-version: 2025-06-18")
+        );
+    }
+
+    #[tokio::test]
+    async fn failed_servers_are_skipped() {
+        let mut config = server_config("broken");
+        config.command = Some("/nonexistent/mcp-server".to_owned());
+        let executor = McpToolExecutor::connect(&[config], Path::new(".")).await;
+        assert!(executor.tools().is_empty());
+    }
+}
@@ -0,0 +1,508 @@
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use anyhow::{Context, Result, bail};
+use async_trait::async_trait;
+use serde_json::{Value, json};
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader};
+use tokio::process::{Child, Command};
+use tokio::sync::{Notify, oneshot};
+use tokio::task::JoinHandle;
+use tracing::{debug, warn};
+
+use crate::config::McpServerConfig;
+
+/// JSON-RPC transport to a single MCP server.
+#[async_trait]
+pub(crate) trait McpTransport: Send + Sync {
+    /// Send a request and wait for its result (the `result` member of the response).
+    async fn request(&self, method: &str, params: Value) -> Result<Value>;
+
+    /// Send a notification (no response expected).
+    async fn notify(&self, method: &str, params: Value) -> Result<()>;
+
+    /// Record the protocol version negotiated during `initialize`.
+    fn set_protocol_version(&self, _version: &str) {}
+}
+
+type PendingMap = Arc<Mutex<HashMap<u64, oneshot::Sender<Result<Value>>>>>;
+type SharedWriter = Arc<tokio::sync::Mutex<Box<dyn AsyncWrite + Send + Unpin>>>;
+
+fn rpc_error_message(error: &Value) -> String {
+    let code = error.get("code").and_then(Value::as_i64).unwrap_or(0);
+    let message = error
+        .get("message")
+        .and_then(Value::as_str)
+        .unwrap_or("unknown error");
+    format!("MCP error {code}: {message}")
+}
+
+fn response_result(message: &Value) -> Result<Value> {
+    if let Some(error) = message.get("error") {
+        bail!(rpc_error_message(error));
+    }
+    Ok(message.get("result").cloned().unwrap_or(Value::Null))
+}
+
+fn response_id(message: &Value) -> Option<u64> {
+    if message.get("method").is_some() {
+        return None;
+    }
+    message.get("id").and_then(Value::as_u64)
+}
+
+// ---------------------------------------------------------------------------
+// stdio
+// ---------------------------------------------------------------------------
+
+/// Newline-delimited JSON-RPC over a child process's stdin/stdout.
+pub(crate) struct StdioTransport {
+    writer: SharedWriter,
+    pending: PendingMap,
+    next_id: AtomicU64,
+    timeout: Duration,
+    reader: JoinHandle<()>,
+    child: Option<Child>,
+}
+
+impl std::fmt::Debug for StdioTransport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StdioTransport")
+            .field("timeout", &self.timeout)
+            .finish_non_exhaustive()
+    }
+}
+
+impl StdioTransport {
+    /// Spawn `command` and speak MCP over its stdio. The child is killed on drop.
+    pub(crate) fn spawn(
+        config: &McpServerConfig,
+        command: &str,
+        env: &BTreeMap<String, String>,
+        cwd: Option<&Path>,
+        timeout: Duration,
+        tools_changed: Arc<Notify>,
+    ) -> Result<Self> {
+        let server = config.name.as_str();
+        let mut cmd = Command::new(command);
+        cmd.args(&config.args)
+            .envs(env)
+            .stdin(std::process::Stdio::piped())
+            .stdout(std::process::Stdio::piped())
+            .stderr(std::process::Stdio::piped())
+            .kill_on_drop(true);
+        if let Some(cwd) = cwd {
+            cmd.current_dir(cwd);
+        }
+        let mut child = cmd
+            .spawn()
+            .with_context(|| format!("failed to spawn MCP server '{server}': {command}"))?;
+
+        let stdin = child.stdin.take().context("MCP server stdin unavailable")?;
+        let stdout = child
+            .stdout
+            .take()
+            .context("MCP server stdout unavailable")?;
+        if let Some(stderr) = child.stderr.take() {
+            let server = server.to_owned();
+            tokio::spawn(async move {
+                let mut lines = BufReader::new(stderr).lines();
+                while let Ok(Some(line)) = lines.next_line().await {
+                    debug!(mcp.server = %server, "{line}");
+                }
+            });
+        }
+
+        let mut transport = Self::from_io(server, stdout, stdin, timeout, tools_changed);
+        transport.child = Some(child);
+        Ok(transport)
+    }
+
+    /// Build a transport over arbitrary byte streams.
+    pub(crate) fn from_io<R, W>(
+        server: &str,
+        reader: R,
+        writer: W,
+        timeout: Duration,
+        tools_changed: Arc<Notify>,
+    ) -> Self
+    where
+        R: AsyncRead + Send + Unpin + 'static,
+        W: AsyncWrite + Send + Unpin + 'static,
+    {
+        let pending: PendingMap = Arc::new(Mutex::new(HashMap::new()));
+        let writer: SharedWriter = Arc::new(tokio::sync::Mutex::new(Box::new(writer)));
+        let reader = tokio::spawn(read_loop(
+            server.to_owned(),
+            reader,
+            Arc::clone(&writer),
+            Arc::clone(&pending),
+            tools_changed,
+        ));
+
+        Self {
+            writer,
+            pending,
+            next_id: AtomicU64::new(1),
+            timeout,
+            reader,
+            child: None,
+        }
+    }
+}
+
+impl Drop for StdioTransport {
+    fn drop(&mut self) {
+        self.reader.abort();
+        if let Some(child) = self.child.as_mut() {
+            let _ = child.start_kill();
+        }
+    }
+}
+
+async fn write_message(writer: &SharedWriter, message: &Value) -> Result<()> {
+    let mut line = serde_json::to_vec(message)?;
+    line.push(b'\n');
+    let mut writer = writer.lock().await;
+    writer.write_all(&line).await?;
+    writer.flush().await?;
+    drop(writer);
+    Ok(())
+}
+
+async fn read_loop<R>(
+    server: String,
+    reader: R,
+    writer: SharedWriter,
+    pending: PendingMap,
+    tools_changed: Arc<Notify>,
+) where
+    R: AsyncRead + Send + Unpin,
+{
+    let mut lines = BufReader::new(reader).lines();
+    loop {
+        let line = match lines.next_line().await {
+            Ok(Some(line)) => line,
+            Ok(None) => break,
+            Err(error) => {
+                warn!(mcp.server = %server, error = %error, "MCP read failed");
+                break;
+            }
+        };
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let message: Value = match serde_json::from_str(line) {
+            Ok(message) => message,
+            Err(error) => {
+                debug!(mcp.server = %server, error = %error, "ignoring non-JSON line from MCP server");
+                continue;
+            }
+        };
+
+        if let Some(id) = response_id(&message) {
+            let sender = pending
+                .lock()
+                .expect("mcp pending mutex poisoned")
+                .remove(&id);
+            if let Some(sender) = sender {
+                let _ = sender.send(response_result(&message));
+            }
+            continue;
+        }
+
+        let Some(method) = message.get("method").and_then(Value::as_str) else {
+            continue;
+        };
+
+        if let Some(id) = message.get("id") {
+            // Server-initiated request. We only answer pings.
+            let reply = if method == "ping" {
+                json!({"jsonrpc": "2.0", "id": id, "result": {}})
+            } else {
+                json!({
+                    "jsonrpc": "2.0",
+                    "id": id,
+                    "error": {"code": -32601, "message": format!("method not supported: {method}")},
+                })
+            };
+            if let Err(error) = write_message(&writer, &reply).await {
+                warn!(mcp.server = %server, error = %error, "failed to answer MCP server request");
+            }
+        } else if method == "notifications/tools/list_changed" {
+            tools_changed.notify_one();
+        } else {
+            debug!(mcp.server = %server, method, "MCP notification");
+        }
+    }
+
+    let waiters: Vec<_> = pending
+        .lock()
+        .expect("mcp pending mutex poisoned")
+        .drain()
+        .map(|(_, sender)| sender)
+        .collect();
+    for sender in waiters {
+        let _ = sender.send(Err(anyhow::anyhow!(
+            "MCP server '{server}' closed the connection"
+        )));
+    }
+}
+
+#[async_trait]
+impl McpTransport for StdioTransport {
+    async fn request(&self, method: &str, params: Value) -> Result<Value> {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let (tx, rx) = oneshot::channel();
+        self.pending
+            .lock()
+            .expect("mcp pending mutex poisoned")
+            .insert(id, tx);
+
+        let message = json!({"jsonrpc": "2.0", "id": id, "method": method, "params": params});
+        if let Err(error) = write_message(&self.writer, &message).await {
+            self.pending
+                .lock()
+                .expect("mcp pending mutex poisoned")
+                .remove(&id);
+            return Err(error.context("failed to write to MCP server"));
+        }
+
+        if let Ok(result) = tokio::time::timeout(self.timeout, rx).await {
+            result.context("MCP server closed the connection")?
+        } else {
+            self.pending
+                .lock()
+                .expect("mcp pending mutex poisoned")
+                .remove(&id);
+            bail!(
+                "MCP request '{method}' timed out after {}s",
+                self.timeout.as_secs()
+            )
+        }
+    }
+
+    async fn notify(&self, method: &str, params: Value) -> Result<()> {
+        let message = json!({"jsonrpc": "2.0", "method": method, "params": params});
+        write_message(&self.writer, &message).await
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Streamable HTTP
+// ---------------------------------------------------------------------------
+
+/// MCP streamable HTTP transport: one POST per message, JSON or SSE responses.
+#[derive(Debug)]
+pub(crate) struct HttpTransport {
+    client: reqwest::Client,
+    url: String,
+    headers: Vec<(String, String)>,
+    session_id: Mutex<Option<String>>,
+    protocol_version: Mutex<Option<String>>,
+    next_id: AtomicU64,
+}
+
+impl HttpTransport {
+    pub(crate) fn new(url: &str, headers: Vec<(String, String)>, timeout: Duration) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(timeout)
+            .build()
+            .unwrap_or_else(|_| reqwest::Client::new());
+        Self {
+            client,
+            url: url.to_owned(),
+            headers,
+            session_id: Mutex::new(None),
+            protocol_version: Mutex::new(None),
+            next_id: AtomicU64::new(1),
+        }
+    }
+
+    async fn post(&self, message: &Value) -> Result<reqwest::Response> {
+        let mut request = self
+            .client
+            .post(&self.url)
+            .header("Accept", "application/json, text/event-stream")
+            .json(message);
+        for (name, value) in &self.headers {
+            request = request.header(name, value);
+        }
+        let session_id = self
+            .session_id
+            .lock()
+            .expect("mcp session mutex poisoned")
+            .clone();
+        if let Some(session_id) = session_id {
+            request = request.header("Mcp-Session-Id", session_id);
+        }
+        let protocol_version = self
+            .protocol_version
+            .lock()
+            .expect("mcp protocol mutex poisoned")
+            .clone();
+        if let Some(version) = protocol_version {
+            request = request.header("MCP-Protocol-Version", version);
+        }
+
+        let response = request
+            .send()
+            .await
+            .with_context(|| format!("MCP request to {} failed", self.url))?;
+
+        if let Some(session_id) = response
+            .headers()
+            .get("mcp-session-id")
+            .and_then(|value| value.to_str().ok())
+        {
+            *self.session_id.lock().expect("mcp session mutex poisoned") =
+                Some(session_id.to_owned());
+        }
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            bail!("MCP server returned HTTP {status}: {}", body.trim());
+        }
+        Ok(response)
+    }
+}
+
+/// Extract JSON payloads from an SSE body (`data:` lines, events separated by blank lines).
+fn parse_sse_messages(body: &str) -> Vec<Value> {
+    let mut messages = Vec::new();
+    let mut data = String::new();
+    for line in body.lines().chain(std::iter::once("")) {
+        if line.is_empty() {
+            if !data.is_empty() {
+                if let Ok(value) = serde_json::from_str(&data) {
+                    messages.push(value);
+                }
+                data.clear();
+            }
+        } else if let Some(rest) = line.strip_prefix("data:") {
+            if !data.is_empty() {
+                data.push('\n');
+            }
+            data.push_str(rest.strip_prefix(' ').unwrap_or(rest));
+        }
+    }
+    messages
+}
+
+#[async_trait]
+impl McpTransport for HttpTransport {
+    async fn request(&self, method: &str, params: Value) -> Result<Value> {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let message = json!({"jsonrpc": "2.0", "id": id, "method": method, "params": params});
+        let response = self.post(&message).await?;
+
+        let is_sse = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .is_some_and(|value| value.starts_with("text/event-stream"));
+        let body = response.text().await?;
+
+        let candidates = if is_sse {
+            parse_sse_messages(&body)
+        } else {
+            match serde_json::from_str::<Value>(&body)
+                .context("MCP server returned invalid JSON")?
+            {
+                Value::Array(batch) => batch,
+                single => vec![single],
+            }
+        };
+
+        candidates
+            .iter()
+            .find(|candidate| response_id(candidate) == Some(id))
+            .map_or_else(
+                || bail!("MCP server sent no response for '{method}'"),
+                response_result,
+            )
+    }
+
+    async fn notify(&self, method: &str, params: Value) -> Result<()> {
+        let message = json!({"jsonrpc": "2.0", "method": method, "params": params});
+        self.post(&message).await?;
+        Ok(())
+    }
+
+    fn set_protocol_version(&self, version: &str) {
+        *self
+            .protocol_version
+            .lock()
+            .expect("mcp protocol mutex poisoned") = Some(version.to_owned());
+    }
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_sse_collects_data_events() {
+        let body = "event: message\ndata: {\"jsonrpc\":\"2.0\",\"id\":1,\"result\":{}}\n\n\
+                    : keepalive\n\ndata: {\"jsonrpc\":\"2.0\",\ndata: \"method\":\"x\"}\n";
+        let messages = parse_sse_messages(body);
+        assert_eq!(messages.len(), 2);
+        assert_eq!(response_id(&messages[0]), Some(1));
+        assert_eq!(messages[1]["method"], "x");
+    }
+
+    #[test]
+    fn response_result_maps_errors() {
+        let err = response_result(&json!({"id": 1, "error": {"code": -32602, "message": "bad"}}))
+            .unwrap_err();
+        assert_eq!(err.to_string(), "MCP error -32602: bad");
+    }
+
+    #[tokio::test]
+    async fn stdio_answers_pings_and_fails_pending_on_close() {
+        let (client_io, server_io) = tokio::io::duplex(4096);
+        let (client_read, client_write) = tokio::io::split(client_io);
+        let (server_read, mut server_write) = tokio::io::split(server_io);
+        let transport = StdioTransport::from_io(
+            "test",
+            client_read,
+            client_write,
+            Duration::from_secs(5),
+            Arc::new(Notify::new()),
+        );
+
+        let server = tokio::spawn(async move {
+            let mut lines = BufReader::new(server_read).lines();
+            server_write
+                .write_all(b"{\"jsonrpc\":\"2.0\",\"id\":\"p1\",\"method\":\"ping\"}\n")
+                .await
+                .unwrap();
+            let mut seen = Vec::new();
+            for _ in 0..2 {
+                let line = lines.next_line().await.unwrap().unwrap();
+                seen.push(serde_json::from_str::<Value>(&line).unwrap());
+            }
+            drop(server_write);
+            seen
+        });
+
+        let err = transport
+            .request("tools/list", json!({}))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("closed"), "{err}");
+
+        let seen = server.await.unwrap();
+        assert!(
+            seen.iter()
+                .any(|m| m["id"] == "p1" && m["result"] == json!({}))
+        );
+        assert!(seen.iter().any(|m| m["method"] == "tools/list"));
+    }
+}
@@ -0,0 +1,631 @@
+use anyhow::{Context, Result, bail};
+use coop_core::TrustLevel;
+use coop_memory::transfer::{self, ImportReport, notes, vault};
+use coop_memory::{
+    ArchivedObservation, ErasureReport, Memory, MemoryQuery, MemoryRecord, Observation,
+    ObservationEdit, ObservationHistoryEntry, ObservationIndex, Person, accessible_stores,
+};
+use serde::Serialize;
+use std::fmt::Write as _;
+use std::path::Path;
+
+use crate::cli::MemoryCommands;
+
+const DATE_FORMAT: &str = "%Y-%m-%d %H:%M";
+
+/// Run a `coop memory` subcommand and return what to print. Reads skip the
+/// ownership filter: the CLI runs as the operator, who sees every row.
+#[allow(clippy::too_many_lines)]
+pub(crate) async fn run(
+    memory: &dyn Memory,
+    command: MemoryCommands,
+    json: bool,
+) -> Result<String> {
+    match command {
+        MemoryCommands::RebuildIndex => {
+            let count = memory.rebuild_index().await?;
+            output(json, &serde_json::json!({ "rebuilt": count }), || {
+                format!("rebuilt vec index: {count} entries\n")
+            })
+        }
+        MemoryCommands::List {
+            store,
+            obs_type,
+            limit,
+            archived: true,
+        } => {
+            let mut rows = memory.archived(limit).await?;
+            rows.retain(|row| {
+                (store.is_empty() || store.contains(&row.store))
+                    && (obs_type.is_empty() || obs_type.contains(&row.obs_type))
+            });
+            output(json, &rows, || format_archived(&rows))
+        }
+        MemoryCommands::List {
+            store,
+            obs_type,
+            limit,
+            archived: false,
+        } => {
+            let rows = search(memory, None, store, obs_type, limit).await?;
+            output(json, &rows, || format_index(&rows))
+        }
+        MemoryCommands::Search {
+            query,
+            store,
+            obs_type,
+            limit,
+        } => {
+            let rows = search(memory, Some(query), store, obs_type, limit).await?;
+            output(json, &rows, || format_index(&rows))
+        }
+        MemoryCommands::Show { id } => {
+            let Some(observation) = memory.get(&[id], None).await?.into_iter().next() else {
+                bail!("observation {id} not found");
+            };
+            output(json, &observation, || format_observation(&observation))
+        }
+        MemoryCommands::History { id } => {
+            let history = memory.history(id).await?;
+            output(json, &history, || format_history(&history))
+        }
+        MemoryCommands::Edit {
+            id,
+            title,
+            narrative,
+            facts,
+            tags,
+            obs_type,
+            store,
+        } => {
+            if let Some(store) = &store {
+                validate_store(store)?;
+            }
+            let edit = ObservationEdit {
+                title,
+                narrative,
+                facts: (!facts.is_empty()).then_some(facts),
+                tags: (!tags.is_empty()).then_some(tags),
+                obs_type,
+                store,
+            };
+            if edit.is_empty() {
+                bail!(
+                    "nothing to change; pass --title, --narrative, --fact, --tag, --type or --store"
+                );
+            }
+            if !memory.edit(id, &edit).await? {
+                bail!("observation {id} not found");
+            }
+            output(json, &serde_json::json!({ "edited": id }), || {
+                format!("edited observation {id}\n")
+            })
+        }
+        MemoryCommands::Delete { id } => {
+            if !memory.delete(id).await? {
+                bail!("observation {id} not found");
+            }
+            output(json, &serde_json::json!({ "deleted": id }), || {
+                format!("deleted observation {id}\n")
+            })
+        }
+        MemoryCommands::People { query } => {
+            let people = memory
+                .people(query.as_deref().unwrap_or_default(), None)
+                .await?;
+            output(json, &people, || format_people(&people))
+        }
+        MemoryCommands::MergePeople { from, into } => {
+            if from == into {
+                bail!("cannot merge '{from}' into itself");
+            }
+            if !memory.merge_people(&from, &into).await? {
+                bail!("person '{from}' not found");
+            }
+            output(
+                json,
+                &serde_json::json!({ "merged": from, "into": into }),
+                || format!("merged '{from}' into '{into}'\n"),
+            )
+        }
+        MemoryCommands::ForgetPerson { name } => {
+            let report = memory.forget_person(&name).await?;
+            if report.is_empty() {
+                bail!("nothing in memory mentions '{name}'");
+            }
+            output(json, &report, || format_erasure(&report))
+        }
+        MemoryCommands::Archive { id } => {
+            if !memory.archive(id).await? {
+                bail!("observation {id} not found");
+            }
+            output(json, &serde_json::json!({ "archived": id }), || {
+                format!("archived observation {id}\n")
+            })
+        }
+        MemoryCommands::Restore { id } => {
+            let Some(restored) = memory.restore(id).await? else {
+                bail!("archived observation {id} not found");
+            };
+            output(
+                json,
+                &serde_json::json!({ "restored": id, "id": restored }),
+                || {
+                    if restored == id {
+                        format!("restored observation {id}\n")
+                    } else {
+                        format!("restored observation {id} as {restored}\n")
+                    }
+                },
+            )
+        }
+        MemoryCommands::Export { format, output } => {
+            export(memory, &format, output.as_deref(), json).await
+        }
+        MemoryCommands::Import { path } => {
+            let report = import(memory, Path::new(&path)).await?;
+            output(json, &report, || format_import(&report))
+        }
+    }
+}
+
+async fn export(
+    memory: &dyn Memory,
+    format: &str,
+    path: Option<&str>,
+    json: bool,
+) -> Result<String> {
+    let records = memory.export().await?;
+    match (format, path) {
+        ("jsonl", None) => return transfer::to_jsonl(&records),
+        ("jsonl", Some(path)) => std::fs::write(path, transfer::to_jsonl(&records)?)
+            .with_context(|| format!("failed to write {path}"))?,
+        ("markdown", Some(path)) => vault::write_vault(Path::new(path), &records)?,
+        ("markdown", None) => bail!("markdown exports need --output <dir>"),
+        (other, _) => bail!("unknown export format '{other}' (expected jsonl or markdown)"),
+    }
+
+    let mut counts = [0_usize; 4];
+    for record in &records {
+        let index = match record {
+            MemoryRecord::Observation(_) => 0,
+            MemoryRecord::Person(_) => 1,
+            MemoryRecord::History(_) => 2,
+            MemoryRecord::SessionSummary(_) => 3,
+        };
+        counts[index] += 1;
+    }
+    let [observations, people, history, session_summaries] = counts;
+    let path = path.unwrap_or_default();
+    output(
+        json,
+        &serde_json::json!({
+            "path": path,
+            "observations": observations,
+            "people": people,
+            "history": history,
+            "session_summaries": session_summaries,
+        }),
+        || {
+            format!(
+                "exported {observations} observations, {people} people, {history} history \
+                 entries and {session_summaries} session summaries to {path}\n"
+            )
+        },
+    )
+}
+
+/// `path` is a JSONL export, a Markdown vault, a workspace with notes, or a
+/// single notes file.
+async fn import(memory: &dyn Memory, path: &Path) -> Result<ImportReport> {
+    if path.is_dir() {
+        if vault::is_vault(path) {
+            return transfer::import(memory, vault::read_vault(path)?).await;
+        }
+        if notes::has_notes(path) {
+            return transfer::import_observations(memory, notes::workspace_notes(path)?).await;
+        }
+        bail!(
+            "{} has no memory vault, MEMORY.md or memory/ notes",
+            path.display()
+        );
+    }
+
+    if path.extension().is_some_and(|ext| ext == "md") {
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+        let in_notes_dir = path
+            .parent()
+            .and_then(Path::file_name)
+            .is_some_and(|dir| dir == "memory");
+        let relative = if in_notes_dir {
+            format!("memory/{name}")
+        } else {
+            name.into_owned()
+        };
+        return transfer::import_observations(memory, notes::file_notes(path, &relative)?).await;
+    }
+
+    let text = std::fs::read_to_string(path)
+        .with_context(|| format!("failed to read {}", path.display()))?;
+    transfer::import(memory, transfer::parse_jsonl(&text)?).await
+}
+
+async fn search(
+    memory: &dyn Memory,
+    text: Option<String>,
+    stores: Vec<String>,
+    types: Vec<String>,
+    limit: usize,
+) -> Result<Vec<ObservationIndex>> {
+    for store in &stores {
+        validate_store(store)?;
+    }
+    let query = MemoryQuery {
+        text,
+        stores,
+        types,
+        limit: limit.max(1),
+        ..Default::default()
+    };
+    memory.search(&query).await
+}
+
+fn validate_store(store: &str) -> Result<()> {
+    let stores = accessible_stores(TrustLevel::Full);
+    if !stores.iter().any(|known| known == store) {
+        bail!("unknown store '{store}' (expected {})", stores.join(", "));
+    }
+    Ok(())
+}
+
+fn output<T: Serialize + ?Sized>(
+    json: bool,
+    value: &T,
+    human: impl FnOnce() -> String,
+) -> Result<String> {
+    if json {
+        Ok(format!("{}\n", serde_json::to_string_pretty(value)?))
+    } else {
+        Ok(human())
+    }
+}
+
+fn format_index(rows: &[ObservationIndex]) -> String {
+    if rows.is_empty() {
+        return "No observations found.\n".to_owned();
+    }
+    let mut out = String::new();
+    for row in rows {
+        let _ = writeln!(
+            out,
+            "{:>6}  {}  {:<7} {:<12} {}",
+            row.id,
+            row.created_at.format(DATE_FORMAT),
+            row.store,
+            row.obs_type,
+            row.title,
+        );
+    }
+    out
+}
+
+fn format_archived(rows: &[ArchivedObservation]) -> String {
+    if rows.is_empty() {
+        return "No archived observations.\n".to_owned();
+    }
+    let mut out = String::new();
+    for row in rows {
+        let _ = writeln!(
+            out,
+            "{:>6}  archived {} ({})  {:<7} {:<12} {}",
+            row.id,
+            row.archived_at.format(DATE_FORMAT),
+            row.archive_reason,
+            row.store,
+            row.obs_type,
+            row.title,
+        );
+    }
+    out
+}
+
+fn format_observation(obs: &Observation) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "#{} {}", obs.id, obs.title);
+    let _ = writeln!(
+        out,
+        "store: {}  type: {}  created: {}  mentions: {}",
+        obs.store,
+        obs.obs_type,
+        obs.created_at.format(DATE_FORMAT),
+        obs.mention_count,
+    );
+    if let Some(owner) = &obs.owner {
+        let _ = write!(out, "owner: {owner}");
+        if !obs.shared_with.is_empty() {
+            let _ = write!(out, "  shared with: {}", obs.shared_with.join(", "));
+        }
+        out.push('\n');
+    }
+    for (label, values) in [
+        ("people", &obs.related_people),
+        ("files", &obs.related_files),
+        ("tags", &obs.tags),
+    ] {
+        if !values.is_empty() {
+            let _ = writeln!(out, "{label}: {}", values.join(", "));
+        }
+    }
+    if !obs.narrative.is_empty() {
+        let _ = write!(out, "\n{}\n", obs.narrative);
+    }
+    if !obs.facts.is_empty() {
+        out.push('\n');
+        for fact in &obs.facts {
+            let _ = writeln!(out, "- {fact}");
+        }
+    }
+    out
+}
+
+fn format_history(history: &[ObservationHistoryEntry]) -> String {
+    if history.is_empty() {
+        return "No history recorded.\n".to_owned();
+    }
+    let mut out = String::new();
+    for entry in history {
+        let _ = write!(
+            out,
+            "{}  {:<8}",
+            entry.created_at.format(DATE_FORMAT),
+            entry.event
+        );
+        match (&entry.old_title, &entry.new_title) {
+            (Some(old), Some(new)) if old != new => {
+                let _ = write!(out, " {old} -> {new}");
+            }
+            (_, Some(title)) | (Some(title), None) => {
+                let _ = write!(out, " {title}");
+            }
+            (None, None) => {}
+        }
+        out.push('\n');
+        if let Some(facts) = &entry.new_facts
+            && entry.old_facts.as_ref() != Some(facts)
+        {
+            let _ = writeln!(out, "    facts: {facts}");
+        }
+    }
+    out
+}
+
+fn format_import(report: &ImportReport) -> String {
+    let mut out = format!(
+        "imported observations: {} added, {} merged, {} unchanged\n",
+        report.added, report.updated, report.unchanged
+    );
+    if report.people > 0 || report.history > 0 || report.session_summaries > 0 {
+        let _ = writeln!(
+            out,
+            "also imported {} people, {} history entries and {} session summaries",
+            report.people, report.history, report.session_summaries
+        );
+    }
+    out
+}
+
+fn format_erasure(report: &ErasureReport) -> String {
+    let mut out = format!("forgot {}\n", report.names.join(", "));
+    for (label, count) in [
+        ("people deleted", report.people_deleted),
+        ("people redacted", report.people_redacted),
+        ("observations deleted", report.observations_deleted),
+        ("observations redacted", report.observations_redacted),
+        ("archived deleted", report.archived_deleted),
+        ("archived redacted", report.archived_redacted),
+        ("history entries redacted", report.history_redacted),
+        (
+            "session summaries redacted",
+            report.session_summaries_redacted,
+        ),
+        (
+            "session messages redacted",
+            report.session_messages_redacted,
+        ),
+    ] {
+        let _ = writeln!(out, "  {label}: {count}");
+    }
+    out
+}
+
+fn format_people(people: &[Person]) -> String {
+    if people.is_empty() {
+        return "No people found.\n".to_owned();
+    }
+    let mut out = String::new();
+    for person in people {
+        let _ = write!(
+            out,
+            "{}  [{}]  {} mentions",
+            person.name, person.store, person.mention_count
+        );
+        if !person.aliases.is_empty() {
+            let _ = write!(out, "  aka {}", person.aliases.join(", "));
+        }
+        out.push('\n');
+    }
+    out
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use coop_memory::{NewObservation, SqliteMemory, WriteOutcome};
+
+    fn memory() -> SqliteMemory {
+        let dir = tempfile::tempdir().unwrap();
+        let memory = SqliteMemory::open(dir.path().join("memory.db"), "coop").unwrap();
+        std::mem::forget(dir);
+        memory
+    }
+
+    async fn add(memory: &SqliteMemory, title: &str) -> i64 {
+        let mut obs = NewObservation::technical(title, "from the admin test");
+        obs.facts = vec![format!("{title} fact")];
+        obs.related_people = vec!["Bob".to_owned()];
+        let WriteOutcome::Added(id) = memory.write(obs).await.unwrap() else {
+            panic!("expected add");
+        };
+        id
+    }
+
+    #[tokio::test]
+    async fn list_show_and_edit_round_trip() {
+        let memory = memory();
+        let id = add(&memory, "deploy uses blue-green").await;
+
+        let list = MemoryCommands::List {
+            store: Vec::new(),
+            obs_type: Vec::new(),
+            limit: 10,
+            archived: false,
+        };
+        let out = run(&memory, list, false).await.unwrap();
+        assert!(out.contains("deploy uses blue-green"), "{out}");
+
+        let edit = MemoryCommands::Edit {
+            id,
+            title: Some("deploy uses canaries".to_owned()),
+            narrative: None,
+            facts: Vec::new(),
+            tags: Vec::new(),
+            obs_type: None,
+            store: None,
+        };
+        run(&memory, edit, false).await.unwrap();
+
+        let out = run(&memory, MemoryCommands::Show { id }, true)
+            .await
+            .unwrap();
+        let shown: serde_json::Value = serde_json::from_str(&out).unwrap();
+        assert_eq!(shown["title"], "deploy uses canaries");
+        assert_eq!(shown["facts"][0], "deploy uses blue-green fact");
+
+        let out = run(&memory, MemoryCommands::History { id }, false)
+            .await
+            .unwrap();
+        assert!(
+            out.contains("EDIT     deploy uses blue-green -> deploy uses canaries"),
+            "{out}"
+        );
+    }
+
+    #[tokio::test]
+    async fn archive_restore_and_delete() {
+        let memory = memory();
+        let id = add(&memory, "old build notes").await;
+
+        run(&memory, MemoryCommands::Archive { id }, false)
+            .await
+            .unwrap();
+        let archived = MemoryCommands::List {
+            store: Vec::new(),
+            obs_type: Vec::new(),
+            limit: 10,
+            archived: true,
+        };
+        let out = run(&memory, archived, false).await.unwrap();
+        assert!(out.contains("(manual)"), "{out}");
+
+        let out = run(&memory, MemoryCommands::Restore { id }, false)
+            .await
+            .unwrap();
+        assert_eq!(out, format!("restored observation {id}\n"));
+
+        run(&memory, MemoryCommands::Delete { id }, false)
+            .await
+            .unwrap();
+        let error = run(&memory, MemoryCommands::Show { id }, false)
+            .await
+            .unwrap_err();
+        assert_eq!(error.to_string(), format!("observation {id} not found"));
+    }
+
+    #[tokio::test]
+    async fn people_merge_and_validation() {
+        let memory = memory();
+        add(&memory, "lunch with bob").await;
+
+        let merge = MemoryCommands::MergePeople {
+            from: "Bob".to_owned(),
+            into: "Robert".to_owned(),
+        };
+        run(&memory, merge, false).await.unwrap();
+        let out = run(&memory, MemoryCommands::People { query: None }, false)
+            .await
+            .unwrap();
+        assert_eq!(out, "Robert  [shared]  1 mentions  aka Bob\n");
+
+        let forget = MemoryCommands::ForgetPerson {
+            name: "robert".to_owned(),
+        };
+        let out = run(&memory, forget, false).await.unwrap();
+        assert!(out.starts_with("forgot robert, Bob\n"), "{out}");
+        assert!(out.contains("  observations deleted: 1\n"), "{out}");
+        let forget = MemoryCommands::ForgetPerson {
+            name: "robert".to_owned(),
+        };
+        let error = run(&memory, forget, false).await.unwrap_err();
+        assert_eq!(error.to_string(), "nothing in memory mentions 'robert'");
+
+        let edit = MemoryCommands::Edit {
+            id: 1,
+            title: None,
+            narrative: None,
+            facts: Vec::new(),
+            tags: Vec::new(),
+            obs_type: None,
+            store: Some("secret".to_owned()),
+        };
+        let error = run(&memory, edit, false).await.unwrap_err();
+        assert!(error.to_string().contains("unknown store 'secret'"));
+    }
+
+    #[tokio::test]
+    async fn export_then_import_into_another_store() {
+        let source = memory();
+        add(&source, "deploys use canaries").await;
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("memory.jsonl");
+        let export = MemoryCommands::Export {
+            format: "jsonl".to_owned(),
+            output: Some(path.display().to_string()),
+        };
+        let out = run(&source, export, false).await.unwrap();
+        assert!(
+            out.starts_with("exported 1 observations, 1 people, 1 history"),
+            "{out}"
+        );
+
+        let target = memory();
+        let import = || MemoryCommands::Import {
+            path: path.display().to_string(),
+        };
+        let out = run(&target, import(), false).await.unwrap();
+        assert!(
+            out.starts_with("imported observations: 1 added, 0 merged, 0 unchanged\n"),
+            "{out}"
+        );
+        let out = run(&target, import(), true).await.unwrap();
+        let report: serde_json::Value = serde_json::from_str(&out).unwrap();
+        assert_eq!(report["unchanged"], 1);
+
+        let markdown = MemoryCommands::Export {
+            format: "markdown".to_owned(),
+            output: None,
+        };
+        let error = run(&source, markdown, false).await.unwrap_err();
+        assert_eq!(error.to_string(), "markdown exports need --output <dir>");
+    }
+}
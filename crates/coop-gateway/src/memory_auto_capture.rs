@@ -1,4 +1,5 @@
 use anyhow::{Context, Result};
+use coop_core::redaction::{self, RedactionMode};
 use coop_core::{Content, Message, Provider, SessionKey, ToolDef, TrustLevel};
 use coop_memory::{NewObservation, min_trust_for_store, normalize_file_path, trust_to_store};
 use serde::Deserialize;
@@ -42,6 +43,8 @@ pub(crate) async fn extract_turn_observations(
     messages: &[Message],
     session_key: &SessionKey,
     trust: TrustLevel,
+    owner: Option<&str>,
+    redaction: RedactionMode,
 ) -> Result<Vec<NewObservation>> {
     if messages.is_empty() {
         return Ok(Vec::new());
@@ -79,7 +82,7 @@ pub(crate) async fn extract_turn_observations(
 
     let observations = extracted
         .into_iter()
-        .filter_map(|row| to_new_observation(row, session_key, &store, min_trust))
+        .filter_map(|row| to_new_observation(row, session_key, &store, min_trust, owner, redaction))
         .collect::<Vec<_>>();
 
     debug!(
@@ -140,6 +143,9 @@ fn format_message(message: &Message) -> String {
             Content::Image { mime_type, .. } => {
                 parts.push(format!("[image mime_type={mime_type}]"));
             }
+            Content::Document { name, .. } => {
+                parts.push(format!("[document name={name}]"));
+            }
             Content::Thinking { .. } => {}
         }
     }
@@ -148,11 +154,18 @@ fn format_message(message: &Message) -> String {
 }
 
 fn to_new_observation(
-    raw: RawObservation,
+    mut raw: RawObservation,
     session_key: &SessionKey,
     store: &str,
     min_trust: TrustLevel,
+    owner: Option<&str>,
+    redaction: RedactionMode,
 ) -> Option<NewObservation> {
+    // Scan before clipping so a truncated key can't slip past the patterns.
+    let mut fields = vec![&mut raw.title, &mut raw.narrative];
+    fields.extend(raw.facts.iter_mut());
+    redaction::redact_fields(redaction, "auto_capture", fields).ok()?;
+
     let title = clip(raw.title.trim(), 80);
     if title.is_empty() {
         return None;
@@ -174,6 +187,9 @@ fn to_new_observation(
         token_count: None,
         expires_at: None,
         min_trust,
+        owner: owner.map(str::to_owned),
+        shared_with: Vec::new(),
+        created_at: None,
     })
 }
 
@@ -34,6 +34,7 @@ pub(crate) async fn build_prompt_index(
     trust: TrustLevel,
     settings: &MemoryPromptIndexConfig,
     user_input: &str,
+    viewer: Option<&str>,
 ) -> Result<Option<String>> {
     if !settings.enabled {
         debug!(reason = "disabled", "memory prompt index skipped");
@@ -56,6 +57,7 @@ pub(crate) async fn build_prompt_index(
         stores: stores.clone(),
         after: Some(recent_cutoff),
         limit,
+        viewer: viewer.map(str::to_owned),
         ..Default::default()
     };
     let recent_results = memory.search(&recent_query).await?;
@@ -66,6 +68,7 @@ pub(crate) async fn build_prompt_index(
             text: Some(terms.clone()),
             stores: stores.clone(),
             limit,
+            viewer: viewer.map(str::to_owned),
             ..Default::default()
         };
         match memory.search(&text_query).await {
@@ -93,7 +96,7 @@ pub(crate) async fn build_prompt_index(
             let mut linked_indexes = Vec::new();
             for path in &file_paths {
                 let prefix_match = path.ends_with('/');
-                match memory.search_by_file(path, prefix_match, 5).await {
+                match memory.search_by_file(path, prefix_match, 5, viewer).await {
                     Ok(rows) => {
                         linked_indexes
                             .extend(rows.into_iter().filter(|row| stores.contains(&row.store)));
@@ -115,7 +118,7 @@ pub(crate) async fn build_prompt_index(
                 "file_linked_observations"
             );
 
-            hydrate_file_links(memory, linked_indexes, limit).await
+            hydrate_file_links(memory, linked_indexes, limit, viewer).await
         }
     } else {
         Vec::new()
@@ -236,6 +239,7 @@ async fn hydrate_file_links(
     memory: &dyn Memory,
     linked_indexes: Vec<ObservationIndex>,
     limit: usize,
+    viewer: Option<&str>,
 ) -> Vec<FileLinkedObservation> {
     let mut deduped = Vec::new();
     let mut seen = HashSet::new();
@@ -253,7 +257,7 @@ async fn hydrate_file_links(
     }
 
     let ids = deduped.iter().map(|row| row.id).collect::<Vec<_>>();
-    let details = match memory.get(&ids).await {
+    let details = match memory.get(&ids, viewer).await {
         Ok(rows) => rows,
         Err(error) => {
             debug!(error = %error, "memory prompt index file detail fetch failed");
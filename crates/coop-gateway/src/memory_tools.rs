@@ -2,12 +2,13 @@ use anyhow::Result;
 use async_trait::async_trait;
 use chrono::{TimeZone, Utc};
 use coop_core::TrustLevel;
+use coop_core::redaction::RedactionMode;
 use coop_core::tool_args::reject_unknown_fields;
 use coop_core::traits::{ToolContext, ToolExecutor};
 use coop_core::types::{ToolDef, ToolOutput};
 use coop_memory::{
-    Memory, MemoryQuery, NewObservation, WriteOutcome, accessible_stores, min_trust_for_store,
-    normalize_file_path, trust_to_store,
+    Memory, MemoryQuery, NewObservation, WriteOutcome, accessible_stores, memory_principal,
+    min_trust_for_store, normalize_file_path, trust_to_store, user_principal,
 };
 use serde_json::Value;
 use std::collections::{HashMap, HashSet};
@@ -15,14 +16,34 @@ use std::path::Path;
 use std::sync::Arc;
 use tracing::{debug, instrument};
 
+use crate::config::SharedConfig;
+
 #[allow(missing_debug_implementations)]
 pub(crate) struct MemoryToolExecutor {
     memory: Arc<dyn Memory>,
+    config: Option<SharedConfig>,
 }
 
 impl MemoryToolExecutor {
     pub(crate) fn new(memory: Arc<dyn Memory>) -> Self {
-        Self { memory }
+        Self {
+            memory,
+            config: None,
+        }
+    }
+
+    /// Read `[redaction]` from `config` on each write. Without it, secrets
+    /// are masked.
+    pub(crate) fn with_config(mut self, config: SharedConfig) -> Self {
+        self.config = Some(config);
+        self
+    }
+
+    fn redaction_mode(&self) -> RedactionMode {
+        self.config
+            .as_ref()
+            .map(|config| config.load().redaction.mode)
+            .unwrap_or_default()
     }
 
     #[allow(clippy::too_many_lines)]
@@ -131,7 +152,12 @@ impl MemoryToolExecutor {
                         "source": { "type": "string" },
                         "token_count": { "type": "integer" },
                         "expires_at_ms": { "type": "integer" },
-                        "session_key": { "type": "string" }
+                        "session_key": { "type": "string" },
+                        "share_with": {
+                            "type": "array",
+                            "items": { "type": "string" },
+                            "description": "User names that may also read this private observation"
+                        }
                     },
                     "required": ["title"]
                 }),
@@ -186,6 +212,23 @@ impl MemoryToolExecutor {
                     "required": ["name", "alias"]
                 }),
             ),
+            ToolDef::new(
+                "memory_forget_person",
+                "Permanently erase a person from memory when they ask to be forgotten. \
+                 Observations only about them are deleted; every other mention (history, \
+                 archive, other people's facts, session summaries and transcripts) is \
+                 redacted. Requires full trust. Returns an erasure report.",
+                serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "name": {
+                            "type": "string",
+                            "description": "Name or alias of the person; all their aliases are erased too"
+                        }
+                    },
+                    "required": ["name"]
+                }),
+            ),
         ]
     }
 
@@ -246,13 +289,14 @@ impl MemoryToolExecutor {
                 .get("max_tokens")
                 .and_then(Value::as_u64)
                 .and_then(|v| usize::try_from(v).ok()),
+            viewer: viewer(ctx),
         };
 
         let mut results = self.memory.search(&query).await?;
 
         if let Some(path) = file_filter.as_ref() {
             let ids = results.iter().map(|result| result.id).collect::<Vec<_>>();
-            let observations = self.memory.get(&ids).await?;
+            let observations = self.memory.get(&ids, query.viewer.as_deref()).await?;
 
             let matching_ids = observations
                 .into_iter()
@@ -325,11 +369,15 @@ impl MemoryToolExecutor {
             return Ok(ToolOutput::success(serde_json::to_string_pretty(&payload)?));
         }
 
-        let mut results = self.memory.search_by_file(&path, prefix, limit).await?;
+        let viewer = viewer(ctx);
+        let mut results = self
+            .memory
+            .search_by_file(&path, prefix, limit, viewer.as_deref())
+            .await?;
         results.retain(|row| allowed.contains(&row.store));
 
         let ids = results.iter().map(|row| row.id).collect::<Vec<_>>();
-        let observations = self.memory.get(&ids).await?;
+        let observations = self.memory.get(&ids, viewer.as_deref()).await?;
         let related_files_by_id = observations
             .into_iter()
             .map(|obs| (obs.id, normalize_file_list(obs.related_files)))
@@ -410,7 +458,10 @@ impl MemoryToolExecutor {
             .min(20);
 
         let allowed = accessible_stores(ctx.trust);
-        let mut results = self.memory.timeline(anchor, before, after).await?;
+        let mut results = self
+            .memory
+            .timeline(anchor, before, after, viewer(ctx).as_deref())
+            .await?;
         results.retain(|obs| allowed.contains(&obs.store));
 
         let payload = serde_json::json!({
@@ -432,7 +483,7 @@ impl MemoryToolExecutor {
         }
 
         let allowed = accessible_stores(ctx.trust);
-        let mut observations = self.memory.get(&ids).await?;
+        let mut observations = self.memory.get(&ids, viewer(ctx).as_deref()).await?;
         observations.retain(|obs| allowed.contains(&obs.store));
 
         let payload = serde_json::json!({
@@ -470,7 +521,7 @@ impl MemoryToolExecutor {
             )));
         }
 
-        let obs = NewObservation {
+        let mut obs = NewObservation {
             session_key: arguments
                 .get("session_key")
                 .and_then(Value::as_str)
@@ -503,8 +554,20 @@ impl MemoryToolExecutor {
                 .and_then(|v| u32::try_from(v).ok()),
             expires_at: millis_to_datetime(arguments.get("expires_at_ms")),
             min_trust: min_trust_for_store(&store),
+            owner: viewer(ctx),
+            shared_with: string_array(arguments.get("share_with"))
+                .iter()
+                .map(|name| user_principal(name))
+                .collect(),
+            created_at: None,
         };
 
+        if let Err(blocked) = obs.redact_secrets(self.redaction_mode(), "memory_write") {
+            return Ok(ToolOutput::error(format!(
+                "memory_write refused: {blocked}. Leave the secret out and try again."
+            )));
+        }
+
         let outcome = self.memory.write(obs).await?;
 
         let payload = match outcome {
@@ -530,7 +593,10 @@ impl MemoryToolExecutor {
             .ok_or_else(|| anyhow::anyhow!("missing required parameter: observation_id"))?;
 
         let allowed = accessible_stores(ctx.trust);
-        let observations = self.memory.get(&[observation_id]).await?;
+        let observations = self
+            .memory
+            .get(&[observation_id], viewer(ctx).as_deref())
+            .await?;
         if observations.is_empty() {
             return Ok(ToolOutput::success("{\"count\":0,\"history\":[]}"));
         }
@@ -561,7 +627,7 @@ impl MemoryToolExecutor {
             .unwrap_or_default();
 
         let allowed = accessible_stores(ctx.trust);
-        let mut people = self.memory.people(query).await?;
+        let mut people = self.memory.people(query, viewer(ctx).as_deref()).await?;
         people.retain(|person| allowed.contains(&person.store));
 
         let payload = serde_json::json!({
@@ -605,6 +671,31 @@ impl MemoryToolExecutor {
         Ok(ToolOutput::success(serde_json::to_string_pretty(&payload)?))
     }
 
+    #[instrument(skip(self, arguments, ctx))]
+    async fn exec_forget_person(&self, arguments: Value, ctx: &ToolContext) -> Result<ToolOutput> {
+        if let Some(output) = reject_unknown_memory_fields("memory_forget_person", &arguments) {
+            return Ok(output);
+        }
+
+        if ctx.trust > TrustLevel::Full {
+            return Ok(ToolOutput::error(
+                "memory_forget_person requires full trust",
+            ));
+        }
+
+        let name = arguments
+            .get("name")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .trim();
+        if name.is_empty() {
+            return Ok(ToolOutput::error("name is required"));
+        }
+
+        let report = self.memory.forget_person(name).await?;
+        Ok(ToolOutput::success(serde_json::to_string_pretty(&report)?))
+    }
+
     #[instrument(skip(self, arguments, ctx))]
     async fn exec_sessions(&self, arguments: Value, ctx: &ToolContext) -> Result<ToolOutput> {
         if let Some(output) = reject_unknown_memory_fields("memory_sessions", &arguments) {
@@ -644,6 +735,7 @@ impl ToolExecutor for MemoryToolExecutor {
             "memory_people" => self.exec_people(arguments, ctx).await,
             "memory_alias" => self.exec_alias(arguments, ctx).await,
             "memory_sessions" => self.exec_sessions(arguments, ctx).await,
+            "memory_forget_person" => self.exec_forget_person(arguments, ctx).await,
             _ => Ok(ToolOutput::error(format!("unknown tool: {name}"))),
         }
     }
@@ -653,6 +745,11 @@ impl ToolExecutor for MemoryToolExecutor {
     }
 }
 
+/// Memory principal for the turn; private rows owned by others are hidden.
+fn viewer(ctx: &ToolContext) -> Option<String> {
+    memory_principal(&ctx.session_kind, ctx.user_name.as_deref())
+}
+
 fn reject_unknown_memory_fields(tool_name: &str, arguments: &Value) -> Option<ToolOutput> {
     let allowed_fields = match tool_name {
         "memory_search" => &[
@@ -682,11 +779,13 @@ fn reject_unknown_memory_fields(tool_name: &str, arguments: &Value) -> Option<To
             "token_count",
             "expires_at_ms",
             "session_key",
+            "share_with",
         ][..],
         "memory_history" => &["observation_id"][..],
         "memory_people" => &["query"][..],
         "memory_sessions" => &["limit"][..],
         "memory_alias" => &["name", "alias"][..],
+        "memory_forget_person" => &["name"][..],
         _ => return Some(ToolOutput::error(format!("unknown tool: {tool_name}"))),
     };
 
@@ -817,6 +916,108 @@ mod tests {
         assert!(out.content.contains("\"count\":0"));
     }
 
+    #[tokio::test]
+    async fn private_writes_are_owned_by_the_writing_user() {
+        let exec = executor();
+        let as_user = |name: &str| {
+            ToolContext::new(
+                "coop:main",
+                SessionKind::Main,
+                TrustLevel::Full,
+                Path::new("."),
+                Some(name),
+            )
+        };
+        let write = exec
+            .execute(
+                "memory_write",
+                serde_json::json!({
+                    "store": "private",
+                    "title": "alice passport renewal",
+                    "share_with": ["carol"]
+                }),
+                &as_user("alice"),
+            )
+            .await
+            .unwrap();
+        assert!(!write.is_error, "{}", write.content);
+
+        let search = serde_json::json!({"query": "passport"});
+        for (user, count) in [("alice", 1), ("bob", 0), ("carol", 1)] {
+            let out = exec
+                .execute("memory_search", search.clone(), &as_user(user))
+                .await
+                .unwrap();
+            assert!(
+                out.content.contains(&format!("\"count\": {count}")),
+                "{user}: {}",
+                out.content
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn write_masks_or_blocks_secrets() {
+        let (exec, memory) = executor_with_memory();
+        let args = serde_json::json!({
+            "title": "Deploy credentials",
+            "facts": ["deploy key is sk-abcdefghijklmnopqrstuvwxyz"]
+        });
+
+        let out = exec
+            .execute("memory_write", args.clone(), &ctx(TrustLevel::Full))
+            .await
+            .unwrap();
+        let payload: Value = serde_json::from_str(&out.content).unwrap();
+        let stored = memory
+            .get(&[payload["id"].as_i64().unwrap()], None)
+            .await
+            .unwrap();
+        assert_eq!(stored[0].facts, vec!["deploy key is [redacted:api_key]"]);
+
+        let config: crate::config::Config = toml::from_str(
+            "[agent]\nid = \"coop\"\nmodel = \"test\"\n\n[redaction]\nmode = \"block\"\n",
+        )
+        .unwrap();
+        let exec = exec.with_config(crate::config::shared_config(config));
+        let out = exec
+            .execute("memory_write", args, &ctx(TrustLevel::Full))
+            .await
+            .unwrap();
+        assert!(out.is_error);
+        assert!(out.content.contains("api_key"), "{}", out.content);
+    }
+
+    #[tokio::test]
+    async fn forget_person_requires_full_trust() {
+        let (exec, memory) = executor_with_memory();
+        let mut obs = NewObservation::technical("Dana's birthday is in May", "");
+        obs.related_people = vec!["Dana".to_owned()];
+        memory.write(obs).await.unwrap();
+
+        let args = serde_json::json!({"name": "Dana"});
+        let denied = exec
+            .execute(
+                "memory_forget_person",
+                args.clone(),
+                &ctx(TrustLevel::Inner),
+            )
+            .await
+            .unwrap();
+        assert!(denied.is_error);
+        assert_eq!(memory.people("Dana", None).await.unwrap().len(), 1);
+
+        let out = exec
+            .execute("memory_forget_person", args, &ctx(TrustLevel::Full))
+            .await
+            .unwrap();
+        assert!(!out.is_error, "{}", out.content);
+        let report: Value = serde_json::from_str(&out.content).unwrap();
+        assert_eq!(report["people_deleted"], 1);
+        assert_eq!(report["observations_deleted"], 1);
+        assert!(memory.people("Dana", None).await.unwrap().is_empty());
+    }
+
     #[tokio::test]
     async fn memory_sessions_returns_recent_summaries() {
         let (exec, memory) = executor_with_memory();
@@ -836,6 +1037,9 @@ mod tests {
                 token_count: Some(32),
                 expires_at: None,
                 min_trust: min_trust_for_store("shared"),
+                owner: None,
+                shared_with: Vec::new(),
+                created_at: None,
             })
             .await
             .unwrap();
@@ -881,6 +1085,9 @@ mod tests {
                 token_count: Some(8),
                 expires_at: None,
                 min_trust: min_trust_for_store("shared"),
+                owner: None,
+                shared_with: Vec::new(),
+                created_at: None,
             })
             .await
             .unwrap();
@@ -903,6 +1110,9 @@ mod tests {
                 token_count: Some(8),
                 expires_at: None,
                 min_trust: min_trust_for_store("shared"),
+                owner: None,
+                shared_with: Vec::new(),
+                created_at: None,
             })
             .await
             .unwrap();
@@ -944,6 +1154,9 @@ mod tests {
                 token_count: Some(8),
                 expires_at: None,
                 min_trust: min_trust_for_store("private"),
+                owner: None,
+                shared_with: Vec::new(),
+                created_at: None,
             })
             .await
             .unwrap();
@@ -963,6 +1176,9 @@ mod tests {
                 token_count: Some(8),
                 expires_at: None,
                 min_trust: min_trust_for_store("social"),
+                owner: None,
+                shared_with: Vec::new(),
+                created_at: None,
             })
             .await
             .unwrap();
@@ -1004,6 +1220,9 @@ mod tests {
                 token_count: Some(8),
                 expires_at: None,
                 min_trust: min_trust_for_store("shared"),
+                owner: None,
+                shared_with: Vec::new(),
+                created_at: None,
             })
             .await
             .unwrap();
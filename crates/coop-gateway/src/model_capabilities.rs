@@ -1,6 +1,9 @@
 use std::collections::BTreeSet;
 
+use coop_agent::{OllamaModel, ProviderKind};
+
 use crate::config::{Config, ModelCapabilitiesConfig, ModelModality, ProviderConfig};
+use crate::local_models;
 use crate::model_catalog::{
     normalize_model_key, resolve_configured_model, resolve_model_reference,
 };
@@ -62,7 +65,33 @@ pub(crate) fn provider_model_capabilities(
         .iter()
         .find(|(candidate, _)| normalize_model_key(candidate) == model_key)
         .map(|(_, config)| config);
-    apply_capabilities(EffectiveModelCapabilities::default(), override_config)
+    // Older Ollama servers don't report capabilities at all.
+    let mut base = local_models::discovered_model(provider, model)
+        .filter(|local| !local.capabilities.is_empty())
+        .map_or_else(EffectiveModelCapabilities::default, |local| {
+            local_capabilities(&local)
+        });
+    // Anthropic and Gemini read PDFs natively; other models get extracted text.
+    if matches!(
+        ProviderKind::from_name(&provider.name),
+        Ok(ProviderKind::Anthropic | ProviderKind::Gemini)
+    ) {
+        base.input_modalities.insert(ModelModality::Document);
+    }
+    apply_capabilities(base, override_config)
+}
+
+/// Capabilities an Ollama server reports for one of its models.
+fn local_capabilities(model: &OllamaModel) -> EffectiveModelCapabilities {
+    let mut input_modalities = BTreeSet::from([ModelModality::Text]);
+    if model.supports("vision") {
+        input_modalities.insert(ModelModality::Image);
+    }
+    EffectiveModelCapabilities {
+        supports_tools: model.supports("tools"),
+        input_modalities,
+        ..EffectiveModelCapabilities::default()
+    }
 }
 
 fn apply_capabilities(
@@ -140,4 +169,33 @@ output_modalities = ["text", "image"]
         assert!(caps.supports_output(ModelModality::Image));
         assert!(!caps.visible_in_main_models());
     }
+
+    #[test]
+    fn document_input_follows_provider() {
+        let cfg = config(
+            r#"
+[agent]
+id = "test"
+model = "anthropic/claude-sonnet-4-20250514"
+
+[[providers]]
+name = "anthropic"
+models = ["claude-sonnet-4-20250514", "claude-haiku-4-5"]
+
+[providers.model_capabilities."claude-haiku-4-5"]
+input_modalities = ["text", "image"]
+
+[[providers]]
+name = "openai"
+models = ["gpt-5.4"]
+"#,
+        );
+
+        let caps = model_capabilities(&cfg, "anthropic/claude-sonnet-4-20250514").unwrap();
+        assert!(caps.supports_input(ModelModality::Document));
+        let caps = model_capabilities(&cfg, "anthropic/claude-haiku-4-5").unwrap();
+        assert!(!caps.supports_input(ModelModality::Document));
+        let caps = model_capabilities(&cfg, "openai/gpt-5.4").unwrap();
+        assert!(!caps.supports_input(ModelModality::Document));
+    }
 }
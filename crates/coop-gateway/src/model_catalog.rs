@@ -2,6 +2,7 @@ use std::collections::HashSet;
 use tracing::debug;
 
 use crate::config::{Config, ProviderConfig};
+use crate::local_models;
 use crate::model_capabilities::provider_model_capabilities;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -125,13 +126,23 @@ pub(crate) fn provider_model_candidates(provider: &ProviderConfig) -> Vec<Availa
         }
     };
 
-    if provider.models.is_empty() {
+    let discovered = local_models::discovered_models(provider);
+    if let (true, Some(discovered)) = (provider.models.is_empty(), &discovered) {
+        for model in discovered {
+            push(&model.name, Some(&local_models::describe(model)));
+        }
+    } else if provider.models.is_empty() {
         for entry in builtins {
             push(entry.id, Some(entry.description));
         }
     } else {
         for model in &provider.models {
-            push(model, builtin_description(builtins, model));
+            let local = local_models::discovered_model(provider, model)
+                .map(|model| local_models::describe(&model));
+            push(
+                model,
+                builtin_description(builtins, model).or(local.as_deref()),
+            );
         }
     }
 
@@ -200,7 +200,7 @@ impl Gateway {
             "compacting session before lower-context model handoff"
         );
 
-        let state = compaction::compact(
+        let (state, _usage) = compaction::compact(
             &all_messages,
             plan.current_provider,
             &system_prompt,
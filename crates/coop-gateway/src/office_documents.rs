@@ -0,0 +1,160 @@
+use anyhow::{Context, Result};
+use coop_core::WorkspaceScope;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tokio::process::Command;
+use tracing::{debug, info, warn};
+
+/// Office extensions we convert to PDF (lowercase, without dot).
+const OFFICE_EXTENSIONS: &[&str] = &[
+    "doc", "docx", "odp", "ods", "odt", "ppt", "pptx", "rtf", "xls", "xlsx",
+];
+
+/// Converted files are cached next to the original as `<file>.pdf`.
+const PDF_SUFFIX: &str = ".pdf";
+
+const CONVERSION_TIMEOUT: Duration = Duration::from_mins(1);
+
+/// Convert office attachments (`[file saved: …]` lines) to PDF with
+/// LibreOffice and add a `[converted to PDF: …]` line pointing at the
+/// result, so the document pipeline picks them up like any other PDF.
+///
+/// Returns `None` when `text` has no office attachments.
+pub(crate) async fn convert_office_attachments(
+    text: &str,
+    scope: &WorkspaceScope,
+) -> Option<String> {
+    if !text.lines().any(|line| saved_office_path(line).is_some()) {
+        return None;
+    }
+
+    let mut lines = Vec::new();
+    for line in text.lines() {
+        lines.push(line.to_owned());
+        let Some(path) = saved_office_path(line) else {
+            continue;
+        };
+        match convert_cached(path, scope).await {
+            Ok(()) => lines.push(format!("[converted to PDF: {path}{PDF_SUFFIX}]")),
+            Err(error) => {
+                warn!(path, error = %format!("{error:#}"), "office document conversion failed");
+                lines.push(format!("[PDF conversion failed: {error}]"));
+            }
+        }
+    }
+    Some(lines.join("\n"))
+}
+
+fn saved_office_path(line: &str) -> Option<&str> {
+    let path = line
+        .trim()
+        .strip_prefix("[file saved: ")?
+        .strip_suffix(']')?;
+    let extension = Path::new(path).extension()?.to_str()?.to_ascii_lowercase();
+    OFFICE_EXTENSIONS
+        .contains(&extension.as_str())
+        .then_some(path)
+}
+
+fn pdf_path(document: &Path) -> PathBuf {
+    let mut name = document.file_name().unwrap_or_default().to_os_string();
+    name.push(PDF_SUFFIX);
+    document.with_file_name(name)
+}
+
+async fn convert_cached(path: &str, scope: &WorkspaceScope) -> Result<()> {
+    let document = scope.resolve_host_path_for_read(path)?;
+    let target = pdf_path(&document);
+    if target.is_file() {
+        debug!(path, "using cached PDF conversion");
+        return Ok(());
+    }
+
+    // soffice names its output after the input stem, so convert into a
+    // scratch directory and move the result into place.
+    let outdir = std::env::temp_dir().join(format!("coop-office-{}", uuid::Uuid::new_v4()));
+    std::fs::create_dir_all(&outdir).context("failed to create conversion directory")?;
+    let result = run_soffice(&document, &outdir, &target).await;
+    let _ = std::fs::remove_dir_all(&outdir);
+    result.with_context(|| format!("failed to convert {path}"))?;
+    info!(path, "office document converted to PDF");
+    Ok(())
+}
+
+async fn run_soffice(document: &Path, outdir: &Path, target: &Path) -> Result<()> {
+    let output = tokio::time::timeout(
+        CONVERSION_TIMEOUT,
+        Command::new("soffice")
+            .args([
+                "--headless",
+                "--norestore",
+                "--convert-to",
+                "pdf",
+                "--outdir",
+            ])
+            .arg(outdir)
+            .arg(document)
+            .kill_on_drop(true)
+            .output(),
+    )
+    .await
+    .map_err(|_elapsed| anyhow::anyhow!("soffice timed out"))?
+    .context("failed to run soffice (install LibreOffice to read office files)")?;
+    anyhow::ensure!(
+        output.status.success(),
+        "soffice exited with {}: {}",
+        output.status,
+        String::from_utf8_lossy(&output.stderr).trim()
+    );
+
+    let stem = document.file_stem().unwrap_or_default();
+    let converted = outdir.join(stem).with_extension("pdf");
+    std::fs::copy(&converted, target).context("soffice produced no PDF")?;
+    Ok(())
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use coop_core::{SessionKind, TrustLevel};
+
+    #[test]
+    fn detects_saved_office_lines() {
+        assert_eq!(
+            saved_office_path("[file saved: ./attachments/1_form.DOCX]"),
+            Some("./attachments/1_form.DOCX")
+        );
+        assert_eq!(
+            saved_office_path("[file saved: ./attachments/1_bill.pdf]"),
+            None
+        );
+        assert_eq!(saved_office_path("please read form.docx"), None);
+    }
+
+    #[tokio::test]
+    async fn cached_conversions_are_referenced() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("form.docx"), b"docx").unwrap();
+        std::fs::write(dir.path().join("form.docx.pdf"), b"%PDF-1.4\n").unwrap();
+        let scope =
+            WorkspaceScope::for_turn(dir.path(), &SessionKind::Main, TrustLevel::Full, None);
+
+        assert!(
+            convert_office_attachments("no attachments here", &scope)
+                .await
+                .is_none()
+        );
+        let text = convert_office_attachments("[file saved: ./form.docx]\nfill this in", &scope)
+            .await
+            .unwrap();
+        assert_eq!(
+            text,
+            "[file saved: ./form.docx]\n[converted to PDF: ./form.docx.pdf]\nfill this in"
+        );
+        assert_eq!(
+            coop_core::documents::detect_document_paths(&text),
+            vec!["./form.docx.pdf"]
+        );
+    }
+}
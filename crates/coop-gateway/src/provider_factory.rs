@@ -102,6 +102,7 @@ pub(crate) fn provider_spec(config: &Config, model: &str) -> Result<ProviderSpec
         extra_headers: provider.extra_headers.clone(),
         refresh_token: provider.refresh_token.clone(),
         reasoning: provider.reasoning.clone(),
+        keep_alive: provider.keep_alive.clone(),
     })
 }
 
@@ -54,6 +54,11 @@ fn estimate_message_chars(message: &Message) -> usize {
             } => id.len() + name.len() + arguments.to_string().len(),
             Content::ToolResult { id, output, .. } => id.len() + output.len(),
             Content::Image { data, mime_type } => data.len() + mime_type.len(),
+            Content::Document {
+                data,
+                mime_type,
+                name,
+            } => data.len() + mime_type.len() + name.len(),
             Content::Thinking {
                 thinking,
                 signature,
@@ -9,12 +9,13 @@ use std::sync::Arc;
 
 use crate::commands;
 use crate::config::{
-    Config, CronDeliveryMode, GroupConfig, GroupTrigger, SharedConfig, TrustCeiling,
+    Config, CronDeliveryMode, GroupConfig, GroupTrigger, SharedConfig, TrustCeiling, UserConfig,
 };
 use crate::gateway::Gateway;
 use crate::group_trigger::{self, TriggerDecision};
 #[cfg(test)]
 use crate::injection::SessionInjection;
+use crate::subagents::TurnOverrides;
 use crate::trust::resolve_trust;
 
 enum GroupTriggerOutcome {
@@ -34,12 +35,45 @@ pub(crate) struct RouteDecision {
 #[derive(Clone)]
 pub(crate) struct MessageRouter {
     config: SharedConfig,
+    /// Gateway for the default `[agent]`.
     gateway: Arc<Gateway>,
+    /// Gateways for additional `[[agents]]`, keyed by agent id.
+    agents: Vec<(String, Arc<Gateway>)>,
 }
 
 impl MessageRouter {
     pub(crate) fn new(config: SharedConfig, gateway: Arc<Gateway>) -> Self {
-        Self { config, gateway }
+        Self {
+            config,
+            gateway,
+            agents: Vec::new(),
+        }
+    }
+
+    /// Register the gateway serving an additional agent.
+    pub(crate) fn with_agent(mut self, gateway: Arc<Gateway>) -> Self {
+        self.agents.push((gateway.agent_id(), gateway));
+        self
+    }
+
+    /// Every gateway this router dispatches to, default agent first.
+    pub(crate) fn gateways(&self) -> impl Iterator<Item = &Arc<Gateway>> {
+        std::iter::once(&self.gateway).chain(self.agents.iter().map(|(_, gateway)| gateway))
+    }
+
+    /// Gateway owning `session_key`, by its agent id.
+    pub(crate) fn gateway_for(&self, session_key: &SessionKey) -> &Arc<Gateway> {
+        self.agents
+            .iter()
+            .find(|(id, _)| *id == session_key.agent_id)
+            .map_or(&self.gateway, |(_, gateway)| gateway)
+    }
+
+    /// Resolve a client session string (`main`, `<agent>:main`,
+    /// `<agent>:dm:...`) against every hosted agent.
+    pub(crate) fn resolve_session(&self, session: &str) -> Option<(&Arc<Gateway>, SessionKey)> {
+        self.gateways()
+            .find_map(|gateway| gateway.resolve_session(session).map(|key| (gateway, key)))
     }
 
     pub(crate) fn route(&self, msg: &InboundMessage) -> RouteDecision {
@@ -48,51 +82,57 @@ impl MessageRouter {
 
     #[cfg(feature = "signal")]
     pub(crate) fn session_is_empty(&self, session_key: &SessionKey) -> bool {
-        self.gateway.session_is_empty(session_key)
+        self.gateway_for(session_key).session_is_empty(session_key)
     }
 
     #[cfg(feature = "signal")]
     pub(crate) fn seed_signal_history(&self, session_key: &SessionKey, history: &[InboundMessage]) {
-        self.gateway.seed_signal_history(session_key, history);
-    }
-
-    /// Whether the Signal channel is configured with `verbose: true`.
-    #[cfg(feature = "signal")]
-    pub(crate) fn signal_verbose(&self) -> bool {
-        self.config
-            .load()
-            .channels
-            .signal
-            .as_ref()
-            .is_some_and(|s| s.verbose)
+        self.gateway_for(session_key)
+            .seed_signal_history(session_key, history);
+    }
+
+    /// Whether the given chat channel is configured with `verbose: true`.
+    #[cfg(any(feature = "signal", feature = "telegram"))]
+    pub(crate) fn channel_verbose(&self, channel: &str) -> bool {
+        let config = self.config.load();
+        match channel {
+            "signal" => config.channels.signal.as_ref().is_some_and(|s| s.verbose),
+            "telegram" => config.channels.telegram.as_ref().is_some_and(|t| t.verbose),
+            _ => false,
+        }
     }
 
-    /// How to handle messages arriving during an active turn.
-    #[cfg(feature = "signal")]
-    pub(crate) fn mid_turn_messages_mode(&self) -> crate::config::MidTurnMessages {
-        self.config
-            .load()
-            .channels
-            .signal
-            .as_ref()
-            .map_or_else(crate::config::MidTurnMessages::default, |s| {
-                s.mid_turn_messages
-            })
+    /// How to handle messages arriving on `channel` during an active turn.
+    #[cfg(any(feature = "signal", feature = "telegram"))]
+    pub(crate) fn mid_turn_messages_mode(&self, channel: &str) -> crate::config::MidTurnMessages {
+        let config = self.config.load();
+        match channel {
+            "signal" => config.channels.signal.as_ref().map(|s| s.mid_turn_messages),
+            "telegram" => config
+                .channels
+                .telegram
+                .as_ref()
+                .map(|t| t.mid_turn_messages),
+            _ => None,
+        }
+        .unwrap_or_default()
     }
 
     pub(crate) fn append_to_session(&self, session_key: &SessionKey, message: coop_core::Message) {
-        self.gateway.append_message(session_key, message);
+        self.gateway_for(session_key)
+            .append_message(session_key, message);
     }
 
     /// Queue a message for injection into a running turn's context.
-    #[cfg(feature = "signal")]
+    #[cfg(any(feature = "signal", feature = "telegram"))]
     pub(crate) fn inject_pending_inbound(&self, session_key: &SessionKey, content: String) {
-        self.gateway.inject_pending_inbound(session_key, content);
+        self.gateway_for(session_key)
+            .inject_pending_inbound(session_key, content);
     }
 
     /// Returns `true` if the given session has an in-progress turn.
     pub(crate) fn has_active_turn(&self, session_key: &SessionKey) -> bool {
-        self.gateway.has_active_turn(session_key)
+        self.gateway_for(session_key).has_active_turn(session_key)
     }
 
     /// Returns the per-session turn lock. Acquire before appending messages to
@@ -101,7 +141,7 @@ impl MessageRouter {
         &self,
         session_key: &SessionKey,
     ) -> Arc<tokio::sync::Mutex<()>> {
-        self.gateway.session_turn_lock(session_key)
+        self.gateway_for(session_key).session_turn_lock(session_key)
     }
 
     pub(crate) async fn dispatch(
@@ -138,7 +178,7 @@ impl MessageRouter {
             "routing session injection"
         );
 
-        self.gateway
+        self.gateway_for(&decision.session_key)
             .run_turn_with_trust(
                 &decision.session_key,
                 &injection.content,
@@ -239,14 +279,28 @@ impl MessageRouter {
         );
         debug!(parent: &span, sender = %msg.sender, "routing message");
         let channel = prompt_channel.unwrap_or(&msg.channel);
-        self.gateway
-            .run_turn_with_trust_and_cron_delivery(
+        let gateway = self.gateway_for(&decision.session_key);
+        let overrides = match gateway
+            .routed_model(
+                &decision.session_key,
+                decision.user_name.as_deref(),
+                &user_input,
+            )
+            .instrument(span.clone())
+            .await
+        {
+            Some(model) => TurnOverrides::default().with_model(model),
+            None => TurnOverrides::default(),
+        };
+        gateway
+            .run_turn_with_options(
                 &decision.session_key,
                 &user_input,
                 decision.trust,
                 decision.user_name.as_deref(),
                 Some(channel),
                 cron_delivery_mode,
+                overrides,
                 event_tx,
             )
             .instrument(span)
@@ -261,8 +315,34 @@ impl MessageRouter {
         event_tx: &mpsc::Sender<TurnEvent>,
     ) {
         let cmd = msg.content.trim();
+        if let Some(retry) = commands::parse_retry(cmd) {
+            let result = self
+                .gateway_for(&decision.session_key)
+                .retry_last_turn(
+                    &decision.session_key,
+                    decision.trust,
+                    decision.user_name.as_deref(),
+                    Some(&msg.channel),
+                    retry.model,
+                    event_tx.clone(),
+                )
+                .await;
+            if let Err(error) = result {
+                let _ = event_tx
+                    .send(TurnEvent::TextDelta(format!("Could not retry: {error}")))
+                    .await;
+                let _ = event_tx
+                    .send(TurnEvent::Done(TurnResult {
+                        messages: Vec::new(),
+                        usage: Usage::default(),
+                        hit_limit: false,
+                    }))
+                    .await;
+            }
+            return;
+        }
         let response = commands::handle_slash_command(
-            &self.gateway,
+            self.gateway_for(&decision.session_key),
             cmd,
             &decision.session_key,
             decision.trust,
@@ -310,14 +390,15 @@ impl MessageRouter {
             );
         }
 
+        let gateway = self.gateway_for(&decision.session_key);
         let should_respond = has_unresolved_mention
             || match group_config.trigger {
                 GroupTrigger::Always => true,
                 GroupTrigger::Llm => {
-                    let history_context = self.gateway.peek_group_history(&decision.session_key);
+                    let history_context = gateway.peek_group_history(&decision.session_key);
                     let full_input =
                         prepend_history_context(&msg.content, history_context.as_deref());
-                    self.gateway
+                    gateway
                         .evaluate_llm_trigger(
                             &decision.session_key,
                             &full_input,
@@ -329,17 +410,16 @@ impl MessageRouter {
                         .await
                 }
                 GroupTrigger::Mention | GroupTrigger::Regex => {
-                    group_trigger::evaluate_trigger(msg, group_config, &config.agent.id)
-                        == TriggerDecision::Respond
+                    group_trigger::evaluate_trigger(
+                        msg,
+                        group_config,
+                        &decision.session_key.agent_id,
+                    ) == TriggerDecision::Respond
                 }
             };
 
         if !should_respond {
-            self.gateway.record_group_history(
-                &decision.session_key,
-                msg,
-                group_config.history_limit,
-            );
+            gateway.record_group_history(&decision.session_key, msg, group_config.history_limit);
             debug!(
                 session = %decision.session_key,
                 trigger = %group_config.trigger,
@@ -356,7 +436,7 @@ impl MessageRouter {
         }
 
         // Drain buffered history and prepend to the message content.
-        let history_context = self.gateway.drain_group_history(&decision.session_key);
+        let history_context = gateway.drain_group_history(&decision.session_key);
         GroupTriggerOutcome::Respond(prepend_history_context(
             &msg.content,
             history_context.as_deref(),
@@ -378,16 +458,19 @@ impl MessageRouter {
         msg: &InboundMessage,
         prompt_channel: Option<String>,
     ) -> Result<(RouteDecision, String)> {
-        self.dispatch_collect_text_with_channel_and_cron_delivery(msg, prompt_channel, None)
-            .await
+        let (decision, text, _usage) = self
+            .dispatch_collect_text_with_channel_and_cron_delivery(msg, prompt_channel, None)
+            .await?;
+        Ok((decision, text))
     }
 
+    /// Collect the final reply text along with the turn's token usage.
     pub(crate) async fn dispatch_collect_text_with_channel_and_cron_delivery(
         &self,
         msg: &InboundMessage,
         prompt_channel: Option<String>,
         cron_delivery_mode: Option<CronDeliveryMode>,
-    ) -> Result<(RouteDecision, String)> {
+    ) -> Result<(RouteDecision, String, Usage)> {
         let (event_tx, mut event_rx) = mpsc::channel(64);
         let router = self.clone();
         let message = msg.clone();
@@ -404,6 +487,7 @@ impl MessageRouter {
         });
 
         let mut text = String::new();
+        let mut usage = Usage::default();
 
         while let Some(event) = event_rx.recv().await {
             match event {
@@ -419,7 +503,8 @@ impl MessageRouter {
                 TurnEvent::Error(message) => {
                     text = message;
                 }
-                TurnEvent::Done(_) => {
+                TurnEvent::Done(result) => {
+                    usage = result.usage;
                     break;
                 }
                 TurnEvent::ToolStart { .. }
@@ -433,7 +518,90 @@ impl MessageRouter {
             Err(error) => anyhow::bail!("router task failed: {error}"),
         };
 
-        Ok((decision, text))
+        Ok((decision, text, usage))
+    }
+
+    /// Dispatch a chat-channel message and deliver the reply through `send`.
+    ///
+    /// When `verbose` is false only the final assistant text is sent; when
+    /// true, accumulated text is flushed before each tool call. The silent
+    /// reply token is never sent, and a fallback error reaches the user if
+    /// the dispatch task dies before replying.
+    #[cfg(any(feature = "signal", feature = "telegram"))]
+    pub(crate) async fn dispatch_to_channel<F, Fut>(
+        &self,
+        msg: &InboundMessage,
+        verbose: bool,
+        mut send: F,
+    ) -> Result<()>
+    where
+        F: FnMut(String) -> Fut,
+        Fut: Future<Output = Result<()>>,
+    {
+        let (event_tx, mut event_rx) = mpsc::channel(64);
+        let router = self.clone();
+        let message = msg.clone();
+        let dispatch_task = tokio::spawn(async move { router.dispatch(&message, event_tx).await });
+
+        let mut text = String::new();
+
+        while let Some(event) = event_rx.recv().await {
+            match event {
+                TurnEvent::TextDelta(delta) => {
+                    text.push_str(&delta);
+                }
+                TurnEvent::ToolStart { .. } => {
+                    if verbose {
+                        flush_channel_text(&mut send, &mut text).await?;
+                    }
+                }
+                TurnEvent::ToolResult { .. } | TurnEvent::Compacting => {}
+                TurnEvent::AssistantMessage(ref message) => {
+                    if !verbose {
+                        // AssistantMessage is emitted only for the terminal reply,
+                        // so replace any accumulated deltas with the exact final
+                        // assistant text, even when the final reply is empty.
+                        text = message.text();
+                    }
+                }
+                TurnEvent::Error(message) => {
+                    text = message;
+                }
+                TurnEvent::Done(_) => {
+                    break;
+                }
+            }
+        }
+
+        // Suppress the silent reply token so NO_REPLY is never sent to the channel.
+        if group_trigger::is_silent_reply(&text) {
+            tracing::debug!(channel = %msg.channel, "suppressing silent reply token");
+            text.clear();
+        }
+
+        flush_channel_text(&mut send, &mut text).await?;
+
+        match dispatch_task.await {
+            Ok(result) => result.map(|_| ()),
+            Err(error) => {
+                // The dispatch task panicked or was cancelled. The user never
+                // received a response because the event channel was dropped
+                // before any text was produced. Send a fallback error message
+                // so the conversation doesn't silently hang.
+                tracing::error!(
+                    error = %error,
+                    channel = %msg.channel,
+                    "dispatch task failed, sending fallback error to user"
+                );
+                let _ = flush_channel_text(
+                    &mut send,
+                    &mut "Something went wrong processing that message. Please try again."
+                        .to_owned(),
+                )
+                .await;
+                anyhow::bail!("router task failed: {error}");
+            }
+        }
     }
 
     pub(crate) async fn review_as_needed_cron_delivery(
@@ -444,7 +612,7 @@ impl MessageRouter {
         prompt_channel: Option<&str>,
         proposed_response: &str,
     ) -> Result<bool> {
-        self.gateway
+        self.gateway_for(&decision.session_key)
             .evaluate_as_needed_cron_delivery(
                 &decision.session_key,
                 cron_message,
@@ -505,9 +673,33 @@ impl MessageRouter {
     }
 }
 
-pub(crate) fn route_message(msg: &InboundMessage, config: &Config) -> RouteDecision {
-    let agent_id = config.agent.id.clone();
+/// Channel of messages from authenticated remote IPC clients; the sender is
+/// the `[[users]]` name the client's token belongs to.
+pub(crate) const REMOTE_IPC_CHANNEL: &str = "ipc";
 
+/// The `[[users]]` entry a message comes from.
+fn find_user<'a>(
+    msg: &InboundMessage,
+    identity: &str,
+    config: &'a Config,
+) -> Option<&'a UserConfig> {
+    if msg.channel == REMOTE_IPC_CHANNEL {
+        // Remote IPC senders are already authenticated as a named user.
+        return config.users.iter().find(|user| user.name == msg.sender);
+    }
+    config.users.iter().find(|user| {
+        user.r#match
+            .iter()
+            .any(|pattern| pattern == identity || pattern == &msg.channel || pattern == &msg.sender)
+    })
+}
+
+/// Pick the session, trust level, and agent for an inbound message.
+///
+/// The agent comes from the `agent` binding of the matched `[[groups]]` entry
+/// for group messages, of the matched `[[users]]` entry otherwise, and from
+/// the session prefix for terminal sessions. Unbound messages go to `[agent]`.
+pub(crate) fn route_message(msg: &InboundMessage, config: &Config) -> RouteDecision {
     if msg.channel == "cron" {
         let rest = msg.sender.strip_prefix("cron:").unwrap_or(&msg.sender);
         let (cron_name, cron_user) = match rest.find(':') {
@@ -524,6 +716,7 @@ pub(crate) fn route_message(msg: &InboundMessage, config: &Config) -> RouteDecis
         };
 
         let trust = resolve_trust(user_trust, TrustLevel::Owner);
+        let agent_id = config.agent_id_for_user(user_name.as_deref()).to_owned();
 
         return RouteDecision {
             session_key: SessionKey {
@@ -537,19 +730,19 @@ pub(crate) fn route_message(msg: &InboundMessage, config: &Config) -> RouteDecis
 
     let identity = format!("{}:{}", msg.channel, msg.sender);
 
-    let explicit_kind = if msg.channel == "terminal:default" {
-        msg.reply_to
-            .as_deref()
-            .and_then(|session| parse_explicit_session_kind(session, &agent_id))
+    let explicit_session = if msg.channel == "terminal:default" || msg.channel == REMOTE_IPC_CHANNEL
+    {
+        msg.reply_to.as_deref().and_then(|session| {
+            config.all_agents().find_map(|agent| {
+                parse_explicit_session_kind(session, &agent.id).map(|kind| (agent.id.clone(), kind))
+            })
+        })
     } else {
         None
     };
+    let (explicit_agent, explicit_kind) = explicit_session.unzip();
 
-    let matched_user = config.users.iter().find(|user| {
-        user.r#match.iter().any(|pattern| {
-            pattern == &identity || pattern == &msg.channel || pattern == &msg.sender
-        })
-    });
+    let matched_user = find_user(msg, &identity, config);
 
     let group_config = find_group_config(msg, config);
 
@@ -567,6 +760,15 @@ pub(crate) fn route_message(msg: &InboundMessage, config: &Config) -> RouteDecis
     );
     let user_name = matched_user.map(|user| user.name.clone());
 
+    let agent_id = explicit_agent.unwrap_or_else(|| {
+        let binding = if msg.is_group {
+            group_config.and_then(|gc| gc.agent.as_deref())
+        } else {
+            matched_user.and_then(|user| user.agent.as_deref())
+        };
+        config.bound_agent_id(binding).to_owned()
+    });
+
     let group_context = msg.is_group
         || explicit_kind
             .as_ref()
@@ -652,6 +854,21 @@ pub(crate) fn find_group_config<'a>(
     })
 }
 
+/// Send accumulated text through `send` and clear the buffer. Whitespace-only
+/// text is dropped.
+#[cfg(any(feature = "signal", feature = "telegram"))]
+async fn flush_channel_text<F, Fut>(send: &mut F, text: &mut String) -> Result<()>
+where
+    F: FnMut(String) -> Fut,
+    Fut: Future<Output = Result<()>>,
+{
+    if text.trim().is_empty() {
+        text.clear();
+        return Ok(());
+    }
+    send(std::mem::take(text)).await
+}
+
 /// The `@mention` sentinel injected by inbound parsing when a U+FFFC
 /// placeholder could not be resolved to a real user name.
 const UNRESOLVED_MENTION_SENTINEL: &str = "@mention";
@@ -695,6 +912,11 @@ fn parse_explicit_session_kind(session: &str, agent_id: &str) -> Option<SessionK
         return Some(SessionKind::Subagent(uuid));
     }
 
+    if let Some(isolated) = rest.strip_prefix("isolated:") {
+        let uuid = uuid::Uuid::parse_str(isolated).ok()?;
+        return Some(SessionKind::Isolated(uuid));
+    }
+
     None
 }
 
@@ -816,6 +1038,7 @@ match = ["signal:bob-uuid"]
             reply_to: reply_to.map(ToOwned::to_owned),
             kind: InboundKind::Text,
             message_timestamp: None,
+            message_id: None,
             group_revision: None,
         }
     }
@@ -946,6 +1169,8 @@ match = ["signal:bob-uuid"]
             default_trust: TrustLevel::Familiar,
             trust_ceiling: TrustCeiling::None,
             history_limit: 50,
+            agent: None,
+            routing: None,
         });
         // mallory-uuid is not in [[users]] → gets group's default_trust
         let msg = inbound("signal", "mallory-uuid", Some("group:deadbeef"), true, None);
@@ -966,6 +1191,8 @@ match = ["signal:bob-uuid"]
             default_trust: TrustLevel::Familiar,
             trust_ceiling: TrustCeiling::Fixed(TrustLevel::Familiar),
             history_limit: 50,
+            agent: None,
+            routing: None,
         });
         // alice has Full trust but ceiling is Familiar
         let msg = inbound("signal", "alice-uuid", Some("group:deadbeef"), true, None);
@@ -1002,6 +1229,8 @@ match = ["signal:bob-uuid"]
             default_trust: TrustLevel::Familiar,
             trust_ceiling: TrustCeiling::None,
             history_limit: 50,
+            agent: None,
+            routing: None,
         });
         let decision = RouteDecision {
             session_key: SessionKey {
@@ -1029,6 +1258,8 @@ match = ["signal:bob-uuid"]
             default_trust: TrustLevel::Familiar,
             trust_ceiling: TrustCeiling::None,
             history_limit: 50,
+            agent: None,
+            routing: None,
         });
         let msg = inbound("signal", "alice-uuid", Some("group:aabb"), true, None);
         assert!(find_group_config(&msg, &cfg).is_some());
@@ -1047,6 +1278,8 @@ match = ["signal:bob-uuid"]
             default_trust: TrustLevel::Familiar,
             trust_ceiling: TrustCeiling::None,
             history_limit: 50,
+            agent: None,
+            routing: None,
         });
         let msg = inbound("signal", "alice-uuid", Some("group:anything"), true, None);
         assert!(find_group_config(&msg, &cfg).is_some());
@@ -1072,6 +1305,8 @@ match = ["signal:bob-uuid"]
             default_trust: TrustLevel::Familiar,
             trust_ceiling: TrustCeiling::None,
             history_limit: 50,
+            agent: None,
+            routing: None,
         });
         let key = SessionKey {
             agent_id: "reid".into(),
@@ -1252,7 +1487,7 @@ match = ["signal:bob-uuid"]
             make_router_with_provider_and_executor(&config, provider, Arc::new(executor));
 
         let msg = inbound("cron", "cron:briefing:alice", None, false, None);
-        let (_decision, text) = router
+        let (_decision, text, _usage) = router
             .dispatch_collect_text_with_channel_and_cron_delivery(
                 &msg,
                 Some("signal".to_owned()),
@@ -1614,6 +1849,7 @@ match = ["signal:bob-uuid"]
             reply_to: None,
             kind: InboundKind::Text,
             message_timestamp: None,
+            message_id: None,
             group_revision: None,
         }
     }
@@ -1629,6 +1865,7 @@ match = ["signal:bob-uuid"]
             reply_to: None,
             kind: InboundKind::Command,
             message_timestamp: None,
+            message_id: None,
             group_revision: None,
         }
     }
@@ -2262,6 +2499,29 @@ models = ["gpt-5-codex"]
         );
     }
 
+    #[tokio::test]
+    async fn slash_usage_reports_recorded_calls() {
+        let config = test_config();
+        let (router, _gw) = make_router_and_gateway(&config);
+        let msg = inbound_with_content("signal", "alice-uuid", "hello");
+        dispatch_and_collect_text(&router, &msg).await;
+
+        let msg = inbound_command("signal", "alice-uuid", "/usage user 7");
+        let (_decision, text) = dispatch_and_collect_text(&router, &msg).await;
+        assert!(text.contains("last 7 days"), "{text}");
+        assert!(text.contains("By user:"), "{text}");
+        assert!(!text.contains("By model:"), "{text}");
+        let alice = text
+            .lines()
+            .find(|line| line.trim_start().starts_with("alice"))
+            .unwrap_or_else(|| panic!("no line for alice: {text}"));
+        assert!(alice.contains("1 calls"), "{alice}");
+
+        let msg = inbound_command("signal", "alice-uuid", "/usage weekly");
+        let (_decision, text) = dispatch_and_collect_text(&router, &msg).await;
+        assert!(text.starts_with("Usage: /usage"), "{text}");
+    }
+
     #[tokio::test]
     async fn slash_commands_require_full_trust() {
         let config = test_config();
@@ -2362,4 +2622,175 @@ models = ["gpt-5-codex"]
             "cumulative should show correct totals, got: {text}"
         );
     }
+
+    fn multi_agent_config() -> Config {
+        toml::from_str(
+            r#"
+[agent]
+id = "reid"
+model = "test"
+
+[[agents]]
+id = "work"
+model = "test"
+
+[[users]]
+name = "alice"
+trust = "full"
+match = ["signal:alice-uuid"]
+agent = "work"
+
+[[users]]
+name = "bob"
+trust = "inner"
+match = ["signal:bob-uuid"]
+
+[[groups]]
+match = ["signal:group:work-chat"]
+trigger = "always"
+agent = "work"
+
+[[groups]]
+match = ["signal:group:family"]
+trigger = "always"
+"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn route_message_picks_bound_agent() {
+        let config = multi_agent_config();
+        let agent_for = |msg: &InboundMessage| route_message(msg, &config).session_key.agent_id;
+
+        assert_eq!(
+            agent_for(&inbound("signal", "alice-uuid", None, false, None)),
+            "work"
+        );
+        assert_eq!(
+            agent_for(&inbound("signal", "bob-uuid", None, false, None)),
+            "reid"
+        );
+        // Group bindings win over the sender's own binding.
+        assert_eq!(
+            agent_for(&inbound(
+                "signal",
+                "bob-uuid",
+                Some("group:work-chat"),
+                true,
+                None
+            )),
+            "work"
+        );
+        assert_eq!(
+            agent_for(&inbound(
+                "signal",
+                "alice-uuid",
+                Some("group:family"),
+                true,
+                None
+            )),
+            "reid"
+        );
+        assert_eq!(
+            agent_for(&inbound("cron", "cron:digest:alice", None, false, None)),
+            "work"
+        );
+        assert_eq!(
+            agent_for(&inbound("cron", "cron:digest", None, false, None)),
+            "reid"
+        );
+
+        let terminal = route_message(
+            &inbound("terminal:default", "tui", None, false, Some("work:main")),
+            &config,
+        );
+        assert_eq!(terminal.session_key.agent_id, "work");
+        assert_eq!(terminal.session_key.kind, SessionKind::Main);
+        assert_eq!(
+            agent_for(&inbound(
+                "terminal:default",
+                "tui",
+                None,
+                false,
+                Some("main")
+            )),
+            "reid"
+        );
+    }
+
+    #[test]
+    fn remote_ipc_routes_as_the_authenticated_user() {
+        let config = test_config();
+
+        let own = route_message(
+            &inbound(REMOTE_IPC_CHANNEL, "bob", None, false, None),
+            &config,
+        );
+        assert_eq!(own.user_name.as_deref(), Some("bob"));
+        assert_eq!(own.trust, TrustLevel::Inner);
+        assert_eq!(own.session_key.kind, SessionKind::Dm("ipc:bob".to_owned()));
+
+        let main = route_message(
+            &inbound(REMOTE_IPC_CHANNEL, "bob", None, false, Some("main")),
+            &config,
+        );
+        assert_eq!(main.session_key.kind, SessionKind::Main);
+        assert_eq!(main.trust, TrustLevel::Inner);
+
+        let unknown = route_message(
+            &inbound(REMOTE_IPC_CHANNEL, "mallory", None, false, None),
+            &config,
+        );
+        assert_eq!(unknown.user_name, None);
+        assert_eq!(unknown.trust, TrustLevel::Public);
+    }
+
+    #[tokio::test]
+    async fn dispatch_runs_turn_on_bound_agent_gateway() {
+        let config = multi_agent_config();
+        let default_workspace = test_workspace();
+        let work_workspace = test_workspace();
+        let make_gateway = |agent: &str, workspace: &tempfile::TempDir, reply: &str| {
+            Arc::new(
+                Gateway::new(
+                    shared_config(config.for_agent(agent).unwrap()),
+                    workspace.path().to_path_buf(),
+                    ProviderRegistry::new(Arc::new(FakeProvider::new(reply))),
+                    Arc::new(DefaultExecutor::new()),
+                    None,
+                    None,
+                )
+                .unwrap(),
+            )
+        };
+        let default_gateway = make_gateway("reid", &default_workspace, "from reid");
+        let work_gateway = make_gateway("work", &work_workspace, "from work");
+        let router = MessageRouter::new(shared_config(config.clone()), default_gateway)
+            .with_agent(Arc::clone(&work_gateway));
+
+        let alice = inbound("signal", "alice-uuid", None, false, None);
+        let (decision, text) = router.dispatch_collect_text(&alice).await.unwrap();
+        assert_eq!(text, "from work");
+        assert_eq!(decision.session_key.agent_id, "work");
+        assert!(work_gateway.list_sessions().contains(&decision.session_key));
+
+        let bob = inbound("signal", "bob-uuid", None, false, None);
+        let (_, text) = router.dispatch_collect_text(&bob).await.unwrap();
+        assert_eq!(text, "from reid");
+
+        let (gateway, key) = router.resolve_session("work:main").unwrap();
+        assert_eq!(gateway.agent_id(), "work");
+        assert_eq!(key.kind, SessionKind::Main);
+        let (gateway, _) = router.resolve_session("main").unwrap();
+        assert_eq!(gateway.agent_id(), "reid");
+        assert!(router.resolve_session("nobody:main").is_none());
+        assert_eq!(
+            router
+                .gateways()
+                .map(|gateway| gateway.agent_id())
+                .collect::<Vec<_>>(),
+            vec!["reid", "work"]
+        );
+    }
 }
@@ -182,6 +182,13 @@ impl SandboxExecutor {
         match result {
             Err(e) => Ok(ToolOutput::error(format!("sandbox exec failed: {e}"))),
             Ok(output) => {
+                debug!(
+                    exit_code = output.exit_code,
+                    landlock = output.enforced.landlock,
+                    seccomp = output.enforced.seccomp,
+                    network_namespace = output.enforced.network_namespaces,
+                    "sandbox: exec enforcement"
+                );
                 let mut combined = output.stdout;
                 if !output.stderr.is_empty() {
                     if !combined.is_empty() {
@@ -3,7 +3,7 @@ use chrono::Utc;
 use chrono_tz::Tz;
 use coop_core::{InboundKind, InboundMessage};
 use cron::Schedule;
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::str::FromStr;
 use std::sync::Arc;
 use std::time::Duration;
@@ -11,10 +11,11 @@ use tokio::sync::mpsc;
 use tokio_util::sync::CancellationToken;
 use tracing::{Instrument, debug, error, info, info_span, warn};
 
-use crate::config::{CronConfig, SharedConfig};
+use crate::config::{CronCatchUp, CronConfig, SharedConfig};
+use crate::cron_jobs::{CronJobStore, all_cron_jobs};
 use crate::cron_runner::{
-    CronCommand, DeliverySender, announce_to_session, deliver_to_target, run_manual_cron_by_name,
-    run_scheduled_cron,
+    CronCommand, DeliverySender, announce_to_session, cron_session_key, deliver_to_target,
+    run_catch_up_cron, run_manual_cron_by_name, run_scheduled_cron,
 };
 use crate::cron_timezone::{next_cron_fire_after, resolve_cron_timezone};
 use crate::reminder::{Reminder, ReminderStore};
@@ -31,6 +32,9 @@ use crate::cron_tool::CronToolExecutor;
 #[cfg(test)]
 use coop_core::OutboundMessage;
 
+/// Most runs replayed for one job under `catch_up = "all"`.
+const MAX_CATCH_UP_RUNS: usize = 24;
+
 #[derive(Clone)]
 struct ParsedCron {
     cfg: CronConfig,
@@ -78,6 +82,7 @@ pub(crate) async fn run_scheduler_with_notify(
         cron_notify,
         reminders,
         None,
+        None,
     )
     .await;
 }
@@ -90,6 +95,7 @@ pub(crate) async fn run_scheduler_with_notify_and_commands(
     shutdown: CancellationToken,
     cron_notify: Option<Arc<tokio::sync::Notify>>,
     reminders: Option<ReminderStore>,
+    cron_jobs: Option<CronJobStore>,
     command_rx: mpsc::Receiver<CronCommand>,
 ) {
     run_scheduler_inner(
@@ -99,6 +105,7 @@ pub(crate) async fn run_scheduler_with_notify_and_commands(
         shutdown,
         cron_notify,
         reminders,
+        cron_jobs,
         Some(command_rx),
     )
     .await;
@@ -112,6 +119,7 @@ async fn run_scheduler_inner(
     shutdown: CancellationToken,
     cron_notify: Option<Arc<tokio::sync::Notify>>,
     reminders: Option<ReminderStore>,
+    cron_jobs: Option<CronJobStore>,
     mut command_rx: Option<mpsc::Receiver<CronCommand>>,
 ) {
     info!("scheduler started");
@@ -128,25 +136,33 @@ async fn run_scheduler_inner(
     // (which queries from wall-clock time) can return the same tick
     // twice when the sleep timer wakes slightly early.
     let mut last_fired: HashMap<String, chrono::DateTime<Utc>> = HashMap::new();
+    let mut caught_up = false;
 
     loop {
-        // Re-read cron entries from shared config on each iteration so
-        // hot-reloaded changes are picked up without a restart.
+        // Re-read cron entries from shared config and the managed job
+        // store on each iteration so hot-reloaded and agent-created
+        // changes are picked up without a restart.
         let snapshot = config.load();
-        if snapshot.cron != last_cron {
-            parsed = parse_and_validate(&snapshot.cron, &snapshot.users, deliver_tx.as_ref());
-            if !snapshot.cron.is_empty() {
+        let cron = all_cron_jobs(&snapshot, cron_jobs.as_ref());
+        if cron != last_cron {
+            parsed = parse_and_validate(&cron, &snapshot.users, deliver_tx.as_ref());
+            if !cron.is_empty() {
                 info!(
                     count = parsed.len(),
-                    total = snapshot.cron.len(),
+                    total = cron.len(),
                     "scheduler cron entries updated"
                 );
             }
-            last_cron = snapshot.cron.clone();
+            last_cron = cron;
         }
         // Drop the config snapshot so it isn't held across the sleep.
         drop(snapshot);
 
+        if !caught_up {
+            spawn_catch_up(&parsed, &config, &router, deliver_tx.clone(), Utc::now());
+            caught_up = true;
+        }
+
         // Fire any due reminders before computing the next sleep.
         if let Some(ref store) = reminders {
             let due = store.take_due();
@@ -195,6 +211,7 @@ async fn run_scheduler_inner(
                         maybe_command,
                         &mut command_rx,
                         &config,
+                        cron_jobs.as_ref(),
                         &router,
                         deliver_tx.clone(),
                     );
@@ -246,6 +263,7 @@ async fn run_scheduler_inner(
                                 &router,
                                 deliver_tx.as_ref(),
                                 &sched_config,
+                                fire_time,
                             )
                             .await
                             {
@@ -262,6 +280,7 @@ async fn run_scheduler_inner(
                     maybe_command,
                     &mut command_rx,
                     &config,
+                    cron_jobs.as_ref(),
                     &router,
                     deliver_tx.clone(),
                 );
@@ -277,6 +296,115 @@ async fn run_scheduler_inner(
     }
 }
 
+/// Replay fire times missed while the gateway was down, as each job's
+/// `catch_up` policy asks. Jobs that never ran have nothing to replay.
+/// Replays run one at a time, oldest first.
+fn spawn_catch_up(
+    parsed: &[ParsedCron],
+    config: &SharedConfig,
+    router: &Arc<MessageRouter>,
+    deliver_tx: Option<DeliverySender>,
+    now: chrono::DateTime<Utc>,
+) {
+    let snapshot = config.load();
+    let mut runs = Vec::new();
+    for entry in parsed {
+        if entry.cfg.catch_up == CronCatchUp::None {
+            continue;
+        }
+        let agent_id = snapshot.agent_id_for_user(entry.cfg.user.as_deref());
+        let session_key = cron_session_key(agent_id, &entry.cfg.name);
+        let last = match router
+            .gateway_for(&session_key)
+            .cron_history()
+            .last_scheduled(&entry.cfg.name)
+        {
+            Ok(Some(last)) => last,
+            Ok(None) => continue,
+            Err(error) => {
+                warn!(cron.name = %entry.cfg.name, error = %error, "failed to read cron history");
+                continue;
+            }
+        };
+        let missed = missed_fire_times(
+            &entry.schedule,
+            entry.timezone,
+            last,
+            now,
+            entry.cfg.catch_up,
+        );
+        if !missed.is_empty() {
+            info!(
+                cron.name = %entry.cfg.name,
+                cron.catch_up = %entry.cfg.catch_up,
+                last_run = %last,
+                missed = missed.len(),
+                "replaying missed cron runs"
+            );
+        }
+        runs.extend(
+            missed
+                .into_iter()
+                .map(|fire_time| (entry.clone(), fire_time)),
+        );
+    }
+    drop(snapshot);
+
+    if runs.is_empty() {
+        return;
+    }
+    runs.sort_by_key(|(_, fire_time)| *fire_time);
+
+    let router = Arc::clone(router);
+    let config = Arc::clone(config);
+    tokio::spawn(async move {
+        for (entry, fire_time) in runs {
+            if let Err(error) = run_catch_up_cron(
+                &entry.cfg,
+                entry.timezone,
+                &router,
+                deliver_tx.as_ref(),
+                &config,
+                fire_time,
+            )
+            .await
+            {
+                error!(cron.name = %entry.cfg.name, error = %error, "catch-up cron failed");
+            }
+        }
+    });
+}
+
+/// Fire times after `last` and up to `now` that `policy` says to replay,
+/// oldest first.
+fn missed_fire_times(
+    schedule: &Schedule,
+    timezone: Tz,
+    last: chrono::DateTime<Utc>,
+    now: chrono::DateTime<Utc>,
+    policy: CronCatchUp,
+) -> Vec<chrono::DateTime<Utc>> {
+    let keep = match policy {
+        CronCatchUp::None => return Vec::new(),
+        CronCatchUp::Last => 1,
+        CronCatchUp::All => MAX_CATCH_UP_RUNS,
+    };
+
+    let mut missed = VecDeque::with_capacity(keep);
+    let mut after = last;
+    while let Some(fire_time) = next_cron_fire_after(schedule, timezone, after) {
+        if fire_time > now {
+            break;
+        }
+        if missed.len() == keep {
+            missed.pop_front();
+        }
+        missed.push_back(fire_time);
+        after = fire_time;
+    }
+    missed.into()
+}
+
 async fn next_command(command_rx: &mut Option<mpsc::Receiver<CronCommand>>) -> Option<CronCommand> {
     match command_rx {
         Some(rx) => rx.recv().await,
@@ -288,6 +416,7 @@ fn handle_cron_command(
     maybe_command: Option<CronCommand>,
     command_rx: &mut Option<mpsc::Receiver<CronCommand>>,
     config: &SharedConfig,
+    cron_jobs: Option<&CronJobStore>,
     router: &Arc<MessageRouter>,
     deliver_tx: Option<DeliverySender>,
 ) {
@@ -315,6 +444,7 @@ fn handle_cron_command(
 
             let router = Arc::clone(router);
             let config = Arc::clone(config);
+            let cron_jobs = cron_jobs.cloned();
             tokio::spawn(async move {
                 let result = run_manual_cron_by_name(
                     &name,
@@ -323,6 +453,7 @@ fn handle_cron_command(
                     &router,
                     deliver_tx.as_ref(),
                     &config,
+                    cron_jobs.as_ref(),
                 )
                 .await;
 
@@ -360,7 +491,7 @@ fn parse_and_validate(
                     delivery.target = %delivery.target,
                     "cron delivery configured but no delivery sender available"
                 );
-            } else if delivery.channel != "signal" {
+            } else if !matches!(delivery.channel.as_str(), "signal" | "telegram") {
                 error!(
                     cron.name = %entry.name,
                     delivery.channel = %delivery.channel,
@@ -404,7 +535,8 @@ async fn fire_cron(
     let config_snapshot = shared_config.load();
     let timezone = resolve_cron_timezone(cfg, &config_snapshot.users).unwrap_or(chrono_tz::UTC);
     drop(config_snapshot);
-    let result = run_scheduled_cron(cfg, timezone, router, deliver_tx, shared_config).await;
+    let result =
+        run_scheduled_cron(cfg, timezone, router, deliver_tx, shared_config, Utc::now()).await;
     if let Err(error) = result {
         error!(cron.name = %cfg.name, error = %error, "scheduled cron failed");
     }
@@ -501,6 +633,7 @@ fn build_reminder_inbound(reminder: &Reminder) -> InboundMessage {
         reply_to: None,
         kind: InboundKind::Text,
         message_timestamp: None,
+        message_id: None,
         group_revision: None,
     }
 }
@@ -513,7 +646,10 @@ async fn dispatch_reminder(
     deliver_tx: Option<&DeliverySender>,
     shared_config: &SharedConfig,
 ) {
-    let agent_id = shared_config.load().agent.id.clone();
+    let agent_id = shared_config
+        .load()
+        .agent_id_for_user(reminder.user.as_deref())
+        .to_owned();
 
     match router
         .dispatch_collect_text_with_channel(inbound, prompt_channel)
@@ -607,6 +743,29 @@ mod tests {
         assert!(parse_cron("* * *").is_err());
     }
 
+    #[test]
+    fn missed_fire_times_follow_policy() {
+        use chrono::TimeZone;
+        let at = |day, hour| Utc.with_ymd_and_hms(2026, 3, day, hour, 0, 0).unwrap();
+        let daily = parse_cron("0 8 * * *").unwrap();
+        let (last, now) = (at(1, 8), at(4, 9));
+
+        assert_eq!(
+            missed_fire_times(&daily, chrono_tz::UTC, last, now, CronCatchUp::All),
+            vec![at(2, 8), at(3, 8), at(4, 8)]
+        );
+        assert_eq!(
+            missed_fire_times(&daily, chrono_tz::UTC, last, now, CronCatchUp::Last),
+            vec![at(4, 8)]
+        );
+        assert!(missed_fire_times(&daily, chrono_tz::UTC, last, now, CronCatchUp::None).is_empty());
+
+        let hourly = parse_cron("0 * * * *").unwrap();
+        let capped = missed_fire_times(&hourly, chrono_tz::UTC, last, now, CronCatchUp::All);
+        assert_eq!(capped.len(), MAX_CATCH_UP_RUNS);
+        assert_eq!(capped.last(), Some(&at(4, 9)));
+    }
+
     #[test]
     fn fire_cron_encodes_sender_with_user() {
         let cfg = CronConfig {
@@ -619,6 +778,9 @@ mod tests {
             deliver: None,
             review_prompt: None,
             sandbox: None,
+            budget: None,
+            fallback: Vec::new(),
+            catch_up: CronCatchUp::None,
         };
         let sender = match &cfg.user {
             Some(user) => format!("cron:{}:{}", cfg.name, user),
@@ -639,6 +801,9 @@ mod tests {
             deliver: None,
             review_prompt: None,
             sandbox: None,
+            budget: None,
+            fallback: Vec::new(),
+            catch_up: CronCatchUp::None,
         };
         let sender = match &cfg.user {
             Some(user) => format!("cron:{}:{}", cfg.name, user),
@@ -680,6 +845,9 @@ mod tests {
             deliver: None,
             review_prompt: None,
             sandbox: None,
+            budget: None,
+            fallback: Vec::new(),
+            catch_up: CronCatchUp::None,
         }];
         let (shared, router, _gateway) =
             make_shared_config_and_router(None, &cron, "cron response ok");
@@ -711,6 +879,12 @@ mod tests {
             r#match: vec!["terminal:default".to_owned()],
             timezone: None,
             sandbox: None,
+            agent: None,
+            ipc_token_env: None,
+            budget: None,
+            fallback: Vec::new(),
+            routing: None,
+            transcribe: None,
         };
         let cron = vec![CronConfig {
             name: "test".to_owned(),
@@ -722,6 +896,9 @@ mod tests {
             deliver: None,
             review_prompt: None,
             sandbox: None,
+            budget: None,
+            fallback: Vec::new(),
+            catch_up: CronCatchUp::None,
         }];
         let (shared, router, gateway) =
             make_shared_config_and_router(Some(&[alice_user]), &cron, "cron response ok");
@@ -754,6 +931,59 @@ mod tests {
         assert_eq!(key.to_string(), "test:cron:test");
     }
 
+    #[tokio::test]
+    async fn scheduler_replays_missed_run_and_records_it() {
+        let cron = vec![CronConfig {
+            name: "briefing".to_owned(),
+            cron: "0 8 * * *".to_owned(),
+            timezone: None,
+            message: "morning briefing".to_owned(),
+            user: None,
+            delivery: None,
+            deliver: None,
+            review_prompt: None,
+            sandbox: None,
+            budget: None,
+            fallback: Vec::new(),
+            catch_up: CronCatchUp::Last,
+        }];
+        let (shared, router, gateway) =
+            make_shared_config_and_router(None, &cron, "cron response ok");
+        let last_run = Utc::now() - chrono::Duration::days(3);
+        gateway.record_cron_run(&crate::cron_history::CronRun {
+            cron_name: "briefing".to_owned(),
+            trigger: "scheduled".to_owned(),
+            scheduled_for: Some(last_run),
+            started_at: last_run,
+            finished_at: last_run,
+            status: "completed".to_owned(),
+            error: None,
+            input_tokens: 0,
+            output_tokens: 0,
+            delivered_to: 0,
+            attempted_to: 0,
+        });
+        let router = Arc::new(router);
+        let cancel = CancellationToken::new();
+
+        let sched_cancel = cancel.clone();
+        let handle = tokio::spawn(async move {
+            run_scheduler(shared, router, None, sched_cancel).await;
+        });
+
+        tokio::time::sleep(Duration::from_millis(500)).await;
+        cancel.cancel();
+        let _ = tokio::time::timeout(Duration::from_secs(2), handle).await;
+
+        let runs = gateway.cron_history().recent(Some("briefing"), 10).unwrap();
+        assert_eq!(runs.len(), 2, "expected exactly one replay: {runs:?}");
+        assert_eq!(runs[0].trigger, "catch_up");
+        assert_eq!(runs[0].status, "completed");
+        let replayed = runs[0].scheduled_for.unwrap();
+        assert!(replayed > last_run + chrono::Duration::days(1));
+        assert!(replayed <= Utc::now());
+    }
+
     #[tokio::test]
     async fn scheduler_fires_without_user() {
         let cron = vec![CronConfig {
@@ -766,6 +996,9 @@ mod tests {
             deliver: None,
             review_prompt: None,
             sandbox: None,
+            budget: None,
+            fallback: Vec::new(),
+            catch_up: CronCatchUp::None,
         }];
         let (shared, router, gateway) =
             make_shared_config_and_router(None, &cron, "cron response ok");
@@ -800,6 +1033,12 @@ mod tests {
             r#match: vec![],
             timezone: None,
             sandbox: None,
+            agent: None,
+            ipc_token_env: None,
+            budget: None,
+            fallback: Vec::new(),
+            routing: None,
+            transcribe: None,
         }];
         let (shared, router, gateway) =
             make_shared_config_and_router(Some(&alice), &[], "cron response ok");
@@ -814,6 +1053,9 @@ mod tests {
             deliver: None,
             review_prompt: None,
             sandbox: None,
+            budget: None,
+            fallback: Vec::new(),
+            catch_up: CronCatchUp::None,
         };
 
         fire_cron(&cfg, &router, None, &shared).await;
@@ -843,6 +1085,9 @@ mod tests {
             deliver: None,
             review_prompt: None,
             sandbox: None,
+            budget: None,
+            fallback: Vec::new(),
+            catch_up: CronCatchUp::None,
         };
 
         fire_cron(&cfg, &router, None, &shared).await;
@@ -872,6 +1117,9 @@ mod tests {
             deliver: None,
             review_prompt: None,
             sandbox: None,
+            budget: None,
+            fallback: Vec::new(),
+            catch_up: CronCatchUp::None,
         };
 
         let cron_key = SessionKey {
@@ -967,7 +1215,7 @@ mod tests {
     #[derive(Debug)]
     struct SequenceProvider {
         model: ModelInfo,
-        responses: std::sync::Mutex<std::collections::VecDeque<String>>,
+        responses: std::sync::Mutex<VecDeque<String>>,
     }
 
     impl SequenceProvider {
@@ -1046,6 +1294,9 @@ mod tests {
             }),
             review_prompt: None,
             sandbox: None,
+            budget: None,
+            fallback: Vec::new(),
+            catch_up: CronCatchUp::None,
         };
 
         fire_cron(&cfg, &router, Some(&deliver_tx), &shared).await;
@@ -1056,6 +1307,37 @@ mod tests {
         assert_eq!(msg.content, "cron response ok");
     }
 
+    #[tokio::test]
+    async fn delivery_router_forwards_by_channel() {
+        let (signal_tx, mut signal_rx) = mpsc::channel::<OutboundMessage>(8);
+        let (telegram_tx, mut telegram_rx) = mpsc::channel::<OutboundMessage>(8);
+        let deliver_tx = crate::cron_runner::spawn_delivery_router(vec![
+            ("signal", DeliverySender::new(signal_tx)),
+            ("telegram", DeliverySender::new(telegram_tx)),
+        ])
+        .unwrap();
+
+        deliver_tx
+            .send("whatsapp", "nobody", "dropped")
+            .await
+            .unwrap();
+        deliver_tx.send("telegram", "42", "hi tg").await.unwrap();
+        deliver_tx
+            .send("signal", "alice-uuid", "hi signal")
+            .await
+            .unwrap();
+
+        let telegram = telegram_rx.recv().await.unwrap();
+        assert_eq!(
+            (telegram.target.as_str(), telegram.content.as_str()),
+            ("42", "hi tg")
+        );
+        let signal = signal_rx.recv().await.unwrap();
+        assert_eq!(signal.target, "alice-uuid");
+        assert!(telegram_rx.try_recv().is_err());
+        assert!(crate::cron_runner::spawn_delivery_router(Vec::new()).is_none());
+    }
+
     #[tokio::test]
     async fn fire_cron_with_delivery_announces_to_dm_session() {
         let (shared, router, gateway) =
@@ -1076,6 +1358,9 @@ mod tests {
             }),
             review_prompt: None,
             sandbox: None,
+            budget: None,
+            fallback: Vec::new(),
+            catch_up: CronCatchUp::None,
         };
 
         fire_cron(&cfg, &router, Some(&deliver_tx), &shared).await;
@@ -1116,6 +1401,9 @@ mod tests {
             }),
             review_prompt: None,
             sandbox: None,
+            budget: None,
+            fallback: Vec::new(),
+            catch_up: CronCatchUp::None,
         };
 
         fire_cron(&cfg, &router, Some(&deliver_tx), &shared).await;
@@ -1170,6 +1458,9 @@ mod tests {
             }),
             review_prompt: None,
             sandbox: None,
+            budget: None,
+            fallback: Vec::new(),
+            catch_up: CronCatchUp::None,
         };
 
         fire_cron(&cfg, &router, Some(&deliver_tx), &shared).await;
@@ -1197,6 +1488,9 @@ mod tests {
             deliver: None,
             review_prompt: None,
             sandbox: None,
+            budget: None,
+            fallback: Vec::new(),
+            catch_up: CronCatchUp::None,
         };
 
         fire_cron(&cfg, &router, Some(&deliver_tx), &shared).await;
@@ -1225,6 +1519,9 @@ mod tests {
             }),
             review_prompt: None,
             sandbox: None,
+            budget: None,
+            fallback: Vec::new(),
+            catch_up: CronCatchUp::None,
         };
 
         fire_cron(&cfg, &router, Some(&deliver_tx), &shared).await;
@@ -1250,6 +1547,9 @@ mod tests {
             }),
             review_prompt: None,
             sandbox: None,
+            budget: None,
+            fallback: Vec::new(),
+            catch_up: CronCatchUp::None,
         };
 
         fire_cron(&cfg, &router, None, &shared).await;
@@ -1280,6 +1580,9 @@ mod tests {
             }),
             review_prompt: None,
             sandbox: None,
+            budget: None,
+            fallback: Vec::new(),
+            catch_up: CronCatchUp::None,
         };
 
         fire_cron(&cfg, &router, Some(&deliver_tx), &shared).await;
@@ -1322,6 +1625,9 @@ mod tests {
             }),
             review_prompt: None,
             sandbox: None,
+            budget: None,
+            fallback: Vec::new(),
+            catch_up: CronCatchUp::None,
         };
 
         fire_cron(&cfg, &router, Some(&deliver_tx), &shared).await;
@@ -1355,6 +1661,9 @@ mod tests {
             }),
             review_prompt: None,
             sandbox: None,
+            budget: None,
+            fallback: Vec::new(),
+            catch_up: CronCatchUp::None,
         };
 
         fire_cron(&cfg, &router, Some(&deliver_tx), &shared).await;
@@ -1395,6 +1704,9 @@ mod tests {
             }),
             review_prompt: None,
             sandbox: None,
+            budget: None,
+            fallback: Vec::new(),
+            catch_up: CronCatchUp::None,
         };
 
         fire_cron(&cfg, &router, Some(&deliver_tx), &shared).await;
@@ -1434,6 +1746,9 @@ mod tests {
             }),
             review_prompt: None,
             sandbox: None,
+            budget: None,
+            fallback: Vec::new(),
+            catch_up: CronCatchUp::None,
         };
 
         fire_cron(&cfg, &router, Some(&deliver_tx), &shared).await;
@@ -1460,6 +1775,9 @@ mod tests {
             deliver: None,
             review_prompt: None,
             sandbox: None,
+            budget: None,
+            fallback: Vec::new(),
+            catch_up: CronCatchUp::None,
         };
 
         fire_cron(&cfg, &router, None, &shared).await;
@@ -1480,6 +1798,9 @@ mod tests {
             }),
             review_prompt: None,
             sandbox: None,
+            budget: None,
+            fallback: Vec::new(),
+            catch_up: CronCatchUp::None,
         }];
         let (shared, router, gateway) =
             make_shared_config_and_router(None, &cron, "cron response ok");
@@ -1553,6 +1874,9 @@ mod tests {
             deliver: None,
             review_prompt: None,
             sandbox: None,
+            budget: None,
+            fallback: Vec::new(),
+            catch_up: CronCatchUp::None,
         }];
         shared.store(Arc::new(new_config));
         notify.notify_one();
@@ -1588,6 +1912,9 @@ mod tests {
             deliver: None,
             review_prompt: None,
             sandbox: None,
+            budget: None,
+            fallback: Vec::new(),
+            catch_up: CronCatchUp::None,
         }];
         let (shared, router, gateway) = make_shared_config_and_router(None, &cron, "response");
         let router = Arc::new(router);
@@ -1624,6 +1951,9 @@ mod tests {
             deliver: None,
             review_prompt: None,
             sandbox: None,
+            budget: None,
+            fallback: Vec::new(),
+            catch_up: CronCatchUp::None,
         }];
         shared.store(Arc::new(new_config));
         notify.notify_one();
@@ -1679,6 +2009,12 @@ mod tests {
             r#match: vec!["signal:alice-uuid".to_owned()],
             timezone: None,
             sandbox: None,
+            agent: None,
+            ipc_token_env: None,
+            budget: None,
+            fallback: Vec::new(),
+            routing: None,
+            transcribe: None,
         }];
         let provider: Arc<dyn Provider> =
             Arc::new(SequenceProvider::new(&["Server needs attention", "YES"]));
@@ -1697,6 +2033,9 @@ mod tests {
             deliver: None,
             review_prompt: None,
             sandbox: None,
+            budget: None,
+            fallback: Vec::new(),
+            catch_up: CronCatchUp::None,
         };
 
         fire_cron(&cfg, &router, Some(&deliver_tx), &shared).await;
@@ -1719,6 +2058,12 @@ mod tests {
             ],
             timezone: None,
             sandbox: None,
+            agent: None,
+            ipc_token_env: None,
+            budget: None,
+            fallback: Vec::new(),
+            routing: None,
+            transcribe: None,
         }];
         let provider: Arc<dyn Provider> =
             Arc::new(SequenceProvider::new(&["Alert: disk full", "YES"]));
@@ -1737,6 +2082,9 @@ mod tests {
             deliver: None,
             review_prompt: None,
             sandbox: None,
+            budget: None,
+            fallback: Vec::new(),
+            catch_up: CronCatchUp::None,
         };
 
         fire_cron(&cfg, &router, Some(&deliver_tx), &shared).await;
@@ -1763,6 +2111,12 @@ mod tests {
             ],
             timezone: None,
             sandbox: None,
+            agent: None,
+            ipc_token_env: None,
+            budget: None,
+            fallback: Vec::new(),
+            routing: None,
+            transcribe: None,
         }];
         let provider: Arc<dyn Provider> =
             Arc::new(SequenceProvider::new(&["Important alert", "YES"]));
@@ -1781,6 +2135,9 @@ mod tests {
             deliver: None,
             review_prompt: None,
             sandbox: None,
+            budget: None,
+            fallback: Vec::new(),
+            catch_up: CronCatchUp::None,
         };
 
         fire_cron(&cfg, &router, Some(&deliver_tx), &shared).await;
@@ -1801,6 +2158,12 @@ mod tests {
             r#match: vec!["signal:alice-uuid".to_owned()],
             timezone: None,
             sandbox: None,
+            agent: None,
+            ipc_token_env: None,
+            budget: None,
+            fallback: Vec::new(),
+            routing: None,
+            transcribe: None,
         }];
         let (shared, router, gateway) =
             make_shared_config_and_router_with_users_and_match(&users, &[], "HEARTBEAT_OK");
@@ -1817,6 +2180,9 @@ mod tests {
             deliver: None,
             review_prompt: None,
             sandbox: None,
+            budget: None,
+            fallback: Vec::new(),
+            catch_up: CronCatchUp::None,
         };
 
         fire_cron(&cfg, &router, Some(&deliver_tx), &shared).await;
@@ -1844,6 +2210,12 @@ mod tests {
             r#match: vec!["signal:alice-uuid".to_owned()],
             timezone: None,
             sandbox: None,
+            agent: None,
+            ipc_token_env: None,
+            budget: None,
+            fallback: Vec::new(),
+            routing: None,
+            transcribe: None,
         }];
         let provider: Arc<dyn Provider> =
             Arc::new(SequenceProvider::new(&["Your server is down", "YES"]));
@@ -1862,6 +2234,9 @@ mod tests {
             deliver: None,
             review_prompt: None,
             sandbox: None,
+            budget: None,
+            fallback: Vec::new(),
+            catch_up: CronCatchUp::None,
         };
 
         fire_cron(&cfg, &router, Some(&deliver_tx), &shared).await;
@@ -1883,6 +2258,12 @@ mod tests {
             ],
             timezone: None,
             sandbox: None,
+            agent: None,
+            ipc_token_env: None,
+            budget: None,
+            fallback: Vec::new(),
+            routing: None,
+            transcribe: None,
         }];
         let provider: Arc<dyn Provider> =
             Arc::new(SequenceProvider::new(&["Alert content", "YES"]));
@@ -1905,6 +2286,9 @@ mod tests {
             }),
             review_prompt: None,
             sandbox: None,
+            budget: None,
+            fallback: Vec::new(),
+            catch_up: CronCatchUp::None,
         };
 
         fire_cron(&cfg, &router, Some(&deliver_tx), &shared).await;
@@ -1934,6 +2318,9 @@ mod tests {
             deliver: None,
             review_prompt: None,
             sandbox: None,
+            budget: None,
+            fallback: Vec::new(),
+            catch_up: CronCatchUp::None,
         };
 
         fire_cron(&cfg, &router, Some(&deliver_tx), &shared).await;
@@ -1967,6 +2354,9 @@ match = ["signal:alice-uuid", "terminal:default", "signal:group:team-chat"]
             deliver: None,
             review_prompt: None,
             sandbox: None,
+            budget: None,
+            fallback: Vec::new(),
+            catch_up: CronCatchUp::None,
         };
 
         let targets = resolve_cron_delivery_targets(&config, &cfg);
@@ -2004,6 +2394,9 @@ match = ["signal:alice-uuid"]
             }),
             review_prompt: None,
             sandbox: None,
+            budget: None,
+            fallback: Vec::new(),
+            catch_up: CronCatchUp::None,
         };
 
         let targets = resolve_cron_delivery_targets(&config, &cfg);
@@ -2034,6 +2427,9 @@ model = "test"
             deliver: None,
             review_prompt: None,
             sandbox: None,
+            budget: None,
+            fallback: Vec::new(),
+            catch_up: CronCatchUp::None,
         };
 
         let targets = resolve_cron_delivery_targets(&config, &cfg);
@@ -2066,6 +2462,9 @@ match = ["signal:alice-uuid"]
             deliver: None,
             review_prompt: None,
             sandbox: None,
+            budget: None,
+            fallback: Vec::new(),
+            catch_up: CronCatchUp::None,
         };
 
         let targets = resolve_cron_delivery_targets(&config, &cfg);
@@ -2150,6 +2549,9 @@ match = ["signal:alice-uuid"]
             deliver: None,
             review_prompt: None,
             sandbox: None,
+            budget: None,
+            fallback: Vec::new(),
+            catch_up: CronCatchUp::None,
         }];
         let (shared, router, _gateway) =
             make_shared_config_and_router_with_provider(None, &cron, provider);
@@ -2197,6 +2599,9 @@ match = ["signal:alice-uuid"]
             }),
             review_prompt: None,
             sandbox: None,
+            budget: None,
+            fallback: Vec::new(),
+            catch_up: CronCatchUp::None,
         };
 
         fire_cron(&cfg, &router, Some(&deliver_tx), &shared).await;
@@ -2228,6 +2633,9 @@ match = ["signal:alice-uuid"]
             }),
             review_prompt: None,
             sandbox: None,
+            budget: None,
+            fallback: Vec::new(),
+            catch_up: CronCatchUp::None,
         };
 
         fire_cron(&cfg, &router, Some(&deliver_tx), &shared).await;
@@ -2258,6 +2666,9 @@ match = ["signal:alice-uuid"]
             }),
             review_prompt: None,
             sandbox: None,
+            budget: None,
+            fallback: Vec::new(),
+            catch_up: CronCatchUp::None,
         };
 
         fire_cron(&cfg, &router, Some(&deliver_tx), &shared).await;
@@ -2292,6 +2703,9 @@ match = ["signal:alice-uuid"]
             }),
             review_prompt: None,
             sandbox: None,
+            budget: None,
+            fallback: Vec::new(),
+            catch_up: CronCatchUp::None,
         };
 
         fire_cron(&cfg, &router, Some(&deliver_tx), &shared).await;
@@ -2324,6 +2738,9 @@ match = ["signal:alice-uuid"]
             }),
             review_prompt: None,
             sandbox: None,
+            budget: None,
+            fallback: Vec::new(),
+            catch_up: CronCatchUp::None,
         };
 
         fire_cron(&cfg, &router, Some(&deliver_tx), &shared).await;
@@ -2366,6 +2783,9 @@ match = ["signal:alice-uuid"]
             }),
             review_prompt: None,
             sandbox: None,
+            budget: None,
+            fallback: Vec::new(),
+            catch_up: CronCatchUp::None,
         };
 
         fire_cron(&cfg, &router, Some(&deliver_tx), &shared).await;
@@ -2398,6 +2818,9 @@ match = ["signal:alice-uuid"]
             }),
             review_prompt: None,
             sandbox: None,
+            budget: None,
+            fallback: Vec::new(),
+            catch_up: CronCatchUp::None,
         };
 
         fire_cron(&cfg, &router, Some(&deliver_tx), &shared).await;
@@ -2450,6 +2873,9 @@ match = ["signal:alice-uuid"]
             }),
             review_prompt: None,
             sandbox: None,
+            budget: None,
+            fallback: Vec::new(),
+            catch_up: CronCatchUp::None,
         };
 
         fire_cron(&cfg, &router, Some(&deliver_tx), &shared).await;
@@ -2505,6 +2931,9 @@ match = ["signal:alice-uuid"]
             deliver: None,
             review_prompt: None,
             sandbox: None,
+            budget: None,
+            fallback: Vec::new(),
+            catch_up: CronCatchUp::None,
         }];
         let (shared, router, _gateway) =
             make_shared_config_and_router(None, &cron, "cron response ok");
@@ -2524,6 +2953,7 @@ match = ["signal:alice-uuid"]
                 sched_cancel,
                 None,
                 None,
+                None,
                 command_rx,
             )
             .await;
@@ -2591,6 +3021,9 @@ match = ["signal:alice-uuid"]
             }),
             review_prompt: None,
             sandbox: None,
+            budget: None,
+            fallback: Vec::new(),
+            catch_up: CronCatchUp::None,
         };
 
         fire_cron(&cfg, &router, Some(&deliver_tx), &shared).await;
@@ -2625,6 +3058,9 @@ match = ["signal:alice-uuid"]
             }),
             review_prompt: None,
             sandbox: None,
+            budget: None,
+            fallback: Vec::new(),
+            catch_up: CronCatchUp::None,
         };
 
         fire_cron(&cfg, &router, Some(&deliver_tx), &shared).await;
@@ -2652,6 +3088,9 @@ match = ["signal:alice-uuid"]
             }),
             review_prompt: None,
             sandbox: None,
+            budget: None,
+            fallback: Vec::new(),
+            catch_up: CronCatchUp::None,
         };
 
         fire_cron(&cfg, &router, Some(&deliver_tx), &shared).await;
@@ -2748,6 +3187,7 @@ match = ["signal:alice-uuid"]
                 let _ = writeln!(toml_str, "delivery = \"{delivery_mode}\"");
             }
             let _ = writeln!(toml_str, "message = \"{}\"", entry.message);
+            let _ = writeln!(toml_str, "catch_up = \"{}\"", entry.catch_up);
             if let Some(ref review_prompt) = entry.review_prompt {
                 let _ = writeln!(toml_str, "review_prompt = \"{review_prompt}\"");
             }
@@ -2825,6 +3265,7 @@ match = ["signal:alice-uuid"]
                 let _ = writeln!(toml_str, "delivery = \"{delivery_mode}\"");
             }
             let _ = writeln!(toml_str, "message = \"{}\"", entry.message);
+            let _ = writeln!(toml_str, "catch_up = \"{}\"", entry.catch_up);
             if let Some(ref review_prompt) = entry.review_prompt {
                 let _ = writeln!(toml_str, "review_prompt = \"{review_prompt}\"");
             }
@@ -2879,6 +3320,9 @@ match = ["signal:alice-uuid"]
             deliver: None,
             review_prompt: None,
             sandbox: None,
+            budget: None,
+            fallback: Vec::new(),
+            catch_up: CronCatchUp::None,
         }];
         let (shared, router, gateway) =
             make_shared_config_and_router_with_provider(None, &cron, provider);
@@ -2935,6 +3379,9 @@ match = ["signal:alice-uuid"]
             }),
             review_prompt: None,
             sandbox: None,
+            budget: None,
+            fallback: Vec::new(),
+            catch_up: CronCatchUp::None,
         };
 
         // Simulate an active turn on the DM session. Acquire the session
@@ -3001,6 +3448,9 @@ match = ["signal:alice-uuid"]
             }),
             review_prompt: None,
             sandbox: None,
+            budget: None,
+            fallback: Vec::new(),
+            catch_up: CronCatchUp::None,
         };
 
         // No active turn on the DM session — cron should proceed immediately.
@@ -3028,6 +3478,9 @@ match = ["signal:alice-uuid"]
             deliver: None,
             review_prompt: None,
             sandbox: None,
+            budget: None,
+            fallback: Vec::new(),
+            catch_up: CronCatchUp::None,
         };
 
         // Even with an active turn on some DM session, cron runs because
@@ -3073,6 +3526,9 @@ match = ["signal:alice-uuid"]
             }),
             review_prompt: None,
             sandbox: None,
+            budget: None,
+            fallback: Vec::new(),
+            catch_up: CronCatchUp::None,
         };
 
         // Simulate an active turn on a DM session (unrelated to group).
@@ -3102,6 +3558,9 @@ match = ["signal:alice-uuid"]
             deliver: None,
             review_prompt: None,
             sandbox: None,
+            budget: None,
+            fallback: Vec::new(),
+            catch_up: CronCatchUp::None,
         }];
         let (shared, router, _gateway) =
             make_shared_config_and_router(None, &cron, "cron response ok");
@@ -3118,6 +3577,7 @@ match = ["signal:alice-uuid"]
                 sched_cancel,
                 None,
                 None,
+                None,
                 command_rx,
             )
             .await;
@@ -3150,6 +3610,65 @@ match = ["signal:alice-uuid"]
             .expect("scheduler task panicked");
     }
 
+    #[tokio::test]
+    async fn scheduler_manual_cron_command_runs_managed_job() {
+        let dir = tempfile::tempdir().unwrap();
+        let jobs = CronJobStore::new(dir.path()).unwrap();
+        jobs.insert(crate::cron_jobs::ManagedCron {
+            name: "inbox".to_owned(),
+            cron: "0 9 * * 1-5".to_owned(),
+            timezone: None,
+            message: "check my inbox".to_owned(),
+            user: None,
+            delivery: None,
+            paused: false,
+            created_at: Utc::now(),
+        });
+        let (shared, router, _gateway) = make_shared_config_and_router(None, &[], "inbox clear");
+        let router = Arc::new(router);
+        let cancel = CancellationToken::new();
+        let (command_tx, command_rx) = mpsc::channel(4);
+
+        let sched_cancel = cancel.clone();
+        let handle = tokio::spawn(async move {
+            run_scheduler_with_notify_and_commands(
+                shared,
+                router,
+                None,
+                sched_cancel,
+                None,
+                None,
+                Some(jobs),
+                command_rx,
+            )
+            .await;
+        });
+
+        let (reply_tx, reply_rx) = tokio::sync::oneshot::channel();
+        command_tx
+            .send(CronCommand::RunNow {
+                name: "inbox".to_owned(),
+                deliver: false,
+                origin_session_id: "test:main".to_owned(),
+                reply: reply_tx,
+            })
+            .await
+            .unwrap();
+
+        let result = tokio::time::timeout(Duration::from_secs(5), reply_rx)
+            .await
+            .expect("manual cron command timed out")
+            .expect("scheduler dropped manual cron reply")
+            .expect("managed cron command failed");
+        assert_eq!(result.response.as_deref(), Some("inbox clear"));
+
+        cancel.cancel();
+        tokio::time::timeout(Duration::from_secs(2), handle)
+            .await
+            .expect("scheduler did not exit after cancellation")
+            .expect("scheduler task panicked");
+    }
+
     #[tokio::test]
     async fn manual_delivery_skips_origin_session_lock_and_injection() {
         let cfg = CronConfig {
@@ -3162,6 +3681,9 @@ match = ["signal:alice-uuid"]
             deliver: None,
             review_prompt: None,
             sandbox: None,
+            budget: None,
+            fallback: Vec::new(),
+            catch_up: CronCatchUp::None,
         };
         let users = [UserConfig {
             name: "alice".to_owned(),
@@ -3170,6 +3692,12 @@ match = ["signal:alice-uuid"]
             r#match: vec!["signal:alice-uuid".to_owned()],
             timezone: None,
             sandbox: None,
+            agent: None,
+            ipc_token_env: None,
+            budget: None,
+            fallback: Vec::new(),
+            routing: None,
+            transcribe: None,
         }];
         let (shared, router, gateway) =
             make_shared_config_and_router_with_users_and_match(&users, &[cfg], "cron response ok");
@@ -3192,6 +3720,7 @@ match = ["signal:alice-uuid"]
                 &router,
                 Some(&deliver_tx),
                 &shared,
+                None,
             ),
         )
         .await
@@ -915,6 +915,12 @@ where
         capture_keys.insert(env_var);
     }
 
+    for user in &config.users {
+        if let Some(env_name) = &user.ipc_token_env {
+            capture_keys.insert(env_name.clone());
+        }
+    }
+
     for key in capture_keys {
         if let Some(value) = lookup(&key) {
             env.insert(key, value);
@@ -1397,6 +1403,7 @@ async fn probe_socket_health(socket: &Path) -> bool {
         client
             .send(ClientMessage::Hello {
                 version: PROTOCOL_VERSION,
+                proof: None,
             })
             .await?;
         let response = client.recv().await?;
@@ -1702,7 +1709,12 @@ mod tests {
                 context_limit: None,
                 workspace: "./workspaces/default".to_owned(),
                 subagents: crate::config::SubagentsConfig::default(),
+                prompt: None,
+                memory_db: None,
+                budget: None,
+                fallback: Vec::new(),
             },
+            agents: Vec::new(),
             users: Vec::new(),
             groups: Vec::new(),
             channels: crate::config::ChannelsConfig::default(),
@@ -1721,14 +1733,24 @@ mod tests {
                 stream_policy: crate::config::StreamPolicy::Prefer,
                 refresh_token: None,
                 reasoning: None,
+                keep_alive: None,
             },
             providers: Vec::new(),
             models: crate::config::ModelsConfig::default(),
             prompt: crate::config::PromptConfig::default(),
             memory: crate::config::MemoryConfig::default(),
             tools: crate::config::ToolsConfig::default(),
+            mcp: Vec::new(),
             cron: Vec::new(),
+            trigger: Vec::new(),
+            webhooks: None,
             sandbox: crate::config::SandboxConfig::default(),
+            ipc: crate::config::IpcConfig::default(),
+            sessions: crate::config::SessionsConfig::default(),
+            usage: crate::config::UsageConfig::default(),
+            routing: crate::config::RoutingConfig::default(),
+            transcription: None,
+            redaction: crate::config::RedactionConfig::default(),
         }
     }
 
@@ -1904,6 +1926,46 @@ mod tests {
         assert_eq!(env["OPENROUTER_API_KEY"], "r");
     }
 
+    #[test]
+    fn environment_captures_remote_ipc_tokens() {
+        let mut config = test_config();
+        config.users = toml::from_str::<Config>(
+            r#"
+[agent]
+id = "coop"
+model = "test"
+
+[[users]]
+name = "alice"
+trust = "full"
+ipc_token_env = "COOP_IPC_TOKEN_ALICE"
+"#,
+        )
+        .unwrap()
+        .users;
+
+        let tmp = tempfile::tempdir().unwrap();
+        let paths = ServicePaths {
+            binary: tmp.path().join("coop"),
+            config: tmp.path().join("coop.toml"),
+            unit_file: tmp.path().join("coop.service"),
+            env_file: tmp.path().join("service.env"),
+            launchd_wrapper: tmp.path().join("wrapper.sh"),
+            trace_file: tmp.path().join("traces.jsonl"),
+            stdout_log: tmp.path().join("stdout.log"),
+            stderr_log: tmp.path().join("stderr.log"),
+        };
+        let source = BTreeMap::from([("COOP_IPC_TOKEN_ALICE".to_owned(), "t".to_owned())]);
+
+        let env =
+            resolve_effective_env_with_lookup(&config, &paths, &[], None, None, None, |key| {
+                source.get(key).cloned()
+            })
+            .unwrap();
+
+        assert_eq!(env["COOP_IPC_TOKEN_ALICE"], "t");
+    }
+
     #[test]
     fn print_redacts_secrets_by_default() {
         let mut env = BTreeMap::new();
@@ -0,0 +1,284 @@
+//! Turn-level session history for `/undo`, `/retry` and `/fork`.
+//!
+//! Every turn is recorded as a node pointing at the message index where it
+//! started, together with the compaction state that was in effect at that
+//! point. Rewinding to a node therefore restores both the transcript and a
+//! summary that only covers messages still in it.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use coop_core::types::SessionKey;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tracing::debug;
+
+use crate::compaction::CompactionState;
+
+/// One user turn in a session.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct TurnNode {
+    /// Index of the turn's first message (the user input).
+    pub start: usize,
+    pub input: String,
+    pub model: String,
+    pub created_at: DateTime<Utc>,
+    /// Index into [`SessionHistory::compactions`] of the state in effect
+    /// when the turn started.
+    #[serde(default)]
+    pub compaction: Option<usize>,
+}
+
+/// Where a forked session came from.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct ForkOrigin {
+    pub session: String,
+    /// Number of the parent's turns the fork starts with.
+    pub turns: usize,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub(crate) struct SessionHistory {
+    #[serde(default)]
+    pub turns: Vec<TurnNode>,
+    /// Distinct compaction states referenced by `turns`, oldest first.
+    #[serde(default)]
+    pub compactions: Vec<CompactionState>,
+    #[serde(default)]
+    pub forked_from: Option<ForkOrigin>,
+}
+
+impl SessionHistory {
+    /// Record a turn starting at message `start`.
+    ///
+    /// Nodes at or after `start` belong to turns that were rolled back, so
+    /// they are dropped first.
+    pub(crate) fn begin_turn(
+        &mut self,
+        start: usize,
+        input: &str,
+        model: &str,
+        compaction: Option<&CompactionState>,
+    ) {
+        self.turns.retain(|turn| turn.start < start);
+        self.trim_compactions();
+
+        let compaction = compaction.map(|state| {
+            let known = self
+                .compactions
+                .iter()
+                .position(|existing| same_compaction(existing, state));
+            known.unwrap_or_else(|| {
+                self.compactions.push(state.clone());
+                self.compactions.len() - 1
+            })
+        });
+        self.turns.push(TurnNode {
+            start,
+            input: input.to_owned(),
+            model: model.to_owned(),
+            created_at: Utc::now(),
+            compaction,
+        });
+    }
+
+    /// Remove the last turn, returning it with the compaction state the
+    /// session had before it.
+    pub(crate) fn pop_turn(&mut self) -> Option<(TurnNode, Option<CompactionState>)> {
+        let turn = self.turns.pop()?;
+        let compaction = turn
+            .compaction
+            .and_then(|index| self.compactions.get(index).cloned());
+        self.trim_compactions();
+        Some((turn, compaction))
+    }
+
+    /// History for a fork of `parent` that keeps its first `turns` turns.
+    pub(crate) fn fork(&self, parent: &SessionKey, turns: usize) -> Self {
+        let turns = turns.min(self.turns.len());
+        let mut fork = Self {
+            turns: self.turns[..turns].to_vec(),
+            compactions: self.compactions.clone(),
+            forked_from: Some(ForkOrigin {
+                session: parent.to_string(),
+                turns,
+            }),
+        };
+        fork.trim_compactions();
+        fork
+    }
+
+    /// Compaction state in effect when turn `index` started.
+    pub(crate) fn compaction_before(&self, index: usize) -> Option<CompactionState> {
+        let turn = self.turns.get(index)?;
+        self.compactions.get(turn.compaction?).cloned()
+    }
+
+    /// Message index where the turn after the first `turns` ones starts.
+    pub(crate) fn end_of_turn(&self, turns: usize) -> Option<usize> {
+        self.turns.get(turns).map(|turn| turn.start)
+    }
+
+    fn trim_compactions(&mut self) {
+        let used = self
+            .turns
+            .iter()
+            .filter_map(|turn| turn.compaction)
+            .max()
+            .map_or(0, |index| index + 1);
+        self.compactions.truncate(used);
+    }
+}
+
+fn same_compaction(a: &CompactionState, b: &CompactionState) -> bool {
+    a.created_at == b.created_at
+        && a.compaction_count == b.compaction_count
+        && a.messages_at_compaction == b.messages_at_compaction
+}
+
+/// Stores session history as JSON files next to the session transcripts.
+pub(crate) struct SessionHistoryStore {
+    dir: PathBuf,
+}
+
+impl SessionHistoryStore {
+    pub(crate) fn new(dir: impl AsRef<Path>) -> Result<Self> {
+        let dir = dir.as_ref().to_path_buf();
+        std::fs::create_dir_all(&dir)
+            .with_context(|| format!("failed to create history dir: {}", dir.display()))?;
+        Ok(Self { dir })
+    }
+
+    pub(crate) fn load(&self, key: &SessionKey) -> Result<SessionHistory> {
+        let path = self.path_for(key);
+        if !path.exists() {
+            return Ok(SessionHistory::default());
+        }
+        let data = std::fs::read_to_string(&path)
+            .with_context(|| format!("failed to read history: {}", path.display()))?;
+        serde_json::from_str(&data)
+            .with_context(|| format!("failed to parse history: {}", path.display()))
+    }
+
+    pub(crate) fn save(&self, key: &SessionKey, history: &SessionHistory) -> Result<()> {
+        let path = self.path_for(key);
+        let data = serde_json::to_string(history)?;
+        std::fs::write(&path, data)
+            .with_context(|| format!("failed to write history: {}", path.display()))?;
+        debug!(session = %key, turns = history.turns.len(), "saved session history");
+        Ok(())
+    }
+
+    pub(crate) fn delete(&self, key: &SessionKey) -> Result<()> {
+        let path = self.path_for(key);
+        if path.exists() {
+            std::fs::remove_file(&path)
+                .with_context(|| format!("failed to delete history: {}", path.display()))?;
+        }
+        Ok(())
+    }
+
+    fn path_for(&self, key: &SessionKey) -> PathBuf {
+        let slug = key.to_string().replace(['/', ':'], "_");
+        self.dir.join(format!("{slug}_history.json"))
+    }
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use coop_core::types::SessionKind;
+
+    fn compaction(count: u32, messages: usize) -> CompactionState {
+        CompactionState {
+            summary: format!("summary {count}"),
+            files_touched: vec![],
+            compaction_count: count,
+            tokens_at_compaction: 1000,
+            created_at: Utc::now(),
+            messages_at_compaction: Some(messages),
+        }
+    }
+
+    fn main_key() -> SessionKey {
+        SessionKey {
+            agent_id: "coop".into(),
+            kind: SessionKind::Main,
+        }
+    }
+
+    #[test]
+    fn pop_restores_the_compaction_from_before_the_turn() {
+        let first = compaction(1, 4);
+        let mut history = SessionHistory::default();
+        history.begin_turn(0, "one", "m", None);
+        history.begin_turn(2, "two", "m", None);
+        history.begin_turn(6, "three", "m", Some(&first));
+        history.begin_turn(8, "four", "m", Some(&first));
+        assert_eq!(history.compactions.len(), 1);
+
+        let (turn, state) = history.pop_turn().unwrap();
+        assert_eq!((turn.start, turn.input.as_str()), (8, "four"));
+        assert_eq!(state.unwrap().summary, "summary 1");
+        history.pop_turn().unwrap();
+        assert!(history.compactions.is_empty());
+
+        let (turn, state) = history.pop_turn().unwrap();
+        assert_eq!(turn.input, "two");
+        assert!(state.is_none());
+    }
+
+    #[test]
+    fn rolled_back_turns_are_replaced() {
+        let mut history = SessionHistory::default();
+        history.begin_turn(0, "one", "m", None);
+        history.begin_turn(2, "failed", "m", None);
+        // The failed turn was rolled back to 2 messages before this one.
+        history.begin_turn(2, "again", "m", None);
+
+        let inputs: Vec<_> = history.turns.iter().map(|t| t.input.as_str()).collect();
+        assert_eq!(inputs, vec!["one", "again"]);
+    }
+
+    #[test]
+    fn fork_keeps_the_compaction_in_effect_at_the_fork_point() {
+        let early = compaction(1, 2);
+        let late = compaction(2, 6);
+        let mut history = SessionHistory::default();
+        history.begin_turn(0, "one", "m", None);
+        history.begin_turn(4, "two", "m", Some(&early));
+        history.begin_turn(8, "three", "m", Some(&late));
+
+        let fork = history.fork(&main_key(), 2);
+        assert_eq!(fork.turns.len(), 2);
+        assert_eq!(history.end_of_turn(2), Some(8));
+        assert_eq!(history.compaction_before(2).unwrap().summary, "summary 2");
+        assert_eq!(history.compaction_before(1).unwrap().summary, "summary 1");
+        assert!(history.compaction_before(0).is_none());
+        assert_eq!(fork.compactions.len(), 1);
+        assert_eq!(fork.forked_from.unwrap().session, "coop:main");
+
+        let fork = history.fork(&main_key(), 10);
+        assert_eq!(fork.turns.len(), 3);
+        assert_eq!(fork.compactions.len(), 2);
+    }
+
+    #[test]
+    fn store_roundtrip_and_delete() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = SessionHistoryStore::new(dir.path()).unwrap();
+        let key = main_key();
+        assert!(store.load(&key).unwrap().turns.is_empty());
+
+        let mut history = SessionHistory::default();
+        history.begin_turn(0, "hello", "m", Some(&compaction(1, 0)));
+        store.save(&key, &history).unwrap();
+        let loaded = store.load(&key).unwrap();
+        assert_eq!(loaded.turns[0].input, "hello");
+        assert_eq!(loaded.compactions.len(), 1);
+
+        store.delete(&key).unwrap();
+        assert!(store.load(&key).unwrap().turns.is_empty());
+        store.delete(&key).unwrap();
+    }
+}
@@ -1,9 +1,15 @@
 use anyhow::{Context, Result};
-use coop_core::{Message, SessionKey};
+use coop_core::{Message, SessionKey, SessionKind};
 use std::io::Write;
 use std::path::{Path, PathBuf};
 use tracing::{debug, warn};
 
+use crate::config::{Config, SessionStoreKind};
+use crate::sqlite_session_store::{SessionFilter, SqliteSessionStore};
+
+/// File name of the SQLite session store inside `sessions/`.
+pub(crate) const SESSION_DB_FILE: &str = "sessions.db";
+
 /// Sync file-backed session store using JSONL format.
 ///
 /// Each session is stored as `{dir}/{slug}.jsonl` where the slug is
@@ -104,11 +110,197 @@ impl DiskSessionStore {
     }
 
     fn path(&self, key: &SessionKey) -> PathBuf {
-        let slug = key.to_string().replace(['/', ':'], "_");
-        self.dir.join(format!("{slug}.jsonl"))
+        self.dir.join(format!("{}.jsonl", session_slug(key)))
+    }
+}
+
+/// The session backend selected by `[sessions] store`.
+pub(crate) enum SessionStorage {
+    Jsonl(DiskSessionStore),
+    Sqlite(SqliteSessionStore),
+}
+
+impl SessionStorage {
+    /// Open the configured backend in a workspace's `sessions/` directory.
+    pub(crate) fn open(kind: SessionStoreKind, dir: impl AsRef<Path>) -> Result<Self> {
+        let dir = dir.as_ref();
+        Ok(match kind {
+            SessionStoreKind::Jsonl => Self::Jsonl(DiskSessionStore::new(dir)?),
+            SessionStoreKind::Sqlite => {
+                Self::Sqlite(SqliteSessionStore::open(dir.join(SESSION_DB_FILE))?)
+            }
+        })
+    }
+
+    pub(crate) fn load(&self, key: &SessionKey) -> Result<Vec<Message>> {
+        match self {
+            Self::Jsonl(store) => store.load(key),
+            Self::Sqlite(store) => store.load(key),
+        }
+    }
+
+    pub(crate) fn append(&self, key: &SessionKey, message: &Message) -> Result<()> {
+        match self {
+            Self::Jsonl(store) => store.append(key, message),
+            Self::Sqlite(store) => store.append(key, message),
+        }
+    }
+
+    pub(crate) fn replace(&self, key: &SessionKey, messages: &[Message]) -> Result<()> {
+        match self {
+            Self::Jsonl(store) => store.replace(key, messages),
+            Self::Sqlite(store) => store.replace(key, messages),
+        }
+    }
+
+    pub(crate) fn delete(&self, key: &SessionKey) -> Result<()> {
+        match self {
+            Self::Jsonl(store) => store.delete(key),
+            Self::Sqlite(store) => store.delete(key),
+        }
+    }
+
+    /// Tag a session with its user. Only the SQLite store indexes this.
+    pub(crate) fn record_user(&self, key: &SessionKey, user: &str) -> Result<()> {
+        match self {
+            Self::Jsonl(_) => Ok(()),
+            Self::Sqlite(store) => store.record_user(key, user),
+        }
+    }
+
+    /// Persisted sessions of `agent_id`. JSONL file names cannot be mapped
+    /// back to session keys reliably, so that store lists nothing.
+    pub(crate) fn list(&self, agent_id: &str) -> Result<Vec<SessionKey>> {
+        match self {
+            Self::Jsonl(_) => Ok(Vec::new()),
+            Self::Sqlite(store) => store.list(SessionFilter {
+                agent_id: Some(agent_id),
+                ..SessionFilter::default()
+            }),
+        }
     }
 }
 
+/// Outcome of [`migrate_jsonl_sessions`].
+#[derive(Debug, Default)]
+pub(crate) struct MigrationSummary {
+    pub imported: Vec<(SessionKey, usize)>,
+    /// Sessions already present in the database, left untouched.
+    pub existing: Vec<SessionKey>,
+    /// Files whose session key could not be recovered.
+    pub unrecognized: Vec<PathBuf>,
+}
+
+/// Copy every `{slug}.jsonl` session in `dir` into `store`.
+///
+/// File names are lossy (`:` and `/` became `_`), so each slug is first
+/// matched against `known` keys (built from config identities), then decoded
+/// from the usual `<agent>_<kind>_<channel>_...` shape. Sessions that already
+/// have messages in the database are skipped, so the migration can be re-run.
+pub(crate) fn migrate_jsonl_sessions(
+    dir: &Path,
+    agent_id: &str,
+    known: &[SessionKey],
+    store: &SqliteSessionStore,
+) -> Result<MigrationSummary> {
+    let disk = DiskSessionStore::new(dir)?;
+    let mut paths: Vec<PathBuf> = std::fs::read_dir(dir)
+        .with_context(|| format!("failed to read session dir: {}", dir.display()))?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "jsonl"))
+        .collect();
+    paths.sort();
+
+    let mut summary = MigrationSummary::default();
+    for path in paths {
+        let Some(slug) = path.file_stem().and_then(|stem| stem.to_str()) else {
+            summary.unrecognized.push(path);
+            continue;
+        };
+        let key = known
+            .iter()
+            .find(|key| session_slug(key) == slug)
+            .cloned()
+            .or_else(|| session_key_from_slug(slug, agent_id));
+        let Some(key) = key else {
+            summary.unrecognized.push(path);
+            continue;
+        };
+
+        if store.message_count(&key)? > 0 {
+            summary.existing.push(key);
+            continue;
+        }
+        let messages = disk.load(&key)?;
+        store.replace(&key, &messages)?;
+        summary.imported.push((key, messages.len()));
+    }
+    Ok(summary)
+}
+
+/// Session keys that config identities can produce for `agent_id`, used to
+/// resolve JSONL file names the generic decoding would get wrong.
+pub(crate) fn known_session_keys(config: &Config, agent_id: &str) -> Vec<SessionKey> {
+    let dms = config.users.iter().flat_map(|user| {
+        user.r#match
+            .iter()
+            .cloned()
+            .chain(std::iter::once(format!("ipc:{}", user.name)))
+            .map(SessionKind::Dm)
+    });
+    let groups = config
+        .groups
+        .iter()
+        .flat_map(|group| group.r#match.iter().cloned().map(SessionKind::Group));
+    let crons = config
+        .cron
+        .iter()
+        .map(|cron| SessionKind::Cron(cron.name.clone()));
+    std::iter::once(SessionKind::Main)
+        .chain(dms)
+        .chain(groups)
+        .chain(crons)
+        .map(|kind| SessionKey {
+            agent_id: agent_id.to_owned(),
+            kind,
+        })
+        .collect()
+}
+
+fn session_slug(key: &SessionKey) -> String {
+    key.to_string().replace(['/', ':'], "_")
+}
+
+/// Best-effort inverse of [`session_slug`] for keys not named in config.
+fn session_key_from_slug(slug: &str, agent_id: &str) -> Option<SessionKey> {
+    let rest = slug.strip_prefix(agent_id)?.strip_prefix('_')?;
+    let kind = if rest == "main" {
+        SessionKind::Main
+    } else if let Some(who) = rest.strip_prefix("dm_") {
+        // `<channel>:<sender>`; senders are uuids, numbers or user names.
+        SessionKind::Dm(who.replacen('_', ":", 1))
+    } else if let Some(group) = rest.strip_prefix("group_") {
+        // `<channel>:group:<id>` for Signal and Telegram groups.
+        let group = match group.split_once("_group_") {
+            Some((channel, id)) => format!("{channel}:group:{id}"),
+            None => group.replacen('_', ":", 1),
+        };
+        SessionKind::Group(group)
+    } else if let Some(uuid) = rest.strip_prefix("isolated_") {
+        SessionKind::Isolated(uuid.parse().ok()?)
+    } else if let Some(uuid) = rest.strip_prefix("subagent_") {
+        SessionKind::Subagent(uuid.parse().ok()?)
+    } else if let Some(name) = rest.strip_prefix("cron_") {
+        SessionKind::Cron(name.to_owned())
+    } else {
+        return None;
+    };
+    Some(SessionKey {
+        agent_id: agent_id.to_owned(),
+        kind,
+    })
+}
+
 #[allow(clippy::unwrap_used)]
 #[cfg(test)]
 mod tests {
@@ -219,4 +411,59 @@ mod tests {
         assert!(path.to_string_lossy().ends_with(".jsonl"));
         assert_eq!(path, store.path(&key));
     }
+
+    #[test]
+    fn slugs_decode_back_to_session_keys() {
+        let cases = [
+            SessionKind::Main,
+            SessionKind::Dm("signal:alice-uuid".to_owned()),
+            SessionKind::Dm("terminal:default".to_owned()),
+            SessionKind::Group("signal:group:abc123".to_owned()),
+            SessionKind::Group("telegram:group:-100123".to_owned()),
+            SessionKind::Cron("morning_briefing".to_owned()),
+            SessionKind::Subagent(uuid::Uuid::new_v4()),
+        ];
+        for kind in cases {
+            let key = SessionKey {
+                agent_id: "coop".to_owned(),
+                kind,
+            };
+            assert_eq!(
+                session_key_from_slug(&session_slug(&key), "coop"),
+                Some(key)
+            );
+        }
+        assert_eq!(session_key_from_slug("other_main", "coop"), None);
+    }
+
+    #[test]
+    fn migration_imports_jsonl_sessions_once() {
+        let dir = tempfile::tempdir().unwrap();
+        let disk = DiskSessionStore::new(dir.path()).unwrap();
+        let dm = test_key();
+        // Not decodable from the slug alone; found through config identities.
+        let odd = SessionKey {
+            agent_id: "coop".to_owned(),
+            kind: SessionKind::Dm("ipc:bob_smith".to_owned()),
+        };
+        disk.append(&dm, &Message::user().with_text("hello"))
+            .unwrap();
+        disk.append(&dm, &Message::assistant().with_text("hi"))
+            .unwrap();
+        disk.append(&odd, &Message::user().with_text("yo")).unwrap();
+        std::fs::write(dir.path().join("stray.jsonl"), "").unwrap();
+
+        let db = SqliteSessionStore::open(dir.path().join(SESSION_DB_FILE)).unwrap();
+        let summary =
+            migrate_jsonl_sessions(dir.path(), "coop", std::slice::from_ref(&odd), &db).unwrap();
+        assert_eq!(summary.imported.len(), 2);
+        assert!(summary.imported.contains(&(dm, 2)));
+        assert!(summary.imported.contains(&(odd.clone(), 1)));
+        assert_eq!(summary.unrecognized, vec![dir.path().join("stray.jsonl")]);
+        assert_eq!(db.load(&odd).unwrap()[0].text(), "yo");
+
+        let again = migrate_jsonl_sessions(dir.path(), "coop", &[], &db).unwrap();
+        assert!(again.imported.is_empty());
+        assert_eq!(again.existing.len(), 2);
+    }
 }
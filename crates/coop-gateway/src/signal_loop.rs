@@ -58,7 +58,7 @@ pub(crate) async fn run_signal_loop(
         // If this session already has an active turn, handle the message
         // according to the configured mid-turn strategy.
         if active_turns.contains_key(&decision.session_key) {
-            let mode = router.mid_turn_messages_mode();
+            let mode = router.mid_turn_messages_mode("signal");
             match mode {
                 MidTurnMessages::Inject => {
                     tracing::info!(
@@ -165,83 +165,18 @@ async fn dispatch_command(
 
 /// Dispatch a turn in the background, sending responses via the action_tx
 /// channel instead of requiring `&mut SignalChannel`.
-///
-/// NOTE: The verbose/quiet event-collection logic here is Signal-specific but
-/// generic enough for any messaging channel. When a second chat channel is
-/// added (WhatsApp, Telegram, etc.), extract this into a shared
-/// `router.dispatch_to_channel(msg, verbose, send_fn)` method — see
-/// `router::dispatch_collect_text` for the cron precedent.
 async fn dispatch_signal_turn_background(
     action_tx: &mpsc::Sender<coop_channels::SignalAction>,
     router: &MessageRouter,
     inbound: &InboundMessage,
     target: &str,
 ) -> Result<()> {
-    let verbose = router.signal_verbose();
-    let (event_tx, mut event_rx) = mpsc::channel(64);
-    let router = router.clone();
-    let message = inbound.clone();
-    let dispatch_task = tokio::spawn(async move { router.dispatch(&message, event_tx).await });
-
-    let mut text = String::new();
-
-    while let Some(event) = event_rx.recv().await {
-        match event {
-            TurnEvent::TextDelta(delta) => {
-                text.push_str(&delta);
-            }
-            TurnEvent::ToolStart { .. } => {
-                if verbose {
-                    flush_text_via_action(action_tx, target, &mut text).await?;
-                }
-            }
-            TurnEvent::ToolResult { .. } | TurnEvent::Compacting => {}
-            TurnEvent::AssistantMessage(ref msg) => {
-                if !verbose {
-                    // AssistantMessage is emitted only for the terminal reply,
-                    // so replace any accumulated deltas with the exact final
-                    // assistant text, even when the final reply is empty.
-                    text = msg.text();
-                }
-            }
-            TurnEvent::Error(message) => {
-                text = message;
-            }
-            TurnEvent::Done(_) => {
-                break;
-            }
-        }
-    }
-
-    // Suppress the silent reply token so NO_REPLY is never sent to the channel.
-    if crate::group_trigger::is_silent_reply(&text) {
-        tracing::debug!(target = target, "suppressing silent reply token");
-        text.clear();
-    }
-
-    flush_text_via_action(action_tx, target, &mut text).await?;
-
-    match dispatch_task.await {
-        Ok(result) => result.map(|_| ()),
-        Err(error) => {
-            // The dispatch task panicked or was cancelled. The user never
-            // received a response because the event channel was dropped
-            // before any text was produced. Send a fallback error message
-            // so the conversation doesn't silently hang.
-            tracing::error!(
-                error = %error,
-                target = target,
-                "dispatch task failed, sending fallback error to user"
-            );
-            let _ = flush_text_via_action(
-                action_tx,
-                target,
-                &mut "Something went wrong processing that message. Please try again.".to_owned(),
-            )
-            .await;
-            anyhow::bail!("router task failed: {error}");
-        }
-    }
+    let verbose = router.channel_verbose("signal");
+    router
+        .dispatch_to_channel(inbound, verbose, |content| async move {
+            send_text_via_action(action_tx, target, content).await
+        })
+        .await
 }
 
 /// Handle a single inbound Signal message (without history bootstrap).
@@ -262,7 +197,7 @@ pub(crate) async fn handle_signal_inbound_once<C: Channel>(
     };
 
     trace_signal_inbound("signal inbound dispatched", &inbound);
-    let verbose = router.signal_verbose();
+    let verbose = router.channel_verbose("signal");
     dispatch_signal_turn(signal_channel, router, &inbound, &target, verbose).await
 }
 
@@ -342,27 +277,21 @@ async fn flush_text<C: Channel>(channel: &C, target: &str, text: &mut String) ->
     Ok(())
 }
 
-/// Send accumulated text via the action channel (for background tasks that
-/// don't hold `&mut SignalChannel`).
-async fn flush_text_via_action(
+/// Send text via the action channel (for background tasks that don't hold
+/// `&mut SignalChannel`).
+async fn send_text_via_action(
     action_tx: &mpsc::Sender<coop_channels::SignalAction>,
     target: &str,
-    text: &mut String,
+    content: String,
 ) -> Result<()> {
-    if text.trim().is_empty() {
-        text.clear();
-    } else {
-        let content = std::mem::take(text);
-        action_tx
-            .send(coop_channels::SignalAction::SendText(OutboundMessage {
-                channel: "signal".to_owned(),
-                target: target.to_owned(),
-                content,
-            }))
-            .await
-            .map_err(|_send_err| anyhow::anyhow!("signal action channel closed"))?;
-    }
-    Ok(())
+    action_tx
+        .send(coop_channels::SignalAction::SendText(OutboundMessage {
+            channel: "signal".to_owned(),
+            target: target.to_owned(),
+            content,
+        }))
+        .await
+        .map_err(|_send_err| anyhow::anyhow!("signal action channel closed"))
 }
 
 fn should_dispatch_signal_message(inbound: &InboundMessage) -> bool {
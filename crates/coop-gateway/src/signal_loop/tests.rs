@@ -206,6 +206,7 @@ fn inbound_message(
         reply_to: reply_to.map(ToOwned::to_owned),
         kind,
         message_timestamp: Some(1234),
+        message_id: None,
         group_revision: None,
     }
 }
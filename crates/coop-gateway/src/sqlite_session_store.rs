@@ -0,0 +1,395 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use coop_core::{Message, SessionKey, SessionKind};
+use rusqlite::{Connection, params};
+use std::path::Path;
+use std::sync::Mutex;
+use tracing::{debug, warn};
+
+/// Sync SQLite-backed session store.
+///
+/// Every session is a row in `sessions` (indexed by agent, kind and user)
+/// with its messages in `messages`. Appends and compaction replacements run
+/// in a transaction, so a crash leaves either the old or the new history.
+pub(crate) struct SqliteSessionStore {
+    conn: Mutex<Connection>,
+}
+
+/// Filters for [`SqliteSessionStore::list`]; `None` matches everything.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct SessionFilter<'a> {
+    pub agent_id: Option<&'a str>,
+    /// `main`, `dm`, `group`, `isolated`, `subagent` or `cron`.
+    pub kind: Option<&'a str>,
+    pub user: Option<&'a str>,
+}
+
+// Each method holds the connection for exactly one statement or transaction.
+#[allow(clippy::significant_drop_tightening)]
+impl SqliteSessionStore {
+    pub(crate) fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("failed to create session dir: {}", parent.display()))?;
+        }
+        let conn = Connection::open(path)
+            .with_context(|| format!("failed to open session db: {}", path.display()))?;
+        conn.execute_batch(
+            "
+            PRAGMA journal_mode = WAL;
+            PRAGMA synchronous = NORMAL;
+            PRAGMA foreign_keys = ON;
+            PRAGMA busy_timeout = 5000;
+
+            CREATE TABLE IF NOT EXISTS sessions (
+                key        TEXT PRIMARY KEY,
+                agent_id   TEXT NOT NULL,
+                kind       TEXT NOT NULL,
+                target     TEXT NOT NULL,
+                user_name  TEXT,
+                updated_at INTEGER NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS idx_sessions_kind ON sessions(agent_id, kind);
+            CREATE INDEX IF NOT EXISTS idx_sessions_user ON sessions(user_name);
+
+            CREATE TABLE IF NOT EXISTS messages (
+                session_key TEXT NOT NULL REFERENCES sessions(key) ON DELETE CASCADE,
+                seq         INTEGER NOT NULL,
+                body        TEXT NOT NULL,
+                PRIMARY KEY (session_key, seq)
+            );
+            ",
+        )
+        .with_context(|| format!("failed to initialize session db: {}", path.display()))?;
+        Ok(Self {
+            conn: Mutex::new(conn),
+        })
+    }
+
+    pub(crate) fn load(&self, key: &SessionKey) -> Result<Vec<Message>> {
+        let conn = self.conn.lock().expect("session db mutex poisoned");
+        let mut stmt = conn
+            .prepare_cached("SELECT seq, body FROM messages WHERE session_key = ?1 ORDER BY seq")?;
+        let rows = stmt.query_map([key.to_string()], |row| {
+            Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
+        })?;
+
+        let mut messages = Vec::new();
+        for row in rows {
+            let (seq, body) = row?;
+            match serde_json::from_str::<Message>(&body) {
+                Ok(message) => messages.push(message),
+                Err(e) => {
+                    warn!(session = %key, seq, error = %e, "skipping corrupt message in session db");
+                }
+            }
+        }
+        debug!(session = %key, count = messages.len(), "loaded session from db");
+        Ok(messages)
+    }
+
+    pub(crate) fn append(&self, key: &SessionKey, message: &Message) -> Result<()> {
+        self.append_all(key, std::slice::from_ref(message))
+    }
+
+    /// Append several messages in one transaction.
+    pub(crate) fn append_all(&self, key: &SessionKey, messages: &[Message]) -> Result<()> {
+        let bodies = messages
+            .iter()
+            .map(serde_json::to_string)
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut conn = self.conn.lock().expect("session db mutex poisoned");
+        let tx = conn.transaction()?;
+        upsert_session(&tx, key)?;
+        {
+            let mut insert = tx.prepare_cached(
+                "INSERT INTO messages (session_key, seq, body)
+                 SELECT ?1, COALESCE(MAX(seq) + 1, 0), ?2 FROM messages WHERE session_key = ?1",
+            )?;
+            for body in &bodies {
+                insert.execute(params![key.to_string(), body])?;
+            }
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    pub(crate) fn replace(&self, key: &SessionKey, messages: &[Message]) -> Result<()> {
+        let bodies = messages
+            .iter()
+            .map(serde_json::to_string)
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut conn = self.conn.lock().expect("session db mutex poisoned");
+        let tx = conn.transaction()?;
+        upsert_session(&tx, key)?;
+        tx.execute(
+            "DELETE FROM messages WHERE session_key = ?1",
+            [key.to_string()],
+        )?;
+        {
+            let mut insert = tx.prepare_cached(
+                "INSERT INTO messages (session_key, seq, body) VALUES (?1, ?2, ?3)",
+            )?;
+            for (seq, body) in bodies.iter().enumerate() {
+                insert.execute(params![key.to_string(), i64::try_from(seq)?, body])?;
+            }
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    pub(crate) fn delete(&self, key: &SessionKey) -> Result<()> {
+        let conn = self.conn.lock().expect("session db mutex poisoned");
+        conn.execute("DELETE FROM sessions WHERE key = ?1", [key.to_string()])?;
+        Ok(())
+    }
+
+    /// Remember which `[[users]]` entry a session belongs to.
+    pub(crate) fn record_user(&self, key: &SessionKey, user: &str) -> Result<()> {
+        let conn = self.conn.lock().expect("session db mutex poisoned");
+        upsert_session(&conn, key)?;
+        conn.execute(
+            "UPDATE sessions SET user_name = ?2 WHERE key = ?1",
+            params![key.to_string(), user],
+        )?;
+        Ok(())
+    }
+
+    /// Persisted sessions matching `filter`, most recently updated first.
+    pub(crate) fn list(&self, filter: SessionFilter<'_>) -> Result<Vec<SessionKey>> {
+        let conn = self.conn.lock().expect("session db mutex poisoned");
+        let mut stmt = conn.prepare_cached(
+            "SELECT agent_id, kind, target FROM sessions
+             WHERE (?1 IS NULL OR agent_id = ?1)
+               AND (?2 IS NULL OR kind = ?2)
+               AND (?3 IS NULL OR user_name = ?3)
+             ORDER BY updated_at DESC, key",
+        )?;
+        let rows = stmt.query_map(params![filter.agent_id, filter.kind, filter.user], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+            ))
+        })?;
+
+        let mut keys = Vec::new();
+        for row in rows {
+            let (agent_id, kind, target) = row?;
+            if let Some(key) = session_key_from_parts(agent_id, &kind, target) {
+                keys.push(key);
+            } else {
+                warn!(kind = %kind, "skipping session with unknown kind in session db");
+            }
+        }
+        Ok(keys)
+    }
+
+    /// Number of messages stored for `key`.
+    pub(crate) fn message_count(&self, key: &SessionKey) -> Result<usize> {
+        let conn = self.conn.lock().expect("session db mutex poisoned");
+        let count: i64 = conn.query_row(
+            "SELECT COUNT(*) FROM messages WHERE session_key = ?1",
+            [key.to_string()],
+            |row| row.get(0),
+        )?;
+        Ok(usize::try_from(count)?)
+    }
+}
+
+#[async_trait]
+impl coop_core::SessionStore for SqliteSessionStore {
+    async fn load(&self, key: &SessionKey) -> Result<Vec<Message>> {
+        Self::load(self, key)
+    }
+
+    async fn save(&self, key: &SessionKey, messages: &[Message]) -> Result<()> {
+        self.append_all(key, messages)
+    }
+
+    async fn replace(&self, key: &SessionKey, messages: &[Message]) -> Result<()> {
+        Self::replace(self, key, messages)
+    }
+
+    async fn list(&self) -> Result<Vec<SessionKey>> {
+        Self::list(self, SessionFilter::default())
+    }
+
+    async fn delete(&self, key: &SessionKey) -> Result<()> {
+        Self::delete(self, key)
+    }
+}
+
+fn upsert_session(conn: &Connection, key: &SessionKey) -> Result<()> {
+    let (kind, target) = session_kind_parts(&key.kind);
+    conn.execute(
+        "INSERT INTO sessions (key, agent_id, kind, target, updated_at)
+         VALUES (?1, ?2, ?3, ?4, ?5)
+         ON CONFLICT(key) DO UPDATE SET updated_at = excluded.updated_at",
+        params![
+            key.to_string(),
+            key.agent_id,
+            kind,
+            target,
+            chrono::Utc::now().timestamp_millis()
+        ],
+    )?;
+    Ok(())
+}
+
+pub(crate) fn session_kind_parts(kind: &SessionKind) -> (&'static str, String) {
+    match kind {
+        SessionKind::Main => ("main", String::new()),
+        SessionKind::Dm(who) => ("dm", who.clone()),
+        SessionKind::Group(id) => ("group", id.clone()),
+        SessionKind::Isolated(uuid) => ("isolated", uuid.to_string()),
+        SessionKind::Subagent(uuid) => ("subagent", uuid.to_string()),
+        SessionKind::Cron(name) => ("cron", name.clone()),
+    }
+}
+
+fn session_key_from_parts(agent_id: String, kind: &str, target: String) -> Option<SessionKey> {
+    let kind = match kind {
+        "main" => SessionKind::Main,
+        "dm" => SessionKind::Dm(target),
+        "group" => SessionKind::Group(target),
+        "isolated" => SessionKind::Isolated(target.parse().ok()?),
+        "subagent" => SessionKind::Subagent(target.parse().ok()?),
+        "cron" => SessionKind::Cron(target),
+        _ => return None,
+    };
+    Some(SessionKey { agent_id, kind })
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(kind: SessionKind) -> SessionKey {
+        SessionKey {
+            agent_id: "coop".to_owned(),
+            kind,
+        }
+    }
+
+    fn open() -> (tempfile::TempDir, SqliteSessionStore) {
+        let dir = tempfile::tempdir().unwrap();
+        let store = SqliteSessionStore::open(dir.path().join("sessions.db")).unwrap();
+        (dir, store)
+    }
+
+    #[test]
+    fn append_replace_and_delete_round_trip() {
+        let (_dir, store) = open();
+        let dm = key(SessionKind::Dm("signal:alice-uuid".to_owned()));
+
+        assert!(store.load(&dm).unwrap().is_empty());
+        store
+            .append(&dm, &Message::user().with_text("hello"))
+            .unwrap();
+        store
+            .append(&dm, &Message::assistant().with_text("hi back"))
+            .unwrap();
+        let loaded = store.load(&dm).unwrap();
+        assert_eq!(loaded.len(), 2);
+        assert_eq!(loaded[1].text(), "hi back");
+
+        store
+            .replace(&dm, &[Message::user().with_text("summary")])
+            .unwrap();
+        store
+            .append(&dm, &Message::user().with_text("next"))
+            .unwrap();
+        let texts: Vec<_> = store.load(&dm).unwrap().iter().map(Message::text).collect();
+        assert_eq!(texts, vec!["summary", "next"]);
+
+        store.delete(&dm).unwrap();
+        assert!(store.load(&dm).unwrap().is_empty());
+        assert!(store.list(SessionFilter::default()).unwrap().is_empty());
+        store.delete(&dm).unwrap();
+    }
+
+    #[test]
+    fn history_survives_reopening() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("sessions.db");
+        let main = key(SessionKind::Main);
+        SqliteSessionStore::open(&path)
+            .unwrap()
+            .append(&main, &Message::user().with_text("remember me"))
+            .unwrap();
+
+        let reopened = SqliteSessionStore::open(&path).unwrap();
+        assert_eq!(reopened.load(&main).unwrap()[0].text(), "remember me");
+        assert_eq!(reopened.message_count(&main).unwrap(), 1);
+    }
+
+    #[test]
+    fn list_filters_by_kind_and_user() {
+        let (_dir, store) = open();
+        let alice = key(SessionKind::Dm("signal:alice-uuid".to_owned()));
+        let family = key(SessionKind::Group("signal:group:abc".to_owned()));
+        let cron = key(SessionKind::Cron("briefing".to_owned()));
+        let subagent = key(SessionKind::Subagent(uuid::Uuid::new_v4()));
+        for session in [&alice, &family, &cron, &subagent] {
+            store
+                .append(session, &Message::user().with_text("hi"))
+                .unwrap();
+        }
+        store.record_user(&alice, "alice").unwrap();
+        store.record_user(&cron, "alice").unwrap();
+
+        let all = store.list(SessionFilter::default()).unwrap();
+        assert_eq!(all.len(), 4);
+        assert!(all.contains(&subagent));
+
+        let groups = store
+            .list(SessionFilter {
+                kind: Some("group"),
+                ..SessionFilter::default()
+            })
+            .unwrap();
+        assert_eq!(groups, vec![family]);
+
+        let mut alices = store
+            .list(SessionFilter {
+                user: Some("alice"),
+                ..SessionFilter::default()
+            })
+            .unwrap();
+        alices.sort_by_cached_key(ToString::to_string);
+        assert_eq!(alices, vec![cron, alice]);
+
+        let other_agent = store
+            .list(SessionFilter {
+                agent_id: Some("work"),
+                ..SessionFilter::default()
+            })
+            .unwrap();
+        assert!(other_agent.is_empty());
+    }
+
+    #[tokio::test]
+    async fn implements_the_core_session_store() {
+        let (_dir, store) = open();
+        let store: &dyn coop_core::SessionStore = &store;
+        let main = key(SessionKind::Main);
+
+        store
+            .save(
+                &main,
+                &[
+                    Message::user().with_text("one"),
+                    Message::assistant().with_text("two"),
+                ],
+            )
+            .await
+            .unwrap();
+        assert_eq!(store.load(&main).await.unwrap().len(), 2);
+        assert_eq!(store.list().await.unwrap(), vec![main.clone()]);
+        store.delete(&main).await.unwrap();
+        assert!(store.list().await.unwrap().is_empty());
+    }
+}
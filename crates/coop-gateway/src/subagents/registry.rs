@@ -4,6 +4,7 @@ use coop_core::SessionKey;
 use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
 use std::sync::Mutex;
+use tokio::sync::broadcast;
 use uuid::Uuid;
 
 use super::SubagentMode;
@@ -98,6 +99,8 @@ impl SubagentRunRecord {
 pub(crate) struct SubagentRegistry {
     path: PathBuf,
     records: Mutex<Vec<SubagentRunRecord>>,
+    /// Every inserted or updated record, for progress listeners.
+    events: broadcast::Sender<SubagentRunRecord>,
 }
 
 impl SubagentRegistry {
@@ -107,19 +110,26 @@ impl SubagentRegistry {
         Ok(Self {
             path,
             records: Mutex::new(records),
+            events: broadcast::channel(64).0,
         })
     }
 
+    pub(crate) fn subscribe(&self) -> broadcast::Receiver<SubagentRunRecord> {
+        self.events.subscribe()
+    }
+
     pub(crate) fn insert(&self, record: SubagentRunRecord) -> Result<()> {
         let snapshot = {
             let mut records = self
                 .records
                 .lock()
                 .expect("subagent registry mutex poisoned");
-            records.push(record);
+            records.push(record.clone());
             records.clone()
         };
-        persist_records(&self.path, &snapshot)
+        persist_records(&self.path, &snapshot)?;
+        let _ = self.events.send(record);
+        Ok(())
     }
 
     pub(crate) fn all(&self) -> Vec<SubagentRunRecord> {
@@ -221,6 +231,7 @@ impl SubagentRegistry {
             (record.clone(), records.clone())
         };
         persist_records(&self.path, &snapshot)?;
+        let _ = self.events.send(updated.clone());
         Ok(Some(updated))
     }
 }
@@ -342,4 +353,31 @@ mod tests {
 
         assert_eq!(registry.active_count(), 1);
     }
+
+    #[test]
+    fn subscribers_see_each_status_transition() {
+        let dir = tempfile::tempdir().unwrap();
+        let registry = SubagentRegistry::new(dir.path()).unwrap();
+        let mut events = registry.subscribe();
+        let original = record(SubagentRunStatus::Queued);
+        let run_id = original.run_id;
+
+        registry.insert(original).unwrap();
+        registry.mark_running(run_id).unwrap();
+        registry
+            .finish(run_id, SubagentRunStatus::Failed, None, Vec::new(), None)
+            .unwrap();
+
+        let statuses: Vec<SubagentRunStatus> = std::iter::from_fn(|| events.try_recv().ok())
+            .map(|record| record.status)
+            .collect();
+        assert_eq!(
+            statuses,
+            vec![
+                SubagentRunStatus::Queued,
+                SubagentRunStatus::Running,
+                SubagentRunStatus::Failed,
+            ]
+        );
+    }
 }
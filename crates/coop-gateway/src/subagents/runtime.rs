@@ -5,7 +5,7 @@ use serde_json::json;
 use std::collections::BTreeSet;
 use std::path::PathBuf;
 use std::sync::{Arc, Mutex, Weak};
-use tokio::sync::{Notify, mpsc, oneshot};
+use tokio::sync::{Notify, broadcast, mpsc, oneshot};
 use tokio_util::sync::CancellationToken;
 use tracing::{Instrument, debug, info_span};
 use uuid::Uuid;
@@ -113,6 +113,11 @@ impl SubagentManager {
         self.registry.list_recent()
     }
 
+    /// Receive each run record as it is queued, started and finished.
+    pub(crate) fn subscribe_runs(&self) -> broadcast::Receiver<SubagentRunRecord> {
+        self.registry.subscribe()
+    }
+
     pub(crate) fn inspect_run(&self, run_id: &str) -> Result<SubagentRunRecord> {
         let run_id = parse_run_id(Some(run_id))?;
         self.registry
@@ -733,7 +738,7 @@ async fn announce_completion(
         gateway.inject_pending_inbound(parent_session, content);
     } else {
         gateway.append_message(parent_session, Message::user().with_text(content.clone()));
-        if let Some((channel, target)) = signal_delivery_target(parent_session)
+        if let Some((channel, target)) = chat_delivery_target(parent_session)
             && let Some(delivery) = manager.delivery_sender()
             && let Err(error) = delivery
                 .send(OutboundMessage {
@@ -752,19 +757,16 @@ async fn announce_completion(
     }
 }
 
-fn signal_delivery_target(parent_session: &SessionKey) -> Option<(String, String)> {
-    match &parent_session.kind {
-        SessionKind::Dm(identity) => identity
-            .strip_prefix("signal:")
-            .map(|target| ("signal".to_owned(), target.to_owned())),
-        SessionKind::Group(group_id) => group_id
-            .strip_prefix("signal:")
-            .map(|target| ("signal".to_owned(), target.to_owned())),
+fn chat_delivery_target(parent_session: &SessionKey) -> Option<(String, String)> {
+    let identity = match &parent_session.kind {
+        SessionKind::Dm(identity) | SessionKind::Group(identity) => identity,
         SessionKind::Main
         | SessionKind::Isolated(_)
         | SessionKind::Cron(_)
-        | SessionKind::Subagent(_) => None,
-    }
+        | SessionKind::Subagent(_) => return None,
+    };
+    let (channel, target) = identity.split_once(':')?;
+    matches!(channel, "signal" | "telegram").then(|| (channel.to_owned(), target.to_owned()))
 }
 
 fn finish_wait(
@@ -0,0 +1,343 @@
+use anyhow::Result;
+use coop_channels::{TelegramAction, TelegramChannel};
+use coop_core::{Channel, InboundKind, InboundMessage, OutboundMessage, SessionKey, TurnEvent};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+
+use crate::config::MidTurnMessages;
+use crate::router::MessageRouter;
+
+pub(crate) async fn run_telegram_loop(
+    mut telegram_channel: TelegramChannel,
+    router: Arc<MessageRouter>,
+) -> Result<()> {
+    tracing::info!(
+        bot = telegram_channel.bot_username(),
+        "telegram loop listening"
+    );
+
+    // Per-session turn tracking: each session can have one active turn.
+    // Different sessions run concurrently.
+    let mut active_turns: HashMap<SessionKey, JoinHandle<Result<()>>> = HashMap::new();
+
+    // Messages queued for dispatch after the current turn completes
+    // (only used in `MidTurnMessages::Queue` mode).
+    let mut queued_inbound: HashMap<SessionKey, Vec<(InboundMessage, String)>> = HashMap::new();
+
+    let action_tx = telegram_channel.action_sender();
+
+    loop {
+        let inbound = Channel::recv(&mut telegram_channel).await?;
+        let Some(target) = telegram_reply_target(&inbound) else {
+            continue;
+        };
+
+        // Commands are handled immediately, even while a turn is running.
+        if inbound.kind == InboundKind::Command {
+            trace_telegram_inbound("telegram command dispatched", &inbound);
+            dispatch_command(&action_tx, &router, &inbound, &target).await?;
+            continue;
+        }
+
+        trace_telegram_inbound("telegram inbound dispatched", &inbound);
+
+        // Clean up completed turns (finished or panicked)
+        active_turns.retain(|_, task| !task.is_finished());
+
+        let decision = router.route(&inbound);
+
+        if active_turns.contains_key(&decision.session_key) {
+            match router.mid_turn_messages_mode("telegram") {
+                MidTurnMessages::Inject => {
+                    tracing::info!(
+                        session = %decision.session_key,
+                        content_len = inbound.content.len(),
+                        "injecting mid-turn message into active session"
+                    );
+                    router.inject_pending_inbound(&decision.session_key, inbound.content.clone());
+                }
+                MidTurnMessages::Queue => {
+                    tracing::info!(
+                        session = %decision.session_key,
+                        content_len = inbound.content.len(),
+                        "queuing message for after current turn completes"
+                    );
+                    queued_inbound
+                        .entry(decision.session_key.clone())
+                        .or_default()
+                        .push((inbound, target));
+                }
+            }
+            continue;
+        }
+
+        // Dispatch queued messages for sessions whose turns just finished.
+        drain_queued_inbound(&mut queued_inbound, &mut active_turns, &action_tx, &router);
+
+        let router_clone = Arc::clone(&router);
+        let action_tx = action_tx.clone();
+        active_turns.insert(
+            decision.session_key,
+            tokio::spawn(async move {
+                dispatch_telegram_turn(&action_tx, router_clone.as_ref(), &inbound, &target).await
+            }),
+        );
+    }
+}
+
+/// Dispatch a command immediately, sending the response back to Telegram.
+async fn dispatch_command(
+    action_tx: &mpsc::Sender<TelegramAction>,
+    router: &MessageRouter,
+    inbound: &InboundMessage,
+    target: &str,
+) -> Result<()> {
+    let (event_tx, mut event_rx) = mpsc::channel(64);
+    let router = router.clone();
+    let message = inbound.clone();
+    let dispatch_task = tokio::spawn(async move { router.dispatch(&message, event_tx).await });
+
+    let mut text = String::new();
+    while let Some(event) = event_rx.recv().await {
+        match event {
+            TurnEvent::TextDelta(delta) => text.push_str(&delta),
+            TurnEvent::Done(_) => break,
+            _ => {}
+        }
+    }
+
+    if !text.trim().is_empty() {
+        send_text(action_tx, target, text).await?;
+    }
+
+    match dispatch_task.await {
+        Ok(result) => result.map(|_| ()),
+        Err(error) => anyhow::bail!("router task failed: {error}"),
+    }
+}
+
+async fn dispatch_telegram_turn(
+    action_tx: &mpsc::Sender<TelegramAction>,
+    router: &MessageRouter,
+    inbound: &InboundMessage,
+    target: &str,
+) -> Result<()> {
+    let verbose = router.channel_verbose("telegram");
+    router
+        .dispatch_to_channel(inbound, verbose, |content| async move {
+            send_text(action_tx, target, content).await
+        })
+        .await
+}
+
+async fn send_text(
+    action_tx: &mpsc::Sender<TelegramAction>,
+    target: &str,
+    content: String,
+) -> Result<()> {
+    action_tx
+        .send(TelegramAction::SendText(OutboundMessage {
+            channel: "telegram".to_owned(),
+            target: target.to_owned(),
+            content,
+        }))
+        .await
+        .map_err(|_send_err| anyhow::anyhow!("telegram action channel closed"))
+}
+
+/// Dispatch the oldest queued message for each session whose turn finished.
+fn drain_queued_inbound(
+    queued: &mut HashMap<SessionKey, Vec<(InboundMessage, String)>>,
+    active_turns: &mut HashMap<SessionKey, JoinHandle<Result<()>>>,
+    action_tx: &mpsc::Sender<TelegramAction>,
+    router: &Arc<MessageRouter>,
+) {
+    let ready: Vec<SessionKey> = queued
+        .keys()
+        .filter(|k| !active_turns.contains_key(k))
+        .cloned()
+        .collect();
+
+    for key in ready {
+        let Some(messages) = queued.get_mut(&key) else {
+            continue;
+        };
+        if messages.is_empty() {
+            queued.remove(&key);
+            continue;
+        }
+
+        let (inbound, target) = messages.remove(0);
+        if messages.is_empty() {
+            queued.remove(&key);
+        }
+
+        tracing::info!(
+            session = %key,
+            remaining = queued.get(&key).map_or(0, Vec::len),
+            "dispatching queued mid-turn message"
+        );
+
+        let router_clone = Arc::clone(router);
+        let action_tx = action_tx.clone();
+        active_turns.insert(
+            key,
+            tokio::spawn(async move {
+                dispatch_telegram_turn(&action_tx, router_clone.as_ref(), &inbound, &target).await
+            }),
+        );
+    }
+}
+
+fn trace_telegram_inbound(message: &'static str, inbound: &InboundMessage) {
+    tracing::debug!(
+        telegram.sender = %inbound.sender,
+        telegram.chat_id = ?inbound.chat_id,
+        telegram.content_len = inbound.content.len(),
+        "{message}"
+    );
+}
+
+fn telegram_reply_target(msg: &InboundMessage) -> Option<String> {
+    if let Some(reply_to) = &msg.reply_to {
+        return Some(reply_to.clone());
+    }
+    if msg.is_group {
+        return msg.chat_id.clone();
+    }
+    Some(msg.sender.clone())
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use coop_channels::{MockBotApi, TelegramSettings};
+    use coop_core::fakes::FakeProvider;
+    use coop_core::tools::DefaultExecutor;
+    use coop_core::{SessionKind, TrustLevel};
+    use std::collections::HashSet;
+    use std::time::Duration;
+
+    use crate::config::{Config, shared_config};
+    use crate::gateway::Gateway;
+    use crate::provider_registry::ProviderRegistry;
+    use crate::router::route_message;
+
+    const WAIT: Duration = Duration::from_secs(5);
+
+    fn test_config() -> Config {
+        toml::from_str(
+            r#"
+[agent]
+id = "coop"
+model = "test-model"
+
+[[users]]
+name = "alice"
+trust = "full"
+match = ["telegram:42"]
+
+[[groups]]
+match = ["telegram:group:-100123"]
+trigger = "always"
+"#,
+        )
+        .unwrap()
+    }
+
+    fn telegram_inbound(sender: &str, chat_id: Option<&str>) -> InboundMessage {
+        InboundMessage {
+            channel: "telegram".to_owned(),
+            sender: sender.to_owned(),
+            content: "hello".to_owned(),
+            chat_id: chat_id.map(ToOwned::to_owned),
+            is_group: chat_id.is_some(),
+            timestamp: chrono::Utc::now(),
+            reply_to: Some(chat_id.unwrap_or(sender).to_owned()),
+            kind: InboundKind::Text,
+            message_timestamp: None,
+            message_id: None,
+            group_revision: None,
+        }
+    }
+
+    #[test]
+    fn routes_telegram_dms_and_groups_to_separate_sessions() {
+        let config = test_config();
+
+        let dm = route_message(&telegram_inbound("42", None), &config);
+        assert_eq!(
+            dm.session_key.kind,
+            SessionKind::Dm("telegram:42".to_owned())
+        );
+        assert_eq!(dm.trust, TrustLevel::Full);
+        assert_eq!(dm.user_name.as_deref(), Some("alice"));
+
+        let group = route_message(&telegram_inbound("42", Some("group:-100123")), &config);
+        assert_eq!(
+            group.session_key.kind,
+            SessionKind::Group("telegram:group:-100123".to_owned())
+        );
+    }
+
+    #[test]
+    fn reply_target_falls_back_to_chat_then_sender() {
+        let mut msg = telegram_inbound("42", Some("group:-100123"));
+        msg.reply_to = None;
+        assert_eq!(
+            telegram_reply_target(&msg).as_deref(),
+            Some("group:-100123")
+        );
+
+        let mut msg = telegram_inbound("42", None);
+        msg.reply_to = None;
+        assert_eq!(telegram_reply_target(&msg).as_deref(), Some("42"));
+    }
+
+    #[tokio::test]
+    async fn replies_to_direct_messages() {
+        let workspace = tempfile::tempdir().unwrap();
+        std::fs::write(workspace.path().join("SOUL.md"), "You are a test agent.").unwrap();
+        let shared = shared_config(test_config());
+        let gateway = Arc::new(
+            Gateway::new(
+                Arc::clone(&shared),
+                workspace.path().to_path_buf(),
+                ProviderRegistry::new(Arc::new(FakeProvider::new("hi from coop"))),
+                Arc::new(DefaultExecutor::new()),
+                None,
+                None,
+            )
+            .unwrap(),
+        );
+        let router = Arc::new(MessageRouter::new(shared, gateway));
+
+        let mock = MockBotApi::start().await.unwrap();
+        let mut settings = TelegramSettings::new("123:test-token", workspace.path());
+        settings.api_base_url = mock.api_base_url().to_owned();
+        settings.poll_timeout = Duration::from_secs(1);
+        settings.trusted_senders = HashSet::from(["42".to_owned()]);
+        let channel = TelegramChannel::connect(settings).await.unwrap();
+        let loop_task = tokio::spawn(run_telegram_loop(channel, router));
+
+        mock.push_update(serde_json::json!({
+            "message": {
+                "message_id": 5,
+                "from": {"id": 42, "is_bot": false, "first_name": "Alice"},
+                "chat": {"id": 42, "type": "private"},
+                "date": 0,
+                "text": "hello"
+            }
+        }));
+
+        let sent = mock.wait_for_calls("sendMessage", 1, WAIT).await;
+        assert_eq!(
+            sent[0].params,
+            serde_json::json!({"chat_id": 42, "text": "hi from coop"})
+        );
+        loop_task.abort();
+    }
+}
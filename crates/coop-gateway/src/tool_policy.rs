@@ -0,0 +1,339 @@
+use std::sync::LazyLock;
+
+use coop_core::{SessionKey, SessionKind, ToolDef, TrustLevel};
+
+use crate::config::{ToolAccessRule, ToolPolicyConfig};
+
+impl ToolAccessRule {
+    pub(crate) fn permits(&self, tool: &str) -> bool {
+        if self
+            .deny
+            .iter()
+            .any(|pattern| pattern_matches(pattern, tool))
+        {
+            return false;
+        }
+        self.allow
+            .as_ref()
+            .is_none_or(|allow| allow.iter().any(|pattern| pattern_matches(pattern, tool)))
+    }
+
+    pub(crate) fn grants(&self, tool: &str) -> bool {
+        self.grant
+            .iter()
+            .any(|pattern| pattern_matches(pattern, tool))
+    }
+}
+
+/// Gateway administration and job scheduling, kept from everyone below
+/// full trust. No configured rule can grant these; the tools check it too.
+const ADMIN_TOOLS: &[&str] = &["config_*", "cron_*"];
+
+/// Tools that run commands or change workspace files.
+const EXEC_TOOLS: &[&str] = &["bash", "write_file", "edit_file"];
+
+/// What public senders may use: reading the workspace and the web.
+const PUBLIC_TOOLS: &[&str] = &["read_file", "web_search", "web_fetch"];
+
+static INNER_DEFAULT: LazyLock<ToolAccessRule> = LazyLock::new(|| ToolAccessRule {
+    allow: None,
+    deny: to_patterns(ADMIN_TOOLS),
+    grant: Vec::new(),
+});
+
+static FAMILIAR_DEFAULT: LazyLock<ToolAccessRule> = LazyLock::new(|| ToolAccessRule {
+    allow: None,
+    deny: to_patterns(ADMIN_TOOLS.iter().chain(EXEC_TOOLS)),
+    grant: Vec::new(),
+});
+
+static PUBLIC_DEFAULT: LazyLock<ToolAccessRule> = LazyLock::new(|| ToolAccessRule {
+    allow: Some(to_patterns(PUBLIC_TOOLS)),
+    deny: Vec::new(),
+    grant: Vec::new(),
+});
+
+static UNRESTRICTED: LazyLock<ToolAccessRule> = LazyLock::new(ToolAccessRule::default);
+
+fn to_patterns<'a>(patterns: impl IntoIterator<Item = &'a &'a str>) -> Vec<String> {
+    patterns.into_iter().map(|&p| p.to_owned()).collect()
+}
+
+/// Built-in access for a trust level, which `[tools.policy.<trust>]`
+/// narrows or extends: full and owner get everything, inner loses gateway
+/// and cron tools, familiar also loses exec, public gets read and web only.
+pub(crate) fn default_rule(trust: TrustLevel) -> &'static ToolAccessRule {
+    match trust {
+        TrustLevel::Owner | TrustLevel::Full => &UNRESTRICTED,
+        TrustLevel::Inner => &INNER_DEFAULT,
+        TrustLevel::Familiar => &FAMILIAR_DEFAULT,
+        TrustLevel::Public => &PUBLIC_DEFAULT,
+    }
+}
+
+/// Whether `[tools.policy]` lets a session at `trust` use `tool`.
+pub(crate) fn tool_allowed(
+    policy: &ToolPolicyConfig,
+    session_key: &SessionKey,
+    trust: TrustLevel,
+    tool: &str,
+) -> bool {
+    if trust > TrustLevel::Full && is_admin_tool(tool) {
+        return false;
+    }
+    let by_default = default_rule(trust).permits(tool);
+    let permitted = match policy.configured_for_trust(trust) {
+        Some(rule) => (by_default || rule.grants(tool)) && rule.permits(tool),
+        None => by_default,
+    };
+    if !permitted {
+        return false;
+    }
+
+    let SessionKind::Group(group_id) = &session_key.kind else {
+        return true;
+    };
+    policy
+        .groups
+        .iter()
+        .filter(|group| {
+            group
+                .r#match
+                .iter()
+                .any(|pattern| pattern == group_id || pattern == "*")
+        })
+        .all(|group| group.rule.permits(tool))
+}
+
+pub(crate) fn is_admin_tool(tool: &str) -> bool {
+    ADMIN_TOOLS
+        .iter()
+        .any(|pattern| pattern_matches(pattern, tool))
+}
+
+/// Drop tool definitions the policy does not allow for this session.
+pub(crate) fn filter_tool_defs(
+    policy: &ToolPolicyConfig,
+    session_key: &SessionKey,
+    trust: TrustLevel,
+    tool_defs: Vec<ToolDef>,
+) -> Vec<ToolDef> {
+    tool_defs
+        .into_iter()
+        .filter(|tool| tool_allowed(policy, session_key, trust, &tool.name))
+        .collect()
+}
+
+/// Match a tool name against a pattern where `*` matches any run of
+/// characters, e.g. `mcp_github_*` or `*_send`.
+pub(crate) fn pattern_matches(pattern: &str, tool: &str) -> bool {
+    let Some((prefix, rest)) = pattern.split_once('*') else {
+        return pattern == tool;
+    };
+    let Some(mut remaining) = tool.strip_prefix(prefix) else {
+        return false;
+    };
+
+    let mut parts = rest.split('*').peekable();
+    while let Some(part) = parts.next() {
+        if parts.peek().is_none() {
+            return remaining.ends_with(part);
+        }
+        match remaining.find(part) {
+            Some(index) => remaining = &remaining[index + part.len()..],
+            None => return false,
+        }
+    }
+    true
+}
+
+/// Tool names are ASCII identifiers; anything else in a pattern is a typo.
+pub(crate) fn is_valid_pattern(pattern: &str) -> bool {
+    !pattern.is_empty()
+        && pattern
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.' | '*'))
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+
+    fn policy(toml_str: &str) -> ToolPolicyConfig {
+        let config: Config = toml::from_str(&format!(
+            "[agent]\nid = \"coop\"\nmodel = \"test\"\n\n{toml_str}"
+        ))
+        .unwrap();
+        config.tools.policy
+    }
+
+    fn session(kind: SessionKind) -> SessionKey {
+        SessionKey {
+            agent_id: "coop".to_owned(),
+            kind,
+        }
+    }
+
+    #[test]
+    fn patterns_support_wildcards() {
+        assert!(pattern_matches("bash", "bash"));
+        assert!(!pattern_matches("bash", "bash_extra"));
+        assert!(pattern_matches("mcp_github_*", "mcp_github_create_issue"));
+        assert!(pattern_matches("*_send", "signal_send"));
+        assert!(pattern_matches("memory_*_get", "memory_people_get"));
+        assert!(!pattern_matches("memory_*_get", "memory_people_search"));
+        assert!(pattern_matches("*", "anything"));
+        assert!(is_valid_pattern("mcp_fs-server_*"));
+        assert!(!is_valid_pattern("bash tool"));
+        assert!(!is_valid_pattern(""));
+    }
+
+    #[test]
+    fn defaults_follow_trust_levels() {
+        let policy = ToolPolicyConfig::default();
+        let dm = session(SessionKind::Dm("signal:alice".to_owned()));
+        let allowed = |trust, tool| tool_allowed(&policy, &dm, trust, tool);
+
+        assert!(allowed(TrustLevel::Full, "config_write"));
+        assert!(allowed(TrustLevel::Full, "bash"));
+
+        assert!(allowed(TrustLevel::Inner, "bash"));
+        assert!(allowed(TrustLevel::Inner, "memory_search"));
+        assert!(!allowed(TrustLevel::Inner, "config_read"));
+        assert!(!allowed(TrustLevel::Inner, "cron_manage"));
+
+        assert!(allowed(TrustLevel::Familiar, "read_file"));
+        assert!(allowed(TrustLevel::Familiar, "memory_search"));
+        assert!(!allowed(TrustLevel::Familiar, "bash"));
+        assert!(!allowed(TrustLevel::Familiar, "write_file"));
+        assert!(!allowed(TrustLevel::Familiar, "edit_file"));
+
+        assert!(allowed(TrustLevel::Public, "read_file"));
+        assert!(allowed(TrustLevel::Public, "web_fetch"));
+        assert!(!allowed(TrustLevel::Public, "memory_search"));
+        assert!(!allowed(TrustLevel::Public, "bash"));
+    }
+
+    #[test]
+    fn configured_rules_narrow_the_default() {
+        let policy = policy(
+            r#"
+[tools.policy.familiar]
+deny = ["web_fetch"]
+
+[tools.policy.public]
+allow = ["read_file", "memory_search"]
+"#,
+        );
+        let dm = session(SessionKind::Dm("signal:carol".to_owned()));
+        let allowed = |trust, tool| tool_allowed(&policy, &dm, trust, tool);
+
+        assert!(!allowed(TrustLevel::Familiar, "web_fetch"));
+        assert!(allowed(TrustLevel::Familiar, "read_file"));
+        for tool in [
+            "bash",
+            "write_file",
+            "edit_file",
+            "config_write",
+            "cron_manage",
+        ] {
+            assert!(!allowed(TrustLevel::Familiar, tool), "{tool}");
+        }
+
+        assert!(allowed(TrustLevel::Public, "read_file"));
+        assert!(!allowed(TrustLevel::Public, "web_search"));
+        assert!(!allowed(TrustLevel::Public, "memory_search"));
+    }
+
+    #[test]
+    fn grants_widen_the_default_but_not_to_admin_tools() {
+        let policy = policy(
+            r#"
+[tools.policy.inner]
+allow = ["*"]
+grant = ["config_*", "cron_*"]
+
+[tools.policy.familiar]
+grant = ["bash", "config_write", "cron_manage"]
+
+[tools.policy.public]
+grant = ["memory_search"]
+"#,
+        );
+        let dm = session(SessionKind::Dm("signal:carol".to_owned()));
+        let allowed = |trust, tool| tool_allowed(&policy, &dm, trust, tool);
+
+        assert!(allowed(TrustLevel::Familiar, "bash"));
+        assert!(!allowed(TrustLevel::Familiar, "write_file"));
+        assert!(allowed(TrustLevel::Public, "memory_search"));
+        assert!(allowed(TrustLevel::Public, "web_fetch"));
+
+        for trust in [TrustLevel::Inner, TrustLevel::Familiar, TrustLevel::Public] {
+            for tool in ["config_read", "config_write", "cron_manage", "cron_trigger"] {
+                assert!(!allowed(trust, tool), "{trust:?} {tool}");
+            }
+        }
+        assert!(allowed(TrustLevel::Full, "config_write"));
+    }
+
+    #[test]
+    fn trust_rules_apply_to_the_effective_trust() {
+        let policy = policy(
+            r#"
+[tools.policy.familiar]
+deny = ["bash", "write_file"]
+
+[tools.policy.public]
+allow = ["read_file", "web_*"]
+deny = ["web_fetch"]
+"#,
+        );
+        let dm = session(SessionKind::Dm("signal:bob".to_owned()));
+
+        assert!(tool_allowed(&policy, &dm, TrustLevel::Inner, "bash"));
+        assert!(!tool_allowed(&policy, &dm, TrustLevel::Familiar, "bash"));
+        assert!(tool_allowed(
+            &policy,
+            &dm,
+            TrustLevel::Familiar,
+            "read_file"
+        ));
+        assert!(tool_allowed(&policy, &dm, TrustLevel::Public, "web_search"));
+        assert!(!tool_allowed(&policy, &dm, TrustLevel::Public, "web_fetch"));
+        assert!(!tool_allowed(
+            &policy,
+            &dm,
+            TrustLevel::Public,
+            "memory_search"
+        ));
+    }
+
+    #[test]
+    fn group_rules_narrow_matching_groups_only() {
+        let policy = policy(
+            r#"
+[[tools.policy.groups]]
+match = ["signal:group:abc"]
+deny = ["signal_*"]
+"#,
+        );
+        let group = session(SessionKind::Group("signal:group:abc".to_owned()));
+        let other = session(SessionKind::Group("signal:group:def".to_owned()));
+        let defs = vec![
+            ToolDef::new("signal_send", "", serde_json::json!({})),
+            ToolDef::new("read_file", "", serde_json::json!({})),
+        ];
+
+        let names: Vec<String> = filter_tool_defs(&policy, &group, TrustLevel::Full, defs.clone())
+            .into_iter()
+            .map(|tool| tool.name)
+            .collect();
+        assert_eq!(names, vec!["read_file"]);
+        assert_eq!(
+            filter_tool_defs(&policy, &other, TrustLevel::Full, defs).len(),
+            2
+        );
+    }
+}
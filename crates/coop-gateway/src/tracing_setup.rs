@@ -1,16 +1,53 @@
+use coop_core::redaction::{self, RedactionMode};
+use std::borrow::Cow;
 use std::fs::{self, File, OpenOptions};
 use std::io::{self, Write};
 use std::path::PathBuf;
-use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Duration;
+use tokio_util::sync::CancellationToken;
 use tracing_appender::non_blocking::WorkerGuard;
+use tracing_subscriber::fmt::MakeWriter;
 use tracing_subscriber::fmt::format::FmtSpan;
 use tracing_subscriber::prelude::*;
 use tracing_subscriber::{EnvFilter, Registry, fmt};
 
+#[cfg(feature = "otel")]
+use opentelemetry::trace::Status;
+#[cfg(feature = "otel")]
+use opentelemetry::trace::TracerProvider as _;
+#[cfg(feature = "otel")]
+use opentelemetry::{Array, KeyValue, StringValue, Value};
+#[cfg(feature = "otel")]
+use opentelemetry_otlp::{Protocol, SpanExporter, WithExportConfig};
+#[cfg(feature = "otel")]
+use opentelemetry_sdk::Resource;
+#[cfg(feature = "otel")]
+use opentelemetry_sdk::error::OTelSdkResult;
+#[cfg(feature = "otel")]
+use opentelemetry_sdk::trace::{SdkTracerProvider, SpanData};
+#[cfg(feature = "otel")]
+use tracing_subscriber::{Layer, registry::LookupSpan};
+
 /// Guard that must be held alive for non-blocking writer flush on shutdown.
-/// When dropped, buffered JSONL lines are flushed to disk.
+/// When dropped, buffered JSONL lines are flushed to disk and pending OTLP
+/// span batches are exported.
 pub(crate) struct TracingGuard {
     _guards: Vec<WorkerGuard>,
+    #[cfg(feature = "otel")]
+    otlp_provider: Option<SdkTracerProvider>,
+}
+
+#[cfg(feature = "otel")]
+impl Drop for TracingGuard {
+    fn drop(&mut self) {
+        if let Some(provider) = self.otlp_provider.take()
+            && let Err(error) = provider.shutdown()
+        {
+            tracing::warn!(error = %error, "failed to flush OTLP spans");
+        }
+    }
 }
 
 /// Rotating appender that always writes to a stable path (e.g. `traces.jsonl`).
@@ -196,6 +233,120 @@ impl Write for RotatingAppender {
     }
 }
 
+// ---------------------------------------------------------------------------
+// Redaction
+// ---------------------------------------------------------------------------
+
+type ModeSource = Box<dyn Fn() -> RedactionMode + Send + Sync>;
+
+/// Reads `[redaction] mode` from the live config once it is bound. Tracing
+/// starts before the config loads, so until then traces are masked.
+static TRACE_MODE: OnceLock<ModeSource> = OnceLock::new();
+
+/// Secrets found in trace output since the last report. Counted rather than
+/// logged: logging from the writer would re-enter the subscriber.
+static TRACE_REDACTIONS: AtomicU64 = AtomicU64::new(0);
+
+/// Apply the configured redaction mode to trace output from now on.
+/// `mode` is called per event, so it should read a reloadable config.
+pub(crate) fn bind_redaction_mode(mode: impl Fn() -> RedactionMode + Send + Sync + 'static) {
+    if TRACE_MODE.set(Box::new(mode)).is_err() {
+        tracing::debug!("trace redaction mode already bound");
+    }
+}
+
+fn trace_redaction_mode() -> RedactionMode {
+    TRACE_MODE.get().map_or(RedactionMode::Mask, |mode| mode())
+}
+
+/// Log how many secrets trace redaction found, every `interval` that found
+/// any, until `shutdown`.
+pub(crate) fn spawn_redaction_report(
+    interval: Duration,
+    shutdown: CancellationToken,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        ticker.tick().await;
+        loop {
+            tokio::select! {
+                () = shutdown.cancelled() => break,
+                _ = ticker.tick() => {}
+            }
+            let count = TRACE_REDACTIONS.swap(0, Ordering::Relaxed);
+            if count > 0 {
+                tracing::warn!(
+                    site = "trace",
+                    mode = trace_redaction_mode().as_str(),
+                    count,
+                    "secret detected"
+                );
+            }
+        }
+    })
+}
+
+/// Apply `mode` to one piece of trace output, counting the secrets found.
+/// `None` when `Block` drops it.
+fn redact_trace(text: &str, mode: RedactionMode) -> Option<Cow<'_, str>> {
+    let count = |found: usize| {
+        TRACE_REDACTIONS.fetch_add(found as u64, Ordering::Relaxed);
+    };
+    match mode {
+        RedactionMode::Mask => {
+            let (masked, found) = redaction::mask_secrets(text);
+            count(found.len());
+            Some(if found.is_empty() {
+                Cow::Borrowed(text)
+            } else {
+                Cow::Owned(masked)
+            })
+        }
+        RedactionMode::Warn => {
+            count(redaction::find_secrets(text).len());
+            Some(Cow::Borrowed(text))
+        }
+        RedactionMode::Block => {
+            let found = redaction::find_secrets(text).len();
+            count(found);
+            (found == 0).then_some(Cow::Borrowed(text))
+        }
+    }
+}
+
+/// Applies `[redaction] mode` to each formatted event before it reaches the
+/// inner writer: `mask` masks secrets, `block` drops the event, `warn` keeps
+/// it. Either way the secrets are counted for [`spawn_redaction_report`].
+struct Redacting<M>(M);
+
+impl<'a, M: MakeWriter<'a>> MakeWriter<'a> for Redacting<M> {
+    type Writer = RedactingWriter<M::Writer>;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        RedactingWriter(self.0.make_writer())
+    }
+}
+
+struct RedactingWriter<W>(W);
+
+impl<W: Write> Write for RedactingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match std::str::from_utf8(buf) {
+            Ok(text) => {
+                if let Some(text) = redact_trace(text, trace_redaction_mode()) {
+                    self.0.write_all(text.as_bytes())?;
+                }
+            }
+            Err(_) => self.0.write_all(buf)?,
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.0.flush()
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Subscriber initialisation
 // ---------------------------------------------------------------------------
@@ -205,6 +356,11 @@ impl Write for RotatingAppender {
 /// Layers:
 /// 1. Console — enabled only when `console` is true (daemon mode), filtered by `RUST_LOG`
 /// 2. JSONL file — activated by `COOP_TRACE_FILE` env var, filtered at `debug`
+/// 3. OTLP export — built with `--features otel` and activated by
+///    `OTEL_EXPORTER_OTLP_ENDPOINT` (or `OTEL_EXPORTER_OTLP_TRACES_ENDPOINT`)
+///
+/// Console and JSONL output and OTLP spans follow `[redaction] mode` once
+/// [`bind_redaction_mode`] is called, and are masked until then.
 ///
 /// TUI commands (`chat`, `attach`) pass `console: false` to avoid polluting the terminal.
 /// Returns a guard that must be held in `main()` to ensure buffered writes flush.
@@ -226,6 +382,7 @@ pub(crate) fn init_with_trace_file(console: bool, trace_file: Option<PathBuf>) -
             .compact()
             .without_time()
             .with_target(false)
+            .with_writer(Redacting(io::stdout))
             .with_filter(filter)
     });
 
@@ -246,7 +403,7 @@ pub(crate) fn init_with_trace_file(console: bool, trace_file: Option<PathBuf>) -
         Some(
             fmt::layer()
                 .json()
-                .with_writer(non_blocking)
+                .with_writer(Redacting(non_blocking))
                 .with_span_list(true)
                 .with_file(true)
                 .with_line_number(true)
@@ -257,10 +414,345 @@ pub(crate) fn init_with_trace_file(console: bool, trace_file: Option<PathBuf>) -
         None
     };
 
+    #[cfg(feature = "otel")]
+    let otlp = OtlpSettings::from_env().map(|settings| {
+        settings.and_then(|settings| {
+            let provider = otlp_tracer_provider(&settings)?;
+            Ok((settings, provider))
+        })
+    });
+    #[cfg(feature = "otel")]
+    let otlp_layer = otlp
+        .as_ref()
+        .and_then(|result| result.as_ref().ok())
+        .map(|(_, provider)| otlp_layer(provider));
+    #[cfg(not(feature = "otel"))]
+    let otlp_layer: Option<tracing_subscriber::layer::Identity> = None;
+
     Registry::default()
+        .with(otlp_layer)
         .with(console_layer)
         .with(jsonl_layer)
         .init();
 
-    TracingGuard { _guards: guards }
+    #[cfg(feature = "otel")]
+    let otlp_provider = match otlp {
+        Some(Ok((settings, provider))) => {
+            tracing::info!(
+                protocol = settings.protocol.as_str(),
+                "OTLP trace export enabled"
+            );
+            Some(provider)
+        }
+        Some(Err(error)) => {
+            tracing::warn!(error = %error, "OTLP trace export disabled");
+            None
+        }
+        None => None,
+    };
+
+    TracingGuard {
+        _guards: guards,
+        #[cfg(feature = "otel")]
+        otlp_provider,
+    }
+}
+
+// ---------------------------------------------------------------------------
+// OTLP export
+// ---------------------------------------------------------------------------
+
+/// Wire protocol used to reach the OTLP collector.
+#[cfg(feature = "otel")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum OtlpProtocol {
+    /// OTLP/gRPC, conventionally on port 4317.
+    Grpc,
+    /// OTLP/HTTP with protobuf bodies, conventionally on port 4318.
+    HttpProtobuf,
+}
+
+#[cfg(feature = "otel")]
+impl OtlpProtocol {
+    /// Parse an `OTEL_EXPORTER_OTLP_PROTOCOL` value.
+    pub(crate) fn parse(value: &str) -> anyhow::Result<Self> {
+        match value.trim() {
+            "grpc" => Ok(Self::Grpc),
+            "http/protobuf" => Ok(Self::HttpProtobuf),
+            other => anyhow::bail!(
+                "unsupported OTLP protocol '{other}' (expected 'grpc' or 'http/protobuf')"
+            ),
+        }
+    }
+
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            Self::Grpc => "grpc",
+            Self::HttpProtobuf => "http/protobuf",
+        }
+    }
+}
+
+#[cfg(feature = "otel")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct OtlpSettings {
+    /// Collector endpoint, used verbatim. `None` leaves endpoint resolution
+    /// to the exporter, which reads the standard `OTEL_EXPORTER_OTLP_*`
+    /// variables and appends `/v1/traces` for HTTP.
+    pub(crate) endpoint: Option<String>,
+    pub(crate) protocol: OtlpProtocol,
+}
+
+#[cfg(feature = "otel")]
+impl OtlpSettings {
+    /// Export is enabled only when an OTLP endpoint variable is set. The
+    /// protocol defaults to gRPC, matching `just trace-jaeger`.
+    fn from_env() -> Option<anyhow::Result<Self>> {
+        let non_empty = |name: &str| {
+            std::env::var(name)
+                .ok()
+                .filter(|value| !value.trim().is_empty())
+        };
+
+        non_empty("OTEL_EXPORTER_OTLP_TRACES_ENDPOINT")
+            .or_else(|| non_empty("OTEL_EXPORTER_OTLP_ENDPOINT"))?;
+
+        let protocol = non_empty("OTEL_EXPORTER_OTLP_TRACES_PROTOCOL")
+            .or_else(|| non_empty("OTEL_EXPORTER_OTLP_PROTOCOL"))
+            .map_or(Ok(OtlpProtocol::Grpc), |value| OtlpProtocol::parse(&value));
+        Some(protocol.map(|protocol| Self {
+            endpoint: None,
+            protocol,
+        }))
+    }
+}
+
+/// Build a tracer provider that batches spans to an OTLP collector.
+///
+/// Must be called from within a Tokio runtime: the gRPC channel spawns its
+/// connection task on the current runtime.
+#[cfg(feature = "otel")]
+pub(crate) fn otlp_tracer_provider(settings: &OtlpSettings) -> anyhow::Result<SdkTracerProvider> {
+    let exporter = match settings.protocol {
+        OtlpProtocol::Grpc => {
+            let mut builder = SpanExporter::builder().with_tonic();
+            if let Some(endpoint) = &settings.endpoint {
+                builder = builder.with_endpoint(endpoint);
+            }
+            builder.build()?
+        }
+        OtlpProtocol::HttpProtobuf => {
+            let mut builder = SpanExporter::builder()
+                .with_http()
+                .with_protocol(Protocol::HttpBinary);
+            if let Some(endpoint) = &settings.endpoint {
+                builder = builder.with_endpoint(endpoint);
+            }
+            builder.build()?
+        }
+    };
+
+    let resource = Resource::builder()
+        .with_service_name("coop")
+        .with_attribute(KeyValue::new("service.version", env!("CARGO_PKG_VERSION")))
+        .build();
+
+    Ok(SdkTracerProvider::builder()
+        .with_batch_exporter(RedactingExporter(exporter))
+        .with_resource(resource)
+        .build())
+}
+
+/// Applies `[redaction] mode` to span names, attributes, events and status
+/// before the inner exporter sends them, like [`Redacting`] does for
+/// formatted output. `block` drops the whole span.
+#[cfg(feature = "otel")]
+#[derive(Debug)]
+struct RedactingExporter<E>(E);
+
+#[cfg(feature = "otel")]
+impl<E: opentelemetry_sdk::trace::SpanExporter> opentelemetry_sdk::trace::SpanExporter
+    for RedactingExporter<E>
+{
+    async fn export(&self, mut batch: Vec<SpanData>) -> OTelSdkResult {
+        let mode = trace_redaction_mode();
+        batch.retain_mut(|span| redact_span(span, mode));
+        self.0.export(batch).await
+    }
+
+    fn shutdown_with_timeout(&mut self, timeout: Duration) -> OTelSdkResult {
+        self.0.shutdown_with_timeout(timeout)
+    }
+
+    fn force_flush(&mut self) -> OTelSdkResult {
+        self.0.force_flush()
+    }
+
+    fn set_resource(&mut self, resource: &Resource) {
+        self.0.set_resource(resource);
+    }
+}
+
+/// Whether to keep `span` after redacting it.
+#[cfg(feature = "otel")]
+fn redact_span(span: &mut SpanData, mode: RedactionMode) -> bool {
+    let mut keep = redact_cow(&mut span.name, mode);
+    for attribute in &mut span.attributes {
+        keep &= redact_attribute(attribute, mode);
+    }
+    for event in &mut span.events.events {
+        keep &= redact_cow(&mut event.name, mode);
+        for attribute in &mut event.attributes {
+            keep &= redact_attribute(attribute, mode);
+        }
+    }
+    if let Status::Error { description } = &mut span.status {
+        keep &= redact_cow(description, mode);
+    }
+    keep
+}
+
+#[cfg(feature = "otel")]
+fn redact_attribute(attribute: &mut KeyValue, mode: RedactionMode) -> bool {
+    match &mut attribute.value {
+        Value::String(text) => redact_string_value(text, mode),
+        Value::Array(Array::String(items)) => {
+            let mut keep = true;
+            for text in items {
+                keep &= redact_string_value(text, mode);
+            }
+            keep
+        }
+        _ => true,
+    }
+}
+
+#[cfg(feature = "otel")]
+fn redact_string_value(text: &mut StringValue, mode: RedactionMode) -> bool {
+    let masked = match redact_trace(text.as_str(), mode) {
+        None => return false,
+        Some(Cow::Owned(masked)) => masked,
+        Some(Cow::Borrowed(_)) => return true,
+    };
+    *text = masked.into();
+    true
+}
+
+#[cfg(feature = "otel")]
+fn redact_cow(text: &mut Cow<'static, str>, mode: RedactionMode) -> bool {
+    let masked = match redact_trace(text, mode) {
+        None => return false,
+        Some(Cow::Owned(masked)) => masked,
+        Some(Cow::Borrowed(_)) => return true,
+    };
+    *text = masked.into();
+    true
+}
+
+/// Layer that turns `tracing` spans into OpenTelemetry spans. Span fields
+/// become attributes, so the `gen_ai.*` fields recorded on `agent_turn`,
+/// `provider_request` and `tool_execute` follow the GenAI semantic
+/// conventions on the collector side.
+#[cfg(feature = "otel")]
+pub(crate) fn otlp_layer<S>(provider: &SdkTracerProvider) -> impl Layer<S> + use<S>
+where
+    S: tracing::Subscriber + for<'span> LookupSpan<'span>,
+{
+    // The exporter's own transport (tonic, hyper, h2) is kept out so
+    // exporting spans never produces more spans.
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| {
+        EnvFilter::new("debug,libsignal_service=info,libsignal_protocol=warn,presage=info,presage_store_sqlite=warn,sqlx=warn,hyper=warn,hyper_util=warn,h2=warn,tonic=warn,tower=warn,reqwest=warn,reqwest_websocket=warn,opentelemetry=warn,opentelemetry_sdk=warn,opentelemetry_otlp=warn")
+    });
+    tracing_opentelemetry::layer()
+        .with_tracer(provider.tracer("coop"))
+        .with_filter(filter)
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn redacting_writer_masks_whole_events() {
+        let mut writer = RedactingWriter(Vec::new());
+        let line = br#"{"fields":{"message":"tool call","args":"{\"api_key\":\"sk-abcdefghijklmnopqrstuvwxyz\"}"}}"#;
+        writer.write_all(line).unwrap();
+        let written = String::from_utf8(writer.0).unwrap();
+        assert!(!written.contains("sk-abcdefghijklmnopqrstuvwxyz"));
+        assert!(written.contains("[redacted:api_key]"));
+    }
+
+    #[test]
+    fn trace_modes_mask_drop_or_keep_and_count() {
+        let line = "tool call password: hunter22";
+        let before = TRACE_REDACTIONS.load(Ordering::Relaxed);
+
+        assert_eq!(
+            redact_trace(line, RedactionMode::Mask).as_deref(),
+            Some("tool call password: [redacted:password]")
+        );
+        assert_eq!(redact_trace(line, RedactionMode::Block), None);
+        assert_eq!(
+            redact_trace(line, RedactionMode::Warn).as_deref(),
+            Some(line)
+        );
+        assert_eq!(
+            redact_trace("nothing here", RedactionMode::Block).as_deref(),
+            Some("nothing here")
+        );
+        assert!(TRACE_REDACTIONS.load(Ordering::Relaxed) >= before + 3);
+    }
+
+    #[cfg(feature = "otel")]
+    #[test]
+    fn redacting_exporter_masks_span_attributes_and_events() {
+        use opentelemetry::InstrumentationScope;
+        use opentelemetry::trace::{Event, SpanContext, SpanId, SpanKind};
+        use opentelemetry_sdk::trace::{SpanEvents, SpanLinks};
+        use std::time::SystemTime;
+
+        let secret = "sk-abcdefghijklmnopqrstuvwxyz";
+        let mut events = SpanEvents::default();
+        events.events.push(Event::new(
+            format!("tool call api_key={secret}"),
+            SystemTime::now(),
+            vec![KeyValue::new(
+                "args",
+                format!(r#"{{"api_key":"{secret}"}}"#),
+            )],
+            0,
+        ));
+        let mut span = SpanData {
+            span_context: SpanContext::empty_context(),
+            parent_span_id: SpanId::INVALID,
+            parent_span_is_remote: false,
+            span_kind: SpanKind::Internal,
+            name: "tool_execute".into(),
+            start_time: SystemTime::now(),
+            end_time: SystemTime::now(),
+            attributes: vec![
+                KeyValue::new("input", format!("key {secret}")),
+                KeyValue::new("gen_ai.tool.name", "bash"),
+            ],
+            dropped_attributes_count: 0,
+            events,
+            links: SpanLinks::default(),
+            status: Status::error(format!("bad key {secret}")),
+            instrumentation_scope: InstrumentationScope::builder("coop").build(),
+        };
+
+        let mut blocked = span.clone();
+        assert!(!redact_span(&mut blocked, RedactionMode::Block));
+        assert!(redact_span(&mut span, RedactionMode::Mask));
+
+        let exported = format!("{span:?}");
+        assert!(!exported.contains(secret), "{exported}");
+        assert_eq!(span.attributes[0].value.as_str(), "key [redacted:api_key]");
+        assert_eq!(span.attributes[1].value.as_str(), "bash");
+        assert_eq!(
+            span.events.events[0].name,
+            "tool call api_key=[redacted:api_key]"
+        );
+    }
 }
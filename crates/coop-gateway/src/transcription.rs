@@ -0,0 +1,357 @@
+use anyhow::{Context, Result};
+use coop_core::WorkspaceScope;
+use reqwest::multipart::{Form, Part};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use tokio::process::Command;
+use tracing::{debug, info, warn};
+
+use crate::config::{TranscriptionBackend, TranscriptionConfig};
+
+/// Audio extensions we transcribe (lowercase, without dot).
+const AUDIO_EXTENSIONS: &[&str] = &[
+    "aac", "amr", "flac", "m4a", "mp3", "oga", "ogg", "opus", "wav", "webm",
+];
+
+/// Transcripts are cached next to the audio as `<file>.transcript.txt`.
+const TRANSCRIPT_SUFFIX: &str = ".transcript.txt";
+
+/// Largest file the OpenAI transcription endpoint accepts.
+const MAX_AUDIO_BYTES: u64 = 25 * 1024 * 1024;
+
+const TRANSCRIPTION_TIMEOUT: Duration = Duration::from_mins(2);
+const DEFAULT_OPENAI_BASE_URL: &str = "https://api.openai.com/v1";
+const DEFAULT_OPENAI_MODEL: &str = "whisper-1";
+
+/// Add a `[transcript: …]` line after each `[file saved: …]` line in `text`
+/// that points at an audio file. Failures become `[transcript failed: …]`
+/// so the agent knows the audio wasn't heard.
+pub(crate) async fn transcribe_attachments(
+    config: &TranscriptionConfig,
+    text: &str,
+    scope: &WorkspaceScope,
+) -> String {
+    let mut lines = Vec::new();
+    for line in text.lines() {
+        lines.push(line.to_owned());
+        let Some(path) = saved_audio_path(line) else {
+            continue;
+        };
+        let transcript_line = match transcribe_cached(config, path, scope).await {
+            Ok(transcript) if transcript.is_empty() => {
+                "[transcript: (no speech detected)]".to_owned()
+            }
+            Ok(transcript) => format!("[transcript: {transcript}]"),
+            Err(error) => {
+                warn!(path, error = %format!("{error:#}"), "audio transcription failed");
+                format!("[transcript failed: {error}]")
+            }
+        };
+        lines.push(transcript_line);
+    }
+    lines.join("\n")
+}
+
+fn saved_audio_path(line: &str) -> Option<&str> {
+    let path = line
+        .trim()
+        .strip_prefix("[file saved: ")?
+        .strip_suffix(']')?;
+    let extension = Path::new(path).extension()?.to_str()?.to_ascii_lowercase();
+    AUDIO_EXTENSIONS
+        .contains(&extension.as_str())
+        .then_some(path)
+}
+
+fn cache_path(audio: &Path) -> PathBuf {
+    let mut name = audio.file_name().unwrap_or_default().to_os_string();
+    name.push(TRANSCRIPT_SUFFIX);
+    audio.with_file_name(name)
+}
+
+async fn transcribe_cached(
+    config: &TranscriptionConfig,
+    path: &str,
+    scope: &WorkspaceScope,
+) -> Result<String> {
+    let audio = scope.resolve_host_path_for_read(path)?;
+    let cache = cache_path(&audio);
+    if let Ok(cached) = std::fs::read_to_string(&cache) {
+        debug!(path, "using cached transcript");
+        return Ok(cached);
+    }
+
+    let size = std::fs::metadata(&audio)
+        .with_context(|| format!("failed to read {path}"))?
+        .len();
+    anyhow::ensure!(
+        size <= MAX_AUDIO_BYTES,
+        "audio file too large ({size} bytes, max {MAX_AUDIO_BYTES})"
+    );
+
+    let started = Instant::now();
+    let raw = match config.backend {
+        TranscriptionBackend::WhisperCpp => transcribe_whisper_cpp(config, &audio).await?,
+        TranscriptionBackend::Openai => {
+            let api_key_env = config.effective_api_key_env();
+            let api_key = std::env::var(api_key_env)
+                .with_context(|| format!("{api_key_env} environment variable not set"))?;
+            transcribe_openai(config, &api_key, &audio).await?
+        }
+    };
+    let transcript = raw.split_whitespace().collect::<Vec<_>>().join(" ");
+    info!(
+        path,
+        backend = ?config.backend,
+        chars = transcript.len(),
+        elapsed_ms = u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX),
+        "audio transcribed"
+    );
+
+    if let Err(error) = std::fs::write(&cache, &transcript) {
+        warn!(path = %cache.display(), error = %error, "failed to cache transcript");
+    }
+    Ok(transcript)
+}
+
+async fn transcribe_whisper_cpp(config: &TranscriptionConfig, audio: &Path) -> Result<String> {
+    let model = config
+        .model
+        .as_deref()
+        .context("transcription.model must point to a whisper.cpp model file")?;
+
+    let is_wav = audio
+        .extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("wav"));
+    let converted = if is_wav {
+        None
+    } else {
+        Some(convert_to_wav(audio).await?)
+    };
+
+    let mut command = Command::new(&config.command);
+    command
+        .arg("-m")
+        .arg(model)
+        .arg("-f")
+        .arg(converted.as_deref().unwrap_or(audio))
+        .args(["-nt", "-np"])
+        .kill_on_drop(true);
+    if let Some(ref language) = config.language {
+        command.arg("-l").arg(language);
+    }
+    let output = tokio::time::timeout(TRANSCRIPTION_TIMEOUT, command.output()).await;
+    if let Some(ref converted) = converted {
+        let _ = std::fs::remove_file(converted);
+    }
+
+    let output = output
+        .map_err(|_elapsed| anyhow::anyhow!("{} timed out", config.command))?
+        .with_context(|| format!("failed to run {}", config.command))?;
+    anyhow::ensure!(
+        output.status.success(),
+        "{} exited with {}: {}",
+        config.command,
+        output.status,
+        String::from_utf8_lossy(&output.stderr).trim()
+    );
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// whisper.cpp reads 16 kHz mono WAV; voice notes arrive as AAC or Opus.
+async fn convert_to_wav(audio: &Path) -> Result<PathBuf> {
+    let wav = std::env::temp_dir().join(format!("coop-transcribe-{}.wav", uuid::Uuid::new_v4()));
+    let output = tokio::time::timeout(
+        TRANSCRIPTION_TIMEOUT,
+        Command::new("ffmpeg")
+            .args(["-nostdin", "-loglevel", "error", "-y", "-i"])
+            .arg(audio)
+            .args(["-ar", "16000", "-ac", "1", "-c:a", "pcm_s16le"])
+            .arg(&wav)
+            .kill_on_drop(true)
+            .output(),
+    )
+    .await
+    .map_err(|_elapsed| anyhow::anyhow!("ffmpeg timed out"))?
+    .context("failed to run ffmpeg (needed to convert audio for whisper.cpp)")?;
+    anyhow::ensure!(
+        output.status.success(),
+        "ffmpeg exited with {}: {}",
+        output.status,
+        String::from_utf8_lossy(&output.stderr).trim()
+    );
+    Ok(wav)
+}
+
+async fn transcribe_openai(
+    config: &TranscriptionConfig,
+    api_key: &str,
+    audio: &Path,
+) -> Result<String> {
+    let base_url = config
+        .base_url
+        .as_deref()
+        .unwrap_or(DEFAULT_OPENAI_BASE_URL)
+        .trim_end_matches('/');
+    let endpoint = format!("{base_url}/audio/transcriptions");
+    let model = config.model.as_deref().unwrap_or(DEFAULT_OPENAI_MODEL);
+
+    let bytes = tokio::fs::read(audio)
+        .await
+        .with_context(|| format!("failed to read {}", audio.display()))?;
+    let file_name = audio.file_name().map_or_else(
+        || "audio".to_owned(),
+        |name| name.to_string_lossy().into_owned(),
+    );
+    let mut form = Form::new()
+        .text("model", model.to_owned())
+        .text("response_format", "json")
+        .part("file", Part::bytes(bytes).file_name(file_name));
+    if let Some(ref language) = config.language {
+        form = form.text("language", language.clone());
+    }
+
+    debug!(endpoint = %endpoint, model, "transcription request");
+    let response = reqwest::Client::builder()
+        .timeout(TRANSCRIPTION_TIMEOUT)
+        .build()
+        .context("failed to create transcription HTTP client")?
+        .post(&endpoint)
+        .bearer_auth(api_key)
+        .multipart(form)
+        .send()
+        .await
+        .context("transcription request failed")?;
+
+    let status = response.status();
+    let body = response.text().await.unwrap_or_default();
+    anyhow::ensure!(
+        status.is_success(),
+        "transcription endpoint error: {status}"
+    );
+    let parsed: serde_json::Value =
+        serde_json::from_str(&body).context("transcription response is not JSON")?;
+    parsed
+        .get("text")
+        .and_then(serde_json::Value::as_str)
+        .map(str::to_owned)
+        .context("transcription response has no text")
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use coop_core::{SessionKind, TrustLevel};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    fn config(backend: TranscriptionBackend, command: &str) -> TranscriptionConfig {
+        TranscriptionConfig {
+            backend,
+            command: command.to_owned(),
+            model: Some("ggml-base.en.bin".to_owned()),
+            base_url: None,
+            api_key_env: None,
+            language: None,
+            default_enabled: true,
+        }
+    }
+
+    fn scope(root: &Path) -> WorkspaceScope {
+        WorkspaceScope::for_turn(root, &SessionKind::Main, TrustLevel::Full, None)
+    }
+
+    #[test]
+    fn detects_saved_audio_lines() {
+        assert_eq!(
+            saved_audio_path("[file saved: ./attachments/1_001_voice.M4A]"),
+            Some("./attachments/1_001_voice.M4A")
+        );
+        assert_eq!(
+            saved_audio_path("[file saved: ./attachments/photo.jpg]"),
+            None
+        );
+        assert_eq!(saved_audio_path("voice.ogg"), None);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn whisper_transcripts_are_inlined_and_cached() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("attachments")).unwrap();
+        std::fs::write(dir.path().join("attachments/note.wav"), b"RIFF").unwrap();
+        let whisper = dir.path().join("whisper");
+        std::fs::write(&whisper, "#!/bin/sh\necho ' pick up   milk'\n").unwrap();
+        std::fs::set_permissions(&whisper, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        let text =
+            "[attachment: note.wav (audio/wav, 4 bytes)]\n[file saved: ./attachments/note.wav]";
+        let config = config(TranscriptionBackend::WhisperCpp, whisper.to_str().unwrap());
+        let rewritten = transcribe_attachments(&config, text, &scope(dir.path())).await;
+        assert_eq!(rewritten, format!("{text}\n[transcript: pick up milk]"));
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("attachments/note.wav.transcript.txt"))
+                .unwrap(),
+            "pick up milk"
+        );
+
+        // A second pass reads the cache instead of running whisper again.
+        std::fs::write(&whisper, "#!/bin/sh\nexit 1\n").unwrap();
+        let again = transcribe_attachments(&config, text, &scope(dir.path())).await;
+        assert_eq!(again, rewritten);
+
+        std::fs::write(dir.path().join("attachments/other.wav"), b"RIFF").unwrap();
+        let failed = transcribe_attachments(
+            &config,
+            "[file saved: ./attachments/other.wav]",
+            &scope(dir.path()),
+        )
+        .await;
+        assert!(failed.contains("[transcript failed:"), "{failed}");
+    }
+
+    #[tokio::test]
+    async fn openai_backend_posts_audio() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut request = Vec::new();
+            let mut buffer = vec![0_u8; 4096];
+            while !String::from_utf8_lossy(&request).contains("--\r\n") {
+                let read = socket.read(&mut buffer).await.unwrap();
+                if read == 0 {
+                    break;
+                }
+                request.extend_from_slice(&buffer[..read]);
+            }
+            let body = r#"{"text":"hello from the bus"}"#;
+            let response = format!(
+                "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+                body.len()
+            );
+            socket.write_all(response.as_bytes()).await.unwrap();
+            String::from_utf8_lossy(&request).into_owned()
+        });
+
+        let dir = tempfile::tempdir().unwrap();
+        let audio = dir.path().join("voice.ogg");
+        std::fs::write(&audio, b"OggS").unwrap();
+        let mut config = config(TranscriptionBackend::Openai, "unused");
+        config.model = None;
+        config.base_url = Some(format!("http://{addr}/v1/"));
+
+        let text = transcribe_openai(&config, "test-key", &audio)
+            .await
+            .unwrap();
+        assert_eq!(text, "hello from the bus");
+        let request = server.await.unwrap();
+        assert!(request.starts_with("POST /v1/audio/transcriptions"));
+        assert!(request.contains("authorization: Bearer test-key"));
+        assert!(request.contains("whisper-1"));
+        assert!(request.contains("filename=\"voice.ogg\""));
+    }
+}
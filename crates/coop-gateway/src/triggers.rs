@@ -0,0 +1,818 @@
+use anyhow::{Context, Result};
+use coop_ipc::auth::constant_time_eq;
+use regex::Regex;
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::io::Read as _;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::mpsc;
+use tokio_util::sync::CancellationToken;
+use tracing::{Instrument, debug, error, info, info_span, warn};
+
+use crate::config::{Config, SharedConfig};
+use crate::cron_runner::{DeliverySender, TriggerSource, run_triggered_job};
+use crate::router::MessageRouter;
+
+const WATCH_POLL_INTERVAL: Duration = Duration::from_secs(2);
+/// Files scanned per watch pattern; deeper trees are cut off with a warning.
+const MAX_WATCHED_FILES: usize = 10_000;
+/// Changed-file content included in the turn message, across all files.
+const MAX_FILE_PAYLOAD_BYTES: usize = 16 * 1024;
+const MAX_WEBHOOK_HEADER_BYTES: usize = 8 * 1024;
+const MAX_WEBHOOK_BODY_BYTES: usize = 64 * 1024;
+const WEBHOOK_READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// One event for the `[[trigger]]` named `name`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct TriggerEvent {
+    pub name: String,
+    pub source: TriggerSource,
+    pub payload: String,
+}
+
+/// Start the file watcher, the webhook listener (when `[webhooks]` is set
+/// and its secret is available) and the task that runs their events.
+pub(crate) async fn spawn_triggers(
+    config: SharedConfig,
+    config_dir: PathBuf,
+    router: Arc<MessageRouter>,
+    deliver_tx: Option<DeliverySender>,
+    shutdown: CancellationToken,
+) -> Result<()> {
+    let (event_tx, event_rx) = mpsc::channel(64);
+
+    let webhooks = config.load().webhooks.clone();
+    if let Some(webhooks) = webhooks {
+        if webhook_secret(&config.load(), env_secret).is_some() {
+            let listener = TcpListener::bind(&webhooks.listen)
+                .await
+                .with_context(|| format!("failed to bind webhooks on {}", webhooks.listen))?;
+            info!(listen = %webhooks.listen, "webhook listener started");
+            tokio::spawn(serve_webhooks(
+                listener,
+                Arc::clone(&config),
+                env_secret,
+                event_tx.clone(),
+                shutdown.clone(),
+            ));
+        } else {
+            warn!(
+                secret_env = %webhooks.secret_env,
+                "webhook listener not started: secret environment variable is not set"
+            );
+        }
+    }
+
+    tokio::spawn(
+        watch_files(Arc::clone(&config), config_dir, event_tx, shutdown.clone())
+            .instrument(info_span!("trigger_file_watch")),
+    );
+    tokio::spawn(dispatch_events(
+        event_rx, config, router, deliver_tx, shutdown,
+    ));
+    Ok(())
+}
+
+/// Run each event as an isolated cron-style turn.
+async fn dispatch_events(
+    mut event_rx: mpsc::Receiver<TriggerEvent>,
+    config: SharedConfig,
+    router: Arc<MessageRouter>,
+    deliver_tx: Option<DeliverySender>,
+    shutdown: CancellationToken,
+) {
+    loop {
+        let event = tokio::select! {
+            event = event_rx.recv() => match event {
+                Some(event) => event,
+                None => return,
+            },
+            () = shutdown.cancelled() => return,
+        };
+        let Some(trigger) = config
+            .load()
+            .trigger
+            .iter()
+            .find(|trigger| trigger.name == event.name)
+            .cloned()
+        else {
+            debug!(trigger.name = %event.name, "trigger removed before its event ran");
+            continue;
+        };
+
+        let cfg = trigger.to_cron(&event.payload);
+        let router = Arc::clone(&router);
+        let config = Arc::clone(&config);
+        let deliver_tx = deliver_tx.clone();
+        tokio::spawn(async move {
+            match run_triggered_job(&cfg, &router, deliver_tx.as_ref(), &config, event.source).await
+            {
+                Ok(result) => info!(
+                    trigger.name = %cfg.name,
+                    trigger.source = event.source.as_str(),
+                    status = result.status.as_str(),
+                    delivered_to = result.delivered_to,
+                    "trigger run finished"
+                ),
+                Err(error) => error!(
+                    trigger.name = %cfg.name,
+                    trigger.source = event.source.as_str(),
+                    error = %format!("{error:#}"),
+                    "trigger run failed"
+                ),
+            }
+        });
+    }
+}
+
+// ---------------------------------------------------------------------------
+// File watch
+// ---------------------------------------------------------------------------
+
+/// Size and modification time of every file matching a watch pattern,
+/// keyed by workspace-relative path.
+type Snapshot = BTreeMap<String, (u64, Option<SystemTime>)>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Change {
+    Created,
+    Modified,
+    Deleted,
+}
+
+impl Change {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Created => "created",
+            Self::Modified => "modified",
+            Self::Deleted => "deleted",
+        }
+    }
+}
+
+type WatchKey = (String, String);
+
+/// Poll every `watch` trigger and send an event when its files change.
+/// The first scan of a pattern only records a baseline.
+async fn watch_files(
+    config: SharedConfig,
+    config_dir: PathBuf,
+    event_tx: mpsc::Sender<TriggerEvent>,
+    shutdown: CancellationToken,
+) {
+    let mut snapshots: BTreeMap<WatchKey, Snapshot> = BTreeMap::new();
+    loop {
+        let watches = watched_patterns(&config.load(), &config_dir);
+        let previous = std::mem::take(&mut snapshots);
+        // Walking directories and reading files blocks; keep it off the
+        // async workers.
+        let poll = tokio::task::spawn_blocking(move || poll_watches(&watches, previous)).await;
+        match poll {
+            Ok((current, events)) => {
+                snapshots = current;
+                for event in events {
+                    if event_tx.send(event).await.is_err() {
+                        return;
+                    }
+                }
+            }
+            Err(error) => warn!(error = %error, "watch poll failed"),
+        }
+
+        tokio::select! {
+            () = tokio::time::sleep(WATCH_POLL_INTERVAL) => {}
+            () = shutdown.cancelled() => return,
+        }
+    }
+}
+
+/// Each `watch` trigger's name, pattern and workspace.
+fn watched_patterns(config: &Config, config_dir: &Path) -> Vec<(WatchKey, PathBuf)> {
+    config
+        .trigger
+        .iter()
+        .filter_map(|trigger| {
+            let pattern = trigger.watch.as_ref()?;
+            match trigger_workspace(config, config_dir, trigger.user.as_deref()) {
+                Ok(workspace) => Some(((trigger.name.clone(), pattern.clone()), workspace)),
+                Err(error) => {
+                    debug!(trigger.name = %trigger.name, error = %error, "watch trigger has no workspace");
+                    None
+                }
+            }
+        })
+        .collect()
+}
+
+/// Scan every watched pattern against its previous snapshot. Returns the
+/// new snapshots and an event for each trigger whose files changed.
+fn poll_watches(
+    watches: &[(WatchKey, PathBuf)],
+    mut previous: BTreeMap<WatchKey, Snapshot>,
+) -> (BTreeMap<WatchKey, Snapshot>, Vec<TriggerEvent>) {
+    let mut snapshots = BTreeMap::new();
+    let mut events = Vec::new();
+    for (key, workspace) in watches {
+        let (name, pattern) = key;
+        let current = match scan(workspace, pattern) {
+            Ok(current) => current,
+            Err(error) => {
+                // Keep the old snapshot so the next scan still sees what
+                // changed in between.
+                debug!(trigger.name = %name, error = %error, "watch scan failed");
+                if let Some(previous) = previous.remove(key) {
+                    snapshots.insert(key.clone(), previous);
+                }
+                continue;
+            }
+        };
+
+        if let Some(previous) = previous.remove(key) {
+            let changes = diff(&previous, &current);
+            if !changes.is_empty() {
+                info!(
+                    trigger.name = %name,
+                    count = changes.len(),
+                    "watched files changed"
+                );
+                events.push(TriggerEvent {
+                    name: name.clone(),
+                    source: TriggerSource::FileWatch,
+                    payload: file_payload(workspace, &changes),
+                });
+            }
+        }
+        snapshots.insert(key.clone(), current);
+    }
+    (snapshots, events)
+}
+
+/// Workspace of the agent that handles `user`'s jobs.
+fn trigger_workspace(config: &Config, config_dir: &Path, user: Option<&str>) -> Result<PathBuf> {
+    let agent_id = config.agent_id_for_user(user);
+    config
+        .for_agent(agent_id)
+        .with_context(|| format!("unknown agent '{agent_id}'"))?
+        .resolve_workspace(config_dir)
+}
+
+/// Compile a workspace-relative glob: `*` and `?` stay within one path
+/// segment, `**` spans any number of them.
+pub(crate) fn watch_regex(pattern: &str) -> Result<Regex> {
+    anyhow::ensure!(!pattern.trim().is_empty(), "watch pattern is empty");
+    anyhow::ensure!(
+        !Path::new(pattern).is_absolute() && !pattern.split('/').any(|part| part == ".."),
+        "watch pattern must stay inside the workspace: {pattern}"
+    );
+
+    let mut regex = String::from("^");
+    let mut chars = pattern.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                if chars.peek() == Some(&'/') {
+                    chars.next();
+                    regex.push_str("(?:.*/)?");
+                } else {
+                    regex.push_str(".*");
+                }
+            }
+            '*' => regex.push_str("[^/]*"),
+            '?' => regex.push_str("[^/]"),
+            c => regex.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    regex.push('$');
+    Regex::new(&regex).with_context(|| format!("invalid watch pattern: {pattern}"))
+}
+
+/// Directory to start scanning from: the pattern's leading literal segments.
+fn scan_root(pattern: &str) -> &str {
+    let wildcard = pattern.find(['*', '?']).unwrap_or(pattern.len());
+    pattern[..wildcard]
+        .rfind('/')
+        .map_or("", |end| &pattern[..end])
+}
+
+fn scan(workspace: &Path, pattern: &str) -> Result<Snapshot> {
+    let regex = watch_regex(pattern)?;
+    let mut snapshot = Snapshot::new();
+    let mut pending = vec![workspace.join(scan_root(pattern))];
+    while let Some(dir) = pending.pop() {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            if file_type.is_dir() {
+                pending.push(path);
+                continue;
+            }
+            if !file_type.is_file() {
+                continue;
+            }
+            let Some(relative) = path
+                .strip_prefix(workspace)
+                .ok()
+                .and_then(|relative| relative.to_str())
+            else {
+                continue;
+            };
+            if !regex.is_match(relative) {
+                continue;
+            }
+            if snapshot.len() >= MAX_WATCHED_FILES {
+                warn!(
+                    pattern,
+                    limit = MAX_WATCHED_FILES,
+                    "watch pattern matches too many files"
+                );
+                return Ok(snapshot);
+            }
+            // Inbox-style folders lose files between readdir and stat.
+            let Ok(metadata) = entry.metadata() else {
+                continue;
+            };
+            snapshot.insert(
+                relative.to_owned(),
+                (metadata.len(), metadata.modified().ok()),
+            );
+        }
+    }
+    Ok(snapshot)
+}
+
+fn diff(previous: &Snapshot, current: &Snapshot) -> Vec<(String, Change)> {
+    let mut changes: Vec<_> = current
+        .iter()
+        .filter_map(|(path, stamp)| match previous.get(path) {
+            None => Some((path.clone(), Change::Created)),
+            Some(old) if old != stamp => Some((path.clone(), Change::Modified)),
+            Some(_) => None,
+        })
+        .collect();
+    changes.extend(
+        previous
+            .keys()
+            .filter(|path| !current.contains_key(*path))
+            .map(|path| (path.clone(), Change::Deleted)),
+    );
+    changes
+}
+
+/// List the changes, then the text of each created or modified file until
+/// the payload budget runs out.
+fn file_payload(workspace: &Path, changes: &[(String, Change)]) -> String {
+    let mut out = String::from("Files changed in the workspace:\n");
+    for (path, change) in changes {
+        let _ = writeln!(out, "- {path} ({})", change.as_str());
+    }
+
+    let mut budget = MAX_FILE_PAYLOAD_BYTES;
+    for (path, change) in changes {
+        if *change == Change::Deleted {
+            continue;
+        }
+        if budget == 0 {
+            out.push_str("\n(further file contents omitted)\n");
+            break;
+        }
+        let Some((content, truncated)) = read_prefix(&workspace.join(path), budget) else {
+            continue;
+        };
+        budget -= content.len();
+        let _ = write!(out, "\n--- {path} ---\n{content}");
+        if truncated {
+            out.push_str("\n(truncated)");
+        }
+        out.push('\n');
+    }
+    out
+}
+
+/// Up to `limit` bytes of a UTF-8 file, cut back to a char boundary, and
+/// whether the file continues past them. `None` for unreadable or
+/// non-UTF-8 files.
+fn read_prefix(path: &Path, limit: usize) -> Option<(String, bool)> {
+    let file = std::fs::File::open(path).ok()?;
+    let mut bytes = Vec::new();
+    file.take(limit as u64 + 1).read_to_end(&mut bytes).ok()?;
+    let truncated = bytes.len() > limit;
+    bytes.truncate(limit);
+    match String::from_utf8(bytes) {
+        Ok(text) => Some((text, truncated)),
+        // A multi-byte char split by the limit is not an encoding error.
+        Err(error) if truncated && error.utf8_error().error_len().is_none() => {
+            let valid = error.utf8_error().valid_up_to();
+            let mut bytes = error.into_bytes();
+            bytes.truncate(valid);
+            String::from_utf8(bytes).ok().map(|text| (text, true))
+        }
+        Err(_) => None,
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Webhooks
+// ---------------------------------------------------------------------------
+
+/// Reads a secret from the environment variable it is named after.
+pub(crate) type SecretLookup = fn(&str) -> Option<String>;
+
+fn env_secret(name: &str) -> Option<String> {
+    std::env::var(name).ok()
+}
+
+/// The shared secret `[webhooks]` currently names, if it is set.
+fn webhook_secret(config: &Config, lookup: SecretLookup) -> Option<String> {
+    let webhooks = config.webhooks.as_ref()?;
+    lookup(&webhooks.secret_env).filter(|secret| !secret.trim().is_empty())
+}
+
+/// Accept webhook calls until shutdown. Each connection carries one request.
+/// The secret is looked up per request, so a reloaded `secret_env` applies
+/// to the next call.
+pub(crate) async fn serve_webhooks(
+    listener: TcpListener,
+    config: SharedConfig,
+    lookup: SecretLookup,
+    event_tx: mpsc::Sender<TriggerEvent>,
+    shutdown: CancellationToken,
+) {
+    loop {
+        let (stream, peer) = tokio::select! {
+            accepted = listener.accept() => match accepted {
+                Ok(accepted) => accepted,
+                Err(error) => {
+                    warn!(error = %error, "webhook accept failed");
+                    continue;
+                }
+            },
+            () = shutdown.cancelled() => {
+                info!("webhook listener shutting down");
+                return;
+            }
+        };
+        let config = Arc::clone(&config);
+        let event_tx = event_tx.clone();
+        tokio::spawn(async move {
+            if let Err(error) = handle_webhook(stream, &config, lookup, &event_tx).await {
+                debug!(peer = %peer, error = %error, "webhook request failed");
+            }
+        });
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct WebhookRequest {
+    method: String,
+    path: String,
+    authorization: Option<String>,
+    body: Vec<u8>,
+}
+
+async fn handle_webhook(
+    mut stream: TcpStream,
+    config: &SharedConfig,
+    lookup: SecretLookup,
+    event_tx: &mpsc::Sender<TriggerEvent>,
+) -> Result<()> {
+    let request = tokio::time::timeout(WEBHOOK_READ_TIMEOUT, read_request(&mut stream)).await;
+    let (status, reason) = match request {
+        Err(_elapsed) => (408, "Request Timeout"),
+        Ok(Err(error)) => {
+            debug!(error = %error, "malformed webhook request");
+            (400, "Bad Request")
+        }
+        Ok(Ok(None)) => (413, "Payload Too Large"),
+        Ok(Ok(Some(request))) => match route(&request, &config.load(), lookup) {
+            Ok(event) => {
+                info!(trigger.name = %event.name, bytes = request.body.len(), "webhook received");
+                if event_tx.send(event).await.is_ok() {
+                    (202, "Accepted")
+                } else {
+                    (503, "Service Unavailable")
+                }
+            }
+            Err(status) => status,
+        },
+    };
+
+    let body = format!("{}\n", reason.to_lowercase());
+    let response = format!(
+        "HTTP/1.1 {status} {reason}\r\nContent-Type: text/plain\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    );
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await?;
+    Ok(())
+}
+
+/// Read one HTTP/1.1 request. `None` means the headers or body exceeded
+/// their limits.
+async fn read_request(stream: &mut TcpStream) -> Result<Option<WebhookRequest>> {
+    let mut buf = Vec::new();
+    let header_end = loop {
+        if let Some(pos) = buf.windows(4).position(|window| window == b"\r\n\r\n") {
+            break pos;
+        }
+        if buf.len() > MAX_WEBHOOK_HEADER_BYTES {
+            return Ok(None);
+        }
+        let mut chunk = [0_u8; 1024];
+        let read = stream.read(&mut chunk).await?;
+        anyhow::ensure!(read > 0, "connection closed before headers ended");
+        buf.extend_from_slice(&chunk[..read]);
+    };
+
+    let head = std::str::from_utf8(&buf[..header_end]).context("headers are not UTF-8")?;
+    let mut lines = head.split("\r\n");
+    let mut request_line = lines.next().unwrap_or_default().split_whitespace();
+    let method = request_line.next().context("missing method")?.to_owned();
+    let path = request_line.next().context("missing path")?.to_owned();
+
+    let mut authorization = None;
+    let mut content_length = 0_usize;
+    for line in lines {
+        let Some((name, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+        if name.eq_ignore_ascii_case("authorization") {
+            authorization = Some(value.to_owned());
+        } else if name.eq_ignore_ascii_case("content-length") {
+            content_length = value.parse().context("invalid content-length")?;
+        }
+    }
+    if content_length > MAX_WEBHOOK_BODY_BYTES {
+        return Ok(None);
+    }
+
+    let mut body = buf.split_off(header_end + 4);
+    while body.len() < content_length {
+        let mut chunk = vec![0_u8; content_length - body.len()];
+        let read = stream.read(&mut chunk).await?;
+        anyhow::ensure!(read > 0, "connection closed before body ended");
+        body.extend_from_slice(&chunk[..read]);
+    }
+    body.truncate(content_length);
+
+    Ok(Some(WebhookRequest {
+        method,
+        path,
+        authorization,
+        body,
+    }))
+}
+
+/// The event a request starts, or the status to reject it with.
+fn route(
+    request: &WebhookRequest,
+    config: &Config,
+    lookup: SecretLookup,
+) -> std::result::Result<TriggerEvent, (u16, &'static str)> {
+    // Callers such as GitHub and Stripe append query parameters.
+    let path = request
+        .path
+        .split_once('?')
+        .map_or(request.path.as_str(), |(path, _query)| path);
+    let Some(name) = path.strip_prefix("/hooks/") else {
+        return Err((404, "Not Found"));
+    };
+    let Some(secret) = webhook_secret(config, lookup) else {
+        warn!("webhook rejected: secret environment variable is not set");
+        return Err((503, "Service Unavailable"));
+    };
+    let authorized = request
+        .authorization
+        .as_deref()
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .is_some_and(|token| constant_time_eq(token.as_bytes(), secret.as_bytes()));
+    if !authorized {
+        warn!(path = %request.path, "webhook rejected: bad secret");
+        return Err((401, "Unauthorized"));
+    }
+    if !config
+        .trigger
+        .iter()
+        .any(|trigger| trigger.webhook && trigger.name == name)
+    {
+        return Err((404, "Not Found"));
+    }
+    if request.method != "POST" {
+        return Err((405, "Method Not Allowed"));
+    }
+
+    let body = String::from_utf8_lossy(&request.body);
+    let payload = if body.trim().is_empty() {
+        format!("Webhook '{name}' was called with no payload.")
+    } else {
+        format!("Webhook '{name}' payload:\n{body}")
+    };
+    Ok(TriggerEvent {
+        name: name.to_owned(),
+        source: TriggerSource::Webhook,
+        payload,
+    })
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::shared_config;
+
+    #[test]
+    fn watch_patterns_match_within_segments() {
+        let inbox = watch_regex("inbox/*.md").unwrap();
+        assert!(inbox.is_match("inbox/note.md"));
+        assert!(!inbox.is_match("inbox/sub/note.md"));
+        assert!(!inbox.is_match("inbox/note.txt"));
+
+        let deep = watch_regex("notes/**/*.md").unwrap();
+        assert!(deep.is_match("notes/a.md"));
+        assert!(deep.is_match("notes/2026/03/a.md"));
+
+        assert_eq!(scan_root("notes/**/*.md"), "notes");
+        assert_eq!(scan_root("*.md"), "");
+        assert!(watch_regex("../secrets/*").is_err());
+        assert!(watch_regex("/etc/*").is_err());
+    }
+
+    #[test]
+    fn scan_and_diff_report_file_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("inbox")).unwrap();
+        std::fs::write(dir.path().join("inbox/old.md"), "old").unwrap();
+        std::fs::write(dir.path().join("inbox/keep.md"), "keep").unwrap();
+        std::fs::write(dir.path().join("inbox/skip.txt"), "skip").unwrap();
+        let before = scan(dir.path(), "inbox/*.md").unwrap();
+        assert_eq!(before.len(), 2);
+
+        std::fs::remove_file(dir.path().join("inbox/old.md")).unwrap();
+        std::fs::write(dir.path().join("inbox/new.md"), "buy milk").unwrap();
+        let after = scan(dir.path(), "inbox/*.md").unwrap();
+
+        let changes = diff(&before, &after);
+        assert_eq!(
+            changes,
+            vec![
+                ("inbox/new.md".to_owned(), Change::Created),
+                ("inbox/old.md".to_owned(), Change::Deleted),
+            ]
+        );
+        let payload = file_payload(dir.path(), &changes);
+        assert!(payload.contains("- inbox/new.md (created)"));
+        assert!(payload.contains("- inbox/old.md (deleted)"));
+        assert!(payload.contains("--- inbox/new.md ---\nbuy milk"));
+    }
+
+    #[test]
+    fn payload_reads_only_the_remaining_budget() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("accents.md"), "xééé").unwrap();
+        std::fs::write(
+            dir.path().join("big.md"),
+            "x".repeat(4 * MAX_FILE_PAYLOAD_BYTES),
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("next.md"), "later").unwrap();
+        std::fs::write(dir.path().join("bin.md"), [0xff, 0xfe]).unwrap();
+
+        let (prefix, truncated) = read_prefix(&dir.path().join("accents.md"), 4).unwrap();
+        assert_eq!(prefix, "xé");
+        assert!(truncated);
+        assert_eq!(
+            read_prefix(&dir.path().join("accents.md"), 7),
+            Some(("xééé".to_owned(), false))
+        );
+        assert!(read_prefix(&dir.path().join("bin.md"), 4).is_none());
+
+        let changes = vec![
+            ("bin.md".to_owned(), Change::Created),
+            ("big.md".to_owned(), Change::Created),
+            ("next.md".to_owned(), Change::Modified),
+        ];
+        let payload = file_payload(dir.path(), &changes);
+        assert!(!payload.contains("--- bin.md ---"));
+        assert!(payload.contains("(truncated)"));
+        assert!(payload.contains("(further file contents omitted)"));
+        assert!(!payload.contains("later"));
+    }
+
+    async fn post(addr: std::net::SocketAddr, path: &str, secret: &str, body: &str) -> u16 {
+        reqwest::Client::new()
+            .post(format!("http://{addr}{path}"))
+            .bearer_auth(secret)
+            .body(body.to_owned())
+            .send()
+            .await
+            .unwrap()
+            .status()
+            .as_u16()
+    }
+
+    fn webhook_config(secret_env: &str) -> Config {
+        toml::from_str(&format!(
+            "[agent]\nid = \"test\"\nmodel = \"test\"\n\n\
+             [webhooks]\nlisten = \"127.0.0.1:0\"\nsecret_env = \"{secret_env}\"\n\n\
+             [[trigger]]\nname = \"doorbell\"\nwebhook = true\n\n\
+             [[trigger]]\nname = \"inbox\"\nwatch = \"inbox/*.md\"\n"
+        ))
+        .unwrap()
+    }
+
+    fn test_secrets(name: &str) -> Option<String> {
+        match name {
+            "HOOK_SECRET" => Some("s3cret".to_owned()),
+            "ROTATED_HOOK_SECRET" => Some("rotated".to_owned()),
+            _ => None,
+        }
+    }
+
+    #[tokio::test]
+    async fn webhook_requires_secret_and_known_trigger() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let (event_tx, mut event_rx) = mpsc::channel(4);
+        let shutdown = CancellationToken::new();
+        tokio::spawn(serve_webhooks(
+            listener,
+            shared_config(webhook_config("HOOK_SECRET")),
+            test_secrets,
+            event_tx,
+            shutdown.clone(),
+        ));
+
+        assert_eq!(post(addr, "/hooks/doorbell", "wrong", "{}").await, 401);
+        assert_eq!(post(addr, "/hooks/inbox", "s3cret", "{}").await, 404);
+        assert_eq!(
+            post(addr, "/hooks/doorbell", "s3cret", "{\"who\":\"courier\"}").await,
+            202
+        );
+
+        let event = event_rx.recv().await.unwrap();
+        assert_eq!(event.name, "doorbell");
+        assert_eq!(event.source, TriggerSource::Webhook);
+        assert_eq!(
+            event.payload,
+            "Webhook 'doorbell' payload:\n{\"who\":\"courier\"}"
+        );
+        assert!(event_rx.try_recv().is_err());
+        shutdown.cancel();
+    }
+
+    #[tokio::test]
+    async fn webhook_ignores_query_and_follows_reloaded_secret() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let (event_tx, mut event_rx) = mpsc::channel(4);
+        let shutdown = CancellationToken::new();
+        let config = shared_config(webhook_config("HOOK_SECRET"));
+        tokio::spawn(serve_webhooks(
+            listener,
+            Arc::clone(&config),
+            test_secrets,
+            event_tx,
+            shutdown.clone(),
+        ));
+
+        assert_eq!(
+            post(addr, "/hooks/doorbell?delivery=42", "s3cret", "{}").await,
+            202
+        );
+        assert_eq!(event_rx.recv().await.unwrap().name, "doorbell");
+
+        config.store(Arc::new(webhook_config("ROTATED_HOOK_SECRET")));
+        assert_eq!(post(addr, "/hooks/doorbell", "s3cret", "{}").await, 401);
+        assert_eq!(post(addr, "/hooks/doorbell", "rotated", "{}").await, 202);
+
+        config.store(Arc::new(webhook_config("UNSET_HOOK_SECRET")));
+        assert_eq!(post(addr, "/hooks/doorbell", "rotated", "{}").await, 503);
+        shutdown.cancel();
+    }
+
+    #[test]
+    fn failed_scans_keep_the_previous_snapshot() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut snapshot = Snapshot::new();
+        snapshot.insert("inbox/a.md".to_owned(), (1, None));
+        // Patterns escaping the workspace fail the scan.
+        let key = ("inbox".to_owned(), "../inbox/*.md".to_owned());
+        let previous = BTreeMap::from([(key.clone(), snapshot.clone())]);
+
+        let (snapshots, events) =
+            poll_watches(&[(key.clone(), dir.path().to_path_buf())], previous);
+
+        assert!(events.is_empty());
+        assert_eq!(snapshots.get(&key), Some(&snapshot));
+    }
+}
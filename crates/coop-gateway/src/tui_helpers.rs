@@ -122,7 +122,10 @@ fn tool_label(name: &str) -> (&'static str, &'static str) {
         "write_file" | "Write" => ("✏️", "Write"),
         "memory_search" | "memory_files" | "memory_timeline" | "memory_get" | "memory_write"
         | "memory_history" | "memory_people" => ("🧠", "Memory"),
+        "memory_forget_person" => ("🧠", "Forget"),
         "cron_trigger" => ("⏰", "Trigger"),
+        "cron_history" => ("⏰", "History"),
+        "cron_manage" => ("⏰", "Schedule"),
         _ => ("🔧", "Run"),
     }
 }
@@ -0,0 +1,112 @@
+use async_trait::async_trait;
+use coop_core::{SessionKey, SessionKind, TypingNotifier};
+use std::sync::Arc;
+
+/// Routes typing indicators to the notifier for the channel a session
+/// belongs to, so each chat channel only sees its own sessions.
+pub(crate) struct ChannelTypingNotifier {
+    notifiers: Vec<(&'static str, Arc<dyn TypingNotifier>)>,
+}
+
+impl std::fmt::Debug for ChannelTypingNotifier {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let channels: Vec<&str> = self.notifiers.iter().map(|(channel, _)| *channel).collect();
+        f.debug_struct("ChannelTypingNotifier")
+            .field("channels", &channels)
+            .finish()
+    }
+}
+
+impl ChannelTypingNotifier {
+    pub(crate) fn new(notifiers: Vec<(&'static str, Arc<dyn TypingNotifier>)>) -> Self {
+        Self { notifiers }
+    }
+
+    /// Collapse to a single notifier when possible; `None` when empty.
+    pub(crate) fn into_notifier(mut self) -> Option<Arc<dyn TypingNotifier>> {
+        match self.notifiers.len() {
+            0 => None,
+            1 => self.notifiers.pop().map(|(_, notifier)| notifier),
+            _ => Some(Arc::new(self)),
+        }
+    }
+}
+
+#[async_trait]
+impl TypingNotifier for ChannelTypingNotifier {
+    async fn set_typing(&self, session_key: &SessionKey, started: bool) {
+        let (SessionKind::Dm(identity) | SessionKind::Group(identity)) = &session_key.kind else {
+            return;
+        };
+        let Some((channel, _)) = identity.split_once(':') else {
+            return;
+        };
+        if let Some((_, notifier)) = self.notifiers.iter().find(|(name, _)| *name == channel) {
+            notifier.set_typing(session_key, started).await;
+        }
+    }
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct RecordingNotifier {
+        calls: Mutex<Vec<(String, bool)>>,
+    }
+
+    #[async_trait]
+    impl TypingNotifier for RecordingNotifier {
+        async fn set_typing(&self, session_key: &SessionKey, started: bool) {
+            self.calls
+                .lock()
+                .unwrap()
+                .push((session_key.to_string(), started));
+        }
+    }
+
+    fn session(kind: SessionKind) -> SessionKey {
+        SessionKey {
+            agent_id: "coop".to_owned(),
+            kind,
+        }
+    }
+
+    #[tokio::test]
+    async fn routes_sessions_by_channel_prefix() {
+        let signal = Arc::new(RecordingNotifier::default());
+        let telegram = Arc::new(RecordingNotifier::default());
+        let notifier = ChannelTypingNotifier::new(vec![
+            ("signal", Arc::clone(&signal) as Arc<dyn TypingNotifier>),
+            ("telegram", Arc::clone(&telegram) as Arc<dyn TypingNotifier>),
+        ])
+        .into_notifier()
+        .unwrap();
+
+        notifier
+            .set_typing(
+                &session(SessionKind::Dm("signal:alice-uuid".to_owned())),
+                true,
+            )
+            .await;
+        notifier
+            .set_typing(
+                &session(SessionKind::Group("telegram:group:-100123".to_owned())),
+                false,
+            )
+            .await;
+        notifier.set_typing(&session(SessionKind::Main), true).await;
+
+        assert_eq!(
+            *signal.calls.lock().unwrap(),
+            vec![("coop:dm:signal:alice-uuid".to_owned(), true)]
+        );
+        assert_eq!(
+            *telegram.calls.lock().unwrap(),
+            vec![("coop:group:telegram:group:-100123".to_owned(), false)]
+        );
+    }
+}
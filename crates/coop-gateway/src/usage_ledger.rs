@@ -0,0 +1,465 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use coop_agent::{ModelPricing, ProviderKind, lookup_model_pricing};
+use coop_core::{SessionKey, Usage};
+use rusqlite::{Connection, params};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write as _;
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use crate::config::Config;
+use crate::model_catalog::resolve_available_model;
+use crate::sqlite_session_store::session_kind_parts;
+
+/// Ledger file in each agent's workspace.
+pub(crate) const USAGE_DB_FILE: &str = "usage.db";
+
+/// One provider call, as recorded in the ledger.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct ProviderCall<'a> {
+    pub session: &'a SessionKey,
+    pub user: Option<&'a str>,
+    pub model: &'a str,
+    pub usage: &'a Usage,
+    pub latency: Duration,
+}
+
+/// What to group a usage report by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum UsageGrouping {
+    User,
+    Model,
+    /// Cron job name; calls outside cron sessions are left out.
+    Cron,
+    Day,
+}
+
+impl UsageGrouping {
+    pub(crate) const ALL: [Self; 4] = [Self::User, Self::Model, Self::Cron, Self::Day];
+
+    pub(crate) fn parse(name: &str) -> Option<Self> {
+        match name {
+            "user" => Some(Self::User),
+            "model" => Some(Self::Model),
+            "cron" => Some(Self::Cron),
+            "day" => Some(Self::Day),
+            _ => None,
+        }
+    }
+
+    fn title(self) -> &'static str {
+        match self {
+            Self::User => "By user",
+            Self::Model => "By model",
+            Self::Cron => "By cron job",
+            Self::Day => "By day",
+        }
+    }
+
+    fn label_sql(self) -> &'static str {
+        match self {
+            Self::User => "COALESCE(user_name, '-')",
+            Self::Model => "model",
+            Self::Cron => "target",
+            Self::Day => "date(at / 1000, 'unixepoch')",
+        }
+    }
+}
+
+/// Filters for [`UsageLedger::report`].
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct UsageFilter<'a> {
+    pub since: Option<DateTime<Utc>>,
+    pub user: Option<&'a str>,
+    /// Only calls made by this cron job.
+    pub cron: Option<&'a str>,
+}
+
+/// Aggregated usage for one row of a report.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct UsageLine {
+    pub label: String,
+    pub calls: u64,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cache_read_tokens: u64,
+    pub cache_write_tokens: u64,
+    pub latency_ms: u64,
+    /// Spend in USD for the calls whose model has known prices.
+    pub cost: f64,
+    /// Whether some calls used a model without known prices.
+    pub unpriced: bool,
+}
+
+/// Append-only record of every provider call a gateway makes.
+pub(crate) struct UsageLedger {
+    conn: Mutex<Connection>,
+}
+
+// Each method holds the connection for exactly one statement.
+#[allow(clippy::significant_drop_tightening)]
+impl UsageLedger {
+    pub(crate) fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("failed to create usage dir: {}", parent.display()))?;
+        }
+        let conn = Connection::open(path)
+            .with_context(|| format!("failed to open usage db: {}", path.display()))?;
+        conn.execute_batch(
+            "
+            PRAGMA journal_mode = WAL;
+            PRAGMA synchronous = NORMAL;
+            PRAGMA busy_timeout = 5000;
+
+            CREATE TABLE IF NOT EXISTS calls (
+                id                 INTEGER PRIMARY KEY,
+                at                 INTEGER NOT NULL,
+                agent_id           TEXT NOT NULL,
+                session_key        TEXT NOT NULL,
+                kind               TEXT NOT NULL,
+                target             TEXT NOT NULL,
+                user_name          TEXT,
+                model              TEXT NOT NULL,
+                key_index          INTEGER,
+                input_tokens       INTEGER NOT NULL,
+                output_tokens      INTEGER NOT NULL,
+                cache_read_tokens  INTEGER NOT NULL,
+                cache_write_tokens INTEGER NOT NULL,
+                latency_ms         INTEGER NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS idx_calls_at ON calls(at);
+            CREATE INDEX IF NOT EXISTS idx_calls_user ON calls(user_name, at);
+            ",
+        )
+        .with_context(|| format!("failed to initialize usage db: {}", path.display()))?;
+        Ok(Self {
+            conn: Mutex::new(conn),
+        })
+    }
+
+    pub(crate) fn record(&self, call: &ProviderCall<'_>) -> Result<()> {
+        self.record_at(call, Utc::now())
+    }
+
+    fn record_at(&self, call: &ProviderCall<'_>, at: DateTime<Utc>) -> Result<()> {
+        let (kind, target) = session_kind_parts(&call.session.kind);
+        let usage = call.usage;
+        let key_index = usage.key_index.and_then(|index| i64::try_from(index).ok());
+        let latency_ms = i64::try_from(call.latency.as_millis()).unwrap_or(i64::MAX);
+        let conn = self.conn.lock().expect("usage db mutex poisoned");
+        conn.prepare_cached(
+            "INSERT INTO calls (
+                at, agent_id, session_key, kind, target, user_name, model, key_index,
+                input_tokens, output_tokens, cache_read_tokens, cache_write_tokens, latency_ms
+             ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)",
+        )?
+        .execute(params![
+            at.timestamp_millis(),
+            call.session.agent_id,
+            call.session.to_string(),
+            kind,
+            target,
+            call.user,
+            call.model,
+            key_index,
+            usage.input_tokens.unwrap_or(0),
+            usage.output_tokens.unwrap_or(0),
+            usage.cache_read_tokens.unwrap_or(0),
+            usage.cache_write_tokens.unwrap_or(0),
+            latency_ms,
+        ])?;
+        Ok(())
+    }
+
+    /// Usage grouped by `by`, most expensive first (oldest first by day).
+    /// `pricing` maps a model id to its prices.
+    pub(crate) fn report(
+        &self,
+        by: UsageGrouping,
+        filter: UsageFilter<'_>,
+        pricing: &dyn Fn(&str) -> Option<ModelPricing>,
+    ) -> Result<Vec<UsageLine>> {
+        let sql = format!(
+            "SELECT {label}, model, COUNT(*), SUM(input_tokens), SUM(output_tokens),
+                    SUM(cache_read_tokens), SUM(cache_write_tokens), SUM(latency_ms)
+             FROM calls
+             WHERE at >= ?1 AND (?2 IS NULL OR user_name = ?2)
+               AND (?3 IS NULL OR (kind = 'cron' AND target = ?3)) {cron_only}
+             GROUP BY 1, 2",
+            label = by.label_sql(),
+            cron_only = if by == UsageGrouping::Cron {
+                "AND kind = 'cron'"
+            } else {
+                ""
+            },
+        );
+        let since = filter.since.map_or(0, |since| since.timestamp_millis());
+        let rows: Vec<(String, String, [i64; 6])> = {
+            let conn = self.conn.lock().expect("usage db mutex poisoned");
+            let mut stmt = conn.prepare(&sql)?;
+            stmt.query_map(params![since, filter.user, filter.cron], |row| {
+                Ok((
+                    row.get(0)?,
+                    row.get(1)?,
+                    [
+                        row.get(2)?,
+                        row.get(3)?,
+                        row.get(4)?,
+                        row.get(5)?,
+                        row.get(6)?,
+                        row.get(7)?,
+                    ],
+                ))
+            })?
+            .collect::<Result<_, _>>()?
+        };
+
+        let mut prices: HashMap<String, Option<ModelPricing>> = HashMap::new();
+        let mut lines: BTreeMap<String, UsageLine> = BTreeMap::new();
+        for (label, model, counts) in rows {
+            let [calls, input, output, cache_read, cache_write, latency] =
+                counts.map(|count| u64::try_from(count).unwrap_or(0));
+            let line = lines.entry(label.clone()).or_insert_with(|| UsageLine {
+                label,
+                ..UsageLine::default()
+            });
+            line.calls += calls;
+            line.input_tokens += input;
+            line.output_tokens += output;
+            line.cache_read_tokens += cache_read;
+            line.cache_write_tokens += cache_write;
+            line.latency_ms += latency;
+            match *prices
+                .entry(model)
+                .or_insert_with_key(|model| pricing(model))
+            {
+                Some(price) => line.cost += price.cost(input, output, cache_read, cache_write),
+                None => line.unpriced = true,
+            }
+        }
+
+        let mut lines: Vec<_> = lines.into_values().collect();
+        if by != UsageGrouping::Day {
+            lines.sort_by(|a, b| {
+                b.cost
+                    .total_cmp(&a.cost)
+                    .then_with(|| b.calls.cmp(&a.calls))
+            });
+        }
+        Ok(lines)
+    }
+
+    /// All usage matching `filter` as a single line.
+    pub(crate) fn total(
+        &self,
+        filter: UsageFilter<'_>,
+        pricing: &dyn Fn(&str) -> Option<ModelPricing>,
+    ) -> Result<UsageLine> {
+        let lines = self.report(UsageGrouping::Model, filter, pricing)?;
+        Ok(lines.iter().fold(UsageLine::default(), |mut total, line| {
+            total.add(line);
+            total
+        }))
+    }
+}
+
+impl UsageLine {
+    /// Fold `other`'s calls, tokens and cost into this line.
+    pub(crate) fn add(&mut self, other: &Self) {
+        self.calls += other.calls;
+        self.input_tokens += other.input_tokens;
+        self.output_tokens += other.output_tokens;
+        self.cache_read_tokens += other.cache_read_tokens;
+        self.cache_write_tokens += other.cache_write_tokens;
+        self.latency_ms += other.latency_ms;
+        self.cost += other.cost;
+        self.unpriced |= other.unpriced;
+    }
+}
+
+/// Prices for `model`: a `[usage.pricing]` override, else models.dev for the
+/// provider that serves the model.
+pub(crate) fn model_pricing(config: &Config, model: &str) -> Option<ModelPricing> {
+    if let Some(pricing) = config.usage.pricing.get(model) {
+        return Some(*pricing);
+    }
+    let provider = resolve_available_model(config, model)
+        .map_or(&config.provider, |resolved| resolved.provider);
+    let kind = ProviderKind::from_name(&provider.name).ok()?;
+    lookup_model_pricing(kind, provider.base_url.as_deref(), model)
+}
+
+/// Render report sections as plain text for the CLI and `/usage`.
+pub(crate) fn format_report(sections: &[(UsageGrouping, Vec<UsageLine>)]) -> String {
+    let mut out = String::new();
+    let mut unpriced = false;
+    for (by, lines) in sections {
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        let _ = writeln!(out, "{}:", by.title());
+        if lines.is_empty() {
+            out.push_str("  (no calls)\n");
+        }
+        for line in lines {
+            unpriced |= line.unpriced;
+            let _ = writeln!(
+                out,
+                "  {:<24} ${:>9.4}{} {:>6} calls {:>10} in {:>9} out {:>10} cached",
+                line.label,
+                line.cost,
+                if line.unpriced { "*" } else { " " },
+                line.calls,
+                line.input_tokens,
+                line.output_tokens,
+                line.cache_read_tokens + line.cache_write_tokens,
+            );
+        }
+    }
+    if unpriced {
+        out.push_str("\n* includes calls to models without known prices\n");
+    }
+    out
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use coop_core::SessionKind;
+
+    fn key(kind: SessionKind) -> SessionKey {
+        SessionKey {
+            agent_id: "coop".to_owned(),
+            kind,
+        }
+    }
+
+    fn usage(input: u32, output: u32) -> Usage {
+        Usage {
+            input_tokens: Some(input),
+            output_tokens: Some(output),
+            ..Default::default()
+        }
+    }
+
+    fn pricing(model: &str) -> Option<ModelPricing> {
+        (model == "priced").then_some(ModelPricing {
+            input: 1.0,
+            output: 10.0,
+            cache_read: None,
+            cache_write: None,
+        })
+    }
+
+    #[test]
+    fn groups_calls_and_prices_them() {
+        let dir = tempfile::tempdir().unwrap();
+        let ledger = UsageLedger::open(dir.path().join(USAGE_DB_FILE)).unwrap();
+        let dm = key(SessionKind::Dm("signal:alice".to_owned()));
+        let heartbeat = key(SessionKind::Cron("heartbeat".to_owned()));
+        let call = |session, user, model, usage| ProviderCall {
+            session,
+            user,
+            model,
+            usage,
+            latency: Duration::from_millis(100),
+        };
+
+        let big = usage(1_000_000, 100_000);
+        let small = usage(1_000, 10);
+        ledger
+            .record(&call(&dm, Some("alice"), "priced", &big))
+            .unwrap();
+        ledger
+            .record(&call(&heartbeat, Some("alice"), "local", &small))
+            .unwrap();
+        ledger
+            .record(&call(&heartbeat, Some("bob"), "priced", &small))
+            .unwrap();
+
+        let users = ledger
+            .report(UsageGrouping::User, UsageFilter::default(), &pricing)
+            .unwrap();
+        assert_eq!(users[0].label, "alice");
+        assert_eq!(users[0].calls, 2);
+        assert!((users[0].cost - 2.0).abs() < 1e-9);
+        assert!(users[0].unpriced);
+        assert!(!users[1].unpriced);
+
+        let cron = ledger
+            .report(UsageGrouping::Cron, UsageFilter::default(), &pricing)
+            .unwrap();
+        assert_eq!(cron.len(), 1);
+        assert_eq!((cron[0].label.as_str(), cron[0].calls), ("heartbeat", 2));
+
+        let bob = ledger
+            .report(
+                UsageGrouping::Model,
+                UsageFilter {
+                    user: Some("bob"),
+                    ..UsageFilter::default()
+                },
+                &pricing,
+            )
+            .unwrap();
+        assert_eq!(bob.len(), 1);
+        assert_eq!(bob[0].input_tokens, 1_000);
+
+        let heartbeat_total = ledger
+            .total(
+                UsageFilter {
+                    cron: Some("heartbeat"),
+                    ..UsageFilter::default()
+                },
+                &pricing,
+            )
+            .unwrap();
+        assert_eq!(heartbeat_total.calls, 2);
+        assert_eq!(heartbeat_total.input_tokens, 2_000);
+        assert!(heartbeat_total.unpriced);
+    }
+
+    #[test]
+    fn day_report_respects_since() {
+        let dir = tempfile::tempdir().unwrap();
+        let ledger = UsageLedger::open(dir.path().join(USAGE_DB_FILE)).unwrap();
+        let session = key(SessionKind::Main);
+        let usage = usage(10, 10);
+        let call = ProviderCall {
+            session: &session,
+            user: None,
+            model: "m",
+            usage: &usage,
+            latency: Duration::ZERO,
+        };
+        let now = Utc::now();
+        ledger
+            .record_at(&call, now - chrono::Duration::days(10))
+            .unwrap();
+        ledger.record_at(&call, now).unwrap();
+
+        let all = ledger
+            .report(UsageGrouping::Day, UsageFilter::default(), &|_| None)
+            .unwrap();
+        assert_eq!(all.len(), 2);
+        assert!(all[0].label < all[1].label);
+
+        let recent = ledger
+            .report(
+                UsageGrouping::Day,
+                UsageFilter {
+                    since: Some(now - chrono::Duration::days(1)),
+                    ..UsageFilter::default()
+                },
+                &|_| None,
+            )
+            .unwrap();
+        assert_eq!(recent.len(), 1);
+        assert_eq!(recent[0].label, now.format("%Y-%m-%d").to_string());
+    }
+}
@@ -14,6 +14,8 @@ use tokio::sync::mpsc;
 use tracing_subscriber::fmt::format::FmtSpan;
 use tracing_subscriber::prelude::*;
 
+#[path = "../src/budget.rs"]
+mod budget;
 #[path = "../src/compaction.rs"]
 mod compaction;
 #[path = "../src/compaction_store.rs"]
@@ -22,6 +24,11 @@ mod compaction_store;
 mod config;
 #[path = "../src/cron_delivery.rs"]
 mod cron_delivery;
+#[allow(dead_code)]
+#[path = "../src/cron_history.rs"]
+mod cron_history;
+#[path = "../src/failover.rs"]
+mod failover;
 #[path = "../src/final_reply.rs"]
 mod final_reply;
 #[path = "../src/gateway.rs"]
@@ -32,6 +39,9 @@ mod group_history;
 #[allow(dead_code)]
 #[path = "../src/group_trigger.rs"]
 mod group_trigger;
+#[allow(dead_code)]
+#[path = "../src/local_models.rs"]
+mod local_models;
 #[path = "../src/memory_auto_capture.rs"]
 mod memory_auto_capture;
 #[path = "../src/memory_prompt_index.rs"]
@@ -40,6 +50,10 @@ mod memory_prompt_index;
 mod model_capabilities;
 #[path = "../src/model_catalog.rs"]
 mod model_catalog;
+#[path = "../src/model_routing.rs"]
+mod model_routing;
+#[path = "../src/office_documents.rs"]
+mod office_documents;
 #[path = "../src/overflow_recovery.rs"]
 mod overflow_recovery;
 #[path = "../src/provider_factory.rs"]
@@ -48,12 +62,22 @@ mod provider_factory;
 #[path = "../src/provider_registry.rs"]
 mod provider_registry;
 #[allow(dead_code)]
+#[path = "../src/session_history.rs"]
+mod session_history;
 #[path = "../src/session_search.rs"]
 mod session_search;
 #[path = "../src/session_store.rs"]
 mod session_store;
+#[path = "../src/sqlite_session_store.rs"]
+mod sqlite_session_store;
 #[path = "../src/subagents/mod.rs"]
 mod subagents;
+#[path = "../src/tool_policy.rs"]
+mod tool_policy;
+#[path = "../src/transcription.rs"]
+mod transcription;
+#[path = "../src/usage_ledger.rs"]
+mod usage_ledger;
 #[path = "../src/user_model_store.rs"]
 mod user_model_store;
 
@@ -213,7 +237,7 @@ async fn auto_capture_writes_observations_after_turn() {
         .unwrap();
     assert_eq!(rows.len(), 1);
 
-    let observations = harness.memory.get(&[rows[0].id]).await.unwrap();
+    let observations = harness.memory.get(&[rows[0].id], None).await.unwrap();
     assert_eq!(
         observations[0].related_files,
         vec!["crates/coop-gateway/src/gateway.rs"]
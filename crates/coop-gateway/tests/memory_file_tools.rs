@@ -8,6 +8,10 @@ use coop_core::TrustLevel;
 use coop_core::traits::{ToolContext, ToolExecutor};
 use coop_memory::{Memory, NewObservation, SqliteMemory, WriteOutcome, min_trust_for_store};
 
+#[allow(dead_code)]
+#[path = "../src/config.rs"]
+mod config;
+#[allow(dead_code)]
 #[path = "../src/memory_tools.rs"]
 mod memory_tools;
 
@@ -60,6 +64,9 @@ impl Harness {
                 token_count: Some(8),
                 expires_at: None,
                 min_trust: min_trust_for_store(store),
+                owner: None,
+                shared_with: Vec::new(),
+                created_at: None,
             })
             .await
             .unwrap();
@@ -23,6 +23,8 @@ use tokio::time::sleep;
 use tracing_subscriber::fmt::format::FmtSpan;
 use tracing_subscriber::prelude::*;
 
+#[path = "../src/budget.rs"]
+mod budget;
 #[path = "../src/compaction.rs"]
 mod compaction;
 #[path = "../src/compaction_store.rs"]
@@ -32,6 +34,9 @@ mod config;
 #[path = "../src/cron_delivery.rs"]
 mod cron_delivery;
 #[allow(dead_code)]
+#[path = "../src/cron_history.rs"]
+mod cron_history;
+#[allow(dead_code)]
 #[path = "../src/group_history.rs"]
 mod group_history;
 #[allow(dead_code)]
@@ -41,10 +46,15 @@ mod group_trigger;
 #[path = "../src/provider_registry.rs"]
 mod provider_registry;
 
+#[path = "../src/failover.rs"]
+mod failover;
 #[path = "../src/final_reply.rs"]
 mod final_reply;
 #[path = "../src/gateway.rs"]
 mod gateway;
+#[allow(dead_code)]
+#[path = "../src/local_models.rs"]
+mod local_models;
 #[path = "../src/memory_auto_capture.rs"]
 mod memory_auto_capture;
 #[path = "../src/memory_prompt_index.rs"]
@@ -53,17 +63,31 @@ mod memory_prompt_index;
 mod model_capabilities;
 #[path = "../src/model_catalog.rs"]
 mod model_catalog;
+#[path = "../src/model_routing.rs"]
+mod model_routing;
+#[path = "../src/office_documents.rs"]
+mod office_documents;
 #[path = "../src/overflow_recovery.rs"]
 mod overflow_recovery;
 #[path = "../src/provider_factory.rs"]
 mod provider_factory;
 #[allow(dead_code)]
+#[path = "../src/session_history.rs"]
+mod session_history;
 #[path = "../src/session_search.rs"]
 mod session_search;
 #[path = "../src/session_store.rs"]
 mod session_store;
+#[path = "../src/sqlite_session_store.rs"]
+mod sqlite_session_store;
 #[path = "../src/subagents/mod.rs"]
 mod subagents;
+#[path = "../src/tool_policy.rs"]
+mod tool_policy;
+#[path = "../src/transcription.rs"]
+mod transcription;
+#[path = "../src/usage_ledger.rs"]
+mod usage_ledger;
 #[path = "../src/user_model_store.rs"]
 mod user_model_store;
 
@@ -324,6 +348,9 @@ async fn seed(memory: &SqliteMemory, store: &str, title: &str) -> i64 {
         token_count: Some(42),
         expires_at: None,
         min_trust: min_trust_for_store(store),
+        owner: None,
+        shared_with: Vec::new(),
+        created_at: None,
     };
 
     let result = memory.write(obs).await.unwrap();
@@ -359,11 +386,12 @@ impl Memory for FailingSearchMemory {
         _anchor: i64,
         _before: usize,
         _after: usize,
+        _viewer: Option<&str>,
     ) -> Result<Vec<ObservationIndex>> {
         Ok(Vec::new())
     }
 
-    async fn get(&self, _ids: &[i64]) -> Result<Vec<Observation>> {
+    async fn get(&self, _ids: &[i64], _viewer: Option<&str>) -> Result<Vec<Observation>> {
         Ok(Vec::new())
     }
 
@@ -371,7 +399,7 @@ impl Memory for FailingSearchMemory {
         anyhow::bail!("not used")
     }
 
-    async fn people(&self, _query: &str) -> Result<Vec<Person>> {
+    async fn people(&self, _query: &str, _viewer: Option<&str>) -> Result<Vec<Person>> {
         Ok(Vec::new())
     }
 
@@ -438,11 +466,12 @@ impl Memory for ScriptedMemory {
         _anchor: i64,
         _before: usize,
         _after: usize,
+        _viewer: Option<&str>,
     ) -> Result<Vec<ObservationIndex>> {
         Ok(Vec::new())
     }
 
-    async fn get(&self, _ids: &[i64]) -> Result<Vec<Observation>> {
+    async fn get(&self, _ids: &[i64], _viewer: Option<&str>) -> Result<Vec<Observation>> {
         Ok(Vec::new())
     }
 
@@ -450,7 +479,7 @@ impl Memory for ScriptedMemory {
         Ok(WriteOutcome::Skipped)
     }
 
-    async fn people(&self, _query: &str) -> Result<Vec<Person>> {
+    async fn people(&self, _query: &str, _viewer: Option<&str>) -> Result<Vec<Person>> {
         Ok(Vec::new())
     }
 
@@ -696,6 +725,7 @@ async fn query_aware_index_surfaces_relevant_non_recent_observation() {
         TrustLevel::Full,
         &config.memory.prompt_index,
         "tell me about the deployment pipeline",
+        None,
     )
     .await
     .unwrap()
@@ -727,6 +757,7 @@ async fn recent_window_rows_are_rendered_before_older_relevance_hits() {
         TrustLevel::Full,
         &config.memory.prompt_index,
         "what happened with migration and deploy",
+        None,
     )
     .await
     .unwrap()
@@ -759,6 +790,7 @@ async fn no_recent_rows_gives_full_slot_budget_to_relevance_hits() {
         TrustLevel::Full,
         &config.memory.prompt_index,
         "relevance",
+        None,
     )
     .await
     .unwrap()
@@ -809,6 +841,7 @@ async fn token_budget_prefers_recent_rows_and_truncates_relevance_rows() {
         TrustLevel::Full,
         &config.memory.prompt_index,
         "incident docs",
+        None,
     )
     .await
     .unwrap()
@@ -123,11 +123,12 @@ impl Memory for HistoricalMemory {
         _anchor: i64,
         _before: usize,
         _after: usize,
+        _viewer: Option<&str>,
     ) -> Result<Vec<ObservationIndex>> {
         Ok(Vec::new())
     }
 
-    async fn get(&self, _ids: &[i64]) -> Result<Vec<Observation>> {
+    async fn get(&self, _ids: &[i64], _viewer: Option<&str>) -> Result<Vec<Observation>> {
         Ok(Vec::new())
     }
 
@@ -135,7 +136,7 @@ impl Memory for HistoricalMemory {
         Ok(WriteOutcome::Skipped)
     }
 
-    async fn people(&self, _query: &str) -> Result<Vec<Person>> {
+    async fn people(&self, _query: &str, _viewer: Option<&str>) -> Result<Vec<Person>> {
         Ok(Vec::new())
     }
 
@@ -206,6 +207,7 @@ async fn fixture_recency_window_is_always_first_under_load() {
         TrustLevel::Full,
         &settings,
         "migration incident summary",
+        None,
     )
     .await
     .unwrap()
@@ -249,6 +251,7 @@ async fn fixture_with_no_recent_rows_allocates_full_limit_to_relevance() {
         TrustLevel::Full,
         &settings,
         "migration incident",
+        None,
     )
     .await
     .unwrap()
@@ -22,6 +22,8 @@ use tokio::sync::mpsc;
 use tracing_subscriber::fmt::format::FmtSpan;
 use tracing_subscriber::prelude::*;
 
+#[path = "../src/budget.rs"]
+mod budget;
 #[path = "../src/compaction.rs"]
 mod compaction;
 #[path = "../src/compaction_store.rs"]
@@ -31,6 +33,9 @@ mod config;
 #[path = "../src/cron_delivery.rs"]
 mod cron_delivery;
 #[allow(dead_code)]
+#[path = "../src/cron_history.rs"]
+mod cron_history;
+#[allow(dead_code)]
 #[path = "../src/group_history.rs"]
 mod group_history;
 #[allow(dead_code)]
@@ -40,10 +45,15 @@ mod group_trigger;
 #[path = "../src/provider_registry.rs"]
 mod provider_registry;
 
+#[path = "../src/failover.rs"]
+mod failover;
 #[path = "../src/final_reply.rs"]
 mod final_reply;
 #[path = "../src/gateway.rs"]
 mod gateway;
+#[allow(dead_code)]
+#[path = "../src/local_models.rs"]
+mod local_models;
 #[path = "../src/memory_auto_capture.rs"]
 mod memory_auto_capture;
 #[path = "../src/memory_prompt_index.rs"]
@@ -56,17 +66,31 @@ mod memory_tools;
 mod model_capabilities;
 #[path = "../src/model_catalog.rs"]
 mod model_catalog;
+#[path = "../src/model_routing.rs"]
+mod model_routing;
+#[path = "../src/office_documents.rs"]
+mod office_documents;
 #[path = "../src/overflow_recovery.rs"]
 mod overflow_recovery;
 #[path = "../src/provider_factory.rs"]
 mod provider_factory;
 #[allow(dead_code)]
+#[path = "../src/session_history.rs"]
+mod session_history;
 #[path = "../src/session_search.rs"]
 mod session_search;
 #[path = "../src/session_store.rs"]
 mod session_store;
+#[path = "../src/sqlite_session_store.rs"]
+mod sqlite_session_store;
 #[path = "../src/subagents/mod.rs"]
 mod subagents;
+#[path = "../src/tool_policy.rs"]
+mod tool_policy;
+#[path = "../src/transcription.rs"]
+mod transcription;
+#[path = "../src/usage_ledger.rs"]
+mod usage_ledger;
 #[path = "../src/user_model_store.rs"]
 mod user_model_store;
 
@@ -449,7 +473,7 @@ async fn update_path_mutates_row_and_records_update_history() {
     assert_eq!(payload["outcome"], "updated");
     assert_eq!(payload["id"].as_i64(), Some(seed_id));
 
-    let current = harness.memory.get(&[seed_id]).await.unwrap();
+    let current = harness.memory.get(&[seed_id], None).await.unwrap();
     assert_eq!(current.len(), 1);
     assert_eq!(current[0].facts, vec!["healthy", "stable"]);
 
@@ -486,7 +510,7 @@ async fn delete_path_expires_old_row_and_inserts_replacement() {
     assert_eq!(payload["outcome"], "deleted");
     assert_eq!(payload["id"].as_i64(), Some(old_id));
 
-    let old = harness.memory.get(&[old_id]).await.unwrap();
+    let old = harness.memory.get(&[old_id], None).await.unwrap();
     assert!(old.is_empty(), "deleted row should be inaccessible");
 
     let rows = search_shared(&harness.memory, "rotation key").await;
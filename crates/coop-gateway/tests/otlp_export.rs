@@ -0,0 +1,235 @@
+#![cfg(feature = "otel")]
+#![allow(clippy::unwrap_used)]
+#![allow(dead_code)]
+
+#[path = "../src/tracing_setup.rs"]
+mod tracing_setup;
+
+use std::sync::{Arc, Mutex};
+
+use opentelemetry_proto::tonic::collector::trace::v1::trace_service_server::{
+    TraceService, TraceServiceServer,
+};
+use opentelemetry_proto::tonic::collector::trace::v1::{
+    ExportTraceServiceRequest, ExportTraceServiceResponse,
+};
+use opentelemetry_proto::tonic::common::v1::any_value::Value;
+use opentelemetry_proto::tonic::trace::v1::Span;
+use prost::Message as _;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tracing_subscriber::prelude::*;
+
+use tracing_setup::{OtlpProtocol, OtlpSettings, otlp_layer, otlp_tracer_provider};
+
+/// In-process OTLP collector that records every exported span.
+#[derive(Clone, Default)]
+struct CollectorStub {
+    spans: Arc<Mutex<Vec<Span>>>,
+}
+
+impl CollectorStub {
+    fn record(&self, request: ExportTraceServiceRequest) {
+        let mut spans = self.spans.lock().unwrap();
+        for resource_spans in request.resource_spans {
+            for scope_spans in resource_spans.scope_spans {
+                spans.extend(scope_spans.spans);
+            }
+        }
+    }
+
+    fn span(&self, name: &str) -> Span {
+        self.spans
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|span| span.name == name)
+            .cloned()
+            .unwrap_or_else(|| panic!("collector did not receive a {name} span"))
+    }
+}
+
+#[tonic::async_trait]
+impl TraceService for CollectorStub {
+    async fn export(
+        &self,
+        request: tonic::Request<ExportTraceServiceRequest>,
+    ) -> Result<tonic::Response<ExportTraceServiceResponse>, tonic::Status> {
+        self.record(request.into_inner());
+        Ok(tonic::Response::new(ExportTraceServiceResponse::default()))
+    }
+}
+
+async fn spawn_grpc_collector(collector: CollectorStub) -> String {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(
+        tonic::transport::Server::builder()
+            .add_service(TraceServiceServer::new(collector))
+            .serve_with_incoming(tonic::transport::server::TcpIncoming::from(listener)),
+    );
+    format!("http://{addr}")
+}
+
+async fn spawn_http_collector(collector: CollectorStub) -> String {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        loop {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let body = read_http_body(&mut socket).await;
+            collector.record(ExportTraceServiceRequest::decode(body.as_slice()).unwrap());
+            socket
+                .write_all(
+                    b"HTTP/1.1 200 OK\r\ncontent-type: application/x-protobuf\r\ncontent-length: 0\r\nconnection: close\r\n\r\n",
+                )
+                .await
+                .unwrap();
+        }
+    });
+    format!("http://{addr}/v1/traces")
+}
+
+async fn read_http_body(socket: &mut tokio::net::TcpStream) -> Vec<u8> {
+    let mut buffer = Vec::new();
+    let mut chunk = [0_u8; 4096];
+    loop {
+        let read = socket.read(&mut chunk).await.unwrap();
+        assert!(read > 0, "connection closed before request completed");
+        buffer.extend_from_slice(&chunk[..read]);
+
+        let Some(header_end) = buffer.windows(4).position(|window| window == b"\r\n\r\n") else {
+            continue;
+        };
+        let headers = String::from_utf8_lossy(&buffer[..header_end]).to_ascii_lowercase();
+        assert!(headers.starts_with("post /v1/traces "), "{headers}");
+        let content_length: usize = headers
+            .lines()
+            .find_map(|line| line.strip_prefix("content-length:"))
+            .map(|value| value.trim().parse().unwrap())
+            .unwrap();
+        let body_start = header_end + 4;
+        if buffer.len() >= body_start + content_length {
+            return buffer[body_start..body_start + content_length].to_vec();
+        }
+    }
+}
+
+fn attribute(span: &Span, key: &str) -> Value {
+    span.attributes
+        .iter()
+        .find(|attribute| attribute.key == key)
+        .and_then(|attribute| attribute.value.clone())
+        .and_then(|value| value.value)
+        .unwrap_or_else(|| panic!("span {} has no {key} attribute", span.name))
+}
+
+/// Emit spans shaped like the gateway's turn loop and flush them through
+/// the OTLP exporter.
+async fn export_turn_spans(settings: OtlpSettings) {
+    let provider = otlp_tracer_provider(&settings).unwrap();
+    let subscriber = tracing_subscriber::Registry::default().with(otlp_layer(&provider));
+
+    tracing::subscriber::with_default(subscriber, || {
+        let turn = tracing::info_span!(
+            "agent_turn",
+            gen_ai.operation.name = "invoke_agent",
+            gen_ai.request.model = "test-model",
+            gen_ai.usage.input_tokens = tracing::field::Empty,
+        );
+        let _turn = turn.enter();
+        {
+            let request = tracing::info_span!(
+                "provider_request",
+                otel.kind = "client",
+                gen_ai.operation.name = "chat",
+                gen_ai.request.model = "test-model",
+                gen_ai.usage.input_tokens = 120_i64,
+                gen_ai.usage.output_tokens = 30_i64,
+            );
+            let _request = request.enter();
+        }
+        {
+            let tool = tracing::info_span!(
+                "tool_execute",
+                gen_ai.operation.name = "execute_tool",
+                gen_ai.tool.name = "read_file",
+                gen_ai.tool.call.id = "call_1",
+            );
+            let _tool = tool.enter();
+        }
+        turn.record("gen_ai.usage.input_tokens", 120_i64);
+    });
+
+    // Shutdown blocks until the batch processor has exported.
+    tokio::task::spawn_blocking(move || provider.shutdown())
+        .await
+        .unwrap()
+        .unwrap();
+}
+
+fn assert_turn_spans(collector: &CollectorStub) {
+    let turn = collector.span("agent_turn");
+    let request = collector.span("provider_request");
+    let tool = collector.span("tool_execute");
+
+    assert_eq!(request.parent_span_id, turn.span_id);
+    assert_eq!(tool.parent_span_id, turn.span_id);
+    assert_eq!(
+        attribute(&turn, "gen_ai.request.model"),
+        Value::StringValue("test-model".to_owned())
+    );
+    assert_eq!(
+        attribute(&turn, "gen_ai.usage.input_tokens"),
+        Value::IntValue(120)
+    );
+    assert_eq!(
+        attribute(&request, "gen_ai.operation.name"),
+        Value::StringValue("chat".to_owned())
+    );
+    assert_eq!(
+        attribute(&request, "gen_ai.usage.output_tokens"),
+        Value::IntValue(30)
+    );
+    assert_eq!(
+        attribute(&tool, "gen_ai.tool.name"),
+        Value::StringValue("read_file".to_owned())
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn exports_turn_spans_over_grpc() {
+    let collector = CollectorStub::default();
+    let endpoint = spawn_grpc_collector(collector.clone()).await;
+
+    export_turn_spans(OtlpSettings {
+        endpoint: Some(endpoint),
+        protocol: OtlpProtocol::Grpc,
+    })
+    .await;
+
+    assert_turn_spans(&collector);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn exports_turn_spans_over_http_protobuf() {
+    let collector = CollectorStub::default();
+    let endpoint = spawn_http_collector(collector.clone()).await;
+
+    export_turn_spans(OtlpSettings {
+        endpoint: Some(endpoint),
+        protocol: OtlpProtocol::HttpProtobuf,
+    })
+    .await;
+
+    assert_turn_spans(&collector);
+}
+
+#[test]
+fn parses_otlp_protocols() {
+    assert_eq!(OtlpProtocol::parse("grpc").unwrap(), OtlpProtocol::Grpc);
+    assert_eq!(
+        OtlpProtocol::parse("http/protobuf").unwrap(),
+        OtlpProtocol::HttpProtobuf
+    );
+    assert!(OtlpProtocol::parse("http/json").is_err());
+}
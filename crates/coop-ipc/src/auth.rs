@@ -0,0 +1,72 @@
+//! Challenge-response authentication for the remote listener.
+//!
+//! The server opens with a random nonce; the client answers with an
+//! HMAC-SHA256 of that nonce keyed by its token. The token never crosses
+//! the wire and a captured proof is useless for any other connection.
+
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// A fresh nonce for one connection attempt (244 random bits).
+pub fn new_challenge() -> String {
+    format!(
+        "{}{}",
+        uuid::Uuid::new_v4().simple(),
+        uuid::Uuid::new_v4().simple()
+    )
+}
+
+/// The proof a client holding `token` sends for `nonce`.
+pub fn challenge_proof(token: &str, nonce: &str) -> String {
+    hex::encode(mac(token, nonce).finalize().into_bytes())
+}
+
+/// Whether `proof` answers `nonce` for `token`, compared in constant time.
+pub fn verify_proof(token: &str, nonce: &str, proof: &str) -> bool {
+    let Ok(proof) = hex::decode(proof) else {
+        return false;
+    };
+    mac(token, nonce).verify_slice(&proof).is_ok()
+}
+
+/// Byte comparison whose timing does not depend on where `a` and `b`
+/// differ, for checking shared secrets such as webhook tokens.
+pub fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
+fn mac(token: &str, nonce: &str) -> HmacSha256 {
+    let mut mac =
+        HmacSha256::new_from_slice(token.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(nonce.as_bytes());
+    mac
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn proofs_verify_only_for_the_same_token_and_nonce() {
+        let nonce = new_challenge();
+        assert_eq!(nonce.len(), 64);
+        assert_ne!(nonce, new_challenge());
+
+        let proof = challenge_proof("s3cret", &nonce);
+        assert!(verify_proof("s3cret", &nonce, &proof));
+        assert!(!verify_proof("other", &nonce, &proof));
+        assert!(!verify_proof("s3cret", &new_challenge(), &proof));
+        assert!(!verify_proof("s3cret", &nonce, "not hex"));
+        assert!(!proof.contains("s3cret"));
+    }
+
+    #[test]
+    fn constant_time_eq_compares_whole_slices() {
+        assert!(constant_time_eq(b"token", b"token"));
+        assert!(!constant_time_eq(b"token", b"tokex"));
+        assert!(!constant_time_eq(b"token", b"token!"));
+        assert!(constant_time_eq(b"", b""));
+    }
+}
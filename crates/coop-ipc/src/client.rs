@@ -1,24 +1,26 @@
 use crate::protocol::{ClientMessage, ServerMessage};
 use anyhow::{Context, Result};
 use std::path::Path;
-use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader, Lines};
-use tokio::net::UnixStream;
-use tokio::net::unix::{OwnedReadHalf, OwnedWriteHalf};
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader, Lines};
+use tokio::net::{TcpStream, UnixStream};
+
+type BoxedReader = Box<dyn AsyncRead + Send + Unpin>;
+type BoxedWriter = Box<dyn AsyncWrite + Send + Unpin>;
 
 #[allow(missing_debug_implementations)]
 pub struct IpcClient {
-    reader: Lines<BufReader<OwnedReadHalf>>,
-    writer: OwnedWriteHalf,
+    reader: Lines<BufReader<BoxedReader>>,
+    writer: BoxedWriter,
 }
 
 #[allow(missing_debug_implementations)]
 pub struct IpcReader {
-    reader: Lines<BufReader<OwnedReadHalf>>,
+    reader: Lines<BufReader<BoxedReader>>,
 }
 
 #[allow(missing_debug_implementations)]
 pub struct IpcWriter {
-    writer: OwnedWriteHalf,
+    writer: BoxedWriter,
 }
 
 impl IpcClient {
@@ -27,11 +29,23 @@ impl IpcClient {
             .await
             .with_context(|| format!("failed to connect to {}", socket_path.display()))?;
         let (read_half, write_half) = stream.into_split();
+        Ok(Self::new(Box::new(read_half), Box::new(write_half)))
+    }
+
+    /// Connect to a gateway's remote listener (`[ipc.remote]`).
+    pub async fn connect_tcp(addr: &str) -> Result<Self> {
+        let stream = TcpStream::connect(addr)
+            .await
+            .with_context(|| format!("failed to connect to {addr}"))?;
+        let (read_half, write_half) = stream.into_split();
+        Ok(Self::new(Box::new(read_half), Box::new(write_half)))
+    }
 
-        Ok(Self {
-            reader: BufReader::new(read_half).lines(),
-            writer: write_half,
-        })
+    fn new(reader: BoxedReader, writer: BoxedWriter) -> Self {
+        Self {
+            reader: BufReader::new(reader).lines(),
+            writer,
+        }
     }
 
     pub fn into_split(self) -> (IpcReader, IpcWriter) {
@@ -66,7 +80,7 @@ impl IpcWriter {
     }
 }
 
-async fn send_message(writer: &mut OwnedWriteHalf, message: &ClientMessage) -> Result<()> {
+async fn send_message(writer: &mut BoxedWriter, message: &ClientMessage) -> Result<()> {
     let encoded = serde_json::to_string(message).context("failed to encode client message")?;
     writer
         .write_all(encoded.as_bytes())
@@ -83,7 +97,7 @@ async fn send_message(writer: &mut OwnedWriteHalf, message: &ClientMessage) -> R
     Ok(())
 }
 
-async fn recv_message(reader: &mut Lines<BufReader<OwnedReadHalf>>) -> Result<ServerMessage> {
+async fn recv_message(reader: &mut Lines<BufReader<BoxedReader>>) -> Result<ServerMessage> {
     loop {
         let line = reader
             .next_line()
@@ -104,7 +118,7 @@ async fn recv_message(reader: &mut Lines<BufReader<OwnedReadHalf>>) -> Result<Se
 mod tests {
     use super::*;
     use crate::protocol::PROTOCOL_VERSION;
-    use crate::server::IpcServer;
+    use crate::server::{IpcServer, RemoteIpcServer};
 
     fn temp_socket(name: &str) -> std::path::PathBuf {
         let millis = std::time::SystemTime::now()
@@ -128,13 +142,15 @@ mod tests {
             assert_eq!(
                 message,
                 ClientMessage::Hello {
-                    version: PROTOCOL_VERSION
+                    version: PROTOCOL_VERSION,
+                    proof: None,
                 }
             );
             connection
                 .send(ServerMessage::Hello {
                     version: PROTOCOL_VERSION,
                     agent_id: "coop".into(),
+                    agents: vec!["coop".into()],
                 })
                 .await
                 .unwrap();
@@ -144,6 +160,7 @@ mod tests {
         client
             .send(ClientMessage::Hello {
                 version: PROTOCOL_VERSION,
+                proof: None,
             })
             .await
             .unwrap();
@@ -154,9 +171,73 @@ mod tests {
             ServerMessage::Hello {
                 version: PROTOCOL_VERSION,
                 agent_id: "coop".into(),
+                agents: vec!["coop".into()],
             }
         );
 
         server_task.await.unwrap();
     }
+
+    #[tokio::test]
+    async fn tcp_client_answers_the_challenge_without_sending_the_token() {
+        let server = RemoteIpcServer::bind("127.0.0.1:0").await.unwrap();
+        let addr = server.local_addr().unwrap().to_string();
+
+        let server_task = tokio::spawn(async move {
+            let (mut connection, _peer) = server.accept().await.unwrap();
+            let nonce = crate::auth::new_challenge();
+            connection
+                .send(ServerMessage::Challenge {
+                    nonce: nonce.clone(),
+                })
+                .await
+                .unwrap();
+            (nonce, connection.recv().await.unwrap())
+        });
+
+        let mut client = IpcClient::connect_tcp(&addr).await.unwrap();
+        let ServerMessage::Challenge { nonce } = client.recv().await.unwrap() else {
+            panic!("expected a challenge");
+        };
+        client
+            .send(ClientMessage::Hello {
+                version: PROTOCOL_VERSION,
+                proof: Some(crate::auth::challenge_proof("secret", &nonce)),
+            })
+            .await
+            .unwrap();
+
+        let (nonce, hello) = server_task.await.unwrap();
+        let ClientMessage::Hello {
+            proof: Some(proof), ..
+        } = hello
+        else {
+            panic!("expected a hello with a proof");
+        };
+        assert!(crate::auth::verify_proof("secret", &nonce, &proof));
+    }
+
+    #[tokio::test]
+    async fn remote_connections_refuse_oversized_lines() {
+        use crate::server::MAX_REMOTE_MESSAGE_BYTES;
+
+        let server = RemoteIpcServer::bind("127.0.0.1:0").await.unwrap();
+        let addr = server.local_addr().unwrap();
+
+        let server_task = tokio::spawn(async move {
+            let (mut connection, _peer) = server.accept().await.unwrap();
+            connection.recv().await
+        });
+
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        let chunk = vec![b'x'; 64 * 1024];
+        for _ in 0..=MAX_REMOTE_MESSAGE_BYTES / chunk.len() {
+            if stream.write_all(&chunk).await.is_err() {
+                break;
+            }
+        }
+
+        let error = server_task.await.unwrap().unwrap_err();
+        assert!(error.to_string().contains("exceeds"), "{error}");
+    }
 }
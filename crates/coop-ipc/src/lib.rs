@@ -1,10 +1,14 @@
+pub mod auth;
 pub mod client;
 pub mod protocol;
 pub mod server;
 
 pub use client::{IpcClient, IpcReader, IpcWriter};
-pub use protocol::{ClientMessage, PROTOCOL_VERSION, ServerMessage};
-pub use server::{IpcConnection, IpcServer};
+pub use protocol::{
+    ClientMessage, HistoryMessage, HistoryToolCall, HistoryToolResult, PROTOCOL_VERSION,
+    ServerMessage, TurnEventEncoder, negotiate_version,
+};
+pub use server::{IpcConnection, IpcServer, RemoteIpcServer};
 
 use std::path::PathBuf;
 
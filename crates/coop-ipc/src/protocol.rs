@@ -1,26 +1,127 @@
-use coop_core::{Content, Message, TurnEvent};
+use coop_core::{Content, Message, Role, TurnEvent};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::collections::HashMap;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
 
-pub const PROTOCOL_VERSION: u32 = 1;
+/// Version 2 adds history, commands, model switching, usage, and
+/// compaction, subagent and tool timing events. Version 1 clients keep
+/// working: the server answers `Hello` with the lower of the two versions
+/// and never sends them anything newer.
+pub const PROTOCOL_VERSION: u32 = 2;
+
+/// Messages returned by one `GetHistory` request when `limit` is omitted.
+pub const DEFAULT_HISTORY_LIMIT: usize = 50;
+/// Upper bound on `GetHistory` page size.
+pub const MAX_HISTORY_LIMIT: usize = 500;
+
+/// Version both sides speak after a client says hello with `client_version`.
+pub fn negotiate_version(client_version: u32) -> u32 {
+    client_version.clamp(1, PROTOCOL_VERSION)
+}
+
+const fn default_history_limit() -> usize {
+    DEFAULT_HISTORY_LIMIT
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum ClientMessage {
-    Hello { version: u32 },
-    Send { session: String, content: String },
-    Clear { session: String },
-    Stop { session: String },
-    ListSessions,
-    Subscribe { session: String },
+    Hello {
+        version: u32,
+        /// Required on the remote listener: the answer to its `Challenge`
+        /// (see [`crate::auth::challenge_proof`]), which maps the client to
+        /// a `[[users]]` entry. Ignored on the local socket.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        proof: Option<String>,
+    },
+    Send {
+        session: String,
+        content: String,
+    },
+    Clear {
+        session: String,
+    },
+    Stop {
+        session: String,
+    },
+    /// List sessions across every hosted agent, or only `agent`'s.
+    ListSessions {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        agent: Option<String>,
+    },
+    /// Sessions are named `main` (default agent) or `<agent>:<session>`.
+    Subscribe {
+        session: String,
+    },
+    /// Page backwards through a session's transcript, newest page first.
+    GetHistory {
+        session: String,
+        /// Index to page back from (exclusive); the end of the transcript
+        /// when omitted. Pass the previous page's `start` to continue.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        before: Option<usize>,
+        #[serde(default = "default_history_limit")]
+        limit: usize,
+    },
+    /// Run a slash command such as `/status` or `/models`.
+    RunCommand {
+        session: String,
+        command: String,
+    },
+    /// Switch the main model for the session's user, like `/model <id>`.
+    SetModel {
+        session: String,
+        model: String,
+    },
+    GetUsage {
+        session: String,
+    },
+}
+
+impl ClientMessage {
+    /// The session this message addresses, if any.
+    pub fn session(&self) -> Option<&str> {
+        match self {
+            Self::Send { session, .. }
+            | Self::Clear { session }
+            | Self::Stop { session }
+            | Self::Subscribe { session }
+            | Self::GetHistory { session, .. }
+            | Self::RunCommand { session, .. }
+            | Self::SetModel { session, .. }
+            | Self::GetUsage { session } => Some(session),
+            Self::Hello { .. } | Self::ListSessions { .. } => None,
+        }
+    }
+
+    /// Lowest protocol version that understands this message.
+    pub fn min_version(&self) -> u32 {
+        match self {
+            Self::GetHistory { .. }
+            | Self::RunCommand { .. }
+            | Self::SetModel { .. }
+            | Self::GetUsage { .. } => 2,
+            _ => 1,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum ServerMessage {
+    /// First message on the remote listener. The client must answer with a
+    /// `Hello` carrying a proof for `nonce`.
+    Challenge {
+        nonce: String,
+    },
     Hello {
         version: u32,
+        /// The default agent.
         agent_id: String,
+        /// Every agent hosted by the gateway, default first.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        agents: Vec<String>,
     },
     TextDelta {
         session: String,
@@ -31,12 +132,18 @@ pub enum ServerMessage {
         id: String,
         name: String,
         arguments: Value,
+        /// Unix time in milliseconds when the tool started (v2).
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        started_at_ms: Option<u64>,
     },
     ToolResult {
         session: String,
         id: String,
         output: String,
         is_error: bool,
+        /// Wall-clock time since the matching `ToolStart` (v2).
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        duration_ms: Option<u64>,
     },
     AssistantMessage {
         session: String,
@@ -54,9 +161,174 @@ pub enum ServerMessage {
     Sessions {
         keys: Vec<String>,
     },
+    /// One page of transcript. Older messages remain while `start > 0`.
+    History {
+        session: String,
+        start: usize,
+        total: usize,
+        messages: Vec<HistoryMessage>,
+    },
+    CommandOutput {
+        session: String,
+        command: String,
+        output: String,
+    },
+    ModelChanged {
+        session: String,
+        model: String,
+        context_limit: usize,
+        /// The session was compacted before handing off to the new model.
+        compacted: bool,
+    },
+    Usage {
+        session: String,
+        model: String,
+        context_limit: usize,
+        /// Input tokens of the last request, i.e. the current context size.
+        context_tokens: u32,
+        input_tokens: u32,
+        output_tokens: u32,
+        cache_read_tokens: u32,
+        cache_write_tokens: u32,
+        messages: usize,
+    },
+    /// The session is being summarized to fit the context window.
+    Compacting {
+        session: String,
+    },
+    /// A subagent spawned from `session` was queued, started or finished.
+    SubagentProgress {
+        session: String,
+        run_id: String,
+        /// `queued`, `running`, `completed`, `failed`, `cancelled` or
+        /// `timed_out`.
+        status: String,
+        task: String,
+        model: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        summary: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        error: Option<String>,
+    },
+}
+
+/// A transcript message as shown to IPC clients.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct HistoryMessage {
+    pub role: Role,
+    /// Unix time in milliseconds.
+    pub created_at_ms: i64,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub text: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tool_calls: Vec<HistoryToolCall>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tool_results: Vec<HistoryToolResult>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct HistoryToolCall {
+    pub id: String,
+    pub name: String,
+    pub arguments: Value,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct HistoryToolResult {
+    pub id: String,
+    pub output: String,
+    pub is_error: bool,
+}
+
+impl From<&Message> for HistoryMessage {
+    fn from(message: &Message) -> Self {
+        let mut tool_calls = Vec::new();
+        let mut tool_results = Vec::new();
+        for content in &message.content {
+            match content {
+                Content::ToolRequest {
+                    id,
+                    name,
+                    arguments,
+                } => tool_calls.push(HistoryToolCall {
+                    id: id.clone(),
+                    name: name.clone(),
+                    arguments: arguments.clone(),
+                }),
+                Content::ToolResult {
+                    id,
+                    output,
+                    is_error,
+                } => tool_results.push(HistoryToolResult {
+                    id: id.clone(),
+                    output: output.clone(),
+                    is_error: *is_error,
+                }),
+                _ => {}
+            }
+        }
+
+        Self {
+            role: message.role,
+            created_at_ms: message.created.timestamp_millis(),
+            text: message.text(),
+            tool_calls,
+            tool_results,
+        }
+    }
 }
 
 impl ServerMessage {
+    /// Lowest protocol version that understands this message.
+    pub fn min_version(&self) -> u32 {
+        match self {
+            Self::History { .. }
+            | Self::CommandOutput { .. }
+            | Self::ModelChanged { .. }
+            | Self::Usage { .. }
+            | Self::Compacting { .. }
+            | Self::SubagentProgress { .. } => 2,
+            _ => 1,
+        }
+    }
+
+    /// Downgrade for a connection speaking `version`: drop messages it does
+    /// not know and fields it never asked for.
+    pub fn for_version(mut self, version: u32) -> Option<Self> {
+        if self.min_version() > version {
+            return None;
+        }
+        if version < 2 {
+            match &mut self {
+                Self::ToolStart { started_at_ms, .. } => *started_at_ms = None,
+                Self::ToolResult { duration_ms, .. } => *duration_ms = None,
+                _ => {}
+            }
+        }
+        Some(self)
+    }
+
+    /// Page of `messages` ending before `before`, at most `limit` long.
+    pub fn history(
+        session: impl Into<String>,
+        messages: &[Message],
+        before: Option<usize>,
+        limit: usize,
+    ) -> Self {
+        let total = messages.len();
+        let end = before.map_or(total, |before| before.min(total));
+        let start = end.saturating_sub(limit.clamp(1, MAX_HISTORY_LIMIT));
+        Self::History {
+            session: session.into(),
+            start,
+            total,
+            messages: messages[start..end]
+                .iter()
+                .map(HistoryMessage::from)
+                .collect(),
+        }
+    }
+
     pub fn from_turn_event(session: impl Into<String>, event: TurnEvent) -> Option<Self> {
         let session = session.into();
 
@@ -75,6 +347,7 @@ impl ServerMessage {
                 id,
                 name,
                 arguments,
+                started_at_ms: None,
             }),
             TurnEvent::ToolResult { id, message } => {
                 let (output, is_error) = tool_result_payload(&message);
@@ -83,6 +356,7 @@ impl ServerMessage {
                     id,
                     output,
                     is_error,
+                    duration_ms: None,
                 })
             }
             TurnEvent::Done(result) => Some(Self::Done {
@@ -91,8 +365,49 @@ impl ServerMessage {
                 hit_limit: result.hit_limit,
             }),
             TurnEvent::Error(message) => Some(Self::Error { session, message }),
-            TurnEvent::Compacting => None,
+            TurnEvent::Compacting => Some(Self::Compacting { session }),
+        }
+    }
+}
+
+/// Maps one turn's events to server messages, timing each tool call.
+#[derive(Debug)]
+pub struct TurnEventEncoder {
+    session: String,
+    tool_starts: HashMap<String, Instant>,
+}
+
+impl TurnEventEncoder {
+    pub fn new(session: impl Into<String>) -> Self {
+        Self {
+            session: session.into(),
+            tool_starts: HashMap::new(),
+        }
+    }
+
+    pub fn encode(&mut self, event: TurnEvent) -> Option<ServerMessage> {
+        let mut message = ServerMessage::from_turn_event(self.session.clone(), event)?;
+        match &mut message {
+            ServerMessage::ToolStart {
+                id, started_at_ms, ..
+            } => {
+                self.tool_starts.insert(id.clone(), Instant::now());
+                *started_at_ms = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .ok()
+                    .and_then(|elapsed| u64::try_from(elapsed.as_millis()).ok());
+            }
+            ServerMessage::ToolResult {
+                id, duration_ms, ..
+            } => {
+                *duration_ms = self
+                    .tool_starts
+                    .remove(id.as_str())
+                    .and_then(|started| u64::try_from(started.elapsed().as_millis()).ok());
+            }
+            _ => {}
         }
+        Some(message)
     }
 }
 
@@ -126,6 +441,40 @@ mod tests {
         assert_eq!(parsed, message);
     }
 
+    #[test]
+    fn list_sessions_agent_filter_is_optional() {
+        let parsed: ClientMessage = serde_json::from_str(r#"{"type":"list_sessions"}"#).unwrap();
+        assert_eq!(parsed, ClientMessage::ListSessions { agent: None });
+        assert_eq!(
+            serde_json::to_string(&parsed).unwrap(),
+            r#"{"type":"list_sessions"}"#
+        );
+
+        let message = ClientMessage::ListSessions {
+            agent: Some("work".into()),
+        };
+        let json = serde_json::to_string(&message).unwrap();
+        assert_eq!(json, r#"{"type":"list_sessions","agent":"work"}"#);
+        assert_eq!(
+            serde_json::from_str::<ClientMessage>(&json).unwrap(),
+            message
+        );
+    }
+
+    #[test]
+    fn hello_without_agents_list_parses() {
+        let parsed: ServerMessage =
+            serde_json::from_str(r#"{"type":"hello","version":1,"agent_id":"coop"}"#).unwrap();
+        assert_eq!(
+            parsed,
+            ServerMessage::Hello {
+                version: 1,
+                agent_id: "coop".into(),
+                agents: Vec::new(),
+            }
+        );
+    }
+
     #[test]
     fn server_message_round_trip() {
         let message = ServerMessage::Done {
@@ -153,6 +502,7 @@ mod tests {
                 id: "call_1".into(),
                 output: "ok".into(),
                 is_error: false,
+                duration_ms: None,
             }
         );
     }
@@ -179,4 +529,150 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    fn negotiates_down_to_the_older_side() {
+        assert_eq!(negotiate_version(1), 1);
+        assert_eq!(negotiate_version(PROTOCOL_VERSION), PROTOCOL_VERSION);
+        assert_eq!(negotiate_version(PROTOCOL_VERSION + 5), PROTOCOL_VERSION);
+        assert_eq!(negotiate_version(0), 1);
+    }
+
+    #[test]
+    fn v1_connections_never_see_v2_messages_or_fields() {
+        let compacting = ServerMessage::Compacting {
+            session: "main".into(),
+        };
+        assert_eq!(compacting.clone().for_version(1), None);
+        assert_eq!(compacting.clone().for_version(2), Some(compacting));
+
+        let tool_start = ServerMessage::ToolStart {
+            session: "main".into(),
+            id: "call_1".into(),
+            name: "bash".into(),
+            arguments: serde_json::json!({}),
+            started_at_ms: Some(1_700_000_000_000),
+        };
+        let json = serde_json::to_string(&tool_start.clone().for_version(1).unwrap()).unwrap();
+        assert!(!json.contains("started_at_ms"), "{json}");
+        assert_eq!(tool_start.clone().for_version(2), Some(tool_start));
+
+        assert_eq!(
+            ClientMessage::GetUsage {
+                session: "main".into()
+            }
+            .min_version(),
+            2
+        );
+        assert_eq!(
+            ClientMessage::Stop {
+                session: "main".into()
+            }
+            .min_version(),
+            1
+        );
+    }
+
+    #[test]
+    fn get_history_defaults_to_newest_page() {
+        let parsed: ClientMessage =
+            serde_json::from_str(r#"{"type":"get_history","session":"main"}"#).unwrap();
+        assert_eq!(
+            parsed,
+            ClientMessage::GetHistory {
+                session: "main".into(),
+                before: None,
+                limit: DEFAULT_HISTORY_LIMIT,
+            }
+        );
+    }
+
+    #[test]
+    fn history_pages_backwards() {
+        let messages: Vec<Message> = (0..5)
+            .map(|i| Message::user().with_text(format!("message {i}")))
+            .collect();
+
+        let ServerMessage::History {
+            start,
+            total,
+            messages: page,
+            ..
+        } = ServerMessage::history("main", &messages, None, 2)
+        else {
+            panic!("expected history");
+        };
+        assert_eq!((start, total), (3, 5));
+        assert_eq!(page[0].text, "message 3");
+        assert_eq!(page[1].text, "message 4");
+
+        let ServerMessage::History {
+            start,
+            messages: page,
+            ..
+        } = ServerMessage::history("main", &messages, Some(start), 10)
+        else {
+            panic!("expected history");
+        };
+        assert_eq!(start, 0);
+        assert_eq!(page.len(), 3);
+    }
+
+    #[test]
+    fn history_message_keeps_tool_calls_and_results() {
+        let call = Message::assistant().with_tool_request(
+            "call_1",
+            "read_file",
+            serde_json::json!({"path": "a.txt"}),
+        );
+        let result = Message::user().with_tool_result("call_1", "contents", true);
+
+        let call = HistoryMessage::from(&call);
+        assert_eq!(call.role, Role::Assistant);
+        assert_eq!(call.tool_calls[0].name, "read_file");
+        let result = HistoryMessage::from(&result);
+        assert_eq!(result.tool_results[0].output, "contents");
+        assert!(result.tool_results[0].is_error);
+    }
+
+    #[test]
+    fn encoder_times_tool_calls_and_reports_compaction() {
+        let mut encoder = TurnEventEncoder::new("main");
+
+        let start = encoder
+            .encode(TurnEvent::ToolStart {
+                id: "call_1".into(),
+                name: "bash".into(),
+                arguments: serde_json::json!({}),
+            })
+            .unwrap();
+        assert!(matches!(
+            start,
+            ServerMessage::ToolStart {
+                started_at_ms: Some(_),
+                ..
+            }
+        ));
+
+        let result = encoder
+            .encode(TurnEvent::ToolResult {
+                id: "call_1".into(),
+                message: Message::user().with_tool_result("call_1", "ok", false),
+            })
+            .unwrap();
+        assert!(matches!(
+            result,
+            ServerMessage::ToolResult {
+                duration_ms: Some(_),
+                ..
+            }
+        ));
+
+        assert_eq!(
+            encoder.encode(TurnEvent::Compacting),
+            Some(ServerMessage::Compacting {
+                session: "main".into()
+            })
+        );
+    }
 }
@@ -1,11 +1,19 @@
 use crate::protocol::{ClientMessage, ServerMessage};
 use anyhow::{Context, Result};
+use std::net::SocketAddr;
 use std::path::{Path, PathBuf};
-use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader, Lines};
-use tokio::net::unix::{OwnedReadHalf, OwnedWriteHalf};
-use tokio::net::{UnixListener, UnixStream};
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream, UnixListener};
 use tracing::{debug, trace};
 
+type BoxedReader = Box<dyn AsyncRead + Send + Unpin>;
+type BoxedWriter = Box<dyn AsyncWrite + Send + Unpin>;
+
+/// Longest message a remote client may send. Lines are read before the
+/// client authenticates, so an unbounded read would let anyone on the
+/// network exhaust memory.
+pub const MAX_REMOTE_MESSAGE_BYTES: usize = 1024 * 1024;
+
 #[allow(missing_debug_implementations)]
 pub struct IpcServer {
     socket_path: PathBuf,
@@ -47,7 +55,12 @@ impl IpcServer {
             .await
             .context("failed to accept IPC connection")?;
         debug!("ipc client connected");
-        Ok(IpcConnection::new(stream))
+        let (read_half, write_half) = stream.into_split();
+        Ok(IpcConnection::new(
+            Box::new(read_half),
+            Box::new(write_half),
+            None,
+        ))
     }
 }
 
@@ -57,28 +70,68 @@ impl Drop for IpcServer {
     }
 }
 
+/// TCP listener for clients on other machines. The gateway authenticates
+/// these connections itself; the stream is not encrypted.
+#[allow(missing_debug_implementations)]
+pub struct RemoteIpcServer {
+    listener: TcpListener,
+}
+
+impl RemoteIpcServer {
+    pub async fn bind(addr: &str) -> Result<Self> {
+        let listener = TcpListener::bind(addr)
+            .await
+            .with_context(|| format!("failed to bind {addr}"))?;
+        Ok(Self { listener })
+    }
+
+    pub fn local_addr(&self) -> Result<SocketAddr> {
+        self.listener
+            .local_addr()
+            .context("failed to read listener address")
+    }
+
+    pub async fn accept(&self) -> Result<(IpcConnection, SocketAddr)> {
+        let (stream, peer): (TcpStream, SocketAddr) = self
+            .listener
+            .accept()
+            .await
+            .context("failed to accept remote IPC connection")?;
+        debug!(peer = %peer, "remote ipc client connected");
+        let (read_half, write_half) = stream.into_split();
+        Ok((
+            IpcConnection::new(
+                Box::new(read_half),
+                Box::new(write_half),
+                Some(MAX_REMOTE_MESSAGE_BYTES),
+            ),
+            peer,
+        ))
+    }
+}
+
 #[allow(missing_debug_implementations)]
 pub struct IpcConnection {
-    reader: Lines<BufReader<OwnedReadHalf>>,
-    writer: OwnedWriteHalf,
+    reader: BufReader<BoxedReader>,
+    writer: BoxedWriter,
+    /// Longest accepted line in bytes; `None` on the local socket.
+    max_line: Option<usize>,
 }
 
 impl IpcConnection {
-    fn new(stream: UnixStream) -> Self {
-        let (read_half, write_half) = stream.into_split();
+    fn new(reader: BoxedReader, writer: BoxedWriter, max_line: Option<usize>) -> Self {
         Self {
-            reader: BufReader::new(read_half).lines(),
-            writer: write_half,
+            reader: BufReader::new(reader),
+            writer,
+            max_line,
         }
     }
 
     pub async fn recv(&mut self) -> Result<ClientMessage> {
         loop {
             let line = self
-                .reader
                 .next_line()
-                .await
-                .context("failed to read client message")?
+                .await?
                 .ok_or_else(|| anyhow::anyhow!("client disconnected"))?;
 
             if line.trim().is_empty() {
@@ -92,6 +145,38 @@ impl IpcConnection {
         }
     }
 
+    /// Read one line, refusing it once it grows past `max_line`.
+    async fn next_line(&mut self) -> Result<Option<String>> {
+        let mut buf = Vec::new();
+        let read = match self.max_line {
+            Some(max) => {
+                // One extra byte for the newline.
+                (&mut self.reader)
+                    .take(max as u64 + 1)
+                    .read_until(b'\n', &mut buf)
+                    .await
+            }
+            None => self.reader.read_until(b'\n', &mut buf).await,
+        }
+        .context("failed to read client message")?;
+        if read == 0 {
+            return Ok(None);
+        }
+        if buf.last() == Some(&b'\n') {
+            buf.pop();
+        } else if let Some(max) = self.max_line
+            && buf.len() > max
+        {
+            anyhow::bail!("client message exceeds {max} bytes");
+        }
+        if buf.last() == Some(&b'\r') {
+            buf.pop();
+        }
+        String::from_utf8(buf)
+            .map(Some)
+            .context("client message is not valid UTF-8")
+    }
+
     pub async fn send(&mut self, message: ServerMessage) -> Result<()> {
         trace!(msg = ?message, "ipc send");
         let encoded = serde_json::to_string(&message).context("failed to encode server message")?;
@@ -1,5 +1,6 @@
 pub mod sqlite;
 pub mod traits;
+pub mod transfer;
 pub mod types;
 
 pub use sqlite::SqliteMemory;
@@ -0,0 +1,464 @@
+use anyhow::Result;
+use rusqlite::{OptionalExtension, params};
+use serde_json::Value as JsonValue;
+
+use crate::types::{ArchivedObservation, ObservationEdit, min_trust_for_store, trust_to_str};
+
+use super::{SqliteMemory, helpers, write_ops};
+
+pub(super) fn edit(memory: &SqliteMemory, id: i64, edit: &ObservationEdit) -> Result<bool> {
+    let Some(current) = memory.load_observation(id)? else {
+        return Ok(false);
+    };
+
+    let title = edit.title.clone().unwrap_or_else(|| current.title.clone());
+    let narrative = edit
+        .narrative
+        .clone()
+        .unwrap_or_else(|| current.narrative.clone());
+    let facts = edit.facts.clone().unwrap_or_else(|| current.facts.clone());
+    let tags = edit.tags.clone().unwrap_or_else(|| current.tags.clone());
+    let obs_type = edit
+        .obs_type
+        .clone()
+        .unwrap_or_else(|| current.obs_type.clone());
+    let store = edit.store.clone().unwrap_or_else(|| current.store.clone());
+
+    let now = helpers::now_ms();
+    let conn = memory.conn.lock().expect("memory db mutex poisoned");
+    conn.execute(
+        "
+            UPDATE observations
+            SET title = ?,
+                narrative = ?,
+                facts = ?,
+                tags = ?,
+                type = ?,
+                store = ?,
+                min_trust = ?,
+                hash = ?,
+                token_count = ?,
+                updated_at = ?
+            WHERE id = ?
+              AND agent_id = ?
+            ",
+        params![
+            title,
+            narrative,
+            helpers::to_json(&facts),
+            helpers::to_json(&tags),
+            obs_type,
+            store,
+            trust_to_str(min_trust_for_store(&store)),
+            helpers::observation_hash(&title, &facts),
+            write_ops::estimate_token_count(&title, &narrative, &facts),
+            now,
+            id,
+            memory.agent_id,
+        ],
+    )?;
+
+    conn.execute(
+        "
+            INSERT INTO observation_history (
+                observation_id,
+                old_title,
+                old_facts,
+                new_title,
+                new_facts,
+                event,
+                created_at
+            ) VALUES (?, ?, ?, ?, ?, 'EDIT', ?)
+            ",
+        params![
+            id,
+            current.title,
+            helpers::to_json(&current.facts),
+            title,
+            helpers::to_json(&facts),
+            now,
+        ],
+    )?;
+
+    drop(conn);
+    Ok(true)
+}
+
+pub(super) fn delete(memory: &SqliteMemory, id: i64) -> Result<bool> {
+    // History and stored embeddings go with the row (ON DELETE CASCADE).
+    let conn = memory.conn.lock().expect("memory db mutex poisoned");
+    let deleted = conn.execute(
+        "DELETE FROM observations WHERE id = ? AND agent_id = ?",
+        params![id, memory.agent_id],
+    )?;
+    drop(conn);
+
+    if deleted > 0 {
+        memory.remove_embedding(id)?;
+    }
+    Ok(deleted > 0)
+}
+
+pub(super) fn merge_people(memory: &SqliteMemory, from: &str, into: &str) -> Result<bool> {
+    if from == into {
+        return Ok(false);
+    }
+
+    let mut conn = memory.conn.lock().expect("memory db mutex poisoned");
+    let tx = conn.transaction()?;
+
+    let Some(source) = load_person_row(&tx, &memory.agent_id, from)? else {
+        return Ok(false);
+    };
+
+    if let Some(target) = load_person_row(&tx, &memory.agent_id, into)? {
+        let mut aliases = target.aliases;
+        for alias in source.aliases.into_iter().chain([from.to_owned()]) {
+            let lower = alias.to_lowercase();
+            if lower != into.to_lowercase() && !aliases.iter().any(|a| a.to_lowercase() == lower) {
+                aliases.push(alias);
+            }
+        }
+
+        // Facts already recorded on the target win.
+        let mut facts = source.facts;
+        if let (JsonValue::Object(merged), JsonValue::Object(existing)) = (&mut facts, target.facts)
+        {
+            merged.extend(existing);
+        }
+
+        let mut shared_with = target.shared_with;
+        for principal in source.shared_with {
+            if !shared_with.contains(&principal) {
+                shared_with.push(principal);
+            }
+        }
+
+        tx.execute(
+            "
+                UPDATE people
+                SET aliases = ?,
+                    facts = ?,
+                    shared_with = ?,
+                    mention_count = COALESCE(mention_count, 0) + ?,
+                    last_mentioned = MAX(COALESCE(last_mentioned, 0), COALESCE(?, 0))
+                WHERE agent_id = ?
+                  AND name = ?
+                ",
+            params![
+                helpers::to_json(&aliases),
+                facts.to_string(),
+                helpers::to_json(&shared_with),
+                source.mention_count,
+                source.last_mentioned,
+                memory.agent_id,
+                into,
+            ],
+        )?;
+        tx.execute(
+            "DELETE FROM people WHERE agent_id = ? AND name = ?",
+            params![memory.agent_id, from],
+        )?;
+    } else {
+        let mut aliases = source.aliases;
+        aliases.push(from.to_owned());
+        tx.execute(
+            "UPDATE people SET name = ?, aliases = ? WHERE agent_id = ? AND name = ?",
+            params![into, helpers::to_json(&aliases), memory.agent_id, from],
+        )?;
+    }
+
+    let mentions: Vec<(i64, String)> = {
+        let mut stmt = tx.prepare(
+            "
+                SELECT o.id, o.related_people
+                FROM observations o
+                WHERE o.agent_id = ?
+                  AND EXISTS (SELECT 1 FROM json_each(o.related_people) WHERE value = ?)
+                ",
+        )?;
+        stmt.query_map(params![memory.agent_id, from], |row| {
+            Ok((row.get(0)?, row.get(1)?))
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()?
+    };
+
+    for (id, related_people) in mentions {
+        let mut people = Vec::new();
+        for name in helpers::from_json(&related_people) {
+            let name = if name == from { into.to_owned() } else { name };
+            if !people.contains(&name) {
+                people.push(name);
+            }
+        }
+        tx.execute(
+            "UPDATE observations SET related_people = ? WHERE id = ?",
+            params![helpers::to_json(&people), id],
+        )?;
+    }
+
+    tx.commit()?;
+    drop(conn);
+    Ok(true)
+}
+
+pub(super) fn archive(memory: &SqliteMemory, id: i64) -> Result<bool> {
+    let mut conn = memory.conn.lock().expect("memory db mutex poisoned");
+    let tx = conn.transaction()?;
+    let archived = tx.execute(
+        "
+            INSERT INTO observation_archive (
+                original_observation_id,
+                agent_id,
+                session_key,
+                store,
+                type,
+                title,
+                narrative,
+                facts,
+                tags,
+                source,
+                related_files,
+                related_people,
+                hash,
+                mention_count,
+                token_count,
+                created_at,
+                updated_at,
+                expires_at,
+                min_trust,
+                owner,
+                shared_with,
+                archived_at,
+                archive_reason
+            )
+            SELECT
+                id,
+                agent_id,
+                session_key,
+                store,
+                type,
+                title,
+                narrative,
+                facts,
+                tags,
+                source,
+                related_files,
+                related_people,
+                hash,
+                mention_count,
+                token_count,
+                created_at,
+                updated_at,
+                expires_at,
+                min_trust,
+                owner,
+                shared_with,
+                ?,
+                'manual'
+            FROM observations
+            WHERE id = ?
+              AND agent_id = ?
+            ",
+        params![helpers::now_ms(), id, memory.agent_id],
+    )?;
+    tx.execute(
+        "DELETE FROM observations WHERE id = ? AND agent_id = ?",
+        params![id, memory.agent_id],
+    )?;
+    tx.commit()?;
+    drop(conn);
+
+    if archived > 0 {
+        memory.remove_embedding(id)?;
+    }
+    Ok(archived > 0)
+}
+
+#[allow(clippy::too_many_lines)]
+pub(super) fn restore(memory: &SqliteMemory, id: i64) -> Result<Option<i64>> {
+    let mut conn = memory.conn.lock().expect("memory db mutex poisoned");
+    let tx = conn.transaction()?;
+
+    let archive_id: Option<i64> = tx
+        .query_row(
+            "
+                SELECT id
+                FROM observation_archive
+                WHERE agent_id = ?
+                  AND original_observation_id = ?
+                ORDER BY archived_at DESC
+                LIMIT 1
+                ",
+            params![memory.agent_id, id],
+            |row| row.get(0),
+        )
+        .optional()?;
+    let Some(archive_id) = archive_id else {
+        return Ok(None);
+    };
+
+    // Keep the original id unless a newer observation has taken it. The
+    // restored row no longer expires; it was brought back on purpose.
+    let now = helpers::now_ms();
+    tx.execute(
+        "
+            INSERT INTO observations (
+                id,
+                agent_id,
+                session_key,
+                store,
+                type,
+                title,
+                narrative,
+                facts,
+                tags,
+                source,
+                related_files,
+                related_people,
+                hash,
+                mention_count,
+                token_count,
+                created_at,
+                updated_at,
+                expires_at,
+                min_trust,
+                owner,
+                shared_with
+            )
+            SELECT
+                CASE
+                    WHEN EXISTS (SELECT 1 FROM observations WHERE id = a.original_observation_id)
+                    THEN NULL
+                    ELSE a.original_observation_id
+                END,
+                a.agent_id,
+                a.session_key,
+                a.store,
+                a.type,
+                a.title,
+                a.narrative,
+                a.facts,
+                a.tags,
+                a.source,
+                a.related_files,
+                a.related_people,
+                a.hash,
+                a.mention_count,
+                a.token_count,
+                a.created_at,
+                ?,
+                NULL,
+                a.min_trust,
+                a.owner,
+                a.shared_with
+            FROM observation_archive a
+            WHERE a.id = ?
+            ",
+        params![now, archive_id],
+    )?;
+    let restored_id = tx.last_insert_rowid();
+
+    tx.execute(
+        "
+            INSERT INTO observation_history (
+                observation_id,
+                old_title,
+                old_facts,
+                new_title,
+                new_facts,
+                event,
+                created_at
+            )
+            SELECT ?, NULL, NULL, title, facts, 'RESTORE', ?
+            FROM observations
+            WHERE id = ?
+            ",
+        params![restored_id, now, restored_id],
+    )?;
+    tx.execute(
+        "DELETE FROM observation_archive WHERE id = ?",
+        params![archive_id],
+    )?;
+    tx.commit()?;
+    drop(conn);
+    Ok(Some(restored_id))
+}
+
+pub(super) fn archived(memory: &SqliteMemory, limit: usize) -> Result<Vec<ArchivedObservation>> {
+    let conn = memory.conn.lock().expect("memory db mutex poisoned");
+    let mut stmt = conn.prepare(
+        "
+            SELECT original_observation_id, title, type, store, created_at, archived_at, archive_reason
+            FROM observation_archive
+            WHERE agent_id = ?
+            ORDER BY archived_at DESC, id DESC
+            LIMIT ?
+            ",
+    )?;
+    let rows = stmt.query_map(
+        params![
+            memory.agent_id,
+            i64::try_from(limit.max(1)).unwrap_or(i64::MAX)
+        ],
+        |row| {
+            Ok(ArchivedObservation {
+                id: row.get(0)?,
+                title: row.get(1)?,
+                obs_type: row.get(2)?,
+                store: row.get(3)?,
+                created_at: helpers::dt_from_ms(row.get(4)?),
+                archived_at: helpers::dt_from_ms(row.get(5)?),
+                archive_reason: row.get(6)?,
+            })
+        },
+    )?;
+
+    let mut out = Vec::new();
+    for row in rows {
+        out.push(row?);
+    }
+    drop(stmt);
+    drop(conn);
+    Ok(out)
+}
+
+struct PersonRow {
+    aliases: Vec<String>,
+    facts: JsonValue,
+    shared_with: Vec<String>,
+    mention_count: i64,
+    last_mentioned: Option<i64>,
+}
+
+fn load_person_row(
+    conn: &rusqlite::Connection,
+    agent_id: &str,
+    name: &str,
+) -> Result<Option<PersonRow>> {
+    Ok(conn
+        .query_row(
+            "
+                SELECT aliases, facts, shared_with, mention_count, last_mentioned
+                FROM people
+                WHERE agent_id = ?
+                  AND name = ?
+                ",
+            params![agent_id, name],
+            |row| {
+                let aliases: String = row.get(0)?;
+                let facts: Option<String> = row.get(1)?;
+                let shared_with: String = row.get(2)?;
+                Ok(PersonRow {
+                    aliases: helpers::from_json(&aliases),
+                    facts: facts
+                        .and_then(|facts| serde_json::from_str(&facts).ok())
+                        .unwrap_or_else(|| serde_json::json!({})),
+                    shared_with: helpers::from_json(&shared_with),
+                    mention_count: row.get::<_, Option<i64>>(3)?.unwrap_or(0),
+                    last_mentioned: row.get(4)?,
+                })
+            },
+        )
+        .optional()?)
+}
@@ -0,0 +1,389 @@
+use anyhow::Result;
+use rusqlite::{Transaction, params};
+
+use crate::types::ErasureReport;
+
+use super::{SqliteMemory, helpers, write_ops};
+
+/// Replaces every mention of a forgotten person.
+const REDACTED: &str = "[redacted]";
+
+/// `observation_history` id with its old/new title and facts.
+type HistoryRow = (
+    i64,
+    Option<String>,
+    Option<String>,
+    Option<String>,
+    Option<String>,
+);
+
+/// Ids whose index entries must be refreshed once the transaction commits.
+pub(super) struct Erasure {
+    pub(super) report: ErasureReport,
+    pub(super) deleted: Vec<i64>,
+    pub(super) redacted: Vec<i64>,
+}
+
+/// Erase `name` (and every alias of the matching person) from memory.
+///
+/// Observations and archived rows that only concern the person are deleted;
+/// anything else that mentions them is redacted in place, including history,
+/// other people's facts, session summaries and indexed session messages.
+pub(super) fn forget_person(memory: &SqliteMemory, name: &str) -> Result<Erasure> {
+    let mut conn = memory.conn.lock().expect("memory db mutex poisoned");
+    let tx = conn.transaction()?;
+    let agent_id = memory.agent_id.as_str();
+
+    let (names, people_deleted) = delete_people(&tx, agent_id, name)?;
+    let mut erasure = Erasure {
+        report: ErasureReport {
+            names: names.clone(),
+            people_deleted,
+            ..ErasureReport::default()
+        },
+        deleted: Vec::new(),
+        redacted: Vec::new(),
+    };
+    if names.is_empty() {
+        return Ok(erasure);
+    }
+
+    for table in ["observations", "observation_archive"] {
+        let (deleted, redacted) = erase_observations(&tx, agent_id, table, &names)?;
+        if table == "observations" {
+            erasure.report.observations_deleted = deleted.len();
+            erasure.report.observations_redacted = redacted.len();
+            erasure.deleted = deleted;
+            erasure.redacted = redacted;
+        } else {
+            erasure.report.archived_deleted = deleted.len();
+            erasure.report.archived_redacted = redacted.len();
+        }
+    }
+
+    erasure.report.history_redacted = redact_history(&tx, agent_id, &names)?;
+    erasure.report.people_redacted = redact_people_facts(&tx, agent_id, &names)?;
+    erasure.report.session_summaries_redacted = redact_session_summaries(&tx, agent_id, &names)?;
+    erasure.report.session_messages_redacted = redact_session_messages(&tx, agent_id, &names)?;
+
+    tx.commit()?;
+    drop(conn);
+    Ok(erasure)
+}
+
+/// Delete the person rows `name` refers to, returning every name and alias
+/// to erase (longest first, so "Bob Smith" goes before "Bob").
+fn delete_people(tx: &Transaction<'_>, agent_id: &str, name: &str) -> Result<(Vec<String>, usize)> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Ok((Vec::new(), 0));
+    }
+
+    let rows: Vec<(String, String)> = {
+        let mut stmt = tx.prepare("SELECT name, aliases FROM people WHERE agent_id = ?")?;
+        stmt.query_map(params![agent_id], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<rusqlite::Result<Vec<_>>>()?
+    };
+
+    let lower = name.to_lowercase();
+    let mut names = vec![name.to_owned()];
+    let mut deleted = 0;
+    for (person, aliases) in rows {
+        let aliases = helpers::from_json(&aliases);
+        let matches = person.to_lowercase() == lower
+            || aliases.iter().any(|alias| alias.to_lowercase() == lower);
+        if !matches {
+            continue;
+        }
+        deleted += tx.execute(
+            "DELETE FROM people WHERE agent_id = ? AND name = ?",
+            params![agent_id, person],
+        )?;
+        for candidate in std::iter::once(person).chain(aliases) {
+            let candidate = candidate.trim().to_owned();
+            if !candidate.is_empty()
+                && !names
+                    .iter()
+                    .any(|known| known.to_lowercase() == candidate.to_lowercase())
+            {
+                names.push(candidate);
+            }
+        }
+    }
+
+    names.sort_by_key(|name| std::cmp::Reverse(name.len()));
+    Ok((names, deleted))
+}
+
+/// Returns (deleted ids, redacted ids) for `observations` or
+/// `observation_archive`. Rows whose related people are all the forgotten
+/// person are about them and go; the rest are redacted.
+fn erase_observations(
+    tx: &Transaction<'_>,
+    agent_id: &str,
+    table: &str,
+    names: &[String],
+) -> Result<(Vec<i64>, Vec<i64>)> {
+    let rows: Vec<(i64, String, String, String, String)> = {
+        let mut stmt = tx.prepare(&format!(
+            "SELECT id, title, COALESCE(narrative, ''), facts, related_people
+             FROM {table}
+             WHERE agent_id = ?"
+        ))?;
+        stmt.query_map(params![agent_id], |row| {
+            Ok((
+                row.get(0)?,
+                row.get(1)?,
+                row.get(2)?,
+                row.get(3)?,
+                row.get(4)?,
+            ))
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()?
+    };
+
+    let mut deleted = Vec::new();
+    let mut redacted = Vec::new();
+    for (id, title, narrative, facts, related_people) in rows {
+        let people = helpers::from_json(&related_people);
+        let (kept_people, removed_people): (Vec<String>, Vec<String>) = people
+            .into_iter()
+            .partition(|person| !is_name(person, names));
+
+        if !removed_people.is_empty() && kept_people.is_empty() {
+            tx.execute(&format!("DELETE FROM {table} WHERE id = ?"), params![id])?;
+            deleted.push(id);
+            continue;
+        }
+
+        let facts = helpers::from_json(&facts);
+        let new_title = redact(&title, names);
+        let new_narrative = redact(&narrative, names);
+        let new_facts = facts
+            .iter()
+            .map(|fact| redact(fact, names))
+            .collect::<Vec<_>>();
+        if removed_people.is_empty()
+            && new_title == title
+            && new_narrative == narrative
+            && new_facts == facts
+        {
+            continue;
+        }
+
+        tx.execute(
+            &format!(
+                "UPDATE {table}
+                 SET title = ?,
+                     narrative = ?,
+                     facts = ?,
+                     related_people = ?,
+                     hash = ?,
+                     token_count = ?
+                 WHERE id = ?"
+            ),
+            params![
+                new_title,
+                new_narrative,
+                helpers::to_json(&new_facts),
+                helpers::to_json(&kept_people),
+                helpers::observation_hash(&new_title, &new_facts),
+                write_ops::estimate_token_count(&new_title, &new_narrative, &new_facts),
+                id,
+            ],
+        )?;
+        redacted.push(id);
+    }
+    Ok((deleted, redacted))
+}
+
+fn redact_history(tx: &Transaction<'_>, agent_id: &str, names: &[String]) -> Result<usize> {
+    let entries: Vec<HistoryRow> = {
+        let mut stmt = tx.prepare(
+            "SELECT h.id, h.old_title, h.old_facts, h.new_title, h.new_facts
+             FROM observation_history h
+             JOIN observations o ON o.id = h.observation_id
+             WHERE o.agent_id = ?",
+        )?;
+        stmt.query_map(params![agent_id], |row| {
+            Ok((
+                row.get(0)?,
+                row.get(1)?,
+                row.get(2)?,
+                row.get(3)?,
+                row.get(4)?,
+            ))
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()?
+    };
+
+    let mut redacted = 0;
+    for (id, old_title, old_facts, new_title, new_facts) in entries {
+        let fields = [old_title, old_facts, new_title, new_facts];
+        let cleaned = fields
+            .iter()
+            .map(|field| field.as_deref().map(|text| redact(text, names)))
+            .collect::<Vec<_>>();
+        if cleaned.iter().zip(&fields).all(|(new, old)| new == old) {
+            continue;
+        }
+        tx.execute(
+            "UPDATE observation_history
+             SET old_title = ?, old_facts = ?, new_title = ?, new_facts = ?
+             WHERE id = ?",
+            params![cleaned[0], cleaned[1], cleaned[2], cleaned[3], id],
+        )?;
+        redacted += 1;
+    }
+    Ok(redacted)
+}
+
+fn redact_people_facts(tx: &Transaction<'_>, agent_id: &str, names: &[String]) -> Result<usize> {
+    let rows: Vec<(i64, String)> = {
+        let mut stmt =
+            tx.prepare("SELECT id, COALESCE(facts, '{}') FROM people WHERE agent_id = ?")?;
+        stmt.query_map(params![agent_id], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<rusqlite::Result<Vec<_>>>()?
+    };
+
+    let mut redacted = 0;
+    for (id, facts) in rows {
+        let cleaned = redact(&facts, names);
+        if cleaned != facts {
+            tx.execute(
+                "UPDATE people SET facts = ? WHERE id = ?",
+                params![cleaned, id],
+            )?;
+            redacted += 1;
+        }
+    }
+    Ok(redacted)
+}
+
+fn redact_session_summaries(
+    tx: &Transaction<'_>,
+    agent_id: &str,
+    names: &[String],
+) -> Result<usize> {
+    let rows: Vec<(i64, String, String, String, String)> = {
+        let mut stmt = tx.prepare(
+            "SELECT id, COALESCE(request, ''), COALESCE(outcome, ''),
+                    COALESCE(decisions, '[]'), COALESCE(open_items, '[]')
+             FROM session_summaries
+             WHERE agent_id = ?",
+        )?;
+        stmt.query_map(params![agent_id], |row| {
+            Ok((
+                row.get(0)?,
+                row.get(1)?,
+                row.get(2)?,
+                row.get(3)?,
+                row.get(4)?,
+            ))
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()?
+    };
+
+    let mut redacted = 0;
+    for (id, request, outcome, decisions, open_items) in rows {
+        let fields = [request, outcome];
+        let lists = [decisions, open_items].map(|list| helpers::from_json(&list));
+        let cleaned_fields = fields.clone().map(|field| redact(&field, names));
+        let cleaned_lists = lists.clone().map(|list| {
+            list.iter()
+                .map(|item| redact(item, names))
+                .collect::<Vec<_>>()
+        });
+        if cleaned_fields == fields && cleaned_lists == lists {
+            continue;
+        }
+        tx.execute(
+            "UPDATE session_summaries
+             SET request = ?, outcome = ?, decisions = ?, open_items = ?
+             WHERE id = ?",
+            params![
+                cleaned_fields[0],
+                cleaned_fields[1],
+                helpers::to_json(&cleaned_lists[0]),
+                helpers::to_json(&cleaned_lists[1]),
+                id,
+            ],
+        )?;
+        redacted += 1;
+    }
+    Ok(redacted)
+}
+
+/// Session messages have no update trigger, so their FTS rows are swapped
+/// by hand.
+fn redact_session_messages(
+    tx: &Transaction<'_>,
+    agent_id: &str,
+    names: &[String],
+) -> Result<usize> {
+    let rows: Vec<(i64, String)> = {
+        let mut stmt = tx.prepare("SELECT id, content FROM session_messages WHERE agent_id = ?")?;
+        stmt.query_map(params![agent_id], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<rusqlite::Result<Vec<_>>>()?
+    };
+
+    let mut redacted = 0;
+    for (id, content) in rows {
+        let cleaned = redact(&content, names);
+        if cleaned == content {
+            continue;
+        }
+        tx.execute(
+            "INSERT INTO session_messages_fts(session_messages_fts, rowid, content)
+             VALUES ('delete', ?, ?)",
+            params![id, content],
+        )?;
+        tx.execute(
+            "UPDATE session_messages SET content = ? WHERE id = ?",
+            params![cleaned, id],
+        )?;
+        tx.execute(
+            "INSERT INTO session_messages_fts(rowid, content) VALUES (?, ?)",
+            params![id, cleaned],
+        )?;
+        redacted += 1;
+    }
+    Ok(redacted)
+}
+
+fn is_name(candidate: &str, names: &[String]) -> bool {
+    let candidate = candidate.trim().to_lowercase();
+    names.iter().any(|name| name.to_lowercase() == candidate)
+}
+
+/// Replace whole-word, case-insensitive mentions of `names` with
+/// [`REDACTED`]. `names` should be longest first.
+fn redact(text: &str, names: &[String]) -> String {
+    let mut out = text.to_owned();
+    for name in names {
+        let needle = name.to_ascii_lowercase();
+        if needle.is_empty() {
+            continue;
+        }
+        let mut result = String::with_capacity(out.len());
+        let haystack = out.to_ascii_lowercase();
+        let mut last = 0;
+        let mut from = 0;
+        while let Some(offset) = haystack[from..].find(&needle) {
+            let start = from + offset;
+            let end = start + needle.len();
+            let before = out[..start].chars().next_back();
+            let after = out[end..].chars().next();
+            let boundary = |c: Option<char>| c.is_none_or(|c| !c.is_alphanumeric());
+            if boundary(before) && boundary(after) {
+                result.push_str(&out[last..start]);
+                result.push_str(REDACTED);
+                last = end;
+            }
+            from = end;
+        }
+        result.push_str(&out[last..]);
+        out = result;
+    }
+    out
+}
@@ -1,13 +1,14 @@
 use anyhow::Result;
 use rusqlite::{params_from_iter, types::Value};
 
-use super::{RawIndex, SqliteMemory, helpers};
+use super::{RawIndex, SqliteMemory, helpers, schema};
 
 pub(super) fn search_by_file(
     memory: &SqliteMemory,
     path: &str,
     prefix_match: bool,
     limit: usize,
+    viewer: Option<&str>,
 ) -> Result<Vec<RawIndex>> {
     let now_ms = helpers::now_ms();
     let limit = i64::try_from(limit.max(1)).unwrap_or(i64::MAX);
@@ -43,6 +44,12 @@ pub(super) fn search_by_file(
         sql.push_str(" AND f.value = ?");
     }
 
+    if viewer.is_some() {
+        sql.push_str(" AND ");
+        sql.push_str(&schema::visibility_condition("o."));
+        params.extend(schema::visibility_params(viewer));
+    }
+
     sql.push_str(" ORDER BY o.updated_at DESC LIMIT ?");
     params.push(Value::from(limit));
 
@@ -29,6 +29,7 @@ pub(super) fn observation_from_row(row: &Row<'_>) -> rusqlite::Result<Observatio
     let tags: String = row.get(4)?;
     let files: String = row.get(7)?;
     let people: String = row.get(8)?;
+    let shared_with: String = row.get(13)?;
 
     Ok(Observation {
         id: row.get(0)?,
@@ -43,6 +44,9 @@ pub(super) fn observation_from_row(row: &Row<'_>) -> rusqlite::Result<Observatio
         created_at: dt_from_ms(row.get(9)?),
         token_count: row.get::<_, Option<u32>>(10)?.unwrap_or(0),
         mention_count: row.get::<_, Option<u32>>(11)?.unwrap_or(0),
+        owner: row.get(12)?,
+        shared_with: from_json(&shared_with),
+        expires_at: row.get::<_, Option<i64>>(14)?.map(dt_from_ms),
     })
 }
 
@@ -16,6 +16,9 @@ pub(super) struct MaintenanceResult {
 
 const DAY_MS: i64 = 86_400_000;
 
+/// Store, owner, type and normalized title.
+type ClusterKey = (String, Option<String>, String, String);
+
 #[derive(Debug)]
 struct CompressionCandidate {
     id: i64,
@@ -29,6 +32,8 @@ struct CompressionCandidate {
     related_people: Vec<String>,
     mention_count: u32,
     min_trust: String,
+    owner: Option<String>,
+    shared_with: Vec<String>,
 }
 
 #[derive(Debug)]
@@ -51,6 +56,8 @@ struct ArchiveCandidate {
     updated_at: i64,
     expires_at: Option<i64>,
     min_trust: String,
+    owner: Option<String>,
+    shared_with_json: String,
 }
 
 #[derive(Debug, Default)]
@@ -160,7 +167,9 @@ fn compress_stale_observations(
                 related_files,
                 related_people,
                 mention_count,
-                min_trust
+                min_trust,
+                owner,
+                shared_with
             FROM observations
             WHERE agent_id = ?
               AND created_at <= ?
@@ -182,6 +191,7 @@ fn compress_stale_observations(
                 let tags_json: String = row.get(6)?;
                 let related_files_json: String = row.get(7)?;
                 let related_people_json: String = row.get(8)?;
+                let shared_with_json: String = row.get(12)?;
 
                 Ok(CompressionCandidate {
                     id: row.get(0)?,
@@ -195,6 +205,8 @@ fn compress_stale_observations(
                     related_people: helpers::from_json(&related_people_json),
                     mention_count: row.get::<_, Option<u32>>(9)?.unwrap_or(0),
                     min_trust: row.get(10)?,
+                    owner: row.get(11)?,
+                    shared_with: helpers::from_json(&shared_with_json),
                 })
             },
         )?;
@@ -210,11 +222,11 @@ fn compress_stale_observations(
         return Ok(CompressionStats::default());
     }
 
-    let mut clusters: BTreeMap<(String, String, String), Vec<CompressionCandidate>> =
-        BTreeMap::new();
+    let mut clusters: BTreeMap<ClusterKey, Vec<CompressionCandidate>> = BTreeMap::new();
     for candidate in candidates {
         let key = (
             candidate.store.clone(),
+            candidate.owner.clone(),
             candidate.obs_type.clone(),
             normalize_title(&candidate.title),
         );
@@ -262,6 +274,12 @@ fn compress_stale_observations(
                 .flat_map(|row| row.related_people.iter().cloned()),
         );
 
+        let summary_shared = union_sorted(
+            cluster
+                .iter()
+                .flat_map(|row| row.shared_with.iter().cloned()),
+        );
+
         let mention_count = cluster
             .iter()
             .fold(0_u32, |acc, row| acc.saturating_add(row.mention_count))
@@ -293,8 +311,10 @@ fn compress_stale_observations(
                 created_at,
                 updated_at,
                 expires_at,
-                min_trust
-            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, NULL, ?)
+                min_trust,
+                owner,
+                shared_with
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, NULL, ?, ?, ?)
             ",
             params![
                 memory.agent_id,
@@ -314,6 +334,8 @@ fn compress_stale_observations(
                 now_ms,
                 now_ms,
                 cluster[0].min_trust.clone(),
+                cluster[0].owner.clone(),
+                helpers::to_json(&summary_shared),
             ],
         )?;
 
@@ -414,7 +436,9 @@ fn archive_observations(
                 created_at,
                 updated_at,
                 expires_at,
-                min_trust
+                min_trust,
+                owner,
+                shared_with
             FROM observations
             WHERE agent_id = ?
               AND (created_at <= ? OR (expires_at IS NOT NULL AND expires_at <= ?))
@@ -450,6 +474,8 @@ fn archive_observations(
                     updated_at: row.get(15)?,
                     expires_at: row.get(16)?,
                     min_trust: row.get(17)?,
+                    owner: row.get(18)?,
+                    shared_with_json: row.get(19)?,
                 })
             },
         )?;
@@ -500,9 +526,11 @@ fn archive_observations(
                 updated_at,
                 expires_at,
                 min_trust,
+                owner,
+                shared_with,
                 archived_at,
                 archive_reason
-            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             ",
             params![
                 row.id,
@@ -524,6 +552,8 @@ fn archive_observations(
                 row.updated_at,
                 row.expires_at,
                 row.min_trust,
+                row.owner,
+                row.shared_with_json,
                 now_ms,
                 archive_reason,
             ],
@@ -1,9 +1,12 @@
+mod admin;
+mod erasure;
 mod file_query;
 mod helpers;
 mod maintenance;
 mod query;
 mod schema;
 mod session_search;
+mod transfer;
 mod write_ops;
 
 #[cfg(test)]
@@ -20,7 +23,8 @@ use tracing::{debug, instrument, warn};
 
 use crate::traits::{EmbeddingProvider, Memory, Reconciler};
 use crate::types::{
-    MemoryMaintenanceConfig, MemoryMaintenanceReport, MemoryQuery, NewObservation, Observation,
+    ArchivedObservation, ErasureReport, MemoryMaintenanceConfig, MemoryMaintenanceReport,
+    MemoryQuery, MemoryRecord, NewObservation, Observation, ObservationEdit,
     ObservationHistoryEntry, ObservationIndex, Person, SessionMessage, SessionSearchHit,
     SessionSummary, WriteOutcome, embedding_text, normalize_file_path,
 };
@@ -251,6 +255,74 @@ impl SqliteMemory {
         Ok(count)
     }
 
+    /// Assign owners to rows written before ownership was tracked.
+    ///
+    /// `resolve` maps a session key to the principal that owns it; rows it
+    /// can't place stay unowned and visible to everyone. People then take
+    /// the owner of the earliest observation that mentions them.
+    pub fn assign_owners(&self, resolve: impl Fn(&str) -> Option<String>) -> Result<usize> {
+        let conn = self.conn.lock().expect("memory db mutex poisoned");
+
+        let session_keys: Vec<String> = {
+            let mut stmt = conn.prepare(
+                "SELECT session_key FROM observations
+                 WHERE agent_id = ?1 AND owner IS NULL AND session_key IS NOT NULL
+                 UNION
+                 SELECT session_key FROM observation_archive
+                 WHERE agent_id = ?1 AND owner IS NULL AND session_key IS NOT NULL",
+            )?;
+            stmt.query_map(params![self.agent_id], |row| row.get(0))?
+                .collect::<rusqlite::Result<Vec<_>>>()?
+        };
+
+        let mut assigned = 0;
+        for session_key in &session_keys {
+            let Some(owner) = resolve(session_key) else {
+                continue;
+            };
+            for table in ["observations", "observation_archive"] {
+                assigned += conn.execute(
+                    &format!(
+                        "UPDATE {table} SET owner = ?
+                         WHERE agent_id = ? AND session_key = ? AND owner IS NULL"
+                    ),
+                    params![owner, self.agent_id, session_key],
+                )?;
+            }
+        }
+
+        assigned += conn.execute(
+            "UPDATE people
+             SET owner = (
+                 SELECT o.owner
+                 FROM observations o, json_each(o.related_people) AS p
+                 WHERE o.agent_id = people.agent_id
+                   AND o.store = people.store
+                   AND o.owner IS NOT NULL
+                   AND p.value = people.name
+                 ORDER BY o.created_at ASC
+                 LIMIT 1
+             )
+             WHERE agent_id = ?
+               AND owner IS NULL
+               AND EXISTS (
+                   SELECT 1
+                   FROM observations o, json_each(o.related_people) AS p
+                   WHERE o.agent_id = people.agent_id
+                     AND o.store = people.store
+                     AND o.owner IS NOT NULL
+                     AND p.value = people.name
+               )",
+            params![self.agent_id],
+        )?;
+
+        drop(conn);
+        if assigned > 0 {
+            debug!(assigned, "memory owners backfilled");
+        }
+        Ok(assigned)
+    }
+
     pub(super) fn remove_embedding(&self, observation_id: i64) -> Result<()> {
         let conn = self.conn.lock().expect("memory db mutex poisoned");
         conn.execute(
@@ -448,6 +520,26 @@ impl SqliteMemory {
         Ok(out)
     }
 
+    /// Recompute and persist the embedding for a stored observation.
+    async fn embed_stored_observation(&self, id: i64, reason: &'static str) -> Result<()> {
+        if let Some(obs) = self.load_observation(id)? {
+            let now = helpers::now_ms();
+            if let Some(embedding) = self
+                .embedding_for_observation(&obs.title, &obs.facts, reason)
+                .await
+                && let Err(error) = self.persist_embedding(id, &embedding, now)
+            {
+                warn!(
+                    observation_id = id,
+                    error = %error,
+                    reason,
+                    "failed to embed observation"
+                );
+            }
+        }
+        Ok(())
+    }
+
     fn load_observation(&self, id: i64) -> Result<Option<Observation>> {
         let now_ms = helpers::now_ms();
         let conn = self.conn.lock().expect("memory db mutex poisoned");
@@ -465,7 +557,10 @@ impl SqliteMemory {
                 related_people,
                 created_at,
                 token_count,
-                mention_count
+                mention_count,
+                owner,
+                shared_with,
+                expires_at
             FROM observations
             WHERE id = ?
               AND agent_id = ?
@@ -499,13 +594,14 @@ impl Memory for SqliteMemory {
         path: &str,
         prefix_match: bool,
         limit: usize,
+        viewer: Option<&str>,
     ) -> Result<Vec<ObservationIndex>> {
         let normalized_path = normalize_file_path(path);
         if normalized_path.is_empty() {
             return Ok(Vec::new());
         }
 
-        let rows = file_query::search_by_file(self, &normalized_path, prefix_match, limit)?;
+        let rows = file_query::search_by_file(self, &normalized_path, prefix_match, limit, viewer)?;
         let indexes = rows
             .into_iter()
             .map(|row| helpers::to_index(row, 0.0))
@@ -527,13 +623,14 @@ impl Memory for SqliteMemory {
         anchor: i64,
         before: usize,
         after: usize,
+        viewer: Option<&str>,
     ) -> Result<Vec<ObservationIndex>> {
-        query::timeline(self, anchor, before, after)
+        query::timeline(self, anchor, before, after, viewer)
     }
 
     #[instrument(skip(self, ids), fields(count = ids.len()))]
-    async fn get(&self, ids: &[i64]) -> Result<Vec<Observation>> {
-        query::get(self, ids)
+    async fn get(&self, ids: &[i64], viewer: Option<&str>) -> Result<Vec<Observation>> {
+        query::get(self, ids, viewer)
     }
 
     #[instrument(skip(self, obs), fields(obs_type = %obs.obs_type, store = %obs.store))]
@@ -542,8 +639,8 @@ impl Memory for SqliteMemory {
     }
 
     #[instrument(skip(self))]
-    async fn people(&self, query: &str) -> Result<Vec<Person>> {
-        write_ops::people(self, query)
+    async fn people(&self, query: &str, viewer: Option<&str>) -> Result<Vec<Person>> {
+        write_ops::people(self, query, viewer)
     }
 
     #[instrument(skip(self))]
@@ -574,20 +671,8 @@ impl Memory for SqliteMemory {
         let result = maintenance::run(self, config)?;
 
         for id in &result.new_summary_ids {
-            if let Some(obs) = self.load_observation(*id)? {
-                let now = helpers::now_ms();
-                if let Some(embedding) = self
-                    .embedding_for_observation(&obs.title, &obs.facts, "maintenance_summary")
-                    .await
-                    && let Err(error) = self.persist_embedding(*id, &embedding, now)
-                {
-                    warn!(
-                        observation_id = id,
-                        error = %error,
-                        "failed to embed maintenance summary"
-                    );
-                }
-            }
+            self.embed_stored_observation(*id, "maintenance_summary")
+                .await?;
         }
 
         Ok(result.report)
@@ -621,4 +706,80 @@ impl Memory for SqliteMemory {
     async fn rebuild_index(&self) -> Result<usize> {
         self.rebuild_vec_index()
     }
+
+    #[instrument(skip(self, edit))]
+    async fn edit(&self, id: i64, edit: &ObservationEdit) -> Result<bool> {
+        if !admin::edit(self, id, edit)? {
+            return Ok(false);
+        }
+        self.embed_stored_observation(id, "edit").await?;
+        Ok(true)
+    }
+
+    #[instrument(skip(self))]
+    async fn delete(&self, id: i64) -> Result<bool> {
+        admin::delete(self, id)
+    }
+
+    #[instrument(skip(self))]
+    async fn merge_people(&self, from: &str, into: &str) -> Result<bool> {
+        admin::merge_people(self, from, into)
+    }
+
+    #[instrument(skip(self))]
+    async fn archive(&self, id: i64) -> Result<bool> {
+        admin::archive(self, id)
+    }
+
+    #[instrument(skip(self))]
+    async fn restore(&self, id: i64) -> Result<Option<i64>> {
+        let restored = admin::restore(self, id)?;
+        if let Some(restored_id) = restored {
+            self.embed_stored_observation(restored_id, "restore")
+                .await?;
+        }
+        Ok(restored)
+    }
+
+    #[instrument(skip(self))]
+    async fn archived(&self, limit: usize) -> Result<Vec<ArchivedObservation>> {
+        admin::archived(self, limit)
+    }
+
+    #[instrument(skip(self))]
+    async fn export(&self) -> Result<Vec<MemoryRecord>> {
+        transfer::export(self)
+    }
+
+    #[instrument(skip(self, person), fields(name = %person.name))]
+    async fn import_person(&self, person: &Person) -> Result<()> {
+        transfer::import_person(self, person)
+    }
+
+    #[instrument(skip(self, entries), fields(count = entries.len()))]
+    async fn import_history(
+        &self,
+        observation_id: i64,
+        entries: &[ObservationHistoryEntry],
+    ) -> Result<()> {
+        transfer::import_history(self, observation_id, entries)
+    }
+
+    #[instrument(skip(self, summary), fields(session = %summary.session_key))]
+    async fn import_session_summary(&self, summary: &SessionSummary) -> Result<bool> {
+        transfer::import_session_summary(self, summary)
+    }
+
+    #[instrument(skip(self))]
+    async fn forget_person(&self, name: &str) -> Result<ErasureReport> {
+        let erasure = erasure::forget_person(self, name)?;
+        for id in &erasure.deleted {
+            self.remove_embedding(*id)?;
+        }
+        for id in &erasure.redacted {
+            self.remove_embedding(*id)?;
+            self.embed_stored_observation(*id, "forget_person").await?;
+        }
+        Ok(erasure.report)
+    }
 }
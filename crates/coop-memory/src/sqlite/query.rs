@@ -3,9 +3,9 @@ use rusqlite::{OptionalExtension, params};
 use std::collections::{HashMap, HashSet};
 use tracing::{debug, warn};
 
-use crate::types::{MemoryQuery, Observation, ObservationIndex};
+use crate::types::{MemoryQuery, Observation, ObservationIndex, visible_to};
 
-use super::{SqliteMemory, helpers};
+use super::{SqliteMemory, helpers, schema};
 
 pub(super) fn search(
     memory: &SqliteMemory,
@@ -110,13 +110,17 @@ pub(super) fn timeline(
     anchor: i64,
     before: usize,
     after: usize,
+    viewer: Option<&str>,
 ) -> Result<Vec<ObservationIndex>> {
     let now = helpers::now_ms();
+    let visible = schema::visibility_condition("");
+    let [v1, v2, v3] = schema::visibility_params(viewer);
     let conn = memory.conn.lock().expect("memory db mutex poisoned");
 
     let anchor_row: Option<super::RawIndex> = conn
         .query_row(
-            "
+            &format!(
+                "
                 SELECT
                     id,
                     title,
@@ -132,8 +136,10 @@ pub(super) fn timeline(
                 WHERE id = ?
                   AND agent_id = ?
                   AND (expires_at IS NULL OR expires_at > ?)
-                ",
-            params![anchor, memory.agent_id, now],
+                  AND {visible}
+                "
+            ),
+            params![anchor, memory.agent_id, now, v1, v2, v3],
             helpers::raw_index_from_row,
         )
         .optional()?;
@@ -142,7 +148,7 @@ pub(super) fn timeline(
         return Ok(Vec::new());
     };
 
-    let mut before_stmt = conn.prepare(
+    let mut before_stmt = conn.prepare(&format!(
         "
             SELECT
                 id,
@@ -159,16 +165,20 @@ pub(super) fn timeline(
             WHERE agent_id = ?
               AND created_at < ?
               AND (expires_at IS NULL OR expires_at > ?)
+              AND {visible}
             ORDER BY created_at DESC
             LIMIT ?
-            ",
-    )?;
+            "
+    ))?;
 
     let before_rows = before_stmt.query_map(
         params![
             memory.agent_id,
             anchor_row.created_at,
             now,
+            v1,
+            v2,
+            v3,
             i64::try_from(before).unwrap_or(i64::MAX),
         ],
         helpers::raw_index_from_row,
@@ -180,7 +190,7 @@ pub(super) fn timeline(
     }
     older.reverse();
 
-    let mut after_stmt = conn.prepare(
+    let mut after_stmt = conn.prepare(&format!(
         "
             SELECT
                 id,
@@ -197,16 +207,20 @@ pub(super) fn timeline(
             WHERE agent_id = ?
               AND created_at > ?
               AND (expires_at IS NULL OR expires_at > ?)
+              AND {visible}
             ORDER BY created_at ASC
             LIMIT ?
-            ",
-    )?;
+            "
+    ))?;
 
     let after_rows = after_stmt.query_map(
         params![
             memory.agent_id,
             anchor_row.created_at,
             now,
+            v1,
+            v2,
+            v3,
             i64::try_from(after).unwrap_or(i64::MAX),
         ],
         helpers::raw_index_from_row,
@@ -235,10 +249,16 @@ pub(super) fn timeline(
     Ok(timeline)
 }
 
-pub(super) fn get(memory: &SqliteMemory, ids: &[i64]) -> Result<Vec<Observation>> {
+pub(super) fn get(
+    memory: &SqliteMemory,
+    ids: &[i64],
+    viewer: Option<&str>,
+) -> Result<Vec<Observation>> {
     let mut by_id = HashMap::new();
     for id in ids {
-        if let Some(obs) = memory.load_observation(*id)? {
+        if let Some(obs) = memory.load_observation(*id)?
+            && visible_to(&obs.store, obs.owner.as_deref(), &obs.shared_with, viewer)
+        {
             by_id.insert(*id, obs);
         }
     }
@@ -215,17 +215,64 @@ pub(super) fn init_schema(conn: &Connection) -> Result<()> {
     )?;
 
     // Add aliases column to existing people tables (idempotent).
-    let has_aliases: bool = conn
-        .prepare("SELECT 1 FROM pragma_table_info('people') WHERE name = 'aliases'")
+    add_column_if_missing(conn, "people", "aliases", "TEXT NOT NULL DEFAULT '[]'")?;
+
+    // Ownership columns (idempotent). Group-session rows belong to their
+    // group; other legacy rows are assigned by `SqliteMemory::assign_owners`,
+    // which needs the gateway's user mapping.
+    for table in ["observations", "observation_archive", "people"] {
+        add_column_if_missing(conn, table, "owner", "TEXT")?;
+        add_column_if_missing(conn, table, "shared_with", "TEXT NOT NULL DEFAULT '[]'")?;
+    }
+    conn.execute_batch(
+        "
+        CREATE INDEX IF NOT EXISTS idx_obs_owner ON observations(owner);
+
+        UPDATE observations
+        SET owner = 'group:' || substr(session_key, instr(session_key, ':group:') + 7)
+        WHERE owner IS NULL
+          AND session_key LIKE '%:group:%';
+        ",
+    )?;
+
+    Ok(())
+}
+
+fn add_column_if_missing(
+    conn: &Connection,
+    table: &str,
+    column: &str,
+    definition: &str,
+) -> Result<()> {
+    let exists: bool = conn
+        .prepare(&format!(
+            "SELECT 1 FROM pragma_table_info('{table}') WHERE name = '{column}'"
+        ))
         .and_then(|mut stmt| stmt.exists([]))
         .unwrap_or(false);
-    if !has_aliases {
-        conn.execute_batch("ALTER TABLE people ADD COLUMN aliases TEXT NOT NULL DEFAULT '[]'")?;
+    if !exists {
+        conn.execute_batch(&format!(
+            "ALTER TABLE {table} ADD COLUMN {column} {definition}"
+        ))?;
     }
-
     Ok(())
 }
 
+/// SQL condition implementing `visible_to` for a row (columns prefixed with
+/// `prefix`). Binds the viewer three times; a NULL viewer disables it.
+pub(super) fn visibility_condition(prefix: &str) -> String {
+    format!(
+        "(? IS NULL OR {prefix}owner IS NULL OR {prefix}store != 'private' \
+         OR {prefix}owner = ? \
+         OR EXISTS (SELECT 1 FROM json_each({prefix}shared_with) WHERE json_each.value = ?))"
+    )
+}
+
+pub(super) fn visibility_params(viewer: Option<&str>) -> [Value; 3] {
+    let viewer = viewer.map_or(Value::Null, |viewer| Value::from(viewer.to_owned()));
+    [viewer.clone(), viewer.clone(), viewer]
+}
+
 pub(super) fn init_vector_schema(conn: &Connection, dimensions: Option<usize>) -> bool {
     let Some(dimensions) = dimensions else {
         return false;
@@ -258,6 +305,12 @@ pub(super) fn append_filters_with_prefix(
     query: &MemoryQuery,
     prefix: &str,
 ) {
+    if query.viewer.is_some() {
+        sql.push_str(" AND ");
+        sql.push_str(&visibility_condition(prefix));
+        params.extend(visibility_params(query.viewer.as_deref()));
+    }
+
     if !query.stores.is_empty() {
         sql.push_str(" AND ");
         sql.push_str(prefix);
@@ -7,13 +7,14 @@ use std::sync::{Arc, Mutex};
 
 use crate::traits::{EmbeddingProvider, Memory, Reconciler};
 use crate::types::{
-    MemoryMaintenanceConfig, MemoryQuery, NewObservation, ReconcileDecision, ReconcileObservation,
-    ReconcileRequest, WriteOutcome, min_trust_for_store, normalize_file_path, trust_from_str,
-    trust_to_str,
+    MemoryMaintenanceConfig, MemoryQuery, MemoryRecord, NewObservation, ObservationEdit,
+    ReconcileDecision, ReconcileObservation, ReconcileRequest, WriteOutcome, min_trust_for_store,
+    normalize_file_path, trust_from_str, trust_to_str,
 };
 use coop_core::{SessionKey, SessionKind, TrustLevel};
 
 use super::SqliteMemory;
+use crate::transfer;
 
 #[derive(Debug)]
 struct RecordingEmbedder {
@@ -113,6 +114,9 @@ fn sample_obs(title: &str, facts: &[&str]) -> NewObservation {
         token_count: Some(50),
         expires_at: None,
         min_trust: min_trust_for_store("shared"),
+        owner: None,
+        shared_with: Vec::new(),
+        created_at: None,
     }
 }
 
@@ -183,7 +187,7 @@ async fn write_and_get_round_trip() {
         other => panic!("unexpected outcome: {other:?}"),
     };
 
-    let obs = memory.get(&[id]).await.unwrap();
+    let obs = memory.get(&[id], None).await.unwrap();
     assert_eq!(obs.len(), 1);
     assert_eq!(obs[0].title, "first");
     assert_eq!(obs[0].store, "shared");
@@ -288,7 +292,7 @@ async fn reconciliation_update_records_history_and_dense_indices() {
         .unwrap();
     assert_eq!(second, WriteOutcome::Updated(id));
 
-    let obs = memory.get(&[id]).await.unwrap();
+    let obs = memory.get(&[id], None).await.unwrap();
     assert_eq!(obs[0].facts, vec!["build", "test", "release"]);
 
     let history = memory.history(id).await.unwrap();
@@ -335,7 +339,7 @@ async fn reconciliation_delete_expires_stale_and_records_history() {
         .unwrap();
     assert_eq!(result, WriteOutcome::Deleted(old_id));
 
-    let old = memory.get(&[old_id]).await.unwrap();
+    let old = memory.get(&[old_id], None).await.unwrap();
     assert!(old.is_empty());
 
     let search = memory
@@ -496,7 +500,7 @@ async fn timeline_returns_chronological_window() {
         unreachable!();
     };
 
-    let timeline = memory.timeline(two, 1, 1).await.unwrap();
+    let timeline = memory.timeline(two, 1, 1, None).await.unwrap();
     let ids = timeline.iter().map(|o| o.id).collect::<Vec<_>>();
     assert_eq!(ids, vec![one, two, three]);
 }
@@ -630,7 +634,7 @@ async fn maintenance_compression_creates_summary_and_expires_originals() {
     assert_eq!(active.len(), 1);
 
     for id in original_ids {
-        let obs = memory.get(&[id]).await.unwrap();
+        let obs = memory.get(&[id], None).await.unwrap();
         assert!(obs.is_empty());
 
         let history = memory.history(id).await.unwrap();
@@ -666,7 +670,7 @@ async fn maintenance_archive_moves_rows_to_archive_table() {
     let report = memory.run_maintenance(&config).await.unwrap();
     assert_eq!(report.archived_rows, 1);
 
-    let active = memory.get(&[id]).await.unwrap();
+    let active = memory.get(&[id], None).await.unwrap();
     assert!(active.is_empty());
     assert_eq!(archive_row_count(&memory), 1);
 
@@ -1024,14 +1028,14 @@ async fn search_by_file_exact_match() {
     };
 
     let hits = memory
-        .search_by_file("src/main.rs", false, 10)
+        .search_by_file("src/main.rs", false, 10, None)
         .await
         .unwrap();
     assert_eq!(hits.len(), 1);
     assert_eq!(hits[0].id, first_id);
 
     let lib_hits = memory
-        .search_by_file("src/lib.rs", false, 10)
+        .search_by_file("src/lib.rs", false, 10, None)
         .await
         .unwrap();
     let mut ids = lib_hits.iter().map(|row| row.id).collect::<Vec<_>>();
@@ -1058,7 +1062,7 @@ async fn search_by_file_prefix_match() {
     memory.write(second).await.unwrap();
 
     let hits = memory
-        .search_by_file("crates/coop-gateway/", true, 10)
+        .search_by_file("crates/coop-gateway/", true, 10, None)
         .await
         .unwrap();
 
@@ -1076,7 +1080,7 @@ async fn search_by_file_respects_expiry() {
     memory.write(expiring).await.unwrap();
 
     let hits = memory
-        .search_by_file("src/expired.rs", false, 10)
+        .search_by_file("src/expired.rs", false, 10, None)
         .await
         .unwrap();
     assert!(hits.is_empty());
@@ -1088,7 +1092,7 @@ async fn search_by_file_empty_results() {
     memory.write(sample_obs("present", &["one"])).await.unwrap();
 
     let hits = memory
-        .search_by_file("does/not/exist.rs", false, 10)
+        .search_by_file("does/not/exist.rs", false, 10, None)
         .await
         .unwrap();
     assert!(hits.is_empty());
@@ -1129,7 +1133,7 @@ async fn people_query_returns_results() {
     m.write(obs_with_people("project alpha", &["Alice", "Bob"]))
         .await
         .unwrap();
-    let results = m.people("Alice").await.unwrap();
+    let results = m.people("Alice", None).await.unwrap();
     assert_eq!(results.len(), 1);
     assert_eq!(results[0].name, "Alice");
     assert!(results[0].aliases.is_empty());
@@ -1145,7 +1149,7 @@ async fn add_alias_to_existing_person() {
     let added = m.add_person_alias("Alice", "ally").await.unwrap();
     assert!(added);
 
-    let results = m.people("Alice").await.unwrap();
+    let results = m.people("Alice", None).await.unwrap();
     assert_eq!(results[0].aliases, vec!["ally"]);
 }
 
@@ -1159,7 +1163,7 @@ async fn alias_deduplicates_case_insensitive() {
     assert!(m.add_person_alias("Alice", "ally").await.unwrap());
     assert!(!m.add_person_alias("Alice", "Ally").await.unwrap());
 
-    let results = m.people("Alice").await.unwrap();
+    let results = m.people("Alice", None).await.unwrap();
     assert_eq!(results[0].aliases.len(), 1);
 }
 
@@ -1179,7 +1183,479 @@ async fn people_searchable_by_alias() {
     m.add_person_alias("Alice", "ally").await.unwrap();
 
     // Searching by alias should find the person
-    let results = m.people("ally").await.unwrap();
+    let results = m.people("ally", None).await.unwrap();
     assert_eq!(results.len(), 1);
     assert_eq!(results[0].name, "Alice");
 }
+
+// ---------------------------------------------------------------------------
+// Ownership tests
+// ---------------------------------------------------------------------------
+
+fn owned_obs(title: &str, store: &str, owner: &str) -> NewObservation {
+    let mut obs = sample_obs(title, &[title]);
+    obs.store = store.to_owned();
+    obs.min_trust = min_trust_for_store(store);
+    obs.owner = Some(owner.to_owned());
+    obs
+}
+
+async fn search_titles(memory: &SqliteMemory, viewer: &str) -> Vec<String> {
+    let query = MemoryQuery {
+        limit: 10,
+        viewer: Some(viewer.to_owned()),
+        ..Default::default()
+    };
+    memory
+        .search(&query)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|index| index.title)
+        .collect()
+}
+
+#[tokio::test]
+async fn private_rows_are_hidden_from_other_principals() {
+    let m = memory();
+    let WriteOutcome::Added(id) = m
+        .write(owned_obs("alice secret", "private", "user:alice"))
+        .await
+        .unwrap()
+    else {
+        panic!("expected add");
+    };
+    m.write(owned_obs("team fact", "shared", "user:alice"))
+        .await
+        .unwrap();
+
+    assert_eq!(search_titles(&m, "user:alice").await.len(), 2);
+    assert_eq!(search_titles(&m, "user:bob").await, vec!["team fact"]);
+    assert!(m.get(&[id], Some("user:bob")).await.unwrap().is_empty());
+    assert_eq!(m.get(&[id], Some("user:alice")).await.unwrap().len(), 1);
+    assert_eq!(m.get(&[id], None).await.unwrap().len(), 1);
+    assert!(
+        m.timeline(id, 5, 5, Some("user:bob"))
+            .await
+            .unwrap()
+            .is_empty()
+    );
+    assert!(
+        m.search_by_file("src/main.rs", false, 10, Some("user:bob"))
+            .await
+            .unwrap()
+            .iter()
+            .all(|index| index.title == "team fact")
+    );
+}
+
+#[tokio::test]
+async fn share_list_grants_access_to_private_rows() {
+    let m = memory();
+    let mut obs = owned_obs("anniversary plans", "private", "user:alice");
+    obs.shared_with = vec!["user:bob".to_owned()];
+    let WriteOutcome::Added(id) = m.write(obs).await.unwrap() else {
+        panic!("expected add");
+    };
+
+    let shared = m.get(&[id], Some("user:bob")).await.unwrap();
+    assert_eq!(shared[0].owner.as_deref(), Some("user:alice"));
+    assert_eq!(shared[0].shared_with, vec!["user:bob"]);
+    assert_eq!(
+        search_titles(&m, "user:bob").await,
+        vec!["anniversary plans"]
+    );
+    assert!(search_titles(&m, "user:carol").await.is_empty());
+}
+
+#[tokio::test]
+async fn identical_private_writes_from_different_owners_stay_separate() {
+    let m = memory();
+    let first = m
+        .write(owned_obs("dentist tuesday", "private", "user:alice"))
+        .await
+        .unwrap();
+    let second = m
+        .write(owned_obs("dentist tuesday", "private", "user:bob"))
+        .await
+        .unwrap();
+
+    assert!(matches!(first, WriteOutcome::Added(_)));
+    assert!(matches!(second, WriteOutcome::Added(_)));
+    assert_eq!(search_titles(&m, "user:bob").await, vec!["dentist tuesday"]);
+}
+
+#[tokio::test]
+async fn private_people_follow_owner_and_share_on_mention() {
+    let m = memory();
+    let mut obs = owned_obs("dinner with carol", "private", "user:alice");
+    obs.related_people = vec!["Carol".to_owned()];
+    m.write(obs).await.unwrap();
+
+    assert_eq!(
+        m.people("Carol", Some("user:alice")).await.unwrap().len(),
+        1
+    );
+    assert!(
+        m.people("Carol", Some("user:bob"))
+            .await
+            .unwrap()
+            .is_empty()
+    );
+
+    let mut obs = owned_obs("carol's birthday", "private", "user:bob");
+    obs.related_people = vec!["Carol".to_owned()];
+    m.write(obs).await.unwrap();
+
+    let people = m.people("Carol", Some("user:bob")).await.unwrap();
+    assert_eq!(people[0].owner.as_deref(), Some("user:alice"));
+    assert_eq!(people[0].shared_with, vec!["user:bob"]);
+}
+
+#[tokio::test]
+async fn schema_migration_assigns_group_owners() {
+    let m = memory();
+    let mut obs = sample_obs("group plans", &["picnic"]);
+    obs.session_key = Some("coop:group:signal:group:abc".to_owned());
+    m.write(obs).await.unwrap();
+
+    let conn = m.conn.lock().expect("memory db mutex poisoned");
+    conn.execute("UPDATE observations SET owner = NULL", [])
+        .unwrap();
+    super::schema::init_schema(&conn).unwrap();
+    let owner: Option<String> = conn
+        .query_row("SELECT owner FROM observations", [], |row| row.get(0))
+        .unwrap();
+    drop(conn);
+
+    assert_eq!(owner.as_deref(), Some("group:signal:group:abc"));
+}
+
+#[tokio::test]
+async fn assign_owners_backfills_rows_and_people() {
+    let m = memory();
+    let mut obs = sample_obs("alice's notes", &["likes tea"]);
+    obs.store = "private".to_owned();
+    obs.session_key = Some("coop:dm:signal:alice-uuid".to_owned());
+    obs.related_people = vec!["Dave".to_owned()];
+    let WriteOutcome::Added(id) = m.write(obs).await.unwrap() else {
+        panic!("expected add");
+    };
+    let mut obs = sample_obs("cron output", &["ran"]);
+    obs.session_key = Some("coop:main".to_owned());
+    m.write(obs).await.unwrap();
+
+    let assigned = m
+        .assign_owners(|key| (key == "coop:dm:signal:alice-uuid").then(|| "user:alice".to_owned()))
+        .unwrap();
+    assert_eq!(assigned, 2);
+    assert!(m.get(&[id], Some("user:bob")).await.unwrap().is_empty());
+    assert!(m.people("Dave", Some("user:bob")).await.unwrap().is_empty());
+    assert_eq!(m.people("Dave", Some("user:alice")).await.unwrap().len(), 1);
+    assert_eq!(m.assign_owners(|_| Some("user:x".to_owned())).unwrap(), 2);
+}
+
+// ---------------------------------------------------------------------------
+// Admin tests
+// ---------------------------------------------------------------------------
+
+async fn add(memory: &SqliteMemory, obs: NewObservation) -> i64 {
+    match memory.write(obs).await.unwrap() {
+        WriteOutcome::Added(id) => id,
+        outcome => panic!("expected add, got {outcome:?}"),
+    }
+}
+
+#[tokio::test]
+async fn edit_rewrites_fields_and_records_history() {
+    let m = memory();
+    let id = add(&m, sample_obs("wrong birthday", &["born in May"])).await;
+
+    let edit = ObservationEdit {
+        title: Some("alice birthday".to_owned()),
+        facts: Some(vec!["born in June".to_owned()]),
+        store: Some("private".to_owned()),
+        ..Default::default()
+    };
+    assert!(m.edit(id, &edit).await.unwrap());
+    assert!(!m.edit(id + 100, &edit).await.unwrap());
+
+    let obs = m.get(&[id], None).await.unwrap();
+    assert_eq!(obs[0].title, "alice birthday");
+    assert_eq!(obs[0].facts, vec!["born in June"]);
+    assert_eq!(obs[0].store, "private");
+    assert_eq!(obs[0].narrative, "narrative for wrong birthday");
+
+    let history = m.history(id).await.unwrap();
+    let last = history.last().unwrap();
+    assert_eq!(last.event, "EDIT");
+    assert_eq!(last.old_title.as_deref(), Some("wrong birthday"));
+}
+
+#[tokio::test]
+async fn delete_removes_row_and_history() {
+    let m = memory();
+    let id = add(&m, sample_obs("secret", &["do not keep"])).await;
+
+    assert!(m.delete(id).await.unwrap());
+    assert!(!m.delete(id).await.unwrap());
+    assert!(m.get(&[id], None).await.unwrap().is_empty());
+    assert!(m.history(id).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn archive_and_restore_round_trip() {
+    let m = memory();
+    let id = add(&m, sample_obs("old project", &["used make"])).await;
+
+    assert!(m.archive(id).await.unwrap());
+    assert!(m.get(&[id], None).await.unwrap().is_empty());
+    let archived = m.archived(10).await.unwrap();
+    assert_eq!(archived.len(), 1);
+    assert_eq!(archived[0].id, id);
+    assert_eq!(archived[0].archive_reason, "manual");
+
+    assert_eq!(m.restore(id).await.unwrap(), Some(id));
+    assert_eq!(m.restore(id).await.unwrap(), None);
+    assert!(m.archived(10).await.unwrap().is_empty());
+    let obs = m.get(&[id], None).await.unwrap();
+    assert_eq!(obs[0].title, "old project");
+    assert_eq!(
+        m.history(id).await.unwrap().last().unwrap().event,
+        "RESTORE"
+    );
+}
+
+#[tokio::test]
+async fn merge_people_folds_aliases_and_mentions() {
+    let m = memory();
+    let id = add(&m, obs_with_people("lunch", &["Bob", "Robert"])).await;
+    add(&m, obs_with_people("ski trip", &["Bob"])).await;
+    m.add_person_alias("Bob", "Bobby").await.unwrap();
+
+    assert!(m.merge_people("Bob", "Robert").await.unwrap());
+    assert!(!m.merge_people("Bob", "Robert").await.unwrap());
+
+    assert!(
+        m.people("Bob", None)
+            .await
+            .unwrap()
+            .iter()
+            .all(|p| p.name == "Robert")
+    );
+    let robert = m.people("Robert", None).await.unwrap();
+    assert_eq!(robert.len(), 1);
+    assert_eq!(robert[0].aliases, vec!["Bobby", "Bob"]);
+    assert_eq!(robert[0].mention_count, 3);
+
+    let obs = m.get(&[id], None).await.unwrap();
+    assert_eq!(obs[0].related_people, vec!["Robert"]);
+}
+
+#[tokio::test]
+async fn merge_people_renames_when_target_is_new() {
+    let m = memory();
+    add(&m, obs_with_people("lunch", &["Bob"])).await;
+
+    assert!(m.merge_people("Bob", "Robert").await.unwrap());
+    let robert = m.people("Robert", None).await.unwrap();
+    assert_eq!(robert[0].aliases, vec!["Bob"]);
+}
+
+async fn exported_memory() -> (SqliteMemory, i64) {
+    let m = memory();
+    let mut decision = sample_obs("use sqlite for memory", &["chosen in march"]);
+    decision.obs_type = "decision".to_owned();
+    decision.session_key = Some("coop:main".to_owned());
+    decision.created_at = Some(chrono::DateTime::from_timestamp(1_700_000_000, 0).unwrap());
+    decision.expires_at = Some(chrono::DateTime::from_timestamp(4_000_000_000, 0).unwrap());
+    let id = add(&m, decision).await;
+    add(&m, obs_with_people("lunch", &["Bob"])).await;
+    m.add_person_alias("Bob", "Bobby").await.unwrap();
+    let edit = ObservationEdit {
+        narrative: Some("sqlite keeps it in one file".to_owned()),
+        ..Default::default()
+    };
+    m.edit(id, &edit).await.unwrap();
+    m.summarize_session(&SessionKey {
+        agent_id: "coop".to_owned(),
+        kind: SessionKind::Main,
+    })
+    .await
+    .unwrap();
+    (m, id)
+}
+
+#[tokio::test]
+async fn jsonl_export_imports_and_merges_duplicates() {
+    let (source, id) = exported_memory().await;
+    let jsonl = transfer::to_jsonl(&source.export().await.unwrap()).unwrap();
+    assert_eq!(jsonl.lines().count(), 8);
+
+    let target = memory();
+    let records = transfer::parse_jsonl(&jsonl).unwrap();
+    let report = transfer::import(&target, records.clone()).await.unwrap();
+    assert_eq!(
+        report,
+        transfer::ImportReport {
+            added: 2,
+            updated: 0,
+            unchanged: 0,
+            people: 2,
+            history: 3,
+            session_summaries: 1,
+        }
+    );
+
+    let obs = target.get(&[id], None).await.unwrap();
+    assert_eq!(obs[0].narrative, "sqlite keeps it in one file");
+    assert_eq!(obs[0].created_at.timestamp(), 1_700_000_000);
+    assert_eq!(
+        obs[0].expires_at.map(|at| at.timestamp()),
+        Some(4_000_000_000)
+    );
+    let events = target.history(id).await.unwrap();
+    assert_eq!(
+        events.iter().map(|e| e.event.as_str()).collect::<Vec<_>>(),
+        vec!["ADD", "EDIT"]
+    );
+    let bob = target.people("Bob", None).await.unwrap();
+    assert_eq!(bob[0].aliases, vec!["Bobby"]);
+    assert_eq!(bob[0].mention_count, 1);
+    assert_eq!(target.recent_session_summaries(5).await.unwrap().len(), 1);
+
+    let again = transfer::import(&target, records).await.unwrap();
+    assert_eq!(again.added, 0);
+    assert_eq!(again.unchanged, 2);
+    assert_eq!(again.session_summaries, 0);
+}
+
+#[tokio::test]
+async fn markdown_vault_round_trips() {
+    let (source, id) = exported_memory().await;
+    let records = source.export().await.unwrap();
+    let dir = tempfile::tempdir().unwrap();
+    let vault = dir.path().join("vault");
+    transfer::vault::write_vault(&vault, &records).unwrap();
+    assert!(transfer::vault::is_vault(&vault));
+    assert!(transfer::vault::write_vault(&vault, &records).is_err());
+
+    let note = std::fs::read_to_string(
+        vault
+            .join("observations")
+            .join(format!("{id:05}-use-sqlite-for-memory.md")),
+    )
+    .unwrap();
+    assert!(
+        note.contains(
+            "# use sqlite for memory\n\nsqlite keeps it in one file\n\n## Facts\n\n- chosen in march\n"
+        ),
+        "{note}"
+    );
+
+    let read = transfer::vault::read_vault(&vault).unwrap();
+    let sorted_lines = |records: &[MemoryRecord]| {
+        let jsonl = transfer::to_jsonl(records).unwrap();
+        let mut lines = jsonl.lines().map(str::to_owned).collect::<Vec<_>>();
+        lines.sort();
+        lines
+    };
+    assert_eq!(sorted_lines(&read), sorted_lines(&records));
+}
+
+#[tokio::test]
+async fn workspace_notes_import_as_private_observations() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        dir.path().join("MEMORY.md"),
+        "## Preferences\n- Prefers tea\n",
+    )
+    .unwrap();
+    std::fs::create_dir(dir.path().join("memory")).unwrap();
+    std::fs::write(
+        dir.path().join("memory").join("2026-03-04.md"),
+        "Fixed the boiler.\n",
+    )
+    .unwrap();
+
+    let m = memory();
+    let notes = transfer::notes::workspace_notes(dir.path()).unwrap();
+    let report = transfer::import_observations(&m, notes.clone())
+        .await
+        .unwrap();
+    assert_eq!(report.added, 2);
+    let again = transfer::import_observations(&m, notes).await.unwrap();
+    assert_eq!(again.unchanged, 2);
+
+    let hits = m
+        .search_by_file("memory/2026-03-04.md", false, 5, None)
+        .await
+        .unwrap();
+    assert_eq!(hits[0].title, "2026-03-04: Daily notes");
+    assert_eq!(hits[0].store, "private");
+}
+
+#[tokio::test]
+async fn forget_person_deletes_and_redacts_every_mention() {
+    let m = memory();
+    let about = add(&m, obs_with_people("Bob likes skiing", &["Bob"])).await;
+    let mut lunch = obs_with_people("lunch with Bob and Alice", &["Bob", "Alice"]);
+    lunch.facts = vec!["Bob paid".to_owned()];
+    let shared = add(&m, lunch).await;
+    let mut note = sample_obs("weekend plans", &["call bobby on sunday"]);
+    note.related_people = Vec::new();
+    let mention = add(&m, note).await;
+    let archived = add(&m, obs_with_people("Bob's old address", &["Bob"])).await;
+    m.archive(archived).await.unwrap();
+    m.add_person_alias("Bob", "Bobby").await.unwrap();
+    m.index_session_message(&crate::types::SessionMessage {
+        session_key: "coop:main".to_owned(),
+        role: "user".to_owned(),
+        content: "remind me to thank Bobby".to_owned(),
+        tool_name: None,
+        created_at: chrono::Utc::now(),
+    })
+    .await
+    .unwrap();
+
+    let report = m.forget_person("bob").await.unwrap();
+    assert_eq!(report.names, vec!["Bobby", "bob"]);
+    assert_eq!(report.people_deleted, 1);
+    assert_eq!(report.observations_deleted, 1);
+    assert_eq!(report.observations_redacted, 2);
+    assert_eq!(report.archived_deleted, 1);
+    assert_eq!(report.history_redacted, 2);
+    assert_eq!(report.session_messages_redacted, 1);
+
+    assert!(m.get(&[about], None).await.unwrap().is_empty());
+    assert!(m.history(about).await.unwrap().is_empty());
+    assert!(m.archived(10).await.unwrap().is_empty());
+    let obs = m.get(&[shared, mention], None).await.unwrap();
+    assert_eq!(obs[0].title, "lunch with [redacted] and Alice");
+    assert_eq!(obs[0].facts, vec!["[redacted] paid"]);
+    assert_eq!(obs[0].related_people, vec!["Alice"]);
+    assert_eq!(obs[1].facts, vec!["call [redacted] on sunday"]);
+    let history = m.history(shared).await.unwrap();
+    assert_eq!(
+        history[0].new_title.as_deref(),
+        Some("lunch with [redacted] and Alice")
+    );
+
+    assert!(m.people("Bob", None).await.unwrap().is_empty());
+    let query = MemoryQuery {
+        text: Some("bob".to_owned()),
+        limit: 10,
+        ..Default::default()
+    };
+    assert!(m.search(&query).await.unwrap().is_empty());
+    assert!(
+        m.search_session_messages("Bobby", 5, None)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+    assert_eq!(
+        m.search_session_messages("thank", 5, None).await.unwrap()[0].snippet,
+        "remind me to thank [redacted]"
+    );
+}
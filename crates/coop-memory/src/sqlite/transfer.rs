@@ -0,0 +1,268 @@
+use anyhow::Result;
+use rusqlite::params;
+use serde_json::Value as JsonValue;
+
+use crate::types::{MemoryRecord, ObservationHistoryEntry, Person, SessionSummary};
+
+use super::{SqliteMemory, helpers, write_ops};
+
+pub(super) fn export(memory: &SqliteMemory) -> Result<Vec<MemoryRecord>> {
+    let now = helpers::now_ms();
+    let conn = memory.conn.lock().expect("memory db mutex poisoned");
+    let mut records = Vec::new();
+
+    let mut stmt = conn.prepare(
+        "
+            SELECT
+                id,
+                title,
+                COALESCE(narrative, ''),
+                facts,
+                tags,
+                type,
+                store,
+                related_files,
+                related_people,
+                created_at,
+                token_count,
+                mention_count,
+                owner,
+                shared_with,
+                expires_at
+            FROM observations
+            WHERE agent_id = ?
+              AND (expires_at IS NULL OR expires_at > ?)
+            ORDER BY id ASC
+            ",
+    )?;
+    for row in stmt.query_map(params![memory.agent_id, now], helpers::observation_from_row)? {
+        records.push(MemoryRecord::Observation(row?));
+    }
+    drop(stmt);
+
+    let mut stmt = conn.prepare(
+        "
+            SELECT h.observation_id, h.old_title, h.old_facts, h.new_title, h.new_facts, h.event, h.created_at
+            FROM observation_history h
+            JOIN observations o ON o.id = h.observation_id
+            WHERE o.agent_id = ?
+              AND (o.expires_at IS NULL OR o.expires_at > ?)
+            ORDER BY h.observation_id ASC, h.created_at ASC, h.id ASC
+            ",
+    )?;
+    for row in stmt.query_map(
+        params![memory.agent_id, now],
+        write_ops::history_entry_from_row,
+    )? {
+        records.push(MemoryRecord::History(row?));
+    }
+    drop(stmt);
+
+    let mut stmt = conn.prepare(
+        "
+            SELECT name, store, facts, aliases, last_mentioned, mention_count, owner, shared_with
+            FROM people
+            WHERE agent_id = ?
+            ORDER BY name ASC
+            ",
+    )?;
+    for row in stmt.query_map(params![memory.agent_id], write_ops::person_from_row)? {
+        records.push(MemoryRecord::Person(row?));
+    }
+    drop(stmt);
+
+    let mut stmt = conn.prepare(
+        "
+            SELECT session_key, request, outcome, decisions, open_items, observation_count, created_at
+            FROM session_summaries
+            WHERE agent_id = ?
+            ORDER BY created_at ASC
+            ",
+    )?;
+    for row in stmt.query_map(
+        params![memory.agent_id],
+        write_ops::session_summary_from_row,
+    )? {
+        records.push(MemoryRecord::SessionSummary(row?));
+    }
+    drop(stmt);
+
+    drop(conn);
+    Ok(records)
+}
+
+pub(super) fn import_person(memory: &SqliteMemory, person: &Person) -> Result<()> {
+    let conn = memory.conn.lock().expect("memory db mutex poisoned");
+    let existing = conn
+        .query_row(
+            "
+                SELECT name, store, facts, aliases, last_mentioned, mention_count, owner, shared_with
+                FROM people
+                WHERE agent_id = ?
+                  AND name = ?
+                ",
+            params![memory.agent_id, person.name],
+            write_ops::person_from_row,
+        )
+        .ok();
+
+    let Some(existing) = existing else {
+        conn.execute(
+            "
+                INSERT INTO people (
+                    agent_id,
+                    name,
+                    store,
+                    facts,
+                    aliases,
+                    last_mentioned,
+                    mention_count,
+                    owner,
+                    shared_with
+                ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+                ",
+            params![
+                memory.agent_id,
+                person.name,
+                person.store,
+                person.facts.to_string(),
+                helpers::to_json(&person.aliases),
+                person.last_mentioned.map(helpers::ms_from_dt),
+                person.mention_count,
+                person.owner,
+                helpers::to_json(&person.shared_with),
+            ],
+        )?;
+        drop(conn);
+        return Ok(());
+    };
+
+    let mut aliases = existing.aliases;
+    for alias in &person.aliases {
+        let lower = alias.to_lowercase();
+        if !aliases.iter().any(|a| a.to_lowercase() == lower) {
+            aliases.push(alias.clone());
+        }
+    }
+
+    let mut facts = person.facts.clone();
+    if let (JsonValue::Object(merged), JsonValue::Object(known)) = (&mut facts, existing.facts) {
+        merged.extend(known);
+    }
+
+    let mut shared_with = existing.shared_with;
+    for principal in &person.shared_with {
+        if !shared_with.contains(principal) {
+            shared_with.push(principal.clone());
+        }
+    }
+
+    let last_mentioned = existing.last_mentioned.max(person.last_mentioned);
+    conn.execute(
+        "
+            UPDATE people
+            SET facts = ?,
+                aliases = ?,
+                shared_with = ?,
+                owner = COALESCE(owner, ?),
+                mention_count = ?,
+                last_mentioned = ?
+            WHERE agent_id = ?
+              AND name = ?
+            ",
+        params![
+            facts.to_string(),
+            helpers::to_json(&aliases),
+            helpers::to_json(&shared_with),
+            person.owner,
+            existing.mention_count.max(person.mention_count),
+            last_mentioned.map(helpers::ms_from_dt),
+            memory.agent_id,
+            person.name,
+        ],
+    )?;
+    drop(conn);
+    Ok(())
+}
+
+pub(super) fn import_history(
+    memory: &SqliteMemory,
+    observation_id: i64,
+    entries: &[ObservationHistoryEntry],
+) -> Result<()> {
+    let mut conn = memory.conn.lock().expect("memory db mutex poisoned");
+    let tx = conn.transaction()?;
+    tx.execute(
+        "DELETE FROM observation_history WHERE observation_id = ?",
+        params![observation_id],
+    )?;
+    for entry in entries {
+        tx.execute(
+            "
+                INSERT INTO observation_history (
+                    observation_id,
+                    old_title,
+                    old_facts,
+                    new_title,
+                    new_facts,
+                    event,
+                    created_at
+                ) VALUES (?, ?, ?, ?, ?, ?, ?)
+                ",
+            params![
+                observation_id,
+                entry.old_title,
+                entry.old_facts,
+                entry.new_title,
+                entry.new_facts,
+                entry.event,
+                helpers::ms_from_dt(entry.created_at),
+            ],
+        )?;
+    }
+    tx.commit()?;
+    drop(conn);
+    Ok(())
+}
+
+pub(super) fn import_session_summary(
+    memory: &SqliteMemory,
+    summary: &SessionSummary,
+) -> Result<bool> {
+    let conn = memory.conn.lock().expect("memory db mutex poisoned");
+    let changed = conn.execute(
+        "
+            INSERT INTO session_summaries (
+                agent_id,
+                session_key,
+                request,
+                outcome,
+                decisions,
+                open_items,
+                observation_count,
+                created_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+            ON CONFLICT(agent_id, session_key)
+            DO UPDATE SET
+                request = excluded.request,
+                outcome = excluded.outcome,
+                decisions = excluded.decisions,
+                open_items = excluded.open_items,
+                observation_count = excluded.observation_count,
+                created_at = excluded.created_at
+            WHERE excluded.created_at > session_summaries.created_at
+            ",
+        params![
+            memory.agent_id,
+            summary.session_key,
+            summary.request,
+            summary.outcome,
+            serde_json::to_string(&summary.decisions)?,
+            serde_json::to_string(&summary.open_items)?,
+            i64::try_from(summary.observation_count).unwrap_or(i64::MAX),
+            helpers::ms_from_dt(summary.created_at),
+        ],
+    )?;
+    drop(conn);
+    Ok(changed > 0)
+}
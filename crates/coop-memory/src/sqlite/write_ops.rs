@@ -9,7 +9,7 @@ use crate::types::{
     min_trust_for_store, normalize_file_path, trust_to_str,
 };
 
-use super::{SqliteMemory, helpers, query};
+use super::{SqliteMemory, helpers, query, schema};
 
 const RECONCILE_LIMIT: usize = 6;
 const RECONCILE_SCORE_THRESHOLD: f32 = 0.05;
@@ -29,6 +29,9 @@ struct ObservationPayload {
     token_count: u32,
     expires_at: Option<i64>,
     min_trust: String,
+    owner: Option<String>,
+    shared_with: Vec<String>,
+    created_at: Option<i64>,
     hash: String,
 }
 
@@ -54,6 +57,9 @@ impl ObservationPayload {
             token_count,
             expires_at: obs.expires_at.map(helpers::ms_from_dt),
             min_trust,
+            owner: obs.owner,
+            shared_with: obs.shared_with,
+            created_at: obs.created_at.map(helpers::ms_from_dt),
             hash,
         }
     }
@@ -92,6 +98,9 @@ impl ObservationPayload {
             token_count,
             expires_at: base.expires_at,
             min_trust: trust_to_str(min_trust_for_store(&store)).to_owned(),
+            owner: base.owner.clone(),
+            shared_with: base.shared_with.clone(),
+            created_at: base.created_at,
             hash,
         }
     }
@@ -121,7 +130,7 @@ pub(super) async fn write(memory: &SqliteMemory, obs: NewObservation) -> Result<
     let now = helpers::now_ms();
     let incoming = ObservationPayload::from_new(obs);
 
-    if bump_exact_duplicate(memory, &incoming.hash, now)? {
+    if bump_exact_duplicate(memory, &incoming.hash, incoming.owner.as_deref(), now)? {
         debug!("memory write exact duplicate");
         return Ok(WriteOutcome::ExactDup);
     }
@@ -132,19 +141,31 @@ pub(super) async fn write(memory: &SqliteMemory, obs: NewObservation) -> Result<
     apply_reconciliation_decision(memory, incoming, candidates, decision, now).await
 }
 
-fn bump_exact_duplicate(memory: &SqliteMemory, hash: &str, now: i64) -> Result<bool> {
+fn bump_exact_duplicate(
+    memory: &SqliteMemory,
+    hash: &str,
+    owner: Option<&str>,
+    now: i64,
+) -> Result<bool> {
+    let [v1, v2, v3] = schema::visibility_params(owner);
     let conn = memory.conn.lock().expect("memory db mutex poisoned");
 
+    // Only rows the writer can see count as duplicates, so one user's
+    // private memory never absorbs another's.
     let exact_dup: Option<(i64, u32)> = conn
         .query_row(
-            "
+            &format!(
+                "
                 SELECT id, mention_count
                 FROM observations
                 WHERE agent_id = ?
                   AND hash = ?
                   AND (expires_at IS NULL OR expires_at > ?)
+                  AND {}
                 ",
-            params![memory.agent_id, hash, now],
+                schema::visibility_condition("")
+            ),
+            params![memory.agent_id, hash, now, v1, v2, v3],
             |row| Ok((row.get(0)?, row.get(1)?)),
         )
         .optional()?;
@@ -177,6 +198,7 @@ async fn find_reconciliation_candidates(
         people: incoming.related_people.clone(),
         limit: RECONCILE_LIMIT,
         max_tokens: None,
+        viewer: incoming.owner.clone(),
         ..Default::default()
     };
 
@@ -403,6 +425,7 @@ async fn apply_update(
             &conn,
             &memory.agent_id,
             &merged.store,
+            merged.owner.as_deref(),
             &merged.related_people,
             now,
         )?;
@@ -487,6 +510,7 @@ fn insert_observation(memory: &SqliteMemory, obs: &ObservationPayload, now: i64)
     let tags_json = helpers::to_json(&obs.tags);
     let files_json = helpers::to_json(&obs.related_files);
     let people_json = helpers::to_json(&obs.related_people);
+    let shared_json = helpers::to_json(&obs.shared_with);
 
     let conn = memory.conn.lock().expect("memory db mutex poisoned");
 
@@ -510,8 +534,10 @@ fn insert_observation(memory: &SqliteMemory, obs: &ObservationPayload, now: i64)
                 created_at,
                 updated_at,
                 expires_at,
-                min_trust
-            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, 1, ?, ?, ?, ?, ?)
+                min_trust,
+                owner,
+                shared_with
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, 1, ?, ?, ?, ?, ?, ?, ?)
             ",
         params![
             memory.agent_id,
@@ -527,10 +553,12 @@ fn insert_observation(memory: &SqliteMemory, obs: &ObservationPayload, now: i64)
             people_json,
             obs.hash,
             obs.token_count,
-            now,
+            obs.created_at.unwrap_or(now),
             now,
             obs.expires_at,
             obs.min_trust,
+            obs.owner,
+            shared_json,
         ],
     )?;
 
@@ -555,6 +583,7 @@ fn insert_observation(memory: &SqliteMemory, obs: &ObservationPayload, now: i64)
         &conn,
         &memory.agent_id,
         &obs.store,
+        obs.owner.as_deref(),
         &obs.related_people,
         now,
     )?;
@@ -567,9 +596,12 @@ fn upsert_people(
     conn: &rusqlite::Connection,
     agent_id: &str,
     store: &str,
+    owner: Option<&str>,
     related_people: &[String],
     now: i64,
 ) -> Result<()> {
+    // The first principal to mention someone owns the row; later mentions
+    // from other principals are added to its share list.
     for person in related_people {
         conn.execute(
             "
@@ -579,15 +611,27 @@ fn upsert_people(
                     store,
                     facts,
                     last_mentioned,
-                    mention_count
-                ) VALUES (?, ?, ?, '{}', ?, 1)
+                    mention_count,
+                    owner
+                ) VALUES (?, ?, ?, '{}', ?, 1, ?)
                 ON CONFLICT(agent_id, name)
                 DO UPDATE SET
                     store = excluded.store,
                     last_mentioned = excluded.last_mentioned,
-                    mention_count = people.mention_count + 1
+                    mention_count = people.mention_count + 1,
+                    shared_with = CASE
+                        WHEN excluded.owner IS NULL
+                          OR people.owner IS NULL
+                          OR people.owner = excluded.owner
+                          OR EXISTS (
+                              SELECT 1 FROM json_each(people.shared_with)
+                              WHERE json_each.value = excluded.owner
+                          )
+                        THEN people.shared_with
+                        ELSE json_insert(people.shared_with, '$[#]', excluded.owner)
+                    END
                 ",
-            params![agent_id, person, store, now],
+            params![agent_id, person, store, now, owner],
         )?;
     }
 
@@ -633,7 +677,7 @@ async fn embed_and_persist(
     }
 }
 
-fn estimate_token_count(title: &str, narrative: &str, facts: &[String]) -> u32 {
+pub(super) fn estimate_token_count(title: &str, narrative: &str, facts: &[String]) -> u32 {
     let mut text = title.to_owned();
     if !narrative.is_empty() {
         text.push(' ');
@@ -669,7 +713,11 @@ fn normalize_related_files(files: Vec<String>) -> Vec<String> {
     out
 }
 
-pub(super) fn people(memory: &SqliteMemory, query: &str) -> Result<Vec<Person>> {
+pub(super) fn people(
+    memory: &SqliteMemory,
+    query: &str,
+    viewer: Option<&str>,
+) -> Result<Vec<Person>> {
     let needle = if query.trim().is_empty() {
         "%".to_owned()
     } else {
@@ -677,34 +725,22 @@ pub(super) fn people(memory: &SqliteMemory, query: &str) -> Result<Vec<Person>>
     };
 
     let conn = memory.conn.lock().expect("memory db mutex poisoned");
-    let mut stmt = conn.prepare(
+    let mut stmt = conn.prepare(&format!(
         "
-            SELECT name, store, facts, aliases, last_mentioned, mention_count
+            SELECT name, store, facts, aliases, last_mentioned, mention_count, owner, shared_with
             FROM people
             WHERE agent_id = ?
               AND (name LIKE ? OR aliases LIKE ?)
+              AND {}
             ORDER BY mention_count DESC, COALESCE(last_mentioned, 0) DESC
             LIMIT 20
             ",
-    )?;
+        schema::visibility_condition("")
+    ))?;
 
-    let rows = stmt.query_map(params![memory.agent_id, needle, needle], |row| {
-        let facts: String = row.get(2)?;
-        let facts_value = serde_json::from_str(&facts).unwrap_or_else(|_| serde_json::json!({}));
-        let aliases_raw: String = row.get(3)?;
-        let aliases: Vec<String> =
-            serde_json::from_str(&aliases_raw).unwrap_or_else(|_| Vec::new());
-        let last_mentioned: Option<i64> = row.get(4)?;
-        let mention_count: u32 = row.get(5)?;
-        Ok(Person {
-            name: row.get(0)?,
-            store: row.get(1)?,
-            facts: facts_value,
-            aliases,
-            last_mentioned: last_mentioned.map(helpers::dt_from_ms),
-            mention_count,
-        })
-    })?;
+    let [v1, v2, v3] = schema::visibility_params(viewer);
+    let params = params![memory.agent_id, needle, needle, v1, v2, v3];
+    let rows = stmt.query_map(params, person_from_row)?;
 
     let mut out = Vec::new();
     for row in rows {
@@ -715,6 +751,26 @@ pub(super) fn people(memory: &SqliteMemory, query: &str) -> Result<Vec<Person>>
     Ok(out)
 }
 
+pub(super) fn person_from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<Person> {
+    let facts: String = row.get(2)?;
+    let facts_value = serde_json::from_str(&facts).unwrap_or_else(|_| serde_json::json!({}));
+    let aliases_raw: String = row.get(3)?;
+    let aliases: Vec<String> = serde_json::from_str(&aliases_raw).unwrap_or_else(|_| Vec::new());
+    let last_mentioned: Option<i64> = row.get(4)?;
+    let mention_count: u32 = row.get(5)?;
+    let shared_with: String = row.get(7)?;
+    Ok(Person {
+        name: row.get(0)?,
+        store: row.get(1)?,
+        facts: facts_value,
+        aliases,
+        last_mentioned: last_mentioned.map(helpers::dt_from_ms),
+        mention_count,
+        owner: row.get(6)?,
+        shared_with: helpers::from_json(&shared_with),
+    })
+}
+
 pub(super) fn add_person_alias(memory: &SqliteMemory, name: &str, alias: &str) -> Result<bool> {
     let alias = alias.trim();
     if alias.is_empty() {
@@ -866,27 +922,7 @@ pub(super) fn recent_session_summaries(
             ",
     )?;
 
-    let rows = stmt.query_map(params![memory.agent_id, limit], |row| {
-        let decisions = row
-            .get::<_, Option<String>>(3)?
-            .unwrap_or_else(|| "[]".to_owned());
-        let open_items = row
-            .get::<_, Option<String>>(4)?
-            .unwrap_or_else(|| "[]".to_owned());
-        let observation_count = row
-            .get::<_, Option<i64>>(5)?
-            .and_then(|value| usize::try_from(value).ok())
-            .unwrap_or_default();
-        Ok(SessionSummary {
-            session_key: row.get(0)?,
-            request: row.get::<_, Option<String>>(1)?.unwrap_or_default(),
-            outcome: row.get::<_, Option<String>>(2)?.unwrap_or_default(),
-            decisions: serde_json::from_str(&decisions).unwrap_or_default(),
-            open_items: serde_json::from_str(&open_items).unwrap_or_default(),
-            observation_count,
-            created_at: helpers::dt_from_ms(row.get(6)?),
-        })
-    })?;
+    let rows = stmt.query_map(params![memory.agent_id, limit], session_summary_from_row)?;
 
     let mut out = Vec::new();
     for row in rows {
@@ -897,6 +933,30 @@ pub(super) fn recent_session_summaries(
     Ok(out)
 }
 
+pub(super) fn session_summary_from_row(
+    row: &rusqlite::Row<'_>,
+) -> rusqlite::Result<SessionSummary> {
+    let decisions = row
+        .get::<_, Option<String>>(3)?
+        .unwrap_or_else(|| "[]".to_owned());
+    let open_items = row
+        .get::<_, Option<String>>(4)?
+        .unwrap_or_else(|| "[]".to_owned());
+    let observation_count = row
+        .get::<_, Option<i64>>(5)?
+        .and_then(|value| usize::try_from(value).ok())
+        .unwrap_or_default();
+    Ok(SessionSummary {
+        session_key: row.get(0)?,
+        request: row.get::<_, Option<String>>(1)?.unwrap_or_default(),
+        outcome: row.get::<_, Option<String>>(2)?.unwrap_or_default(),
+        decisions: serde_json::from_str(&decisions).unwrap_or_default(),
+        open_items: serde_json::from_str(&open_items).unwrap_or_default(),
+        observation_count,
+        created_at: helpers::dt_from_ms(row.get(6)?),
+    })
+}
+
 pub(super) fn history(
     memory: &SqliteMemory,
     observation_id: i64,
@@ -911,17 +971,7 @@ pub(super) fn history(
             ",
     )?;
 
-    let rows = stmt.query_map(params![observation_id], |row| {
-        Ok(ObservationHistoryEntry {
-            observation_id: row.get(0)?,
-            old_title: row.get(1)?,
-            old_facts: row.get(2)?,
-            new_title: row.get(3)?,
-            new_facts: row.get(4)?,
-            event: row.get(5)?,
-            created_at: helpers::dt_from_ms(row.get(6)?),
-        })
-    })?;
+    let rows = stmt.query_map(params![observation_id], history_entry_from_row)?;
 
     let mut out = Vec::new();
     for row in rows {
@@ -931,3 +981,17 @@ pub(super) fn history(
     drop(conn);
     Ok(out)
 }
+
+pub(super) fn history_entry_from_row(
+    row: &rusqlite::Row<'_>,
+) -> rusqlite::Result<ObservationHistoryEntry> {
+    Ok(ObservationHistoryEntry {
+        observation_id: row.get(0)?,
+        old_title: row.get(1)?,
+        old_facts: row.get(2)?,
+        new_title: row.get(3)?,
+        new_facts: row.get(4)?,
+        event: row.get(5)?,
+        created_at: helpers::dt_from_ms(row.get(6)?),
+    })
+}
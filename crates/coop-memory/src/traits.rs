@@ -3,7 +3,8 @@ use async_trait::async_trait;
 use coop_core::SessionKey;
 
 use crate::types::{
-    MemoryMaintenanceConfig, MemoryMaintenanceReport, MemoryQuery, NewObservation, Observation,
+    ArchivedObservation, ErasureReport, MemoryMaintenanceConfig, MemoryMaintenanceReport,
+    MemoryQuery, MemoryRecord, NewObservation, Observation, ObservationEdit,
     ObservationHistoryEntry, ObservationIndex, Person, ReconcileDecision, ReconcileRequest,
     SessionMessage, SessionSearchHit, SessionSummary, WriteOutcome,
 };
@@ -28,6 +29,9 @@ pub trait Reconciler: Send + Sync {
     async fn reconcile(&self, request: &ReconcileRequest) -> Result<ReconcileDecision>;
 }
 
+/// Read methods take a `viewer` principal (see `memory_principal`) and hide
+/// rows it doesn't own (see `visible_to`). `None` reads everything; trust is
+/// enforced separately through stores.
 #[async_trait]
 pub trait Memory: Send + Sync {
     async fn search(&self, query: &MemoryQuery) -> Result<Vec<ObservationIndex>>;
@@ -37,6 +41,7 @@ pub trait Memory: Send + Sync {
         _path: &str,
         _prefix_match: bool,
         _limit: usize,
+        _viewer: Option<&str>,
     ) -> Result<Vec<ObservationIndex>> {
         Ok(Vec::new())
     }
@@ -46,13 +51,14 @@ pub trait Memory: Send + Sync {
         anchor: i64,
         before: usize,
         after: usize,
+        viewer: Option<&str>,
     ) -> Result<Vec<ObservationIndex>>;
 
-    async fn get(&self, ids: &[i64]) -> Result<Vec<Observation>>;
+    async fn get(&self, ids: &[i64], viewer: Option<&str>) -> Result<Vec<Observation>>;
 
     async fn write(&self, obs: NewObservation) -> Result<WriteOutcome>;
 
-    async fn people(&self, query: &str) -> Result<Vec<Person>>;
+    async fn people(&self, query: &str, viewer: Option<&str>) -> Result<Vec<Person>>;
 
     /// Add an alias for a known person. If the person doesn't exist, this is a no-op.
     /// Aliases are deduplicated (case-insensitive).
@@ -104,4 +110,71 @@ pub trait Memory: Send + Sync {
     async fn rebuild_index(&self) -> Result<usize> {
         Ok(0)
     }
+
+    /// Apply a manual correction. Returns `false` if the observation doesn't
+    /// exist.
+    async fn edit(&self, _id: i64, _edit: &ObservationEdit) -> Result<bool> {
+        Ok(false)
+    }
+
+    /// Permanently remove an observation and its history.
+    async fn delete(&self, _id: i64) -> Result<bool> {
+        Ok(false)
+    }
+
+    /// Fold person `from` into `into`: `from` becomes an alias and
+    /// observations mentioning it are rewritten. Returns `false` if `from`
+    /// doesn't exist.
+    async fn merge_people(&self, _from: &str, _into: &str) -> Result<bool> {
+        Ok(false)
+    }
+
+    /// Move an observation to the archive.
+    async fn archive(&self, _id: i64) -> Result<bool> {
+        Ok(false)
+    }
+
+    /// Move an archived observation back, returning its id in the live table
+    /// (the original id unless it has been reused).
+    async fn restore(&self, _id: i64) -> Result<Option<i64>> {
+        Ok(None)
+    }
+
+    /// Most recently archived observations, newest first.
+    async fn archived(&self, _limit: usize) -> Result<Vec<ArchivedObservation>> {
+        Ok(Vec::new())
+    }
+
+    /// Every live observation, person, history entry and session summary,
+    /// for `transfer::to_jsonl` / `transfer::write_vault`.
+    async fn export(&self) -> Result<Vec<MemoryRecord>> {
+        Ok(Vec::new())
+    }
+
+    /// Insert or fold in an exported person: aliases and share lists are
+    /// merged, facts already known win, and counts keep the larger value.
+    async fn import_person(&self, _person: &Person) -> Result<()> {
+        Ok(())
+    }
+
+    /// Replace an observation's history with exported entries.
+    async fn import_history(
+        &self,
+        _observation_id: i64,
+        _entries: &[ObservationHistoryEntry],
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    /// Store an exported session summary unless a newer one exists. Returns
+    /// `false` if it was skipped.
+    async fn import_session_summary(&self, _summary: &SessionSummary) -> Result<bool> {
+        Ok(false)
+    }
+
+    /// Erase a person and their aliases everywhere: rows only about them are
+    /// deleted, other mentions are redacted, and search indexes refreshed.
+    async fn forget_person(&self, _name: &str) -> Result<ErasureReport> {
+        Ok(ErasureReport::default())
+    }
 }
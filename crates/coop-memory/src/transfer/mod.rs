@@ -0,0 +1,149 @@
+//! Moving memory between databases and files.
+//!
+//! Exports are JSONL (one tagged [`MemoryRecord`] per line) or a Markdown
+//! vault (see [`vault`]). Imports replay observations through
+//! [`Memory::write`], so exact duplicates and reconciled matches fold into
+//! existing rows instead of being copied. [`notes`] turns hand-written
+//! `MEMORY.md` and daily-notes files into observations.
+
+pub mod notes;
+pub mod vault;
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fmt::Write as _;
+
+use crate::traits::Memory;
+use crate::types::{
+    MemoryRecord, NewObservation, Observation, ObservationHistoryEntry, WriteOutcome,
+    min_trust_for_store,
+};
+
+/// Source recorded on observations created by an import.
+pub const IMPORT_SOURCE: &str = "import";
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ImportReport {
+    /// Observations stored as new rows.
+    pub added: usize,
+    /// Observations merged into (or replacing) an existing row.
+    pub updated: usize,
+    /// Observations already present, or judged redundant by reconciliation.
+    pub unchanged: usize,
+    pub people: usize,
+    pub history: usize,
+    pub session_summaries: usize,
+}
+
+pub fn to_jsonl(records: &[MemoryRecord]) -> Result<String> {
+    let mut out = String::new();
+    for record in records {
+        let _ = writeln!(out, "{}", serde_json::to_string(record)?);
+    }
+    Ok(out)
+}
+
+pub fn parse_jsonl(text: &str) -> Result<Vec<MemoryRecord>> {
+    text.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            serde_json::from_str(line)
+                .with_context(|| format!("invalid record on line {}", index + 1))
+        })
+        .collect()
+}
+
+/// Import exported records. Observations go through reconciliation; history
+/// is kept only for observations that end up as new rows.
+pub async fn import(memory: &dyn Memory, records: Vec<MemoryRecord>) -> Result<ImportReport> {
+    let mut observations = Vec::new();
+    let mut people = Vec::new();
+    let mut summaries = Vec::new();
+    let mut history: HashMap<i64, Vec<ObservationHistoryEntry>> = HashMap::new();
+    for record in records {
+        match record {
+            MemoryRecord::Observation(obs) => observations.push(obs),
+            MemoryRecord::Person(person) => people.push(person),
+            MemoryRecord::History(entry) => {
+                history.entry(entry.observation_id).or_default().push(entry);
+            }
+            MemoryRecord::SessionSummary(summary) => summaries.push(summary),
+        }
+    }
+
+    let mut report = ImportReport::default();
+    for obs in observations {
+        let exported_id = obs.id;
+        let outcome = write_one(memory, from_exported(obs), &mut report).await?;
+        if let WriteOutcome::Added(id) = outcome
+            && let Some(entries) = history.get(&exported_id)
+        {
+            memory.import_history(id, entries).await?;
+            report.history += entries.len();
+        }
+    }
+
+    // People after observations: writes bump mention counts, and the import
+    // keeps the larger of the two.
+    for person in &people {
+        memory.import_person(person).await?;
+        report.people += 1;
+    }
+
+    for summary in &summaries {
+        if memory.import_session_summary(summary).await? {
+            report.session_summaries += 1;
+        }
+    }
+
+    Ok(report)
+}
+
+/// Write new observations (e.g. from [`notes`]) through reconciliation.
+pub async fn import_observations(
+    memory: &dyn Memory,
+    observations: Vec<NewObservation>,
+) -> Result<ImportReport> {
+    let mut report = ImportReport::default();
+    for obs in observations {
+        write_one(memory, obs, &mut report).await?;
+    }
+    Ok(report)
+}
+
+async fn write_one(
+    memory: &dyn Memory,
+    obs: NewObservation,
+    report: &mut ImportReport,
+) -> Result<WriteOutcome> {
+    let outcome = memory.write(obs).await?;
+    match outcome {
+        WriteOutcome::Added(_) => report.added += 1,
+        WriteOutcome::Updated(_) | WriteOutcome::Deleted(_) => report.updated += 1,
+        WriteOutcome::ExactDup | WriteOutcome::Skipped => report.unchanged += 1,
+    }
+    Ok(outcome)
+}
+
+fn from_exported(obs: Observation) -> NewObservation {
+    NewObservation {
+        session_key: None,
+        min_trust: min_trust_for_store(&obs.store),
+        store: obs.store,
+        obs_type: obs.obs_type,
+        title: obs.title,
+        narrative: obs.narrative,
+        facts: obs.facts,
+        tags: obs.tags,
+        source: IMPORT_SOURCE.to_owned(),
+        related_files: obs.related_files,
+        related_people: obs.related_people,
+        token_count: None,
+        expires_at: obs.expires_at,
+        owner: obs.owner,
+        shared_with: obs.shared_with,
+        created_at: Some(obs.created_at),
+    }
+}
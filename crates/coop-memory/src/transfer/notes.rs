@@ -0,0 +1,254 @@
+//! Import hand-written memory files: a workspace's `MEMORY.md` and daily
+//! notes (`memory/YYYY-MM-DD.md`).
+//!
+//! Every heading starts an observation titled after it. Bullet points become
+//! facts and other text the narrative. Daily notes are dated by their file
+//! name and default to the `event` type.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, NaiveDate, Utc};
+use std::path::Path;
+
+use crate::types::{NewObservation, min_trust_for_store};
+
+use super::IMPORT_SOURCE;
+
+const MEMORY_FILE: &str = "MEMORY.md";
+const DAILY_NOTES_DIR: &str = "memory";
+
+/// Notes files only reach full-trust sessions, so they import as private.
+const NOTES_STORE: &str = "private";
+
+/// Whether `dir` has a `MEMORY.md` or daily-notes directory to import.
+pub fn has_notes(dir: &Path) -> bool {
+    dir.join(MEMORY_FILE).is_file() || dir.join(DAILY_NOTES_DIR).is_dir()
+}
+
+/// Observations from `MEMORY.md` and every `memory/*.md` file in `workspace`.
+pub fn workspace_notes(workspace: &Path) -> Result<Vec<NewObservation>> {
+    let mut observations = Vec::new();
+    let memory_file = workspace.join(MEMORY_FILE);
+    if memory_file.is_file() {
+        observations.extend(file_notes(&memory_file, MEMORY_FILE)?);
+    }
+
+    let daily_dir = workspace.join(DAILY_NOTES_DIR);
+    if daily_dir.is_dir() {
+        let mut files = Vec::new();
+        for entry in std::fs::read_dir(&daily_dir)? {
+            let path = entry?.path();
+            if path.extension().is_some_and(|ext| ext == "md") {
+                files.push(path);
+            }
+        }
+        files.sort();
+        for path in files {
+            let name = path.file_name().unwrap_or_default().to_string_lossy();
+            let relative = format!("{DAILY_NOTES_DIR}/{name}");
+            observations.extend(file_notes(&path, &relative)?);
+        }
+    }
+    Ok(observations)
+}
+
+/// Observations from one notes file. `relative` is recorded as the related
+/// file; a `YYYY-MM-DD` file name marks it as a daily note.
+pub fn file_notes(path: &Path, relative: &str) -> Result<Vec<NewObservation>> {
+    let text = std::fs::read_to_string(path)
+        .with_context(|| format!("failed to read {}", path.display()))?;
+    let date = path
+        .file_stem()
+        .and_then(|stem| NaiveDate::parse_from_str(&stem.to_string_lossy(), "%Y-%m-%d").ok());
+    Ok(parse_notes(&text, relative, date))
+}
+
+pub fn parse_notes(text: &str, relative: &str, date: Option<NaiveDate>) -> Vec<NewObservation> {
+    let mut sections = vec![Section::default()];
+    for line in text.lines() {
+        if let Some(heading) = heading(line) {
+            sections.push(Section {
+                heading: Some(heading.to_owned()),
+                ..Section::default()
+            });
+            continue;
+        }
+        let Some(section) = sections.last_mut() else {
+            continue;
+        };
+        if let Some(item) = bullet(line) {
+            section.tasks |= item.starts_with("[ ]") || item.starts_with("[x]");
+            let item = item
+                .trim_start_matches("[ ]")
+                .trim_start_matches("[x]")
+                .trim();
+            if !item.is_empty() {
+                section.facts.push(item.to_owned());
+            }
+        } else {
+            section.narrative.push(line.trim_end().to_owned());
+        }
+    }
+
+    let created_at = date
+        .and_then(|date| date.and_hms_opt(12, 0, 0))
+        .map(|time| DateTime::<Utc>::from_naive_utc_and_offset(time, Utc));
+    sections
+        .into_iter()
+        .filter_map(|section| section.into_observation(relative, date, created_at))
+        .collect()
+}
+
+#[derive(Default)]
+struct Section {
+    heading: Option<String>,
+    narrative: Vec<String>,
+    facts: Vec<String>,
+    tasks: bool,
+}
+
+impl Section {
+    fn into_observation(
+        self,
+        relative: &str,
+        date: Option<NaiveDate>,
+        created_at: Option<DateTime<Utc>>,
+    ) -> Option<NewObservation> {
+        let narrative = self.narrative.join("\n").trim().to_owned();
+        if narrative.is_empty() && self.facts.is_empty() {
+            return None;
+        }
+
+        let heading = self.heading.unwrap_or_else(|| match date {
+            Some(_) => "Daily notes".to_owned(),
+            None => format!("Notes from {relative}"),
+        });
+        let obs_type = infer_type(&heading, date.is_some(), self.tasks);
+        let title = match date {
+            Some(date) => format!("{date}: {heading}"),
+            None => heading,
+        };
+        let tag = if date.is_some() {
+            "daily-notes"
+        } else {
+            "memory-md"
+        };
+
+        Some(NewObservation {
+            session_key: None,
+            store: NOTES_STORE.to_owned(),
+            obs_type: obs_type.to_owned(),
+            title,
+            narrative,
+            facts: self.facts,
+            tags: vec![tag.to_owned()],
+            source: IMPORT_SOURCE.to_owned(),
+            related_files: vec![relative.to_owned()],
+            related_people: Vec::new(),
+            token_count: None,
+            expires_at: None,
+            min_trust: min_trust_for_store(NOTES_STORE),
+            owner: None,
+            shared_with: Vec::new(),
+            created_at,
+        })
+    }
+}
+
+fn heading(line: &str) -> Option<&str> {
+    let trimmed = line.trim_start_matches('#');
+    if trimmed.len() == line.len() || !trimmed.starts_with(' ') {
+        return None;
+    }
+    let heading = trimmed.trim();
+    (!heading.is_empty()).then_some(heading)
+}
+
+fn bullet(line: &str) -> Option<&str> {
+    let trimmed = line.trim_start();
+    if let Some(item) = ["- ", "* ", "+ "]
+        .iter()
+        .find_map(|marker| trimmed.strip_prefix(marker))
+    {
+        return Some(item.trim());
+    }
+    let (number, item) = trimmed.split_once(". ")?;
+    (!number.is_empty() && number.chars().all(|c| c.is_ascii_digit())).then(|| item.trim())
+}
+
+fn infer_type(heading: &str, daily: bool, tasks: bool) -> &'static str {
+    let heading = heading.to_lowercase();
+    let mentions = |words: &[&str]| words.iter().any(|word| heading.contains(word));
+    if tasks || mentions(&["todo", "task", "follow up", "open items"]) {
+        "task"
+    } else if mentions(&["decision", "decided"]) {
+        "decision"
+    } else if mentions(&["prefer", "likes", "dislikes", "style"]) {
+        "preference"
+    } else if daily {
+        "event"
+    } else {
+        "discovery"
+    }
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MEMORY_MD: &str = "\
+# Long-term memory
+
+## Preferences
+- Prefers short replies
+- Uses metric units
+
+## Decisions
+We moved the homelab to NixOS.
+- Keep backups on the NAS
+
+## Follow up
+- [ ] renew passport
+";
+
+    #[test]
+    fn memory_md_sections_become_typed_observations() {
+        let observations = parse_notes(MEMORY_MD, "MEMORY.md", None);
+        let summary = observations
+            .iter()
+            .map(|obs| (obs.title.as_str(), obs.obs_type.as_str(), obs.facts.len()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            summary,
+            vec![
+                ("Preferences", "preference", 2),
+                ("Decisions", "decision", 1),
+                ("Follow up", "task", 1),
+            ]
+        );
+        assert_eq!(observations[1].narrative, "We moved the homelab to NixOS.");
+        assert_eq!(observations[2].facts, vec!["renew passport"]);
+        assert_eq!(observations[0].store, "private");
+        assert_eq!(observations[0].related_files, vec!["MEMORY.md"]);
+        assert!(observations[0].created_at.is_none());
+    }
+
+    #[test]
+    fn daily_notes_are_dated_events() {
+        let date = NaiveDate::from_ymd_opt(2026, 3, 4);
+        let observations = parse_notes(
+            "Called the plumber.\n\n## Standup\n1. shipped export\n",
+            "memory/2026-03-04.md",
+            date,
+        );
+        assert_eq!(observations.len(), 2);
+        assert_eq!(observations[0].title, "2026-03-04: Daily notes");
+        assert_eq!(observations[0].obs_type, "event");
+        assert_eq!(observations[1].title, "2026-03-04: Standup");
+        assert_eq!(observations[1].facts, vec!["shipped export"]);
+        assert_eq!(
+            observations[1].created_at.unwrap().to_rfc3339(),
+            "2026-03-04T12:00:00+00:00"
+        );
+    }
+}
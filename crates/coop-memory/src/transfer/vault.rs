@@ -0,0 +1,323 @@
+//! Markdown vault: one file per observation, person and session summary.
+//!
+//! ```text
+//! observations/00042-deploys-use-canaries.md
+//! people/alice.md
+//! sessions/agent-main.md
+//! ```
+//!
+//! Each file starts with `---` frontmatter whose values are JSON (so it is
+//! also valid YAML). Observation titles, narratives and facts live in the
+//! body so they can be edited in any Markdown editor; people and session
+//! summaries are read back from the frontmatter alone.
+
+use anyhow::{Context, Result, bail};
+use serde_json::{Map, Value};
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+
+use crate::types::{MemoryRecord, Observation, ObservationHistoryEntry, Person, SessionSummary};
+
+const OBSERVATIONS_DIR: &str = "observations";
+const PEOPLE_DIR: &str = "people";
+const SESSIONS_DIR: &str = "sessions";
+const FACTS_HEADING: &str = "## Facts";
+
+/// Whether `dir` looks like a vault written by [`write_vault`].
+pub fn is_vault(dir: &Path) -> bool {
+    [OBSERVATIONS_DIR, PEOPLE_DIR, SESSIONS_DIR]
+        .iter()
+        .any(|sub| dir.join(sub).is_dir())
+}
+
+/// Write `records` into `dir`, which must be empty or not exist yet.
+pub fn write_vault(dir: &Path, records: &[MemoryRecord]) -> Result<()> {
+    if dir.exists() && dir.read_dir()?.next().is_some() {
+        bail!("{} is not empty", dir.display());
+    }
+    for sub in [OBSERVATIONS_DIR, PEOPLE_DIR, SESSIONS_DIR] {
+        std::fs::create_dir_all(dir.join(sub))?;
+    }
+
+    let mut history: Vec<&ObservationHistoryEntry> = Vec::new();
+    for record in records {
+        if let MemoryRecord::History(entry) = record {
+            history.push(entry);
+        }
+    }
+
+    for record in records {
+        let (path, contents) = match record {
+            MemoryRecord::Observation(obs) => {
+                let entries = history
+                    .iter()
+                    .filter(|entry| entry.observation_id == obs.id)
+                    .copied()
+                    .collect::<Vec<_>>();
+                (
+                    dir.join(OBSERVATIONS_DIR).join(format!(
+                        "{:05}-{}.md",
+                        obs.id,
+                        slug(&obs.title)
+                    )),
+                    render_observation(obs, &entries)?,
+                )
+            }
+            MemoryRecord::Person(person) => (
+                dir.join(PEOPLE_DIR)
+                    .join(format!("{}.md", slug(&person.name))),
+                render_person(person)?,
+            ),
+            MemoryRecord::SessionSummary(summary) => (
+                dir.join(SESSIONS_DIR)
+                    .join(format!("{}.md", slug(&summary.session_key))),
+                render_session_summary(summary)?,
+            ),
+            MemoryRecord::History(_) => continue,
+        };
+        let path = unique_path(path);
+        std::fs::write(&path, contents)
+            .with_context(|| format!("failed to write {}", path.display()))?;
+    }
+    Ok(())
+}
+
+/// Read a vault back into records, in the order [`super::import`] expects.
+pub fn read_vault(dir: &Path) -> Result<Vec<MemoryRecord>> {
+    let mut records = Vec::new();
+    for path in markdown_files(&dir.join(OBSERVATIONS_DIR))? {
+        let text = std::fs::read_to_string(&path)?;
+        let (obs, history) = parse_observation(&text)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        records.push(MemoryRecord::Observation(obs));
+        records.extend(history.into_iter().map(MemoryRecord::History));
+    }
+    for path in markdown_files(&dir.join(PEOPLE_DIR))? {
+        let text = std::fs::read_to_string(&path)?;
+        let (frontmatter, _) = split_frontmatter(&text)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        let person: Person = serde_json::from_value(Value::Object(frontmatter))
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        records.push(MemoryRecord::Person(person));
+    }
+    for path in markdown_files(&dir.join(SESSIONS_DIR))? {
+        let text = std::fs::read_to_string(&path)?;
+        let (frontmatter, _) = split_frontmatter(&text)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        let summary: SessionSummary = serde_json::from_value(Value::Object(frontmatter))
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        records.push(MemoryRecord::SessionSummary(summary));
+    }
+    Ok(records)
+}
+
+fn markdown_files(dir: &Path) -> Result<Vec<PathBuf>> {
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+    let mut files = Vec::new();
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.extension().is_some_and(|ext| ext == "md") {
+            files.push(path);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+fn render_observation(obs: &Observation, history: &[&ObservationHistoryEntry]) -> Result<String> {
+    let mut frontmatter = to_object(obs)?;
+    for body_field in ["title", "narrative", "facts"] {
+        frontmatter.remove(body_field);
+    }
+    if !history.is_empty() {
+        frontmatter.insert("history".to_owned(), serde_json::to_value(history)?);
+    }
+
+    let mut out = render_frontmatter(&frontmatter)?;
+    let _ = writeln!(out, "# {}", obs.title);
+    if !obs.narrative.trim().is_empty() {
+        let _ = write!(out, "\n{}\n", obs.narrative.trim());
+    }
+    if !obs.facts.is_empty() {
+        let _ = write!(out, "\n{FACTS_HEADING}\n\n");
+        for fact in &obs.facts {
+            let _ = writeln!(out, "- {fact}");
+        }
+    }
+    Ok(out)
+}
+
+fn parse_observation(text: &str) -> Result<(Observation, Vec<ObservationHistoryEntry>)> {
+    let (mut frontmatter, body) = split_frontmatter(text)?;
+    let history = match frontmatter.remove("history") {
+        Some(history) => serde_json::from_value(history)?,
+        None => Vec::new(),
+    };
+
+    let mut title = None;
+    let mut narrative = Vec::new();
+    let mut facts = Vec::new();
+    let mut in_facts = false;
+    for line in body.lines() {
+        if title.is_none()
+            && let Some(heading) = line.strip_prefix("# ")
+        {
+            title = Some(heading.trim().to_owned());
+        } else if line.trim() == FACTS_HEADING {
+            in_facts = true;
+        } else if in_facts {
+            if let Some(fact) = line.trim().strip_prefix("- ") {
+                facts.push(fact.trim().to_owned());
+            }
+        } else if title.is_some() {
+            narrative.push(line);
+        }
+    }
+    let Some(title) = title else {
+        bail!("missing '# title' line");
+    };
+
+    frontmatter.insert("title".to_owned(), Value::String(title));
+    frontmatter.insert(
+        "narrative".to_owned(),
+        Value::String(narrative.join("\n").trim().to_owned()),
+    );
+    frontmatter.insert("facts".to_owned(), serde_json::to_value(facts)?);
+    let obs: Observation = serde_json::from_value(Value::Object(frontmatter))?;
+    Ok((obs, history))
+}
+
+fn render_person(person: &Person) -> Result<String> {
+    let mut out = render_frontmatter(&to_object(person)?)?;
+    let _ = writeln!(out, "# {}", person.name);
+    if !person.aliases.is_empty() {
+        let _ = write!(out, "\nAlso known as {}.\n", person.aliases.join(", "));
+    }
+    Ok(out)
+}
+
+fn render_session_summary(summary: &SessionSummary) -> Result<String> {
+    let mut out = render_frontmatter(&to_object(summary)?)?;
+    let _ = writeln!(out, "# {}", summary.session_key);
+    if !summary.request.is_empty() {
+        let _ = write!(out, "\nRequest: {}\n", summary.request);
+    }
+    if !summary.outcome.is_empty() {
+        let _ = write!(out, "\nOutcome: {}\n", summary.outcome);
+    }
+    for (heading, items) in [
+        ("Decisions", &summary.decisions),
+        ("Open items", &summary.open_items),
+    ] {
+        if !items.is_empty() {
+            let _ = write!(out, "\n## {heading}\n\n");
+            for item in items {
+                let _ = writeln!(out, "- {item}");
+            }
+        }
+    }
+    Ok(out)
+}
+
+fn to_object(value: &impl serde::Serialize) -> Result<Map<String, Value>> {
+    match serde_json::to_value(value)? {
+        Value::Object(map) => Ok(map),
+        _ => bail!("expected an object"),
+    }
+}
+
+fn render_frontmatter(fields: &Map<String, Value>) -> Result<String> {
+    let mut out = "---\n".to_owned();
+    for (key, value) in fields {
+        let _ = writeln!(out, "{key}: {}", serde_json::to_string(value)?);
+    }
+    out.push_str("---\n\n");
+    Ok(out)
+}
+
+/// Split `---` frontmatter from the body. Values that aren't JSON (e.g. a
+/// hand-edited `store: shared`) are read as plain strings.
+fn split_frontmatter(text: &str) -> Result<(Map<String, Value>, &str)> {
+    let Some(rest) = text.strip_prefix("---\n") else {
+        bail!("missing frontmatter");
+    };
+    let Some((header, body)) = rest.split_once("\n---\n") else {
+        bail!("unterminated frontmatter");
+    };
+
+    let mut fields = Map::new();
+    for line in header.lines().filter(|line| !line.trim().is_empty()) {
+        let Some((key, raw)) = line.split_once(':') else {
+            bail!("invalid frontmatter line '{line}'");
+        };
+        let raw = raw.trim();
+        let value = serde_json::from_str(raw).unwrap_or_else(|_| Value::String(raw.to_owned()));
+        fields.insert(key.trim().to_owned(), value);
+    }
+    Ok((fields, body))
+}
+
+/// Names that only differ in case or punctuation share a slug; number the
+/// later ones.
+fn unique_path(path: PathBuf) -> PathBuf {
+    if !path.exists() {
+        return path;
+    }
+    let stem = path
+        .file_stem()
+        .unwrap_or_default()
+        .to_string_lossy()
+        .into_owned();
+    let mut n = 2;
+    loop {
+        let candidate = path.with_file_name(format!("{stem}-{n}.md"));
+        if !candidate.exists() {
+            return candidate;
+        }
+        n += 1;
+    }
+}
+
+fn slug(text: &str) -> String {
+    let mut out = String::new();
+    for c in text.chars().flat_map(char::to_lowercase) {
+        if c.is_alphanumeric() {
+            out.push(c);
+        } else if !out.is_empty() && !out.ends_with('-') {
+            out.push('-');
+        }
+        if out.len() >= 60 {
+            break;
+        }
+    }
+    let out = out.trim_end_matches('-');
+    if out.is_empty() {
+        "untitled".to_owned()
+    } else {
+        out.to_owned()
+    }
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hand_edited_frontmatter_reads_plain_strings() {
+        let (fields, body) =
+            split_frontmatter("---\nstore: shared\nmention_count: 3\n---\n\n# Title\n").unwrap();
+        assert_eq!(fields["store"], "shared");
+        assert_eq!(fields["mention_count"], 3);
+        assert_eq!(body, "\n# Title\n");
+    }
+
+    #[test]
+    fn slugs_are_filename_safe() {
+        assert_eq!(slug("Deploys use canaries!"), "deploys-use-canaries");
+        assert_eq!(slug("dm:signal:alice"), "dm-signal-alice");
+        assert_eq!(slug("???"), "untitled");
+    }
+}
@@ -1,5 +1,6 @@
 use chrono::{DateTime, Utc};
-use coop_core::TrustLevel;
+use coop_core::redaction::{self, RedactionMode, SecretKind, SecretsBlocked};
+use coop_core::{SessionKind, TrustLevel};
 use serde::{Deserialize, Serialize};
 
 /// Query options for memory search.
@@ -13,6 +14,9 @@ pub struct MemoryQuery {
     pub before: Option<DateTime<Utc>>,
     pub limit: usize,
     pub max_tokens: Option<usize>,
+    /// Principal doing the reading (see [`memory_principal`]). `None` skips
+    /// the ownership filter.
+    pub viewer: Option<String>,
 }
 
 /// Layer 1: compact search result.
@@ -44,6 +48,13 @@ pub struct Observation {
     pub created_at: DateTime<Utc>,
     pub token_count: u32,
     pub mention_count: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub owner: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub shared_with: Vec<String>,
+    /// When the observation stops being returned. `None` keeps it forever.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<DateTime<Utc>>,
 }
 
 /// New observation to be written to memory.
@@ -62,6 +73,13 @@ pub struct NewObservation {
     pub token_count: Option<u32>,
     pub expires_at: Option<DateTime<Utc>>,
     pub min_trust: TrustLevel,
+    /// Principal the observation belongs to (see [`memory_principal`]).
+    pub owner: Option<String>,
+    /// Other principals allowed to see a `private` observation.
+    pub shared_with: Vec<String>,
+    /// When the observation was first made. `None` means now; imports set
+    /// it to keep the original date.
+    pub created_at: Option<DateTime<Utc>>,
 }
 
 impl NewObservation {
@@ -80,8 +98,23 @@ impl NewObservation {
             token_count: None,
             expires_at: None,
             min_trust: TrustLevel::Inner,
+            owner: None,
+            shared_with: Vec::new(),
+            created_at: None,
         }
     }
+
+    /// Scan the title, narrative and facts for secrets and apply `mode`.
+    /// `site` names the writer in the log line.
+    pub fn redact_secrets(
+        &mut self,
+        mode: RedactionMode,
+        site: &str,
+    ) -> Result<Vec<SecretKind>, SecretsBlocked> {
+        let mut fields = vec![&mut self.title, &mut self.narrative];
+        fields.extend(self.facts.iter_mut());
+        redaction::redact_fields(mode, site, fields)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -179,6 +212,41 @@ pub struct ObservationHistoryEntry {
     pub created_at: DateTime<Utc>,
 }
 
+/// Manual correction to an observation. `None` fields are left unchanged.
+#[derive(Debug, Clone, Default)]
+pub struct ObservationEdit {
+    pub title: Option<String>,
+    pub narrative: Option<String>,
+    pub facts: Option<Vec<String>>,
+    pub tags: Option<Vec<String>>,
+    pub obs_type: Option<String>,
+    pub store: Option<String>,
+}
+
+impl ObservationEdit {
+    pub fn is_empty(&self) -> bool {
+        self.title.is_none()
+            && self.narrative.is_none()
+            && self.facts.is_none()
+            && self.tags.is_none()
+            && self.obs_type.is_none()
+            && self.store.is_none()
+    }
+}
+
+/// Observation moved out of the live table, by maintenance or by hand.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchivedObservation {
+    /// The observation's id before it was archived.
+    pub id: i64,
+    pub title: String,
+    pub obs_type: String,
+    pub store: String,
+    pub created_at: DateTime<Utc>,
+    pub archived_at: DateTime<Utc>,
+    pub archive_reason: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Person {
     pub name: String,
@@ -187,6 +255,56 @@ pub struct Person {
     pub aliases: Vec<String>,
     pub last_mentioned: Option<DateTime<Utc>>,
     pub mention_count: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub owner: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub shared_with: Vec<String>,
+}
+
+/// What [`Memory::forget_person`](crate::Memory::forget_person) removed.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ErasureReport {
+    /// The name and every alias that was erased.
+    pub names: Vec<String>,
+    pub people_deleted: usize,
+    /// Other people whose facts mentioned them.
+    pub people_redacted: usize,
+    pub observations_deleted: usize,
+    pub observations_redacted: usize,
+    pub archived_deleted: usize,
+    pub archived_redacted: usize,
+    pub history_redacted: usize,
+    pub session_summaries_redacted: usize,
+    pub session_messages_redacted: usize,
+}
+
+impl ErasureReport {
+    /// Whether nothing referred to the person.
+    pub fn is_empty(&self) -> bool {
+        [
+            self.people_deleted,
+            self.people_redacted,
+            self.observations_deleted,
+            self.observations_redacted,
+            self.archived_deleted,
+            self.archived_redacted,
+            self.history_redacted,
+            self.session_summaries_redacted,
+            self.session_messages_redacted,
+        ]
+        .iter()
+        .all(|count| *count == 0)
+    }
+}
+
+/// One line of a memory export (see `coop_memory::transfer`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum MemoryRecord {
+    Observation(Observation),
+    Person(Person),
+    History(ObservationHistoryEntry),
+    SessionSummary(SessionSummary),
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -288,6 +406,39 @@ pub fn accessible_stores(trust: TrustLevel) -> Vec<String> {
     .collect()
 }
 
+pub fn user_principal(name: &str) -> String {
+    format!("user:{name}")
+}
+
+pub fn group_principal(id: &str) -> String {
+    format!("group:{id}")
+}
+
+/// Memory principal for a turn, mirroring `WorkspacePrincipal`: the group in
+/// group chats, otherwise the mapped user. Unmapped turns have no principal.
+pub fn memory_principal(kind: &SessionKind, user_name: Option<&str>) -> Option<String> {
+    match kind {
+        SessionKind::Group(id) => Some(group_principal(id)),
+        _ => user_name.map(user_principal),
+    }
+}
+
+/// Whether `viewer` may see a row. Ownership only narrows the `private`
+/// store: a private row owned by someone else is hidden unless it is shared
+/// with the viewer. Unowned rows and the other stores stay visible by trust
+/// alone.
+pub fn visible_to(
+    store: &str,
+    owner: Option<&str>,
+    shared_with: &[String],
+    viewer: Option<&str>,
+) -> bool {
+    let (Some(viewer), Some(owner)) = (viewer, owner) else {
+        return true;
+    };
+    store != "private" || owner == viewer || shared_with.iter().any(|p| p == viewer)
+}
+
 pub fn trust_to_str(trust: TrustLevel) -> &'static str {
     match trust {
         TrustLevel::Owner => "owner",
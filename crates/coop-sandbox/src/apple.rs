@@ -343,6 +343,7 @@ async fn exec_long_lived(
             exit_code: -1,
             stdout: String::new(),
             stderr: format!("command timed out after {}s", timeout.as_secs()),
+            enforced: SandboxCapabilities::default(),
         }),
         Ok(Err(e)) => anyhow::bail!("failed to exec in container {}: {e}", container_name),
         Ok(Ok(output)) => {
@@ -352,6 +353,7 @@ async fn exec_long_lived(
                 exit_code,
                 stdout: String::from_utf8_lossy(&output.stdout).into_owned(),
                 stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
+                enforced: SandboxCapabilities::default(),
             })
         }
     }
@@ -410,6 +412,7 @@ async fn exec_ephemeral(
             exit_code: -1,
             stdout: String::new(),
             stderr: format!("command timed out after {}s", timeout.as_secs()),
+            enforced: SandboxCapabilities::default(),
         }),
         Ok(Err(e)) => anyhow::bail!("failed to spawn apple/container: {e}"),
         Ok(Ok(output)) => {
@@ -419,6 +422,7 @@ async fn exec_ephemeral(
                 exit_code,
                 stdout: String::from_utf8_lossy(&output.stdout).into_owned(),
                 stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
+                enforced: SandboxCapabilities::default(),
             })
         }
     }
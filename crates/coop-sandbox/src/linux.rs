@@ -1,5 +1,8 @@
+mod confine;
+
 use crate::policy::{ExecOutput, NetworkMode, SandboxCapabilities, SandboxInfo, SandboxPolicy};
 use anyhow::Result;
+use confine::Confinement;
 use std::time::Duration;
 use tracing::{debug, info, warn};
 
@@ -52,27 +55,38 @@ pub fn probe() -> Result<SandboxInfo> {
     })
 }
 
+#[allow(unsafe_code)]
 fn check_user_namespaces() -> bool {
+    use std::os::unix::process::CommandExt;
+
     if let Ok(content) = std::fs::read_to_string("/proc/sys/kernel/unprivileged_userns_clone")
         && content.trim() == "0"
     {
         return false;
     }
 
-    std::process::Command::new("unshare")
-        .args(["--user", "true"])
-        .stdout(std::process::Stdio::null())
-        .stderr(std::process::Stdio::null())
-        .status()
-        .is_ok_and(|s| s.success())
+    let mut cmd = std::process::Command::new("true");
+    cmd.stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null());
+    // SAFETY: unshare is async-signal-safe.
+    unsafe {
+        cmd.pre_exec(|| {
+            if libc::unshare(libc::CLONE_NEWUSER) == 0 {
+                Ok(())
+            } else {
+                Err(std::io::Error::last_os_error())
+            }
+        });
+    }
+    cmd.status().is_ok_and(|s| s.success())
 }
 
 fn check_landlock() -> bool {
-    std::path::Path::new("/sys/kernel/security/landlock").exists()
+    confine::landlock_abi() > 0
 }
 
 fn check_seccomp() -> bool {
-    std::fs::read_to_string("/proc/self/status").is_ok_and(|content| content.contains("Seccomp:"))
+    confine::seccomp_arch().is_some() && confine::seccomp_supported()
 }
 
 fn check_pasta() -> bool {
@@ -100,8 +114,11 @@ fn check_cgroups_v2() -> bool {
 
 /// Execute a command inside a Linux sandbox.
 ///
-/// Uses user/mount/network/PID namespaces for isolation. The workspace
-/// directory is mounted read-write; host tooling paths are read-only.
+/// Uses user/mount/network/PID namespaces for isolation, Landlock to keep
+/// writes inside the workspace (host tooling paths are read-only), and a
+/// seccomp filter that denies privileged syscalls. All of it is applied in
+/// the forked child before `exec`; [`ExecOutput::enforced`] reports which
+/// layers actually took effect.
 pub async fn exec(
     policy: &SandboxPolicy,
     command: &str,
@@ -133,22 +150,12 @@ pub async fn exec(
         return exec_internet_only(policy, command, timeout).await;
     }
 
-    let mut cmd = tokio::process::Command::new("unshare");
-
-    cmd.arg("--user");
-    cmd.arg("--mount");
-    cmd.arg("--pid");
-    cmd.arg("--fork");
-
-    if effective_network == NetworkMode::None {
-        cmd.arg("--net");
-    }
-
-    cmd.arg("--map-root-user");
+    let (confinement, report) =
+        Confinement::prepare(&policy.workspace, effective_network == NetworkMode::None)?;
 
     let setup_script = build_sandbox_script(policy, command);
-    cmd.args(["sh", "-c", &setup_script]);
-
+    let mut cmd = tokio::process::Command::new("sh");
+    cmd.args(["-c", &setup_script]);
     cmd.current_dir(&policy.workspace);
     cmd.env("HOME", policy.workspace.display().to_string());
     cmd.env(
@@ -156,8 +163,27 @@ pub async fn exec(
         "/usr/local/sbin:/usr/local/bin:/usr/sbin:/usr/bin:/sbin:/bin",
     );
     cmd.env("TERM", "xterm-256color");
+    cmd.stdout(std::process::Stdio::piped());
+    cmd.stderr(std::process::Stdio::piped());
+    // The spawned process supervises the sandbox; killing it on timeout
+    // takes down the whole PID namespace.
+    cmd.kill_on_drop(true);
+    confinement.install(&mut cmd);
+
+    let deadline = tokio::time::Instant::now() + timeout;
+    let child = cmd.spawn();
+    // Dropping the command closes the parent's copy of the status pipe.
+    drop(cmd);
+    let child = child.map_err(|e| anyhow::anyhow!("failed to spawn sandbox process: {e}"))?;
+    let enforced = report.read(deadline).await;
+    debug!(
+        landlock = enforced.landlock,
+        seccomp = enforced.seccomp,
+        network_namespace = enforced.network_namespaces,
+        "sandbox confinement applied"
+    );
 
-    let result = tokio::time::timeout(timeout, cmd.output()).await;
+    let result = tokio::time::timeout_at(deadline, child.wait_with_output()).await;
 
     match result {
         Err(_) => {
@@ -166,10 +192,11 @@ pub async fn exec(
                 exit_code: -1,
                 stdout: String::new(),
                 stderr: format!("command timed out after {}s", timeout.as_secs()),
+                enforced,
             })
         }
         Ok(Err(e)) => {
-            anyhow::bail!("failed to spawn sandbox process: {e}");
+            anyhow::bail!("failed to wait for sandbox process: {e}");
         }
         Ok(Ok(output)) => {
             let exit_code = output.status.code().unwrap_or(-1);
@@ -187,6 +214,7 @@ pub async fn exec(
                 exit_code,
                 stdout,
                 stderr,
+                enforced,
             })
         }
     }
@@ -198,6 +226,9 @@ pub async fn exec(
 /// Inside that namespace we set iptables rules to block private/local ranges,
 /// then exec `unshare` for user/mount/pid isolation. The inner user namespace
 /// cannot remove the iptables rules because it doesn't own the net namespace.
+///
+/// Landlock and seccomp are not applied on this path: the confined shell is
+/// started by `pasta` rather than by this process.
 async fn exec_internet_only(
     policy: &SandboxPolicy,
     command: &str,
@@ -208,7 +239,12 @@ async fn exec_internet_only(
         "sandboxed exec: internet-only via pasta"
     );
 
-    let inner_script = build_sandbox_script(policy, command);
+    warn!("landlock and seccomp are not applied in internet-only network mode");
+
+    let inner_script = format!(
+        "mount -t proc proc /proc 2>/dev/null\n{}",
+        build_sandbox_script(policy, command)
+    );
     let escaped_inner = inner_script.replace('\'', "'\\''");
 
     // Outer script runs inside pasta's namespace (has CAP_NET_ADMIN).
@@ -236,6 +272,12 @@ exec unshare --user --mount --pid --fork --map-root-user sh -c '{escaped_inner}'
     );
     cmd.env("TERM", "xterm-256color");
 
+    let enforced = SandboxCapabilities {
+        user_namespaces: true,
+        network_namespaces: true,
+        internet_only: true,
+        ..SandboxCapabilities::default()
+    };
     let result = tokio::time::timeout(timeout, cmd.output()).await;
 
     match result {
@@ -245,6 +287,7 @@ exec unshare --user --mount --pid --fork --map-root-user sh -c '{escaped_inner}'
                 exit_code: -1,
                 stdout: String::new(),
                 stderr: format!("command timed out after {}s", timeout.as_secs()),
+                enforced,
             })
         }
         Ok(Err(e)) => {
@@ -266,6 +309,7 @@ exec unshare --user --mount --pid --fork --map-root-user sh -c '{escaped_inner}'
                 exit_code,
                 stdout,
                 stderr,
+                enforced,
             })
         }
     }
@@ -287,94 +331,13 @@ fn build_sandbox_script(policy: &SandboxPolicy, command: &str) -> String {
         let _ = writeln!(script, "ulimit -u {} 2>/dev/null", policy.pids_limit);
     }
 
-    script.push_str("mount -t proc proc /proc 2>/dev/null\n");
     let _ = writeln!(script, "cd '{workspace}'");
     let _ = writeln!(script, "export HOME='{workspace}'");
     script.push_str("export PATH='/usr/local/sbin:/usr/local/bin:/usr/sbin:/usr/bin:/sbin:/bin'\n");
     script.push_str("export TERM='xterm-256color'\n");
 
-    if check_landlock() {
-        append_landlock_script(&mut script, &workspace.to_string());
-    }
-
     let escaped = command.replace('\'', "'\\''");
     let _ = writeln!(script, "exec sh -c '{escaped}'");
 
     script
 }
-
-fn append_landlock_script(script: &mut String, workspace: &str) {
-    use std::fmt::Write;
-    let _ = writeln!(
-        script,
-        r#"
-cat > /tmp/landlock_restrict.c << 'LANDLOCK_EOF'
-#include <linux/landlock.h>
-#include <sys/syscall.h>
-#include <sys/prctl.h>
-#include <unistd.h>
-#include <fcntl.h>
-#include <stdio.h>
-#include <stdlib.h>
-
-int main() {{
-    struct landlock_ruleset_attr ruleset_attr = {{
-        .handled_access_fs = LANDLOCK_ACCESS_FS_EXECUTE |
-                           LANDLOCK_ACCESS_FS_WRITE_FILE |
-                           LANDLOCK_ACCESS_FS_READ_FILE |
-                           LANDLOCK_ACCESS_FS_READ_DIR |
-                           LANDLOCK_ACCESS_FS_REMOVE_DIR |
-                           LANDLOCK_ACCESS_FS_REMOVE_FILE |
-                           LANDLOCK_ACCESS_FS_MAKE_CHAR |
-                           LANDLOCK_ACCESS_FS_MAKE_DIR |
-                           LANDLOCK_ACCESS_FS_MAKE_REG |
-                           LANDLOCK_ACCESS_FS_MAKE_SOCK |
-                           LANDLOCK_ACCESS_FS_MAKE_FIFO |
-                           LANDLOCK_ACCESS_FS_MAKE_BLOCK |
-                           LANDLOCK_ACCESS_FS_MAKE_SYM,
-    }};
-    int ruleset_fd = syscall(SYS_landlock_create_ruleset, &ruleset_attr, sizeof(ruleset_attr), 0);
-    if (ruleset_fd < 0) {{ perror("landlock_create_ruleset"); exit(1); }}
-    struct landlock_path_beneath_attr path_beneath = {{
-        .allowed_access = LANDLOCK_ACCESS_FS_EXECUTE | LANDLOCK_ACCESS_FS_WRITE_FILE |
-                        LANDLOCK_ACCESS_FS_READ_FILE | LANDLOCK_ACCESS_FS_READ_DIR |
-                        LANDLOCK_ACCESS_FS_REMOVE_DIR | LANDLOCK_ACCESS_FS_REMOVE_FILE |
-                        LANDLOCK_ACCESS_FS_MAKE_CHAR | LANDLOCK_ACCESS_FS_MAKE_DIR |
-                        LANDLOCK_ACCESS_FS_MAKE_REG | LANDLOCK_ACCESS_FS_MAKE_SOCK |
-                        LANDLOCK_ACCESS_FS_MAKE_FIFO | LANDLOCK_ACCESS_FS_MAKE_BLOCK |
-                        LANDLOCK_ACCESS_FS_MAKE_SYM,
-        .parent_fd = open("{workspace}", O_PATH | O_CLOEXEC),
-    }};
-    if (path_beneath.parent_fd < 0) {{ perror("open workspace"); exit(1); }}
-    if (syscall(SYS_landlock_add_rule, ruleset_fd, LANDLOCK_RULE_PATH_BENEATH, &path_beneath, 0) < 0) {{
-        perror("landlock_add_rule workspace"); exit(1);
-    }}
-    close(path_beneath.parent_fd);
-    const char* readonly_paths[] = {{
-        "/usr", "/lib", "/lib64", "/bin", "/sbin", "/etc/ld.so.cache", "/etc/passwd",
-        "/etc/group", "/etc/nsswitch.conf", "/etc/resolv.conf", "/dev/null", "/dev/zero",
-        "/dev/urandom", "/proc", "/sys/fs/cgroup", "/tmp"
-    }};
-    for (int i = 0; i < sizeof(readonly_paths) / sizeof(readonly_paths[0]); i++) {{
-        struct landlock_path_beneath_attr ro_path = {{
-            .allowed_access = LANDLOCK_ACCESS_FS_EXECUTE | LANDLOCK_ACCESS_FS_READ_FILE |
-                            LANDLOCK_ACCESS_FS_READ_DIR,
-            .parent_fd = open(readonly_paths[i], O_PATH | O_CLOEXEC),
-        }};
-        if (ro_path.parent_fd >= 0) {{
-            syscall(SYS_landlock_add_rule, ruleset_fd, LANDLOCK_RULE_PATH_BENEATH, &ro_path, 0);
-            close(ro_path.parent_fd);
-        }}
-    }}
-    if (prctl(PR_SET_NO_NEW_PRIVS, 1, 0, 0, 0)) {{ perror("prctl"); exit(1); }}
-    if (syscall(SYS_landlock_restrict_self, ruleset_fd, 0) < 0) {{ perror("landlock_restrict_self"); exit(1); }}
-    close(ruleset_fd);
-    return 0;
-}}
-LANDLOCK_EOF
-
-gcc -o /tmp/landlock_restrict /tmp/landlock_restrict.c 2>/dev/null && /tmp/landlock_restrict
-rm -f /tmp/landlock_restrict.c /tmp/landlock_restrict 2>/dev/null
-"#
-    );
-}
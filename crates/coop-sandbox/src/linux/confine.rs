@@ -0,0 +1,426 @@
+//! In-process confinement for sandboxed commands.
+//!
+//! Everything that needs allocation (Landlock ruleset, seccomp program,
+//! uid/gid maps) is prepared in the parent. The `pre_exec` hook then only
+//! issues raw syscalls: unshare namespaces, fork into the new PID namespace,
+//! restrict the filesystem with Landlock, install the seccomp filter, and
+//! report what was enforced over a pipe before `exec`.
+
+#![allow(unsafe_code)]
+
+use anyhow::{Context, Result};
+use landlock::{
+    ABI, Access, AccessFs, Ruleset, RulesetAttr, RulesetCreatedAttr, path_beneath_rules,
+};
+use seccompiler::{BpfProgram, SeccompAction, SeccompFilter, TargetArch};
+use std::collections::BTreeMap;
+use std::io::{self, Read};
+use std::os::fd::{AsRawFd, FromRawFd, OwnedFd, RawFd};
+use std::path::Path;
+use tokio::time::Instant;
+use tracing::warn;
+
+use crate::policy::SandboxCapabilities;
+
+/// Host paths the sandboxed command may read and execute from.
+const READ_ONLY_PATHS: &[&str] = &[
+    "/usr",
+    "/lib",
+    "/lib64",
+    "/bin",
+    "/sbin",
+    "/etc/ld.so.cache",
+    "/etc/passwd",
+    "/etc/group",
+    "/etc/nsswitch.conf",
+    "/etc/resolv.conf",
+    "/etc/hosts",
+    "/etc/ssl",
+    "/dev/zero",
+    "/dev/urandom",
+    "/sys/fs/cgroup",
+    "/tmp",
+];
+
+/// Host paths the sandboxed command may write to besides the workspace.
+const WRITABLE_DEVICES: &[&str] = &["/dev/null"];
+
+/// Syscalls that fail with `EPERM` inside the sandbox: kernel/module
+/// management, tracing other processes, re-entering or creating namespaces,
+/// mounting, and host clock changes.
+const DENIED_SYSCALLS: &[i64] = &[
+    libc::SYS_ptrace,
+    libc::SYS_process_vm_readv,
+    libc::SYS_process_vm_writev,
+    libc::SYS_kexec_load,
+    libc::SYS_init_module,
+    libc::SYS_finit_module,
+    libc::SYS_delete_module,
+    libc::SYS_mount,
+    libc::SYS_umount2,
+    libc::SYS_pivot_root,
+    libc::SYS_fsopen,
+    libc::SYS_fsmount,
+    libc::SYS_move_mount,
+    libc::SYS_open_tree,
+    libc::SYS_mount_setattr,
+    libc::SYS_swapon,
+    libc::SYS_swapoff,
+    libc::SYS_reboot,
+    libc::SYS_bpf,
+    libc::SYS_perf_event_open,
+    libc::SYS_keyctl,
+    libc::SYS_add_key,
+    libc::SYS_request_key,
+    libc::SYS_userfaultfd,
+    libc::SYS_unshare,
+    libc::SYS_setns,
+    libc::SYS_open_by_handle_at,
+    libc::SYS_name_to_handle_at,
+    libc::SYS_acct,
+    libc::SYS_quotactl,
+    libc::SYS_settimeofday,
+    libc::SYS_clock_settime,
+    libc::SYS_adjtimex,
+    libc::SYS_syslog,
+];
+
+const LANDLOCK_CREATE_RULESET_VERSION: libc::c_uint = 1;
+const LANDLOCK_RULE_PATH_BENEATH: libc::c_int = 1;
+
+const ENFORCED_NAMESPACES: u8 = 1 << 0;
+const ENFORCED_LANDLOCK: u8 = 1 << 1;
+const ENFORCED_SECCOMP: u8 = 1 << 2;
+
+#[repr(C, packed)]
+struct LandlockPathBeneathAttr {
+    allowed_access: u64,
+    parent_fd: i32,
+}
+
+/// Highest Landlock ABI version supported by the running kernel, or 0.
+pub(super) fn landlock_abi() -> i32 {
+    // SAFETY: querying the ABI version takes no pointers.
+    let version = unsafe {
+        libc::syscall(
+            libc::SYS_landlock_create_ruleset,
+            std::ptr::null::<libc::c_void>(),
+            0usize,
+            LANDLOCK_CREATE_RULESET_VERSION,
+        )
+    };
+    i32::try_from(version).unwrap_or(0).max(0)
+}
+
+/// Architecture to build the seccomp filter for, if seccompiler supports it.
+pub(super) fn seccomp_arch() -> Option<TargetArch> {
+    TargetArch::try_from(std::env::consts::ARCH).ok()
+}
+
+/// Whether the kernel was built with seccomp support.
+pub(super) fn seccomp_supported() -> bool {
+    // SAFETY: PR_GET_SECCOMP takes no pointers.
+    unsafe { libc::prctl(libc::PR_GET_SECCOMP, 0, 0, 0, 0) >= 0 }
+}
+
+/// Confinement prepared in the parent and applied in the child.
+pub(super) struct Confinement {
+    unshare_flags: libc::c_int,
+    uid_map: Vec<u8>,
+    gid_map: Vec<u8>,
+    landlock: Option<LandlockRuleset>,
+    seccomp: Option<BpfProgram>,
+    status_tx: OwnedFd,
+}
+
+struct LandlockRuleset {
+    fd: OwnedFd,
+    /// Access granted to the PID namespace's own `/proc`, which is mounted
+    /// after the ruleset is built and so needs its own rule.
+    proc_access: u64,
+}
+
+/// Reads the enforcement report written by the child just before `exec`.
+pub(super) struct EnforcementReport {
+    status_rx: OwnedFd,
+    isolate_network: bool,
+}
+
+impl Confinement {
+    pub(super) fn prepare(
+        workspace: &Path,
+        isolate_network: bool,
+    ) -> Result<(Self, EnforcementReport)> {
+        let mut unshare_flags = libc::CLONE_NEWUSER | libc::CLONE_NEWNS | libc::CLONE_NEWPID;
+        if isolate_network {
+            unshare_flags |= libc::CLONE_NEWNET;
+        }
+
+        // SAFETY: getuid/getgid cannot fail.
+        let (uid, gid) = unsafe { (libc::getuid(), libc::getgid()) };
+
+        let (status_rx, status_tx) = status_pipe()?;
+
+        let confinement = Self {
+            unshare_flags,
+            uid_map: format!("0 {uid} 1").into_bytes(),
+            gid_map: format!("0 {gid} 1").into_bytes(),
+            landlock: build_landlock(workspace)?,
+            seccomp: build_seccomp()?,
+            status_tx,
+        };
+        let report = EnforcementReport {
+            status_rx,
+            isolate_network,
+        };
+        Ok((confinement, report))
+    }
+
+    /// Install the confinement as the command's `pre_exec` hook.
+    ///
+    /// The spawned child becomes a supervisor that waits for the confined
+    /// process (PID 1 of the new namespace) and exits with its status, so
+    /// killing the child tears down the whole namespace.
+    pub(super) fn install(self, cmd: &mut tokio::process::Command) {
+        // SAFETY: `apply` only issues async-signal-safe syscalls and never
+        // allocates; everything it needs was prepared in `prepare`.
+        unsafe {
+            cmd.pre_exec(move || self.apply());
+        }
+    }
+
+    fn apply(&self) -> io::Result<()> {
+        // SAFETY: raw syscalls on buffers and fds owned by `self`.
+        unsafe {
+            check(libc::unshare(self.unshare_flags))?;
+            // Older kernels have no setgroups file; the maps still work there.
+            let _ = write_file(c"/proc/self/setgroups", b"deny");
+            write_file(c"/proc/self/uid_map", &self.uid_map)?;
+            write_file(c"/proc/self/gid_map", &self.gid_map)?;
+
+            let pid = check(libc::fork())?;
+            if pid > 0 {
+                supervise(pid);
+            }
+
+            check(libc::prctl(libc::PR_SET_PDEATHSIG, libc::SIGKILL, 0, 0, 0))?;
+            check(libc::mount(
+                std::ptr::null(),
+                c"/".as_ptr(),
+                std::ptr::null(),
+                libc::MS_REC | libc::MS_PRIVATE,
+                std::ptr::null(),
+            ))?;
+            let _ = libc::mount(
+                c"proc".as_ptr(),
+                c"/proc".as_ptr(),
+                c"proc".as_ptr(),
+                libc::MS_NOSUID | libc::MS_NODEV | libc::MS_NOEXEC,
+                std::ptr::null(),
+            );
+
+            let mut enforced = ENFORCED_NAMESPACES;
+            if let Some(ruleset) = &self.landlock {
+                restrict_filesystem(ruleset)?;
+                enforced |= ENFORCED_LANDLOCK;
+            }
+            if let Some(program) = &self.seccomp {
+                seccompiler::apply_filter(program)
+                    .map_err(|_seccomp_error| io::Error::last_os_error())?;
+                enforced |= ENFORCED_SECCOMP;
+            }
+
+            let status_fd = self.status_tx.as_raw_fd();
+            check(libc::write(status_fd, (&raw const enforced).cast(), 1))?;
+        }
+        Ok(())
+    }
+}
+
+impl EnforcementReport {
+    /// What the child reported as enforced. Call once the command has been
+    /// spawned and the parent's copy of the write end has been dropped.
+    ///
+    /// The pipe is read on the blocking pool. A child that neither reports
+    /// nor exits by `deadline` is treated as having enforced nothing.
+    pub(super) async fn read(self, deadline: Instant) -> SandboxCapabilities {
+        let mut rx = std::fs::File::from(self.status_rx);
+        let read = tokio::task::spawn_blocking(move || {
+            let mut status = [0u8; 1];
+            match rx.read(&mut status) {
+                Ok(1) => status[0],
+                _ => 0,
+            }
+        });
+        let bits = match tokio::time::timeout_at(deadline, read).await {
+            Ok(Ok(bits)) => bits,
+            Ok(Err(_)) | Err(_) => 0,
+        };
+
+        let namespaces = bits & ENFORCED_NAMESPACES != 0;
+        SandboxCapabilities {
+            user_namespaces: namespaces,
+            network_namespaces: namespaces && self.isolate_network,
+            landlock: bits & ENFORCED_LANDLOCK != 0,
+            seccomp: bits & ENFORCED_SECCOMP != 0,
+            cgroups_v2: false,
+            internet_only: false,
+        }
+    }
+}
+
+fn build_landlock(workspace: &Path) -> Result<Option<LandlockRuleset>> {
+    let abi = ABI::from(landlock_abi());
+    if abi == ABI::Unsupported {
+        warn!("landlock not supported by this kernel — filesystem access is not restricted");
+        return Ok(None);
+    }
+
+    let ruleset = Ruleset::default()
+        .handle_access(AccessFs::from_all(abi))
+        .and_then(Ruleset::create)
+        .and_then(|r| r.add_rules(path_beneath_rules([workspace], AccessFs::from_all(abi))))
+        .and_then(|r| {
+            r.add_rules(path_beneath_rules(
+                READ_ONLY_PATHS,
+                AccessFs::from_read(abi),
+            ))
+        })
+        .and_then(|r| {
+            r.add_rules(path_beneath_rules(
+                WRITABLE_DEVICES,
+                AccessFs::from_all(abi),
+            ))
+        })
+        .context("failed to build landlock ruleset")?;
+
+    let fd = Option::<OwnedFd>::from(ruleset).context("landlock ruleset has no file descriptor")?;
+    Ok(Some(LandlockRuleset {
+        fd,
+        proc_access: AccessFs::from_read(abi).bits(),
+    }))
+}
+
+fn build_seccomp() -> Result<Option<BpfProgram>> {
+    let Some(arch) = seccomp_arch() else {
+        warn!(
+            arch = std::env::consts::ARCH,
+            "seccomp filtering not supported on this architecture"
+        );
+        return Ok(None);
+    };
+    if !seccomp_supported() {
+        warn!("seccomp not supported by this kernel — syscall filtering disabled");
+        return Ok(None);
+    }
+
+    let rules = DENIED_SYSCALLS
+        .iter()
+        .map(|&syscall| (syscall, Vec::new()))
+        .collect::<BTreeMap<_, _>>();
+    let filter = SeccompFilter::new(
+        rules,
+        SeccompAction::Allow,
+        SeccompAction::Errno(libc::EPERM.cast_unsigned()),
+        arch,
+    )
+    .context("failed to build seccomp filter")?;
+    let program: BpfProgram = filter
+        .try_into()
+        .context("failed to compile seccomp filter")?;
+    Ok(Some(program))
+}
+
+fn status_pipe() -> Result<(OwnedFd, OwnedFd)> {
+    let mut fds: [RawFd; 2] = [-1; 2];
+    // SAFETY: `fds` has room for the two descriptors pipe2 writes.
+    if unsafe { libc::pipe2(fds.as_mut_ptr(), libc::O_CLOEXEC) } != 0 {
+        return Err(io::Error::last_os_error()).context("failed to create sandbox status pipe");
+    }
+    // SAFETY: pipe2 succeeded, so both descriptors are open and owned by us.
+    Ok(unsafe { (OwnedFd::from_raw_fd(fds[0]), OwnedFd::from_raw_fd(fds[1])) })
+}
+
+/// Wait for the confined process and exit with its status. Runs in the
+/// forked supervisor, which must never return into `exec`.
+unsafe fn supervise(pid: libc::pid_t) -> ! {
+    // SAFETY: the supervisor only waits and exits. Closing every inherited
+    // descriptor lets the parent see EOF on the exec and status pipes.
+    unsafe {
+        if libc::syscall(libc::SYS_close_range, 0u32, u32::MAX, 0u32) != 0 {
+            let max_fd = libc::c_int::try_from(libc::sysconf(libc::_SC_OPEN_MAX)).unwrap_or(1024);
+            for fd in 0..max_fd {
+                libc::close(fd);
+            }
+        }
+
+        let mut status = 0;
+        loop {
+            if libc::waitpid(pid, &raw mut status, 0) == pid {
+                break;
+            }
+            if io::Error::last_os_error().raw_os_error() != Some(libc::EINTR) {
+                libc::_exit(1);
+            }
+        }
+
+        let code = if libc::WIFEXITED(status) {
+            libc::WEXITSTATUS(status)
+        } else if libc::WIFSIGNALED(status) {
+            128 + libc::WTERMSIG(status)
+        } else {
+            1
+        };
+        libc::_exit(code)
+    }
+}
+
+/// Add a rule for the freshly mounted `/proc`, then enforce the ruleset.
+unsafe fn restrict_filesystem(ruleset: &LandlockRuleset) -> io::Result<()> {
+    let ruleset_fd = ruleset.fd.as_raw_fd();
+    // SAFETY: raw syscalls with a stack-allocated rule and owned fds.
+    unsafe {
+        let proc_fd = libc::open(c"/proc".as_ptr(), libc::O_PATH | libc::O_CLOEXEC);
+        if proc_fd >= 0 {
+            let rule = LandlockPathBeneathAttr {
+                allowed_access: ruleset.proc_access,
+                parent_fd: proc_fd,
+            };
+            libc::syscall(
+                libc::SYS_landlock_add_rule,
+                ruleset_fd,
+                LANDLOCK_RULE_PATH_BENEATH,
+                &raw const rule,
+                0u32,
+            );
+            libc::close(proc_fd);
+        }
+
+        check(libc::prctl(libc::PR_SET_NO_NEW_PRIVS, 1, 0, 0, 0))?;
+        if libc::syscall(libc::SYS_landlock_restrict_self, ruleset_fd, 0u32) != 0 {
+            return Err(io::Error::last_os_error());
+        }
+    }
+    Ok(())
+}
+
+unsafe fn write_file(path: &std::ffi::CStr, contents: &[u8]) -> io::Result<()> {
+    // SAFETY: `path` is NUL-terminated and `contents` outlives the write.
+    unsafe {
+        let fd = check(libc::open(path.as_ptr(), libc::O_WRONLY | libc::O_CLOEXEC))?;
+        let written = libc::write(fd, contents.as_ptr().cast(), contents.len());
+        libc::close(fd);
+        if usize::try_from(written).ok() != Some(contents.len()) {
+            return Err(io::Error::last_os_error());
+        }
+    }
+    Ok(())
+}
+
+fn check<T: Default + PartialOrd>(ret: T) -> io::Result<T> {
+    if ret < T::default() {
+        Err(io::Error::last_os_error())
+    } else {
+        Ok(ret)
+    }
+}
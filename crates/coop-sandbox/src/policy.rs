@@ -49,6 +49,8 @@ pub struct ExecOutput {
     pub exit_code: i32,
     pub stdout: String,
     pub stderr: String,
+    /// Sandboxing layers that were actually applied to this command.
+    pub enforced: SandboxCapabilities,
 }
 
 /// Information about sandbox capabilities on this platform.
@@ -58,7 +60,9 @@ pub struct SandboxInfo {
     pub capabilities: SandboxCapabilities,
 }
 
-/// What sandboxing features are available.
+/// Sandboxing features. From [`probe`](crate::probe) these are what the
+/// host supports; on [`ExecOutput::enforced`] they are what a command ran
+/// under.
 #[allow(clippy::struct_excessive_bools)]
 #[derive(Debug, Clone, Default)]
 pub struct SandboxCapabilities {
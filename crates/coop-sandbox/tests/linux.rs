@@ -143,3 +143,64 @@ async fn exit_code_propagated() {
         .expect("exec should succeed");
     assert_eq!(output.exit_code, 42);
 }
+
+#[tokio::test]
+async fn reports_enforced_confinement() {
+    if !should_run() {
+        return;
+    }
+    let dir = tempfile::tempdir().expect("tempdir");
+    let policy = test_policy(dir.path());
+    let output = exec(&policy, "true", Duration::from_secs(10))
+        .await
+        .expect("exec should succeed");
+    let info = probe().expect("probe should succeed");
+
+    assert!(output.enforced.user_namespaces);
+    assert!(output.enforced.network_namespaces);
+    assert_eq!(output.enforced.landlock, info.capabilities.landlock);
+    assert_eq!(output.enforced.seccomp, info.capabilities.seccomp);
+}
+
+#[tokio::test]
+async fn landlock_blocks_writes_outside_workspace() {
+    if !should_run() {
+        return;
+    }
+    let dir = tempfile::tempdir().expect("tempdir");
+    let outside = tempfile::tempdir().expect("tempdir");
+    let target = outside.path().join("escape.txt");
+    let policy = test_policy(dir.path());
+
+    let output = exec(
+        &policy,
+        &format!("echo escaped > '{}'", target.display()),
+        Duration::from_secs(10),
+    )
+    .await
+    .expect("exec should succeed");
+    if !output.enforced.landlock {
+        return;
+    }
+
+    assert_ne!(output.exit_code, 0);
+    assert!(!target.exists());
+}
+
+#[tokio::test]
+async fn seccomp_denies_namespace_escape() {
+    if !should_run() {
+        return;
+    }
+    let dir = tempfile::tempdir().expect("tempdir");
+    let policy = test_policy(dir.path());
+
+    let output = exec(&policy, "unshare --user true", Duration::from_secs(10))
+        .await
+        .expect("exec should succeed");
+    if !output.enforced.seccomp {
+        return;
+    }
+
+    assert_ne!(output.exit_code, 0);
+}